@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ForgeFfiError, NetInterface, ABI_VERSION};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentCollector {
+    Netif,
+    System,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AgentConfig {
+    pub abi: u32,
+    pub interval_ms: u64,
+    #[serde(default = "AgentConfig::default_collect")]
+    pub collect: Vec<AgentCollector>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spool_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_spool_files: Option<u32>,
+}
+
+impl AgentConfig {
+    pub const MIN_INTERVAL_MS: u64 = 100;
+
+    #[must_use]
+    pub fn v1(interval_ms: u64) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            interval_ms,
+            collect: Self::default_collect(),
+            spool_dir: None,
+            max_spool_files: None,
+        }
+    }
+
+    fn default_collect() -> Vec<AgentCollector> {
+        vec![AgentCollector::Netif, AgentCollector::System]
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    pub os: String,
+    pub arch: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uptime_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AgentSnapshot {
+    pub abi: u32,
+    pub seq: u64,
+    pub timestamp_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netif: Option<Vec<NetInterface>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemSnapshot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ForgeFfiError>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+// debug-track-buffers 构建下尚未 tool_free 的出参缓冲区
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OutstandingBuffer {
+    // 分配序号，从 1 递增，便于比较两次快照
+    pub id: u64,
+    pub len: usize,
+    pub age_ms: u64,
+    // 分配时的调用栈；缺少调试符号时只有地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backtrace: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OutstandingBuffersResponse {
+    pub abi: u32,
+    pub count: usize,
+    pub total_bytes: usize,
+    // 按分配顺序
+    pub items: Vec<OutstandingBuffer>,
+}
@@ -0,0 +1,17 @@
+use std::ffi::c_void;
+
+// 事件类回调（netif watch、agent 等长期订阅）共用的 C 签名；生成头文件时命名为 tool_event_cb。
+// 下面的 /// 注释会随 cbindgen 进入头文件，是对宿主的线程约定，修改时要同步更新各语言绑定
+
+/// 事件回调：`json_ptr`/`json_len` 是一条 UTF-8 JSON 事件，只在回调期间有效，需要保留时自行复制。
+///
+/// 线程约定：
+/// - 回调在该订阅句柄专属的库内部线程上触发，从不在调用 start 的线程上同步触发；
+/// - 同一句柄的回调串行、按事件顺序触发；不同句柄的回调可能在各自线程上并发；
+/// - 在其它线程调用对应的 stop 返回后不会再有回调，此时才可以释放 user_data；
+/// - 允许在回调内部调用 stop，当前回调返回后不再触发，user_data 须在回调返回后再释放；
+/// - 回调不得让异常或 panic 穿过 C 边界；回调阻塞会推迟同一句柄的后续事件。
+///
+/// 句柄是不透明的非 0 u64，带代次：stop 之后旧句柄不会匹配到新的订阅，重复 stop 返回 NotFound。
+pub type ToolEventCb =
+    Option<unsafe extern "C" fn(json_ptr: *const u8, json_len: usize, user_data: *mut c_void)>;
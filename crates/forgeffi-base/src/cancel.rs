@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{ErrorCode, ForgeFfiError};
+
+// 宿主持有、可跨线程共享的取消标志；一旦取消不可复位
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// 与命令 deadline 一样按线程传递，平台实现无需逐层加参数；派生工作线程时需显式转交
+pub fn with_cancel_token<T>(token: Option<CancelToken>, f: impl FnOnce() -> T) -> T {
+    let prev = CURRENT.with(|c| c.replace(token));
+    let out = f();
+    CURRENT.with(|c| *c.borrow_mut() = prev);
+    out
+}
+
+#[must_use]
+pub fn current_cancel_token() -> Option<CancelToken> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+#[must_use]
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|c| c.borrow().as_ref().is_some_and(CancelToken::is_cancelled))
+}
+
+pub fn check_cancelled() -> Result<(), ForgeFfiError> {
+    if is_cancelled() {
+        return Err(ForgeFfiError::catalog(ErrorCode::Cancelled, "cancelled", &[]));
+    }
+    Ok(())
+}
+
+// 取消后子进程被杀、socket 被放弃，各处原本的错误映射五花八门，统一改写为 Cancelled
+#[must_use]
+pub fn cancelled_if_requested(e: ForgeFfiError) -> ForgeFfiError {
+    if e.code == ErrorCode::Cancelled || !is_cancelled() {
+        return e;
+    }
+    let message = format!("操作已取消: {}", e.message);
+    e.recode(ErrorCode::Cancelled, message)
+}
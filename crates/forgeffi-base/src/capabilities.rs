@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{NetIfBackendInfo, NetIfScope};
+
+// 聚合 FFI 库里编译进来的一个模块（net/fs/sys）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ModuleCapabilities {
+    pub module: String,
+    pub ffi_abi: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    // 模块内 op 的 "op" 标签（net 为 NetIfOp，sys 为 ServiceOp），只列出当前平台/后端下有实现的
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ops: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<NetIfBackendInfo>,
+    // op 标签 -> 当前平台可显式指定的 scope（仅 net）；不带 scope 调用时不受此限制
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub op_scopes: BTreeMap<String, Vec<NetIfScope>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CapabilitiesResponse {
+    pub abi: u32,
+    pub min_abi: u32,
+    pub max_abi: u32,
+    pub api_level: u32,
+    pub os: String,
+    pub arch: String,
+    pub modules: Vec<ModuleCapabilities>,
+}
+
+// 编译期嵌入的构建信息，用于确认现场二进制的来源；取不到的字段（无 git、无 rustc）省略
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub abi: u32,
+    pub api_level: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    // 设置了 SOURCE_DATE_EPOCH 时取该值，便于可复现构建
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_unix_secs: Option<u64>,
+    pub profile: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<String>,
+    // 聚合库的 cargo feature（net/fs/sys/full）
+    pub features: Vec<String>,
+    // forgeffi-sys 中编译进来的模块，同 enabled_modules()
+    pub modules: Vec<String>,
+}
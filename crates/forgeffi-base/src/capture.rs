@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ForgeFfiError, ABI_VERSION};
+
+// tcpdump 的默认截断长度，足够容纳 GRO 合并后的大包
+pub const CAPTURE_DEFAULT_SNAPLEN: u32 = 262_144;
+pub const CAPTURE_MAX_SECS: u32 = 86_400;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CaptureStartRequest {
+    pub abi: u32,
+    // netif list 中的网卡名
+    pub iface: String,
+    // pcap-filter 语法的子集，见 forgeffi_sys::capture::CaptureFilter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    // 文件达到该大小后停止（含 pcapng 头部）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snaplen: Option<u32>,
+    // 输出的 pcapng 文件；已存在时覆盖
+    pub path: String,
+}
+
+impl CaptureStartRequest {
+    #[must_use]
+    pub fn v1(iface: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            iface: iface.into(),
+            filter: None,
+            max_bytes: None,
+            max_secs: None,
+            snaplen: None,
+            path: path.into(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureStopReason {
+    // 宿主调用了 stop（或 tool_shutdown）
+    Stopped,
+    MaxBytes,
+    MaxSecs,
+    // 抓包源或写文件失败，见 error
+    Failed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CaptureSummary {
+    pub abi: u32,
+    pub iface: String,
+    pub path: String,
+    // pcapng 的链路类型（1 为以太网，101 为裸 IP）
+    pub linktype: u16,
+    // 写入文件的包数
+    pub packets: u64,
+    // 收到但未通过过滤的包数
+    pub filtered: u64,
+    pub file_bytes: u64,
+    pub duration_ms: u64,
+    pub reason: CaptureStopReason,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DnsRecordType {
+    #[default]
+    A,
+    Aaaa,
+    Txt,
+    Srv,
+    Mx,
+    Cname,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DnsResolveRequest {
+    pub abi: u32,
+    pub name: String,
+    #[serde(default)]
+    pub record_type: DnsRecordType,
+    // "8.8.8.8"、"8.8.8.8:53" 或 "[2001:4860:4860::8888]:53"；为空时使用系统配置的 DNS 服务器
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl DnsResolveRequest {
+    #[must_use]
+    pub fn v1(name: impl Into<String>, record_type: DnsRecordType) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            name: name.into(),
+            record_type,
+            server: None,
+            timeout_ms: None,
+        }
+    }
+}
+
+// CNAME/MX/SRV 的目标写入 target，MX 的 preference 与 SRV 的 priority 写入 priority
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DnsAnswer {
+    pub name: String,
+    pub record_type: DnsRecordType,
+    pub ttl: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DnsResolveResponse {
+    pub abi: u32,
+    pub name: String,
+    pub record_type: DnsRecordType,
+    pub server: String,
+    #[serde(default)]
+    pub answers: Vec<DnsAnswer>,
+}
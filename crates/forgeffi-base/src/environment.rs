@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InitSystem {
+    Systemd,
+    Openrc,
+    Sysvinit,
+    Launchd,
+    // Windows 服务控制管理器
+    Scm,
+    Other,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerKind {
+    Docker,
+    Podman,
+    Lxc,
+    Kubernetes,
+    Wsl,
+    Other,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelinuxMode {
+    Disabled,
+    Permissive,
+    Enforcing,
+}
+
+// 各网络配置组件是否在运行/生效；非 Linux 平台全部为 false
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetworkStackInfo {
+    #[serde(default)]
+    pub network_manager: bool,
+    #[serde(default)]
+    pub systemd_networkd: bool,
+    #[serde(default)]
+    pub systemd_resolved: bool,
+    // /etc/netplan 下存在配置文件
+    #[serde(default)]
+    pub netplan: bool,
+    // /etc/network/interfaces（ifupdown）存在且配置了非回环网卡
+    #[serde(default)]
+    pub ifupdown: bool,
+}
+
+// 只做探测，不修改系统；拿不到的字段为空
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentReport {
+    pub abi: u32,
+    pub os: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init_system: Option<InitSystem>,
+    pub network: NetworkStackInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerKind>,
+    // 虚拟化平台名，沿用 systemd-detect-virt 的命名（kvm、vmware、microsoft 等）；裸机为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virtualization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selinux: Option<SelinuxMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apparmor: Option<bool>,
+}
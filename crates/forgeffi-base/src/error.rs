@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::default_error_id;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[repr(i32)]
@@ -9,6 +12,15 @@ pub enum ErrorCode {
     Unsupported = 3,
     PermissionDenied = 4,
     SystemError = 5,
+    TargetGone = 6,
+    Timeout = 7,
+    Busy = 8,
+    PartialFailure = 9,
+    NotImplemented = 10,
+    Cancelled = 11,
+    BufferTooSmall = 12,
+    // 旧版本反序列化新增的错误码时落到 Unknown，而不是整条 JSON 解析失败
+    #[serde(other)]
     Unknown = 999,
 }
 
@@ -23,47 +35,148 @@ impl ErrorCode {
 pub struct ForgeFfiError {
     pub code: ErrorCode,
     pub message: String,
+    // 稳定的机器可读标识（见 messages.rs 的目录），宿主据此自行翻译
+    #[serde(default)]
+    pub error_id: String,
+    // 填充 message 模板的参数，与 error_id 一起供宿主翻译
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+    // 可选的结构化细节，平铺在错误 JSON 里，仅在有值时写出；message 仍保留完整的可读描述。
+    // 装箱是为了让 Result<_, ForgeFfiError> 保持小体积
+    #[serde(flatten)]
+    pub detail: Box<ErrorDetail>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorDetail {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_errno: Option<i32>,
+    // 失败的外部命令行，如 `ip ["link", "set", "eth0", "up"]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    // 给最终用户的处理建议
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    // 原样重试有可能成功（资源忙、超时等）；Busy/Timeout 默认为 true
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub retryable: bool,
 }
 
 impl ForgeFfiError {
     #[must_use]
-    pub fn invalid_argument<M: Into<String>>(message: M) -> Self {
+    pub fn new<M: Into<String>>(code: ErrorCode, message: M) -> Self {
         Self {
-            code: ErrorCode::InvalidArgument,
+            code,
             message: message.into(),
+            error_id: default_error_id(code).to_string(),
+            params: BTreeMap::new(),
+            detail: Box::new(ErrorDetail {
+                retryable: matches!(code, ErrorCode::Busy | ErrorCode::Timeout),
+                ..ErrorDetail::default()
+            }),
         }
     }
 
+    // 改写错误码与 message（error_id、retryable 随新错误码重置），保留 errno/command/stderr/hint
     #[must_use]
-    pub fn not_found<M: Into<String>>(message: M) -> Self {
+    pub fn recode<M: Into<String>>(self, code: ErrorCode, message: M) -> Self {
+        let retryable = matches!(code, ErrorCode::Busy | ErrorCode::Timeout);
         Self {
-            code: ErrorCode::NotFound,
-            message: message.into(),
+            detail: Box::new(ErrorDetail {
+                retryable,
+                ..*self.detail
+            }),
+            ..Self::new(code, message)
         }
     }
 
     #[must_use]
-    pub fn unsupported<M: Into<String>>(message: M) -> Self {
-        Self {
-            code: ErrorCode::Unsupported,
-            message: message.into(),
+    pub fn with_errno(mut self, errno: i32) -> Self {
+        self.detail.os_errno = Some(errno);
+        self
+    }
+
+    #[must_use]
+    pub fn with_command<S: Into<String>>(mut self, command: S) -> Self {
+        self.detail.command = Some(command.into());
+        self
+    }
+
+    // 空白的 stderr 不记录
+    #[must_use]
+    pub fn with_stderr(mut self, stderr: &str) -> Self {
+        let stderr = stderr.trim();
+        if !stderr.is_empty() {
+            self.detail.stderr = Some(stderr.to_string());
         }
+        self
+    }
+
+    #[must_use]
+    pub fn with_hint<S: Into<String>>(mut self, hint: S) -> Self {
+        self.detail.hint = Some(hint.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.detail.retryable = retryable;
+        self
+    }
+    #[must_use]
+    pub fn invalid_argument<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::InvalidArgument, message)
+    }
+
+    #[must_use]
+    pub fn not_found<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    #[must_use]
+    pub fn unsupported<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::Unsupported, message)
     }
 
     #[must_use]
     pub fn permission_denied<M: Into<String>>(message: M) -> Self {
-        Self {
-            code: ErrorCode::PermissionDenied,
-            message: message.into(),
-        }
+        Self::new(ErrorCode::PermissionDenied, message)
     }
 
     #[must_use]
     pub fn system_error<M: Into<String>>(message: M) -> Self {
-        Self {
-            code: ErrorCode::SystemError,
-            message: message.into(),
-        }
+        Self::new(ErrorCode::SystemError, message)
+    }
+
+    #[must_use]
+    pub fn target_gone<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::TargetGone, message)
+    }
+
+    #[must_use]
+    pub fn timeout<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::Timeout, message)
+    }
+
+    #[must_use]
+    pub fn busy<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::Busy, message)
+    }
+
+    #[must_use]
+    pub fn partial_failure<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::PartialFailure, message)
+    }
+
+    #[must_use]
+    pub fn not_implemented<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::NotImplemented, message)
     }
-}
 
+    #[must_use]
+    pub fn cancelled<M: Into<String>>(message: M) -> Self {
+        Self::new(ErrorCode::Cancelled, message)
+    }
+}
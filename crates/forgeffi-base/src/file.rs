@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+// FFI 以 u32 传递打开模式，数值即 C 侧常量
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u32)]
+pub enum FsOpenMode {
+    // 只读，文件必须存在
+    Read = 0,
+    // 只写，不存在则创建，存在则截断
+    Write = 1,
+    // 追加写，不存在则创建
+    Append = 2,
+    // 读写，文件必须存在，不截断
+    ReadWrite = 3,
+    // 只写，文件必须不存在
+    CreateNew = 4,
+}
+
+impl FsOpenMode {
+    #[must_use]
+    pub fn from_u32(v: u32) -> Option<Self> {
+        match v {
+            0 => Some(Self::Read),
+            1 => Some(Self::Write),
+            2 => Some(Self::Append),
+            3 => Some(Self::ReadWrite),
+            4 => Some(Self::CreateNew),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn can_read(self) -> bool {
+        matches!(self, Self::Read | Self::ReadWrite)
+    }
+
+    #[must_use]
+    pub fn can_write(self) -> bool {
+        !matches!(self, Self::Read)
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+// include/exclude 为 glob，匹配相对 path 的路径（分隔符统一为 /）；include 为空表示全部
+// expected 给出时响应中的 matched 表示摘要是否一致（不区分大小写）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HashRequest {
+    pub abi: u32,
+    pub path: String,
+    #[serde(default)]
+    pub algorithm: HashAlgorithm,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+}
+
+impl HashRequest {
+    #[must_use]
+    pub fn v1(path: impl Into<String>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            path: path.into(),
+            algorithm: HashAlgorithm::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            expected: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HashProgress {
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub files_done: u64,
+    pub files_total: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HashFileEntry {
+    pub path: String,
+    pub size: u64,
+    pub digest: String,
+}
+
+// 目录的 digest 是对清单 "<digest>  <path>\n"（按 path 排序）再做一次同算法摘要，
+// 与 `sha256sum` 输出格式一致，便于宿主侧复算
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HashResponse {
+    pub abi: u32,
+    pub algorithm: HashAlgorithm,
+    pub digest: String,
+    pub is_dir: bool,
+    pub total_bytes: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<HashFileEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched: Option<bool>,
+}
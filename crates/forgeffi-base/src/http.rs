@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::ABI_VERSION;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HttpProbeRequest {
+    pub abi: u32,
+    pub url: String,
+    // 默认 GET
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    // 默认 true；关闭后不校验证书链与主机名，仅用于自签名设备
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_tls: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_body_bytes: Option<u32>,
+}
+
+impl HttpProbeRequest {
+    #[must_use]
+    pub fn v1(url: impl Into<String>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            url: url.into(),
+            method: None,
+            headers: BTreeMap::new(),
+            timeout_ms: None,
+            verify_tls: None,
+            max_body_bytes: None,
+        }
+    }
+}
+
+// 4xx/5xx 也属于正常探测结果，通过 status 返回而不是错误码
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HttpProbeResponse {
+    pub abi: u32,
+    pub url: String,
+    pub status: u16,
+    pub status_text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    pub latency_us: u64,
+    pub body: String,
+    pub body_truncated: bool,
+}
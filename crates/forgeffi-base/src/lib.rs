@@ -1,8 +1,60 @@
 #![forbid(unsafe_code)]
 pub const ABI_VERSION: u32 = 1;
+// 仍接受的最老请求 abi；[MIN_SUPPORTED_ABI, ABI_VERSION) 的请求在解码时逐版本升级
+pub const MIN_SUPPORTED_ABI: u32 = 1;
 
+mod agent;
+mod buffers;
+mod callback;
+mod cancel;
+mod capabilities;
+mod capture;
+mod dns;
+mod environment;
 mod error;
+mod file;
+mod hash;
+mod http;
+mod lldp;
+mod messages;
+mod metrics;
+mod mounts;
 mod netif;
+mod persist;
+mod ping;
+mod privileges;
+mod probe;
+mod runtime;
+mod service;
+mod sockets;
+mod time;
+mod trace;
+mod wifi;
 
+pub use agent::*;
+pub use buffers::*;
+pub use callback::*;
+pub use cancel::*;
+pub use capabilities::*;
+pub use capture::*;
+pub use dns::*;
+pub use environment::*;
 pub use error::*;
+pub use file::*;
+pub use hash::*;
+pub use http::*;
+pub use lldp::*;
+pub use messages::*;
+pub use metrics::*;
+pub use mounts::*;
 pub use netif::*;
+pub use persist::*;
+pub use ping::*;
+pub use privileges::*;
+pub use probe::*;
+pub use runtime::*;
+pub use service::*;
+pub use sockets::*;
+pub use time::*;
+pub use trace::*;
+pub use wifi::*;
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+// LLDP 默认每 30 秒发送一次，CDP 为 60 秒；被动监听至少要覆盖一个发送周期
+pub const LLDP_DEFAULT_LISTEN_SECS: u32 = 31;
+pub const LLDP_MAX_LISTEN_SECS: u32 = 180;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LldpRequest {
+    pub abi: u32,
+    // 为空时查询所有已启用的物理网卡
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+    // 没有 lldpd/lldpad 而改为被动抓包时的最长等待时间
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen_secs: Option<u32>,
+}
+
+impl LldpRequest {
+    #[must_use]
+    pub fn v1() -> Self {
+        Self {
+            abi: ABI_VERSION,
+            iface: None,
+            listen_secs: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LldpProtocol {
+    Lldp,
+    Cdp,
+}
+
+// 邻居信息的来源：lldpd（lldpctl）、lldpad（lldptool）或本库直接抓包解析
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LldpSource {
+    Lldpd,
+    Lldpad,
+    Capture,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LldpNeighbor {
+    pub protocol: LldpProtocol,
+    // MAC 统一为小写冒号分隔，其余按原样
+    pub chassis_id: String,
+    // LLDP 的 ID 子类型：mac、ip、ifname、local 等；CDP 没有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chassis_id_type: Option<String>,
+    // 对端交换机的端口，如 Gi1/0/24
+    pub port_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_id_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mgmt_addresses: Vec<String>,
+    // 端口的 native/PVID VLAN（LLDP 802.1 Port VLAN ID 或 CDP Native VLAN）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vlan_id: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LldpInterface {
+    pub name: String,
+    pub neighbors: Vec<LldpNeighbor>,
+}
+
+// items 只包含发现了邻居的网卡，按网卡名排序
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LldpResponse {
+    pub abi: u32,
+    pub source: LldpSource,
+    pub items: Vec<LldpInterface>,
+}
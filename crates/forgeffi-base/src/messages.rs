@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::{ErrorCode, ForgeFfiError};
+
+// 错误信息目录：error_id -> (中文模板, 英文模板)，模板里的 {name} 由 params 填充。
+// error_id 是对外契约，只增不改；宿主可以按 error_id + params 自行翻译。
+// 目录外的错误 message 仍是中文，error_id 为按错误码的通用 id
+const CATALOG: &[(&str, &str, &str)] = &[
+    (
+        "abi_unsupported",
+        "abi 版本不受支持: supported={min}..={max} got={got}",
+        "unsupported abi version: supported={min}..={max} got={got}",
+    ),
+    (
+        "abi_mismatch",
+        "abi 版本不受支持: supported={min}..={max} expected={expected} got={got}",
+        "unsupported abi version: supported={min}..={max} expected={expected} got={got}",
+    ),
+    ("request_json_invalid", "解析请求 JSON 失败: {detail}", "failed to parse request JSON: {detail}"),
+    ("init_config_invalid", "解析 init 配置失败: {detail}", "failed to parse init config: {detail}"),
+    ("cancelled", "操作已取消", "operation cancelled"),
+    ("op_cancelled", "请求已取消，op 未执行", "request cancelled, op not executed"),
+    (
+        "op_deadline_exceeded",
+        "已超过请求 deadline_ms={deadline_ms}，op 未执行",
+        "request deadline_ms={deadline_ms} exceeded, op not executed",
+    ),
+    ("iface_not_found_index", "未找到网卡 if_index={if_index}", "interface not found: if_index={if_index}"),
+    ("iface_not_found_name", "未找到网卡 name={name}", "interface not found: name={name}"),
+    ("iface_gone", "网卡在执行过程中消失: name={name}", "interface disappeared during apply: name={name}"),
+    (
+        "iface_rate_limited",
+        "网卡 {name} 超过每秒 {rate} 个 op 的限制，op 未执行",
+        "interface {name} exceeded {rate} ops per second, op not executed",
+    ),
+    (
+        "apply_concurrency_limited",
+        "进行中的 apply 请求已达上限 {max}（当前 {active}），请稍后重试",
+        "too many concurrent apply requests (limit {max}, active {active}), retry later",
+    ),
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MessageLocale {
+    Zh,
+    En,
+}
+
+impl MessageLocale {
+    // 缺省与 zh* 为中文，其它语言一律用英文作为中性回落
+    #[must_use]
+    pub fn from_tag(tag: Option<&str>) -> Self {
+        match tag.map(str::trim) {
+            None | Some("") => Self::Zh,
+            Some(t) if t.to_ascii_lowercase().starts_with("zh") => Self::Zh,
+            Some(_) => Self::En,
+        }
+    }
+}
+
+fn template(id: &str, locale: MessageLocale) -> Option<&'static str> {
+    CATALOG.iter().find(|(i, _, _)| *i == id).map(|(_, zh, en)| match locale {
+        MessageLocale::Zh => *zh,
+        MessageLocale::En => *en,
+    })
+}
+
+fn render(template: &str, params: &BTreeMap<String, String>) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |s, (k, v)| s.replace(&format!("{{{k}}}"), v))
+}
+
+// 未登记具体 error_id 的错误按错误码给出通用 id
+#[must_use]
+pub const fn default_error_id(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::Ok => "ok",
+        ErrorCode::InvalidArgument => "invalid_argument",
+        ErrorCode::NotFound => "not_found",
+        ErrorCode::Unsupported => "unsupported",
+        ErrorCode::PermissionDenied => "permission_denied",
+        ErrorCode::SystemError => "system_error",
+        ErrorCode::TargetGone => "target_gone",
+        ErrorCode::Timeout => "timeout",
+        ErrorCode::Busy => "busy",
+        ErrorCode::PartialFailure => "partial_failure",
+        ErrorCode::NotImplemented => "not_implemented",
+        ErrorCode::Cancelled => "cancelled",
+        ErrorCode::BufferTooSmall => "buffer_too_small",
+        ErrorCode::Unknown => "unknown",
+    }
+}
+
+impl ForgeFfiError {
+    // 按目录构造：message 用 init 配置的 locale 渲染，error_id/params 原样带出
+    #[must_use]
+    pub fn catalog(code: ErrorCode, error_id: &'static str, params: &[(&str, &dyn Display)]) -> Self {
+        let params: BTreeMap<String, String> = params.iter().map(|(k, v)| ((*k).to_string(), v.to_string())).collect();
+        let locale = MessageLocale::from_tag(crate::runtime_config().locale.as_deref());
+        debug_assert!(template(error_id, locale).is_some(), "error_id 未登记: {error_id}");
+        let message = template(error_id, locale).map_or_else(|| error_id.to_string(), |t| render(t, &params));
+        Self {
+            error_id: error_id.to_string(),
+            params,
+            ..Self::new(code, message)
+        }
+    }
+
+    // 旧版本写出的 JSON 没有 error_id，按错误码回落
+    #[must_use]
+    pub fn id(&self) -> &str {
+        if self.error_id.is_empty() {
+            default_error_id(self.code)
+        } else {
+            &self.error_id
+        }
+    }
+}
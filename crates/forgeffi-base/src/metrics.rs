@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::ErrorCode;
+
+// 延迟直方图各桶的上界（毫秒），另有一个超出最大上界的桶
+pub const METRICS_LATENCY_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+// 进程启动（或动态库加载）以来的累计值，只增不减
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MetricsResponse {
+    pub abi: u32,
+    pub latency_buckets_ms: Vec<u64>,
+    pub operations: Vec<OperationMetrics>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<CommandMetrics>,
+}
+
+// 按 span 名与 op 类型聚合，如 ("netif.op", "set_mtu")；整个 apply 的 span 没有 op
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OperationMetrics {
+    pub span: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub op: Option<String>,
+    pub total: u64,
+    pub ok: u64,
+    pub failed: u64,
+    // skip_if_applied 或去抖跳过的，不计入 ok
+    pub skipped: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<ErrorCode, u64>,
+    // 与 latency_buckets_ms 对应的非累计计数，最后一个是超出最大上界的
+    pub latency_counts: Vec<u64>,
+    pub latency_sum_us: u64,
+    pub latency_max_us: u64,
+}
+
+// 只统计 span 期间执行的外部命令
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CommandMetrics {
+    pub program: String,
+    pub total: u64,
+    // 非零退出、未能启动、超时或被取消
+    pub failed: u64,
+    pub duration_sum_us: u64,
+}
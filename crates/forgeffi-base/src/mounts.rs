@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MountListRequest {
+    pub abi: u32,
+    // 默认只返回有容量的文件系统（跳过 proc/sysfs/cgroup 等伪文件系统与空光驱）
+    #[serde(default)]
+    pub include_virtual: bool,
+}
+
+impl MountListRequest {
+    #[must_use]
+    pub fn v1() -> Self {
+        Self {
+            abi: ABI_VERSION,
+            include_virtual: false,
+        }
+    }
+}
+
+// available_bytes 为非特权用户可用空间，可能小于 free_bytes（ext4 预留块、配额）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MountPoint {
+    pub mount_point: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fs_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub free_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+    pub read_only: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MountListResponse {
+    pub abi: u32,
+    pub items: Vec<MountPoint>,
+}
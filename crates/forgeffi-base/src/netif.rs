@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{ErrorCode, ForgeFfiError, ABI_VERSION};
+use crate::{ErrorCode, ForgeFfiError, ABI_VERSION, MIN_SUPPORTED_ABI};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -10,6 +10,8 @@ pub enum IfaceKind {
     Virtual,
     Loopback,
     Tunnel,
+    Vlan,
+    Bridge,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -60,6 +62,184 @@ pub enum IpOrigin {
     Dhcp,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpFamily {
+    Ipv4,
+    Ipv6,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteOrigin {
+    Unknown,
+    Static,
+    Dhcp,
+    RouterAdvertisement,
+    Kernel,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DefaultRoute {
+    pub family: IpFamily,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<RouteOrigin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_src: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ipv6Mode {
+    Slaac,
+    Dhcp6,
+    Static,
+}
+
+// 与 Linux bonding 驱动的模式一一对应；Windows NIC Teaming 只能映射其中一部分，broadcast 不支持
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BondMode {
+    BalanceRr,
+    ActiveBackup,
+    BalanceXor,
+    Broadcast,
+    // 802.3ad
+    Lacp,
+    BalanceTlb,
+    BalanceAlb,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TunnelKind {
+    Wireguard,
+    Gre,
+    Ipip,
+    // IPv6-in-IPv4
+    Sit,
+    // 识别为隧道但不属于以上几类（ip6gre、vti 等），只用于 list 输出
+    Other,
+}
+
+// 创建/更新 WireGuard peer 的参数；密钥均为 wg 使用的 base64 编码（32 字节）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WireguardPeer {
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preshared_key: Option<String>,
+    // host:port，IPv6 写成 [addr]:port
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persistent_keepalive_secs: Option<u16>,
+}
+
+// list 输出中的 peer 状态；不含 preshared_key
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WireguardPeerInfo {
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+    // 最近一次握手的 Unix 时间（秒）；从未握手时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_handshake_unix: Option<u64>,
+    #[serde(default)]
+    pub rx_bytes: u64,
+    #[serde(default)]
+    pub tx_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persistent_keepalive_secs: Option<u16>,
+}
+
+// 不含私钥
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WireguardInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fwmark: Option<u32>,
+    #[serde(default)]
+    pub peers: Vec<WireguardPeerInfo>,
+}
+
+// kind 为 tunnel 的网卡附带的隧道信息；local/remote 为 any 时留空
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TunnelInfo {
+    pub kind: TunnelKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+    // 读取 wg 状态需要 wg 工具与 CAP_NET_ADMIN，拿不到时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wireguard: Option<WireguardInfo>,
+}
+
+// AddRoute/DelRoute 的参数；出口网卡为请求的 target
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StaticRoute {
+    // ip/prefix_len，主机路由为 /32 或 /128
+    pub dest: String,
+    // 为空时为直连路由
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    // 路由表编号，省略时为 main（254）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<u32>,
+    // 首选源地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src: Option<String>,
+}
+
+// 策略路由规则（ip rule）：命中条件的流量查 table 指定的路由表。
+// 地址族取自 from/to，两者都省略时由 ipv6 决定；删除时按全部字段匹配
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RoutingRule {
+    pub table: u32,
+    // 越小越先匹配；省略时由内核分配
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    // 源地址 ip/prefix_len
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fwmark: Option<u32>,
+    // 入口网卡名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iif: Option<String>,
+    // 出口网卡名（本机发出且绑定了该网卡的流量）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oif: Option<String>,
+    #[serde(default)]
+    pub ipv6: bool,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpDadState {
+    Tentative,
+    Duplicate,
+    Deprecated,
+    Preferred,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct IpAddrFlags(pub u32);
 
@@ -79,6 +259,25 @@ pub struct IpAddrEntry {
     pub origin: Option<IpOrigin>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub flags: Option<IpAddrFlags>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dad_state: Option<IpDadState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_lft_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_lft_secs: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Duplex {
+    Half,
+    Full,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct LinkSpeedMode {
+    pub speed_mbps: u32,
+    pub duplex: Duplex,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -88,6 +287,20 @@ pub struct NetIfCapabilities {
     pub can_add_del_ip: bool,
     pub can_set_dhcp: bool,
     pub can_set_dns: bool,
+    #[serde(default)]
+    pub can_set_ipv6: bool,
+    #[serde(default)]
+    pub can_rename: bool,
+    #[serde(default)]
+    pub can_set_description: bool,
+    #[serde(default)]
+    pub can_set_mac: bool,
+    // AddRoute/DelRoute 与策略路由规则；目前只有 Linux 支持
+    #[serde(default)]
+    pub can_manage_routes: bool,
+    // 可用于 SetLinkSpeed 的固定速率；为空表示无法获取或不支持强制速率
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_speeds: Vec<LinkSpeedMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
 }
@@ -111,10 +324,24 @@ pub struct NetInterface {
     pub mtu: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub speed_bps: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vlan_id: Option<u16>,
     #[serde(default)]
     pub ipv4: Vec<IpAddrEntry>,
     #[serde(default)]
     pub ipv6: Vec<IpAddrEntry>,
+    #[serde(default)]
+    pub default_routes: Vec<DefaultRoute>,
+    // 取自该网卡 metric 最小的默认路由，便于直接渲染设置页
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv4_gateway: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_gateway: Option<String>,
+    // 该网卡上配置的 DNS 服务器（按系统优先级）；全局 DNS 不计入
+    #[serde(default)]
+    pub dns_servers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tunnel: Option<TunnelInfo>,
     pub capabilities: NetIfCapabilities,
 }
 
@@ -131,7 +358,16 @@ pub struct IfaceSelector {
 pub enum NetIfOp {
     SetAdminState { up: bool },
     SetMtu { mtu: u32 },
-    AddIp { ip: String, prefix_len: u8 },
+    AddIp {
+        ip: String,
+        prefix_len: u8,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_dad_ms: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        valid_lft_secs: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        preferred_lft_secs: Option<u32>,
+    },
     DelIp { ip: String, prefix_len: u8 },
     SetIpv4Dhcp { enable: bool },
     SetIpv4Static {
@@ -140,27 +376,322 @@ pub enum NetIfOp {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         gateway: Option<String>,
     },
+    SetIpv6Mode {
+        mode: Ipv6Mode,
+    },
+    SetIpv6Static {
+        ip: String,
+        prefix_len: u8,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gateway: Option<String>,
+    },
+    CreateVlan {
+        parent: String,
+        vlan_id: u16,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+    DeleteVlan,
+    CreateBridge {
+        name: String,
+    },
+    DeleteBridge,
+    AddBridgeMember {
+        bridge: String,
+        member: String,
+    },
+    RemoveBridgeMember,
+    // 测试用虚拟网卡：Linux dummy、macOS feth（name 须为 feth<N>）、Windows KM-TEST 环回网卡（需要 devcon）
+    CreateDummy {
+        name: String,
+    },
+    // 删除 target 指向的虚拟网卡；物理网卡会被系统拒绝
+    DeleteInterface,
+    // 链路聚合：Linux bonding 驱动，Windows NIC Teaming（LBFO，仅 Windows Server，members 不能为空）。
+    // 成员加入前会被 down，由 bond 接管其状态
+    CreateBond {
+        name: String,
+        mode: BondMode,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        members: Vec<String>,
+    },
+    DeleteBond,
+    AddBondMember {
+        bond: String,
+        member: String,
+    },
+    // target 为成员网卡
+    RemoveBondMember,
+    // 隧道目前只支持 Linux：WireGuard 需要 wireguard 内核模块与 wg 工具，创建后为 down
+    CreateWireguard {
+        name: String,
+        private_key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        listen_port: Option<u16>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        peers: Vec<WireguardPeer>,
+    },
+    // 按 public_key 新增或覆盖 target 上的 peer；allowed_ips 整体替换
+    SetWireguardPeer {
+        peer: WireguardPeer,
+    },
+    RemoveWireguardPeer {
+        public_key: String,
+    },
+    // kind 只能为 gre、ipip 或 sit，remote/local 为 IPv4
+    CreateIpTunnel {
+        name: String,
+        kind: TunnelKind,
+        remote: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        local: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl: Option<u8>,
+    },
+    // 删除 target 指向的 WireGuard 或 IP 隧道网卡
+    DeleteTunnel,
+    // 路由与策略路由目前只支持 Linux（iproute2）；多 WAN 时每个出口一张路由表，再用规则按源地址/fwmark 选表
+    AddRoute {
+        route: StaticRoute,
+    },
+    // 按 dest、table 匹配，给出 gateway/metric/src 时一并匹配
+    DelRoute {
+        route: StaticRoute,
+    },
+    AddRoutingRule {
+        rule: RoutingRule,
+    },
+    DelRoutingRule {
+        rule: RoutingRule,
+    },
+    SetDnsSuffix {
+        suffix: String,
+    },
+    SwitchNetworkLocation {
+        name: String,
+    },
+    SetServiceOrder {
+        services: Vec<String>,
+    },
+    SetDnsRegistration {
+        register: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        use_suffix: Option<bool>,
+    },
+    // Linux 上改名需要先 down，执行后会恢复原 admin 状态
+    SetName {
+        new_name: String,
+    },
+    // Linux 写入 ifalias；空字符串表示清除
+    SetDescription {
+        text: String,
+    },
+    // 接受 aa:bb:cc:dd:ee:ff、aa-bb-cc-dd-ee-ff 或 12 位十六进制
+    SetMac {
+        mac: String,
+    },
+    // speed_mbps 为空表示恢复自动协商；duplex 缺省为全双工
+    SetLinkSpeed {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        speed_mbps: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duplex: Option<Duplex>,
+    },
+    // 为空的方向取消限速。Linux 用 tc（出方向 HTB，入方向 ingress qdisc + police）；
+    // Windows 用按源地址匹配的 QoS 策略，只能限制出方向
+    SetBandwidthLimit {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        egress_mbps: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ingress_mbps: Option<u32>,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NetIfOpResult {
     pub i: usize,
     pub ok: bool,
+    // skip_if_applied 时系统已处于目标状态，未下发任何命令
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<NetIfStepResult>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfStepResult {
+    pub step: String,
+    pub ok: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<ForgeFfiError>,
 }
 
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetIfSortKey {
+    #[default]
+    IfIndex,
+    Name,
+}
+
+impl NetIfSortKey {
+    #[must_use]
+    pub const fn from_u32(v: u32) -> Option<Self> {
+        match v {
+            0 => Some(Self::IfIndex),
+            1 => Some(Self::Name),
+            _ => None,
+        }
+    }
+}
+
+// 每个地址族当前实际生效的默认路由；source_address 为系统访问公网时选用的本机源地址
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActiveDefaultRoute {
+    pub family: IpFamily,
+    pub if_index: u32,
+    pub if_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<RouteOrigin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_address: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DefaultRouteResponse {
+    pub abi: u32,
+    pub routes: Vec<ActiveDefaultRoute>,
+}
+
+// Linux 的一个 qdisc，或 Windows 的一条 QoS 策略
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QosEntry {
+    // qdisc 类型（htb、ingress、fq_codel 等）；Windows 为 qos_policy
+    pub kind: String,
+    // qdisc 句柄（如 "1:"）或策略名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    // 非 root qdisc 的父句柄
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    // Windows 策略的源地址匹配条件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_src: Option<String>,
+    // 能读出时的限速：HTB 为 SetBandwidthLimit 建的分类速率，ingress 为 police 速率，Windows 为 ThrottleRateAction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_bps: Option<u64>,
+}
+
+// egress_mbps/ingress_mbps 只反映 SetBandwidthLimit 建立的整形，其它工具的配置只出现在 entries 中
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfQos {
+    pub if_index: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_mbps: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress_mbps: Option<u32>,
+    #[serde(default)]
+    pub entries: Vec<QosEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfQosResponse {
+    pub abi: u32,
+    pub items: Vec<NetIfQos>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NetIfListResponse {
     pub abi: u32,
     pub items: Vec<NetInterface>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_location: Option<NetworkLocation>,
+}
+
+// next_cursor 为空表示已到末尾；cursor 对调用方不透明，原样回传即可
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfListPage {
+    pub abi: u32,
+    pub items: Vec<NetInterface>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+// 各条件之间为“与”；kinds 为空表示不限类型，family 表示至少带有一个该族地址
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfListFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<IfaceKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub names_glob: Option<String>,
+    #[serde(default)]
+    pub only_up: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<IpFamily>,
+}
+
+// fields 为 NetInterface 的 JSON 字段名，为空表示全部；if_index 与 name 总是输出
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfListRequest {
+    pub abi: u32,
+    #[serde(default)]
+    pub filter: NetIfListFilter,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    #[serde(default)]
+    pub sort: NetIfSortKey,
+}
+
+impl NetIfListRequest {
+    #[must_use]
+    pub fn v1(filter: NetIfListFilter) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            filter,
+            fields: Vec::new(),
+            sort: NetIfSortKey::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetworkLocation {
+    pub current: String,
+    #[serde(default)]
+    pub available: Vec<String>,
+    #[serde(default)]
+    pub service_order: Vec<NetworkService>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetworkService {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    pub enabled: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NetIfApplyRequest {
     pub abi: u32,
     pub target: IfaceSelector,
-    pub ops: Vec<NetIfOp>,
+    pub ops: Vec<NetIfOpSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+    // 执行前先比对当前状态，已满足的 op 直接跳过，避免重复下发导致链路抖动
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_if_applied: bool,
+    // commit-confirm：执行完成后这么多秒内未用响应中的 confirm_token 确认，则自动回滚到执行前的状态。
+    // 用于远程改 IP/网关，改错导致失联时无需人工介入
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_timeout_secs: Option<u32>,
 }
 
 impl NetIfApplyRequest {
@@ -169,7 +700,54 @@ impl NetIfApplyRequest {
         Self {
             abi: ABI_VERSION,
             target,
-            ops,
+            ops: ops.into_iter().map(NetIfOpSpec::from).collect(),
+            deadline_ms: None,
+            skip_if_applied: false,
+            confirm_timeout_secs: None,
+        }
+    }
+}
+
+// 修改的生效范围：runtime 只改当前运行状态（重启后丢失），persistent 只写配置（下次激活或重启后生效），both 两者都做
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetIfScope {
+    Runtime,
+    Persistent,
+    Both,
+}
+
+impl NetIfScope {
+    pub const ALL: [Self; 3] = [Self::Runtime, Self::Persistent, Self::Both];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Runtime => "runtime",
+            Self::Persistent => "persistent",
+            Self::Both => "both",
+        }
+    }
+}
+
+// JSON 中 timeout_ms/scope 与 op 字段平铺在同一个对象里：{"op":"set_mtu","mtu":1400,"timeout_ms":500}
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfOpSpec {
+    #[serde(flatten)]
+    pub op: NetIfOp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    // 不填时沿用各平台的默认行为；平台不支持所要求的范围时该 op 返回 Unsupported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<NetIfScope>,
+}
+
+impl From<NetIfOp> for NetIfOpSpec {
+    fn from(op: NetIfOp) -> Self {
+        Self {
+            op,
+            timeout_ms: None,
+            scope: None,
         }
     }
 }
@@ -177,8 +755,20 @@ impl NetIfApplyRequest {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NetIfApplyResponse {
     pub abi: u32,
+    // 本库接受的请求 abi 范围；旧版本库不输出时为 0
+    #[serde(default)]
+    pub min_abi: u32,
+    #[serde(default)]
+    pub max_abi: u32,
     pub ok: bool,
     pub results: Vec<NetIfOpResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_known: Option<NetInterface>,
+    // 仅 commit-confirm 模式且有 op 生效时给出；confirm_deadline_ms 为 Unix 毫秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_deadline_ms: Option<u64>,
 }
 
 impl NetIfApplyResponse {
@@ -186,12 +776,19 @@ impl NetIfApplyResponse {
     pub fn error(abi: u32, e: ForgeFfiError) -> Self {
         Self {
             abi,
+            min_abi: MIN_SUPPORTED_ABI,
+            max_abi: ABI_VERSION,
             ok: false,
             results: vec![NetIfOpResult {
                 i: 0,
                 ok: false,
+                skipped: false,
                 error: Some(e),
+                steps: Vec::new(),
             }],
+            last_known: None,
+            confirm_token: None,
+            confirm_deadline_ms: None,
         }
     }
 
@@ -199,10 +796,363 @@ impl NetIfApplyResponse {
     pub fn invalid_abi(expected: u32, got: u32) -> Self {
         Self::error(
             expected,
-            ForgeFfiError {
-                code: ErrorCode::InvalidArgument,
-                message: format!("abi 版本不匹配: expected={expected} got={got}"),
-            },
+            ForgeFfiError::catalog(
+                ErrorCode::InvalidArgument,
+                "abi_mismatch",
+                &[
+                    ("min", &MIN_SUPPORTED_ABI),
+                    ("max", &ABI_VERSION),
+                    ("expected", &expected),
+                    ("got", &got),
+                ],
+            ),
         )
     }
 }
+
+// 同一组 ops 依次作用于每个 target，target 之间并发执行；deadline_ms 对整个批次生效
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfMultiApplyRequest {
+    pub abi: u32,
+    pub targets: Vec<IfaceSelector>,
+    pub ops: Vec<NetIfOpSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_if_applied: bool,
+}
+
+impl NetIfMultiApplyRequest {
+    #[must_use]
+    pub fn v1(targets: Vec<IfaceSelector>, ops: Vec<NetIfOp>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            targets,
+            ops: ops.into_iter().map(NetIfOpSpec::from).collect(),
+            max_parallel: None,
+            deadline_ms: None,
+            skip_if_applied: false,
+        }
+    }
+}
+
+// error 表示该 target 整体未执行（如找不到网卡），此时 results 为空
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfTargetResult {
+    pub i: usize,
+    pub target: IfaceSelector,
+    pub ok: bool,
+    #[serde(default)]
+    pub results: Vec<NetIfOpResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_known: Option<NetInterface>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfMultiApplyResponse {
+    pub abi: u32,
+    pub ok: bool,
+    pub targets: Vec<NetIfTargetResult>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfDesiredAddr {
+    pub ip: String,
+    pub prefix_len: u8,
+}
+
+// 为 None 的字段不受管理，保持现状；addresses 给出时其余非链路本地、非 DHCP 下发的地址会被删除
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfDesiredState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Vec<NetIfDesiredAddr>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_state: Option<AdminState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dhcp: Option<bool>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfReconcileRequest {
+    pub abi: u32,
+    pub target: IfaceSelector,
+    pub desired: NetIfDesiredState,
+    // 只计算 diff，不执行
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+}
+
+impl NetIfReconcileRequest {
+    #[must_use]
+    pub fn v1(target: IfaceSelector, desired: NetIfDesiredState) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            target,
+            desired,
+            dry_run: false,
+            deadline_ms: None,
+        }
+    }
+}
+
+// diff 为计算出的最小 op 序列；results 与 diff 按下标一一对应，dry_run 时为空
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfReconcileResponse {
+    pub abi: u32,
+    pub ok: bool,
+    pub diff: Vec<NetIfOp>,
+    #[serde(default)]
+    pub results: Vec<NetIfOpResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_known: Option<NetInterface>,
+}
+
+// targets 为空表示全部网卡
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfSnapshotRequest {
+    pub abi: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<IfaceSelector>,
+}
+
+impl NetIfSnapshotRequest {
+    #[must_use]
+    pub fn v1(targets: Vec<IfaceSelector>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            targets,
+        }
+    }
+}
+
+// 可恢复的配置文档；abi 与生成时的 ABI_VERSION 一致，恢复时校验
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfSnapshot {
+    pub abi: u32,
+    pub taken_at_ms: u64,
+    pub interfaces: Vec<NetIfSnapshotEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfSnapshotEntry {
+    pub name: String,
+    pub if_index: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    pub state: NetIfDesiredState,
+}
+
+// commit-confirm 的待确认记录，落盘以便进程崩溃后下次 init 仍能回滚。
+// 回滚时先执行 rollback_ops（快照不含网关，改静态地址前的网关靠它恢复），再按 snapshot restore
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfPendingConfirm {
+    pub token: String,
+    pub target: String,
+    pub deadline_ms: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollback_ops: Vec<NetIfOp>,
+    pub snapshot: NetIfSnapshot,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfRestoreRequest {
+    pub abi: u32,
+    pub snapshot: NetIfSnapshot,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+// 按 name 定位网卡（if_index 重启后可能变化）；找不到的条目 error 为 NotFound
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfRestoreItem {
+    pub name: String,
+    pub ok: bool,
+    #[serde(default)]
+    pub diff: Vec<NetIfOp>,
+    #[serde(default)]
+    pub results: Vec<NetIfOpResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfRestoreResponse {
+    pub abi: u32,
+    pub ok: bool,
+    pub items: Vec<NetIfRestoreItem>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetIfBackend {
+    Iproute2,
+    Nmcli,
+    // 通过 /etc/netplan 持久化，由 netplan 渲染给 networkd 或 NetworkManager
+    Netplan,
+    Networkd,
+    Powershell,
+    Netsh,
+    Iphlpapi,
+    Ifconfig,
+    Networksetup,
+    // 内存中的假网卡表，不触碰系统；所有平台可用
+    Mock,
+}
+
+impl NetIfBackend {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Iproute2 => "iproute2",
+            Self::Nmcli => "nmcli",
+            Self::Netplan => "netplan",
+            Self::Networkd => "networkd",
+            Self::Powershell => "powershell",
+            Self::Netsh => "netsh",
+            Self::Iphlpapi => "iphlpapi",
+            Self::Ifconfig => "ifconfig",
+            Self::Networksetup => "networksetup",
+            Self::Mock => "mock",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        [
+            Self::Iproute2,
+            Self::Nmcli,
+            Self::Netplan,
+            Self::Networkd,
+            Self::Powershell,
+            Self::Netsh,
+            Self::Iphlpapi,
+            Self::Ifconfig,
+            Self::Networksetup,
+            Self::Mock,
+        ]
+        .into_iter()
+        .find(|b| b.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+}
+
+// runtime 负责即时生效，persist 负责重启后保留；同一 role 内按 items 顺序回退
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetIfBackendRole {
+    Runtime,
+    Persist,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfBackendInfo {
+    pub backend: NetIfBackend,
+    pub role: NetIfBackendRole,
+    pub available: bool,
+    pub implemented: bool,
+    pub selected: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfBackendsResponse {
+    pub abi: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<NetIfBackend>,
+    pub items: Vec<NetIfBackendInfo>,
+}
+
+// mock 后端的网卡表与故障注入；interfaces 格式与 list 输出一致，为空时使用内置的 lo + eth0
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfMockConfig {
+    pub abi: u32,
+    #[serde(default)]
+    pub interfaces: Vec<NetInterface>,
+    // 按 "op" 标签匹配，命中时该 op 以 SystemError 失败，用于覆盖失败路径
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fail_ops: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfMockRecord {
+    // 不需要 target 的 op（create_vlan 等）为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(flatten)]
+    pub op: NetIfOp,
+    pub ok: bool,
+    // 合并成一次调用执行的 op 共享同一序号；单独执行的为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfMockLog {
+    pub abi: u32,
+    pub applied: Vec<NetIfMockRecord>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetIfEventKind {
+    NewLink,
+    DelLink,
+    NewAddr,
+    DelAddr,
+    NewRoute,
+    DelRoute,
+    Overrun,
+    Unknown,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfEvent {
+    pub abi: u32,
+    pub seq: u64,
+    pub kind: NetIfEventKind,
+    pub timestamp_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kernel_type: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_index: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix_len: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dad_state: Option<IpDadState>,
+}
+
+impl NetIfEvent {
+    #[must_use]
+    pub fn new(seq: u64, kind: NetIfEventKind, timestamp_ms: u64) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            seq,
+            kind,
+            timestamp_ms,
+            kernel_type: None,
+            if_index: None,
+            name: None,
+            ip: None,
+            prefix_len: None,
+            gateway: None,
+            metric: None,
+            dad_state: None,
+        }
+    }
+}
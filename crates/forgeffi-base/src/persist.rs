@@ -0,0 +1,148 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ForgeFfiError, ABI_VERSION};
+
+pub const PERSIST_SCHEMA_KEY: &str = "forgeffi_schema";
+const TEXT_HEADER_TAG: &str = "forgeffi-schema:";
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PersistHeader {
+    pub kind: String,
+    pub version: u32,
+    pub abi: u32,
+}
+
+pub type JsonMigration = fn(Value) -> Result<Value, ForgeFfiError>;
+
+// version 0 表示没有 header 的旧文件；migrations 中的 (from, f) 把 from 版本升级到 from+1
+#[derive(Copy, Clone, Debug)]
+pub struct PersistSchema {
+    pub kind: &'static str,
+    pub version: u32,
+    pub migrations: &'static [(u32, JsonMigration)],
+}
+
+impl PersistSchema {
+    #[must_use]
+    pub fn header(&self) -> PersistHeader {
+        PersistHeader {
+            kind: self.kind.to_string(),
+            version: self.version,
+            abi: ABI_VERSION,
+        }
+    }
+
+    pub fn encode_json<T: Serialize>(&self, data: &T) -> Result<Vec<u8>, ForgeFfiError> {
+        let mut v = serde_json::to_value(data)
+            .map_err(|e| ForgeFfiError::system_error(format!("序列化 {} 失败: {e}", self.kind)))?;
+        let Value::Object(map) = &mut v else {
+            return Err(ForgeFfiError::system_error(format!(
+                "{} 必须序列化为 JSON 对象",
+                self.kind
+            )));
+        };
+        let header = serde_json::to_value(self.header())
+            .map_err(|e| ForgeFfiError::system_error(format!("序列化 schema header 失败: {e}")))?;
+        map.insert(PERSIST_SCHEMA_KEY.to_string(), header);
+        serde_json::to_vec(&v)
+            .map_err(|e| ForgeFfiError::system_error(format!("序列化 {} 失败: {e}", self.kind)))
+    }
+
+    pub fn decode_json<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, ForgeFfiError> {
+        let mut v: Value = serde_json::from_slice(bytes).map_err(|e| {
+            ForgeFfiError::invalid_argument(format!("解析 {} 文件失败: {e}", self.kind))
+        })?;
+        let Value::Object(map) = &mut v else {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "{} 文件不是 JSON 对象",
+                self.kind
+            )));
+        };
+        let version = match map.remove(PERSIST_SCHEMA_KEY) {
+            None => 0,
+            Some(h) => {
+                let h: PersistHeader = serde_json::from_value(h).map_err(|e| {
+                    ForgeFfiError::invalid_argument(format!("解析 schema header 失败: {e}"))
+                })?;
+                self.check_kind(&h.kind)?;
+                h.version
+            }
+        };
+        let v = self.migrate(version, v)?;
+        serde_json::from_value(v).map_err(|e| {
+            ForgeFfiError::invalid_argument(format!("解析 {} 文件失败: {e}", self.kind))
+        })
+    }
+
+    pub fn migrate(&self, from: u32, mut v: Value) -> Result<Value, ForgeFfiError> {
+        self.check_version(from)?;
+        let mut cur = from;
+        while cur < self.version {
+            let Some((_, f)) = self.migrations.iter().find(|(v, _)| *v == cur) else {
+                return Err(ForgeFfiError::unsupported(format!(
+                    "{} 文件版本 {cur} 过旧，不支持迁移到 {}",
+                    self.kind, self.version
+                )));
+            };
+            v = f(v)?;
+            cur += 1;
+        }
+        Ok(v)
+    }
+
+    #[must_use]
+    pub fn text_header(&self, comment: &str) -> String {
+        format!(
+            "{comment} {TEXT_HEADER_TAG} kind={} version={} abi={ABI_VERSION}\n",
+            self.kind, self.version
+        )
+    }
+
+    // 返回文本文件的 schema 版本（没有 header 时为 0）；kind 不符或版本比当前新时拒绝
+    pub fn check_text(&self, text: &str, comment: &str) -> Result<u32, ForgeFfiError> {
+        let Some(rest) = text
+            .lines()
+            .next()
+            .and_then(|l| l.strip_prefix(comment))
+            .and_then(|l| l.trim_start().strip_prefix(TEXT_HEADER_TAG))
+        else {
+            return Ok(0);
+        };
+        let (mut kind, mut version) = (None, None);
+        for kv in rest.split_whitespace() {
+            match kv.split_once('=') {
+                Some(("kind", v)) => kind = Some(v),
+                Some(("version", v)) => version = v.parse::<u32>().ok(),
+                _ => {}
+            }
+        }
+        self.check_kind(kind.unwrap_or_default())?;
+        let version = version.ok_or_else(|| {
+            ForgeFfiError::invalid_argument(format!("{} 文件 schema header 缺少 version", self.kind))
+        })?;
+        self.check_version(version)?;
+        Ok(version)
+    }
+
+    fn check_kind(&self, kind: &str) -> Result<(), ForgeFfiError> {
+        if kind != self.kind {
+            return Err(ForgeFfiError::unsupported(format!(
+                "文件 schema 不匹配: expected={} got={kind}",
+                self.kind
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_version(&self, version: u32) -> Result<(), ForgeFfiError> {
+        if version > self.version {
+            return Err(ForgeFfiError::unsupported(format!(
+                "{} 文件由更新版本写入（version={version}，当前支持 {}），拒绝处理",
+                self.kind, self.version
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PingMethod {
+    RawSocket,
+    DgramSocket,
+    SystemPing,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PingRequest {
+    pub abi: u32,
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<PingMethod>,
+}
+
+impl PingRequest {
+    #[must_use]
+    pub fn v1(host: impl Into<String>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            host: host.into(),
+            count: None,
+            interval_ms: None,
+            timeout_ms: None,
+            payload_size: None,
+            method: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PingReply {
+    pub seq: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtt_us: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+}
+
+// rtt_us 为空表示该序号丢包；jitter_us 为相邻两次成功 RTT 差值的平均
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PingResponse {
+    pub abi: u32,
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub method: PingMethod,
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rtt_us: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_rtt_us: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rtt_us: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_us: Option<u64>,
+    #[serde(default)]
+    pub replies: Vec<PingReply>,
+}
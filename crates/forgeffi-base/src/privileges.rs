@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivilegeVerdict {
+    Allowed,
+    Denied,
+    // 取决于外部策略（polkit、macOS 授权），只能实际执行时才知道
+    Unknown,
+    // 当前平台不提供该操作，与权限无关
+    Unsupported,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OpPrivilege {
+    pub op: String,
+    pub verdict: PrivilegeVerdict,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+// capabilities 只在 Linux 上填充（CAP_NET_ADMIN 等有效能力集）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PrivilegeReport {
+    pub abi: u32,
+    pub elevated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+    pub ops: Vec<OpPrivilege>,
+}
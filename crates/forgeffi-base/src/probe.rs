@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ABI_VERSION;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+// UDP 没有握手，收不到回包也收不到 ICMP 端口不可达时只能判定为 filtered
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeStatus {
+    Open,
+    Closed,
+    Filtered,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetProbeRequest {
+    pub abi: u32,
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub protocol: ProbeProtocol,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+}
+
+impl NetProbeRequest {
+    #[must_use]
+    pub fn v1(host: impl Into<String>, port: u16, protocol: ProbeProtocol) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            host: host.into(),
+            port,
+            protocol,
+            timeout_ms: None,
+            payload: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetProbeResponse {
+    pub abi: u32,
+    pub protocol: ProbeProtocol,
+    pub host: String,
+    pub port: u16,
+    pub status: ProbeStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_us: Option<u64>,
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+
+use crate::{NetIfBackend, ABI_VERSION};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+}
+
+// Windows 上执行脚本的 PowerShell：pwsh 为 PowerShell 7+，powershell 为系统自带的 Windows PowerShell 5.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerShellEngine {
+    Pwsh,
+    Powershell,
+}
+
+impl PowerShellEngine {
+    // 按优先顺序尝试的引擎：配置了就只用配置的，否则 pwsh 在前
+    #[must_use]
+    pub fn candidates(configured: Option<PowerShellEngine>) -> &'static [PowerShellEngine] {
+        match configured {
+            Some(Self::Pwsh) => &[Self::Pwsh],
+            Some(Self::Powershell) => &[Self::Powershell],
+            None => &[Self::Pwsh, Self::Powershell],
+        }
+    }
+
+    #[must_use]
+    pub fn program(self) -> &'static str {
+        match self {
+            Self::Pwsh => "pwsh",
+            Self::Powershell => "powershell",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    pub abi: u32,
+    #[serde(default)]
+    pub log_level: LogLevel,
+    // netif list 缓存有效期，0 表示不缓存
+    #[serde(default)]
+    pub netif_cache_ttl_ms: u64,
+    // 固定使用的 netif 后端；缺省时读取环境变量 FORGEFFI_NETIF_BACKEND，再按平台默认顺序探测
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netif_backend: Option<NetIfBackend>,
+    // 所有修改系统状态的调用（netif apply、wifi 连接、时间配置）进程内串行执行；
+    // 用于并发调用 nmcli/PowerShell 会互相破坏配置的环境
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub serialize_mutations: bool,
+    // netif apply 的限流与去抖，缺省不限制
+    #[serde(default, skip_serializing_if = "NetIfRateLimit::is_unlimited")]
+    pub netif_rate_limit: NetIfRateLimit,
+    // 错误信息语言（BCP 47，如 "en"、"zh-CN"）；缺省为中文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    // 外部命令（ip/nmcli/PowerShell 等）的默认超时，0 表示不限制；请求自带的 deadline/timeout 优先
+    #[serde(default)]
+    pub command_timeout_ms: u64,
+    // commit-confirm 待确认记录的存放目录；缺省为 /var/lib/forgeffi/netif-confirm（Windows 为
+    // %ProgramData%\forgeffi\netif-confirm）。目录须归当前有效用户所有且不对其他用户开放，否则拒绝使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netif_confirm_dir: Option<String>,
+    // span 环形缓冲容量（tool_trace_dump_json）；缺省为 TRACE_DEFAULT_CAPACITY，0 表示不记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_capacity: Option<u32>,
+    // 固定使用的 PowerShell（仅 Windows）；缺省时依次探测 pwsh、powershell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub powershell_engine: Option<PowerShellEngine>,
+}
+
+// 防止宿主失控地重复调用 apply 造成链路反复抖动；各项为 0 表示不限制
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetIfRateLimit {
+    // 单个网卡每秒最多执行的 op 数（令牌桶，允许瞬时突发到该值）；超出的 op 不执行，结果为 Busy
+    #[serde(default)]
+    pub max_ops_per_sec_per_iface: u32,
+    // 同时进行中的 apply 请求上限（串行模式下排队等待的也计入）；超出时整个请求直接返回 Busy
+    #[serde(default)]
+    pub max_concurrent_applies: u32,
+    // 去抖窗口：同一网卡上与最近一次成功执行相同的 op 在窗口内再次出现时跳过（skipped=true），
+    // 不消耗配额
+    #[serde(default)]
+    pub debounce_ms: u64,
+}
+
+impl NetIfRateLimit {
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl RuntimeConfig {
+    #[must_use]
+    pub fn v1() -> Self {
+        Self {
+            abi: ABI_VERSION,
+            log_level: LogLevel::default(),
+            netif_cache_ttl_ms: 0,
+            netif_backend: None,
+            serialize_mutations: false,
+            netif_rate_limit: NetIfRateLimit::default(),
+            locale: None,
+            command_timeout_ms: 0,
+            netif_confirm_dir: None,
+            trace_capacity: None,
+            powershell_engine: None,
+        }
+    }
+}
+
+// 进程级的生效配置；各模块按需读取，不再各自查环境变量。未 init 时为 RuntimeConfig::v1()
+static CURRENT: OnceLock<RwLock<RuntimeConfig>> = OnceLock::new();
+
+fn current() -> &'static RwLock<RuntimeConfig> {
+    CURRENT.get_or_init(|| RwLock::new(RuntimeConfig::v1()))
+}
+
+#[must_use]
+pub fn runtime_config() -> RuntimeConfig {
+    match current().read() {
+        Ok(g) => g.clone(),
+        Err(p) => p.into_inner().clone(),
+    }
+}
+
+pub fn set_runtime_config(config: RuntimeConfig) {
+    match current().write() {
+        Ok(mut g) => *g = config,
+        Err(p) => *p.into_inner() = config,
+    }
+}
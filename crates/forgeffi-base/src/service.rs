@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ForgeFfiError, ABI_VERSION};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceManager {
+    Systemd,
+    // Windows 服务控制管理器
+    Scm,
+    Launchd,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Running,
+    Stopped,
+    Starting,
+    Stopping,
+    Failed,
+    Unknown,
+}
+
+// systemd 的 enabled/disabled/masked、SCM 的 Automatic/Manual/Disabled、launchd 的 enabled/disabled 归一后的结果
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStartMode {
+    Auto,
+    Manual,
+    Disabled,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    // systemd 为完整 unit 名（含 .service），SCM 为服务名，launchd 为 label
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub state: ServiceState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_mode: Option<ServiceStartMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceListResponse {
+    pub abi: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manager: Option<ServiceManager>,
+    // 当前平台有实现的 ServiceOp 标签
+    #[serde(default)]
+    pub supported_ops: Vec<String>,
+    pub items: Vec<ServiceInfo>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceStatusRequest {
+    pub abi: u32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ServiceOp {
+    Start { name: String },
+    Stop { name: String },
+    // 未运行的服务会被启动
+    Restart { name: String },
+    // 开机自启；SCM 设为 Automatic
+    Enable { name: String },
+    // 取消开机自启，不停止当前实例；SCM 设为 Disabled
+    Disable { name: String },
+}
+
+impl ServiceOp {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Start { name }
+            | Self::Stop { name }
+            | Self::Restart { name }
+            | Self::Enable { name }
+            | Self::Disable { name } => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceApplyRequest {
+    pub abi: u32,
+    pub ops: Vec<ServiceOp>,
+}
+
+impl ServiceApplyRequest {
+    #[must_use]
+    pub fn v1(ops: Vec<ServiceOp>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            ops,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceOpResult {
+    pub i: usize,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceApplyResponse {
+    pub abi: u32,
+    pub ok: bool,
+    pub results: Vec<ServiceOpResult>,
+    // 本次涉及的服务执行后的状态，按首次出现的顺序；查询失败的服务不列出
+    #[serde(default)]
+    pub last_known: Vec<ServiceInfo>,
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{IpFamily, ABI_VERSION};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketState {
+    Listen,
+    SynSent,
+    SynReceived,
+    Established,
+    FinWait1,
+    FinWait2,
+    CloseWait,
+    Closing,
+    LastAck,
+    TimeWait,
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SocketListRequest {
+    pub abi: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<SocketProtocol>,
+    // 只返回 TCP LISTEN 以及未 connect 的 UDP socket
+    #[serde(default)]
+    pub listening_only: bool,
+}
+
+impl SocketListRequest {
+    #[must_use]
+    pub fn v1() -> Self {
+        Self {
+            abi: ABI_VERSION,
+            protocol: None,
+            listening_only: false,
+        }
+    }
+}
+
+// UDP 没有连接状态，state 为空；pid/process_name 在权限不足时为空
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SocketEntry {
+    pub protocol: SocketProtocol,
+    pub family: IpFamily,
+    pub local_address: String,
+    pub local_port: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<SocketState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SocketListResponse {
+    pub abi: u32,
+    pub items: Vec<SocketEntry>,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ForgeFfiError, ABI_VERSION};
+
+// 各字段在平台无法提供时为空（例如 Windows 不报告同步状态、macOS 非 root 读不到 NTP 开关）
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TimeStatus {
+    pub abi: u32,
+    pub unix_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utc_offset_secs: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ntp_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ntp_synchronized: Option<bool>,
+    #[serde(default)]
+    pub ntp_servers: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TimeOp {
+    // Linux/macOS 使用 IANA 名称（Asia/Shanghai），Windows 使用时区 Id（China Standard Time）
+    SetTimezone {
+        timezone: String,
+    },
+    SetNtpServers {
+        servers: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enabled: Option<bool>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TimeApplyRequest {
+    pub abi: u32,
+    pub ops: Vec<TimeOp>,
+}
+
+impl TimeApplyRequest {
+    #[must_use]
+    pub fn v1(ops: Vec<TimeOp>) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            ops,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TimeOpResult {
+    pub i: usize,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ForgeFfiError>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TimeApplyResponse {
+    pub abi: u32,
+    pub ok: bool,
+    pub results: Vec<TimeOpResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_known: Option<TimeStatus>,
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{ErrorCode, NetIfBackend};
+
+// 未配置 RuntimeConfig.trace_capacity 时环形缓冲保留的 span 数
+pub const TRACE_DEFAULT_CAPACITY: u32 = 256;
+
+// 一段已结束的操作（如一次 netif apply、其中的一个 op），用于排查现场的慢操作
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TraceSpan {
+    // 进程内递增，从 1 开始
+    pub id: u64,
+    // 同一线程上外层 span 的 id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u64>,
+    // 如 "netif.apply"、"netif.op"
+    pub name: String,
+    pub start_unix_ms: u64,
+    pub duration_us: u64,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorCode>,
+    // 显式指定，或按执行过的命令推断；没有外部命令且未指定时缺省
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<NetIfBackend>,
+    // 如 target、op、index
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+    // span 期间在本线程上执行的外部命令，按执行顺序
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<TraceCommand>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TraceCommand {
+    pub program: String,
+    // 口令、密钥类参数之后的值已替换为 "***"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    pub duration_us: u64,
+    // 被信号终止或未能启动时缺省
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    // 未能启动、超时或被取消时的说明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TraceDumpResponse {
+    pub abi: u32,
+    pub capacity: u32,
+    // 因缓冲区已满被挤掉的 span 数（累计）
+    pub dropped: u64,
+    // 按结束时间排序，最早的在前
+    pub spans: Vec<TraceSpan>,
+}
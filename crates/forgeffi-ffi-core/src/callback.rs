@@ -0,0 +1,117 @@
+// 事件回调的 Rust 侧封装与订阅句柄表，watch/agent 等长期订阅共用。
+// 线程约定见 forgeffi_base::ToolEventCb：每个订阅在自己的线程上串行回调，stop 会等回调线程退出
+use forgeffi_base::ToolEventCb;
+use serde::Serialize;
+use std::ffi::c_void;
+
+// 宿主传入的回调与 user_data；user_data 的线程安全由宿主按 ToolEventCb 的约定保证
+pub struct EventCallback {
+    f: unsafe extern "C" fn(json_ptr: *const u8, json_len: usize, user_data: *mut c_void),
+    user_data: *mut c_void,
+}
+
+unsafe impl Send for EventCallback {}
+
+impl EventCallback {
+    pub fn new(cb: ToolEventCb, user_data: *mut c_void) -> Option<Self> {
+        cb.map(|f| Self { f, user_data })
+    }
+
+    // 序列化失败时丢弃这条事件，不打断订阅线程
+    pub fn emit<T: Serialize>(&self, ev: &T) {
+        if let Ok(buf) = serde_json::to_vec(ev) {
+            self.emit_json(&buf);
+        }
+    }
+
+    pub fn emit_json(&self, json: &[u8]) {
+        unsafe { (self.f)(json.as_ptr(), json.len(), self.user_data) };
+    }
+}
+
+// 句柄 = 代次 << 32 | (槽位 + 1)，0 永远无效。槽位释放后代次递增，
+// 旧句柄即使槽位被复用也不会匹配到新的订阅
+pub struct HandleRegistry<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+impl<T> HandleRegistry<T> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> u64 {
+        let index = match self.free.pop() {
+            Some(i) => i,
+            None => {
+                self.slots.push(Slot {
+                    generation: 1,
+                    value: None,
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.value = Some(value);
+        (u64::from(slot.generation) << 32) | u64::from(index + 1)
+    }
+
+    pub fn get(&self, handle: u64) -> Option<&T> {
+        let (index, generation) = split(handle)?;
+        self.slots
+            .get(index)
+            .filter(|s| s.generation == generation)
+            .and_then(|s| s.value.as_ref())
+    }
+
+    pub fn remove(&mut self, handle: u64) -> Option<T> {
+        let (index, generation) = split(handle)?;
+        let slot = self.slots.get_mut(index).filter(|s| s.generation == generation)?;
+        let value = slot.value.take()?;
+        release(slot);
+        self.free.push(index as u32);
+        Some(value)
+    }
+
+    // shutdown 时取出全部订阅，已发出的句柄随之失效
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut out = Vec::new();
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if let Some(v) = slot.value.take() {
+                release(slot);
+                self.free.push(index as u32);
+                out.push(v);
+            }
+        }
+        out
+    }
+}
+
+impl<T> Default for HandleRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn split(handle: u64) -> Option<(usize, u32)> {
+    let index = (handle & u64::from(u32::MAX)) as u32;
+    let generation = (handle >> 32) as u32;
+    if index == 0 || generation == 0 {
+        return None;
+    }
+    Some(((index - 1) as usize, generation))
+}
+
+// 代次回绕时跳过 0，保证句柄非 0
+fn release<T>(slot: &mut Slot<T>) {
+    slot.generation = slot.generation.wrapping_add(1).max(1);
+}
@@ -0,0 +1,59 @@
+// 取消令牌以 u64 句柄交给宿主；0 表示不使用令牌
+use forgeffi_base::{cancelled_if_requested, with_cancel_token, CancelToken, ErrorCode, ForgeFfiError};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+static TOKENS: Mutex<BTreeMap<u64, CancelToken>> = Mutex::new(BTreeMap::new());
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+fn tokens() -> MutexGuard<'static, BTreeMap<u64, CancelToken>> {
+    match TOKENS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_cancel_token_new() -> u64 {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    tokens().insert(handle, CancelToken::new());
+    handle
+}
+
+// 取消后仍需 free；对已取消的令牌重复调用无副作用
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_cancel_token_cancel(handle: u64) -> i32 {
+    match tokens().get(&handle) {
+        Some(t) => {
+            t.cancel();
+            0
+        }
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+// 进行中的操作持有令牌副本，free 不会影响它们，也不会触发取消
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_cancel_token_free(handle: u64) -> i32 {
+    match tokens().remove(&handle) {
+        Some(_) => 0,
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+pub fn with_token<T>(
+    handle: u64,
+    f: impl FnOnce() -> Result<T, ForgeFfiError>,
+) -> Result<T, ForgeFfiError> {
+    let token = match handle {
+        0 => None,
+        h => Some(
+            tokens()
+                .get(&h)
+                .cloned()
+                .ok_or_else(|| ForgeFfiError::not_found(format!("取消令牌不存在: {h}")))?,
+        ),
+    };
+    with_cancel_token(token, || f().map_err(cancelled_if_requested))
+}
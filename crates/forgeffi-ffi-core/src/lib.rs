@@ -0,0 +1,94 @@
+//! 各 FFI crate 共用的导出与出参约定。
+//!
+//! 并发约定（适用于所有 `tool_*` 导出）：
+//!
+//! - 任意导出都可以在多个线程上同时调用；全局状态（句柄表、缓存、后端固定、取消令牌）
+//!   均由内部锁保护，某个线程在持锁时 panic 也不会让后续调用永久失败。
+//! - 句柄（watch、agent、fs、取消令牌）可以在创建线程之外的线程上使用和释放；
+//!   在 watch/agent 自己的回调里 stop 也是安全的。
+//! - 回调可能在库内部线程上执行（watch/agent），也可能在调用线程上同步执行（apply 进度）。
+//!   watch/agent 的事件回调统一为 `tool_event_cb`（[`forgeffi_base::ToolEventCb`]），
+//!   线程约定写在该 typedef 的注释里并随头文件生成；订阅句柄带代次，stop 之后旧句柄不会误中新订阅。
+//! - 读取类调用（list、probe 等）互不阻塞；修改类调用默认也可并发。
+//!   `RuntimeConfig.serialize_mutations` 打开后，netif apply、wifi 连接与时间配置在进程内串行，
+//!   用于并发调用 nmcli/PowerShell 会互相破坏配置的环境。
+//! - `RuntimeConfig.netif_rate_limit` 可限制每张网卡每秒的 op 数、同时进行的 apply 数，
+//!   并对短时间内重复的相同 op 去抖；被限流的 op 或请求返回 `Busy`，不会阻塞等待。
+//! - `tool_init`/`tool_shutdown` 可以与其它调用并发，但 shutdown 期间新创建的句柄不保证被回收。
+//!   全局选项（日志级别、后端、缓存 TTL、语言、命令超时、限流）都通过 init 配置设置，
+//!   `tool_config_json` 返回当前生效值；环境变量只作为未配置时的兜底。
+
+#![allow(unsafe_code)]
+
+use forgeffi_base::ErrorCode;
+
+pub mod callback;
+pub mod cancel;
+pub mod mem;
+pub mod metrics;
+pub mod trace;
+pub mod track;
+
+pub use cancel::{tool_cancel_token_cancel, tool_cancel_token_free, tool_cancel_token_new};
+pub use metrics::{tool_metrics_json, tool_metrics_prometheus};
+pub use trace::{tool_trace_dump_json, tool_trace_subscribe, tool_trace_unsubscribe};
+pub use track::tool_debug_outstanding_buffers_json;
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_init(cfg_ptr: *const u8, cfg_len: usize) -> i32 {
+    match unsafe { mem::optional_req_str(cfg_ptr, cfg_len) }.and_then(forgeffi_sys::runtime::init_json) {
+        Ok(_) => 0,
+        Err(e) => e.code.as_i32(),
+    }
+}
+
+// 与 tool_init 相同，但总是写出 JSON：成功时是生效的 RuntimeConfig（含默认值），失败时是 ErrorResponse
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_init_json(
+    cfg_ptr: *const u8,
+    cfg_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { mem::optional_req_str(cfg_ptr, cfg_len) }
+        .and_then(forgeffi_sys::runtime::init_json)
+        .and_then(|config| forgeffi_protocol::encode_json("init 配置", &config));
+    mem::finish(out_ptr, out_len, res)
+}
+
+// 当前生效的全局配置；未调用过 init 时为默认值
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_config_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = forgeffi_protocol::encode_json("init 配置", &forgeffi_sys::runtime::config());
+    mem::finish(out_ptr, out_len, res)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_shutdown() -> i32 {
+    forgeffi_sys::runtime::shutdown();
+    0
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_free(ptr: *mut u8, len: usize) {
+    if ptr.is_null() {
+        return;
+    }
+    #[cfg(feature = "debug-track-buffers")]
+    if !track::unregister(ptr, len) {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+    }
+}
@@ -0,0 +1,83 @@
+// 各 FFI crate 共用的出参缓冲区约定：成功或失败都写出一段 JSON，由调用方用 tool_free 释放。
+// 调用前导出函数已检查 out_ptr/out_len 非空
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+
+pub fn write_error_out(out_ptr: *mut *mut u8, out_len: *mut usize, e: &ForgeFfiError) {
+    let buf = forgeffi_protocol::encode_error(e);
+    unsafe {
+        write_out(out_ptr, out_len, buf);
+    }
+}
+
+// 转成 Box<[u8]> 保证容量等于长度：tool_free 只拿得到长度，按 Vec 的多余容量释放是未定义行为
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn write_out(out_ptr: *mut *mut u8, out_len: *mut usize, buf: Vec<u8>) {
+    let buf = buf.into_boxed_slice();
+    let len = buf.len();
+    let ptr = Box::into_raw(buf).cast::<u8>();
+    #[cfg(feature = "debug-track-buffers")]
+    crate::track::register(ptr, len);
+    unsafe {
+        *out_ptr = ptr;
+        *out_len = len;
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn optional_req_str<'a>(req_ptr: *const u8, req_len: usize) -> Result<Option<&'a str>, ForgeFfiError> {
+    if req_ptr.is_null() || req_len == 0 {
+        return Ok(None);
+    }
+    let req_bytes = unsafe { std::slice::from_raw_parts(req_ptr, req_len) };
+    std::str::from_utf8(req_bytes)
+        .map(Some)
+        .map_err(|e| ForgeFfiError::invalid_argument(format!("请求不是 UTF-8: {e}")))
+}
+
+pub fn finish(out_ptr: *mut *mut u8, out_len: *mut usize, res: Result<Vec<u8>, ForgeFfiError>) -> i32 {
+    match res {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// 调用方自备缓冲区的变体：不分配、不需要 tool_free。
+// written_len 总是写入完整 JSON 的字节数；buf_len 不足时不写 buf，返回 BufferTooSmall，
+// 失败时 buf 里是 ErrorResponse（装不下则同样只给出所需长度，返回码仍是原错误码）。
+// 两次调用之间结果可能变化，重试时应预留余量
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn finish_into(
+    buf: *mut u8,
+    buf_len: usize,
+    written_len: *mut usize,
+    res: Result<Vec<u8>, ForgeFfiError>,
+) -> i32 {
+    let (bytes, code) = match res {
+        Ok(b) => (b, ErrorCode::Ok),
+        Err(e) => (forgeffi_protocol::encode_error(&e), e.code),
+    };
+    unsafe {
+        *written_len = bytes.len();
+    }
+    if bytes.len() > buf_len || buf.is_null() {
+        return if code == ErrorCode::Ok {
+            ErrorCode::BufferTooSmall.as_i32()
+        } else {
+            code.as_i32()
+        };
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, bytes.len());
+    }
+    code.as_i32()
+}
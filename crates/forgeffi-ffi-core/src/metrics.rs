@@ -0,0 +1,23 @@
+// 指标：按 (span, op) 聚合的计数、错误码与延迟直方图，以及外部命令计数。结构见 forgeffi_base::MetricsResponse
+use crate::mem::finish;
+use forgeffi_base::ErrorCode;
+
+// 进程启动以来的累计值，不随 init/shutdown 清零
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_metrics_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, forgeffi_sys::metrics::json_bytes())
+}
+
+// 同样的数据按 Prometheus 文本格式（UTF-8，非 JSON）写出，宿主可直接作为 /metrics 响应体
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_metrics_prometheus(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, Ok(forgeffi_sys::metrics::prometheus_text().into_bytes()))
+}
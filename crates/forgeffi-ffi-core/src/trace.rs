@@ -0,0 +1,68 @@
+// 结构化 trace：apply/op span 的环形缓冲与订阅。span 结构见 forgeffi_base::TraceSpan
+use crate::callback::{EventCallback, HandleRegistry};
+use crate::mem::finish;
+use forgeffi_base::{ErrorCode, ToolEventCb};
+use forgeffi_sys::trace::TraceSubscription;
+use std::ffi::c_void;
+use std::sync::{Mutex, MutexGuard};
+
+static SUBSCRIPTIONS: Mutex<HandleRegistry<TraceSubscription>> = Mutex::new(HandleRegistry::new());
+
+fn subscriptions() -> MutexGuard<'static, HandleRegistry<TraceSubscription>> {
+    match SUBSCRIPTIONS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+// 最近结束的 span（容量由 init 配置的 trace_capacity 决定），不清空缓冲
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_trace_dump_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, forgeffi_sys::trace::dump_json_bytes())
+}
+
+// 每个 span 结束后以一条 TraceSpan JSON 回调；回调积压过多时丢弃新的 span，不会拖慢 apply
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_trace_subscribe(cb: ToolEventCb, user_data: *mut c_void, out_handle: *mut u64) -> i32 {
+    let Some(cb) = EventCallback::new(cb, user_data) else {
+        return ErrorCode::InvalidArgument.as_i32();
+    };
+    if out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    match forgeffi_sys::trace::subscribe(Box::new(move |span| cb.emit(span))) {
+        Ok(sub) => {
+            forgeffi_sys::runtime::register_shutdown_hook("trace", unsubscribe_all);
+            let handle = subscriptions().insert(sub);
+            unsafe {
+                *out_handle = handle;
+            }
+            0
+        }
+        Err(e) => e.code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_trace_unsubscribe(handle: u64) -> i32 {
+    let sub = subscriptions().remove(handle);
+    match sub {
+        Some(s) => {
+            s.stop();
+            0
+        }
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+fn unsubscribe_all() {
+    let all = subscriptions().drain();
+    for s in all {
+        s.stop();
+    }
+}
@@ -0,0 +1,114 @@
+// debug-track-buffers：登记 write_out 交出的每个缓冲区（长度、分配时调用栈），tool_free 时注销，
+// 宿主可随时用 tool_debug_outstanding_buffers_json 查看尚未释放的分配，定位漏掉的 tool_free。
+// 每次分配都抓调用栈，开销较大，只用于调试构建；未启用特性时导出仍在，返回 Unsupported
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+
+#[cfg(feature = "debug-track-buffers")]
+mod imp {
+    use forgeffi_base::{LogLevel, OutstandingBuffer, OutstandingBuffersResponse, ABI_VERSION};
+    use std::backtrace::Backtrace;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Mutex, MutexGuard};
+    use std::time::Instant;
+
+    struct Entry {
+        id: u64,
+        len: usize,
+        at: Instant,
+        backtrace: Backtrace,
+    }
+
+    // 以地址为键；同一地址释放后可能被重新分配，注销总是先于下一次登记
+    static LIVE: Mutex<BTreeMap<usize, Entry>> = Mutex::new(BTreeMap::new());
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+    fn live() -> MutexGuard<'static, BTreeMap<usize, Entry>> {
+        match LIVE.lock() {
+            Ok(g) => g,
+            Err(p) => p.into_inner(),
+        }
+    }
+
+    // 空缓冲区是悬垂指针、不对应真实分配，不登记
+    pub(crate) fn register(ptr: *mut u8, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let entry = Entry {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            len,
+            at: Instant::now(),
+            backtrace: Backtrace::force_capture(),
+        };
+        live().insert(ptr.addr(), entry);
+    }
+
+    // 返回 false 表示不是本库交出的缓冲区（重复释放或外来指针），调用方应放弃释放
+    pub(crate) fn unregister(ptr: *mut u8, len: usize) -> bool {
+        if len == 0 {
+            return true;
+        }
+        match live().remove(&ptr.addr()) {
+            Some(e) if e.len == len => true,
+            Some(e) => {
+                forgeffi_sys::runtime::log(
+                    LogLevel::Warn,
+                    format_args!("tool_free 长度不符: #{} 分配 {} 字节，传入 {len}", e.id, e.len),
+                );
+                true
+            }
+            None => {
+                forgeffi_sys::runtime::log(
+                    LogLevel::Warn,
+                    format_args!("tool_free 收到未登记的指针 {ptr:p}（{len} 字节），可能是重复释放，已忽略"),
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) fn outstanding() -> OutstandingBuffersResponse {
+        let g = live();
+        let mut items: Vec<OutstandingBuffer> = g
+            .values()
+            .map(|e| OutstandingBuffer {
+                id: e.id,
+                len: e.len,
+                age_ms: e.at.elapsed().as_millis() as u64,
+                backtrace: Some(e.backtrace.to_string()),
+            })
+            .collect();
+        drop(g);
+        items.sort_by_key(|b| b.id);
+        OutstandingBuffersResponse {
+            abi: ABI_VERSION,
+            count: items.len(),
+            total_bytes: items.iter().map(|b| b.len).sum(),
+            items,
+        }
+    }
+}
+
+#[cfg(feature = "debug-track-buffers")]
+pub(crate) use imp::{register, unregister};
+
+// 快照在编码前取得，本次返回的缓冲区不计入结果（但它同样需要 tool_free）
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_debug_outstanding_buffers_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    crate::mem::finish(out_ptr, out_len, outstanding_json())
+}
+
+#[cfg(feature = "debug-track-buffers")]
+fn outstanding_json() -> Result<Vec<u8>, ForgeFfiError> {
+    forgeffi_protocol::encode_json("未释放缓冲区", &imp::outstanding())
+}
+
+#[cfg(not(feature = "debug-track-buffers"))]
+fn outstanding_json() -> Result<Vec<u8>, ForgeFfiError> {
+    Err(ForgeFfiError::unsupported("未启用 debug-track-buffers 特性，无法追踪缓冲区"))
+}
@@ -0,0 +1,53 @@
+// 订阅句柄表与事件回调封装的用例
+use forgeffi_ffi_core::callback::{EventCallback, HandleRegistry};
+use std::ffi::c_void;
+
+#[test]
+fn stale_handles_do_not_match_reused_slots() {
+    let mut reg = HandleRegistry::new();
+    let a = reg.insert("a");
+    assert_ne!(a, 0);
+    assert_eq!(reg.get(a), Some(&"a"));
+    assert_eq!(reg.remove(a), Some("a"));
+    assert_eq!(reg.remove(a), None, "重复 stop");
+
+    // 槽位被复用，但代次不同
+    let b = reg.insert("b");
+    assert_ne!(a, b);
+    assert_eq!(reg.get(a), None);
+    assert_eq!(reg.remove(a), None);
+    assert_eq!(reg.get(b), Some(&"b"));
+
+    assert_eq!(reg.get(0), None);
+    assert_eq!(reg.get(u64::from(u32::MAX)), None);
+}
+
+#[test]
+fn drain_invalidates_every_handle() {
+    let mut reg = HandleRegistry::new();
+    let handles: Vec<u64> = (0..4).map(|i| reg.insert(i)).collect();
+    reg.remove(handles[1]);
+    let mut all = reg.drain();
+    all.sort();
+    assert_eq!(all, vec![0, 2, 3]);
+    assert!(handles.iter().all(|h| reg.get(*h).is_none()));
+    let fresh = reg.insert(9);
+    assert!(!handles.contains(&fresh));
+}
+
+unsafe extern "C" fn collect(json_ptr: *const u8, json_len: usize, user_data: *mut c_void) {
+    let out = unsafe { &mut *user_data.cast::<Vec<String>>() };
+    let bytes = unsafe { std::slice::from_raw_parts(json_ptr, json_len) };
+    out.push(String::from_utf8(bytes.to_vec()).expect("UTF-8"));
+}
+
+#[test]
+fn event_callback_passes_json_and_user_data() {
+    let mut got: Vec<String> = Vec::new();
+    let ud = (&mut got as *mut Vec<String>).cast::<c_void>();
+    assert!(EventCallback::new(None, ud).is_none());
+    let cb = EventCallback::new(Some(collect), ud).expect("回调");
+    cb.emit(&serde_json::json!({"kind": "up", "if_index": 2}));
+    cb.emit_json(b"{}");
+    assert_eq!(got, vec![r#"{"if_index":2,"kind":"up"}"#.to_string(), "{}".to_string()]);
+}
@@ -0,0 +1,86 @@
+// 出参缓冲区约定（write_out → tool_free、finish_into）的 Rust 侧用例。
+// 只用纯 Rust 路径，可以在 Miri 下运行：cargo +nightly miri test -p forgeffi-ffi-core --test mem
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+use forgeffi_ffi_core::mem::{finish, finish_into, optional_req_str, write_error_out, write_out};
+use forgeffi_ffi_core::tool_free;
+
+fn take(f: impl FnOnce(*mut *mut u8, *mut usize) -> i32) -> (i32, Vec<u8>) {
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    let rc = f(&mut ptr, &mut len);
+    assert!(!ptr.is_null());
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+    unsafe { tool_free(ptr, len) };
+    (rc, bytes)
+}
+
+#[test]
+fn write_out_round_trips_through_tool_free() {
+    for size in [0usize, 1, 7, 4096] {
+        // 容量大于长度的 Vec 也必须能按长度释放
+        let mut buf = Vec::with_capacity(size * 2 + 3);
+        buf.extend((0..size).map(|i| i as u8));
+        let expected = buf.clone();
+        let (_, got) = take(|p, l| {
+            unsafe { write_out(p, l, buf) };
+            0
+        });
+        assert_eq!(got, expected);
+    }
+    unsafe { tool_free(std::ptr::null_mut(), 0) };
+    unsafe { tool_free(std::ptr::null_mut(), 16) };
+}
+
+#[test]
+fn finish_writes_json_on_both_paths() {
+    let (rc, bytes) = take(|p, l| finish(p, l, Ok(br#"{"ok":true}"#.to_vec())));
+    assert_eq!(rc, 0);
+    assert_eq!(bytes, br#"{"ok":true}"#);
+
+    let (rc, bytes) = take(|p, l| finish(p, l, Err(ForgeFfiError::not_found("nope".to_string()))));
+    assert_eq!(rc, ErrorCode::NotFound.as_i32());
+    let text = String::from_utf8(bytes).expect("UTF-8");
+    assert!(text.contains("NotFound") && text.contains("nope"), "{text}");
+
+    let (_, bytes) = take(|p, l| {
+        write_error_out(p, l, &ForgeFfiError::busy("稍后重试".to_string()));
+        0
+    });
+    assert!(String::from_utf8(bytes).expect("UTF-8").contains("Busy"));
+}
+
+#[test]
+fn finish_into_reports_required_length() {
+    let json = br#"{"items":[1,2,3]}"#.to_vec();
+    let mut written = 0usize;
+    let mut small = [0xAAu8; 4];
+    let rc = unsafe { finish_into(small.as_mut_ptr(), small.len(), &mut written, Ok(json.clone())) };
+    assert_eq!(rc, ErrorCode::BufferTooSmall.as_i32());
+    assert_eq!(written, json.len());
+    assert_eq!(small, [0xAA; 4], "空间不足时不应写 buf");
+
+    let rc = unsafe { finish_into(std::ptr::null_mut(), 0, &mut written, Ok(json.clone())) };
+    assert_eq!(rc, ErrorCode::BufferTooSmall.as_i32());
+    assert_eq!(written, json.len());
+
+    let mut buf = vec![0u8; written];
+    let rc = unsafe { finish_into(buf.as_mut_ptr(), buf.len(), &mut written, Ok(json.clone())) };
+    assert_eq!(rc, 0);
+    assert_eq!(buf, json);
+
+    let err = ForgeFfiError::invalid_argument("bad".to_string());
+    let rc = unsafe { finish_into(std::ptr::null_mut(), 0, &mut written, Err(err)) };
+    assert_eq!(rc, ErrorCode::InvalidArgument.as_i32(), "装不下错误 JSON 时仍返回原错误码");
+    assert!(written > 0);
+}
+
+#[test]
+fn optional_req_str_rejects_invalid_utf8() {
+    assert_eq!(unsafe { optional_req_str(std::ptr::null(), 5) }.ok(), Some(None));
+    let ok = b"{}";
+    assert_eq!(unsafe { optional_req_str(ok.as_ptr(), 0) }.ok(), Some(None));
+    assert_eq!(unsafe { optional_req_str(ok.as_ptr(), ok.len()) }.ok(), Some(Some("{}")));
+    let bad = [0xFFu8, 0xFE];
+    let e = unsafe { optional_req_str(bad.as_ptr(), bad.len()) }.expect_err("非 UTF-8");
+    assert_eq!(e.code, ErrorCode::InvalidArgument);
+}
@@ -0,0 +1,50 @@
+// debug-track-buffers 的登记/注销用例：cargo test -p forgeffi-ffi-core --features debug-track-buffers --test track
+#![cfg(feature = "debug-track-buffers")]
+
+use forgeffi_base::OutstandingBuffersResponse;
+use forgeffi_ffi_core::mem::write_out;
+use forgeffi_ffi_core::{tool_debug_outstanding_buffers_json, tool_free};
+
+fn outstanding() -> OutstandingBuffersResponse {
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    let rc = unsafe { tool_debug_outstanding_buffers_json(&mut ptr, &mut len) };
+    assert_eq!(rc, 0);
+    let res = serde_json::from_slice(unsafe { std::slice::from_raw_parts(ptr, len) }).expect("JSON");
+    unsafe { tool_free(ptr, len) };
+    res
+}
+
+fn alloc(bytes: &[u8]) -> (*mut u8, usize) {
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    unsafe { write_out(&mut ptr, &mut len, bytes.to_vec()) };
+    (ptr, len)
+}
+
+#[test]
+fn outstanding_buffers_are_reported_until_freed() {
+    let before = outstanding();
+    assert_eq!(before.count, 0, "{before:?}");
+
+    let (a, a_len) = alloc(b"first");
+    let (b, b_len) = alloc(b"second!");
+    let (empty, empty_len) = alloc(b"");
+    let now = outstanding();
+    assert_eq!(now.count, 2, "空缓冲区不登记");
+    assert_eq!(now.total_bytes, a_len + b_len);
+    assert!(now.items[0].id < now.items[1].id);
+    assert_eq!(now.items[0].len, a_len);
+    assert!(now.items.iter().all(|i| i.backtrace.as_deref().is_some_and(|s| !s.is_empty())));
+
+    unsafe { tool_free(a, a_len) };
+    unsafe { tool_free(empty, empty_len) };
+    let now = outstanding();
+    assert_eq!(now.count, 1);
+    assert_eq!(now.items[0].len, b_len);
+
+    unsafe { tool_free(b, b_len) };
+    // 重复释放被识别并忽略，不会真正二次释放
+    unsafe { tool_free(b, b_len) };
+    assert_eq!(outstanding().count, 0);
+}
@@ -0,0 +1,75 @@
+// 把版本来源信息以 FORGEFFI_BUILD_* 环境变量嵌入编译产物，由 tool_build_info_json 输出。
+// 取不到的值写空串，运行时按缺失处理；不引入构建依赖
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=FORGEFFI_GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    emit("FORGEFFI_BUILD_GIT_COMMIT", &git_commit().unwrap_or_default());
+    emit("FORGEFFI_BUILD_UNIX_SECS", &build_unix_secs().map(|s| s.to_string()).unwrap_or_default());
+    emit("FORGEFFI_BUILD_TARGET", &std::env::var("TARGET").unwrap_or_default());
+    emit("FORGEFFI_BUILD_PROFILE", &std::env::var("PROFILE").unwrap_or_default());
+    emit("FORGEFFI_BUILD_RUSTC", &rustc_version().unwrap_or_default());
+    emit("FORGEFFI_BUILD_FEATURES", &features().join(","));
+}
+
+fn emit(key: &str, value: &str) {
+    println!("cargo:rustc-env={key}={}", value.replace(['\r', '\n'], " "));
+}
+
+// 从源码包构建（没有 .git）时可用 FORGEFFI_GIT_COMMIT 显式传入
+fn git_commit() -> Option<String> {
+    if let Some(v) = std::env::var("FORGEFFI_GIT_COMMIT").ok().filter(|v| !v.trim().is_empty()) {
+        return Some(v.trim().to_string());
+    }
+    let git_dir = git_dir()?;
+    // 提交或切换分支后重新生成
+    let head = git_dir.join("HEAD");
+    println!("cargo:rerun-if-changed={}", head.display());
+    if let Some(r) = std::fs::read_to_string(&head)
+        .ok()
+        .and_then(|h| h.strip_prefix("ref: ").map(|r| r.trim().to_string()))
+    {
+        println!("cargo:rerun-if-changed={}", git_dir.join(r).display());
+    }
+    println!("cargo:rerun-if-changed={}", git_dir.join("packed-refs").display());
+
+    let out = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(git_dir.parent()?)
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(out.stdout).ok()?.trim().to_string();
+    (out.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+fn git_dir() -> Option<PathBuf> {
+    let manifest = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+    manifest.ancestors().map(|d| d.join(".git")).find(|p| p.is_dir())
+}
+
+fn build_unix_secs() -> Option<u64> {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(v) => v.trim().parse().ok(),
+        Err(_) => SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs()),
+    }
+}
+
+fn rustc_version() -> Option<String> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let out = Command::new(rustc).arg("-V").output().ok()?;
+    out.status.success().then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn features() -> Vec<String> {
+    let mut out: Vec<String> = std::env::vars()
+        .filter_map(|(k, _)| k.strip_prefix("CARGO_FEATURE_").map(|f| f.to_ascii_lowercase().replace('_', "-")))
+        .filter(|f| f != "default")
+        .collect();
+    out.sort();
+    out
+}
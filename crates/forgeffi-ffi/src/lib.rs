@@ -1,5 +1,10 @@
 #![allow(unsafe_code)]
 
+use forgeffi_base::{BuildInfo, CapabilitiesResponse, ErrorCode, ABI_VERSION, MIN_SUPPORTED_ABI};
+use std::ffi::c_char;
+use forgeffi_ffi_core::mem::finish;
+use forgeffi_protocol::encode_json;
+
 #[cfg(feature = "net")]
 pub use forgeffi_net_ffi::*;
 
@@ -11,6 +16,70 @@ pub use forgeffi_sys_ffi::*;
 
 #[unsafe(no_mangle)]
 pub extern "C" fn tool_ffi_abi_version() -> u32 {
-    forgeffi_base::ABI_VERSION
+    ABI_VERSION
+}
+
+// 以 NUL 结尾的静态字符串（crate 版本），调用方不要释放
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
 }
 
+fn build_env(value: &'static str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        abi: ABI_VERSION,
+        api_level: forgeffi_sys::API_LEVEL.as_u32(),
+        git_commit: build_env(env!("FORGEFFI_BUILD_GIT_COMMIT")),
+        build_unix_secs: env!("FORGEFFI_BUILD_UNIX_SECS").parse().ok(),
+        profile: env!("FORGEFFI_BUILD_PROFILE").to_string(),
+        target: env!("FORGEFFI_BUILD_TARGET").to_string(),
+        rustc_version: build_env(env!("FORGEFFI_BUILD_RUSTC")),
+        features: env!("FORGEFFI_BUILD_FEATURES")
+            .split(',')
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect(),
+        modules: forgeffi_sys::enabled_modules().iter().map(|m| m.to_string()).collect(),
+    }
+}
+
+// 现场排查用：版本、提交、构建时间、编译目标与启用的 feature/模块
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_build_info_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, encode_json("构建信息", &build_info()))
+}
+
+// 宿主据此裁剪 UI，而不是逐个 op 试探 Unsupported；只列出编译进本库的模块
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_capabilities_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let resp = CapabilitiesResponse {
+        abi: ABI_VERSION,
+        min_abi: MIN_SUPPORTED_ABI,
+        max_abi: ABI_VERSION,
+        api_level: forgeffi_sys::API_LEVEL.as_u32(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        modules: vec![
+            #[cfg(feature = "net")]
+            forgeffi_net_ffi::net_capabilities(),
+            #[cfg(feature = "fs")]
+            forgeffi_fs_ffi::fs_capabilities(),
+            #[cfg(feature = "sys")]
+            forgeffi_sys_ffi::sys_capabilities(),
+        ],
+    };
+    finish(out_ptr, out_len, encode_json("能力描述", &resp))
+}
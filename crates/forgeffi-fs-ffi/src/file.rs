@@ -0,0 +1,170 @@
+// 句柄生命周期：
+// - tool_fs_open 成功后句柄一直有效，直到 tool_fs_close 或 tool_shutdown；句柄值不会复用。
+// - 同一句柄上的 read/write 串行执行，不同句柄之间互不阻塞。
+// - close 与另一线程上进行中的 read/write 并发时，进行中的调用正常完成，之后文件才真正关闭。
+use forgeffi_base::{ErrorCode, ForgeFfiError, FsOpenMode};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+struct OpenFile {
+    file: File,
+    mode: FsOpenMode,
+}
+
+static FILES: Mutex<BTreeMap<u64, Arc<Mutex<OpenFile>>>> = Mutex::new(BTreeMap::new());
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_fs_open(
+    path_ptr: *const u8,
+    path_len: usize,
+    mode: u32,
+    out_handle: *mut u64,
+) -> i32 {
+    if path_ptr.is_null() || path_len == 0 || out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let Some(mode) = FsOpenMode::from_u32(mode) else {
+        return ErrorCode::InvalidArgument.as_i32();
+    };
+    let path_bytes = unsafe { std::slice::from_raw_parts(path_ptr, path_len) };
+    let Ok(path) = std::str::from_utf8(path_bytes) else {
+        return ErrorCode::InvalidArgument.as_i32();
+    };
+
+    let file = match forgeffi_fs::file::open(Path::new(path), mode) {
+        Ok(f) => f,
+        Err(ForgeFfiError { code, .. }) => return code.as_i32(),
+    };
+    forgeffi_sys::runtime::register_shutdown_hook("fs", close_all_files);
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    let entry = Arc::new(Mutex::new(OpenFile { file, mode }));
+    match FILES.lock() {
+        Ok(mut g) => g.insert(handle, entry),
+        Err(p) => p.into_inner().insert(handle, entry),
+    };
+    unsafe {
+        *out_handle = handle;
+    }
+    0
+}
+
+// out_read 为 0 表示已到文件末尾
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_fs_read(
+    handle: u64,
+    buf: *mut u8,
+    len: usize,
+    out_read: *mut usize,
+) -> i32 {
+    if out_read.is_null() || (buf.is_null() && len != 0) {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let buf: &mut [u8] = if len == 0 {
+        &mut []
+    } else {
+        unsafe { std::slice::from_raw_parts_mut(buf, len) }
+    };
+    let res = with_file(handle, |f| {
+        if !f.mode.can_read() {
+            return Err(ErrorCode::InvalidArgument);
+        }
+        loop {
+            match f.file.read(buf) {
+                Ok(n) => return Ok(n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error_code(e)),
+            }
+        }
+    });
+    match res {
+        Ok(n) => {
+            unsafe {
+                *out_read = n;
+            }
+            0
+        }
+        Err(code) => code.as_i32(),
+    }
+}
+
+// 成功时整段 buf 都已写入；失败时已写入的字节数不确定
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_fs_write(
+    handle: u64,
+    buf: *const u8,
+    len: usize,
+    out_written: *mut usize,
+) -> i32 {
+    if out_written.is_null() || (buf.is_null() && len != 0) {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let buf: &[u8] = if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(buf, len) }
+    };
+    let res = with_file(handle, |f| {
+        if !f.mode.can_write() {
+            return Err(ErrorCode::InvalidArgument);
+        }
+        f.file.write_all(buf).map_err(io_error_code)?;
+        Ok(buf.len())
+    });
+    match res {
+        Ok(n) => {
+            unsafe {
+                *out_written = n;
+            }
+            0
+        }
+        Err(code) => code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_fs_close(handle: u64) -> i32 {
+    let file = match FILES.lock() {
+        Ok(mut g) => g.remove(&handle),
+        Err(p) => p.into_inner().remove(&handle),
+    };
+    match file {
+        Some(_) => 0,
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+// 只在全局表上短暂加锁取出 Arc，避免慢 I/O 阻塞其他句柄
+fn with_file<T>(
+    handle: u64,
+    f: impl FnOnce(&mut OpenFile) -> Result<T, ErrorCode>,
+) -> Result<T, ErrorCode> {
+    let entry = match FILES.lock() {
+        Ok(g) => g.get(&handle).cloned(),
+        Err(p) => p.into_inner().get(&handle).cloned(),
+    };
+    let entry = entry.ok_or(ErrorCode::NotFound)?;
+    let mut g = match entry.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    f(&mut g)
+}
+
+fn io_error_code(e: io::Error) -> ErrorCode {
+    forgeffi_fs::file::map_file_error(Path::new(""), e).code
+}
+
+fn close_all_files() {
+    match FILES.lock() {
+        Ok(mut g) => g.clear(),
+        Err(p) => p.into_inner().clear(),
+    }
+}
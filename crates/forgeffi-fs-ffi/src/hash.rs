@@ -0,0 +1,39 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError, HashProgress};
+use std::ffi::c_void;
+
+use forgeffi_ffi_core::mem::{finish, optional_req_str};
+
+// 进度回调在调用线程上同步触发，json_ptr 只在回调期间有效
+pub type ToolFsProgressCb =
+    Option<unsafe extern "C" fn(json_ptr: *const u8, json_len: usize, user_data: *mut c_void)>;
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_fs_hash_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    cb: ToolFsProgressCb,
+    user_data: *mut c_void,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let mut sink = |p: &HashProgress| {
+        if let Some(f) = cb
+            && let Ok(buf) = serde_json::to_vec(p)
+        {
+            unsafe { f(buf.as_ptr(), buf.len(), user_data) };
+        }
+    };
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => {
+            let progress: Option<forgeffi_fs::hash::ProgressSink<'_>> =
+                if cb.is_some() { Some(&mut sink) } else { None };
+            forgeffi_fs::hash::hash_json_bytes(s, progress)
+        },
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    finish(out_ptr, out_len, res)
+}
@@ -1,7 +1,29 @@
 #![allow(unsafe_code)]
 
+mod file;
+mod hash;
+mod mounts;
+
+use forgeffi_base::ModuleCapabilities;
+use std::collections::BTreeMap;
+
+pub use file::*;
+pub use forgeffi_ffi_core::*;
+pub use hash::*;
+pub use mounts::*;
+
 #[unsafe(no_mangle)]
 pub extern "C" fn tool_fs_ffi_abi_version() -> u32 {
     1
 }
 
+pub fn fs_capabilities() -> ModuleCapabilities {
+    ModuleCapabilities {
+        module: "fs".to_string(),
+        ffi_abi: tool_fs_ffi_abi_version(),
+        features: ["file", "hash", "mounts"].map(str::to_string).to_vec(),
+        ops: Vec::new(),
+        backends: Vec::new(),
+        op_scopes: BTreeMap::new(),
+    }
+}
@@ -0,0 +1,19 @@
+use forgeffi_base::ErrorCode;
+
+use forgeffi_ffi_core::mem::{finish, optional_req_str};
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_fs_mounts_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }
+        .and_then(forgeffi_fs::mounts::list_json_bytes);
+    finish(out_ptr, out_len, res)
+}
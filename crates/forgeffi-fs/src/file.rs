@@ -0,0 +1,45 @@
+use forgeffi_base::{ForgeFfiError, FsOpenMode};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+pub fn open(path: &Path, mode: FsOpenMode) -> Result<File, ForgeFfiError> {
+    if path.as_os_str().is_empty() {
+        return Err(ForgeFfiError::invalid_argument("path 不能为空"));
+    }
+    let mut opts = OpenOptions::new();
+    match mode {
+        FsOpenMode::Read => opts.read(true),
+        FsOpenMode::Write => opts.write(true).create(true).truncate(true),
+        FsOpenMode::Append => opts.append(true).create(true),
+        FsOpenMode::ReadWrite => opts.read(true).write(true),
+        FsOpenMode::CreateNew => opts.write(true).create_new(true),
+    };
+    let file = opts.open(path).map_err(|e| map_file_error(path, e))?;
+    // 目录在 Unix 上可以只读打开，但后续 read 会失败，这里提前拒绝
+    if file.metadata().map(|m| m.is_dir()).unwrap_or(false) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{} 是目录",
+            path.display()
+        )));
+    }
+    Ok(file)
+}
+
+pub fn map_file_error(path: &Path, e: io::Error) -> ForgeFfiError {
+    let msg = format!("{}: {e}", path.display());
+    match e.kind() {
+        io::ErrorKind::NotFound => ForgeFfiError::not_found(msg),
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+            ForgeFfiError::permission_denied(msg)
+        }
+        io::ErrorKind::AlreadyExists
+        | io::ErrorKind::IsADirectory
+        | io::ErrorKind::NotADirectory
+        | io::ErrorKind::InvalidInput
+        | io::ErrorKind::InvalidFilename => ForgeFfiError::invalid_argument(msg),
+        io::ErrorKind::ResourceBusy => ForgeFfiError::busy(msg),
+        io::ErrorKind::TimedOut => ForgeFfiError::timeout(msg),
+        _ => ForgeFfiError::system_error(msg),
+    }
+}
@@ -0,0 +1,237 @@
+use forgeffi_base::{
+    ForgeFfiError, HashAlgorithm, HashFileEntry, HashProgress, HashRequest, HashResponse,
+    ABI_VERSION,
+};
+use forgeffi_protocol::{decode_request, encode_json, validate_hash_request};
+use glob::Pattern;
+use sha2::Digest;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::file::map_file_error;
+
+pub const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+// 进度回调节流：每读满这么多字节或每完成一个文件回调一次
+const PROGRESS_STEP_BYTES: u64 = 4 * 1024 * 1024;
+
+pub type ProgressSink<'a> = &'a mut dyn FnMut(&HashProgress);
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(alg: HashAlgorithm) -> Self {
+        match alg {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(h) => h
+                .finalize()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+            Self::Blake3(h) => h.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+struct Filter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl Filter {
+    fn new(req: &HashRequest) -> Result<Self, ForgeFfiError> {
+        let compile = |pats: &[String]| {
+            pats.iter()
+                .map(|p| {
+                    Pattern::new(p).map_err(|e| {
+                        ForgeFfiError::invalid_argument(format!("glob 非法: {p}: {e}"))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: compile(&req.include)?,
+            exclude: compile(&req.exclude)?,
+        })
+    }
+
+    fn excluded(&self, rel: &str) -> bool {
+        self.exclude.iter().any(|p| p.matches(rel))
+    }
+
+    fn accepts(&self, rel: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(rel)))
+            && !self.excluded(rel)
+    }
+}
+
+struct Progress<'a> {
+    sink: Option<ProgressSink<'a>>,
+    state: HashProgress,
+    last_reported: u64,
+}
+
+impl Progress<'_> {
+    fn advance(&mut self, n: u64) {
+        self.state.bytes_done += n;
+        if self.state.bytes_done - self.last_reported >= PROGRESS_STEP_BYTES {
+            self.report();
+        }
+    }
+
+    fn report(&mut self) {
+        self.last_reported = self.state.bytes_done;
+        if let Some(sink) = self.sink.as_mut() {
+            sink(&self.state);
+        }
+    }
+}
+
+pub fn hash(
+    req: &HashRequest,
+    progress: Option<ProgressSink<'_>>,
+) -> Result<HashResponse, ForgeFfiError> {
+    validate_hash_request(req)?;
+    let root = Path::new(&req.path);
+    let meta = fs::metadata(root).map_err(|e| map_file_error(root, e))?;
+
+    let mut progress = Progress {
+        sink: progress,
+        state: HashProgress {
+            bytes_done: 0,
+            bytes_total: 0,
+            files_done: 0,
+            files_total: 1,
+            current: None,
+        },
+        last_reported: 0,
+    };
+
+    let (digest, files) = if meta.is_file() {
+        progress.state.bytes_total = meta.len();
+        let digest = hash_file(root, req.algorithm, &mut progress)?;
+        progress.state.files_done = 1;
+        progress.report();
+        (digest, Vec::new())
+    } else if meta.is_dir() {
+        let filter = Filter::new(req)?;
+        let mut found = Vec::new();
+        walk(root, "", &filter, &mut found)?;
+        found.sort_by(|a, b| a.0.cmp(&b.0));
+        progress.state.files_total = found.len() as u64;
+        progress.state.bytes_total = found.iter().map(|f| f.2).sum();
+
+        let mut manifest = Hasher::new(req.algorithm);
+        let mut files = Vec::with_capacity(found.len());
+        for (rel, abs, _) in found {
+            progress.state.current = Some(rel.clone());
+            let before = progress.state.bytes_done;
+            let digest = hash_file(&abs, req.algorithm, &mut progress)?;
+            manifest.update(format!("{digest}  {rel}\n").as_bytes());
+            files.push(HashFileEntry {
+                path: rel,
+                size: progress.state.bytes_done - before,
+                digest,
+            });
+            progress.state.files_done += 1;
+            progress.report();
+        }
+        (manifest.finalize_hex(), files)
+    } else {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{} 不是普通文件或目录",
+            root.display()
+        )));
+    };
+
+    Ok(HashResponse {
+        abi: ABI_VERSION,
+        algorithm: req.algorithm,
+        matched: req
+            .expected
+            .as_ref()
+            .map(|e| e.eq_ignore_ascii_case(&digest)),
+        digest,
+        is_dir: meta.is_dir(),
+        total_bytes: progress.state.bytes_done,
+        files,
+    })
+}
+
+pub fn hash_json_bytes(
+    req_json: &str,
+    progress: Option<ProgressSink<'_>>,
+) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: HashRequest = decode_request(req_json)?;
+    encode_json("哈希结果", &hash(&req, progress)?)
+}
+
+// 符号链接不跟随（避免环路与逃出目录），只收集普通文件
+fn walk(
+    dir: &Path,
+    prefix: &str,
+    filter: &Filter,
+    out: &mut Vec<(String, PathBuf, u64)>,
+) -> Result<(), ForgeFfiError> {
+    let entries = fs::read_dir(dir).map_err(|e| map_file_error(dir, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| map_file_error(dir, e))?;
+        let path = entry.path();
+        let ft = entry.file_type().map_err(|e| map_file_error(&path, e))?;
+        let name = entry.file_name();
+        let rel = if prefix.is_empty() {
+            name.to_string_lossy().into_owned()
+        } else {
+            format!("{prefix}/{}", name.to_string_lossy())
+        };
+        if ft.is_dir() {
+            if !filter.excluded(&rel) {
+                walk(&path, &rel, filter, out)?;
+            }
+        } else if ft.is_file() && filter.accepts(&rel) {
+            let size = entry.metadata().map_err(|e| map_file_error(&path, e))?.len();
+            out.push((rel, path, size));
+        }
+    }
+    Ok(())
+}
+
+fn hash_file(
+    path: &Path,
+    alg: HashAlgorithm,
+    progress: &mut Progress<'_>,
+) -> Result<String, ForgeFfiError> {
+    let mut file = File::open(path).map_err(|e| map_file_error(path, e))?;
+    let mut hasher = Hasher::new(alg);
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+    loop {
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(map_file_error(path, e)),
+        };
+        hasher.update(&buf[..n]);
+        progress.advance(n as u64);
+    }
+    Ok(hasher.finalize_hex())
+}
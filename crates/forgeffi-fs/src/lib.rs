@@ -1,2 +1,5 @@
 #![forbid(unsafe_code)]
 
+pub mod file;
+pub mod hash;
+pub mod mounts;
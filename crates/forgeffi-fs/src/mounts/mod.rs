@@ -0,0 +1,57 @@
+use forgeffi_base::{ForgeFfiError, MountListRequest, MountListResponse, MountPoint, ABI_VERSION};
+use forgeffi_protocol::{check_abi, decode_request, encode_json};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub fn list(include_virtual: bool) -> Result<Vec<MountPoint>, ForgeFfiError> {
+    let mut items = platform::list()?;
+    if !include_virtual {
+        items.retain(|m| m.total_bytes.is_some_and(|b| b > 0));
+    }
+    Ok(items)
+}
+
+pub fn list_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: MountListRequest = match req_json {
+        Some(s) => decode_request(s)?,
+        None => MountListRequest::v1(),
+    };
+    check_abi(req.abi)?;
+    encode_json("挂载点列表", &MountListResponse {
+        abi: ABI_VERSION,
+        items: list(req.include_virtual)?,
+    })
+}
+
+// statvfs 失败（无权限、网络盘卡死前的 stale handle）时只返回挂载信息，不影响整体结果
+// fsblkcnt_t 在 macOS 上是 u32，Linux 上是 u64
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[allow(clippy::unnecessary_cast)]
+fn fill_space(m: &mut MountPoint) {
+    use nix::sys::statvfs::{statvfs, FsFlags};
+
+    let Ok(st) = statvfs(m.mount_point.as_str()) else {
+        return;
+    };
+    let frsize = st.fragment_size() as u64;
+    m.total_bytes = Some(st.blocks() as u64 * frsize);
+    m.free_bytes = Some(st.blocks_free() as u64 * frsize);
+    m.available_bytes = Some(st.blocks_available() as u64 * frsize);
+    m.read_only |= st.flags().contains(FsFlags::ST_RDONLY);
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+pub(super) fn list() -> Result<Vec<MountPoint>, ForgeFfiError> {
+    let text = std::fs::read_to_string("/proc/self/mounts")
+        .map_err(|e| ForgeFfiError::system_error(format!("读取 /proc/self/mounts 失败: {e}")))?;
+    let mut out = parse_mounts(&text);
+    for m in &mut out {
+        fill_space(m);
+    }
+    Ok(out)
+}
+
+// 同一挂载点被多次 mount 时只有最后一条可见
+fn parse_mounts(text: &str) -> Vec<MountPoint> {
+    let mut out: Vec<MountPoint> = Vec::new();
+    for line in text.lines() {
+        let f: Vec<&str> = line.split_whitespace().collect();
+        if f.len() < 4 {
+            continue;
+        }
+        let m = MountPoint {
+            mount_point: unescape(f[1]),
+            device: Some(unescape(f[0])).filter(|d| d != "none"),
+            fs_type: Some(f[2].to_string()),
+            total_bytes: None,
+            free_bytes: None,
+            available_bytes: None,
+            read_only: f[3].split(',').any(|o| o == "ro"),
+        };
+        out.retain(|x| x.mount_point != m.mount_point);
+        out.push(m);
+    }
+    out
+}
+
+// 内核把空格、制表符、换行和反斜杠转义为 \040 \011 \012 \134
+fn unescape(s: &str) -> String {
+    let b = s.as_bytes();
+    let mut out = Vec::with_capacity(b.len());
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] == b'\\'
+            && let Some(oct) = b.get(i + 1..i + 4)
+            && oct.iter().all(|c| (b'0'..=b'7').contains(c))
+        {
+            out.push(oct.iter().fold(0u8, |acc, c| acc.wrapping_mul(8).wrapping_add(c - b'0')));
+            i += 4;
+            continue;
+        }
+        out.push(b[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+use std::process::{Command, Stdio};
+
+// mount(8) 的输出即 getmntinfo 的结果：/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)
+pub(super) fn list() -> Result<Vec<MountPoint>, ForgeFfiError> {
+    let out = Command::new("/sbin/mount")
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: mount: {e}")))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ForgeFfiError::system_error(format!("命令失败: mount: {}", stderr.trim())));
+    }
+    let mut items = parse_mount_output(&String::from_utf8_lossy(&out.stdout));
+    for m in &mut items {
+        fill_space(m);
+    }
+    Ok(items)
+}
+
+fn parse_mount_output(text: &str) -> Vec<MountPoint> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let Some((device, rest)) = line.split_once(" on ") else {
+            continue;
+        };
+        let Some((mount_point, opts)) = rest.rsplit_once(" (") else {
+            continue;
+        };
+        let opts: Vec<&str> = opts.trim_end_matches(')').split(", ").collect();
+        out.push(MountPoint {
+            mount_point: mount_point.to_string(),
+            device: Some(device.to_string()),
+            fs_type: opts.first().map(|s| s.to_string()),
+            total_bytes: None,
+            free_bytes: None,
+            available_bytes: None,
+            read_only: opts.contains(&"read-only"),
+        });
+    }
+    out
+}
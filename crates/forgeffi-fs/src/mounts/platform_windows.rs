@@ -0,0 +1,68 @@
+use super::*;
+
+use serde_json::Value;
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Stdio};
+
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+// Win32_LogicalDisk 与 GetLogicalDriveStrings + GetDiskFreeSpaceEx 给出的是同一组盘符
+pub(super) fn list() -> Result<Vec<MountPoint>, ForgeFfiError> {
+    let script = r#"
+[Console]::OutputEncoding = [System.Text.UTF8Encoding]::new()
+$vols = @{}; Get-CimInstance Win32_Volume -ErrorAction SilentlyContinue | ForEach-Object { if ($_.DriveLetter) { $vols[$_.DriveLetter] = $_.DeviceID } }
+@(Get-CimInstance Win32_LogicalDisk | Select-Object DeviceID, FileSystem, Size, FreeSpace, Access, ProviderName, @{n='Volume';e={$vols[$_.DeviceID]}}) | ConvertTo-Json -Compress
+"#;
+    // 按 init 配置的 powershell_engine；未配置时 pwsh 不存在再退回 powershell
+    let configured = forgeffi_base::runtime_config().powershell_engine;
+    let mut result = Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+    let mut program = "powershell";
+    for engine in forgeffi_base::PowerShellEngine::candidates(configured) {
+        program = engine.program();
+        result = Command::new(program)
+            .args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script])
+            .stdin(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .output();
+        if !matches!(&result, Err(e) if e.kind() == std::io::ErrorKind::NotFound) {
+            break;
+        }
+    }
+    let out = result.map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}")))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ForgeFfiError::system_error(format!("PowerShell 失败: {}", stderr.trim())));
+    }
+    let v: Value = serde_json::from_slice(&out.stdout)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}")))?;
+    let disks = match v {
+        Value::Array(a) => a,
+        Value::Null => Vec::new(),
+        other => vec![other],
+    };
+
+    let mut items = Vec::new();
+    for d in disks {
+        let Some(id) = d.get("DeviceID").and_then(Value::as_str) else {
+            continue;
+        };
+        let free = d.get("FreeSpace").and_then(Value::as_u64);
+        // 网络盘的设备取 UNC 路径，本地盘取卷 GUID 路径
+        let device = d
+            .get("ProviderName")
+            .and_then(Value::as_str)
+            .or_else(|| d.get("Volume").and_then(Value::as_str))
+            .map(str::to_string);
+        items.push(MountPoint {
+            mount_point: format!("{id}\\"),
+            device,
+            fs_type: d.get("FileSystem").and_then(Value::as_str).map(str::to_string),
+            total_bytes: d.get("Size").and_then(Value::as_u64),
+            free_bytes: free,
+            available_bytes: free,
+            // Access: 1=只读 2=只写 3=读写
+            read_only: d.get("Access").and_then(Value::as_u64) == Some(1),
+        });
+    }
+    Ok(items)
+}
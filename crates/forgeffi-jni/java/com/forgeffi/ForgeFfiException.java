@@ -0,0 +1,21 @@
+package com.forgeffi;
+
+/** code 与 C ABI 的 ErrorCode 数值一致，codeName 为其名称（例如 "PermissionDenied"）。 */
+public class ForgeFfiException extends RuntimeException {
+    private final int code;
+    private final String codeName;
+
+    public ForgeFfiException(int code, String codeName, String message) {
+        super(codeName + ": " + message);
+        this.code = code;
+        this.codeName = codeName;
+    }
+
+    public int getCode() {
+        return code;
+    }
+
+    public String getCodeName() {
+        return codeName;
+    }
+}
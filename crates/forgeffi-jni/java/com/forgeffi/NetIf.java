@@ -0,0 +1,20 @@
+package com.forgeffi;
+
+/**
+ * 网卡查询与配置。JSON 结构与 C ABI 的 tool_netif_list_json / tool_netif_apply_json 相同，
+ * 失败时抛出 {@link ForgeFfiException}。
+ */
+public final class NetIf {
+    static {
+        System.loadLibrary("forgeffi_jni");
+    }
+
+    private NetIf() {
+    }
+
+    public static native int abiVersion();
+
+    public static native String listJson();
+
+    public static native String applyJson(String req);
+}
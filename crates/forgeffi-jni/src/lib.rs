@@ -0,0 +1,94 @@
+// JNI 绑定：JNI_OnLoad 时把 native 方法注册到 com.forgeffi.NetIf，
+// 失败时抛出 com.forgeffi.ForgeFfiException(code, codeName, message)，code 为 ErrorCode 数值
+use std::ffi::c_void;
+
+use forgeffi_base::{ForgeFfiError, ABI_VERSION};
+use jni::objects::{JClass, JString, JThrowable, JValue};
+use jni::sys::{jint, jstring, JNI_ERR, JNI_VERSION_1_6};
+use jni::{JNIEnv, JavaVM, NativeMethod};
+
+const NETIF_CLASS: &str = "com/forgeffi/NetIf";
+const EXCEPTION_CLASS: &str = "com/forgeffi/ForgeFfiException";
+
+#[unsafe(no_mangle)]
+#[allow(non_snake_case)]
+pub extern "system" fn JNI_OnLoad(vm: JavaVM, _reserved: *mut c_void) -> jint {
+    let Ok(mut env) = vm.get_env() else {
+        return JNI_ERR;
+    };
+    let methods = [
+        NativeMethod {
+            name: "abiVersion".into(),
+            sig: "()I".into(),
+            fn_ptr: abi_version as *mut c_void,
+        },
+        NativeMethod {
+            name: "listJson".into(),
+            sig: "()Ljava/lang/String;".into(),
+            fn_ptr: list_json as *mut c_void,
+        },
+        NativeMethod {
+            name: "applyJson".into(),
+            sig: "(Ljava/lang/String;)Ljava/lang/String;".into(),
+            fn_ptr: apply_json as *mut c_void,
+        },
+    ];
+    match env.register_native_methods(NETIF_CLASS, &methods) {
+        Ok(()) => JNI_VERSION_1_6,
+        Err(_) => JNI_ERR,
+    }
+}
+
+extern "system" fn abi_version(_env: JNIEnv<'_>, _class: JClass<'_>) -> jint {
+    ABI_VERSION as jint
+}
+
+extern "system" fn list_json(mut env: JNIEnv<'_>, _class: JClass<'_>) -> jstring {
+    let res = forgeffi_sys::netif::list_json_bytes();
+    finish(&mut env, res)
+}
+
+extern "system" fn apply_json(mut env: JNIEnv<'_>, _class: JClass<'_>, req: JString<'_>) -> jstring {
+    let res = if req.is_null() {
+        Err(ForgeFfiError::invalid_argument("请求为空"))
+    } else {
+        match env.get_string(&req) {
+            Ok(s) => forgeffi_sys::netif::apply_json_bytes(&String::from(s)),
+            Err(e) => Err(ForgeFfiError::invalid_argument(format!("读取请求失败: {e}"))),
+        }
+    };
+    finish(&mut env, res)
+}
+
+// 出错时返回 null 并在 Java 侧留下待抛出的异常
+fn finish(env: &mut JNIEnv<'_>, res: Result<Vec<u8>, ForgeFfiError>) -> jstring {
+    let text = match res {
+        Ok(buf) => String::from_utf8_lossy(&buf).into_owned(),
+        Err(e) => {
+            throw(env, &e);
+            return std::ptr::null_mut();
+        }
+    };
+    match env.new_string(text) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+fn throw(env: &mut JNIEnv<'_>, e: &ForgeFfiError) {
+    let res = (|| -> jni::errors::Result<()> {
+        let name = env.new_string(format!("{:?}", e.code))?;
+        let msg = env.new_string(&e.message)?;
+        let obj = env.new_object(
+            EXCEPTION_CLASS,
+            "(ILjava/lang/String;Ljava/lang/String;)V",
+            &[JValue::Int(e.code.as_i32()), JValue::Object(&name), JValue::Object(&msg)],
+        )?;
+        env.throw(JThrowable::from(obj))
+    })();
+    // 异常类缺失（例如被混淆裁剪）时退回 RuntimeException，保证调用方仍能看到错误
+    if res.is_err() {
+        let _ = env.exception_clear();
+        let _ = env.throw_new("java/lang/RuntimeException", format!("{:?}: {}", e.code, e.message));
+    }
+}
@@ -0,0 +1,35 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+
+use forgeffi_ffi_core::mem::{finish, optional_req_str};
+
+// req 为 CaptureStartRequest JSON；成功时 out_handle 写入非 0 句柄，之后须调用 tool_net_capture_stop
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_net_capture_start(req_ptr: *const u8, req_len: usize, out_handle: *mut u64) -> i32 {
+    if out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => forgeffi_sys::capture::start_json(s),
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    match res {
+        Ok(handle) => {
+            unsafe {
+                *out_handle = handle;
+            }
+            0
+        }
+        Err(ForgeFfiError { code, .. }) => code.as_i32(),
+    }
+}
+
+// 停止抓包并释放句柄，out 写入 CaptureSummary JSON；达到上限已自行结束的抓包同样需要调用
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_net_capture_stop(handle: u64, out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, forgeffi_sys::capture::stop_json_bytes(handle))
+}
@@ -1,6 +1,10 @@
-use forgeffi_base::{ErrorCode, ForgeFfiError, ABI_VERSION};
+use forgeffi_base::{
+    ErrorCode, ForgeFfiError, ModuleCapabilities, NetIfOpResult, NetIfSortKey, ABI_VERSION,
+};
+use std::ffi::c_void;
 
-use crate::mem::{write_error_out, write_out};
+use forgeffi_ffi_core::cancel::with_token;
+use forgeffi_ffi_core::mem::{finish, finish_into, optional_req_str, write_error_out, write_out};
 
 #[unsafe(no_mangle)]
 pub extern "C" fn tool_netif_abi_version() -> u32 {
@@ -12,6 +16,26 @@ pub extern "C" fn tool_net_ffi_abi_version() -> u32 {
     ABI_VERSION
 }
 
+// 供聚合库的 tool_capabilities_json 汇总
+pub fn net_capabilities() -> ModuleCapabilities {
+    ModuleCapabilities {
+        module: "net".to_string(),
+        ffi_abi: tool_net_ffi_abi_version(),
+        features: ["netif", "watch", "wifi", "dns", "ping", "probe", "http", "capture", "lldp"]
+            .map(str::to_string)
+            .to_vec(),
+        ops: forgeffi_sys::netif::supported_ops()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        backends: forgeffi_sys::netif::backends().items,
+        op_scopes: forgeffi_sys::netif::op_scopes()
+            .into_iter()
+            .map(|(op, scopes)| (op.to_string(), scopes.to_vec()))
+            .collect(),
+    }
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn tool_netif_list_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
@@ -33,6 +57,135 @@ pub unsafe extern "C" fn tool_netif_list_json(out_ptr: *mut *mut u8, out_len: *m
     }
 }
 
+// buf 可为空（buf_len=0）用于只查询所需长度
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_json_into(
+    buf: *mut u8,
+    buf_len: usize,
+    written_len: *mut usize,
+) -> i32 {
+    if written_len.is_null() || (buf.is_null() && buf_len != 0) {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    unsafe { finish_into(buf, buf_len, written_len, forgeffi_sys::netif::list_json_bytes()) }
+}
+
+// cancel_token 为 tool_cancel_token_new 返回的句柄，0 表示不可取消
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_json_cancellable(
+    cancel_token: u64,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, with_token(cancel_token, forgeffi_sys::netif::list_json_bytes))
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_sorted_json(
+    sort_key: u32,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let Some(key) = NetIfSortKey::from_u32(sort_key) else {
+        let e = ForgeFfiError::invalid_argument(format!("未知的 sort_key: {sort_key}"));
+        write_error_out(out_ptr, out_len, &e);
+        return e.code.as_i32();
+    };
+
+    match forgeffi_sys::netif::list_json_bytes_sorted(key) {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// cursor 为空（NULL 或长度 0）表示第一页；page_size 为 0 时使用默认值
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_page_json(
+    cursor_ptr: *const u8,
+    cursor_len: usize,
+    page_size: u32,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    let res = unsafe { optional_req_str(cursor_ptr, cursor_len) }
+        .and_then(|cursor| forgeffi_sys::netif::list_page_json_bytes(cursor, page_size));
+    finish(out_ptr, out_len, res)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_filtered_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    if req_ptr.is_null() || req_len == 0 {
+        let e = ForgeFfiError::invalid_argument("请求为空");
+        write_error_out(out_ptr, out_len, &e);
+        return e.code.as_i32();
+    }
+
+    let req_bytes = unsafe { std::slice::from_raw_parts(req_ptr, req_len) };
+    let req_str = match std::str::from_utf8(req_bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            let err = ForgeFfiError::invalid_argument(format!("请求不是 UTF-8: {e}"));
+            write_error_out(out_ptr, out_len, &err);
+            return err.code.as_i32();
+        }
+    };
+
+    match forgeffi_sys::netif::list_filtered_json_bytes(req_str) {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// ttl_ms 为 0 时关闭缓存；设置后已有缓存立即失效
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_netif_cache_set_ttl(ttl_ms: u64) {
+    forgeffi_sys::netif::set_cache_ttl_ms(ttl_ms);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_netif_cache_invalidate() {
+    forgeffi_sys::netif::cache_invalidate();
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn tool_netif_apply_json(
@@ -74,14 +227,333 @@ pub unsafe extern "C" fn tool_netif_apply_json(
     }
 }
 
+// 已执行完的 op 照常报告结果，尚未开始的 op 以 Cancelled 失败
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_apply_json_cancellable(
+    req_ptr: *const u8,
+    req_len: usize,
+    cancel_token: u64,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => with_token(cancel_token, || forgeffi_sys::netif::apply_json_bytes(s)),
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    finish(out_ptr, out_len, res)
+}
+
+// 注意：返回 BufferTooSmall 时 ops 已经执行过，用更大的缓冲区重试会再执行一遍；
+// 需要幂等重试时配合 skip_if_applied，或直接预留足够大的缓冲区
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_apply_json_into(
+    req_ptr: *const u8,
+    req_len: usize,
+    buf: *mut u8,
+    buf_len: usize,
+    written_len: *mut usize,
+) -> i32 {
+    if written_len.is_null() || (buf.is_null() && buf_len != 0) {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => forgeffi_sys::netif::apply_json_bytes(s),
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    unsafe { finish_into(buf, buf_len, written_len, res) }
+}
+
+pub type ToolNetifProgressCb = Option<
+    unsafe extern "C" fn(
+        index: usize,
+        total: usize,
+        json_ptr: *const u8,
+        json_len: usize,
+        user_data: *mut c_void,
+    ),
+>;
+
+// 回调在调用线程上同步执行，json 为该 op 的 NetIfOpResult，仅在回调期间有效；cb 为空时等同 tool_netif_apply_json
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_apply_json_ex(
+    req_ptr: *const u8,
+    req_len: usize,
+    cb: ToolNetifProgressCb,
+    user_data: *mut c_void,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let mut on_progress = |i: usize, total: usize, r: &NetIfOpResult| {
+        if let Some(f) = cb
+            && let Ok(buf) = serde_json::to_vec(r)
+        {
+            unsafe { f(i, total, buf.as_ptr(), buf.len(), user_data) };
+        }
+    };
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => forgeffi_sys::netif::apply_json_bytes_with_progress(s, &mut on_progress),
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    finish(out_ptr, out_len, res)
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::missing_safety_doc)]
-pub unsafe extern "C" fn tool_free(ptr: *mut u8, len: usize) {
-    if ptr.is_null() {
-        return;
+pub unsafe extern "C" fn tool_netif_multi_apply_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
     }
-    unsafe {
-        drop(Vec::from_raw_parts(ptr, len, len));
+    if req_ptr.is_null() || req_len == 0 {
+        let e = ForgeFfiError::invalid_argument("请求为空");
+        write_error_out(out_ptr, out_len, &e);
+        return e.code.as_i32();
+    }
+
+    let req_bytes = unsafe { std::slice::from_raw_parts(req_ptr, req_len) };
+    let req_str = match std::str::from_utf8(req_bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            let err = ForgeFfiError::invalid_argument(format!("请求不是 UTF-8: {e}"));
+            write_error_out(out_ptr, out_len, &err);
+            return err.code.as_i32();
+        }
+    };
+
+    match forgeffi_sys::netif::multi_apply_json_bytes(req_str) {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
     }
 }
 
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_reconcile_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    if req_ptr.is_null() || req_len == 0 {
+        let e = ForgeFfiError::invalid_argument("请求为空");
+        write_error_out(out_ptr, out_len, &e);
+        return e.code.as_i32();
+    }
+
+    let req_bytes = unsafe { std::slice::from_raw_parts(req_ptr, req_len) };
+    let req_str = match std::str::from_utf8(req_bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            let err = ForgeFfiError::invalid_argument(format!("请求不是 UTF-8: {e}"));
+            write_error_out(out_ptr, out_len, &err);
+            return err.code.as_i32();
+        }
+    };
+
+    match forgeffi_sys::netif::reconcile_json_bytes(req_str) {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// req 为空（NULL 或长度 0）时快照全部网卡
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_snapshot_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    let res = unsafe { optional_req_str(req_ptr, req_len) }
+        .and_then(forgeffi_sys::netif::snapshot_json_bytes);
+    finish(out_ptr, out_len, res)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_restore_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    if req_ptr.is_null() || req_len == 0 {
+        let e = ForgeFfiError::invalid_argument("请求为空");
+        write_error_out(out_ptr, out_len, &e);
+        return e.code.as_i32();
+    }
+
+    let req_bytes = unsafe { std::slice::from_raw_parts(req_ptr, req_len) };
+    let req_str = match std::str::from_utf8(req_bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            let err = ForgeFfiError::invalid_argument(format!("请求不是 UTF-8: {e}"));
+            write_error_out(out_ptr, out_len, &err);
+            return err.code.as_i32();
+        }
+    };
+
+    match forgeffi_sys::netif::restore_json_bytes(req_str) {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// 确认 commit-confirm 模式下的变更（token 取自 apply 响应的 confirm_token）；
+// 已超时回滚或 token 未知时返回 NotFound
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_confirm(token_ptr: *const u8, token_len: usize) -> i32 {
+    let res = unsafe { optional_req_str(token_ptr, token_len) }.and_then(|token| {
+        let token = token.ok_or_else(|| ForgeFfiError::invalid_argument("token 为空"))?;
+        forgeffi_sys::netif::confirm(token)
+    });
+    match res {
+        Ok(()) => 0,
+        Err(e) => e.code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_default_route_json(
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    match forgeffi_sys::netif::default_route_json_bytes() {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// 每块网卡的限速（SetBandwidthLimit）与当前 qdisc / QoS 策略
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_qos_json(
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    match forgeffi_sys::netif::qos_json_bytes() {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_backends_json(
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    match forgeffi_sys::netif::backends_json_bytes() {
+        Ok(buf) => {
+            unsafe {
+                write_out(out_ptr, out_len, buf);
+            }
+            0
+        }
+        Err(e) => {
+            write_error_out(out_ptr, out_len, &e);
+            e.code.as_i32()
+        }
+    }
+}
+
+// 替换 mock 后端的网卡表与故障注入并清空 op 记录；cfg 为空（NULL 或长度 0）时恢复内置网卡表。
+// 只影响 mock 后端，是否启用由 FORGEFFI_NETIF_BACKEND / tool_init 的 netif_backend 或 mock feature 决定
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_mock_configure_json(cfg_ptr: *const u8, cfg_len: usize) -> i32 {
+    let res = unsafe { optional_req_str(cfg_ptr, cfg_len) }
+        .and_then(forgeffi_sys::netif::mock::configure_json);
+    match res {
+        Ok(()) => 0,
+        Err(e) => e.code.as_i32(),
+    }
+}
+
+// 自上次 configure 以来 mock 后端收到的 op，按执行顺序
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_mock_log_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, forgeffi_sys::netif::mock::log_json_bytes())
+}
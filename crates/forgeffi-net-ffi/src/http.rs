@@ -0,0 +1,21 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+
+use forgeffi_ffi_core::mem::{finish, optional_req_str};
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_net_http_probe_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(|req| match req {
+        Some(s) => forgeffi_net::http::http_probe_json_bytes(s),
+        None => Err(ForgeFfiError::invalid_argument("请求为空")),
+    });
+    finish(out_ptr, out_len, res)
+}
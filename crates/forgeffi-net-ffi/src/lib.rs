@@ -1,7 +1,26 @@
 #![allow(unsafe_code)]
 
+mod capture;
+mod dns;
 mod exports;
-mod mem;
+mod http;
+mod lldp;
+mod ping;
+mod probe;
+mod structs;
+#[cfg(target_family = "wasm")]
+mod wasm;
+mod watch;
+mod wifi;
 
+pub use capture::*;
+pub use dns::*;
 pub use exports::*;
-
+pub use forgeffi_ffi_core::*;
+pub use http::*;
+pub use lldp::*;
+pub use ping::*;
+pub use probe::*;
+pub use structs::*;
+pub use watch::*;
+pub use wifi::*;
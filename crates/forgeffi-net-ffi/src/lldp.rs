@@ -0,0 +1,38 @@
+use forgeffi_base::ErrorCode;
+
+use forgeffi_ffi_core::cancel::with_token;
+use forgeffi_ffi_core::mem::{finish, optional_req_str};
+
+// req 为可选的 LldpRequest JSON（req_ptr 为空时查询全部网卡）。没有 lldpd/lldpad 时会被动监听
+// 最长 listen_secs 秒，期间阻塞；需要中断时用 tool_net_lldp_json_cancellable
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_net_lldp_json(
+    req_ptr: *const u8,
+    req_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }.and_then(forgeffi_sys::lldp::query_json_bytes);
+    finish(out_ptr, out_len, res)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_net_lldp_json_cancellable(
+    req_ptr: *const u8,
+    req_len: usize,
+    cancel_token: u64,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(req_ptr, req_len) }
+        .and_then(|req| with_token(cancel_token, || forgeffi_sys::lldp::query_json_bytes(req)));
+    finish(out_ptr, out_len, res)
+}
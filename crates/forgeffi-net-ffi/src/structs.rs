@@ -0,0 +1,209 @@
+// 网卡列表的 C 结构体视图，供不想解析 JSON 的调用方使用。
+// 字段是 NetInterface 的子集；需要完整信息（路由、DNS、能力等）时仍用 tool_netif_list_json。
+// 数组、字符串与地址数组都归本库所有，只能整体交给 tool_netif_list_structs_free 释放。
+use forgeffi_base::{AdminState, ErrorCode, IfaceKind, IpAddrEntry, NetInterface, OperState};
+use forgeffi_protocol::parse_mac;
+use std::ffi::{c_char, CString};
+use std::net::IpAddr;
+
+pub const TOOL_NETIF_FLAG_UP: u32 = 1 << 0;
+pub const TOOL_NETIF_FLAG_RUNNING: u32 = 1 << 1;
+pub const TOOL_NETIF_FLAG_LOOPBACK: u32 = 1 << 2;
+pub const TOOL_NETIF_FLAG_BROADCAST: u32 = 1 << 3;
+pub const TOOL_NETIF_FLAG_MULTICAST: u32 = 1 << 4;
+pub const TOOL_NETIF_FLAG_POINT_TO_POINT: u32 = 1 << 5;
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToolNetIfaceKind {
+    Unknown = 0,
+    Physical = 1,
+    Virtual = 2,
+    Loopback = 3,
+    Tunnel = 4,
+    Vlan = 5,
+    Bridge = 6,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToolNetAdminState {
+    Unknown = 0,
+    Up = 1,
+    Down = 2,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToolNetOperState {
+    Unknown = 0,
+    Up = 1,
+    Down = 2,
+    Dormant = 3,
+    LowerLayerDown = 4,
+}
+
+// family 为 4 或 6；IPv4 只使用 addr 的前 4 字节，其余为 0
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct ToolNetIpAddr {
+    pub family: u8,
+    pub prefix_len: u8,
+    pub addr: [u8; 16],
+}
+
+// 数值字段为 0 表示未知/不适用；is_physical: -1 未知，0 否，1 是
+#[repr(C)]
+#[derive(Debug)]
+pub struct ToolNetIface {
+    pub if_index: u32,
+    pub kind: ToolNetIfaceKind,
+    pub admin_state: ToolNetAdminState,
+    pub oper_state: ToolNetOperState,
+    pub flags: u32,
+    pub mtu: u32,
+    pub vlan_id: u32,
+    pub speed_bps: u64,
+    // UTF-8，NUL 结尾
+    pub name: *mut c_char,
+    // 可为 NULL
+    pub display_name: *mut c_char,
+    pub has_mac: u8,
+    pub mac: [u8; 6],
+    pub is_physical: i8,
+    // 先 IPv4 后 IPv6；addr_count 为 0 时 addrs 为 NULL
+    pub addrs: *mut ToolNetIpAddr,
+    pub addr_count: usize,
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_structs(
+    out_array: *mut *mut ToolNetIface,
+    out_count: *mut usize,
+) -> i32 {
+    if out_array.is_null() || out_count.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    unsafe {
+        *out_array = std::ptr::null_mut();
+        *out_count = 0;
+    }
+    let items = match forgeffi_sys::netif::list_response() {
+        Ok(r) => r.items,
+        Err(e) => return e.code.as_i32(),
+    };
+    let boxed: Box<[ToolNetIface]> = items.iter().map(to_struct).collect();
+    let count = boxed.len();
+    if count == 0 {
+        return 0;
+    }
+    unsafe {
+        *out_array = Box::into_raw(boxed).cast::<ToolNetIface>();
+        *out_count = count;
+    }
+    0
+}
+
+// array/count 必须原样来自 tool_netif_list_structs；传 NULL 无副作用
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_list_structs_free(array: *mut ToolNetIface, count: usize) {
+    if array.is_null() {
+        return;
+    }
+    let boxed = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(array, count)) };
+    for it in &boxed {
+        unsafe {
+            free_c_string(it.name);
+            free_c_string(it.display_name);
+            if !it.addrs.is_null() {
+                drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                    it.addrs,
+                    it.addr_count,
+                )));
+            }
+        }
+    }
+}
+
+fn to_struct(it: &NetInterface) -> ToolNetIface {
+    let mac = it.mac.as_deref().and_then(|m| parse_mac(m).ok());
+    let addrs: Box<[ToolNetIpAddr]> = it.ipv4.iter().chain(&it.ipv6).filter_map(to_addr).collect();
+    let addr_count = addrs.len();
+    ToolNetIface {
+        if_index: it.if_index,
+        kind: match it.kind {
+            IfaceKind::Unknown => ToolNetIfaceKind::Unknown,
+            IfaceKind::Physical => ToolNetIfaceKind::Physical,
+            IfaceKind::Virtual => ToolNetIfaceKind::Virtual,
+            IfaceKind::Loopback => ToolNetIfaceKind::Loopback,
+            IfaceKind::Tunnel => ToolNetIfaceKind::Tunnel,
+            IfaceKind::Vlan => ToolNetIfaceKind::Vlan,
+            IfaceKind::Bridge => ToolNetIfaceKind::Bridge,
+        },
+        admin_state: match it.admin_state {
+            AdminState::Unknown => ToolNetAdminState::Unknown,
+            AdminState::Up => ToolNetAdminState::Up,
+            AdminState::Down => ToolNetAdminState::Down,
+        },
+        oper_state: match it.oper_state {
+            None | Some(OperState::Unknown) => ToolNetOperState::Unknown,
+            Some(OperState::Up) => ToolNetOperState::Up,
+            Some(OperState::Down) => ToolNetOperState::Down,
+            Some(OperState::Dormant) => ToolNetOperState::Dormant,
+            Some(OperState::LowerLayerDown) => ToolNetOperState::LowerLayerDown,
+        },
+        flags: it.flags.0,
+        mtu: it.mtu.unwrap_or(0),
+        vlan_id: it.vlan_id.map_or(0, u32::from),
+        speed_bps: it.speed_bps.unwrap_or(0),
+        name: to_c_string(&it.name),
+        display_name: it
+            .display_name
+            .as_deref()
+            .map_or(std::ptr::null_mut(), to_c_string),
+        has_mac: u8::from(mac.is_some()),
+        mac: mac.unwrap_or_default(),
+        is_physical: it.is_physical.map_or(-1, i8::from),
+        addrs: if addr_count == 0 {
+            std::ptr::null_mut()
+        } else {
+            Box::into_raw(addrs).cast::<ToolNetIpAddr>()
+        },
+        addr_count,
+    }
+}
+
+fn to_addr(a: &IpAddrEntry) -> Option<ToolNetIpAddr> {
+    // IPv6 链路本地地址可能带 %zone
+    let ip: IpAddr = a.ip.split('%').next()?.parse().ok()?;
+    let mut addr = [0u8; 16];
+    let family = match ip {
+        IpAddr::V4(v4) => {
+            addr[..4].copy_from_slice(&v4.octets());
+            4
+        }
+        IpAddr::V6(v6) => {
+            addr = v6.octets();
+            6
+        }
+    };
+    Some(ToolNetIpAddr {
+        family,
+        prefix_len: a.prefix_len,
+        addr,
+    })
+}
+
+// 名字里不会出现 NUL；万一出现则截断，而不是丢掉整条记录
+fn to_c_string(s: &str) -> *mut c_char {
+    let s = s.split('\0').next().unwrap_or_default();
+    CString::new(s).unwrap_or_default().into_raw()
+}
+
+unsafe fn free_c_string(p: *mut c_char) {
+    if !p.is_null() {
+        drop(unsafe { CString::from_raw(p) });
+    }
+}
@@ -0,0 +1,80 @@
+// wasm 导出面：不经过裸指针出参，字符串由 wasm-bindgen / 组件模型负责跨边界拷贝。
+// wasm32-unknown-unknown 面向浏览器（wasm-bindgen），wasm32-wasip1 导出 wit/forgeffi.wit 的组件 world
+use forgeffi_base::{ForgeFfiError, ABI_VERSION};
+
+fn into_string(res: Result<Vec<u8>, ForgeFfiError>) -> Result<String, String> {
+    match res {
+        Ok(buf) => Ok(String::from_utf8_lossy(&buf).into_owned()),
+        Err(e) => Err(String::from_utf8_lossy(&forgeffi_protocol::encode_error(&e)).into_owned()),
+    }
+}
+
+fn list_json() -> Result<String, String> {
+    into_string(forgeffi_sys::netif::list_json_bytes())
+}
+
+fn list_filtered_json(req: &str) -> Result<String, String> {
+    into_string(forgeffi_sys::netif::list_filtered_json_bytes(req))
+}
+
+fn list_page_json(cursor: Option<&str>, page_size: u32) -> Result<String, String> {
+    into_string(forgeffi_sys::netif::list_page_json_bytes(cursor, page_size))
+}
+
+#[cfg(target_os = "unknown")]
+mod bindgen {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen(js_name = netifAbiVersion)]
+    pub fn netif_abi_version() -> u32 {
+        super::ABI_VERSION
+    }
+
+    // 失败时抛出错误 JSON 字符串
+    #[wasm_bindgen(js_name = netifListJson)]
+    pub fn netif_list_json() -> Result<String, JsValue> {
+        super::list_json().map_err(JsValue::from)
+    }
+
+    #[wasm_bindgen(js_name = netifListFilteredJson)]
+    pub fn netif_list_filtered_json(req: &str) -> Result<String, JsValue> {
+        super::list_filtered_json(req).map_err(JsValue::from)
+    }
+
+    #[wasm_bindgen(js_name = netifListPageJson)]
+    pub fn netif_list_page_json(cursor: Option<String>, page_size: u32) -> Result<String, JsValue> {
+        super::list_page_json(cursor.as_deref(), page_size).map_err(JsValue::from)
+    }
+}
+
+#[cfg(target_os = "wasi")]
+mod component {
+    wit_bindgen::generate!({
+        world: "forgeffi-net",
+        path: "wit",
+    });
+
+    use exports::forgeffi::net::netif::Guest;
+
+    struct NetIf;
+
+    impl Guest for NetIf {
+        fn abi_version() -> u32 {
+            super::ABI_VERSION
+        }
+
+        fn list_json() -> Result<String, String> {
+            super::list_json()
+        }
+
+        fn list_filtered_json(req: String) -> Result<String, String> {
+            super::list_filtered_json(&req)
+        }
+
+        fn list_page_json(cursor: Option<String>, page_size: u32) -> Result<String, String> {
+            super::list_page_json(cursor.as_deref(), page_size)
+        }
+    }
+
+    export!(NetIf);
+}
@@ -0,0 +1,67 @@
+use forgeffi_base::{ErrorCode, ToolEventCb};
+use forgeffi_ffi_core::callback::{EventCallback, HandleRegistry};
+use forgeffi_sys::netif::{EventSink, NetIfWatcher};
+use std::ffi::c_void;
+use std::sync::{Mutex, MutexGuard};
+
+// 与 tool_event_cb 同签名，保留旧名兼容已有宿主代码
+pub type ToolNetifWatchCb = ToolEventCb;
+
+static WATCHERS: Mutex<HandleRegistry<NetIfWatcher>> = Mutex::new(HandleRegistry::new());
+
+// 其它线程在持锁时 panic 不应让后续调用永久失败
+fn watchers() -> MutexGuard<'static, HandleRegistry<NetIfWatcher>> {
+    match WATCHERS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_netif_watch_start(
+    cb: ToolNetifWatchCb,
+    user_data: *mut c_void,
+    out_handle: *mut u64,
+) -> i32 {
+    let Some(cb) = EventCallback::new(cb, user_data) else {
+        return ErrorCode::InvalidArgument.as_i32();
+    };
+    if out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    let sink: EventSink = Box::new(move |ev| cb.emit(ev));
+
+    match forgeffi_sys::netif::watch(sink) {
+        Ok(w) => {
+            forgeffi_sys::runtime::register_shutdown_hook("netif_watch", stop_all_watchers);
+            let handle = watchers().insert(w);
+            unsafe {
+                *out_handle = handle;
+            }
+            0
+        }
+        Err(e) => e.code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_netif_watch_stop(handle: u64) -> i32 {
+    // 先出锁再 stop：stop 要等回调线程退出，回调里可能再调用 watch 相关导出
+    let w = watchers().remove(handle);
+    match w {
+        Some(w) => {
+            w.stop();
+            0
+        }
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+fn stop_all_watchers() {
+    let all = watchers().drain();
+    for w in all {
+        w.stop();
+    }
+}
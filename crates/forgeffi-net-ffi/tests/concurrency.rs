@@ -0,0 +1,101 @@
+// 多线程同时调用导出函数：不崩溃、不死锁，出参始终是完整的 JSON
+use forgeffi_net_ffi::*;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Barrier;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 20;
+
+fn call_json(f: impl FnOnce(*mut *mut u8, *mut usize) -> i32) -> (i32, serde_json::Value) {
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    let rc = f(&mut ptr, &mut len);
+    assert!(!ptr.is_null(), "rc={rc} 时未写出 JSON");
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+    unsafe { tool_free(ptr, len) };
+    let v: serde_json::Value = serde_json::from_slice(&bytes).expect("出参不是合法 JSON");
+    if rc != 0 {
+        assert_eq!(v["ok"], serde_json::Value::Bool(false), "失败时应写出 ErrorResponse");
+    }
+    (rc, v)
+}
+
+#[test]
+fn list_and_cache_calls_from_many_threads() {
+    let barrier = Barrier::new(THREADS);
+    std::thread::scope(|s| {
+        for t in 0..THREADS {
+            let barrier = &barrier;
+            s.spawn(move || {
+                barrier.wait();
+                let req = br#"{"abi":1,"fields":["name","mtu"]}"#;
+                for i in 0..ROUNDS {
+                    match (t + i) % 5 {
+                        0 => tool_netif_cache_set_ttl(if i % 2 == 0 { 0 } else { 1000 }),
+                        1 => tool_netif_cache_invalidate(),
+                        2 => {
+                            call_json(|p, l| unsafe {
+                                tool_netif_list_filtered_json(req.as_ptr(), req.len(), p, l)
+                            });
+                        }
+                        3 => {
+                            call_json(|p, l| unsafe { tool_netif_backends_json(p, l) });
+                        }
+                        _ => {
+                            call_json(|p, l| unsafe { tool_netif_list_json(p, l) });
+                        }
+                    }
+                }
+            });
+        }
+    });
+    tool_netif_cache_set_ttl(0);
+}
+
+#[test]
+fn cancel_tokens_are_independent_across_threads() {
+    std::thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                for _ in 0..ROUNDS * 10 {
+                    let h = tool_cancel_token_new();
+                    assert_ne!(h, 0);
+                    assert_eq!(tool_cancel_token_cancel(h), 0);
+                    assert_eq!(tool_cancel_token_cancel(h), 0);
+                    assert_eq!(tool_cancel_token_free(h), 0);
+                    assert_ne!(tool_cancel_token_free(h), 0);
+                }
+            });
+        }
+    });
+}
+
+unsafe extern "C" fn count_event(_json: *const u8, _len: usize, user_data: *mut c_void) {
+    let n = unsafe { &*user_data.cast::<AtomicUsize>() };
+    n.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn watchers_start_and_stop_concurrently() {
+    let events = AtomicUsize::new(0);
+    // 裸指针不能跨线程传递，按地址传给各线程
+    let ud = std::ptr::from_ref(&events) as usize;
+    std::thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(move || {
+                for _ in 0..ROUNDS / 4 {
+                    let mut h = 0u64;
+                    let rc = unsafe {
+                        tool_netif_watch_start(Some(count_event), ud as *mut c_void, &mut h)
+                    };
+                    // 平台不支持 watch 时只要求返回错误码
+                    if rc == 0 {
+                        assert_eq!(tool_netif_watch_stop(h), 0);
+                        assert_ne!(tool_netif_watch_stop(h), 0);
+                    }
+                }
+            });
+        }
+    });
+}
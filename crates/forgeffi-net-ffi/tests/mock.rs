@@ -0,0 +1,704 @@
+// 通过 mock 后端跑通 init → configure → apply → list 的完整 FFI 链路，不需要任何权限
+use forgeffi_net_ffi::*;
+use std::sync::Mutex;
+
+// mock 网卡表是进程级状态，同一二进制内的用例需要串行
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn call_json(f: impl FnOnce(*mut *mut u8, *mut usize) -> i32) -> (i32, serde_json::Value) {
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    let rc = f(&mut ptr, &mut len);
+    assert!(!ptr.is_null(), "rc={rc} 时未写出 JSON");
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+    unsafe { tool_free(ptr, len) };
+    (rc, serde_json::from_slice(&bytes).expect("出参不是合法 JSON"))
+}
+
+fn use_mock(cfg: Option<&str>) {
+    let init = format!(r#"{{"abi":{},"netif_backend":"mock"}}"#, tool_netif_abi_version());
+    assert_eq!(unsafe { tool_init(init.as_ptr(), init.len()) }, 0);
+    let rc = match cfg {
+        Some(c) => unsafe { tool_netif_mock_configure_json(c.as_ptr(), c.len()) },
+        None => unsafe { tool_netif_mock_configure_json(std::ptr::null(), 0) },
+    };
+    assert_eq!(rc, 0);
+}
+
+fn apply(req: &str) -> (i32, serde_json::Value) {
+    call_json(|p, l| unsafe { tool_netif_apply_json(req.as_ptr(), req.len(), p, l) })
+}
+
+fn list() -> serde_json::Value {
+    let (rc, v) = call_json(|p, l| unsafe { tool_netif_list_json(p, l) });
+    assert_eq!(rc, 0, "{v}");
+    v
+}
+
+#[test]
+fn apply_changes_table_and_is_recorded() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+
+    let names: Vec<_> = list()["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|it| it["name"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(names, ["lo", "eth0"]);
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"set_mtu","mtu":9000},
+            {"op":"add_ip","ip":"198.51.100.7","prefix_len":24},
+            {"op":"create_dummy","name":"dummy0"}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["ok"], true, "{v}");
+
+    let items = list()["items"].clone();
+    let eth0 = items
+        .as_array()
+        .and_then(|a| a.iter().find(|it| it["name"] == "eth0"))
+        .expect("eth0");
+    assert_eq!(eth0["mtu"], 9000);
+    assert!(eth0["ipv4"].as_array().expect("ipv4").iter().any(|a| a["ip"] == "198.51.100.7"));
+    assert!(items.as_array().expect("items").iter().any(|it| it["name"] == "dummy0"));
+
+    let (rc, log) = call_json(|p, l| unsafe { tool_netif_mock_log_json(p, l) });
+    assert_eq!(rc, 0);
+    let ops: Vec<_> = log["applied"]
+        .as_array()
+        .expect("applied")
+        .iter()
+        .map(|r| r["op"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(ops, ["set_mtu", "add_ip", "create_dummy"]);
+    assert_eq!(log["applied"][0]["target"], "eth0");
+
+    let (rc, v) = call_json(|p, l| unsafe { tool_netif_backends_json(p, l) });
+    assert_eq!(rc, 0);
+    let selected: Vec<_> = v["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .filter(|it| it["selected"] == true)
+        .map(|it| it["backend"].clone())
+        .collect();
+    assert_eq!(selected, ["mock"]);
+}
+
+#[test]
+fn configured_table_and_injected_failures() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let cfg = r#"{"abi":1,"fail_ops":["set_mtu"],"interfaces":[{
+        "if_index":7,"name":"wan0","kind":"physical","admin_state":"up","flags":3,
+        "ipv4":[],"ipv6":[],"capabilities":{
+            "can_set_admin_state":true,"can_set_mtu":true,"can_add_del_ip":true,"can_set_dhcp":true,
+            "can_set_dns":true,"can_set_ipv6":true,"can_rename":true,"can_set_description":true,
+            "can_set_mac":true}
+    }]}"#;
+    use_mock(Some(cfg));
+
+    let items = list()["items"].clone();
+    assert_eq!(items.as_array().map(Vec::len), Some(1));
+    assert_eq!(items[0]["name"], "wan0");
+
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"if_index":7},"ops":[
+            {"op":"set_mtu","mtu":1400},
+            {"op":"set_admin_state","up":false}
+        ]}"#,
+    );
+    assert_eq!(v["ok"], false, "{v}");
+    assert_eq!(v["results"][0]["ok"], false);
+    assert_eq!(v["results"][1]["ok"], true);
+    assert_eq!(list()["items"][0]["admin_state"], "down");
+
+    let (_, log) = call_json(|p, l| unsafe { tool_netif_mock_log_json(p, l) });
+    assert_eq!(log["applied"][0]["ok"], false);
+    assert_eq!(log["applied"][1]["ok"], true);
+
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_mtu","mtu":1400}]}"#);
+    assert_eq!(v["ok"], false, "默认网卡表已被替换: {v}");
+}
+
+#[test]
+fn consecutive_ops_share_one_batch() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(Some(r#"{"abi":1,"fail_ops":["add_ip"]}"#));
+
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"set_mtu","mtu":1450},
+            {"op":"add_ip","ip":"198.51.100.9","prefix_len":24},
+            {"op":"set_admin_state","up":true},
+            {"op":"create_dummy","name":"dummy1"}
+        ]}"#,
+    );
+    assert_eq!(v["ok"], false, "{v}");
+    assert_eq!(v["results"][0]["ok"], true, "{v}");
+    assert_eq!(v["results"][1]["error"]["code"], "SystemError", "失败应归到对应的 op: {v}");
+    assert_eq!(v["results"][2]["ok"], true, "{v}");
+    assert_eq!(v["results"][3]["ok"], true, "{v}");
+
+    let (_, log) = call_json(|p, l| unsafe { tool_netif_mock_log_json(p, l) });
+    let applied = log["applied"].as_array().expect("applied");
+    assert_eq!(applied.len(), 4, "{log}");
+    assert!(applied[0]["batch"].is_u64(), "{log}");
+    assert_eq!(applied[1]["batch"], applied[0]["batch"], "{log}");
+    assert_eq!(applied[2]["batch"], applied[0]["batch"], "{log}");
+    assert_eq!(applied[1]["ok"], false);
+    assert!(applied[3].get("batch").is_none(), "不需要 target 的 op 单独执行: {log}");
+    assert_eq!(eth0_mtu(), 1450);
+
+    // skip_if_applied 需要逐个 op 对比状态，不合并
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"skip_if_applied":true,"ops":[
+            {"op":"set_mtu","mtu":1450},
+            {"op":"set_admin_state","up":false}
+        ]}"#,
+    );
+    assert_eq!(v["ok"], true, "{v}");
+    assert_eq!(v["results"][0]["skipped"], true, "{v}");
+    let (_, log) = call_json(|p, l| unsafe { tool_netif_mock_log_json(p, l) });
+    assert!(log["applied"][4].get("batch").is_none(), "{log}");
+}
+
+#[test]
+fn init_json_rate_limit_and_debounce() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let bad = r#"{"abi":1,"netif_rate_limit":{"max_ops_per_sec_per_iface":"x"}}"#;
+    let (rc, v) = call_json(|p, l| unsafe { tool_init_json(bad.as_ptr(), bad.len(), p, l) });
+    assert_eq!(rc, 1, "{v}");
+    assert_eq!(v["error"]["code"], "InvalidArgument", "{v}");
+
+    let init = r#"{"abi":1,"netif_backend":"mock",
+        "netif_rate_limit":{"max_ops_per_sec_per_iface":2,"debounce_ms":60000}}"#;
+    let (rc, v) = call_json(|p, l| unsafe { tool_init_json(init.as_ptr(), init.len(), p, l) });
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["log_level"], "warn", "生效配置应包含默认值: {v}");
+    assert_eq!(v["netif_rate_limit"]["max_concurrent_applies"], 0);
+    assert_eq!(unsafe { tool_netif_mock_configure_json(std::ptr::null(), 0) }, 0);
+
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"set_mtu","mtu":1400},
+            {"op":"set_mtu","mtu":1400},
+            {"op":"set_mtu","mtu":1500},
+            {"op":"set_mtu","mtu":1600}
+        ]}"#,
+    );
+    assert_eq!(v["ok"], false, "{v}");
+    assert_eq!(v["results"][0]["ok"], true);
+    assert_eq!(v["results"][1]["skipped"], true, "相同 op 应被去抖: {v}");
+    assert_eq!(v["results"][2]["ok"], true);
+    assert_eq!(v["results"][3]["error"]["code"], "Busy", "{v}");
+    assert_eq!(v["results"][3]["error"]["retryable"], true, "{v}");
+
+    let (_, log) = call_json(|p, l| unsafe { tool_netif_mock_log_json(p, l) });
+    assert_eq!(log["applied"].as_array().map(Vec::len), Some(2), "被去抖或限流的 op 不应执行: {log}");
+}
+
+#[test]
+fn config_json_reflects_last_init() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let init = r#"{"abi":1,"netif_backend":"mock","locale":"en","command_timeout_ms":15000,"powershell_engine":"pwsh"}"#;
+    assert_eq!(unsafe { tool_init(init.as_ptr(), init.len()) }, 0);
+    let (rc, v) = call_json(|p, l| unsafe { tool_config_json(p, l) });
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["locale"], "en");
+    assert_eq!(v["command_timeout_ms"], 15000);
+    assert_eq!(v["netif_backend"], "mock");
+    assert_eq!(v["powershell_engine"], "pwsh");
+
+    for bad in [r#"{"abi":1,"locale":7}"#, r#"{"abi":1,"powershell_engine":"cmd"}"#] {
+        assert_eq!(unsafe { tool_init(bad.as_ptr(), bad.len()) }, 1, "{bad}");
+    }
+    let (_, v) = call_json(|p, l| unsafe { tool_config_json(p, l) });
+    assert_eq!(v["locale"], "en", "失败的 init 不应改动生效配置: {v}");
+}
+
+#[test]
+fn errors_carry_error_id_and_follow_locale() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let req = r#"{"abi":1,"target":{"name":"nope0"},"ops":[{"op":"set_mtu","mtu":1400}]}"#;
+    for (locale, message) in [("en", "interface not found: name=nope0"), ("zh-CN", "未找到网卡 name=nope0")] {
+        let init = format!(r#"{{"abi":1,"netif_backend":"mock","locale":"{locale}"}}"#);
+        assert_eq!(unsafe { tool_init(init.as_ptr(), init.len()) }, 0);
+        assert_eq!(unsafe { tool_netif_mock_configure_json(std::ptr::null(), 0) }, 0);
+        let (rc, v) = apply(req);
+        assert_eq!(rc, 2, "{v}");
+        assert_eq!(v["error"]["error_id"], "iface_not_found_name", "{v}");
+        assert_eq!(v["error"]["params"]["name"], "nope0", "{v}");
+        assert_eq!(v["error"]["message"], message, "{v}");
+        assert!(v["error"].get("retryable").is_none() && v["error"].get("stderr").is_none(), "{v}");
+    }
+
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_mtu","mtu":0}]}"#);
+    assert_eq!(v["results"][0]["error"]["error_id"], "invalid_argument", "目录外的错误按错误码给出 id: {v}");
+}
+
+#[test]
+fn bond_lifecycle() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+
+    let (_, v) = apply(r#"{"abi":1,"target":{},"ops":[{"op":"create_bond","name":"bond0","mode":"lacp","members":["eth0","eth0"]}]}"#);
+    assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "重复成员应被拒绝: {v}");
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{},"ops":[
+            {"op":"create_bond","name":"bond0","mode":"active_backup","members":["eth0"]},
+            {"op":"add_bond_member","bond":"bond0","member":"lo"}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["ok"], true, "{v}");
+    assert!(list()["items"].as_array().expect("items").iter().any(|it| it["name"] == "bond0"));
+
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"delete_bond"}]}"#);
+    assert_eq!(v["ok"], false, "物理网卡不能按 bond 删除: {v}");
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"bond0"},"ops":[{"op":"delete_bond"}]}"#);
+    assert_eq!(v["ok"], true, "{v}");
+    assert!(!list()["items"].as_array().expect("items").iter().any(|it| it["name"] == "bond0"));
+}
+
+#[test]
+fn tunnel_lifecycle() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+
+    const PEER: &str = "QmV0YS1wZWVyLXB1YmxpYy1rZXktZm9yLXRlc3RzLTA=";
+    let find = |name: &str| {
+        list()["items"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .find(|it| it["name"] == name)
+            .cloned()
+    };
+
+    let (_, v) = apply(r#"{"abi":1,"target":{},"ops":[{"op":"create_wireguard","name":"wg0","private_key":"short"}]}"#);
+    assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "非法私钥应被拒绝: {v}");
+    let (_, v) = apply(r#"{"abi":1,"target":{},"ops":[{"op":"create_ip_tunnel","name":"gre1","kind":"wireguard","remote":"198.51.100.1"}]}"#);
+    assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "CreateIpTunnel 不接受 wireguard: {v}");
+
+    let (rc, v) = apply(&format!(
+        r#"{{"abi":1,"target":{{}},"ops":[
+            {{"op":"create_wireguard","name":"wg0","private_key":"cGhhbnRvbS1wcml2YXRlLWtleS1ub3QtcmVhbC0wMDA=","listen_port":51820,
+              "peers":[{{"public_key":"{PEER}","endpoint":"203.0.113.7:51820","allowed_ips":["10.66.66.0/24"]}}]}},
+            {{"op":"create_ip_tunnel","name":"gre1","kind":"gre","remote":"198.51.100.1","local":"192.0.2.10","ttl":64}}
+        ]}}"#
+    ));
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["ok"], true, "{v}");
+    let wg = find("wg0").expect("wg0");
+    assert_eq!(wg["kind"], "tunnel", "{wg}");
+    assert_eq!(wg["tunnel"]["kind"], "wireguard", "{wg}");
+    assert_eq!(wg["tunnel"]["wireguard"]["listen_port"], 51820, "{wg}");
+    assert_eq!(wg["tunnel"]["wireguard"]["peers"][0]["allowed_ips"][0], "10.66.66.0/24", "{wg}");
+    assert!(!wg.to_string().contains("private"), "list 不能带出私钥: {wg}");
+    let gre = find("gre1").expect("gre1");
+    assert_eq!(gre["tunnel"]["remote"], "198.51.100.1", "{gre}");
+    assert_eq!(gre["tunnel"]["ttl"], 64, "{gre}");
+
+    let (_, v) = apply(&format!(
+        r#"{{"abi":1,"target":{{"name":"wg0"}},"ops":[{{"op":"set_wireguard_peer","peer":{{"public_key":"{PEER}","allowed_ips":["10.77.0.0/16"]}}}}]}}"#
+    ));
+    assert_eq!(v["ok"], true, "{v}");
+    let peers = find("wg0").expect("wg0")["tunnel"]["wireguard"]["peers"].clone();
+    assert_eq!(peers.as_array().map(Vec::len), Some(1), "同一公钥应覆盖而不是追加: {peers}");
+    assert_eq!(peers[0]["allowed_ips"][0], "10.77.0.0/16", "{peers}");
+    let (_, v) = apply(&format!(
+        r#"{{"abi":1,"target":{{"name":"wg0"}},"ops":[{{"op":"remove_wireguard_peer","public_key":"{PEER}"}}]}}"#
+    ));
+    assert_eq!(v["ok"], true, "{v}");
+    assert_eq!(find("wg0").expect("wg0")["tunnel"]["wireguard"]["peers"], serde_json::json!([]));
+
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"delete_tunnel"}]}"#);
+    assert_eq!(v["ok"], false, "非隧道网卡不能按 tunnel 删除: {v}");
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"wg0"},"ops":[{"op":"delete_tunnel"}]}"#);
+    assert_eq!(v["ok"], true, "{v}");
+    assert!(find("wg0").is_none());
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"gre1"},"ops":[{"op":"delete_tunnel"}]}"#);
+    assert_eq!(v["ok"], true, "{v}");
+}
+
+#[test]
+fn routes_and_policy_rules() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+    let eth0 = list()["items"]
+        .as_array()
+        .and_then(|a| a.iter().find(|it| it["name"] == "eth0").cloned())
+        .expect("eth0");
+    assert_eq!(eth0["capabilities"]["can_manage_routes"], true, "{eth0}");
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"add_route","route":{"dest":"203.0.113.0/24","gateway":"192.0.2.1","table":100}},
+            {"op":"add_route","route":{"dest":"198.51.100.10/32","metric":50}},
+            {"op":"add_route","route":{"dest":"203.0.113.0/24","gateway":"192.0.2.1","table":100}}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["results"][0]["ok"], true, "{v}");
+    assert_eq!(v["results"][1]["ok"], true, "{v}");
+    assert_eq!(v["results"][2]["error"]["code"], "SystemError", "重复添加应失败: {v}");
+
+    for (route, why) in [
+        (r#"{"dest":"203.0.113.5/24"}"#, "主机位不为 0"),
+        (r#"{"dest":"203.0.113.0/33"}"#, "prefix_len 超出范围"),
+        (r#"{"dest":"2001:db8::/32","gateway":"192.0.2.1"}"#, "gateway 地址族不一致"),
+        (r#"{"dest":"203.0.113.0/24","table":0}"#, "table 为 0"),
+    ] {
+        let (_, v) = apply(&format!(
+            r#"{{"abi":1,"target":{{"name":"eth0"}},"ops":[{{"op":"add_route","route":{route}}}]}}"#
+        ));
+        assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "{why}: {v}");
+    }
+
+    // 删除时省略的 gateway 不参与匹配
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"del_route","route":{"dest":"203.0.113.0/24","table":100}},
+            {"op":"del_route","route":{"dest":"203.0.113.0/24","table":100}}
+        ]}"#,
+    );
+    assert_eq!(v["results"][0]["ok"], true, "{v}");
+    assert_eq!(v["results"][1]["error"]["code"], "SystemError", "{v}");
+
+    let rule = r#"{"table":100,"priority":1000,"from":"192.0.2.0/24","iif":"eth0"}"#;
+    let (rc, v) = apply(&format!(
+        r#"{{"abi":1,"target":{{}},"ops":[
+            {{"op":"add_routing_rule","rule":{rule}}},
+            {{"op":"add_routing_rule","rule":{{"table":200,"fwmark":2,"ipv6":true}}}},
+            {{"op":"del_routing_rule","rule":{rule}}},
+            {{"op":"del_routing_rule","rule":{rule}}}
+        ]}}"#
+    ));
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["results"][0]["ok"], true, "{v}");
+    assert_eq!(v["results"][1]["ok"], true, "{v}");
+    assert_eq!(v["results"][2]["ok"], true, "{v}");
+    assert_eq!(v["results"][3]["error"]["code"], "SystemError", "规则已删除: {v}");
+
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{},"ops":[
+            {"op":"add_routing_rule","rule":{"table":100,"from":"192.0.2.0/24","ipv6":true}},
+            {"op":"add_routing_rule","rule":{"table":100,"from":"192.0.2.0/24","to":"2001:db8::/32"}}
+        ]}"#,
+    );
+    assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "{v}");
+    assert_eq!(v["results"][1]["error"]["code"], "InvalidArgument", "{v}");
+}
+
+fn qos_of(name: &str) -> serde_json::Value {
+    let (rc, v) = call_json(|p, l| unsafe { tool_netif_qos_json(p, l) });
+    assert_eq!(rc, 0, "{v}");
+    v["items"]
+        .as_array()
+        .and_then(|a| a.iter().find(|it| it["name"] == name).cloned())
+        .unwrap_or_else(|| panic!("qos 中没有 {name}: {v}"))
+}
+
+#[test]
+fn bandwidth_limit_and_qos_read() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+    assert_eq!(qos_of("eth0")["entries"], serde_json::json!([]));
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_bandwidth_limit","egress_mbps":100,"ingress_mbps":50}]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    let q = qos_of("eth0");
+    assert_eq!(q["egress_mbps"], 100, "{q}");
+    assert_eq!(q["ingress_mbps"], 50, "{q}");
+    assert_eq!(q["entries"][0]["kind"], "htb", "{q}");
+    assert_eq!(q["entries"][1]["rate_bps"], 50_000_000u64, "{q}");
+
+    // 省略的方向取消限速
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_bandwidth_limit","ingress_mbps":20}]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    let q = qos_of("eth0");
+    assert!(q.get("egress_mbps").is_none(), "{q}");
+    assert_eq!(q["ingress_mbps"], 20, "{q}");
+    assert_eq!(q["entries"].as_array().map(Vec::len), Some(1), "{q}");
+
+    let (_, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_bandwidth_limit","egress_mbps":0}]}"#,
+    );
+    assert_eq!(v["results"][0]["error"]["code"], "InvalidArgument", "{v}");
+
+    let (rc, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_bandwidth_limit"}]}"#);
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(qos_of("eth0")["entries"], serde_json::json!([]));
+}
+
+fn eth0_mtu() -> serde_json::Value {
+    list()["items"]
+        .as_array()
+        .and_then(|a| a.iter().find(|it| it["name"] == "eth0"))
+        .expect("eth0")["mtu"]
+        .clone()
+}
+
+#[test]
+fn commit_confirm_reverts_unless_confirmed() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let dir = std::env::temp_dir().join(format!("forgeffi-confirm-test-{}", std::process::id()));
+    let init = serde_json::json!({"abi": 1, "netif_backend": "mock", "netif_confirm_dir": dir}).to_string();
+    assert_eq!(unsafe { tool_init(init.as_ptr(), init.len()) }, 0);
+    assert_eq!(unsafe { tool_netif_mock_configure_json(std::ptr::null(), 0) }, 0);
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":1,"ops":[{"op":"create_dummy","name":"d0"}]}"#,
+    );
+    assert_eq!(rc, 1, "无法按快照撤销的 op 应被拒绝: {v}");
+
+    let (rc, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":1,"ops":[{"op":"set_mtu","mtu":1400}]}"#);
+    assert_eq!(rc, 0, "{v}");
+    let token = v["confirm_token"].as_str().expect("confirm_token").to_string();
+    assert!(v["confirm_deadline_ms"].as_u64().is_some(), "{v}");
+    assert!(dir.join(format!("{token}.json")).exists(), "待确认记录应落盘");
+    assert_eq!(unsafe { tool_netif_confirm(token.as_ptr(), token.len()) }, 0);
+    assert_eq!(unsafe { tool_netif_confirm(token.as_ptr(), token.len()) }, 2, "重复确认应返回 NotFound");
+    assert!(!dir.join(format!("{token}.json")).exists(), "确认后记录应删除");
+    std::thread::sleep(std::time::Duration::from_millis(1300));
+    assert_eq!(eth0_mtu(), 1400, "已确认的变更不应回滚");
+
+    let (rc, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":1,"ops":[{"op":"set_mtu","mtu":1280}]}"#);
+    assert_eq!(rc, 0, "{v}");
+    let token = v["confirm_token"].as_str().expect("confirm_token").to_string();
+    let (_, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":1,"ops":[{"op":"set_mtu","mtu":1300}]}"#);
+    assert_eq!(v["error"]["code"], "Busy", "同一网卡有待确认变更时应拒绝: {v}");
+    assert_eq!(eth0_mtu(), 1280);
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while eth0_mtu() != 1400 && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert_eq!(eth0_mtu(), 1400, "超时未确认应回滚到执行前的状态");
+    assert_eq!(unsafe { tool_netif_confirm(token.as_ptr(), token.len()) }, 2);
+    assert!(!dir.join(format!("{token}.json")).exists(), "回滚成功后记录应删除");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// init 会以当前权限按记录回滚：不合规目录里的记录、他人放入的记录都不能被接管
+#[cfg(unix)]
+#[test]
+fn commit_confirm_ignores_foreign_records() {
+    use std::os::unix::fs::{chown, PermissionsExt};
+    use std::path::Path;
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    let base = std::env::temp_dir().join(format!("forgeffi-confirm-foreign-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&base);
+    let init_with = |dir: &Path| {
+        let init = serde_json::json!({"abi": 1, "netif_backend": "mock", "netif_confirm_dir": dir}).to_string();
+        assert_eq!(unsafe { tool_init(init.as_ptr(), init.len()) }, 0);
+    };
+
+    let own = base.join("own");
+    init_with(&own);
+    assert_eq!(unsafe { tool_netif_mock_configure_json(std::ptr::null(), 0) }, 0);
+    let (rc, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":60,"ops":[{"op":"set_mtu","mtu":1400}]}"#);
+    assert_eq!(rc, 0, "{v}");
+    let token = v["confirm_token"].as_str().expect("confirm_token").to_string();
+    let file = format!("{token}.json");
+    assert_eq!(
+        std::fs::metadata(&own).map(|m| m.permissions().mode() & 0o777).ok(),
+        Some(0o700),
+        "记录目录应以 0700 创建"
+    );
+    assert_eq!(
+        std::fs::metadata(own.join(&file)).map(|m| m.permissions().mode() & 0o777).ok(),
+        Some(0o600),
+        "记录应以 0600 创建"
+    );
+    // 改成已过期，被接管时会立即回滚到 mtu 1500
+    let mut record: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(own.join(&file)).expect("记录")).expect("记录不是合法 JSON");
+    record["deadline_ms"] = 0.into();
+    assert_eq!(unsafe { tool_netif_confirm(token.as_ptr(), token.len()) }, 0);
+    let plant = |dir: &Path| {
+        std::fs::write(dir.join(&file), record.to_string()).expect("写入记录");
+    };
+    let assert_ignored = |dir: &Path, what: &str| {
+        init_with(dir);
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        assert_eq!(eth0_mtu(), 1400, "{what}里的记录不应被接管");
+        assert!(dir.join(&file).exists(), "{what}里的记录不应被删除");
+    };
+
+    let open = base.join("open");
+    std::fs::create_dir_all(&open).expect("创建目录");
+    std::fs::set_permissions(&open, std::fs::Permissions::from_mode(0o777)).expect("chmod");
+    plant(&open);
+    assert_ignored(&open, "其他用户可写的目录");
+
+    let foreign = base.join("foreign");
+    std::fs::create_dir_all(&foreign).expect("创建目录");
+    std::fs::set_permissions(&foreign, std::fs::Permissions::from_mode(0o700)).expect("chmod");
+    plant(&foreign);
+    // 构造他人所有的目录和文件需要 root
+    if chown(&foreign, Some(65534), Some(65534)).is_ok() {
+        assert_ignored(&foreign, "他人所有的目录");
+        plant(&own);
+        chown(own.join(&file), Some(65534), Some(65534)).expect("chown");
+        assert_ignored(&own, "他人所有的文件");
+        std::fs::remove_file(own.join(&file)).expect("删除记录");
+    }
+
+    plant(&own);
+    init_with(&own);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while eth0_mtu() != 1500 && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert_eq!(eth0_mtu(), 1500, "本用户目录里的过期记录应被接管并回滚");
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+#[test]
+fn op_scope_is_accepted_and_validated() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"skip_if_applied":true,"ops":[
+            {"op":"set_mtu","mtu":1500,"scope":"runtime"},
+            {"op":"set_mtu","mtu":1500,"scope":"persistent"}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["results"][0]["skipped"], true, "{v}");
+    assert_ne!(v["results"][1]["skipped"], true, "只写配置的 op 不能按运行状态跳过: {v}");
+
+    let (rc, v) = apply(r#"{"abi":1,"target":{"name":"eth0"},"ops":[{"op":"set_mtu","mtu":1400,"scope":"reboot"}]}"#);
+    assert_eq!(rc, 1, "未知 scope 应被拒绝: {v}");
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"confirm_timeout_secs":1,"ops":[{"op":"set_mtu","mtu":1400,"scope":"persistent"}]}"#,
+    );
+    assert_eq!(rc, 1, "{v}");
+    assert_eq!(eth0_mtu(), 1500);
+}
+
+static TRACED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+unsafe extern "C" fn on_span(json_ptr: *const u8, json_len: usize, _user_data: *mut std::ffi::c_void) {
+    let bytes = unsafe { std::slice::from_raw_parts(json_ptr, json_len) };
+    TRACED
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .push(String::from_utf8_lossy(bytes).into_owned());
+}
+
+#[test]
+fn apply_spans_are_dumped_and_streamed() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+    TRACED.lock().unwrap_or_else(|p| p.into_inner()).clear();
+    let mut handle = 0u64;
+    assert_eq!(unsafe { tool_trace_subscribe(Some(on_span), std::ptr::null_mut(), &mut handle) }, 0);
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"set_mtu","mtu":1280},
+            {"op":"add_ip","ip":"not-an-ip","prefix_len":24}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+    assert_eq!(v["ok"], false, "{v}");
+
+    let (rc, dump) = call_json(|p, l| unsafe { tool_trace_dump_json(p, l) });
+    assert_eq!(rc, 0, "{dump}");
+    let spans = dump["spans"].as_array().expect("spans");
+    let root = spans.iter().rev().find(|s| s["name"] == "netif.apply").expect("apply span");
+    assert_eq!(root["fields"]["target"], "eth0");
+    assert_eq!(root["ok"], false);
+    let ops: Vec<_> = spans.iter().filter(|s| s["parent"] == root["id"]).collect();
+    assert_eq!(ops.len(), 2, "{dump}");
+    assert_eq!(ops[0]["fields"]["op"], "set_mtu");
+    assert_eq!(ops[0]["ok"], true);
+    assert_eq!(ops[0]["backend"], "mock");
+    assert_eq!(ops[1]["ok"], false);
+    assert_eq!(ops[1]["error"], "InvalidArgument");
+    assert!(ops.iter().all(|s| s["duration_us"].is_u64()));
+
+    // 订阅者在自己的线程上按结束顺序收到同样的 span
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while TRACED.lock().unwrap_or_else(|p| p.into_inner()).len() < 3 && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(tool_trace_unsubscribe(handle), 0);
+    assert_eq!(tool_trace_unsubscribe(handle), 2);
+    let got: Vec<serde_json::Value> = TRACED
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .iter()
+        .map(|s| serde_json::from_str(s).expect("span JSON"))
+        .collect();
+    let names: Vec<_> = got.iter().map(|s| s["name"].as_str().unwrap_or_default()).collect();
+    assert_eq!(names, ["netif.op", "netif.op", "netif.apply"]);
+    assert_eq!(got[2]["id"], root["id"]);
+}
+
+fn op_metrics(m: &serde_json::Value, op: &str) -> serde_json::Value {
+    m["operations"]
+        .as_array()
+        .expect("operations")
+        .iter()
+        .find(|o| o["span"] == "netif.op" && o["op"] == op)
+        .cloned()
+        .unwrap_or(serde_json::Value::Null)
+}
+
+#[test]
+fn metrics_count_ops_by_result_and_error() {
+    let _g = SERIAL.lock().unwrap_or_else(|p| p.into_inner());
+    use_mock(None);
+    let (rc, before) = call_json(|p, l| unsafe { tool_metrics_json(p, l) });
+    assert_eq!(rc, 0, "{before}");
+    let count = |m: &serde_json::Value, op: &str, key: &str| op_metrics(m, op)[key].as_u64().unwrap_or(0);
+
+    let (rc, v) = apply(
+        r#"{"abi":1,"target":{"name":"eth0"},"ops":[
+            {"op":"set_mtu","mtu":1300},
+            {"op":"add_ip","ip":"not-an-ip","prefix_len":24}
+        ]}"#,
+    );
+    assert_eq!(rc, 0, "{v}");
+
+    let (rc, after) = call_json(|p, l| unsafe { tool_metrics_json(p, l) });
+    assert_eq!(rc, 0, "{after}");
+    assert_eq!(count(&after, "set_mtu", "ok"), count(&before, "set_mtu", "ok") + 1);
+    assert_eq!(count(&after, "add_ip", "failed"), count(&before, "add_ip", "failed") + 1);
+    let add_ip = op_metrics(&after, "add_ip");
+    assert!(add_ip["errors"]["InvalidArgument"].as_u64().unwrap_or(0) >= 1, "{after}");
+    let buckets = after["latency_buckets_ms"].as_array().expect("buckets").len();
+    let counts = add_ip["latency_counts"].as_array().expect("latency_counts");
+    assert_eq!(counts.len(), buckets + 1);
+    assert_eq!(counts.iter().filter_map(|c| c.as_u64()).sum::<u64>(), add_ip["total"].as_u64().unwrap());
+
+    let mut ptr = std::ptr::null_mut();
+    let mut len = 0usize;
+    assert_eq!(unsafe { tool_metrics_prometheus(&mut ptr, &mut len) }, 0);
+    let text = String::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec()).expect("UTF-8");
+    unsafe { tool_free(ptr, len) };
+    assert!(text.contains("# TYPE forgeffi_operations_total counter"), "{text}");
+    assert!(
+        text.contains(r#"forgeffi_operation_errors_total{span="netif.op",op="add_ip",code="InvalidArgument"}"#),
+        "{text}"
+    );
+    assert!(
+        text.contains(r#"forgeffi_operation_duration_seconds_bucket{span="netif.op",op="set_mtu",le="+Inf"}"#),
+        "{text}"
+    );
+}
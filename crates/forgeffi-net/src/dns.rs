@@ -0,0 +1,235 @@
+use forgeffi_base::{DnsAnswer, DnsRecordType, DnsResolveRequest, DnsResolveResponse, ForgeFfiError, ABI_VERSION};
+use forgeffi_protocol::{
+    decode_request, encode_json, parse_dns_server, validate_dns_name, validate_resolve_request,
+    DNS_DEFAULT_TIMEOUT_MS,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+#[cfg(not(target_family = "wasm"))]
+use forgeffi_base::ErrorCode;
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::proto::error::ProtoErrorKind;
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::proto::op::ResponseCode;
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::proto::rr::{RData, Record, RecordType};
+#[cfg(not(target_family = "wasm"))]
+use hickory_resolver::{Name, Resolver};
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+
+pub fn resolve_request(req: &DnsResolveRequest) -> Result<DnsResolveResponse, ForgeFfiError> {
+    validate_resolve_request(req)?;
+    let server = req.server.as_deref().map(parse_dns_server).transpose()?;
+    let timeout = Duration::from_millis(req.timeout_ms.unwrap_or(DNS_DEFAULT_TIMEOUT_MS));
+    let (server, answers) = resolve(&req.name, req.record_type, server, timeout)?;
+    Ok(DnsResolveResponse {
+        abi: ABI_VERSION,
+        name: req.name.clone(),
+        record_type: req.record_type,
+        server: server.to_string(),
+        answers,
+    })
+}
+
+pub fn resolve_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: DnsResolveRequest = decode_request(req_json)?;
+    encode_json("dns 解析响应", &resolve_request(&req)?)
+}
+
+// wasm 目标没有可用的阻塞 socket，DNS 解析应由宿主完成
+#[cfg(target_family = "wasm")]
+pub fn resolve(
+    name: &str,
+    _record_type: DnsRecordType,
+    _server: Option<SocketAddr>,
+    _timeout: Duration,
+) -> Result<(SocketAddr, Vec<DnsAnswer>), ForgeFfiError> {
+    validate_dns_name(name)?;
+    Err(ForgeFfiError::unsupported("wasm 目标不支持 DNS 解析"))
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn resolve(
+    name: &str,
+    record_type: DnsRecordType,
+    server: Option<SocketAddr>,
+    timeout: Duration,
+) -> Result<(SocketAddr, Vec<DnsAnswer>), ForgeFfiError> {
+    validate_dns_name(name)?;
+    let servers = match server {
+        Some(s) => vec![s],
+        None => system_servers()?,
+    };
+    let deadline = Instant::now() + timeout;
+    let mut last = ForgeFfiError::timeout(format!("解析 {name} 超时"));
+
+    // 多个服务器由这里依次尝试而不是交给 hickory 轮询，这样才能报告实际应答的服务器；
+    // 共用同一个超时预算，NXDOMAIN 等权威结果不再重试
+    for server in servers {
+        let Some(remaining) = deadline
+            .checked_duration_since(Instant::now())
+            .filter(|d| !d.is_zero())
+        else {
+            break;
+        };
+        match query(server, name, record_type, remaining) {
+            Ok(answers) => return Ok((server, answers)),
+            Err(e) if e.code == ErrorCode::NotFound => return Err(e),
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+// 只向一个服务器查询：UDP 截断时由 hickory 改用 TCP 重试，事务 ID 由 hickory 随机生成
+#[cfg(not(target_family = "wasm"))]
+fn query(
+    server: SocketAddr,
+    name: &str,
+    record_type: DnsRecordType,
+    timeout: Duration,
+) -> Result<Vec<DnsAnswer>, ForgeFfiError> {
+    let group = NameServerConfigGroup::from_ips_clear(&[server.ip()], server.port(), true);
+    let config = ResolverConfig::from_parts(None, Vec::new(), group);
+    let mut opts = ResolverOpts::default();
+    opts.timeout = timeout;
+    opts.attempts = 1;
+    opts.cache_size = 0;
+    opts.use_hosts_file = false;
+    opts.edns0 = true;
+    // 保留 CNAME 链，与所查类型的记录一起返回
+    opts.preserve_intermediates = true;
+    let resolver = Resolver::new(config, opts)
+        .map_err(|e| ForgeFfiError::system_error(format!("创建 DNS 解析器失败: {e}")))?;
+
+    // 以 FQDN 查询，不追加搜索域
+    let fqdn = format!("{}.", name.trim_end_matches('.'));
+    let wanted = record_type_of(record_type);
+    match resolver.lookup(fqdn.as_str(), wanted) {
+        Ok(lookup) => Ok(lookup
+            .record_iter()
+            .filter(|r| r.record_type() == wanted || r.record_type() == RecordType::CNAME)
+            .filter_map(to_answer)
+            .collect()),
+        Err(e) => map_resolve_error(server, name, &e),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn to_answer(r: &Record) -> Option<DnsAnswer> {
+    let mut a = DnsAnswer {
+        name: name_str(r.name()),
+        record_type: DnsRecordType::Cname,
+        ttl: r.ttl(),
+        address: None,
+        text: None,
+        target: None,
+        priority: None,
+        weight: None,
+        port: None,
+    };
+    match r.data()? {
+        RData::A(ip) => {
+            a.record_type = DnsRecordType::A;
+            a.address = Some(ip.0.to_string());
+        }
+        RData::AAAA(ip) => {
+            a.record_type = DnsRecordType::Aaaa;
+            a.address = Some(ip.0.to_string());
+        }
+        // TXT 记录由若干长度前缀字符串组成，按 RFC 7208 的习惯直接拼接
+        RData::TXT(txt) => {
+            a.record_type = DnsRecordType::Txt;
+            let bytes: Vec<u8> = txt.txt_data().iter().flat_map(|s| s.iter().copied()).collect();
+            a.text = Some(String::from_utf8_lossy(&bytes).into_owned());
+        }
+        RData::MX(mx) => {
+            a.record_type = DnsRecordType::Mx;
+            a.priority = Some(mx.preference());
+            a.target = Some(name_str(mx.exchange()));
+        }
+        RData::SRV(srv) => {
+            a.record_type = DnsRecordType::Srv;
+            a.priority = Some(srv.priority());
+            a.weight = Some(srv.weight());
+            a.port = Some(srv.port());
+            a.target = Some(name_str(srv.target()));
+        }
+        RData::CNAME(cname) => {
+            a.target = Some(name_str(&cname.0));
+        }
+        _ => return None,
+    }
+    Some(a)
+}
+
+// 与请求里的写法一致，不带末尾的点；根域名写作 "."
+#[cfg(not(target_family = "wasm"))]
+fn name_str(n: &Name) -> String {
+    if n.is_root() {
+        return ".".to_string();
+    }
+    n.to_string().trim_end_matches('.').to_string()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn record_type_of(t: DnsRecordType) -> RecordType {
+    match t {
+        DnsRecordType::A => RecordType::A,
+        DnsRecordType::Aaaa => RecordType::AAAA,
+        DnsRecordType::Txt => RecordType::TXT,
+        DnsRecordType::Srv => RecordType::SRV,
+        DnsRecordType::Mx => RecordType::MX,
+        DnsRecordType::Cname => RecordType::CNAME,
+    }
+}
+
+// NOERROR 但没有所查类型的记录时返回空列表，与服务器的应答一致
+#[cfg(not(target_family = "wasm"))]
+fn map_resolve_error(server: SocketAddr, name: &str, e: &ResolveError) -> Result<Vec<DnsAnswer>, ForgeFfiError> {
+    let err = match e.kind() {
+        ResolveErrorKind::NoRecordsFound { response_code, .. } => match *response_code {
+            ResponseCode::NoError => return Ok(Vec::new()),
+            ResponseCode::NXDomain => ForgeFfiError::not_found(format!("域名不存在: {name}")),
+            ResponseCode::ServFail => {
+                ForgeFfiError::system_error(format!("DNS 服务器解析失败（SERVFAIL）: {name}"))
+            }
+            ResponseCode::Refused => {
+                ForgeFfiError::permission_denied(format!("DNS 服务器拒绝查询（REFUSED）: {name}"))
+            }
+            rcode => ForgeFfiError::system_error(format!("DNS 查询失败 rcode={rcode}: {name}")),
+        },
+        ResolveErrorKind::Timeout => ForgeFfiError::timeout(format!("查询 DNS 服务器 {server} 超时: {name}")),
+        ResolveErrorKind::Proto(p) if matches!(p.kind(), ProtoErrorKind::Timeout) => {
+            ForgeFfiError::timeout(format!("查询 DNS 服务器 {server} 超时: {name}"))
+        }
+        ResolveErrorKind::Io(io) if io.kind() == std::io::ErrorKind::PermissionDenied => {
+            ForgeFfiError::permission_denied(format!("查询 DNS 服务器 {server} 失败: {io}"))
+        }
+        _ => ForgeFfiError::system_error(format!("查询 DNS 服务器 {server} 失败: {e}")),
+    };
+    Err(err)
+}
+
+// 系统配置由 hickory 读取：Unix 上解析 /etc/resolv.conf，Windows 上通过 IP Helper API 读取各网卡的 DNS 服务器
+#[cfg(not(target_family = "wasm"))]
+fn system_servers() -> Result<Vec<SocketAddr>, ForgeFfiError> {
+    let (config, _) = hickory_resolver::system_conf::read_system_conf()
+        .map_err(|e| ForgeFfiError::not_found(format!("读取系统 DNS 配置失败: {e}")))?;
+    let mut servers: Vec<SocketAddr> = Vec::new();
+    // 每个服务器在配置里同时有 UDP 和 TCP 两项
+    for ns in config.name_servers() {
+        if !servers.contains(&ns.socket_addr) {
+            servers.push(ns.socket_addr);
+        }
+    }
+    if servers.is_empty() {
+        return Err(ForgeFfiError::not_found("未找到系统 DNS 服务器".to_string()));
+    }
+    Ok(servers)
+}
@@ -0,0 +1,164 @@
+use forgeffi_base::{ForgeFfiError, HttpProbeRequest, HttpProbeResponse};
+use forgeffi_protocol::{decode_request, encode_json, validate_http_probe_request};
+#[cfg(not(target_family = "wasm"))]
+use forgeffi_base::ABI_VERSION;
+#[cfg(not(target_family = "wasm"))]
+use forgeffi_protocol::{HTTP_DEFAULT_MAX_BODY_BYTES, PROBE_DEFAULT_TIMEOUT_MS};
+#[cfg(not(target_family = "wasm"))]
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+#[cfg(not(target_family = "wasm"))]
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+#[cfg(not(target_family = "wasm"))]
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+#[cfg(not(target_family = "wasm"))]
+use rustls::{DigitallySignedStruct, SignatureScheme};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{self, Read};
+#[cfg(not(target_family = "wasm"))]
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::time::{Duration, Instant};
+
+pub fn http_probe(req: &HttpProbeRequest) -> Result<HttpProbeResponse, ForgeFfiError> {
+    validate_http_probe_request(req)?;
+    send_request(req)
+}
+
+// wasm 目标没有可用的阻塞 socket，HTTP 请求应由宿主（浏览器 fetch / WASI http）发起
+#[cfg(target_family = "wasm")]
+fn send_request(_req: &HttpProbeRequest) -> Result<HttpProbeResponse, ForgeFfiError> {
+    Err(ForgeFfiError::unsupported("wasm 目标不支持 HTTP 探测"))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn send_request(req: &HttpProbeRequest) -> Result<HttpProbeResponse, ForgeFfiError> {
+    let timeout = Duration::from_millis(req.timeout_ms.unwrap_or(PROBE_DEFAULT_TIMEOUT_MS));
+    let max_body = req.max_body_bytes.unwrap_or(HTTP_DEFAULT_MAX_BODY_BYTES);
+
+    let mut builder = ureq::AgentBuilder::new().timeout(timeout);
+    if req.verify_tls == Some(false) {
+        builder = builder.tls_config(insecure_tls_config()?);
+    }
+    let agent = builder.build();
+    let mut call = agent.request(req.method.as_deref().unwrap_or("GET"), &req.url);
+    for (name, value) in &req.headers {
+        call = call.set(name, value);
+    }
+
+    let started = Instant::now();
+    let resp = match call.call() {
+        Ok(r) => r,
+        Err(ureq::Error::Status(_, r)) => r,
+        Err(ureq::Error::Transport(t)) => return Err(map_transport_error(&req.url, t)),
+    };
+    let latency_us = u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX);
+
+    let url = resp.get_url().to_string();
+    let status = resp.status();
+    let status_text = resp.status_text().to_string();
+    let content_type = resp.header("content-type").map(str::to_string);
+    // 多读 1 字节用于判断是否截断
+    let mut body = Vec::new();
+    resp.into_reader()
+        .take(u64::from(max_body) + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| map_io_error(&req.url, e))?;
+    let body_truncated = body.len() > max_body as usize;
+    body.truncate(max_body as usize);
+
+    Ok(HttpProbeResponse {
+        abi: ABI_VERSION,
+        url,
+        status,
+        status_text,
+        content_type,
+        latency_us,
+        body: String::from_utf8_lossy(&body).into_owned(),
+        body_truncated,
+    })
+}
+
+pub fn http_probe_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: HttpProbeRequest = decode_request(req_json)?;
+    encode_json("http 探测响应", &http_probe(&req)?)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn map_transport_error(url: &str, t: ureq::Transport) -> ForgeFfiError {
+    let msg = format!("请求 {url} 失败: {t}");
+    let io_kind = std::error::Error::source(&t)
+        .and_then(|e| e.downcast_ref::<io::Error>())
+        .map(io::Error::kind);
+    if matches!(io_kind, Some(io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock)) {
+        return ForgeFfiError::timeout(msg);
+    }
+    match t.kind() {
+        ureq::ErrorKind::Dns => ForgeFfiError::not_found(msg),
+        ureq::ErrorKind::InvalidUrl | ureq::ErrorKind::UnknownScheme => {
+            ForgeFfiError::invalid_argument(msg)
+        }
+        _ => ForgeFfiError::system_error(msg),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn map_io_error(url: &str, e: io::Error) -> ForgeFfiError {
+    let msg = format!("读取 {url} 响应失败: {e}");
+    match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => ForgeFfiError::timeout(msg),
+        _ => ForgeFfiError::system_error(msg),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn insecure_tls_config() -> Result<Arc<rustls::ClientConfig>, ForgeFfiError> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| ForgeFfiError::system_error(format!("初始化 TLS 配置失败: {e}")))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(NoVerify(provider)))
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+#[cfg(not(target_family = "wasm"))]
+// 跳过证书链与主机名校验，但仍校验握手签名，保证对端持有证书私钥
+#[derive(Debug)]
+struct NoVerify(Arc<CryptoProvider>);
+
+#[cfg(not(target_family = "wasm"))]
+impl ServerCertVerifier for NoVerify {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
@@ -1,2 +1,6 @@
 #![forbid(unsafe_code)]
 
+pub mod dns;
+pub mod http;
+pub mod ping;
+pub mod probe;
@@ -0,0 +1,101 @@
+use forgeffi_base::{
+    ForgeFfiError, PingMethod, PingReply, PingRequest, PingResponse, ABI_VERSION,
+};
+use forgeffi_protocol::{
+    decode_request, encode_json, validate_ping_request, PING_DEFAULT_COUNT,
+    PING_DEFAULT_INTERVAL_MS, PING_DEFAULT_PAYLOAD_SIZE, PING_DEFAULT_TIMEOUT_MS,
+};
+use std::net::IpAddr;
+use std::time::Duration;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod socket;
+mod system;
+
+#[derive(Clone, Debug)]
+struct PingOptions {
+    count: u32,
+    #[cfg_attr(target_os = "windows", allow(dead_code))]
+    interval: Duration,
+    timeout: Duration,
+    payload_size: u16,
+}
+
+pub fn ping(req: &PingRequest) -> Result<PingResponse, ForgeFfiError> {
+    validate_ping_request(req)?;
+    let opts = PingOptions {
+        count: req.count.unwrap_or(PING_DEFAULT_COUNT),
+        interval: Duration::from_millis(req.interval_ms.unwrap_or(PING_DEFAULT_INTERVAL_MS)),
+        timeout: Duration::from_millis(req.timeout_ms.unwrap_or(PING_DEFAULT_TIMEOUT_MS)),
+        payload_size: req.payload_size.unwrap_or(PING_DEFAULT_PAYLOAD_SIZE),
+    };
+    let addr = crate::probe::resolve(&req.host, 0)?[0].ip();
+
+    let (method, replies) = match req.method {
+        Some(m) => (m, run(m, addr, &opts)?),
+        None => run_auto(addr, &opts)?,
+    };
+    Ok(summarize(&req.host, addr, method, replies))
+}
+
+pub fn ping_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: PingRequest = decode_request(req_json)?;
+    encode_json("ping 响应", &ping(&req)?)
+}
+
+// 依次尝试 raw socket、非特权 ICMP socket、系统 ping；只有在 socket 无法创建时才降级
+fn run_auto(
+    addr: IpAddr,
+    opts: &PingOptions,
+) -> Result<(PingMethod, Vec<PingReply>), ForgeFfiError> {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    for method in [PingMethod::RawSocket, PingMethod::DgramSocket] {
+        if let Ok(sock) = socket::IcmpSocket::open(method, addr) {
+            return Ok((method, sock.run(addr, opts)));
+        }
+    }
+    Ok((PingMethod::SystemPing, system::run(addr, opts)?))
+}
+
+fn run(method: PingMethod, addr: IpAddr, opts: &PingOptions) -> Result<Vec<PingReply>, ForgeFfiError> {
+    match method {
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        PingMethod::RawSocket | PingMethod::DgramSocket => {
+            Ok(socket::IcmpSocket::open(method, addr)?.run(addr, opts))
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        PingMethod::RawSocket | PingMethod::DgramSocket => Err(ForgeFfiError::unsupported(
+            "当前平台仅支持系统 ping".to_string(),
+        )),
+        PingMethod::SystemPing => system::run(addr, opts),
+    }
+}
+
+fn summarize(host: &str, addr: IpAddr, method: PingMethod, replies: Vec<PingReply>) -> PingResponse {
+    let rtts: Vec<u64> = replies.iter().filter_map(|r| r.rtt_us).collect();
+    let sent = u32::try_from(replies.len()).unwrap_or(u32::MAX);
+    let received = u32::try_from(rtts.len()).unwrap_or(u32::MAX);
+    let loss_percent = if sent == 0 {
+        0.0
+    } else {
+        f64::from(sent - received) * 100.0 / f64::from(sent)
+    };
+    let jitter_us = (rtts.len() >= 2).then(|| {
+        let total: u64 = rtts.windows(2).map(|w| w[0].abs_diff(w[1])).sum();
+        total / (rtts.len() as u64 - 1)
+    });
+    PingResponse {
+        abi: ABI_VERSION,
+        host: host.to_string(),
+        address: Some(addr.to_string()),
+        method,
+        sent,
+        received,
+        loss_percent,
+        min_rtt_us: rtts.iter().min().copied(),
+        avg_rtt_us: (!rtts.is_empty()).then(|| rtts.iter().sum::<u64>() / rtts.len() as u64),
+        max_rtt_us: rtts.iter().max().copied(),
+        jitter_us,
+        replies,
+    }
+}
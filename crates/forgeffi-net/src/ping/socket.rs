@@ -0,0 +1,169 @@
+use super::*;
+
+use nix::errno::Errno;
+use nix::sys::socket::{
+    recvfrom, sendto, setsockopt, socket, sockopt, AddressFamily, MsgFlags, SockFlag,
+    SockProtocol, SockType, SockaddrIn, SockaddrIn6, SockaddrStorage,
+};
+use nix::sys::time::TimeVal;
+use std::net::{SocketAddrV4, SocketAddrV6};
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::time::Instant;
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+pub(super) struct IcmpSocket {
+    fd: OwnedFd,
+    ident: u16,
+    // Linux 的非特权 ICMP socket 会改写 identifier，由内核按 socket 过滤回包
+    check_ident: bool,
+}
+
+impl IcmpSocket {
+    pub(super) fn open(method: PingMethod, addr: IpAddr) -> Result<Self, ForgeFfiError> {
+        let ty = match method {
+            PingMethod::RawSocket => SockType::Raw,
+            _ => SockType::Datagram,
+        };
+        let (family, proto) = match addr {
+            IpAddr::V4(_) => (AddressFamily::Inet, SockProtocol::Icmp),
+            IpAddr::V6(_) => (AddressFamily::Inet6, SockProtocol::IcmpV6),
+        };
+        // macOS 不支持 SOCK_CLOEXEC
+        #[cfg(target_os = "linux")]
+        let flags = SockFlag::SOCK_CLOEXEC;
+        #[cfg(not(target_os = "linux"))]
+        let flags = SockFlag::empty();
+        let fd = socket(family, ty, flags, proto).map_err(|e| {
+            let msg = format!("创建 ICMP socket 失败（{method:?}）: {e}");
+            match e {
+                Errno::EPERM | Errno::EACCES => ForgeFfiError::permission_denied(msg),
+                Errno::EAFNOSUPPORT | Errno::EPROTONOSUPPORT => ForgeFfiError::unsupported(msg),
+                _ => ForgeFfiError::system_error(msg),
+            }
+        })?;
+        Ok(Self {
+            fd,
+            ident: std::process::id() as u16,
+            check_ident: method == PingMethod::RawSocket || !cfg!(target_os = "linux"),
+        })
+    }
+
+    pub(super) fn run(&self, addr: IpAddr, opts: &PingOptions) -> Vec<PingReply> {
+        let mut replies = Vec::with_capacity(opts.count as usize);
+        for seq in 1..=opts.count {
+            let started = Instant::now();
+            replies.push(self.echo(addr, seq as u16, opts));
+            if seq < opts.count
+                && let Some(rest) = opts.interval.checked_sub(started.elapsed())
+            {
+                std::thread::sleep(rest);
+            }
+        }
+        replies
+    }
+
+    // 发送失败（如网络不可达）与超时一样按丢包处理
+    fn echo(&self, addr: IpAddr, seq: u16, opts: &PingOptions) -> PingReply {
+        let lost = PingReply {
+            seq: u32::from(seq),
+            rtt_us: None,
+            ttl: None,
+        };
+        let packet = build_echo(addr.is_ipv4(), self.ident, seq, opts.payload_size);
+        let started = Instant::now();
+        let sent = match addr {
+            IpAddr::V4(ip) => sendto(
+                self.fd.as_raw_fd(),
+                &packet,
+                &SockaddrIn::from(SocketAddrV4::new(ip, 0)),
+                MsgFlags::empty(),
+            ),
+            IpAddr::V6(ip) => sendto(
+                self.fd.as_raw_fd(),
+                &packet,
+                &SockaddrIn6::from(SocketAddrV6::new(ip, 0, 0, 0)),
+                MsgFlags::empty(),
+            ),
+        };
+        if sent.is_err() {
+            return lost;
+        }
+
+        let deadline = started + opts.timeout;
+        let mut buf = vec![0u8; usize::from(opts.payload_size) + 128];
+        loop {
+            let Some(remaining) = deadline.checked_duration_since(Instant::now()).filter(|d| !d.is_zero())
+            else {
+                return lost;
+            };
+            let tv = TimeVal::new(
+                remaining.as_secs() as _,
+                remaining.subsec_micros().max(1) as _,
+            );
+            if setsockopt(&self.fd, sockopt::ReceiveTimeout, &tv).is_err() {
+                return lost;
+            }
+            let n = match recvfrom::<SockaddrStorage>(self.fd.as_raw_fd(), &mut buf) {
+                Ok((n, _)) => n,
+                Err(Errno::EINTR) => continue,
+                Err(_) => return lost,
+            };
+            if let Some(ttl) = self.match_reply(&buf[..n], addr.is_ipv4(), seq) {
+                return PingReply {
+                    seq: u32::from(seq),
+                    rtt_us: u64::try_from(started.elapsed().as_micros()).ok(),
+                    ttl,
+                };
+            }
+        }
+    }
+
+    // 返回 Some(ttl) 表示是本次请求的回包；IPv4 下 raw socket（以及 macOS 的 dgram socket）会带上 IP 头
+    fn match_reply(&self, buf: &[u8], v4: bool, seq: u16) -> Option<Option<u8>> {
+        let (icmp, ttl) = if v4 && buf.first().is_some_and(|b| b >> 4 == 4) {
+            let ihl = usize::from(buf[0] & 0x0f) * 4;
+            (buf.get(ihl..)?, buf.get(8).copied())
+        } else {
+            (buf, None)
+        };
+        if icmp.len() < 8 {
+            return None;
+        }
+        let reply_type = if v4 { ICMP_ECHO_REPLY } else { ICMPV6_ECHO_REPLY };
+        let ident = u16::from_be_bytes([icmp[4], icmp[5]]);
+        let rseq = u16::from_be_bytes([icmp[6], icmp[7]]);
+        (icmp[0] == reply_type && rseq == seq && (!self.check_ident || ident == self.ident))
+            .then_some(ttl)
+    }
+}
+
+fn build_echo(v4: bool, ident: u16, seq: u16, payload_size: u16) -> Vec<u8> {
+    let mut p = Vec::with_capacity(8 + usize::from(payload_size));
+    p.push(if v4 { ICMP_ECHO_REQUEST } else { ICMPV6_ECHO_REQUEST });
+    p.push(0);
+    p.extend_from_slice(&[0, 0]);
+    p.extend_from_slice(&ident.to_be_bytes());
+    p.extend_from_slice(&seq.to_be_bytes());
+    p.extend((0..payload_size).map(|i| i as u8));
+    // ICMPv6 校验和包含伪首部，由内核填写
+    if v4 {
+        let sum = checksum(&p);
+        p[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+    p
+}
+
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], c.get(1).copied().unwrap_or(0)])))
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
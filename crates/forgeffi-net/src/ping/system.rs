@@ -0,0 +1,156 @@
+use super::*;
+
+use std::process::{Command, Stdio};
+
+pub(super) fn run(addr: IpAddr, opts: &PingOptions) -> Result<Vec<PingReply>, ForgeFfiError> {
+    let (program, args) = ping_args(addr, opts);
+    let out = Command::new(program)
+        .args(&args)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                ForgeFfiError::unsupported(format!("未找到系统 ping 命令: {program}"))
+            }
+            _ => ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}")),
+        })?;
+
+    let text = String::from_utf8_lossy(&out.stdout);
+    let parsed = parse_output(&text);
+    // 全部丢包时 ping 也返回非 0，只有既无回包又无输出时才视为执行失败
+    if !out.status.success() && parsed.is_empty() && !text.contains(&addr.to_string()) {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ForgeFfiError::system_error(format!(
+            "命令失败: {program}: {}",
+            stderr.trim()
+        )));
+    }
+
+    let mut replies: Vec<PingReply> = (1..=opts.count)
+        .map(|seq| PingReply {
+            seq,
+            rtt_us: None,
+            ttl: None,
+        })
+        .collect();
+    for r in parsed {
+        if let Some(slot) = replies.iter_mut().find(|s| s.seq == r.seq) {
+            *slot = r;
+        }
+    }
+    Ok(replies)
+}
+
+#[cfg(target_os = "windows")]
+fn ping_args(addr: IpAddr, opts: &PingOptions) -> (&'static str, Vec<String>) {
+    // Windows ping 不支持自定义间隔，固定约 1 秒
+    let args = vec![
+        "-n".to_string(),
+        opts.count.to_string(),
+        "-w".to_string(),
+        opts.timeout.as_millis().to_string(),
+        "-l".to_string(),
+        opts.payload_size.to_string(),
+        addr.to_string(),
+    ];
+    ("ping", args)
+}
+
+#[cfg(target_os = "macos")]
+fn ping_args(addr: IpAddr, opts: &PingOptions) -> (&'static str, Vec<String>) {
+    let mut args = vec![
+        "-n".to_string(),
+        "-c".to_string(),
+        opts.count.to_string(),
+        "-i".to_string(),
+        format!("{:.3}", opts.interval.as_secs_f64()),
+        "-s".to_string(),
+        opts.payload_size.to_string(),
+    ];
+    // ping6 没有 -W；ping 的 -W 单位为毫秒
+    let program = if addr.is_ipv4() {
+        args.extend(["-W".to_string(), opts.timeout.as_millis().to_string()]);
+        "ping"
+    } else {
+        "ping6"
+    };
+    args.push(addr.to_string());
+    (program, args)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn ping_args(addr: IpAddr, opts: &PingOptions) -> (&'static str, Vec<String>) {
+    let wait_secs = opts.timeout.as_millis().div_ceil(1000);
+    let args = vec![
+        "-n".to_string(),
+        "-c".to_string(),
+        opts.count.to_string(),
+        "-i".to_string(),
+        format!("{:.3}", opts.interval.as_secs_f64()),
+        "-W".to_string(),
+        wait_secs.to_string(),
+        "-s".to_string(),
+        opts.payload_size.to_string(),
+        addr.to_string(),
+    ];
+    ("ping", args)
+}
+
+// Windows 的输出没有序号，且会随系统语言变化，只按 "TTL=" 行依次编号
+#[cfg(target_os = "windows")]
+fn parse_output(text: &str) -> Vec<PingReply> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let Some(ttl) = line.split_once("TTL=").map(|(_, v)| v) else {
+            continue;
+        };
+        let rtt_us = line.split_whitespace().find_map(|tok| {
+            let v = tok.strip_suffix("ms")?;
+            let v = v.rsplit(['=', '<']).next()?;
+            v.parse::<u64>().ok().map(|ms| ms * 1000)
+        });
+        out.push(PingReply {
+            seq: out.len() as u32 + 1,
+            rtt_us: rtt_us.or(Some(0)),
+            ttl: ttl.trim().parse().ok(),
+        });
+    }
+    out
+}
+
+// iputils/busybox/BSD 格式：64 bytes from 1.2.3.4: icmp_seq=1 ttl=64 time=0.045 ms
+#[cfg(not(target_os = "windows"))]
+fn parse_output(text: &str) -> Vec<PingReply> {
+    // macOS 与 busybox 的序号从 0 开始
+    let base = if cfg!(target_os = "macos") || text.contains(" seq=") {
+        1
+    } else {
+        0
+    };
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let mut seq = None;
+        let mut ttl = None;
+        let mut rtt_us = None;
+        for tok in line.split_whitespace() {
+            let Some((k, v)) = tok.split_once('=') else {
+                continue;
+            };
+            match k {
+                "icmp_seq" | "seq" => seq = v.parse::<u32>().ok(),
+                "ttl" | "hlim" => ttl = v.parse::<u8>().ok(),
+                "time" => rtt_us = v.parse::<f64>().ok().map(|ms| (ms * 1000.0).round() as u64),
+                _ => {}
+            }
+        }
+        if let (Some(seq), Some(rtt_us)) = (seq, rtt_us) {
+            out.push(PingReply {
+                seq: seq + base,
+                rtt_us: Some(rtt_us),
+                ttl,
+            });
+        }
+    }
+    out
+}
@@ -0,0 +1,189 @@
+use forgeffi_base::{
+    check_cancelled, current_cancel_token, ForgeFfiError, NetProbeRequest, NetProbeResponse,
+    ProbeProtocol, ProbeStatus, ABI_VERSION,
+};
+use forgeffi_protocol::{decode_request, encode_json, validate_probe_request, PROBE_DEFAULT_TIMEOUT_MS};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProbeResult {
+    pub status: ProbeStatus,
+    pub address: SocketAddr,
+    pub latency: Duration,
+}
+
+pub fn tcp_connect(host: &str, port: u16, timeout: Duration) -> Result<ProbeResult, ForgeFfiError> {
+    let addrs = resolve(host, port)?;
+    let deadline = Instant::now() + timeout;
+    let mut last: Result<ProbeResult, ForgeFfiError> =
+        Err(ForgeFfiError::timeout(format!("连接 {host}:{port} 超时")));
+
+    // 多个地址依次尝试，共用同一个超时预算
+    for addr in addrs {
+        check_cancelled()?;
+        let started = Instant::now();
+        let Some(remaining) = deadline.checked_duration_since(started).filter(|d| !d.is_zero())
+        else {
+            break;
+        };
+        let status = match connect_cancellable(addr, remaining)? {
+            Ok(()) => ProbeStatus::Open,
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => ProbeStatus::Closed,
+            Err(e) if is_timeout(&e) => ProbeStatus::Filtered,
+            Err(e) => {
+                last = Err(map_probe_io_error(addr, e));
+                continue;
+            }
+        };
+        let r = ProbeResult {
+            status,
+            address: addr,
+            latency: started.elapsed(),
+        };
+        if status != ProbeStatus::Filtered {
+            return Ok(r);
+        }
+        last = Ok(r);
+    }
+    last
+}
+
+pub fn udp_probe(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+    payload: &[u8],
+) -> Result<ProbeResult, ForgeFfiError> {
+    let addr = resolve(host, port)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ForgeFfiError::not_found(format!("无法解析主机: {host}")))?;
+    let bind: SocketAddr = if addr.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let sock = UdpSocket::bind(bind).map_err(|e| map_probe_io_error(addr, e))?;
+    sock.connect(addr).map_err(|e| map_probe_io_error(addr, e))?;
+
+    let started = Instant::now();
+    let deadline = started + timeout;
+    sock.send(payload).map_err(|e| map_probe_io_error(addr, e))?;
+    let mut buf = [0u8; 1500];
+    // 分片等待，每片之间检查取消
+    let received = loop {
+        check_cancelled()?;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break Err(io::Error::from(io::ErrorKind::TimedOut));
+        }
+        sock.set_read_timeout(Some(remaining.min(CANCEL_POLL_INTERVAL)))
+            .map_err(|e| map_probe_io_error(addr, e))?;
+        match sock.recv(&mut buf) {
+            Err(e) if is_timeout(&e) => {}
+            r => break r,
+        }
+    };
+    // 已 connect 的 UDP socket 会把 ICMP 端口不可达报告为 ConnectionRefused（Windows 上为 ConnectionReset）
+    let status = match received {
+        Ok(_) => ProbeStatus::Open,
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset
+            ) =>
+        {
+            ProbeStatus::Closed
+        }
+        Err(e) if is_timeout(&e) => ProbeStatus::Filtered,
+        Err(e) => return Err(map_probe_io_error(addr, e)),
+    };
+    Ok(ProbeResult {
+        status,
+        address: addr,
+        latency: started.elapsed(),
+    })
+}
+
+pub fn probe(req: &NetProbeRequest) -> Result<NetProbeResponse, ForgeFfiError> {
+    validate_probe_request(req)?;
+    let timeout = Duration::from_millis(req.timeout_ms.unwrap_or(PROBE_DEFAULT_TIMEOUT_MS));
+    let r = match req.protocol {
+        ProbeProtocol::Tcp => tcp_connect(&req.host, req.port, timeout)?,
+        ProbeProtocol::Udp => udp_probe(
+            &req.host,
+            req.port,
+            timeout,
+            req.payload.as_deref().unwrap_or_default().as_bytes(),
+        )?,
+    };
+    Ok(NetProbeResponse {
+        abi: ABI_VERSION,
+        protocol: req.protocol,
+        host: req.host.clone(),
+        port: req.port,
+        status: r.status,
+        address: Some(r.address.to_string()),
+        latency_us: u64::try_from(r.latency.as_micros()).ok(),
+    })
+}
+
+pub fn probe_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetProbeRequest = decode_request(req_json)?;
+    encode_json("probe 响应", &probe(&req)?)
+}
+
+pub(crate) fn resolve(host: &str, port: u16) -> Result<Vec<SocketAddr>, ForgeFfiError> {
+    let addrs: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| ForgeFfiError::not_found(format!("无法解析主机 {host}: {e}")))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(ForgeFfiError::not_found(format!("无法解析主机: {host}")));
+    }
+    Ok(addrs)
+}
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// 阻塞的 connect 无法中断：有取消令牌时放到辅助线程里连接，取消后直接放弃等待，
+// 辅助线程最迟在 timeout 到期时自行退出
+fn connect_cancellable(
+    addr: SocketAddr,
+    timeout: Duration,
+) -> Result<io::Result<()>, ForgeFfiError> {
+    let Some(token) = current_cancel_token() else {
+        return Ok(TcpStream::connect_timeout(&addr, timeout).map(drop));
+    };
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(TcpStream::connect_timeout(&addr, timeout).map(drop));
+    });
+    loop {
+        match rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+            Ok(r) => return Ok(r),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) if !token.is_cancelled() => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                return Err(ForgeFfiError::cancelled(format!("连接 {addr} 已取消")));
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                return Ok(Err(io::Error::other("连接线程异常退出")));
+            }
+        }
+    }
+}
+
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock)
+}
+
+fn map_probe_io_error(addr: SocketAddr, e: io::Error) -> ForgeFfiError {
+    let msg = format!("探测 {addr} 失败: {e}");
+    match e.kind() {
+        io::ErrorKind::PermissionDenied => ForgeFfiError::permission_denied(msg),
+        io::ErrorKind::AddrNotAvailable => ForgeFfiError::unsupported(msg),
+        _ => ForgeFfiError::system_error(msg),
+    }
+}
@@ -0,0 +1,244 @@
+// DNS 解析：在 127.0.0.1 上起一个只应答一次的假服务器，回放手工构造的响应报文，
+// 覆盖名字压缩、CNAME 链、各记录类型的字段映射、rcode 映射以及畸形报文
+use forgeffi_base::{DnsAnswer, DnsRecordType, ErrorCode, ForgeFfiError};
+use forgeffi_net::dns::resolve;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Duration;
+
+const TYPE_A: u16 = 1;
+const TYPE_CNAME: u16 = 5;
+const TYPE_MX: u16 = 15;
+const TYPE_TXT: u16 = 16;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+
+// 指向问题区里的查询名（报文头固定 12 字节）
+const QNAME: [u8; 2] = [0xc0, 0x0c];
+
+// 收到的查询报文 -> 应答报文
+type Build = fn(&[u8]) -> Vec<u8>;
+
+struct Reply {
+    rcode: u8,
+    answers: Vec<(Vec<u8>, u16, u32, Vec<u8>)>,
+}
+
+impl Reply {
+    fn ok() -> Self {
+        Self {
+            rcode: 0,
+            answers: Vec::new(),
+        }
+    }
+
+    fn rcode(rcode: u8) -> Self {
+        Self {
+            rcode,
+            answers: Vec::new(),
+        }
+    }
+
+    fn answer(mut self, owner: &[u8], rtype: u16, ttl: u32, rdata: Vec<u8>) -> Self {
+        self.answers.push((owner.to_vec(), rtype, ttl, rdata));
+        self
+    }
+
+    // 回显查询的 id 与问题区，附上 answers；不带 OPT 记录
+    fn encode(&self, query: &[u8]) -> Vec<u8> {
+        let mut m = query[..2].to_vec();
+        m.extend_from_slice(&[0x81, 0x80 | self.rcode]);
+        m.extend_from_slice(&[0, 1]);
+        m.extend_from_slice(&(self.answers.len() as u16).to_be_bytes());
+        m.extend_from_slice(&[0, 0, 0, 0]);
+        m.extend_from_slice(&query[12..question_end(query)]);
+        for (owner, rtype, ttl, rdata) in &self.answers {
+            m.extend_from_slice(owner);
+            m.extend_from_slice(&rtype.to_be_bytes());
+            m.extend_from_slice(&[0, 1]);
+            m.extend_from_slice(&ttl.to_be_bytes());
+            m.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            m.extend_from_slice(rdata);
+        }
+        m
+    }
+}
+
+fn question_end(query: &[u8]) -> usize {
+    let mut pos = 12;
+    while query[pos] != 0 {
+        pos += 1 + usize::from(query[pos]);
+    }
+    pos + 1 + 4
+}
+
+fn labels(name: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    for label in name.split('.') {
+        out.push(label.len() as u8);
+        out.extend_from_slice(label.as_bytes());
+    }
+    out
+}
+
+// 起一个假服务器，对收到的第一个查询按 build 的结果应答
+fn serve(build: Build) -> SocketAddr {
+    let sock = UdpSocket::bind("127.0.0.1:0").expect("bind");
+    let addr = sock.local_addr().expect("local_addr");
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 1500];
+        let Ok((n, peer)) = sock.recv_from(&mut buf) else {
+            return;
+        };
+        let _ = sock.send_to(&build(&buf[..n]), peer);
+    });
+    addr
+}
+
+fn lookup(name: &str, t: DnsRecordType, build: Build) -> Result<Vec<DnsAnswer>, ForgeFfiError> {
+    let server = serve(build);
+    let (used, answers) = resolve(name, t, Some(server), Duration::from_millis(1500))?;
+    assert_eq!(used, server);
+    Ok(answers)
+}
+
+fn answer(name: &str, record_type: DnsRecordType, ttl: u32) -> DnsAnswer {
+    DnsAnswer {
+        name: name.to_string(),
+        record_type,
+        ttl,
+        address: None,
+        text: None,
+        target: None,
+        priority: None,
+        weight: None,
+        port: None,
+    }
+}
+
+#[test]
+fn follows_compressed_cname_chain() {
+    let answers = lookup("www.example.com", DnsRecordType::A, |q| {
+        // CNAME 的目标 "web" + 指向问题区里 "example.com" 的指针（12 + 1 + 3）；
+        // A 记录的属主名再指回这个目标
+        let mut target = labels("web");
+        target.extend_from_slice(&[0xc0, 16]);
+        let target_at = question_end(q) + QNAME.len() + 10;
+        Reply::ok()
+            .answer(&QNAME, TYPE_CNAME, 300, target)
+            .answer(&[0xc0, target_at as u8], TYPE_A, 60, vec![192, 0, 2, 7])
+            .encode(q)
+    })
+    .expect("解析成功");
+    let mut cname = answer("www.example.com", DnsRecordType::Cname, 300);
+    cname.target = Some("web.example.com".to_string());
+    let mut a = answer("web.example.com", DnsRecordType::A, 60);
+    a.address = Some("192.0.2.7".to_string());
+    assert_eq!(answers, [cname, a]);
+}
+
+#[test]
+fn maps_record_fields() {
+    let answers = lookup("example.com", DnsRecordType::Aaaa, |q| {
+        let mut ip = vec![0x20, 0x01, 0x0d, 0xb8];
+        ip.extend_from_slice(&[0; 11]);
+        ip.push(1);
+        Reply::ok().answer(&QNAME, TYPE_AAAA, 30, ip).encode(q)
+    })
+    .expect("AAAA");
+    assert_eq!(answers[0].address.as_deref(), Some("2001:db8::1"));
+
+    // 多段 TXT 直接拼接
+    let answers = lookup("example.com", DnsRecordType::Txt, |q| {
+        let mut rdata = vec![7];
+        rdata.extend_from_slice(b"v=spf1 ");
+        rdata.push(4);
+        rdata.extend_from_slice(b"-all");
+        Reply::ok().answer(&QNAME, TYPE_TXT, 30, rdata).encode(q)
+    })
+    .expect("TXT");
+    assert_eq!(answers[0].text.as_deref(), Some("v=spf1 -all"));
+
+    let answers = lookup("example.com", DnsRecordType::Mx, |q| {
+        let mut rdata = 10u16.to_be_bytes().to_vec();
+        rdata.extend_from_slice(&labels("mail"));
+        rdata.extend_from_slice(&QNAME);
+        Reply::ok().answer(&QNAME, TYPE_MX, 30, rdata).encode(q)
+    })
+    .expect("MX");
+    let mut mx = answer("example.com", DnsRecordType::Mx, 30);
+    mx.priority = Some(10);
+    mx.target = Some("mail.example.com".to_string());
+    assert_eq!(answers, [mx]);
+
+    let answers = lookup("_sip._tcp.example.com", DnsRecordType::Srv, |q| {
+        let mut rdata = Vec::new();
+        for v in [5u16, 20, 5060] {
+            rdata.extend_from_slice(&v.to_be_bytes());
+        }
+        rdata.extend_from_slice(&labels("sip.example.com"));
+        rdata.push(0);
+        Reply::ok().answer(&QNAME, TYPE_SRV, 30, rdata).encode(q)
+    })
+    .expect("SRV");
+    let mut srv = answer("_sip._tcp.example.com", DnsRecordType::Srv, 30);
+    srv.priority = Some(5);
+    srv.weight = Some(20);
+    srv.port = Some(5060);
+    srv.target = Some("sip.example.com".to_string());
+    assert_eq!(answers, [srv]);
+
+    // 与所查类型无关的记录被丢弃
+    let answers = lookup("example.com", DnsRecordType::A, |q| {
+        Reply::ok()
+            .answer(&QNAME, TYPE_A, 30, vec![192, 0, 2, 1])
+            .answer(&QNAME, TYPE_TXT, 30, vec![1, b'x'])
+            .encode(q)
+    })
+    .expect("A");
+    assert_eq!(answers.len(), 1);
+}
+
+#[test]
+fn maps_response_codes() {
+    assert_eq!(lookup("example.com", DnsRecordType::A, |q| Reply::ok().encode(q)).ok(), Some(Vec::new()));
+
+    let err = lookup("missing.example.com", DnsRecordType::A, |q| Reply::rcode(3).encode(q))
+        .expect_err("NXDOMAIN");
+    assert_eq!(err.code, ErrorCode::NotFound, "{err:?}");
+
+    let err = lookup("example.com", DnsRecordType::A, |q| Reply::rcode(5).encode(q)).expect_err("REFUSED");
+    assert_eq!(err.code, ErrorCode::PermissionDenied, "{err:?}");
+}
+
+#[test]
+fn malformed_responses_fail_without_panicking() {
+    let cases: [Build; 4] = [
+        // rdlength 超出报文
+        |q| {
+            let mut m = Reply::ok().answer(&QNAME, TYPE_A, 30, vec![192, 0, 2, 1]).encode(q);
+            let len = m.len();
+            m[len - 5] = 0xff;
+            m
+        },
+        // 属主名是指向自己的压缩指针
+        |q| {
+            let at = question_end(q) as u8;
+            Reply::ok().answer(&[0xc0, at], TYPE_A, 30, vec![192, 0, 2, 1]).encode(q)
+        },
+        // ANCOUNT 声称有记录但报文在问题区后结束
+        |q| {
+            let mut m = Reply::ok().encode(q);
+            m[7] = 1;
+            m
+        },
+        // A 记录的 rdata 不是 4 字节
+        |q| Reply::ok().answer(&QNAME, TYPE_A, 30, vec![192, 0, 2]).encode(q),
+    ];
+    for (i, build) in cases.into_iter().enumerate() {
+        let err = lookup("example.com", DnsRecordType::A, build).expect_err(&format!("case {i}"));
+        assert!(
+            matches!(err.code, ErrorCode::SystemError | ErrorCode::Timeout),
+            "case {i}: {err:?}"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+// 结构与 C ABI 的 JSON 一致（snake_case 字段），完整字段见 forgeffi-base/src/netif.rs
+
+export interface NetInterface {
+  if_index: number;
+  name: string;
+  display_name?: string;
+  kind: string;
+  is_physical?: boolean;
+  admin_state: string;
+  oper_state?: string;
+  mac?: string;
+  mtu?: number;
+  ipv4: Array<{ ip: string; prefix_len: number; [key: string]: unknown }>;
+  ipv6: Array<{ ip: string; prefix_len: number; [key: string]: unknown }>;
+  ipv4_gateway?: string;
+  ipv6_gateway?: string;
+  [key: string]: unknown;
+}
+
+export interface NetIfListResponse {
+  abi: number;
+  items: NetInterface[];
+  network_location?: Record<string, unknown>;
+}
+
+// abi 可省略，默认为当前 ABI 版本
+export interface NetIfApplyRequest {
+  abi?: number;
+  target: Record<string, unknown>;
+  ops: Array<Record<string, unknown>>;
+  deadline_ms?: number;
+  skip_if_applied?: boolean;
+  [key: string]: unknown;
+}
+
+export interface NetIfApplyResponse {
+  abi: number;
+  min_abi: number;
+  max_abi: number;
+  ok: boolean;
+  results: Array<{ i: number; ok: boolean; skipped?: boolean; error?: { code: string; message: string }; [key: string]: unknown }>;
+  last_known?: NetInterface;
+  [key: string]: unknown;
+}
+
+// reject 的 Error.code 为 ErrorCode 名称，例如 "PermissionDenied"
+export interface ForgeFfiError extends Error {
+  code: string;
+}
+
+export function abiVersion(): number;
+export function listInterfaces(): Promise<NetIfListResponse>;
+export function apply(req: NetIfApplyRequest): Promise<NetIfApplyResponse>;
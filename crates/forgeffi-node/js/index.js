@@ -0,0 +1,4 @@
+'use strict';
+
+// dist 中 forgeffi.node 与本文件同目录，按 target 分别发布
+module.exports = require('./forgeffi.node');
@@ -0,0 +1,81 @@
+// Node.js 原生扩展：直接调用 forgeffi-sys，不经过 C ABI 与 JSON 出参缓冲区。
+// 耗时调用放到 libuv 线程池执行，JS 侧拿到 Promise；失败时 reject 的 Error 带 code（ErrorCode 名称）
+use forgeffi_base::{ForgeFfiError, NetIfApplyRequest, ABI_VERSION};
+use forgeffi_protocol::check_abi;
+use napi::bindgen_prelude::*;
+use napi::{Env, JsUnknown, Task};
+use napi_derive::napi;
+use serde_json::Value;
+
+#[napi]
+pub fn abi_version() -> u32 {
+    ABI_VERSION
+}
+
+pub struct ListTask;
+
+impl Task for ListTask {
+    type Output = std::result::Result<Value, ForgeFfiError>;
+    type JsValue = JsUnknown;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(forgeffi_sys::netif::list_response().and_then(to_value))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        settle(env, output)
+    }
+}
+
+pub struct ApplyTask(Value);
+
+impl Task for ApplyTask {
+    type Output = std::result::Result<Value, ForgeFfiError>;
+    type JsValue = JsUnknown;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(decode_apply(self.0.take())
+            .and_then(forgeffi_sys::netif::apply_request)
+            .and_then(to_value))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        settle(env, output)
+    }
+}
+
+#[napi(ts_return_type = "Promise<NetIfListResponse>")]
+pub fn list_interfaces() -> AsyncTask<ListTask> {
+    AsyncTask::new(ListTask)
+}
+
+// req 与 tool_netif_apply_json 的请求 JSON 结构相同，abi 字段可省略
+#[napi(ts_args_type = "req: NetIfApplyRequest", ts_return_type = "Promise<NetIfApplyResponse>")]
+pub fn apply(req: Value) -> AsyncTask<ApplyTask> {
+    AsyncTask::new(ApplyTask(req))
+}
+
+fn decode_apply(mut req: Value) -> std::result::Result<NetIfApplyRequest, ForgeFfiError> {
+    if let Value::Object(m) = &mut req {
+        m.entry("abi").or_insert(Value::from(ABI_VERSION));
+    }
+    let req: NetIfApplyRequest = serde_json::from_value(req)
+        .map_err(|e| ForgeFfiError::invalid_argument(format!("请求格式错误: {e}")))?;
+    check_abi(req.abi)?;
+    Ok(req)
+}
+
+fn to_value<T: serde::Serialize>(v: T) -> std::result::Result<Value, ForgeFfiError> {
+    serde_json::to_value(v).map_err(|e| ForgeFfiError::system_error(format!("序列化响应失败: {e}")))
+}
+
+fn settle(env: Env, output: std::result::Result<Value, ForgeFfiError>) -> Result<JsUnknown> {
+    match output {
+        Ok(v) => env.to_js_value(&v),
+        Err(e) => {
+            let mut err = env.create_error(Error::new(Status::GenericFailure, e.message))?;
+            err.set_named_property("code", env.create_string(&format!("{:?}", e.code))?)?;
+            Err(Error::from(err.into_unknown()))
+        }
+    }
+}
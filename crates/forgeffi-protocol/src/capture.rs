@@ -0,0 +1,47 @@
+use forgeffi_base::{CaptureStartRequest, ForgeFfiError, CAPTURE_DEFAULT_SNAPLEN, CAPTURE_MAX_SECS};
+
+use crate::check_abi;
+
+// 小于一个 pcapng 头部加几个包没有意义，多半是单位写错（KB/MB）
+pub const CAPTURE_MIN_BYTES: u64 = 4096;
+pub const CAPTURE_MIN_SNAPLEN: u32 = 64;
+pub const CAPTURE_MAX_FILTER_LEN: usize = 1024;
+
+pub fn validate_capture_request(req: &CaptureStartRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    // iface 会传给 tcpdump/dumpcap，禁止以 '-' 开头避免被当成参数
+    if req.iface.is_empty()
+        || req.iface.starts_with('-')
+        || req.iface.chars().any(|c| c.is_control() || c == '/')
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("iface 含有非法字符或为空: {:?}", req.iface)));
+    }
+    if req.path.is_empty() || req.path.chars().any(char::is_control) {
+        return Err(ForgeFfiError::invalid_argument("path 不能为空且不能包含控制字符"));
+    }
+    if let Some(filter) = &req.filter
+        && (filter.len() > CAPTURE_MAX_FILTER_LEN || filter.chars().any(char::is_control))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "filter 不能超过 {CAPTURE_MAX_FILTER_LEN} 字节且不能包含控制字符"
+        )));
+    }
+    if let Some(n) = req.max_bytes
+        && n < CAPTURE_MIN_BYTES
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("max_bytes 不能小于 {CAPTURE_MIN_BYTES}")));
+    }
+    if let Some(s) = req.max_secs
+        && !(1..=CAPTURE_MAX_SECS).contains(&s)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("max_secs 必须在 1..={CAPTURE_MAX_SECS}")));
+    }
+    if let Some(n) = req.snaplen
+        && !(CAPTURE_MIN_SNAPLEN..=CAPTURE_DEFAULT_SNAPLEN).contains(&n)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "snaplen 必须在 {CAPTURE_MIN_SNAPLEN}..={CAPTURE_DEFAULT_SNAPLEN}"
+        )));
+    }
+    Ok(())
+}
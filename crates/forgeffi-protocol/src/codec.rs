@@ -0,0 +1,101 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError, ABI_VERSION, MIN_SUPPORTED_ABI};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// FFI 出错时写出的 JSON：{"abi":1,"ok":false,"error":{"code":...,"message":...}}
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub abi: u32,
+    // 宿主据此判断是否需要降级/升级请求格式
+    #[serde(default)]
+    pub min_abi: u32,
+    #[serde(default)]
+    pub max_abi: u32,
+    pub ok: bool,
+    pub error: ForgeFfiError,
+}
+
+impl ErrorResponse {
+    #[must_use]
+    pub fn new(error: ForgeFfiError) -> Self {
+        Self {
+            abi: ABI_VERSION,
+            min_abi: MIN_SUPPORTED_ABI,
+            max_abi: ABI_VERSION,
+            ok: false,
+            error,
+        }
+    }
+}
+
+pub fn check_abi(abi: u32) -> Result<(), ForgeFfiError> {
+    if !(MIN_SUPPORTED_ABI..=ABI_VERSION).contains(&abi) {
+        return Err(ForgeFfiError::catalog(
+            ErrorCode::InvalidArgument,
+            "abi_unsupported",
+            &[("min", &MIN_SUPPORTED_ABI), ("max", &ABI_VERSION), ("got", &abi)],
+        ));
+    }
+    Ok(())
+}
+
+// ABI_UPGRADES[i] 把 abi=MIN_SUPPORTED_ABI+i 的请求 JSON 改写成下一版本的形状；
+// ABI_VERSION 递增时在末尾追加一项，删掉最老一项时同步提高 MIN_SUPPORTED_ABI
+type AbiUpgrade = fn(&mut serde_json::Map<String, serde_json::Value>) -> Result<(), ForgeFfiError>;
+
+const ABI_UPGRADES: &[AbiUpgrade] = &[];
+
+const _: () = assert!(ABI_UPGRADES.len() as u32 == ABI_VERSION - MIN_SUPPORTED_ABI);
+
+// 只改写顶层带受支持旧 abi 的对象；越界的 abi 原样保留，交给 check_abi 报错
+pub fn upgrade_request(v: &mut serde_json::Value) -> Result<(), ForgeFfiError> {
+    let Some(obj) = v.as_object_mut() else {
+        return Ok(());
+    };
+    let Some(abi) = obj.get("abi").and_then(serde_json::Value::as_u64) else {
+        return Ok(());
+    };
+    let Ok(abi) = u32::try_from(abi) else {
+        return Ok(());
+    };
+    if !(MIN_SUPPORTED_ABI..ABI_VERSION).contains(&abi) {
+        return Ok(());
+    }
+    for step in &ABI_UPGRADES[(abi - MIN_SUPPORTED_ABI) as usize..] {
+        step(obj)?;
+    }
+    obj.insert("abi".to_string(), ABI_VERSION.into());
+    Ok(())
+}
+
+pub fn decode_request<T: DeserializeOwned>(s: &str) -> Result<T, ForgeFfiError> {
+    let mut v: serde_json::Value = serde_json::from_str(s).map_err(invalid_request_json)?;
+    upgrade_request(&mut v)?;
+    serde_json::from_value(v).map_err(invalid_request_json)
+}
+
+fn invalid_request_json(e: serde_json::Error) -> ForgeFfiError {
+    ForgeFfiError::catalog(ErrorCode::InvalidArgument, "request_json_invalid", &[("detail", &e)])
+}
+
+pub fn encode_json<T: Serialize>(what: &str, v: &T) -> Result<Vec<u8>, ForgeFfiError> {
+    serde_json::to_vec(v).map_err(|e| ForgeFfiError::system_error(format!("序列化 {what} 失败: {e}")))
+}
+
+#[must_use]
+pub fn encode_error(e: &ForgeFfiError) -> Vec<u8> {
+    serde_json::to_vec(&ErrorResponse::new(e.clone())).unwrap_or_else(|_| b"{\"ok\":false}".to_vec())
+}
+
+// 响应可能是成功 DTO，也可能是 ErrorResponse；后者直接还原成 Err
+pub fn decode_response<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ForgeFfiError> {
+    let v: serde_json::Value = serde_json::from_slice(bytes)
+        .map_err(|e| ForgeFfiError::invalid_argument(format!("解析响应 JSON 失败: {e}")))?;
+    if v.get("ok") == Some(&serde_json::Value::Bool(false)) && v.get("error").is_some() {
+        let r: ErrorResponse = serde_json::from_value(v)
+            .map_err(|e| ForgeFfiError::invalid_argument(format!("解析错误响应失败: {e}")))?;
+        return Err(r.error);
+    }
+    serde_json::from_value(v)
+        .map_err(|e| ForgeFfiError::invalid_argument(format!("解析响应 JSON 失败: {e}")))
+}
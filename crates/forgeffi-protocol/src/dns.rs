@@ -0,0 +1,59 @@
+use forgeffi_base::{DnsResolveRequest, ForgeFfiError};
+use std::net::{IpAddr, SocketAddr};
+
+use crate::{check_abi, PROBE_MAX_TIMEOUT_MS};
+
+pub const DNS_DEFAULT_TIMEOUT_MS: u64 = 3000;
+pub const DNS_DEFAULT_PORT: u16 = 53;
+
+pub fn validate_dns_name(name: &str) -> Result<(), ForgeFfiError> {
+    let trimmed = name.strip_suffix('.').unwrap_or(name);
+    if trimmed.is_empty() || trimmed.len() > 253 {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "域名长度非法: {name}"
+        )));
+    }
+    for label in trimmed.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "域名标签长度非法: {name}"
+            )));
+        }
+        // 国际化域名需由调用方先转成 punycode
+        if !label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "域名含有非法字符: {name}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_dns_server(server: &str) -> Result<SocketAddr, ForgeFfiError> {
+    if let Ok(addr) = server.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    server
+        .parse::<IpAddr>()
+        .map(|ip| SocketAddr::new(ip, DNS_DEFAULT_PORT))
+        .map_err(|_| ForgeFfiError::invalid_argument(format!("DNS 服务器地址非法: {server}")))
+}
+
+pub fn validate_resolve_request(req: &DnsResolveRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    validate_dns_name(&req.name)?;
+    if let Some(server) = &req.server {
+        parse_dns_server(server)?;
+    }
+    if let Some(ms) = req.timeout_ms
+        && !(1..=PROBE_MAX_TIMEOUT_MS).contains(&ms)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "timeout_ms 必须在 1..={PROBE_MAX_TIMEOUT_MS}"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use forgeffi_base::{ForgeFfiError, HashRequest};
+
+use crate::check_abi;
+
+pub const HASH_MAX_PATTERNS: usize = 64;
+
+// SHA-256 与 BLAKE3（默认输出长度）都是 32 字节
+pub const HASH_DIGEST_HEX_LEN: usize = 64;
+
+pub fn validate_hash_request(req: &HashRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if req.path.is_empty() || req.path.contains('\0') {
+        return Err(ForgeFfiError::invalid_argument("path 不能为空或包含 NUL"));
+    }
+    if req.include.len() > HASH_MAX_PATTERNS || req.exclude.len() > HASH_MAX_PATTERNS {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "include/exclude 最多 {HASH_MAX_PATTERNS} 条"
+        )));
+    }
+    if let Some(p) = req.include.iter().chain(&req.exclude).find(|p| p.is_empty()) {
+        return Err(ForgeFfiError::invalid_argument(format!("glob 不能为空: {p:?}")));
+    }
+    if let Some(e) = &req.expected
+        && (e.len() != HASH_DIGEST_HEX_LEN || !e.bytes().all(|b| b.is_ascii_hexdigit()))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "expected 必须是 {HASH_DIGEST_HEX_LEN} 位十六进制摘要: {e}"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use forgeffi_base::{ForgeFfiError, HttpProbeRequest};
+
+use crate::{check_abi, PROBE_MAX_TIMEOUT_MS};
+
+pub const HTTP_DEFAULT_MAX_BODY_BYTES: u32 = 4096;
+pub const HTTP_MAX_BODY_BYTES: u32 = 1024 * 1024;
+
+const HTTP_METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "DELETE", "OPTIONS", "PATCH"];
+
+pub fn validate_http_probe_request(req: &HttpProbeRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    let lower = req.url.to_ascii_lowercase();
+    if !(lower.starts_with("http://") || lower.starts_with("https://")) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "url 必须以 http:// 或 https:// 开头: {}",
+            req.url
+        )));
+    }
+    if req.url.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "url 含有非法字符: {}",
+            req.url
+        )));
+    }
+    if let Some(m) = &req.method
+        && !HTTP_METHODS.contains(&m.as_str())
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "不支持的 HTTP 方法: {m}"
+        )));
+    }
+    for (name, value) in &req.headers {
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "HTTP 头名称非法: {name}"
+            )));
+        }
+        if value.chars().any(|c| c == '\r' || c == '\n' || c == '\0') {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "HTTP 头 {name} 的值含有非法字符"
+            )));
+        }
+    }
+    if let Some(ms) = req.timeout_ms
+        && !(1..=PROBE_MAX_TIMEOUT_MS).contains(&ms)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "timeout_ms 必须在 1..={PROBE_MAX_TIMEOUT_MS}"
+        )));
+    }
+    if let Some(n) = req.max_body_bytes
+        && n > HTTP_MAX_BODY_BYTES
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "max_body_bytes 不能超过 {HTTP_MAX_BODY_BYTES}"
+        )));
+    }
+    Ok(())
+}
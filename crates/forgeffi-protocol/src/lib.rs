@@ -0,0 +1,29 @@
+#![forbid(unsafe_code)]
+
+// 只包含 JSON 协议本身（DTO、校验、编解码），不依赖任何平台实现，
+// 供进程外客户端或动态加载 cdylib 的 Rust 程序共享同一套类型。
+pub use forgeffi_base::*;
+
+mod capture;
+mod codec;
+mod dns;
+mod hash;
+mod http;
+mod lldp;
+mod netif;
+mod probe;
+mod service;
+mod time;
+mod wifi;
+
+pub use capture::*;
+pub use codec::*;
+pub use dns::*;
+pub use hash::*;
+pub use http::*;
+pub use lldp::*;
+pub use netif::*;
+pub use probe::*;
+pub use service::*;
+pub use time::*;
+pub use wifi::*;
@@ -0,0 +1,21 @@
+use forgeffi_base::{ForgeFfiError, LldpRequest, LLDP_MAX_LISTEN_SECS};
+
+use crate::check_abi;
+
+pub fn validate_lldp_request(req: &LldpRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    // iface 会传给 lldpctl/lldptool，禁止以 '-' 开头避免被当成参数
+    if let Some(iface) = &req.iface
+        && (iface.is_empty() || iface.starts_with('-') || iface.chars().any(|c| c.is_control() || c == '/'))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("iface 含有非法字符或为空: {iface:?}")));
+    }
+    if let Some(s) = req.listen_secs
+        && !(1..=LLDP_MAX_LISTEN_SECS).contains(&s)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "listen_secs 必须在 1..={LLDP_MAX_LISTEN_SECS}"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,1004 @@
+use forgeffi_base::{
+    AdminState, ForgeFfiError, IfaceSelector, IpAddrEntry, IpOrigin, IpScope, NetIfApplyRequest,
+    NetIfDesiredAddr, NetIfDesiredState, NetIfListRequest, NetIfMultiApplyRequest, NetIfOp,
+    NetIfReconcileRequest, NetIfRestoreRequest, NetIfScope, NetInterface, RoutingRule, StaticRoute, TunnelKind,
+    WireguardPeer,
+};
+use std::net::IpAddr;
+
+use crate::check_abi;
+
+pub const LFT_FOREVER: u32 = u32::MAX;
+
+// 只给 valid 时 preferred 与之相同；只给 preferred 时 valid 为永久
+pub fn resolve_lifetimes(valid: Option<u32>, preferred: Option<u32>) -> Option<(u32, u32)> {
+    if valid.is_none() && preferred.is_none() {
+        return None;
+    }
+    let valid = valid.unwrap_or(LFT_FOREVER);
+    Some((valid, preferred.unwrap_or(valid)))
+}
+
+// 与 NetIfOp 的 "op" 标签一一对应
+pub const NETIF_OPS: &[&str] = &[
+    "set_admin_state",
+    "set_mtu",
+    "add_ip",
+    "del_ip",
+    "set_ipv4_dhcp",
+    "set_ipv4_static",
+    "set_ipv6_mode",
+    "set_ipv6_static",
+    "create_vlan",
+    "delete_vlan",
+    "create_bridge",
+    "delete_bridge",
+    "add_bridge_member",
+    "remove_bridge_member",
+    "create_dummy",
+    "delete_interface",
+    "create_bond",
+    "delete_bond",
+    "add_bond_member",
+    "remove_bond_member",
+    "create_wireguard",
+    "set_wireguard_peer",
+    "remove_wireguard_peer",
+    "create_ip_tunnel",
+    "delete_tunnel",
+    "add_route",
+    "del_route",
+    "add_routing_rule",
+    "del_routing_rule",
+    "set_dns_suffix",
+    "switch_network_location",
+    "set_service_order",
+    "set_dns_registration",
+    "set_name",
+    "set_description",
+    "set_mac",
+    "set_link_speed",
+    "set_bandwidth_limit",
+];
+
+#[must_use]
+pub fn netif_op_tag(op: &NetIfOp) -> &'static str {
+    match op {
+        NetIfOp::SetAdminState { .. } => "set_admin_state",
+        NetIfOp::SetMtu { .. } => "set_mtu",
+        NetIfOp::AddIp { .. } => "add_ip",
+        NetIfOp::DelIp { .. } => "del_ip",
+        NetIfOp::SetIpv4Dhcp { .. } => "set_ipv4_dhcp",
+        NetIfOp::SetIpv4Static { .. } => "set_ipv4_static",
+        NetIfOp::SetIpv6Mode { .. } => "set_ipv6_mode",
+        NetIfOp::SetIpv6Static { .. } => "set_ipv6_static",
+        NetIfOp::CreateVlan { .. } => "create_vlan",
+        NetIfOp::DeleteVlan => "delete_vlan",
+        NetIfOp::CreateBridge { .. } => "create_bridge",
+        NetIfOp::DeleteBridge => "delete_bridge",
+        NetIfOp::AddBridgeMember { .. } => "add_bridge_member",
+        NetIfOp::RemoveBridgeMember => "remove_bridge_member",
+        NetIfOp::CreateDummy { .. } => "create_dummy",
+        NetIfOp::DeleteInterface => "delete_interface",
+        NetIfOp::CreateBond { .. } => "create_bond",
+        NetIfOp::DeleteBond => "delete_bond",
+        NetIfOp::AddBondMember { .. } => "add_bond_member",
+        NetIfOp::RemoveBondMember => "remove_bond_member",
+        NetIfOp::CreateWireguard { .. } => "create_wireguard",
+        NetIfOp::SetWireguardPeer { .. } => "set_wireguard_peer",
+        NetIfOp::RemoveWireguardPeer { .. } => "remove_wireguard_peer",
+        NetIfOp::CreateIpTunnel { .. } => "create_ip_tunnel",
+        NetIfOp::DeleteTunnel => "delete_tunnel",
+        NetIfOp::AddRoute { .. } => "add_route",
+        NetIfOp::DelRoute { .. } => "del_route",
+        NetIfOp::AddRoutingRule { .. } => "add_routing_rule",
+        NetIfOp::DelRoutingRule { .. } => "del_routing_rule",
+        NetIfOp::SetDnsSuffix { .. } => "set_dns_suffix",
+        NetIfOp::SwitchNetworkLocation { .. } => "switch_network_location",
+        NetIfOp::SetServiceOrder { .. } => "set_service_order",
+        NetIfOp::SetDnsRegistration { .. } => "set_dns_registration",
+        NetIfOp::SetName { .. } => "set_name",
+        NetIfOp::SetDescription { .. } => "set_description",
+        NetIfOp::SetMac { .. } => "set_mac",
+        NetIfOp::SetLinkSpeed { .. } => "set_link_speed",
+        NetIfOp::SetBandwidthLimit { .. } => "set_bandwidth_limit",
+    }
+}
+
+pub fn op_uses_target(op: &NetIfOp) -> bool {
+    !matches!(
+        op,
+        NetIfOp::CreateVlan { .. }
+            | NetIfOp::CreateBridge { .. }
+            | NetIfOp::CreateDummy { .. }
+            | NetIfOp::CreateBond { .. }
+            | NetIfOp::AddBondMember { .. }
+            | NetIfOp::CreateWireguard { .. }
+            | NetIfOp::CreateIpTunnel { .. }
+            | NetIfOp::AddRoutingRule { .. }
+            | NetIfOp::DelRoutingRule { .. }
+            | NetIfOp::AddBridgeMember { .. }
+            | NetIfOp::SwitchNetworkLocation { .. }
+            | NetIfOp::SetServiceOrder { .. }
+    )
+}
+
+pub fn validate_ifname(field: &str, name: &str) -> Result<(), ForgeFfiError> {
+    if name.is_empty() {
+        return Err(ForgeFfiError::invalid_argument(format!("{field} 不能为空")));
+    }
+    if name.chars().any(|c| c.is_control() || c == '\'' || c == '"') {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{field} 含有非法字符: {name}"
+        )));
+    }
+    Ok(())
+}
+
+// 与内核 dev_valid_name 一致：IFNAMSIZ 含结尾 NUL，最多 15 字节
+pub fn validate_linux_ifname(name: &str) -> Result<(), ForgeFfiError> {
+    if name.is_empty() || name.len() > 15 {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "Linux 接口名长度必须在 1..=15 字节: {name}"
+        )));
+    }
+    if name == "." || name == ".." || name.chars().any(|c| c == '/' || c == ':' || c.is_whitespace()) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "Linux 接口名不能为 . 或 ..，且不能包含 / : 或空白: {name}"
+        )));
+    }
+    Ok(())
+}
+
+// 网络连接名沿用文件名规则；U+2018..=U+201B 在 PowerShell 里同样是单引号，一并拒绝
+pub fn validate_windows_ifname(name: &str) -> Result<(), ForgeFfiError> {
+    if name.is_empty() || name.chars().count() > 255 || name.trim() != name {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "Windows 网卡名长度必须在 1..=255 且首尾不能有空白: {name:?}"
+        )));
+    }
+    if name.chars().any(|c| c.is_control() || "\\/:*?\"<>|'`$\u{2018}\u{2019}\u{201A}\u{201B}".contains(c)) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "Windows 网卡名含有非法字符: {name}"
+        )));
+    }
+    Ok(())
+}
+
+// 只允许单播地址；组播位（首字节最低位）置位或全零的地址网卡会拒绝或导致通信异常
+pub fn parse_mac(mac: &str) -> Result<[u8; 6], ForgeFfiError> {
+    let hex: String = if mac.len() == 17 {
+        let sep = mac.as_bytes()[2];
+        if !(sep == b':' || sep == b'-')
+            || mac.bytes().skip(2).step_by(3).any(|b| b != sep)
+        {
+            return Err(ForgeFfiError::invalid_argument(format!("非法 MAC 地址: {mac}")));
+        }
+        mac.split(sep as char).collect()
+    } else {
+        mac.to_string()
+    };
+    if hex.len() != 12 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ForgeFfiError::invalid_argument(format!("非法 MAC 地址: {mac}")));
+    }
+    let mut out = [0u8; 6];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 MAC 地址: {mac}")))?;
+    }
+    if out[0] & 0x01 != 0 {
+        return Err(ForgeFfiError::invalid_argument(format!("MAC 地址不能是组播地址: {mac}")));
+    }
+    if out == [0; 6] {
+        return Err(ForgeFfiError::invalid_argument("MAC 地址不能全为 0"));
+    }
+    Ok(out)
+}
+
+// 只对能从 list 结果可靠判断的 op 返回 true；其余一律按未生效处理，照常执行
+pub fn op_already_applied(op: &NetIfOp, it: &NetInterface) -> bool {
+    match op {
+        NetIfOp::SetAdminState { up } => {
+            it.admin_state == if *up { AdminState::Up } else { AdminState::Down }
+        }
+        NetIfOp::SetMtu { mtu } => it.mtu == Some(*mtu),
+        NetIfOp::AddIp { ip, prefix_len, .. } => find_addr(it, ip, *prefix_len).is_some(),
+        NetIfOp::DelIp { ip, prefix_len } => {
+            ip.parse::<IpAddr>().is_ok() && find_addr(it, ip, *prefix_len).is_none()
+        }
+        NetIfOp::SetIpv4Dhcp { enable: true } => {
+            it.ipv4.iter().any(|a| a.origin == Some(IpOrigin::Dhcp))
+        }
+        NetIfOp::SetIpv4Dhcp { enable: false } => {
+            !it.ipv4.is_empty() && it.ipv4.iter().all(|a| a.origin == Some(IpOrigin::Static))
+        }
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => static_applied(&it.ipv4, it, ip, *prefix_len, gateway.as_deref(), &it.ipv4_gateway),
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => static_applied(&it.ipv6, it, ip, *prefix_len, gateway.as_deref(), &it.ipv6_gateway),
+        NetIfOp::SetName { new_name } => it.name == *new_name,
+        NetIfOp::SetMac { mac } => it
+            .mac
+            .as_deref()
+            .is_some_and(|cur| mac_hex(cur) == mac_hex(mac)),
+        _ => false,
+    }
+}
+
+fn find_addr<'a>(it: &'a NetInterface, ip: &str, prefix_len: u8) -> Option<&'a IpAddrEntry> {
+    let want = ip.parse::<IpAddr>().ok()?;
+    it.ipv4.iter().chain(&it.ipv6).find(|a| {
+        a.prefix_len == prefix_len
+            && a.ip.split('%').next().and_then(|v| v.parse::<IpAddr>().ok()) == Some(want)
+    })
+}
+
+// 静态配置要求地址存在且不是 DHCP 下发的；指定了网关时网关也要一致
+fn static_applied(
+    family: &[IpAddrEntry],
+    it: &NetInterface,
+    ip: &str,
+    prefix_len: u8,
+    gateway: Option<&str>,
+    current_gateway: &Option<String>,
+) -> bool {
+    if find_addr(it, ip, prefix_len).is_none()
+        || family.iter().any(|a| a.origin == Some(IpOrigin::Dhcp))
+    {
+        return false;
+    }
+    match gateway {
+        Some(g) => {
+            let want = g.parse::<IpAddr>().ok();
+            want.is_some() && current_gateway.as_deref().and_then(|c| c.parse().ok()) == want
+        }
+        None => true,
+    }
+}
+
+fn mac_hex(mac: &str) -> String {
+    mac.chars()
+        .filter(char::is_ascii_hexdigit)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+pub fn validate_netif_op(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    match op {
+        NetIfOp::SetAdminState { .. } => Ok(()),
+        NetIfOp::SetMtu { mtu } => {
+            if *mtu == 0 {
+                return Err(ForgeFfiError::invalid_argument("mtu 不能为 0"));
+            }
+            Ok(())
+        }
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            if *valid_lft_secs == Some(0) {
+                return Err(ForgeFfiError::invalid_argument("valid_lft_secs 不能为 0"));
+            }
+            if let Some((valid, preferred)) = resolve_lifetimes(*valid_lft_secs, *preferred_lft_secs)
+                && preferred > valid
+            {
+                return Err(ForgeFfiError::invalid_argument(
+                    "preferred_lft_secs 不能大于 valid_lft_secs".to_string(),
+                ));
+            }
+            if *prefix_len == 0 {
+                return Err(ForgeFfiError::invalid_argument(
+                    "添加 IP 不允许 prefix_len=0".to_string(),
+                ));
+            }
+            let addr: std::net::IpAddr = ip
+                .parse()
+                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
+            match addr {
+                std::net::IpAddr::V4(_) => {
+                    if *prefix_len > 32 {
+                        return Err(ForgeFfiError::invalid_argument(
+                            "IPv4 prefix_len 必须在 0..=32".to_string(),
+                        ));
+                    }
+                }
+                std::net::IpAddr::V6(_) => {
+                    if *prefix_len > 128 {
+                        return Err(ForgeFfiError::invalid_argument(
+                            "IPv6 prefix_len 必须在 0..=128".to_string(),
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::DelIp { ip, prefix_len } => {
+            let addr: std::net::IpAddr = ip
+                .parse()
+                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
+            match addr {
+                std::net::IpAddr::V4(_) => {
+                    if *prefix_len > 32 {
+                        return Err(ForgeFfiError::invalid_argument(
+                            "IPv4 prefix_len 必须在 0..=32".to_string(),
+                        ));
+                    }
+                }
+                std::net::IpAddr::V6(_) => {
+                    if *prefix_len > 128 {
+                        return Err(ForgeFfiError::invalid_argument(
+                            "IPv6 prefix_len 必须在 0..=128".to_string(),
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::SetIpv4Dhcp { .. } => Ok(()),
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => {
+            if *prefix_len == 0 {
+                return Err(ForgeFfiError::invalid_argument(
+                    "IPv4 prefix_len 必须在 1..=32".to_string(),
+                ));
+            }
+            if *prefix_len > 32 {
+                return Err(ForgeFfiError::invalid_argument(
+                    "IPv4 prefix_len 必须在 1..=32".to_string(),
+                ));
+            }
+            let addr: std::net::IpAddr = ip
+                .parse()
+                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
+            if !matches!(addr, std::net::IpAddr::V4(_)) {
+                return Err(ForgeFfiError::invalid_argument(
+                    "SetIpv4Static 仅支持 IPv4".to_string(),
+                ));
+            }
+            if let Some(gw) = gateway {
+                let gw_addr: std::net::IpAddr = gw
+                    .parse()
+                    .map_err(|_| ForgeFfiError::invalid_argument(format!("非法网关: {gw}")))?;
+                if !matches!(gw_addr, std::net::IpAddr::V4(_)) {
+                    return Err(ForgeFfiError::invalid_argument(
+                        "网关必须是 IPv4".to_string(),
+                    ));
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::SetIpv6Mode { .. } => Ok(()),
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => {
+            if *prefix_len == 0 || *prefix_len > 128 {
+                return Err(ForgeFfiError::invalid_argument(
+                    "IPv6 prefix_len 必须在 1..=128".to_string(),
+                ));
+            }
+            if ip.parse::<std::net::Ipv6Addr>().is_err() {
+                return Err(ForgeFfiError::invalid_argument(format!("非法 IPv6 地址: {ip}")));
+            }
+            if let Some(gw) = gateway {
+                let bare = gw.split('%').next().unwrap_or(gw);
+                if bare.parse::<std::net::Ipv6Addr>().is_err() {
+                    return Err(ForgeFfiError::invalid_argument(format!("非法 IPv6 网关: {gw}")));
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::CreateVlan {
+            parent,
+            vlan_id,
+            name,
+        } => {
+            validate_ifname("parent", parent)?;
+            if !(1..=4094).contains(vlan_id) {
+                return Err(ForgeFfiError::invalid_argument(
+                    "vlan_id 必须在 1..=4094".to_string(),
+                ));
+            }
+            if let Some(name) = name {
+                validate_ifname("name", name)?;
+            }
+            Ok(())
+        }
+        NetIfOp::DeleteVlan => Ok(()),
+        NetIfOp::CreateBridge { name } => validate_ifname("name", name),
+        NetIfOp::DeleteBridge => Ok(()),
+        NetIfOp::AddBridgeMember { bridge, member } => {
+            validate_ifname("bridge", bridge)?;
+            validate_ifname("member", member)
+        }
+        NetIfOp::RemoveBridgeMember => Ok(()),
+        NetIfOp::CreateDummy { name } => validate_ifname("name", name),
+        NetIfOp::DeleteInterface => Ok(()),
+        NetIfOp::CreateBond { name, members, .. } => {
+            validate_ifname("name", name)?;
+            for (i, m) in members.iter().enumerate() {
+                validate_ifname(&format!("members[{i}]"), m)?;
+                if m == name || members[..i].contains(m) {
+                    return Err(ForgeFfiError::invalid_argument(format!("members[{i}] 重复: {m}")));
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::DeleteBond => Ok(()),
+        NetIfOp::AddBondMember { bond, member } => {
+            validate_ifname("bond", bond)?;
+            validate_ifname("member", member)
+        }
+        NetIfOp::RemoveBondMember => Ok(()),
+        NetIfOp::CreateWireguard {
+            name,
+            private_key,
+            listen_port,
+            peers,
+        } => {
+            validate_ifname("name", name)?;
+            validate_wireguard_key("private_key", private_key)?;
+            if *listen_port == Some(0) {
+                return Err(ForgeFfiError::invalid_argument("listen_port 不能为 0"));
+            }
+            for (i, p) in peers.iter().enumerate() {
+                validate_wireguard_peer(&format!("peers[{i}]"), p)?;
+                if peers[..i].iter().any(|q| q.public_key == p.public_key) {
+                    return Err(ForgeFfiError::invalid_argument(format!(
+                        "peers[{i}].public_key 重复: {}",
+                        p.public_key
+                    )));
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::SetWireguardPeer { peer } => validate_wireguard_peer("peer", peer),
+        NetIfOp::RemoveWireguardPeer { public_key } => validate_wireguard_key("public_key", public_key),
+        NetIfOp::CreateIpTunnel {
+            name,
+            kind,
+            remote,
+            local,
+            ttl,
+        } => {
+            validate_ifname("name", name)?;
+            if !matches!(kind, TunnelKind::Gre | TunnelKind::Ipip | TunnelKind::Sit) {
+                return Err(ForgeFfiError::invalid_argument(format!(
+                    "CreateIpTunnel 的 kind 只能为 gre、ipip 或 sit: {kind:?}"
+                )));
+            }
+            if remote.parse::<std::net::Ipv4Addr>().is_err() {
+                return Err(ForgeFfiError::invalid_argument(format!("非法 IPv4 remote: {remote}")));
+            }
+            if let Some(local) = local
+                && local.parse::<std::net::Ipv4Addr>().is_err()
+            {
+                return Err(ForgeFfiError::invalid_argument(format!("非法 IPv4 local: {local}")));
+            }
+            if *ttl == Some(0) {
+                return Err(ForgeFfiError::invalid_argument("ttl 不能为 0（继承内层 TTL 时省略该字段）"));
+            }
+            Ok(())
+        }
+        NetIfOp::DeleteTunnel => Ok(()),
+        NetIfOp::AddRoute { route } | NetIfOp::DelRoute { route } => validate_static_route(route),
+        NetIfOp::AddRoutingRule { rule } | NetIfOp::DelRoutingRule { rule } => validate_routing_rule(rule),
+        NetIfOp::SetDnsSuffix { suffix } => validate_dns_suffix(suffix),
+        NetIfOp::SetDnsRegistration { .. } => Ok(()),
+        // 具体语法由各平台在执行时用 validate_linux_ifname / validate_windows_ifname 校验
+        NetIfOp::SetName { new_name } => validate_ifname("new_name", new_name),
+        NetIfOp::SetMac { mac } => parse_mac(mac).map(|_| ()),
+        NetIfOp::SetLinkSpeed { speed_mbps, duplex } => {
+            if *speed_mbps == Some(0) {
+                return Err(ForgeFfiError::invalid_argument("speed_mbps 不能为 0"));
+            }
+            if speed_mbps.is_none() && duplex.is_some() {
+                return Err(ForgeFfiError::invalid_argument(
+                    "指定 duplex 时必须同时指定 speed_mbps（自动协商不能单独固定双工）",
+                ));
+            }
+            Ok(())
+        }
+        NetIfOp::SetBandwidthLimit {
+            egress_mbps,
+            ingress_mbps,
+        } => {
+            if *egress_mbps == Some(0) || *ingress_mbps == Some(0) {
+                return Err(ForgeFfiError::invalid_argument(
+                    "egress_mbps/ingress_mbps 不能为 0（取消限速时省略该字段）",
+                ));
+            }
+            Ok(())
+        }
+        NetIfOp::SetDescription { text } => {
+            // ifalias 上限 IFALIASZ-1
+            if text.len() > 255 {
+                return Err(ForgeFfiError::invalid_argument("描述不能超过 255 字节"));
+            }
+            if text.chars().any(char::is_control) {
+                return Err(ForgeFfiError::invalid_argument("描述不能包含控制字符"));
+            }
+            Ok(())
+        }
+        NetIfOp::SwitchNetworkLocation { name } => validate_ifname("name", name),
+        NetIfOp::SetServiceOrder { services } => {
+            if services.is_empty() {
+                return Err(ForgeFfiError::invalid_argument("services 不能为空"));
+            }
+            for s in services {
+                validate_ifname("services[]", s)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+// wg 密钥为 32 字节的标准 base64：43 个字符加一个 '='
+pub fn validate_wireguard_key(field: &str, key: &str) -> Result<(), ForgeFfiError> {
+    let body = key.strip_suffix('=').unwrap_or_default();
+    if body.len() != 43 || !body.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/') {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{field} 不是合法的 WireGuard 密钥（32 字节 base64）"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_wireguard_peer(field: &str, peer: &WireguardPeer) -> Result<(), ForgeFfiError> {
+    validate_wireguard_key(&format!("{field}.public_key"), &peer.public_key)?;
+    if let Some(psk) = &peer.preshared_key {
+        validate_wireguard_key(&format!("{field}.preshared_key"), psk)?;
+    }
+    if let Some(ep) = &peer.endpoint {
+        let port = ep.rsplit_once(':').and_then(|(host, port)| {
+            let host = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')).unwrap_or(host);
+            (!host.is_empty() && !host.contains(char::is_whitespace)).then_some(port)
+        });
+        if port.and_then(|p| p.parse::<u16>().ok()).is_none_or(|p| p == 0) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "{field}.endpoint 必须为 host:port: {ep}"
+            )));
+        }
+    }
+    for (i, cidr) in peer.allowed_ips.iter().enumerate() {
+        if parse_cidr(cidr).is_none() {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "{field}.allowed_ips[{i}] 必须为 ip/prefix_len: {cidr}"
+            )));
+        }
+    }
+    if peer.persistent_keepalive_secs == Some(0) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{field}.persistent_keepalive_secs 不能为 0（关闭时省略该字段）"
+        )));
+    }
+    Ok(())
+}
+
+// ip/prefix_len；prefix_len 超出地址族范围时返回 None
+pub fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+    let (ip, len) = cidr.split_once('/')?;
+    let ip = ip.parse::<IpAddr>().ok()?;
+    let len = len.parse::<u8>().ok()?;
+    let max = if ip.is_ipv4() { 32 } else { 128 };
+    (len <= max).then_some((ip, len))
+}
+
+// 路由目的与规则的 from/to 必须是网络地址（主机位为 0），与 ip route/ip rule 的要求一致
+fn parse_network(field: &str, cidr: &str) -> Result<IpAddr, ForgeFfiError> {
+    let (ip, len) = parse_cidr(cidr)
+        .ok_or_else(|| ForgeFfiError::invalid_argument(format!("{field} 必须为 ip/prefix_len: {cidr}")))?;
+    let (bits, width) = match ip {
+        IpAddr::V4(v) => (u128::from(u32::from(v)), 32),
+        IpAddr::V6(v) => (u128::from(v), 128),
+    };
+    let host_mask = u128::MAX.checked_shr(128 - (width - u32::from(len))).unwrap_or(0);
+    if bits & host_mask != 0 {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{field} 的主机位不为 0（应写网络地址）: {cidr}"
+        )));
+    }
+    Ok(ip)
+}
+
+fn check_family(field: &str, value: &str, ipv6: bool) -> Result<(), ForgeFfiError> {
+    let ip = value
+        .parse::<IpAddr>()
+        .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP {field}: {value}")))?;
+    if ip.is_ipv6() != ipv6 {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{field} 与路由目的的地址族不一致: {value}"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_static_route(route: &StaticRoute) -> Result<(), ForgeFfiError> {
+    let ipv6 = parse_network("dest", &route.dest)?.is_ipv6();
+    if let Some(gw) = &route.gateway {
+        check_family("gateway", gw, ipv6)?;
+    }
+    if let Some(src) = &route.src {
+        check_family("src", src, ipv6)?;
+    }
+    if route.table == Some(0) {
+        return Err(ForgeFfiError::invalid_argument("table 不能为 0（main 表省略该字段）"));
+    }
+    Ok(())
+}
+
+fn validate_routing_rule(rule: &RoutingRule) -> Result<(), ForgeFfiError> {
+    if rule.table == 0 {
+        return Err(ForgeFfiError::invalid_argument("table 不能为 0"));
+    }
+    let from = rule.from.as_deref().map(|c| parse_network("from", c)).transpose()?;
+    let to = rule.to.as_deref().map(|c| parse_network("to", c)).transpose()?;
+    if let (Some(from), Some(to)) = (from, to)
+        && from.is_ipv6() != to.is_ipv6()
+    {
+        return Err(ForgeFfiError::invalid_argument("from 与 to 的地址族不一致"));
+    }
+    if let Some(ip) = from.or(to)
+        && rule.ipv6
+        && !ip.is_ipv6()
+    {
+        return Err(ForgeFfiError::invalid_argument("ipv6=true 但 from/to 为 IPv4"));
+    }
+    for (field, name) in [("iif", &rule.iif), ("oif", &rule.oif)] {
+        if let Some(name) = name {
+            validate_ifname(field, name)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_dns_suffix(suffix: &str) -> Result<(), ForgeFfiError> {
+    if suffix.is_empty() {
+        return Ok(());
+    }
+    if suffix.len() > 253 {
+        return Err(ForgeFfiError::invalid_argument("DNS 后缀不能超过 253 个字符"));
+    }
+    let label_ok = |l: &str| {
+        !l.is_empty()
+            && l.len() <= 63
+            && !l.starts_with('-')
+            && !l.ends_with('-')
+            && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if !suffix.trim_end_matches('.').split('.').all(label_ok) {
+        return Err(ForgeFfiError::invalid_argument(format!("非法 DNS 后缀: {suffix}")));
+    }
+    Ok(())
+}
+
+pub fn validate_apply_request(req: &NetIfApplyRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    let has_target = req.target.if_index.is_some_and(|v| v != 0) || req.target.name.is_some();
+    if !has_target && req.ops.iter().any(|s| op_uses_target(&s.op)) {
+        return Err(ForgeFfiError::invalid_argument(
+            "target 必须至少包含 if_index 或 name".to_string(),
+        ));
+    }
+    for (i, spec) in req.ops.iter().enumerate() {
+        if spec.timeout_ms == Some(0) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "ops[{i}]: timeout_ms 不能为 0"
+            )));
+        }
+        validate_netif_op(&spec.op).map_err(|e| ForgeFfiError {
+            message: format!("ops[{i}]: {}", e.message),
+            ..e
+        })?;
+    }
+    validate_commit_confirm(req)
+}
+
+pub const NETIF_CONFIRM_MAX_SECS: u32 = 3600;
+
+// 回滚靠执行前的快照 restore，只接受快照能还原的 op；改名、删除、创建类 op 无法按快照撤销
+pub fn validate_commit_confirm(req: &NetIfApplyRequest) -> Result<(), ForgeFfiError> {
+    let Some(secs) = req.confirm_timeout_secs else {
+        return Ok(());
+    };
+    if secs == 0 || secs > NETIF_CONFIRM_MAX_SECS {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "confirm_timeout_secs 必须在 1..={NETIF_CONFIRM_MAX_SECS} 之间: {secs}"
+        )));
+    }
+    if req.ops.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("commit-confirm 模式下 ops 不能为空".to_string()));
+    }
+    for (i, spec) in req.ops.iter().enumerate() {
+        if !matches!(
+            spec.op,
+            NetIfOp::SetAdminState { .. }
+                | NetIfOp::SetMtu { .. }
+                | NetIfOp::AddIp { .. }
+                | NetIfOp::DelIp { .. }
+                | NetIfOp::SetIpv4Dhcp { .. }
+                | NetIfOp::SetIpv4Static { .. }
+        ) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "ops[{i}]: commit-confirm 只支持 set_admin_state/set_mtu/add_ip/del_ip/set_ipv4_dhcp/set_ipv4_static"
+            )));
+        }
+        // 只写配置的变更不影响当前连通性，确认没有意义，回滚也无法只撤销配置
+        if spec.scope == Some(NetIfScope::Persistent) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "ops[{i}]: commit-confirm 不支持 scope=persistent"
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub const NETIF_MULTI_MAX_TARGETS: usize = 256;
+pub const NETIF_MULTI_MAX_PARALLEL: u32 = 32;
+pub const NETIF_MULTI_DEFAULT_PARALLEL: u32 = 8;
+
+pub fn validate_multi_apply_request(req: &NetIfMultiApplyRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if req.targets.is_empty() || req.targets.len() > NETIF_MULTI_MAX_TARGETS {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "targets 数量必须在 1..={NETIF_MULTI_MAX_TARGETS} 之间"
+        )));
+    }
+    if let Some(n) = req.max_parallel
+        && (n == 0 || n > NETIF_MULTI_MAX_PARALLEL)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "max_parallel 必须在 1..={NETIF_MULTI_MAX_PARALLEL} 之间: {n}"
+        )));
+    }
+    for (i, t) in req.targets.iter().enumerate() {
+        if !(t.if_index.is_some_and(|v| v != 0) || t.name.is_some()) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "targets[{i}]: 必须至少包含 if_index 或 name"
+            )));
+        }
+        // 同一网卡并发执行会互相踩踏
+        if req.targets[..i].contains(t) {
+            return Err(ForgeFfiError::invalid_argument(format!("targets[{i}]: 与前面的 target 重复")));
+        }
+    }
+    // 与网卡无关的 op 会被每个 target 重复执行，批量请求里不允许
+    if let Some(i) = req.ops.iter().position(|s| !op_uses_target(&s.op)) {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "ops[{i}]: 该 op 不作用于单个网卡，不能用于批量请求"
+        )));
+    }
+    for (i, spec) in req.ops.iter().enumerate() {
+        if spec.timeout_ms == Some(0) {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "ops[{i}]: timeout_ms 不能为 0"
+            )));
+        }
+        validate_netif_op(&spec.op).map_err(|e| ForgeFfiError {
+            message: format!("ops[{i}]: {}", e.message),
+            ..e
+        })?;
+    }
+    Ok(())
+}
+
+// NetInterface 序列化后的顶层字段名，供 list 投影校验
+pub const NETIF_LIST_FIELDS: &[&str] = &[
+    "if_index",
+    "name",
+    "display_name",
+    "kind",
+    "is_physical",
+    "admin_state",
+    "oper_state",
+    "flags",
+    "mac",
+    "mtu",
+    "speed_bps",
+    "vlan_id",
+    "ipv4",
+    "ipv6",
+    "default_routes",
+    "ipv4_gateway",
+    "ipv6_gateway",
+    "dns_servers",
+    "capabilities",
+];
+
+pub fn validate_list_request(req: &NetIfListRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if let Some(g) = &req.filter.names_glob
+        && (g.is_empty() || g.contains('\0'))
+    {
+        return Err(ForgeFfiError::invalid_argument("names_glob 不能为空或包含 NUL"));
+    }
+    if let Some(f) = req.fields.iter().find(|f| !NETIF_LIST_FIELDS.contains(&f.as_str())) {
+        return Err(ForgeFfiError::invalid_argument(format!("未知的字段: {f}")));
+    }
+    Ok(())
+}
+
+pub const NETIF_PAGE_DEFAULT: usize = 64;
+pub const NETIF_PAGE_MAX: usize = 1024;
+
+// cursor 是上一页最后一个网卡的 if_index；按 if_index 翻页，期间增删网卡不会重复返回。
+// 没有 cursor 时返回 None 表示从头开始，不能用 0 代替：个别平台上存在 if_index 为 0 的网卡
+pub fn parse_list_cursor(cursor: Option<&str>) -> Result<Option<u32>, ForgeFfiError> {
+    cursor
+        .map(|c| {
+            c.parse::<u32>()
+                .map_err(|_| ForgeFfiError::invalid_argument(format!("cursor 非法: {c}")))
+        })
+        .transpose()
+}
+
+pub fn resolve_page_size(page_size: u32) -> Result<usize, ForgeFfiError> {
+    match page_size as usize {
+        0 => Ok(NETIF_PAGE_DEFAULT),
+        n if n > NETIF_PAGE_MAX => Err(ForgeFfiError::invalid_argument(format!(
+            "page_size 不能超过 {NETIF_PAGE_MAX}: {n}"
+        ))),
+        n => Ok(n),
+    }
+}
+
+pub fn validate_reconcile_request(req: &NetIfReconcileRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if !(req.target.if_index.is_some_and(|v| v != 0) || req.target.name.is_some()) {
+        return Err(ForgeFfiError::invalid_argument(
+            "target 必须至少包含 if_index 或 name".to_string(),
+        ));
+    }
+    let d = &req.desired;
+    if let Some(mtu) = d.mtu {
+        validate_netif_op(&NetIfOp::SetMtu { mtu })?;
+    }
+    if d.admin_state == Some(AdminState::Unknown) {
+        return Err(ForgeFfiError::invalid_argument("admin_state 只能是 up 或 down"));
+    }
+    if let Some(addrs) = &d.addresses {
+        if d.dhcp == Some(true) && !addrs.is_empty() {
+            return Err(ForgeFfiError::invalid_argument(
+                "dhcp 为 true 时不能同时指定 addresses",
+            ));
+        }
+        for (i, a) in addrs.iter().enumerate() {
+            validate_netif_op(&NetIfOp::DelIp {
+                ip: a.ip.clone(),
+                prefix_len: a.prefix_len,
+            })
+            .map_err(|e| ForgeFfiError {
+                message: format!("addresses[{i}]: {}", e.message),
+                ..e
+            })?;
+            if addrs[..i].iter().any(|b| {
+                b.prefix_len == a.prefix_len && b.ip.parse::<IpAddr>().ok() == a.ip.parse().ok()
+            }) {
+                return Err(ForgeFfiError::invalid_argument(format!(
+                    "addresses[{i}]: 地址重复: {}/{}",
+                    a.ip, a.prefix_len
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 链路本地/主机地址和 DHCP 下发的地址由系统维护，不参与 reconcile/快照
+fn is_managed_addr(a: &IpAddrEntry) -> bool {
+    !(matches!(a.scope, Some(IpScope::Link | IpScope::Host))
+        || a.origin == Some(IpOrigin::Dhcp)
+        || a.ip.contains('%'))
+}
+
+// 顺序：先 down（如需要）、MTU、DHCP，再删多余地址、补缺失地址，最后 up
+pub fn plan_reconcile(desired: &NetIfDesiredState, it: &NetInterface) -> Vec<NetIfOp> {
+    let mut ops = Vec::new();
+    let admin = desired.admin_state.map(|s| NetIfOp::SetAdminState {
+        up: s == AdminState::Up,
+    });
+    if let Some(op @ NetIfOp::SetAdminState { up: false }) = &admin {
+        ops.push(op.clone());
+    }
+    if let Some(mtu) = desired.mtu {
+        ops.push(NetIfOp::SetMtu { mtu });
+    }
+    if let Some(enable) = desired.dhcp {
+        ops.push(NetIfOp::SetIpv4Dhcp { enable });
+    }
+    if let Some(want) = &desired.addresses {
+        let wanted = |a: &IpAddrEntry| {
+            let ip = a.ip.split('%').next().and_then(|v| v.parse::<IpAddr>().ok());
+            want.iter()
+                .any(|w| w.prefix_len == a.prefix_len && w.ip.parse::<IpAddr>().ok() == ip)
+        };
+        for a in it.ipv4.iter().chain(&it.ipv6) {
+            if is_managed_addr(a) && !wanted(a) {
+                ops.push(NetIfOp::DelIp {
+                    ip: a.ip.clone(),
+                    prefix_len: a.prefix_len,
+                });
+            }
+        }
+        for w in want {
+            ops.push(NetIfOp::AddIp {
+                ip: w.ip.clone(),
+                prefix_len: w.prefix_len,
+                wait_dad_ms: None,
+                valid_lft_secs: None,
+                preferred_lft_secs: None,
+            });
+        }
+    }
+    if let Some(op @ NetIfOp::SetAdminState { up: true }) = &admin {
+        ops.push(op.clone());
+    }
+    ops.retain(|op| !op_already_applied(op, it));
+    ops
+}
+
+// 与 plan_reconcile 互逆：对同一网卡 plan_reconcile(&desired_state_of(it), it) 为空
+pub fn desired_state_of(it: &NetInterface) -> NetIfDesiredState {
+    let dhcp = if it.ipv4.iter().any(|a| a.origin == Some(IpOrigin::Dhcp)) {
+        Some(true)
+    } else if !it.ipv4.is_empty() && it.ipv4.iter().all(|a| a.origin == Some(IpOrigin::Static)) {
+        Some(false)
+    } else {
+        None
+    };
+    let addresses = (dhcp != Some(true)).then(|| {
+        it.ipv4
+            .iter()
+            .chain(&it.ipv6)
+            .filter(|a| is_managed_addr(a))
+            .map(|a| NetIfDesiredAddr {
+                ip: a.ip.clone(),
+                prefix_len: a.prefix_len,
+            })
+            .collect()
+    });
+    NetIfDesiredState {
+        addresses,
+        mtu: it.mtu,
+        admin_state: Some(it.admin_state).filter(|s| *s != AdminState::Unknown),
+        dhcp,
+    }
+}
+
+pub fn validate_restore_request(req: &NetIfRestoreRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    check_abi(req.snapshot.abi).map_err(|e| ForgeFfiError {
+        message: format!("snapshot: {}", e.message),
+        ..e
+    })?;
+    for (i, entry) in req.snapshot.interfaces.iter().enumerate() {
+        let probe = NetIfReconcileRequest {
+            abi: req.abi,
+            target: IfaceSelector {
+                if_index: None,
+                name: Some(entry.name.clone()),
+            },
+            desired: entry.state.clone(),
+            dry_run: true,
+            deadline_ms: None,
+        };
+        validate_reconcile_request(&probe).map_err(|e| ForgeFfiError {
+            message: format!("interfaces[{i}] ({}): {}", entry.name, e.message),
+            ..e
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use forgeffi_base::{ForgeFfiError, NetProbeRequest, PingRequest};
+
+use crate::check_abi;
+
+pub const PROBE_DEFAULT_TIMEOUT_MS: u64 = 3000;
+pub const PROBE_MAX_TIMEOUT_MS: u64 = 60_000;
+
+pub const PING_DEFAULT_COUNT: u32 = 4;
+pub const PING_DEFAULT_INTERVAL_MS: u64 = 1000;
+pub const PING_DEFAULT_TIMEOUT_MS: u64 = 1000;
+pub const PING_DEFAULT_PAYLOAD_SIZE: u16 = 56;
+
+pub fn validate_probe_request(req: &NetProbeRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    validate_host(&req.host)?;
+    if req.port == 0 {
+        return Err(ForgeFfiError::invalid_argument("port 不能为 0"));
+    }
+    if let Some(ms) = req.timeout_ms
+        && !(1..=PROBE_MAX_TIMEOUT_MS).contains(&ms)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "timeout_ms 必须在 1..={PROBE_MAX_TIMEOUT_MS}"
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_ping_request(req: &PingRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    validate_host(&req.host)?;
+    if let Some(n) = req.count
+        && !(1..=100).contains(&n)
+    {
+        return Err(ForgeFfiError::invalid_argument("count 必须在 1..=100"));
+    }
+    // 系统 ping 对小于 200ms 的间隔需要 root
+    if let Some(ms) = req.interval_ms
+        && !(200..=10_000).contains(&ms)
+    {
+        return Err(ForgeFfiError::invalid_argument("interval_ms 必须在 200..=10000"));
+    }
+    if let Some(ms) = req.timeout_ms
+        && !(1..=PROBE_MAX_TIMEOUT_MS).contains(&ms)
+    {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "timeout_ms 必须在 1..={PROBE_MAX_TIMEOUT_MS}"
+        )));
+    }
+    if let Some(n) = req.payload_size
+        && n > 65_000
+    {
+        return Err(ForgeFfiError::invalid_argument("payload_size 不能超过 65000"));
+    }
+    Ok(())
+}
+
+fn validate_host(host: &str) -> Result<(), ForgeFfiError> {
+    if host.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("host 不能为空"));
+    }
+    // host 会传给系统 ping，禁止以 '-' 开头避免被当成参数
+    if host.starts_with('-') || host.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return Err(ForgeFfiError::invalid_argument(format!("host 含有非法字符: {host}")));
+    }
+    Ok(())
+}
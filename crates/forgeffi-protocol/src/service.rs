@@ -0,0 +1,52 @@
+use forgeffi_base::{ForgeFfiError, ServiceApplyRequest, ServiceOp, ServiceStatusRequest};
+
+use crate::check_abi;
+
+// 与 ServiceOp 的 "op" 标签一一对应
+pub const SERVICE_OPS: &[&str] = &["start", "stop", "restart", "enable", "disable"];
+
+#[must_use]
+pub fn service_op_tag(op: &ServiceOp) -> &'static str {
+    match op {
+        ServiceOp::Start { .. } => "start",
+        ServiceOp::Stop { .. } => "stop",
+        ServiceOp::Restart { .. } => "restart",
+        ServiceOp::Enable { .. } => "enable",
+        ServiceOp::Disable { .. } => "disable",
+    }
+}
+
+// 覆盖 systemd unit 名（含 \x2d 转义与模板 @）、SCM 服务名与 launchd label；
+// 名字会拼进命令行与 PowerShell 脚本，引号、路径分隔符与前导 '-' 一律拒绝
+pub fn validate_service_name(name: &str) -> Result<(), ForgeFfiError> {
+    if name.is_empty() || name.len() > 256 || name.trim() != name {
+        return Err(ForgeFfiError::invalid_argument(format!("服务名长度或首尾空白非法: {name:?}")));
+    }
+    if name.starts_with('-')
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " ._-@:+\\".contains(c))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("服务名含有非法字符: {name}")));
+    }
+    Ok(())
+}
+
+pub fn validate_service_status_request(req: &ServiceStatusRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    validate_service_name(&req.name)
+}
+
+pub fn validate_service_apply_request(req: &ServiceApplyRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if req.ops.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("ops 不能为空"));
+    }
+    for (i, op) in req.ops.iter().enumerate() {
+        validate_service_name(op.name()).map_err(|e| ForgeFfiError {
+            message: format!("ops[{i}]: {}", e.message),
+            ..e
+        })?;
+    }
+    Ok(())
+}
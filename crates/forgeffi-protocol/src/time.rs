@@ -0,0 +1,66 @@
+use forgeffi_base::{ForgeFfiError, TimeApplyRequest, TimeOp};
+
+use crate::check_abi;
+
+pub const TIME_MAX_NTP_SERVERS: usize = 8;
+
+pub fn validate_timezone(tz: &str) -> Result<(), ForgeFfiError> {
+    if tz.is_empty() || tz.len() > 64 || tz.trim() != tz {
+        return Err(ForgeFfiError::invalid_argument(format!("时区名称非法: {tz:?}")));
+    }
+    // 允许 IANA 名称与 Windows 时区 Id（含空格、括号、点号）
+    if tz.starts_with(['/', '-', '.'])
+        || tz.contains("..")
+        || !tz
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " _+-/().".contains(c))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("时区名称含有非法字符: {tz}")));
+    }
+    Ok(())
+}
+
+pub fn validate_ntp_server(server: &str) -> Result<(), ForgeFfiError> {
+    if server.is_empty() || server.len() > 253 {
+        return Err(ForgeFfiError::invalid_argument(format!("NTP 服务器长度非法: {server:?}")));
+    }
+    if server.starts_with('-')
+        || !server
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".-_:[]".contains(c))
+    {
+        return Err(ForgeFfiError::invalid_argument(format!("NTP 服务器含有非法字符: {server}")));
+    }
+    Ok(())
+}
+
+pub fn validate_time_op(op: &TimeOp) -> Result<(), ForgeFfiError> {
+    match op {
+        TimeOp::SetTimezone { timezone } => validate_timezone(timezone),
+        TimeOp::SetNtpServers { servers, enabled } => {
+            if servers.is_empty() && *enabled != Some(false) {
+                return Err(ForgeFfiError::invalid_argument("servers 不能为空"));
+            }
+            if servers.len() > TIME_MAX_NTP_SERVERS {
+                return Err(ForgeFfiError::invalid_argument(format!(
+                    "servers 最多 {TIME_MAX_NTP_SERVERS} 个"
+                )));
+            }
+            servers.iter().try_for_each(|s| validate_ntp_server(s))
+        }
+    }
+}
+
+pub fn validate_time_apply_request(req: &TimeApplyRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    if req.ops.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("ops 不能为空"));
+    }
+    for (i, op) in req.ops.iter().enumerate() {
+        validate_time_op(op).map_err(|e| ForgeFfiError {
+            message: format!("ops[{i}]: {}", e.message),
+            ..e
+        })?;
+    }
+    Ok(())
+}
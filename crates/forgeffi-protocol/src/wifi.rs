@@ -0,0 +1,47 @@
+use forgeffi_base::{ForgeFfiError, WifiConnectRequest};
+
+use crate::check_abi;
+
+pub fn validate_wifi_field(field: &str, v: &str) -> Result<(), ForgeFfiError> {
+    if v.is_empty() {
+        return Err(ForgeFfiError::invalid_argument(format!("{field} 不能为空")));
+    }
+    // 引号在 SSID 与 WPA 口令里都合法；各平台都以独立参数或转义后的 XML 传给系统工具，只拒绝 NUL 与控制字符
+    if v.chars().any(char::is_control) {
+        return Err(ForgeFfiError::invalid_argument(format!("{field} 含有非法字符")));
+    }
+    Ok(())
+}
+
+pub fn validate_ssid(ssid: &str) -> Result<(), ForgeFfiError> {
+    validate_wifi_field("ssid", ssid)?;
+    if ssid.len() > 32 {
+        return Err(ForgeFfiError::invalid_argument(
+            "ssid 长度必须在 1..=32 字节".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_psk(psk: &str) -> Result<(), ForgeFfiError> {
+    validate_wifi_field("psk", psk)?;
+    let is_hex_key = psk.len() == 64 && psk.chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex_key && !(8..=63).contains(&psk.len()) {
+        return Err(ForgeFfiError::invalid_argument(
+            "psk 必须为 8..=63 个字符或 64 位十六进制".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_connect_request(req: &WifiConnectRequest) -> Result<(), ForgeFfiError> {
+    check_abi(req.abi)?;
+    validate_ssid(&req.ssid)?;
+    if let Some(psk) = &req.psk {
+        validate_psk(psk)?;
+    }
+    if let Some(ifname) = &req.interface {
+        validate_wifi_field("interface", ifname)?;
+    }
+    Ok(())
+}
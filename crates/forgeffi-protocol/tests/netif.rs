@@ -0,0 +1,243 @@
+// netif 协议层纯函数（reconcile 计划、幂等判断、分页参数、名称校验）的用例
+use forgeffi_protocol::{
+    desired_state_of, op_already_applied, parse_list_cursor, plan_reconcile, resolve_page_size, validate_windows_ifname,
+    AdminState, ErrorCode, NetIfDesiredAddr, NetIfDesiredState, NetIfOp, NetInterface, NETIF_PAGE_DEFAULT,
+    NETIF_PAGE_MAX,
+};
+use serde_json::json;
+
+// 只需写出与用例相关的字段，其余取一块已启用、无地址的物理网卡
+fn iface(fields: serde_json::Value) -> NetInterface {
+    let mut v = json!({
+        "if_index": 2,
+        "name": "eth0",
+        "kind": "physical",
+        "admin_state": "up",
+        "flags": 0,
+        "mtu": 1500,
+        "capabilities": {
+            "can_set_admin_state": true,
+            "can_set_mtu": true,
+            "can_add_del_ip": true,
+            "can_set_dhcp": true,
+            "can_set_dns": true
+        }
+    });
+    for (k, x) in fields.as_object().expect("字段须为对象") {
+        v[k] = x.clone();
+    }
+    serde_json::from_value(v).expect("NetInterface")
+}
+
+fn addr(ip: &str, prefix_len: u8, origin: &str, scope: &str) -> serde_json::Value {
+    json!({"ip": ip, "prefix_len": prefix_len, "origin": origin, "scope": scope})
+}
+
+fn desired_addrs(list: &[(&str, u8)]) -> Option<Vec<NetIfDesiredAddr>> {
+    Some(
+        list.iter()
+            .map(|(ip, prefix_len)| NetIfDesiredAddr {
+                ip: ip.to_string(),
+                prefix_len: *prefix_len,
+            })
+            .collect(),
+    )
+}
+
+fn add_ip(ip: &str, prefix_len: u8) -> NetIfOp {
+    NetIfOp::AddIp {
+        ip: ip.to_string(),
+        prefix_len,
+        wait_dad_ms: None,
+        valid_lft_secs: None,
+        preferred_lft_secs: None,
+    }
+}
+
+fn del_ip(ip: &str, prefix_len: u8) -> NetIfOp {
+    NetIfOp::DelIp {
+        ip: ip.to_string(),
+        prefix_len,
+    }
+}
+
+#[test]
+fn desired_state_of_current_interface_plans_nothing() {
+    let cases = [
+        iface(json!({
+            "ipv4": [addr("192.0.2.10", 24, "static", "global"), addr("192.0.2.11", 24, "static", "global")],
+            "ipv6": [addr("2001:db8::10", 64, "static", "global"), addr("fe80::1%eth0", 64, "unknown", "link")],
+        })),
+        iface(json!({
+            "ipv4": [addr("198.51.100.20", 24, "dhcp", "global")],
+            "ipv6": [addr("fe80::2", 64, "unknown", "link")],
+        })),
+        iface(json!({"admin_state": "down", "mtu": 9000})),
+        iface(json!({"admin_state": "unknown", "mtu": null})),
+        // origin 未知时 dhcp 不受管理，地址仍逐个保留
+        iface(json!({"ipv4": [addr("203.0.113.5", 24, "unknown", "global")]})),
+        iface(json!({"name": "lo", "kind": "loopback", "ipv4": [addr("127.0.0.1", 8, "unknown", "host")]})),
+    ];
+    for it in &cases {
+        let desired = desired_state_of(it);
+        assert_eq!(plan_reconcile(&desired, it), [], "{}: {desired:?}", it.name);
+    }
+}
+
+#[test]
+fn reconcile_deletes_before_adding_between_admin_state_changes() {
+    let it = iface(json!({
+        "admin_state": "down",
+        "ipv4": [addr("192.0.2.10", 24, "static", "global"), addr("192.0.2.99", 24, "static", "global")],
+        "ipv6": [addr("fe80::1", 64, "unknown", "link")],
+    }));
+    let desired = NetIfDesiredState {
+        addresses: desired_addrs(&[("192.0.2.10", 24), ("198.51.100.7", 25)]),
+        mtu: Some(1400),
+        admin_state: Some(AdminState::Up),
+        dhcp: None,
+    };
+    // 已有的 192.0.2.10 不重复添加，链路本地地址不删除，up 放在最后
+    assert_eq!(
+        plan_reconcile(&desired, &it),
+        [
+            NetIfOp::SetMtu { mtu: 1400 },
+            del_ip("192.0.2.99", 24),
+            add_ip("198.51.100.7", 25),
+            NetIfOp::SetAdminState { up: true },
+        ]
+    );
+
+    let it = iface(json!({"ipv4": [addr("192.0.2.10", 24, "static", "global")]}));
+    let desired = NetIfDesiredState {
+        addresses: desired_addrs(&[("192.0.2.20", 24)]),
+        admin_state: Some(AdminState::Down),
+        ..Default::default()
+    };
+    // down 放在最前，避免改地址期间链路仍在收发
+    assert_eq!(
+        plan_reconcile(&desired, &it),
+        [
+            NetIfOp::SetAdminState { up: false },
+            del_ip("192.0.2.10", 24),
+            add_ip("192.0.2.20", 24),
+        ]
+    );
+}
+
+#[test]
+fn reconcile_matches_addresses_by_value_and_keeps_dhcp_ones() {
+    let it = iface(json!({
+        "ipv4": [addr("198.51.100.20", 24, "dhcp", "global")],
+        "ipv6": [addr("2001:db8::1", 64, "static", "global")],
+    }));
+    let desired = NetIfDesiredState {
+        addresses: desired_addrs(&[("2001:0db8:0000::0001", 64)]),
+        ..Default::default()
+    };
+    assert_eq!(plan_reconcile(&desired, &it), []);
+
+    // 前缀长度不同视为不同地址
+    let desired = NetIfDesiredState {
+        addresses: desired_addrs(&[("2001:db8::1", 48)]),
+        ..Default::default()
+    };
+    assert_eq!(plan_reconcile(&desired, &it), [del_ip("2001:db8::1", 64), add_ip("2001:db8::1", 48)]);
+
+    // addresses 为空表示清空受管理的地址
+    let desired = NetIfDesiredState {
+        addresses: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert_eq!(plan_reconcile(&desired, &it), [del_ip("2001:db8::1", 64)]);
+}
+
+#[test]
+fn list_cursor_and_page_size() {
+    assert_eq!(parse_list_cursor(None).ok(), Some(None), "第一页从头开始");
+    // 0 是合法的 if_index，作为 cursor 时表示从它之后开始
+    assert_eq!(parse_list_cursor(Some("0")).ok(), Some(Some(0)));
+    assert_eq!(parse_list_cursor(Some("42")).ok(), Some(Some(42)));
+    assert_eq!(parse_list_cursor(Some("4294967295")).ok(), Some(Some(u32::MAX)));
+    for bad in ["", "-1", "abc", " 7", "4294967296", "0x10"] {
+        let err = parse_list_cursor(Some(bad)).expect_err(bad);
+        assert_eq!(err.code, ErrorCode::InvalidArgument, "{bad:?}");
+    }
+
+    assert_eq!(resolve_page_size(0).ok(), Some(NETIF_PAGE_DEFAULT), "0 取默认页大小");
+    assert_eq!(resolve_page_size(1).ok(), Some(1));
+    assert_eq!(resolve_page_size(NETIF_PAGE_MAX as u32).ok(), Some(NETIF_PAGE_MAX));
+    assert_eq!(
+        resolve_page_size(NETIF_PAGE_MAX as u32 + 1).map_err(|e| e.code).err(),
+        Some(ErrorCode::InvalidArgument)
+    );
+}
+
+#[test]
+fn op_already_applied_only_trusts_listable_state() {
+    let it = iface(json!({
+        "mac": "02:AB:CD:00:00:01",
+        "ipv4": [addr("192.0.2.10", 24, "static", "global")],
+        "ipv6": [addr("2001:db8::1", 64, "static", "global")],
+        "ipv4_gateway": "192.0.2.1",
+    }));
+    let static_v4 = |ip: &str, gateway: Option<&str>| NetIfOp::SetIpv4Static {
+        ip: ip.to_string(),
+        prefix_len: 24,
+        gateway: gateway.map(str::to_string),
+    };
+    let applied = [
+        NetIfOp::SetAdminState { up: true },
+        NetIfOp::SetMtu { mtu: 1500 },
+        add_ip("192.0.2.10", 24),
+        add_ip("2001:0db8::0001", 64),
+        del_ip("192.0.2.99", 24),
+        NetIfOp::SetIpv4Dhcp { enable: false },
+        static_v4("192.0.2.10", None),
+        static_v4("192.0.2.10", Some("192.0.2.1")),
+        NetIfOp::SetName {
+            new_name: "eth0".to_string(),
+        },
+        NetIfOp::SetMac {
+            mac: "02-ab-cd-00-00-01".to_string(),
+        },
+    ];
+    for op in &applied {
+        assert!(op_already_applied(op, &it), "应判为已生效: {op:?}");
+    }
+    let pending = [
+        NetIfOp::SetAdminState { up: false },
+        NetIfOp::SetMtu { mtu: 9000 },
+        add_ip("192.0.2.10", 25),
+        del_ip("192.0.2.10", 24),
+        // 非法地址照常执行，由执行层报错
+        del_ip("not-an-ip", 24),
+        NetIfOp::SetIpv4Dhcp { enable: true },
+        static_v4("192.0.2.10", Some("192.0.2.254")),
+        static_v4("192.0.2.11", None),
+        NetIfOp::SetMac {
+            mac: "02:ab:cd:00:00:02".to_string(),
+        },
+        NetIfOp::CreateDummy {
+            name: "dummy0".to_string(),
+        },
+    ];
+    for op in &pending {
+        assert!(!op_already_applied(op, &it), "不应判为已生效: {op:?}");
+    }
+
+    // DHCP 下发的地址不能满足静态配置；没有地址时不能认为已关闭 DHCP
+    let dhcp = iface(json!({"ipv4": [addr("192.0.2.10", 24, "dhcp", "global")]}));
+    assert!(op_already_applied(&NetIfOp::SetIpv4Dhcp { enable: true }, &dhcp));
+    assert!(!op_already_applied(&static_v4("192.0.2.10", None), &dhcp));
+    assert!(!op_already_applied(&NetIfOp::SetIpv4Dhcp { enable: false }, &iface(json!({}))));
+}
+
+#[test]
+fn windows_ifname_rejects_powershell_quotes() {
+    assert!(validate_windows_ifname("以太网 2").is_ok());
+    for bad in ["a'b", "a\u{2018}b", "a\u{2019}b", "a\u{201A}b", "a\u{201B}b", "a`b", "a$b"] {
+        let err = validate_windows_ifname(bad).expect_err(bad);
+        assert_eq!(err.code, ErrorCode::InvalidArgument, "{bad:?}");
+    }
+}
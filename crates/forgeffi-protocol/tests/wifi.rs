@@ -0,0 +1,14 @@
+// wifi 参数校验的用例
+use forgeffi_protocol::{validate_psk, validate_ssid, ErrorCode};
+
+#[test]
+fn quotes_are_legal_but_control_chars_are_not() {
+    assert!(validate_ssid("Bob's \"Home\"").is_ok());
+    assert!(validate_psk("pa'ss\"word").is_ok());
+    for bad in ["a\0b", "a\nb", "a\tb"] {
+        let err = validate_ssid(bad).expect_err(bad);
+        assert_eq!(err.code, ErrorCode::InvalidArgument, "{bad:?}");
+        let err = validate_psk(&format!("{bad}passwd")).expect_err(bad);
+        assert_eq!(err.code, ErrorCode::InvalidArgument, "{bad:?}");
+    }
+}
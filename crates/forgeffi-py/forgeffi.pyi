@@ -0,0 +1,11 @@
+# 返回结构与 C ABI 的 JSON 一致（snake_case 字段），完整字段见 forgeffi-base/src/netif.rs
+from typing import Any, Dict
+
+class ForgeFfiError(Exception):
+    # args == (code, message)，code 为 ErrorCode 名称，例如 "PermissionDenied"
+    ...
+
+def abi_version() -> int: ...
+def list_interfaces() -> Dict[str, Any]: ...
+# req 的 abi 字段可省略
+def apply(req: Dict[str, Any]) -> Dict[str, Any]: ...
@@ -0,0 +1,127 @@
+// Python 扩展模块：直接调用 forgeffi-sys，返回 dict/list 等原生对象，不经过 ctypes 与 JSON 文本。
+// 系统调用期间释放 GIL；失败抛出 forgeffi.ForgeFfiError(code, message)，code 为 ErrorCode 名称
+use forgeffi_base::{ForgeFfiError, NetIfApplyRequest, ABI_VERSION};
+use forgeffi_protocol::check_abi;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::{Map, Number, Value};
+
+create_exception!(forgeffi, PyForgeFfiError, PyException);
+
+#[pyfunction]
+fn abi_version() -> u32 {
+    ABI_VERSION
+}
+
+#[pyfunction]
+fn list_interfaces(py: Python<'_>) -> PyResult<Py<PyAny>> {
+    let resp = py.detach(forgeffi_sys::netif::list_response).map_err(to_py_err)?;
+    to_py(py, &to_value(resp)?)
+}
+
+// req 与 tool_netif_apply_json 的请求结构相同，abi 可省略
+#[pyfunction]
+fn apply(py: Python<'_>, req: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
+    let mut req = from_py(req.as_any())?;
+    if let Value::Object(m) = &mut req {
+        m.entry("abi").or_insert(Value::from(ABI_VERSION));
+    }
+    let req: NetIfApplyRequest = serde_json::from_value(req)
+        .map_err(|e| to_py_err(ForgeFfiError::invalid_argument(format!("请求格式错误: {e}"))))?;
+    check_abi(req.abi).map_err(to_py_err)?;
+    let resp = py
+        .detach(|| forgeffi_sys::netif::apply_request(req))
+        .map_err(to_py_err)?;
+    to_py(py, &to_value(resp)?)
+}
+
+#[pymodule]
+#[pyo3(name = "forgeffi")]
+fn forgeffi_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ForgeFfiError", m.py().get_type::<PyForgeFfiError>())?;
+    m.add_function(wrap_pyfunction!(abi_version, m)?)?;
+    m.add_function(wrap_pyfunction!(list_interfaces, m)?)?;
+    m.add_function(wrap_pyfunction!(apply, m)?)?;
+    Ok(())
+}
+
+fn to_py_err(e: ForgeFfiError) -> PyErr {
+    PyForgeFfiError::new_err((format!("{:?}", e.code), e.message))
+}
+
+fn to_value<T: serde::Serialize>(v: T) -> PyResult<Value> {
+    serde_json::to_value(v)
+        .map_err(|e| to_py_err(ForgeFfiError::system_error(format!("序列化响应失败: {e}"))))
+}
+
+fn to_py(py: Python<'_>, v: &Value) -> PyResult<Py<PyAny>> {
+    Ok(match v {
+        Value::Null => py.None(),
+        Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any().unbind(),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.into_pyobject(py)?.into_any().unbind()
+            } else if let Some(u) = n.as_u64() {
+                u.into_pyobject(py)?.into_any().unbind()
+            } else {
+                n.as_f64().unwrap_or(f64::NAN).into_pyobject(py)?.into_any().unbind()
+            }
+        }
+        Value::String(s) => PyString::new(py, s).into_any().unbind(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for it in items {
+                list.append(to_py(py, it)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (k, it) in map {
+                dict.set_item(k, to_py(py, it)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+fn from_py(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    // bool 是 int 的子类，必须先判断
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = obj.cast::<PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if obj.is_instance_of::<PyInt>() {
+        match obj.extract::<i64>() {
+            Ok(i) => Ok(Value::from(i)),
+            Err(_) => Ok(Value::from(obj.extract::<u64>()?)),
+        }
+    } else if let Ok(f) = obj.cast::<PyFloat>() {
+        Number::from_f64(f.value())
+            .map(Value::Number)
+            .ok_or_else(|| to_py_err(ForgeFfiError::invalid_argument("请求中包含 NaN/Inf")))
+    } else if let Ok(s) = obj.cast::<PyString>() {
+        Ok(Value::String(s.to_cow()?.into_owned()))
+    } else if let Ok(d) = obj.cast::<PyDict>() {
+        let mut map = Map::new();
+        for (k, v) in d.iter() {
+            let k = k
+                .cast::<PyString>()
+                .map_err(|_| to_py_err(ForgeFfiError::invalid_argument("dict 的键必须是 str")))?;
+            map.insert(k.to_cow()?.into_owned(), from_py(&v)?);
+        }
+        Ok(Value::Object(map))
+    } else if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        obj.try_iter()?
+            .map(|it| from_py(&it?))
+            .collect::<PyResult<Vec<_>>>()
+            .map(Value::Array)
+    } else {
+        Err(to_py_err(ForgeFfiError::invalid_argument(format!(
+            "不支持的参数类型: {}",
+            obj.get_type().name()?
+        ))))
+    }
+}
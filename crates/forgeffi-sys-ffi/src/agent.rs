@@ -0,0 +1,74 @@
+use forgeffi_base::{AgentConfig, ErrorCode, ForgeFfiError, ToolEventCb};
+use forgeffi_ffi_core::callback::{EventCallback, HandleRegistry};
+use forgeffi_sys::agent::{Agent, SnapshotSink};
+use std::ffi::c_void;
+use std::sync::{Mutex, MutexGuard};
+
+// 与 tool_event_cb 同签名，保留旧名兼容已有宿主代码
+pub type ToolAgentCb = ToolEventCb;
+
+static AGENTS: Mutex<HandleRegistry<Agent>> = Mutex::new(HandleRegistry::new());
+
+// 其它线程在持锁时 panic 不应让后续调用永久失败
+fn agents() -> MutexGuard<'static, HandleRegistry<Agent>> {
+    match AGENTS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_agent_start(
+    cfg_ptr: *const u8,
+    cfg_len: usize,
+    cb: ToolAgentCb,
+    user_data: *mut c_void,
+    out_handle: *mut u64,
+) -> i32 {
+    if cfg_ptr.is_null() || cfg_len == 0 || out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+
+    let cfg_bytes = unsafe { std::slice::from_raw_parts(cfg_ptr, cfg_len) };
+    let config: AgentConfig = match serde_json::from_slice(cfg_bytes) {
+        Ok(c) => c,
+        Err(_) => return ErrorCode::InvalidArgument.as_i32(),
+    };
+
+    let sink: Option<SnapshotSink> = EventCallback::new(cb, user_data).map(|cb| {
+        let sink: SnapshotSink = Box::new(move |snap| cb.emit(snap));
+        sink
+    });
+
+    match Agent::start(config, sink) {
+        Ok(agent) => {
+            forgeffi_sys::runtime::register_shutdown_hook("agent", stop_all_agents);
+            let handle = agents().insert(agent);
+            unsafe {
+                *out_handle = handle;
+            }
+            0
+        }
+        Err(ForgeFfiError { code, .. }) => code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_agent_stop(handle: u64) -> i32 {
+    let agent = agents().remove(handle);
+    match agent {
+        Some(a) => {
+            a.stop();
+            0
+        }
+        None => ErrorCode::NotFound.as_i32(),
+    }
+}
+
+fn stop_all_agents() {
+    let all = agents().drain();
+    for a in all {
+        a.stop();
+    }
+}
@@ -1,7 +1,43 @@
 #![allow(unsafe_code)]
 
+mod agent;
+mod environment;
+mod power;
+mod privileges;
+mod service;
+mod sockets;
+mod time;
+
+use forgeffi_base::ModuleCapabilities;
+use std::collections::BTreeMap;
+
+pub use agent::*;
+pub use environment::*;
+pub use forgeffi_ffi_core::*;
+pub use power::*;
+pub use privileges::*;
+pub use service::*;
+pub use sockets::*;
+pub use time::*;
+
 #[unsafe(no_mangle)]
 pub extern "C" fn tool_sys_ffi_abi_version() -> u32 {
     1
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_sys_api_level() -> u32 {
+    forgeffi_sys::API_LEVEL.as_u32()
+}
+
+pub fn sys_capabilities() -> ModuleCapabilities {
+    ModuleCapabilities {
+        module: "sys".to_string(),
+        ffi_abi: tool_sys_ffi_abi_version(),
+        features: ["agent", "environment", "power", "privileges", "service", "sockets", "time"].map(str::to_string).to_vec(),
+        // sys 模块的 op 目前只有服务管理（ServiceOp）
+        ops: forgeffi_sys::service::supported_ops().into_iter().map(str::to_string).collect(),
+        backends: Vec::new(),
+        op_scopes: BTreeMap::new(),
+    }
+}
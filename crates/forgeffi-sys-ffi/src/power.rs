@@ -0,0 +1,33 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError};
+
+use forgeffi_ffi_core::mem::optional_req_str;
+
+// reason 为 UTF-8 文本（不含结尾 NUL）；成功时 out_handle 写入非 0 句柄
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_sys_inhibit_sleep(reason_ptr: *const u8, reason_len: usize, out_handle: *mut u64) -> i32 {
+    if out_handle.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    let res = unsafe { optional_req_str(reason_ptr, reason_len) }.and_then(|reason| match reason {
+        Some(r) => forgeffi_sys::power::inhibit_sleep(r),
+        None => Err(ForgeFfiError::invalid_argument("reason 为空")),
+    });
+    match res {
+        Ok(handle) => {
+            unsafe {
+                *out_handle = handle;
+            }
+            0
+        }
+        Err(ForgeFfiError { code, .. }) => code.as_i32(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn tool_sys_release_sleep_inhibit(handle: u64) -> i32 {
+    match forgeffi_sys::power::release(handle) {
+        Ok(()) => 0,
+        Err(e) => e.code.as_i32(),
+    }
+}
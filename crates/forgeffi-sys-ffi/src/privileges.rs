@@ -0,0 +1,12 @@
+use forgeffi_base::ErrorCode;
+
+use forgeffi_ffi_core::mem::finish;
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn tool_sys_privileges_json(out_ptr: *mut *mut u8, out_len: *mut usize) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        return ErrorCode::InvalidArgument.as_i32();
+    }
+    finish(out_ptr, out_len, forgeffi_sys::privileges::report_json_bytes())
+}
@@ -0,0 +1,221 @@
+use forgeffi_base::{
+    AgentCollector, AgentConfig, AgentSnapshot, ForgeFfiError, PersistSchema, SystemSnapshot,
+    ABI_VERSION,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::util::{map_io_error, now_unix_ms, write_atomic};
+
+pub type SnapshotSink = Box<dyn FnMut(&AgentSnapshot) + Send + 'static>;
+
+pub const SPOOL_SCHEMA: PersistSchema = PersistSchema {
+    kind: "agent_snapshot",
+    version: 1,
+    migrations: &[(0, Ok)],
+};
+
+pub struct Agent {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Agent {
+    pub fn start(config: AgentConfig, mut sink: Option<SnapshotSink>) -> Result<Self, ForgeFfiError> {
+        validate_config(&config)?;
+        let spool_dir = config.spool_dir.as_ref().map(PathBuf::from);
+        if let Some(dir) = &spool_dir {
+            fs::create_dir_all(dir).map_err(map_io_error)?;
+        }
+        if sink.is_none() && spool_dir.is_none() {
+            return Err(ForgeFfiError::invalid_argument(
+                "agent 至少需要回调或 spool_dir 之一",
+            ));
+        }
+
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let stop_thread = Arc::clone(&stop);
+        let handle = std::thread::Builder::new()
+            .name("forgeffi-agent".to_string())
+            .spawn(move || {
+                let interval = Duration::from_millis(config.interval_ms);
+                let mut seq = 0u64;
+                loop {
+                    seq += 1;
+                    let snap = collect_snapshot(seq, &config.collect);
+                    if let Some(f) = sink.as_mut() {
+                        f(&snap);
+                    }
+                    if let Some(dir) = &spool_dir {
+                        let _ = write_spool(dir, &snap, config.max_spool_files);
+                    }
+
+                    let (lock, cvar) = &*stop_thread;
+                    let Ok(guard) = lock.lock() else {
+                        return;
+                    };
+                    let Ok((guard, _)) = cvar.wait_timeout_while(guard, interval, |stopped| !*stopped)
+                    else {
+                        return;
+                    };
+                    if *guard {
+                        return;
+                    }
+                }
+            })
+            .map_err(|e| ForgeFfiError::system_error(format!("启动 agent 线程失败: {e}")))?;
+
+        Ok(Self {
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        let (lock, cvar) = &*self.stop;
+        if let Ok(mut stopped) = lock.lock() {
+            *stopped = true;
+        }
+        cvar.notify_all();
+        // 在自己的回调里 stop 时不能 join 自身，线程看到 stop 标志后自行退出
+        if let Some(h) = self.handle.take()
+            && h.thread().id() != std::thread::current().id()
+        {
+            let _ = h.join();
+        }
+    }
+}
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn validate_config(config: &AgentConfig) -> Result<(), ForgeFfiError> {
+    forgeffi_protocol::check_abi(config.abi)?;
+    if config.interval_ms < AgentConfig::MIN_INTERVAL_MS {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "interval_ms 不能小于 {}",
+            AgentConfig::MIN_INTERVAL_MS
+        )));
+    }
+    if config.collect.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("collect 不能为空"));
+    }
+    Ok(())
+}
+
+pub fn collect_snapshot(seq: u64, collect: &[AgentCollector]) -> AgentSnapshot {
+    let mut snap = AgentSnapshot {
+        abi: ABI_VERSION,
+        seq,
+        timestamp_ms: now_unix_ms(),
+        netif: None,
+        system: None,
+        errors: Vec::new(),
+    };
+    for c in collect {
+        match c {
+            #[cfg(feature = "netif")]
+            AgentCollector::Netif => match crate::netif::list_interfaces() {
+                Ok(items) => snap.netif = Some(items),
+                Err(e) => snap.errors.push(e),
+            },
+            #[cfg(not(feature = "netif"))]
+            AgentCollector::Netif => snap
+                .errors
+                .push(ForgeFfiError::unsupported("未启用 netif 模块")),
+            AgentCollector::System => snap.system = Some(system_snapshot()),
+        }
+    }
+    snap
+}
+
+pub fn system_snapshot() -> SystemSnapshot {
+    SystemSnapshot {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        hostname: hostname(),
+        uptime_secs: uptime_secs(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn hostname() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(target_os = "windows")]
+fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok().filter(|s| !s.is_empty())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn hostname() -> Option<String> {
+    let out = crate::util::command("hostname").output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let s = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if s.is_empty() { None } else { Some(s) }
+}
+
+#[cfg(target_os = "linux")]
+fn uptime_secs() -> Option<u64> {
+    let text = fs::read_to_string("/proc/uptime").ok()?;
+    let first = text.split_whitespace().next()?;
+    first.parse::<f64>().ok().map(|v| v as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn uptime_secs() -> Option<u64> {
+    None
+}
+
+pub fn read_spool_file(path: &Path) -> Result<AgentSnapshot, ForgeFfiError> {
+    let buf = fs::read(path).map_err(map_io_error)?;
+    SPOOL_SCHEMA.decode_json(&buf)
+}
+
+fn write_spool(dir: &Path, snap: &AgentSnapshot, max_files: Option<u32>) -> Result<(), ForgeFfiError> {
+    let buf = SPOOL_SCHEMA.encode_json(snap)?;
+    let path = dir.join(format!("snapshot-{}-{:020}.json", snap.timestamp_ms, snap.seq));
+    write_atomic(&path, &buf).map_err(map_io_error)?;
+
+    if let Some(max) = max_files {
+        prune_spool(dir, max as usize).map_err(map_io_error)?;
+    }
+    Ok(())
+}
+
+fn prune_spool(dir: &Path, max: usize) -> std::io::Result<()> {
+    let mut files = Vec::new();
+    for ent in fs::read_dir(dir)? {
+        let ent = ent?;
+        let name = ent.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with("snapshot-") && name.ends_with(".json") {
+            files.push(ent.path());
+        }
+    }
+    if files.len() <= max {
+        return Ok(());
+    }
+    files.sort();
+    let excess = files.len() - max;
+    for p in files.into_iter().take(excess) {
+        let _ = fs::remove_file(p);
+    }
+    Ok(())
+}
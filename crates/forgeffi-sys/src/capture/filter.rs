@@ -0,0 +1,419 @@
+// pcap-filter 语法的一个子集，在用户态对每个包求值，三个平台的语义因此一致。支持：
+//   ip / ip6 / arp / tcp / udp / icmp / icmp6
+//   ether proto N
+//   [src|dst] host ADDR        （IPv4/IPv6，ARP 包按发送方/目标协议地址匹配）
+//   [src|dst] net ADDR/LEN
+//   [tcp|udp] [src|dst] port N
+// 用 and/&&、or/||、not/! 与括号组合，优先级 not > and > or。不认识的写法返回 InvalidArgument，
+// 不会悄悄放过所有包
+use forgeffi_base::ForgeFfiError;
+use forgeffi_protocol::parse_cidr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub const LINKTYPE_NULL: u16 = 0;
+pub const LINKTYPE_ETHERNET: u16 = 1;
+pub const LINKTYPE_RAW: u16 = 101;
+pub const LINKTYPE_LOOP: u16 = 108;
+pub const LINKTYPE_IPV4: u16 = 228;
+pub const LINKTYPE_IPV6: u16 = 229;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_ARP: u16 = 0x0806;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: [u16; 3] = [0x8100, 0x88a8, 0x9100];
+
+const IPPROTO_ICMP: u8 = 1;
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+const IPPROTO_ICMPV6: u8 = 58;
+
+// 防止畸形包里的扩展头链无限循环
+const MAX_IPV6_EXT_HEADERS: usize = 8;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Dir {
+    Any,
+    Src,
+    Dst,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Proto {
+    Ip,
+    Ip6,
+    Arp,
+    Tcp,
+    Udp,
+    Icmp,
+    Icmp6,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Proto(Proto),
+    EtherProto(u16),
+    Host(Dir, IpAddr),
+    Net(Dir, IpAddr, u8),
+    Port(Option<u8>, Dir, u16),
+}
+
+// 空字符串或只有空白时匹配全部
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CaptureFilter {
+    expr: Option<Expr>,
+}
+
+impl CaptureFilter {
+    pub fn parse(text: &str) -> Result<Self, ForgeFfiError> {
+        let tokens = tokenize(text);
+        if tokens.is_empty() {
+            return Ok(Self::default());
+        }
+        let mut p = Parser { tokens, pos: 0 };
+        let expr = p.or()?;
+        if let Some(t) = p.peek() {
+            return Err(syntax_error(format!("多余的 {t:?}")));
+        }
+        Ok(Self { expr: Some(expr) })
+    }
+
+    #[must_use]
+    pub fn matches_all(&self) -> bool {
+        self.expr.is_none()
+    }
+
+    // linktype 不认识时只有空过滤器能匹配
+    #[must_use]
+    pub fn matches(&self, linktype: u16, frame: &[u8]) -> bool {
+        match &self.expr {
+            None => true,
+            Some(e) => decode(linktype, frame).is_some_and(|pkt| eval(e, &pkt)),
+        }
+    }
+}
+
+fn syntax_error(detail: String) -> ForgeFfiError {
+    ForgeFfiError::invalid_argument(format!("filter 语法错误: {detail}"))
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let single = match c {
+            '(' | ')' | '!' => Some(c.to_string()),
+            '&' | '|' if chars.peek() == Some(&c) => {
+                chars.next();
+                Some(format!("{c}{c}"))
+            }
+            _ => None,
+        };
+        if single.is_some() || c.is_whitespace() {
+            if !cur.is_empty() {
+                out.push(std::mem::take(&mut cur));
+            }
+            out.extend(single);
+        } else {
+            cur.push(c);
+        }
+    }
+    if !cur.is_empty() {
+        out.push(cur);
+    }
+    out
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expect_value(&mut self, what: &str) -> Result<String, ForgeFfiError> {
+        self.next().ok_or_else(|| syntax_error(format!("{what} 后缺少值")))
+    }
+
+    fn or(&mut self) -> Result<Expr, ForgeFfiError> {
+        let mut left = self.and()?;
+        while matches!(self.peek(), Some("or" | "||")) {
+            self.pos += 1;
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, ForgeFfiError> {
+        let mut left = self.unary()?;
+        while matches!(self.peek(), Some("and" | "&&")) {
+            self.pos += 1;
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ForgeFfiError> {
+        match self.next().as_deref() {
+            Some("not" | "!") => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some("(") => {
+                let e = self.or()?;
+                match self.next().as_deref() {
+                    Some(")") => Ok(e),
+                    _ => Err(syntax_error("缺少 )".to_string())),
+                }
+            }
+            Some(t) => self.primitive(t.to_string()),
+            None => Err(syntax_error("表达式不完整".to_string())),
+        }
+    }
+
+    fn primitive(&mut self, first: String) -> Result<Expr, ForgeFfiError> {
+        let l4 = match first.as_str() {
+            "tcp" => Some(IPPROTO_TCP),
+            "udp" => Some(IPPROTO_UDP),
+            _ => None,
+        };
+        let mut tok = first;
+        // tcp/udp 后面跟 src/dst/port 时是端口限定，否则是单独的协议
+        if l4.is_some() {
+            if !matches!(self.peek(), Some("src" | "dst" | "port")) {
+                return Ok(Expr::Proto(if l4 == Some(IPPROTO_TCP) { Proto::Tcp } else { Proto::Udp }));
+            }
+            tok = self.next().unwrap_or_default();
+        }
+        let dir = match tok.as_str() {
+            "src" => Dir::Src,
+            "dst" => Dir::Dst,
+            _ => Dir::Any,
+        };
+        if dir != Dir::Any {
+            tok = self.expect_value(if dir == Dir::Src { "src" } else { "dst" })?;
+        }
+        match tok.as_str() {
+            "port" => {
+                let v = self.expect_value("port")?;
+                let port = v.parse::<u16>().map_err(|_| syntax_error(format!("端口无效: {v}")))?;
+                Ok(Expr::Port(l4, dir, port))
+            }
+            _ if l4.is_some() => Err(syntax_error(format!("tcp/udp 后只能跟 [src|dst] port: {tok}"))),
+            "host" => {
+                let v = self.expect_value("host")?;
+                let ip = v.parse::<IpAddr>().map_err(|_| syntax_error(format!("host 必须是 IP 地址: {v}")))?;
+                Ok(Expr::Host(dir, ip))
+            }
+            "net" => {
+                let v = self.expect_value("net")?;
+                let (ip, len) = parse_cidr(&v).ok_or_else(|| syntax_error(format!("net 必须为 ip/prefix_len: {v}")))?;
+                Ok(Expr::Net(dir, ip, len))
+            }
+            _ if dir != Dir::Any => Err(syntax_error(format!("src/dst 后只能跟 host、net 或 port: {tok}"))),
+            "ip" => Ok(Expr::Proto(Proto::Ip)),
+            "ip6" => Ok(Expr::Proto(Proto::Ip6)),
+            "arp" => Ok(Expr::Proto(Proto::Arp)),
+            "icmp" => Ok(Expr::Proto(Proto::Icmp)),
+            "icmp6" => Ok(Expr::Proto(Proto::Icmp6)),
+            "ether" => {
+                match self.next().as_deref() {
+                    Some("proto") => {}
+                    _ => return Err(syntax_error("ether 后只支持 proto".to_string())),
+                }
+                let v = self.expect_value("ether proto")?;
+                let n = match v.strip_prefix("0x") {
+                    Some(hex) => u16::from_str_radix(hex, 16),
+                    None => v.parse::<u16>(),
+                };
+                Ok(Expr::EtherProto(n.map_err(|_| syntax_error(format!("ether proto 无效: {v}")))?))
+            }
+            _ => Err(syntax_error(format!("不支持的原语: {tok}"))),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Decoded {
+    ethertype: Option<u16>,
+    src: Option<IpAddr>,
+    dst: Option<IpAddr>,
+    l4: Option<u8>,
+    // 非首个分片没有端口
+    ports: Option<(u16, u16)>,
+}
+
+fn be16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*b.get(at)?, *b.get(at + 1)?]))
+}
+
+fn decode(linktype: u16, frame: &[u8]) -> Option<Decoded> {
+    let (ethertype, payload) = match linktype {
+        LINKTYPE_ETHERNET => {
+            let mut ty = be16(frame, 12)?;
+            let mut off = 14;
+            while ETHERTYPE_VLAN.contains(&ty) {
+                ty = be16(frame, off + 2)?;
+                off += 4;
+            }
+            (ty, frame.get(off..)?)
+        }
+        LINKTYPE_RAW => match frame.first()? >> 4 {
+            4 => (ETHERTYPE_IPV4, frame),
+            6 => (ETHERTYPE_IPV6, frame),
+            _ => return None,
+        },
+        LINKTYPE_IPV4 => (ETHERTYPE_IPV4, frame),
+        LINKTYPE_IPV6 => (ETHERTYPE_IPV6, frame),
+        // NULL 的 4 字节地址族按抓包主机字节序，LOOP 固定网络字节序；BSD 上 IPv6 的 AF 值各不相同
+        LINKTYPE_NULL | LINKTYPE_LOOP => {
+            let raw: [u8; 4] = frame.get(..4)?.try_into().ok()?;
+            let af = if linktype == LINKTYPE_LOOP || raw[0] == 0 {
+                u32::from_be_bytes(raw)
+            } else {
+                u32::from_le_bytes(raw)
+            };
+            let ty = match af {
+                2 => ETHERTYPE_IPV4,
+                10 | 24 | 28 | 30 => ETHERTYPE_IPV6,
+                _ => return None,
+            };
+            (ty, &frame[4..])
+        }
+        _ => return None,
+    };
+    let mut d = Decoded {
+        ethertype: Some(ethertype),
+        ..Decoded::default()
+    };
+    match ethertype {
+        ETHERTYPE_IPV4 => decode_ipv4(payload, &mut d),
+        ETHERTYPE_IPV6 => decode_ipv6(payload, &mut d),
+        ETHERTYPE_ARP => decode_arp(payload, &mut d),
+        _ => {}
+    }
+    Some(d)
+}
+
+fn decode_ipv4(p: &[u8], d: &mut Decoded) {
+    let Some(&vihl) = p.first() else { return };
+    let ihl = usize::from(vihl & 0x0f) * 4;
+    if vihl >> 4 != 4 || ihl < 20 || p.len() < ihl {
+        return;
+    }
+    d.src = Some(IpAddr::V4(Ipv4Addr::new(p[12], p[13], p[14], p[15])));
+    d.dst = Some(IpAddr::V4(Ipv4Addr::new(p[16], p[17], p[18], p[19])));
+    d.l4 = Some(p[9]);
+    let first_fragment = u16::from_be_bytes([p[6], p[7]]) & 0x1fff == 0;
+    if first_fragment {
+        d.ports = ports_of(&p[ihl..]);
+    }
+}
+
+fn decode_ipv6(p: &[u8], d: &mut Decoded) {
+    if p.len() < 40 || p[0] >> 4 != 6 {
+        return;
+    }
+    let addr = |at: usize| {
+        let mut b = [0u8; 16];
+        b.copy_from_slice(&p[at..at + 16]);
+        IpAddr::V6(Ipv6Addr::from(b))
+    };
+    d.src = Some(addr(8));
+    d.dst = Some(addr(24));
+    let mut next = p[6];
+    let mut off = 40;
+    for _ in 0..MAX_IPV6_EXT_HEADERS {
+        let len = match next {
+            // hop-by-hop、routing、destination options
+            0 | 43 | 60 => p.get(off + 1).map(|l| (usize::from(*l) + 1) * 8),
+            44 => {
+                let Some(frag) = be16(p, off + 2) else { return };
+                if frag & 0xfff8 != 0 {
+                    d.l4 = p.get(off).copied();
+                    return;
+                }
+                Some(8)
+            }
+            // AH 的长度单位是 4 字节
+            51 => p.get(off + 1).map(|l| (usize::from(*l) + 2) * 4),
+            _ => break,
+        };
+        let Some(len) = len else { return };
+        let Some(&n) = p.get(off) else { return };
+        next = n;
+        off += len;
+    }
+    d.l4 = Some(next);
+    if let Some(rest) = p.get(off..) {
+        d.ports = ports_of(rest);
+    }
+}
+
+fn decode_arp(p: &[u8], d: &mut Decoded) {
+    // 只认以太网上的 IPv4 ARP：hlen 6，plen 4
+    if be16(p, 2) != Some(ETHERTYPE_IPV4) || p.get(4) != Some(&6) || p.get(5) != Some(&4) || p.len() < 28 {
+        return;
+    }
+    d.src = Some(IpAddr::V4(Ipv4Addr::new(p[14], p[15], p[16], p[17])));
+    d.dst = Some(IpAddr::V4(Ipv4Addr::new(p[24], p[25], p[26], p[27])));
+}
+
+fn ports_of(l4: &[u8]) -> Option<(u16, u16)> {
+    Some((be16(l4, 0)?, be16(l4, 2)?))
+}
+
+fn dir_matches<T>(dir: Dir, src: Option<T>, dst: Option<T>, f: impl Fn(T) -> bool) -> bool {
+    match dir {
+        Dir::Src => src.is_some_and(&f),
+        Dir::Dst => dst.is_some_and(&f),
+        Dir::Any => src.is_some_and(&f) || dst.is_some_and(&f),
+    }
+}
+
+fn in_net(ip: IpAddr, net: IpAddr, len: u8) -> bool {
+    let (a, b, width) = match (ip, net) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => (u128::from(u32::from(a)), u128::from(u32::from(b)), 32),
+        (IpAddr::V6(a), IpAddr::V6(b)) => (u128::from(a), u128::from(b), 128),
+        _ => return false,
+    };
+    let shift = width - u32::from(len);
+    a.checked_shr(shift).unwrap_or(0) == b.checked_shr(shift).unwrap_or(0)
+}
+
+fn eval(e: &Expr, p: &Decoded) -> bool {
+    match e {
+        Expr::And(a, b) => eval(a, p) && eval(b, p),
+        Expr::Or(a, b) => eval(a, p) || eval(b, p),
+        Expr::Not(a) => !eval(a, p),
+        Expr::EtherProto(t) => p.ethertype == Some(*t),
+        Expr::Proto(proto) => match proto {
+            Proto::Ip => p.ethertype == Some(ETHERTYPE_IPV4),
+            Proto::Ip6 => p.ethertype == Some(ETHERTYPE_IPV6),
+            Proto::Arp => p.ethertype == Some(ETHERTYPE_ARP),
+            Proto::Tcp => p.l4 == Some(IPPROTO_TCP),
+            Proto::Udp => p.l4 == Some(IPPROTO_UDP),
+            Proto::Icmp => p.ethertype == Some(ETHERTYPE_IPV4) && p.l4 == Some(IPPROTO_ICMP),
+            Proto::Icmp6 => p.l4 == Some(IPPROTO_ICMPV6),
+        },
+        Expr::Host(dir, ip) => dir_matches(*dir, p.src, p.dst, |a| a == *ip),
+        Expr::Net(dir, net, len) => dir_matches(*dir, p.src, p.dst, |a| in_net(a, *net, *len)),
+        Expr::Port(l4, dir, port) => {
+            let proto_ok = match l4 {
+                Some(want) => p.l4 == Some(*want),
+                None => matches!(p.l4, Some(IPPROTO_TCP | IPPROTO_UDP)),
+            };
+            proto_ok
+                && dir_matches(*dir, p.ports.map(|(s, _)| s), p.ports.map(|(_, d)| d), |x| x == *port)
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// 抓包：每个句柄对应一个后台线程，从平台抓包源读包、按 filter 过滤后写入 pcapng 文件。
+// Linux 直接读 AF_PACKET；macOS 通过 tcpdump（BPF），Windows 通过 Npcap 附带的 dumpcap，
+// 二者输出经典 pcap 流，这里解析后统一转写为 pcapng。filter 在用户态求值，各平台语义一致。
+// 达到 max_bytes / max_secs 后线程自行结束、文件已完整写出，仍需 stop 取回摘要并释放句柄
+use forgeffi_base::{
+    CaptureStartRequest, CaptureStopReason, CaptureSummary, ForgeFfiError, LogLevel, ABI_VERSION,
+    CAPTURE_DEFAULT_SNAPLEN,
+};
+use forgeffi_protocol::{decode_request, encode_json, validate_capture_request};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::util::map_io_error;
+
+pub mod filter;
+pub mod pcap;
+pub mod pcapng;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod pipe;
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub use filter::CaptureFilter;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Packet {
+    // Unix 纪元起的微秒
+    pub ts_us: u64,
+    // 线上的原始长度，data 可能因 snaplen 被截断
+    pub orig_len: u32,
+    pub data: Vec<u8>,
+}
+
+// 平台抓包源。next 在没有包时最多阻塞几百毫秒后返回 None，让工作线程检查停止标志
+pub(crate) trait PacketSource: Send {
+    fn linktype(&self) -> u16;
+    fn next(&mut self) -> Result<Option<Packet>, ForgeFfiError>;
+}
+
+struct Session {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<CaptureSummary>,
+}
+
+static SESSIONS: Mutex<BTreeMap<u64, Session>> = Mutex::new(BTreeMap::new());
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+fn sessions() -> MutexGuard<'static, BTreeMap<u64, Session>> {
+    match SESSIONS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn now_unix_us() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+// 不写文件、直接逐包读取，供 lldp 被动监听等内部调用方使用
+#[cfg_attr(not(feature = "lldp"), allow(dead_code))]
+pub(crate) fn open_source(iface: &str, snaplen: u32) -> Result<Box<dyn PacketSource>, ForgeFfiError> {
+    platform::open(iface, snaplen)
+}
+
+// 网卡不存在、权限不足、filter 语法错误等在这里同步返回；返回的句柄非 0，须用 stop 释放
+pub fn start(req: &CaptureStartRequest) -> Result<u64, ForgeFfiError> {
+    validate_capture_request(req)?;
+    let filter = CaptureFilter::parse(req.filter.as_deref().unwrap_or(""))?;
+    let snaplen = req.snaplen.unwrap_or(CAPTURE_DEFAULT_SNAPLEN);
+    let source = platform::open(&req.iface, snaplen)?;
+    let file = File::create(&req.path).map_err(map_io_error)?;
+    let writer = pcapng::PcapngWriter::new(BufWriter::new(file), source.linktype(), snaplen, &req.iface)
+        .map_err(map_io_error)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let worker = Worker {
+        req: req.clone(),
+        filter,
+        snaplen: snaplen as usize,
+        stop: stop.clone(),
+    };
+    let thread = std::thread::Builder::new()
+        .name("forgeffi-capture".to_string())
+        .spawn(move || worker.run(source, writer))
+        .map_err(|e| ForgeFfiError::system_error(format!("创建抓包线程失败: {e}")))?;
+
+    crate::runtime::register_shutdown_hook("capture", stop_all);
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    sessions().insert(handle, Session { stop, thread });
+    Ok(handle)
+}
+
+pub fn start_json(req_json: &str) -> Result<u64, ForgeFfiError> {
+    let req: CaptureStartRequest = decode_request(req_json)?;
+    start(&req)
+}
+
+pub fn stop(handle: u64) -> Result<CaptureSummary, ForgeFfiError> {
+    let session = sessions()
+        .remove(&handle)
+        .ok_or_else(|| ForgeFfiError::not_found(format!("未找到抓包句柄: {handle}")))?;
+    session.stop.store(true, Ordering::Release);
+    session
+        .thread
+        .join()
+        .map_err(|_| ForgeFfiError::system_error("抓包线程异常退出"))
+}
+
+pub fn stop_json_bytes(handle: u64) -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("抓包摘要", &stop(handle)?)
+}
+
+pub fn stop_all() {
+    let all = std::mem::take(&mut *sessions());
+    for s in all.values() {
+        s.stop.store(true, Ordering::Release);
+    }
+    for s in all.into_values() {
+        let _ = s.thread.join();
+    }
+}
+
+// 当前未释放的句柄，按升序
+pub fn active() -> Vec<u64> {
+    sessions().keys().copied().collect()
+}
+
+struct Worker {
+    req: CaptureStartRequest,
+    filter: CaptureFilter,
+    snaplen: usize,
+    stop: Arc<AtomicBool>,
+}
+
+impl Worker {
+    fn run(
+        self,
+        mut source: Box<dyn PacketSource>,
+        mut writer: pcapng::PcapngWriter<BufWriter<File>>,
+    ) -> CaptureSummary {
+        let linktype = source.linktype();
+        let started = Instant::now();
+        let deadline = self.req.max_secs.map(|s| started + Duration::from_secs(u64::from(s)));
+        let mut packets = 0u64;
+        let mut filtered = 0u64;
+        let mut error = None;
+        let reason = loop {
+            if self.stop.load(Ordering::Acquire) {
+                break CaptureStopReason::Stopped;
+            }
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                break CaptureStopReason::MaxSecs;
+            }
+            let mut pkt = match source.next() {
+                Ok(Some(p)) => p,
+                Ok(None) => continue,
+                Err(e) => {
+                    error = Some(e);
+                    break CaptureStopReason::Failed;
+                }
+            };
+            if !self.filter.matches(linktype, &pkt.data) {
+                filtered += 1;
+                continue;
+            }
+            pkt.data.truncate(self.snaplen);
+            let grow = pcapng::packet_block_len(pkt.data.len());
+            if self.req.max_bytes.is_some_and(|max| writer.bytes_written() + grow > max) {
+                break CaptureStopReason::MaxBytes;
+            }
+            if let Err(e) = writer.write_packet(pkt.ts_us, pkt.orig_len, &pkt.data) {
+                error = Some(map_io_error(e));
+                break CaptureStopReason::Failed;
+            }
+            packets += 1;
+        };
+        let file_bytes = writer.bytes_written();
+        let (reason, error) = match (writer.finish(), error) {
+            (Err(e), None) => (CaptureStopReason::Failed, Some(map_io_error(e))),
+            (_, error) => (reason, error),
+        };
+        drop(source);
+        crate::runtime::log(
+            LogLevel::Debug,
+            format_args!("抓包结束: {} {packets} 个包 ({reason:?})", self.req.iface),
+        );
+        CaptureSummary {
+            abi: ABI_VERSION,
+            iface: self.req.iface,
+            path: self.req.path,
+            linktype,
+            packets,
+            filtered,
+            file_bytes,
+            duration_ms: started.elapsed().as_millis() as u64,
+            reason,
+            error,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+// 经典 pcap 流的读取：tcpdump -w - 与 dumpcap -P -w - 的输出。支持两种字节序与纳秒时间戳变体，
+// 时间戳统一换算为微秒
+use super::Packet;
+use forgeffi_base::ForgeFfiError;
+use std::io::{self, Read};
+
+const MAGIC_US: u32 = 0xA1B2_C3D4;
+const MAGIC_NS: u32 = 0xA1B2_3C4D;
+// 比任何合理的 snaplen 都大，挡住畸形流里的超大长度
+const MAX_RECORD_LEN: u32 = 16 * 1024 * 1024;
+
+pub struct PcapReader<R: Read> {
+    inner: R,
+    swapped: bool,
+    nanos: bool,
+    linktype: u16,
+    snaplen: u32,
+}
+
+fn format_error(detail: impl std::fmt::Display) -> ForgeFfiError {
+    ForgeFfiError::system_error(format!("pcap 流格式错误: {detail}"))
+}
+
+// 读满 buf；开头即 EOF 时返回 false
+fn read_exact_or_eof(r: &mut impl Read, buf: &mut [u8]) -> Result<bool, ForgeFfiError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(format_error("记录被截断")),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(ForgeFfiError::system_error(format!("读取 pcap 流失败: {e}"))),
+        }
+    }
+    Ok(true)
+}
+
+impl<R: Read> PcapReader<R> {
+    pub fn new(mut inner: R) -> Result<Self, ForgeFfiError> {
+        let mut hdr = [0u8; 24];
+        if !read_exact_or_eof(&mut inner, &mut hdr)? {
+            return Err(format_error("缺少文件头"));
+        }
+        let magic_le = u32::from_le_bytes([hdr[0], hdr[1], hdr[2], hdr[3]]);
+        let magic_be = u32::from_be_bytes([hdr[0], hdr[1], hdr[2], hdr[3]]);
+        let (swapped, magic) = match (magic_le, magic_be) {
+            (m @ (MAGIC_US | MAGIC_NS), _) => (false, m),
+            (_, m @ (MAGIC_US | MAGIC_NS)) => (true, m),
+            _ => return Err(format_error(format!("未知 magic {magic_le:#010x}"))),
+        };
+        let mut r = Self {
+            inner,
+            swapped,
+            nanos: magic == MAGIC_NS,
+            linktype: 0,
+            snaplen: 0,
+        };
+        r.snaplen = r.u32_at(&hdr, 16);
+        // 高 16 位是 FCS 等附加信息
+        r.linktype = (r.u32_at(&hdr, 20) & 0xffff) as u16;
+        Ok(r)
+    }
+
+    fn u32_at(&self, b: &[u8], at: usize) -> u32 {
+        let raw = [b[at], b[at + 1], b[at + 2], b[at + 3]];
+        if self.swapped {
+            u32::from_be_bytes(raw)
+        } else {
+            u32::from_le_bytes(raw)
+        }
+    }
+
+    #[must_use]
+    pub fn linktype(&self) -> u16 {
+        self.linktype
+    }
+
+    #[must_use]
+    pub fn snaplen(&self) -> u32 {
+        self.snaplen
+    }
+
+    // 流正常结束时返回 None
+    pub fn next_packet(&mut self) -> Result<Option<Packet>, ForgeFfiError> {
+        let mut rec = [0u8; 16];
+        if !read_exact_or_eof(&mut self.inner, &mut rec)? {
+            return Ok(None);
+        }
+        let sec = u64::from(self.u32_at(&rec, 0));
+        let frac = u64::from(self.u32_at(&rec, 4));
+        let incl = self.u32_at(&rec, 8);
+        let orig_len = self.u32_at(&rec, 12);
+        if incl > MAX_RECORD_LEN {
+            return Err(format_error(format!("记录长度 {incl} 过大")));
+        }
+        let mut data = vec![0u8; incl as usize];
+        if !read_exact_or_eof(&mut self.inner, &mut data)? && incl > 0 {
+            return Err(format_error("记录被截断"));
+        }
+        let ts_us = sec * 1_000_000 + if self.nanos { frac / 1000 } else { frac };
+        Ok(Some(Packet { ts_us, orig_len, data }))
+    }
+}
@@ -0,0 +1,102 @@
+// pcapng 写入：一个 Section Header Block、一个 Interface Description Block，之后每个包一个
+// Enhanced Packet Block。统一小端写出（读取方按 SHB 的字节序标记识别），时间戳为默认的微秒精度
+use std::io::{self, Write};
+
+const SHB_TYPE: u32 = 0x0A0D_0D0A;
+const IDB_TYPE: u32 = 0x0000_0001;
+const EPB_TYPE: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+const OPT_END: u16 = 0;
+const SHB_USERAPPL: u16 = 4;
+const IF_NAME: u16 = 2;
+
+// EPB 的块头、固定字段与块尾
+const EPB_OVERHEAD: u64 = 32;
+
+fn pad4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    // 选项值长度字段只有 16 位，过长的接口名直接截断
+    let value = &value[..value.len().min(usize::from(u16::MAX) & !3)];
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    body.extend_from_slice(value);
+    body.resize(pad4(body.len()), 0);
+}
+
+// 写入一个包后文件增长的字节数，用于在写之前判断是否超出 max_bytes
+#[must_use]
+pub fn packet_block_len(captured_len: usize) -> u64 {
+    EPB_OVERHEAD + pad4(captured_len) as u64
+}
+
+pub struct PcapngWriter<W: Write> {
+    out: W,
+    bytes: u64,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    pub fn new(out: W, linktype: u16, snaplen: u32, if_name: &str) -> io::Result<Self> {
+        let mut w = Self { out, bytes: 0 };
+
+        let mut shb = Vec::with_capacity(64);
+        shb.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes());
+        shb.extend_from_slice(&0u16.to_le_bytes());
+        // section 长度未知
+        shb.extend_from_slice(&(-1i64).to_le_bytes());
+        push_option(&mut shb, SHB_USERAPPL, concat!("ForgeFFI ", env!("CARGO_PKG_VERSION")).as_bytes());
+        push_option(&mut shb, OPT_END, &[]);
+        w.write_block(SHB_TYPE, &shb)?;
+
+        let mut idb = Vec::with_capacity(32);
+        idb.extend_from_slice(&linktype.to_le_bytes());
+        idb.extend_from_slice(&0u16.to_le_bytes());
+        idb.extend_from_slice(&snaplen.to_le_bytes());
+        if !if_name.is_empty() {
+            push_option(&mut idb, IF_NAME, if_name.as_bytes());
+            push_option(&mut idb, OPT_END, &[]);
+        }
+        w.write_block(IDB_TYPE, &idb)?;
+        Ok(w)
+    }
+
+    fn write_block(&mut self, ty: u32, body: &[u8]) -> io::Result<()> {
+        let total = u32::try_from(12 + pad4(body.len()))
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pcapng 块过大"))?;
+        self.out.write_all(&ty.to_le_bytes())?;
+        self.out.write_all(&total.to_le_bytes())?;
+        self.out.write_all(body)?;
+        self.out.write_all(&[0u8; 3][..pad4(body.len()) - body.len()])?;
+        self.out.write_all(&total.to_le_bytes())?;
+        self.bytes += u64::from(total);
+        Ok(())
+    }
+
+    pub fn write_packet(&mut self, ts_us: u64, orig_len: u32, data: &[u8]) -> io::Result<()> {
+        let captured = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "包过大"))?;
+        let mut body = Vec::with_capacity(20 + data.len());
+        body.extend_from_slice(&0u32.to_le_bytes());
+        body.extend_from_slice(&((ts_us >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(ts_us as u32).to_le_bytes());
+        body.extend_from_slice(&captured.to_le_bytes());
+        body.extend_from_slice(&orig_len.max(captured).to_le_bytes());
+        body.extend_from_slice(data);
+        self.write_block(EPB_TYPE, &body)
+    }
+
+    // 已写入的字节数（即文件大小）
+    #[must_use]
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
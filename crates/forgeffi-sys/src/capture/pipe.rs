@@ -0,0 +1,133 @@
+// 外部抓包程序（tcpdump / dumpcap）把经典 pcap 流写到 stdout：读线程解析后经有界通道交给工作线程，
+// next 用 recv_timeout 等待，停止时结束子进程。通道满时读线程阻塞，背压最终落到抓包程序的内核缓冲区
+use super::pcap::PcapReader;
+use super::{Packet, PacketSource};
+use crate::util::{command, command_failed, map_spawn_error};
+use forgeffi_base::ForgeFfiError;
+use std::process::{Child, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+const POLL: Duration = Duration::from_millis(200);
+// 打开网卡、写出 pcap 文件头的等待上限
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+const QUEUE_DEPTH: usize = 4096;
+
+pub(super) struct PipeSource {
+    program: String,
+    child: Child,
+    linktype: u16,
+    rx: Receiver<Result<Packet, ForgeFfiError>>,
+}
+
+pub(super) fn spawn(program: &str, args: &[String]) -> Result<PipeSource, ForgeFfiError> {
+    let mut child = command(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| map_spawn_error(program, e))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| ForgeFfiError::system_error(format!("{program} 没有 stdout")))?;
+
+    let (header_tx, header_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::sync_channel(QUEUE_DEPTH);
+    let spawned = std::thread::Builder::new()
+        .name("forgeffi-capture-pipe".to_string())
+        .spawn(move || {
+            let mut reader = match PcapReader::new(stdout) {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = header_tx.send(Err(e));
+                    return;
+                }
+            };
+            let _ = header_tx.send(Ok(reader.linktype()));
+            loop {
+                let item = match reader.next_packet() {
+                    Ok(Some(p)) => Ok(p),
+                    // 子进程退出；发送端随线程结束而关闭，工作线程据此得知
+                    Ok(None) => return,
+                    Err(e) => Err(e),
+                };
+                let failed = item.is_err();
+                if tx.send(item).is_err() || failed {
+                    return;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(ForgeFfiError::system_error(format!("创建抓包读线程失败: {e}")));
+    }
+
+    match header_rx.recv_timeout(STARTUP_TIMEOUT) {
+        Ok(Ok(linktype)) => {
+            // 之后不再读 stderr，关掉管道避免写满后阻塞抓包程序
+            child.stderr = None;
+            Ok(PipeSource {
+                program: program.to_string(),
+                child,
+                linktype,
+                rx,
+            })
+        }
+        Ok(Err(_)) | Err(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            let mut stderr = String::new();
+            if let Some(mut err) = child.stderr.take() {
+                let _ = std::io::Read::read_to_string(&mut err, &mut stderr);
+            }
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let mut err = command_failed(
+                program,
+                &args,
+                &stderr,
+                format!("启动抓包失败: {program} 未输出 pcap 流: {}", stderr.trim()),
+            );
+            if is_permission_message(&stderr) {
+                err = ForgeFfiError::permission_denied(err.message.clone())
+                    .with_command(program)
+                    .with_stderr(&stderr);
+            }
+            Err(err)
+        }
+    }
+}
+
+fn is_permission_message(stderr: &str) -> bool {
+    let s = stderr.to_lowercase();
+    s.contains("permission") || s.contains("not permitted") || s.contains("access is denied")
+}
+
+impl PacketSource for PipeSource {
+    fn linktype(&self) -> u16 {
+        self.linktype
+    }
+
+    fn next(&mut self) -> Result<Option<Packet>, ForgeFfiError> {
+        match self.rx.recv_timeout(POLL) {
+            Ok(item) => item.map(Some),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                let status = self.child.try_wait().ok().flatten();
+                Err(ForgeFfiError::system_error(format!(
+                    "{} 意外退出（{}）",
+                    self.program,
+                    status.map_or_else(|| "仍在运行".to_string(), |s| s.to_string())
+                )))
+            }
+        }
+    }
+}
+
+impl Drop for PipeSource {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,106 @@
+// AF_PACKET 原始套接字（ETH_P_ALL），收发两个方向的包都能看到。只按 ifindex 过滤出目标网卡，
+// 不做 bind：nix 没有安全的 sockaddr_ll 构造方式，多余的包在用户态丢弃即可
+use super::filter::{LINKTYPE_ETHERNET, LINKTYPE_RAW};
+use super::{now_unix_us, Packet, PacketSource};
+use forgeffi_base::ForgeFfiError;
+use nix::errno::Errno;
+use nix::sys::socket::{
+    recvmsg, setsockopt, socket, sockopt, AddressFamily, LinkAddr, MsgFlags, SockFlag, SockProtocol,
+    SockType,
+};
+use nix::sys::time::{TimeVal, TimeValLike};
+use std::io::IoSliceMut;
+use std::os::fd::{AsRawFd, OwnedFd};
+
+const ARPHRD_ETHER: u16 = 1;
+const ARPHRD_LOOPBACK: u16 = 772;
+const ARPHRD_PPP: u16 = 512;
+const ARPHRD_TUNNEL: u16 = 768;
+const ARPHRD_TUNNEL6: u16 = 769;
+const ARPHRD_SIT: u16 = 776;
+const ARPHRD_NONE: u16 = 65534;
+
+// 内核默认的接收缓冲区在突发流量下很快溢出
+const RCVBUF_BYTES: usize = 4 * 1024 * 1024;
+
+struct AfPacket {
+    fd: OwnedFd,
+    ifindex: usize,
+    linktype: u16,
+    buf: Vec<u8>,
+}
+
+fn map_errno(e: Errno) -> ForgeFfiError {
+    match e {
+        Errno::EPERM | Errno::EACCES => ForgeFfiError::permission_denied(format!("AF_PACKET: {e}"))
+            .with_hint("抓包需要 root 或 CAP_NET_RAW"),
+        _ => ForgeFfiError::system_error(format!("AF_PACKET: {e}")),
+    }
+    .with_errno(e as i32)
+}
+
+fn sysfs_value(iface: &str, attr: &str) -> Result<String, ForgeFfiError> {
+    std::fs::read_to_string(format!("/sys/class/net/{iface}/{attr}"))
+        .map(|s| s.trim().to_string())
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ForgeFfiError::not_found(format!("网卡不存在: {iface}")),
+            _ => crate::util::map_io_error(e),
+        })
+}
+
+pub(super) fn open(iface: &str, snaplen: u32) -> Result<Box<dyn PacketSource>, ForgeFfiError> {
+    forgeffi_protocol::validate_linux_ifname(iface)?;
+    let parse_num = |attr: &str| -> Result<u64, ForgeFfiError> {
+        let v = sysfs_value(iface, attr)?;
+        v.parse()
+            .map_err(|_| ForgeFfiError::system_error(format!("无法解析 /sys/class/net/{iface}/{attr}: {v}")))
+    };
+    let ifindex = parse_num("ifindex")? as usize;
+    let linktype = match parse_num("type")? as u16 {
+        ARPHRD_ETHER | ARPHRD_LOOPBACK => LINKTYPE_ETHERNET,
+        // 没有链路层头的设备（tun、WireGuard、PPP、IP 隧道）收到的是裸 IP
+        ARPHRD_NONE | ARPHRD_PPP | ARPHRD_TUNNEL | ARPHRD_TUNNEL6 | ARPHRD_SIT => LINKTYPE_RAW,
+        other => {
+            return Err(ForgeFfiError::not_implemented(format!(
+                "暂不支持抓取该类型网卡: {iface}（ARPHRD {other}）"
+            )));
+        }
+    };
+
+    let fd = socket(AddressFamily::Packet, SockType::Raw, SockFlag::SOCK_CLOEXEC, SockProtocol::EthAll)
+        .map_err(map_errno)?;
+    setsockopt(&fd, sockopt::ReceiveTimeout, &TimeVal::milliseconds(200)).map_err(map_errno)?;
+    // 调不大时沿用系统默认值
+    let _ = setsockopt(&fd, sockopt::RcvBuf, &RCVBUF_BYTES);
+    Ok(Box::new(AfPacket {
+        fd,
+        ifindex,
+        linktype,
+        buf: vec![0u8; snaplen as usize],
+    }))
+}
+
+impl PacketSource for AfPacket {
+    fn linktype(&self) -> u16 {
+        self.linktype
+    }
+
+    fn next(&mut self) -> Result<Option<Packet>, ForgeFfiError> {
+        let mut iov = [IoSliceMut::new(&mut self.buf)];
+        // MSG_TRUNC 让返回值为包的真实长度，超过缓冲区的部分被截掉
+        let (len, addr) = match recvmsg::<LinkAddr>(self.fd.as_raw_fd(), &mut iov, None, MsgFlags::MSG_TRUNC) {
+            Ok(msg) => (msg.bytes, msg.address),
+            Err(Errno::EAGAIN | Errno::EINTR) => return Ok(None),
+            Err(e) => return Err(map_errno(e)),
+        };
+        if addr.is_none_or(|a| a.ifindex() != self.ifindex) {
+            return Ok(None);
+        }
+        let captured = len.min(self.buf.len());
+        Ok(Some(Packet {
+            ts_us: now_unix_us(),
+            orig_len: u32::try_from(len).unwrap_or(u32::MAX),
+            data: self.buf[..captured].to_vec(),
+        }))
+    }
+}
@@ -0,0 +1,21 @@
+// 通过 tcpdump 读 BPF：-U 每个包立即刷出，-w - 输出经典 pcap 流。不把 filter 交给 tcpdump，
+// 由上层在用户态求值以保持各平台语义一致
+use super::pipe;
+use super::PacketSource;
+use forgeffi_base::ForgeFfiError;
+
+pub(super) fn open(iface: &str, snaplen: u32) -> Result<Box<dyn PacketSource>, ForgeFfiError> {
+    let args = vec![
+        "-i".to_string(),
+        iface.to_string(),
+        "-U".to_string(),
+        "-n".to_string(),
+        "-s".to_string(),
+        snaplen.to_string(),
+        "-w".to_string(),
+        "-".to_string(),
+    ];
+    pipe::spawn("/usr/sbin/tcpdump", &args)
+        .map(|s| Box::new(s) as Box<dyn PacketSource>)
+        .map_err(|e| e.with_hint("读取 BPF 设备需要 root，或把用户加入 access_bpf 组（Wireshark 的 ChmodBPF）"))
+}
@@ -0,0 +1,6 @@
+use super::PacketSource;
+use forgeffi_base::ForgeFfiError;
+
+pub(super) fn open(_iface: &str, _snaplen: u32) -> Result<Box<dyn PacketSource>, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持抓包".to_string()))
+}
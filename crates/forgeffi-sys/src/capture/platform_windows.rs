@@ -0,0 +1,30 @@
+// 通过 Npcap 附带的 dumpcap（随 Wireshark 安装）抓包：-P 输出经典 pcap 而不是 pcapng，
+// 由上层统一转写。优先用 Wireshark 默认安装目录下的 dumpcap，没有时从 PATH 查找
+use super::pipe;
+use super::PacketSource;
+use forgeffi_base::ForgeFfiError;
+
+const DUMPCAP: &str = "dumpcap";
+
+fn default_install() -> Option<String> {
+    let base = std::env::var("ProgramFiles").ok()?;
+    let path = std::path::Path::new(&base).join("Wireshark").join("dumpcap.exe");
+    path.exists().then(|| path.to_string_lossy().into_owned())
+}
+
+pub(super) fn open(iface: &str, snaplen: u32) -> Result<Box<dyn PacketSource>, ForgeFfiError> {
+    let args = vec![
+        "-i".to_string(),
+        iface.to_string(),
+        "-P".to_string(),
+        "-q".to_string(),
+        "-s".to_string(),
+        snaplen.to_string(),
+        "-w".to_string(),
+        "-".to_string(),
+    ];
+    let program = default_install().unwrap_or_else(|| DUMPCAP.to_string());
+    pipe::spawn(&program, &args)
+        .map(|s| Box::new(s) as Box<dyn PacketSource>)
+        .map_err(|e| e.with_hint("需要安装 Npcap 与 Wireshark 的 dumpcap；iface 可用网卡别名或 dumpcap -D 的序号"))
+}
@@ -0,0 +1,89 @@
+use forgeffi_base::{
+    ContainerKind, EnvironmentReport, ForgeFfiError, InitSystem, NetworkStackInfo, SelinuxMode, ABI_VERSION,
+};
+use forgeffi_protocol::encode_json;
+
+use crate::util::{command, CommandExt};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+// 每一项探测独立失败，缺失的工具只会让对应字段为空，整体不报错
+pub fn report() -> EnvironmentReport {
+    let mut r = EnvironmentReport {
+        abi: ABI_VERSION,
+        os: std::env::consts::OS.to_string(),
+        os_version: None,
+        kernel: None,
+        init_system: None,
+        network: NetworkStackInfo::default(),
+        container: None,
+        virtualization: None,
+        selinux: None,
+        apparmor: None,
+    };
+    platform::fill(&mut r);
+    r
+}
+
+pub fn report_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("环境报告", &report())
+}
+
+// 命令成功时返回去掉首尾空白的 stdout；命令不存在或失败时为空
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn capture(program: &str, args: &[&str]) -> Option<String> {
+    let out = command(program).args(args).output_with_deadline().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// 按 DMI 厂商/型号识别常见虚拟化平台，命名与 systemd-detect-virt 一致
+#[cfg_attr(not(any(target_os = "linux", target_os = "windows")), allow(dead_code))]
+fn dmi_virtualization(vendor: &str, product: &str) -> Option<String> {
+    let v = format!("{vendor} {product}").to_lowercase();
+    let name = if v.contains("kvm") {
+        "kvm"
+    } else if v.contains("qemu") {
+        "qemu"
+    } else if v.contains("vmware") {
+        "vmware"
+    } else if v.contains("virtualbox") || v.contains("innotek") {
+        "oracle"
+    } else if v.contains("microsoft") && v.contains("virtual") {
+        "microsoft"
+    } else if v.contains("xen") {
+        "xen"
+    } else if v.contains("amazon ec2") {
+        "amazon"
+    } else if v.contains("google compute engine") {
+        "google"
+    } else if v.contains("parallels") {
+        "parallels"
+    } else if v.contains("bochs") {
+        "bochs"
+    } else {
+        return None;
+    };
+    Some(name.to_string())
+}
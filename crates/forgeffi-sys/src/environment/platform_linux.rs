@@ -0,0 +1,138 @@
+use super::*;
+
+use std::path::Path;
+
+pub(super) fn fill(r: &mut EnvironmentReport) {
+    r.os_version = os_release_pretty_name();
+    r.kernel = read_trimmed("/proc/sys/kernel/osrelease");
+    r.init_system = Some(init_system());
+    r.network = network_stack(r.init_system == Some(InitSystem::Systemd));
+    r.container = container(r.kernel.as_deref());
+    // 容器共享宿主内核，DMI 反映的是宿主所在的平台
+    r.virtualization = capture("systemd-detect-virt", &["--vm"])
+        .filter(|v| v != "none")
+        .or_else(|| {
+            dmi_virtualization(
+                &read_trimmed("/sys/class/dmi/id/sys_vendor").unwrap_or_default(),
+                &read_trimmed("/sys/class/dmi/id/product_name").unwrap_or_default(),
+            )
+        })
+        .or_else(|| {
+            std::fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .filter(|c| c.lines().any(|l| l.starts_with("flags") && l.split_whitespace().any(|f| f == "hypervisor")))
+                .map(|_| "vm-other".to_string())
+        });
+    r.selinux = selinux();
+    r.apparmor = read_trimmed("/sys/module/apparmor/parameters/enabled").map(|v| v == "Y");
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn os_release_pretty_name() -> Option<String> {
+    let text = std::fs::read_to_string("/etc/os-release")
+        .or_else(|_| std::fs::read_to_string("/usr/lib/os-release"))
+        .ok()?;
+    text.lines()
+        .find_map(|l| l.strip_prefix("PRETTY_NAME="))
+        .map(|v| v.trim().trim_matches('"').to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn init_system() -> InitSystem {
+    if Path::new("/run/systemd/system").exists() {
+        return InitSystem::Systemd;
+    }
+    if Path::new("/run/openrc").exists() {
+        return InitSystem::Openrc;
+    }
+    match read_trimmed("/proc/1/comm").as_deref() {
+        Some("openrc-init") => InitSystem::Openrc,
+        Some("init") => InitSystem::Sysvinit,
+        _ => InitSystem::Other,
+    }
+}
+
+fn network_stack(systemd: bool) -> NetworkStackInfo {
+    let units = ["NetworkManager", "systemd-networkd", "systemd-resolved"];
+    // is-active 在任一 unit 未运行时返回非 0，但仍按参数顺序逐行输出状态
+    let active: Vec<bool> = if systemd {
+        command("systemctl")
+            .arg("is-active")
+            .args(units)
+            .output_with_deadline()
+            .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(|l| l.trim() == "active").collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let is_active = |i: usize, pid_path: &str| active.get(i).copied().unwrap_or_else(|| Path::new(pid_path).exists());
+    NetworkStackInfo {
+        network_manager: is_active(0, "/run/NetworkManager/NetworkManager.pid"),
+        systemd_networkd: is_active(1, "/run/systemd/netif/state"),
+        systemd_resolved: is_active(2, "/run/systemd/resolve/resolv.conf"),
+        netplan: std::fs::read_dir("/etc/netplan").is_ok_and(|d| {
+            d.flatten()
+                .any(|e| e.path().extension().is_some_and(|x| x == "yaml" || x == "yml"))
+        }),
+        ifupdown: std::fs::read_to_string("/etc/network/interfaces").is_ok_and(|t| {
+            t.lines()
+                .filter_map(|l| l.trim().strip_prefix("iface "))
+                .any(|l| l.split_whitespace().next() != Some("lo"))
+        }),
+    }
+}
+
+fn container(kernel: Option<&str>) -> Option<ContainerKind> {
+    // Kubernetes 的 pod 同时也是 docker/containerd 容器，优先报告编排层
+    if std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        || Path::new("/var/run/secrets/kubernetes.io").exists()
+    {
+        return Some(ContainerKind::Kubernetes);
+    }
+    if let Some(v) = capture("systemd-detect-virt", &["--container"]).filter(|v| v != "none") {
+        return Some(match v.as_str() {
+            "docker" => ContainerKind::Docker,
+            "podman" => ContainerKind::Podman,
+            "lxc" | "lxc-libvirt" => ContainerKind::Lxc,
+            "wsl" => ContainerKind::Wsl,
+            _ => ContainerKind::Other,
+        });
+    }
+    if kernel.is_some_and(|k| k.to_lowercase().contains("microsoft")) {
+        return Some(ContainerKind::Wsl);
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some(ContainerKind::Docker);
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some(ContainerKind::Podman);
+    }
+    let cgroup = std::fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    if cgroup.contains("kubepods") {
+        Some(ContainerKind::Kubernetes)
+    } else if cgroup.contains("docker") {
+        Some(ContainerKind::Docker)
+    } else if cgroup.contains("lxc") {
+        Some(ContainerKind::Lxc)
+    } else if std::env::var("container").is_ok_and(|v| !v.is_empty()) {
+        Some(ContainerKind::Other)
+    } else {
+        None
+    }
+}
+
+// 内核未启用 SELinux 时 /sys/fs/selinux 不存在；只装了用户态配置时报告为 disabled
+fn selinux() -> Option<SelinuxMode> {
+    match read_trimmed("/sys/fs/selinux/enforce").as_deref() {
+        Some("1") => Some(SelinuxMode::Enforcing),
+        Some(_) => Some(SelinuxMode::Permissive),
+        None if Path::new("/etc/selinux/config").exists() => Some(SelinuxMode::Disabled),
+        None => None,
+    }
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+pub(super) fn fill(r: &mut EnvironmentReport) {
+    r.init_system = Some(InitSystem::Launchd);
+    r.os_version = capture("sw_vers", &["-productVersion"]);
+    r.kernel = capture("uname", &["-r"]);
+    // Apple 虚拟化框架、Parallels、VMware 等都会设置 kern.hv_vmm_present
+    if capture("sysctl", &["-n", "kern.hv_vmm_present"]).as_deref() == Some("1") {
+        let model = capture("sysctl", &["-n", "hw.model"]).unwrap_or_default().to_lowercase();
+        r.virtualization = Some(
+            if model.contains("vmware") {
+                "vmware"
+            } else if model.contains("parallels") {
+                "parallels"
+            } else if model.starts_with("virtualmac") {
+                "apple"
+            } else {
+                "vm-other"
+            }
+            .to_string(),
+        );
+    }
+}
@@ -0,0 +1,3 @@
+use super::*;
+
+pub(super) fn fill(_r: &mut EnvironmentReport) {}
@@ -0,0 +1,34 @@
+use super::*;
+
+use serde_json::Value;
+
+// 一次 PowerShell 调用取齐版本、虚拟化与容器信息；容器内 ContainerType 注册表值存在
+pub(super) fn fill(r: &mut EnvironmentReport) {
+    r.init_system = Some(InitSystem::Scm);
+    let script = r#"
+$os = Get-CimInstance Win32_OperatingSystem
+$cs = Get-CimInstance Win32_ComputerSystem
+$ct = (Get-ItemProperty 'HKLM:\SYSTEM\CurrentControlSet\Control' -Name ContainerType -ErrorAction SilentlyContinue).ContainerType
+[pscustomobject]@{ caption=$os.Caption; version=$os.Version; maker=$cs.Manufacturer; model=$cs.Model; hv=$cs.HypervisorPresent; container=$ct } | ConvertTo-Json -Compress
+"#;
+    let Some(text) = capture(
+        crate::util::powershell_program(),
+        &["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script],
+    ) else {
+        return;
+    };
+    let Ok(v) = serde_json::from_str::<Value>(&text) else {
+        return;
+    };
+    let s = |k: &str| v.get(k).and_then(Value::as_str).unwrap_or_default().to_string();
+    r.os_version = Some(s("caption")).filter(|c| !c.is_empty());
+    r.kernel = Some(s("version")).filter(|c| !c.is_empty());
+    if v.get("container").is_some_and(|c| !c.is_null()) {
+        r.container = Some(ContainerKind::Other);
+    }
+    // HypervisorPresent 在启用了 Hyper-V/VBS 的宿主机上同样为 true，只在型号能识别时才判为虚拟机
+    r.virtualization = dmi_virtualization(&s("maker"), &s("model"));
+    if r.virtualization.is_none() && v.get("hv").and_then(Value::as_bool) == Some(true) && s("model").contains("Virtual") {
+        r.virtualization = Some("microsoft".to_string());
+    }
+}
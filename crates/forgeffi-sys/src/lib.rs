@@ -1,4 +1,147 @@
+//! ForgeFFI 系统能力实现。
+//!
+//! 各子模块通过 feature 独立启用（默认全部启用），下游可以只依赖需要的部分：
+//!
+//! | feature | 模块 | 说明 |
+//! | --- | --- | --- |
+//! | `netif` | [`netif`] | 网卡枚举与配置 |
+//! | `watch` | `netif::watch` | 网卡变更监听（依赖 `netif`） |
+//! | `agent` | [`agent`] | 周期快照 agent（启用 `netif` 时才会采集网卡） |
+//! | `wifi` | [`wifi`] | 无线网络扫描与连接 |
+//! | `sockets` | [`sockets`] | TCP/UDP socket 与所属进程枚举 |
+//! | `time` | [`time`] | 时区与 NTP 状态查询和配置 |
+//! | `privileges` | [`privileges`] | 进程权限检测与 netif op 预检 |
+//! | `power` | [`power`] | 长时间操作期间阻止系统睡眠 |
+//! | `service` | [`service`] | systemd / Windows 服务 / launchd 的查询与启停 |
+//! | `environment` | [`environment`] | init 系统、网络组件、容器/虚拟化、SELinux/AppArmor 探测 |
+//! | `capture` | [`capture`] | 抓包并写出 pcapng（AF_PACKET / tcpdump / dumpcap） |
+//! | `lldp` | [`lldp`] | LLDP/CDP 邻居发现（lldpd / lldpad / 被动抓包，依赖 `capture` 与 `netif`） |
+//! | `mock` | `netif::mock` | 默认使用内存假网卡表代替系统后端（依赖 `netif`，默认关闭） |
+//!
+//! [`runtime`] 不受 feature 控制：负责进程级 init/shutdown，FFI 层把 watcher、agent 等后台线程
+//! 登记为 shutdown hook，保证宿主反复加载/卸载动态库时线程被确定性地回收。
+//!
+//! 版本策略：
+//!
+//! - crate 版本遵循 semver；`0.x` 阶段 minor 升级可能包含破坏性变更。
+//! - [`API_LEVEL`] 描述公开 API/JSON 契约：新增字段、op 或模块时 `minor` 递增，
+//!   删除或改变既有语义时 `major` 递增。
+//! - JSON 中的 `abi` 字段即 [`forgeffi_base::ABI_VERSION`]，只有线格式不兼容时才会变化。
+//!   `abi` 落在 [`forgeffi_base::MIN_SUPPORTED_ABI`]..=`ABI_VERSION` 内的请求都被接受，
+//!   旧版本请求在解码时升级为当前形状；错误与 apply 响应带 `min_abi`/`max_abi` 供宿主协商。
+
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "agent")]
+pub mod agent;
+#[cfg(feature = "capture")]
+pub mod capture;
+#[cfg(feature = "environment")]
+pub mod environment;
+#[cfg(feature = "lldp")]
+pub mod lldp;
+pub mod metrics;
+#[cfg(feature = "netif")]
 pub mod netif;
+#[cfg(feature = "power")]
+pub mod power;
+#[cfg(feature = "privileges")]
+pub mod privileges;
+pub mod runtime;
+#[cfg(feature = "service")]
+pub mod service;
+#[cfg(feature = "sockets")]
+pub mod sockets;
+#[cfg(feature = "time")]
+pub mod time;
+// span 目前只由 netif apply 打开
+#[cfg_attr(not(feature = "netif"), allow(dead_code))]
+pub mod trace;
+#[cfg(feature = "wifi")]
+pub mod wifi;
+
+#[cfg(any(
+    feature = "agent",
+    feature = "netif",
+    feature = "wifi",
+    feature = "sockets",
+    feature = "time",
+    feature = "privileges",
+    feature = "power",
+    feature = "service",
+    feature = "environment",
+    feature = "capture",
+    feature = "lldp"
+))]
+#[cfg_attr(
+    not(all(
+        feature = "agent",
+        feature = "netif",
+        feature = "wifi",
+        feature = "sockets",
+        feature = "time",
+        feature = "privileges",
+        feature = "power",
+        feature = "service",
+        feature = "environment",
+        feature = "capture",
+        feature = "lldp"
+    )),
+    allow(dead_code)
+)]
+mod util;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ApiLevel {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ApiLevel {
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        ((self.major as u32) << 16) | self.minor as u32
+    }
+
+    #[must_use]
+    pub const fn is_compatible_with(self, required: ApiLevel) -> bool {
+        self.major == required.major && self.minor >= required.minor
+    }
+}
+
+pub const API_LEVEL: ApiLevel = ApiLevel { major: 1, minor: 21 };
+
+#[must_use]
+pub const fn api_level() -> ApiLevel {
+    API_LEVEL
+}
 
+#[must_use]
+pub fn enabled_modules() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "netif")]
+        "netif",
+        #[cfg(feature = "watch")]
+        "watch",
+        #[cfg(feature = "agent")]
+        "agent",
+        #[cfg(feature = "wifi")]
+        "wifi",
+        #[cfg(feature = "sockets")]
+        "sockets",
+        #[cfg(feature = "time")]
+        "time",
+        #[cfg(feature = "privileges")]
+        "privileges",
+        #[cfg(feature = "power")]
+        "power",
+        #[cfg(feature = "service")]
+        "service",
+        #[cfg(feature = "environment")]
+        "environment",
+        #[cfg(feature = "capture")]
+        "capture",
+        #[cfg(feature = "lldp")]
+        "lldp",
+    ]
+}
@@ -0,0 +1,185 @@
+// 链路层邻居发现（LLDP/CDP）：依次尝试 lldpd（lldpctl）、lldpad（lldptool），都不可用时
+// 在候选网卡上被动抓包，每块网卡收到第一个 LLDP/CDP 帧即结束，最长等待 listen_secs。
+// 被动监听不加组播成员也不开混杂模式：Linux 上部分网卡/固件会丢弃 01:80:c2:00:00:0e，
+// 这种情况只能装 lldpd；macOS 的 tcpdump 与 Windows 的 dumpcap 默认开混杂模式，不受影响
+use forgeffi_base::{
+    check_cancelled, current_cancel_token, with_cancel_token, ForgeFfiError, IfaceFlags, IfaceKind, LldpInterface,
+    LldpNeighbor, LldpRequest, LldpResponse, LldpSource, LogLevel, ABI_VERSION, LLDP_DEFAULT_LISTEN_SECS,
+};
+use forgeffi_protocol::{decode_request, encode_json, validate_lldp_request};
+use std::time::{Duration, Instant};
+
+use crate::capture::filter::LINKTYPE_ETHERNET;
+use crate::util::{command, CommandExt};
+
+pub mod parse;
+
+// LLDPDU 最长 1500 字节，CDP 一般也在 1500 以内
+const LISTEN_SNAPLEN: u32 = 2048;
+
+pub fn query(req: &LldpRequest) -> Result<LldpResponse, ForgeFfiError> {
+    validate_lldp_request(req)?;
+    let wanted = req.iface.as_deref();
+
+    match from_lldpctl() {
+        Ok(mut items) => {
+            items.retain(|i| wanted.is_none_or(|w| w == i.name));
+            return Ok(response(LldpSource::Lldpd, items));
+        }
+        Err(e) => debug_fallback("lldpctl", &e),
+    }
+    check_cancelled()?;
+
+    let ifaces = candidates(wanted)?;
+    match from_lldptool(&ifaces) {
+        Ok(items) => return Ok(response(LldpSource::Lldpad, items)),
+        Err(e) => debug_fallback("lldptool", &e),
+    }
+    check_cancelled()?;
+
+    let secs = req.listen_secs.unwrap_or(LLDP_DEFAULT_LISTEN_SECS);
+    let items = listen_all(&ifaces, Duration::from_secs(u64::from(secs)), wanted.is_some())?;
+    Ok(response(LldpSource::Capture, items))
+}
+
+pub fn query_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: LldpRequest = match req_json {
+        Some(s) => decode_request(s)?,
+        None => LldpRequest::v1(),
+    };
+    encode_json("LLDP 邻居", &query(&req)?)
+}
+
+fn response(source: LldpSource, mut items: Vec<LldpInterface>) -> LldpResponse {
+    items.retain(|i| !i.neighbors.is_empty());
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    LldpResponse {
+        abi: ABI_VERSION,
+        source,
+        items,
+    }
+}
+
+fn debug_fallback(program: &str, e: &ForgeFfiError) {
+    crate::runtime::log(LogLevel::Debug, format_args!("{program} 不可用，改用下一种方式: {}", e.message));
+}
+
+// 指定网卡时只查它；否则取已启用、带 MAC 的物理网卡（虚拟网卡、网桥、隧道收不到交换机的 LLDP）
+fn candidates(wanted: Option<&str>) -> Result<Vec<String>, ForgeFfiError> {
+    if let Some(name) = wanted {
+        return Ok(vec![name.to_string()]);
+    }
+    Ok(crate::netif::list_interfaces()?
+        .into_iter()
+        .filter(|it| {
+            matches!(it.kind, IfaceKind::Physical | IfaceKind::Unknown)
+                && it.mac.is_some()
+                && it.flags.0 & IfaceFlags::UP != 0
+                && it.flags.0 & IfaceFlags::LOOPBACK == 0
+        })
+        .map(|it| it.name)
+        .collect())
+}
+
+fn run(program: &str, args: &[&str]) -> Result<Vec<u8>, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| crate::util::map_spawn_error(program, e))?;
+    if out.status.success() {
+        Ok(out.stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        Err(crate::util::command_failed(
+            program,
+            args,
+            &stderr,
+            format!("命令失败: {program} {args:?}: {}", stderr.trim()),
+        ))
+    }
+}
+
+// lldpd 没在运行时 lldpctl 连不上 socket 而以非零状态退出
+fn from_lldpctl() -> Result<Vec<LldpInterface>, ForgeFfiError> {
+    parse::parse_lldpctl_json(&run("lldpctl", &["-f", "json"])?)
+}
+
+// lldptool 每次只查一块网卡；全部失败（lldpad 未运行或未管理这些网卡）才算不可用
+fn from_lldptool(ifaces: &[String]) -> Result<Vec<LldpInterface>, ForgeFfiError> {
+    let mut items = Vec::new();
+    let mut last_err = None;
+    for name in ifaces {
+        match run("lldptool", &["-t", "-n", "-i", name]) {
+            Ok(out) => items.push(LldpInterface {
+                name: name.clone(),
+                neighbors: parse::parse_lldptool_text(&String::from_utf8_lossy(&out)).into_iter().collect(),
+            }),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    match last_err {
+        Some(e) if items.is_empty() => Err(e),
+        _ if ifaces.is_empty() => Err(ForgeFfiError::not_found("没有可查询的网卡")),
+        _ => Ok(items),
+    }
+}
+
+// 每块网卡一个线程并行监听。指定了网卡时错误直接返回；查询全部网卡时个别网卡打不开只记日志，
+// 全部失败才返回第一个错误
+fn listen_all(ifaces: &[String], window: Duration, strict: bool) -> Result<Vec<LldpInterface>, ForgeFfiError> {
+    let deadline = Instant::now() + window;
+    let cancel = current_cancel_token();
+    let results: Vec<(String, Result<Vec<LldpNeighbor>, ForgeFfiError>)> = std::thread::scope(|s| {
+        let handles: Vec<_> = ifaces
+            .iter()
+            .map(|name| {
+                let cancel = cancel.clone();
+                s.spawn(move || with_cancel_token(cancel, || listen(name, deadline)))
+            })
+            .collect();
+        ifaces
+            .iter()
+            .cloned()
+            .zip(handles)
+            .map(|(name, h)| {
+                let r = h
+                    .join()
+                    .unwrap_or_else(|_| Err(ForgeFfiError::system_error("LLDP 监听线程异常退出")));
+                (name, r)
+            })
+            .collect()
+    });
+
+    let mut items = Vec::new();
+    let mut first_err = None;
+    for (name, r) in results {
+        match r {
+            Ok(neighbors) => items.push(LldpInterface { name, neighbors }),
+            Err(e) if strict || e.code == forgeffi_base::ErrorCode::Cancelled => return Err(e),
+            Err(e) => {
+                crate::runtime::log(LogLevel::Debug, format_args!("{name} 无法监听 LLDP: {}", e.message));
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    match first_err {
+        Some(e) if items.is_empty() => Err(e),
+        _ => Ok(items),
+    }
+}
+
+fn listen(iface: &str, deadline: Instant) -> Result<Vec<LldpNeighbor>, ForgeFfiError> {
+    let mut source = crate::capture::open_source(iface, LISTEN_SNAPLEN)?;
+    if source.linktype() != LINKTYPE_ETHERNET {
+        return Ok(Vec::new());
+    }
+    while Instant::now() < deadline {
+        check_cancelled()?;
+        if let Some(pkt) = source.next()?
+            && let Some(n) = parse::parse_discovery_frame(&pkt.data)
+        {
+            return Ok(vec![n]);
+        }
+    }
+    Ok(Vec::new())
+}
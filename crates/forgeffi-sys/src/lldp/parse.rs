@@ -0,0 +1,343 @@
+// lldpctl -f json、lldptool -t -n 的输出与抓到的 LLDP/CDP 帧。只做转换不执行命令，
+// 任何平台都能编译，tests/parsers.rs 用 golden 样本比对
+use forgeffi_base::{ForgeFfiError, LldpInterface, LldpNeighbor, LldpProtocol};
+use serde_json::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+const ETHERTYPE_LLDP: u16 = 0x88cc;
+const CDP_DST: [u8; 6] = [0x01, 0x00, 0x0c, 0xcc, 0xcc, 0xcc];
+// 802.2 LLC（AA AA 03）+ SNAP（OUI 00000C，类型 0x2000）
+const CDP_SNAP: [u8; 8] = [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x0c, 0x20, 0x00];
+const IEEE_8021_OUI: [u8; 3] = [0x00, 0x80, 0xc2];
+
+fn empty_neighbor(protocol: LldpProtocol) -> LldpNeighbor {
+    LldpNeighbor {
+        protocol,
+        chassis_id: String::new(),
+        chassis_id_type: None,
+        port_id: String::new(),
+        port_id_type: None,
+        port_description: None,
+        system_name: None,
+        system_description: None,
+        mgmt_addresses: Vec::new(),
+        vlan_id: None,
+        ttl_secs: None,
+    }
+}
+
+fn complete(n: LldpNeighbor) -> Option<LldpNeighbor> {
+    (!n.chassis_id.is_empty() && !n.port_id.is_empty()).then_some(n)
+}
+
+fn normalize_id(kind: Option<&str>, value: &str) -> String {
+    if kind == Some("mac") {
+        value.to_ascii_lowercase()
+    } else {
+        value.to_string()
+    }
+}
+
+// lldpctl -f json 在只有一个元素时输出对象、多个时输出数组
+fn as_list(v: Option<&Value>) -> Vec<&Value> {
+    match v {
+        Some(Value::Array(a)) => a.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(other) => vec![other],
+    }
+}
+
+fn text(v: Option<&Value>) -> Option<String> {
+    match v? {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        // 老版本把值包在 {"value": ...} 里
+        Value::Object(o) => text(o.get("value")),
+        _ => None,
+    }
+}
+
+fn id_of(v: Option<&Value>) -> (Option<String>, Option<String>) {
+    match v {
+        Some(Value::Object(o)) => {
+            let kind = text(o.get("type"));
+            let value = text(o.get("value")).map(|s| normalize_id(kind.as_deref(), &s));
+            (kind, value)
+        }
+        other => (None, text(other)),
+    }
+}
+
+// 同一网卡有多个邻居时 lldpctl 会重复输出该网卡，这里按网卡名合并
+pub fn parse_lldpctl_json(bytes: &[u8]) -> Result<Vec<LldpInterface>, ForgeFfiError> {
+    let root: Value = serde_json::from_slice(bytes)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 lldpctl JSON 失败: {e}")))?;
+    let lldp = root
+        .get("lldp")
+        .ok_or_else(|| ForgeFfiError::system_error("lldpctl JSON 缺少 lldp 字段"))?;
+    let mut out: Vec<LldpInterface> = Vec::new();
+    for entry in as_list(lldp.get("interface")) {
+        let Some(obj) = entry.as_object() else { continue };
+        for (name, iface) in obj {
+            let Some(n) = lldpctl_neighbor(iface) else { continue };
+            match out.iter_mut().find(|i| &i.name == name) {
+                Some(i) => i.neighbors.push(n),
+                None => out.push(LldpInterface {
+                    name: name.clone(),
+                    neighbors: vec![n],
+                }),
+            }
+        }
+    }
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(out)
+}
+
+fn lldpctl_neighbor(iface: &Value) -> Option<LldpNeighbor> {
+    // EDP/FDP/SONMP 等厂商协议不在返回范围内
+    let protocol = match iface.get("via")?.as_str()? {
+        "LLDP" => LldpProtocol::Lldp,
+        v if v.starts_with("CDP") => LldpProtocol::Cdp,
+        _ => return None,
+    };
+    let mut n = empty_neighbor(protocol);
+
+    // 有系统名时 chassis 是 {"<名字>": {...}}，没有时直接是 {...}
+    let chassis = iface.get("chassis")?.as_object()?;
+    let chassis = if chassis.contains_key("id") {
+        chassis
+    } else {
+        let (name, inner) = chassis.iter().next()?;
+        n.system_name = Some(name.clone());
+        inner.as_object()?
+    };
+    (n.chassis_id_type, n.chassis_id) = match id_of(chassis.get("id")) {
+        (kind, Some(v)) => (kind, v),
+        _ => return None,
+    };
+    if n.system_name.is_none() {
+        n.system_name = text(chassis.get("name"));
+    }
+    n.system_description = text(chassis.get("descr"));
+    n.mgmt_addresses = as_list(chassis.get("mgmt-ip")).into_iter().filter_map(|v| text(Some(v))).collect();
+
+    let port = iface.get("port")?;
+    (n.port_id_type, n.port_id) = match id_of(port.get("id")) {
+        (kind, Some(v)) => (kind, v),
+        _ => return None,
+    };
+    n.port_description = text(port.get("descr"));
+    n.ttl_secs = text(port.get("ttl"))
+        .or_else(|| text(chassis.get("ttl")))
+        .and_then(|s| s.parse().ok());
+
+    let vlans = as_list(iface.get("vlan"));
+    let pvid = vlans
+        .iter()
+        .find(|v| v.get("pvid").and_then(Value::as_bool) == Some(true))
+        .or(vlans.first());
+    n.vlan_id = pvid.and_then(|v| text(v.get("vlan-id"))).and_then(|s| s.parse().ok());
+    complete(n)
+}
+
+// lldptool -t -n -i IF：每个 TLV 一行标题（以 TLV 结尾，不缩进），值在其后缩进的行里
+pub fn parse_lldptool_text(text: &str) -> Option<LldpNeighbor> {
+    let mut n = empty_neighbor(LldpProtocol::Lldp);
+    let mut tlv = "";
+    let mut values: Vec<&str> = Vec::new();
+    let mut flush = |tlv: &str, values: &mut Vec<&str>| {
+        let joined = values.join("\n");
+        let first = values.first().copied().unwrap_or_default();
+        match tlv {
+            "Chassis ID TLV" => (n.chassis_id_type, n.chassis_id) = lldptool_id(first),
+            "Port ID TLV" => (n.port_id_type, n.port_id) = lldptool_id(first),
+            "Time to Live TLV" => n.ttl_secs = first.parse().ok(),
+            "Port Description TLV" => n.port_description = Some(joined).filter(|s| !s.is_empty()),
+            "System Name TLV" => n.system_name = Some(joined).filter(|s| !s.is_empty()),
+            "System Description TLV" => n.system_description = Some(joined).filter(|s| !s.is_empty()),
+            "Management Address TLV" => n.mgmt_addresses.extend(values.iter().filter_map(|v| {
+                let (kind, addr) = v.split_once(':')?;
+                matches!(kind, "IPv4" | "IPv6").then(|| addr.trim().to_string())
+            })),
+            "Port VLAN ID TLV" => {
+                n.vlan_id = first.strip_prefix("PVID:").and_then(|v| v.trim().parse().ok());
+            }
+            _ => {}
+        }
+        values.clear();
+    };
+    for line in text.lines() {
+        if line.starts_with(char::is_whitespace) {
+            let v = line.trim();
+            if !v.is_empty() {
+                values.push(v);
+            }
+        } else if line.trim_end().ends_with("TLV") {
+            flush(tlv, &mut values);
+            tlv = line.trim_end();
+        }
+    }
+    flush(tlv, &mut values);
+    complete(n)
+}
+
+fn lldptool_id(line: &str) -> (Option<String>, String) {
+    match line.split_once(": ") {
+        Some((kind, value)) => {
+            let kind = match kind.to_ascii_lowercase().as_str() {
+                "ipv4" | "ipv6" => "ip".to_string(),
+                other => other.replace(' ', "_"),
+            };
+            let value = normalize_id(Some(&kind), value.trim());
+            (Some(kind), value)
+        }
+        None => (None, line.to_string()),
+    }
+}
+
+// 以太网帧；不是 LLDP/CDP 或缺少必需 TLV 时返回 None
+pub fn parse_discovery_frame(frame: &[u8]) -> Option<LldpNeighbor> {
+    let ethertype = u16::from_be_bytes([*frame.get(12)?, *frame.get(13)?]);
+    if ethertype == ETHERTYPE_LLDP {
+        return parse_lldpdu(frame.get(14..)?);
+    }
+    // CDP 用 802.3 长度字段而不是 EtherType
+    if frame.get(..6)? == CDP_DST && frame.get(14..22)? == CDP_SNAP {
+        return parse_cdp(frame.get(22..)?);
+    }
+    None
+}
+
+fn lossy(b: &[u8]) -> String {
+    String::from_utf8_lossy(b).trim_end_matches(['\0', ' ', '\n', '\r']).to_string()
+}
+
+fn mac(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{x:02x}")).collect::<Vec<_>>().join(":")
+}
+
+// LLDP 管理地址与 network address 子类型的地址族编号（IANA）
+fn network_address(b: &[u8]) -> Option<String> {
+    match (b.first()?, b.len()) {
+        (1, 5) => Some(Ipv4Addr::new(b[1], b[2], b[3], b[4]).to_string()),
+        (2, 17) => {
+            let mut a = [0u8; 16];
+            a.copy_from_slice(&b[1..]);
+            Some(Ipv6Addr::from(a).to_string())
+        }
+        _ => None,
+    }
+}
+
+fn lldp_id(subtype: u8, value: &[u8], chassis: bool) -> (Option<String>, String) {
+    let kind = match (chassis, subtype) {
+        (true, 1) => "chassis",
+        (true, 2) | (false, 1) => "ifalias",
+        (true, 3) | (false, 2) => "port",
+        (true, 4) | (false, 3) => "mac",
+        (true, 5) | (false, 4) => "ip",
+        (true, 6) | (false, 5) => "ifname",
+        (false, 6) => "agent_circuit",
+        _ => "local",
+    };
+    let text = match kind {
+        "mac" if value.len() == 6 => mac(value),
+        "ip" => network_address(value).unwrap_or_else(|| lossy(value)),
+        _ => lossy(value),
+    };
+    (Some(kind.to_string()), text)
+}
+
+fn parse_lldpdu(mut p: &[u8]) -> Option<LldpNeighbor> {
+    let mut n = empty_neighbor(LldpProtocol::Lldp);
+    while p.len() >= 2 {
+        let hdr = u16::from_be_bytes([p[0], p[1]]);
+        let (ty, len) = (hdr >> 9, usize::from(hdr & 0x1ff));
+        let v = p.get(2..2 + len)?;
+        p = &p[2 + len..];
+        match ty {
+            0 => break,
+            1 if !v.is_empty() => (n.chassis_id_type, n.chassis_id) = lldp_id(v[0], &v[1..], true),
+            2 if !v.is_empty() => (n.port_id_type, n.port_id) = lldp_id(v[0], &v[1..], false),
+            3 if v.len() == 2 => n.ttl_secs = Some(u32::from(u16::from_be_bytes([v[0], v[1]]))),
+            4 => n.port_description = Some(lossy(v)),
+            5 => n.system_name = Some(lossy(v)),
+            6 => n.system_description = Some(lossy(v)),
+            8 => {
+                // 地址串长度（含子类型字节）+ 地址族 + 地址
+                let alen = usize::from(*v.first()?);
+                if let Some(addr) = v.get(1..1 + alen).and_then(network_address) {
+                    n.mgmt_addresses.push(addr);
+                }
+            }
+            127 if v.len() >= 6 && v[..3] == IEEE_8021_OUI && v[3] == 1 => {
+                n.vlan_id = Some(u16::from_be_bytes([v[4], v[5]]));
+            }
+            _ => {}
+        }
+    }
+    complete(n)
+}
+
+fn cdp_addresses(v: &[u8], out: &mut Vec<String>) {
+    let Some(count) = v.get(..4).map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]])) else {
+        return;
+    };
+    let mut p = &v[4..];
+    for _ in 0..count.min(16) {
+        // 协议类型（1 = NLPID）、协议长度、协议、地址长度（2 字节）、地址
+        let Some(&plen) = p.get(1) else { return };
+        let plen = usize::from(plen);
+        let Some(proto) = p.get(2..2 + plen) else { return };
+        let Some(alen) = p.get(2 + plen..4 + plen).map(|b| usize::from(u16::from_be_bytes([b[0], b[1]]))) else {
+            return;
+        };
+        let Some(addr) = p.get(4 + plen..4 + plen + alen) else { return };
+        match (p[0], proto, addr.len()) {
+            (1, [0xcc], 4) => out.push(Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3]).to_string()),
+            // 802.2 格式、SNAP 协议 0x86DD
+            (2, [0xaa, 0xaa, 0x03, 0, 0, 0, 0x86, 0xdd], 16) => {
+                let mut a = [0u8; 16];
+                a.copy_from_slice(addr);
+                out.push(Ipv6Addr::from(a).to_string());
+            }
+            _ => {}
+        }
+        p = &p[4 + plen + alen..];
+    }
+}
+
+fn parse_cdp(p: &[u8]) -> Option<LldpNeighbor> {
+    let mut n = empty_neighbor(LldpProtocol::Cdp);
+    n.ttl_secs = Some(u32::from(*p.get(1)?));
+    let mut p = p.get(4..)?;
+    let mut addresses = Vec::new();
+    let mut mgmt = Vec::new();
+    while p.len() >= 4 {
+        let ty = u16::from_be_bytes([p[0], p[1]]);
+        let len = usize::from(u16::from_be_bytes([p[2], p[3]]));
+        if len < 4 {
+            break;
+        }
+        let v = p.get(4..len)?;
+        p = &p[len..];
+        match ty {
+            0x0001 => {
+                n.chassis_id = lossy(v);
+                n.system_name = Some(n.chassis_id.clone());
+            }
+            0x0002 => cdp_addresses(v, &mut addresses),
+            0x0003 => {
+                n.port_id = lossy(v);
+                n.port_id_type = Some("ifname".to_string());
+            }
+            0x0005 => n.system_description = Some(lossy(v)),
+            0x000a if v.len() == 2 => n.vlan_id = Some(u16::from_be_bytes([v[0], v[1]])),
+            0x0016 => cdp_addresses(v, &mut mgmt),
+            _ => {}
+        }
+    }
+    // 优先用专门的管理地址 TLV，没有时退回设备地址
+    n.mgmt_addresses = if mgmt.is_empty() { addresses } else { mgmt };
+    complete(n)
+}
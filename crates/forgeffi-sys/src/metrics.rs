@@ -0,0 +1,192 @@
+// 轻量指标：每个结束的 trace span 计入按 (span, op) 聚合的计数与延迟直方图，span 内的外部命令按程序名计数。
+// 长期运行的宿主定期拉取 JSON 或 Prometheus 文本即可监控，不必自己给每次调用打点
+use forgeffi_base::{
+    CommandMetrics, ErrorCode, ForgeFfiError, MetricsResponse, OperationMetrics, TraceSpan, ABI_VERSION,
+    METRICS_LATENCY_BUCKETS_MS,
+};
+use forgeffi_protocol::encode_json;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Mutex, MutexGuard};
+
+const BUCKETS: usize = METRICS_LATENCY_BUCKETS_MS.len() + 1;
+
+#[derive(Default)]
+struct OpStats {
+    total: u64,
+    ok: u64,
+    failed: u64,
+    skipped: u64,
+    errors: BTreeMap<ErrorCode, u64>,
+    latency: [u64; BUCKETS],
+    sum_us: u64,
+    max_us: u64,
+}
+
+#[derive(Default)]
+struct CommandStats {
+    total: u64,
+    failed: u64,
+    sum_us: u64,
+}
+
+struct Registry {
+    ops: BTreeMap<(String, Option<String>), OpStats>,
+    commands: BTreeMap<String, CommandStats>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    ops: BTreeMap::new(),
+    commands: BTreeMap::new(),
+});
+
+fn registry() -> MutexGuard<'static, Registry> {
+    match REGISTRY.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+// 由 trace 在 span 结束时调用，与 trace_capacity 无关
+pub(crate) fn observe(span: &TraceSpan) {
+    let mut g = registry();
+    let key = (span.name.clone(), span.fields.get("op").cloned());
+    let s = g.ops.entry(key).or_default();
+    s.total += 1;
+    if !span.ok {
+        s.failed += 1;
+    } else if span.fields.contains_key("skipped") {
+        s.skipped += 1;
+    } else {
+        s.ok += 1;
+    }
+    if let Some(code) = span.error {
+        *s.errors.entry(code).or_default() += 1;
+    }
+    let bucket = METRICS_LATENCY_BUCKETS_MS
+        .iter()
+        .position(|b| span.duration_us <= b * 1000)
+        .unwrap_or(BUCKETS - 1);
+    s.latency[bucket] += 1;
+    s.sum_us += span.duration_us;
+    s.max_us = s.max_us.max(span.duration_us);
+
+    for c in &span.commands {
+        let s = g.commands.entry(c.program.clone()).or_default();
+        s.total += 1;
+        if c.error.is_some() || c.exit_code != Some(0) {
+            s.failed += 1;
+        }
+        s.sum_us += c.duration_us;
+    }
+}
+
+pub fn snapshot() -> MetricsResponse {
+    let g = registry();
+    MetricsResponse {
+        abi: ABI_VERSION,
+        latency_buckets_ms: METRICS_LATENCY_BUCKETS_MS.to_vec(),
+        operations: g
+            .ops
+            .iter()
+            .map(|((span, op), s)| OperationMetrics {
+                span: span.clone(),
+                op: op.clone(),
+                total: s.total,
+                ok: s.ok,
+                failed: s.failed,
+                skipped: s.skipped,
+                errors: s.errors.clone(),
+                latency_counts: s.latency.to_vec(),
+                latency_sum_us: s.sum_us,
+                latency_max_us: s.max_us,
+            })
+            .collect(),
+        commands: g
+            .commands
+            .iter()
+            .map(|(program, s)| CommandMetrics {
+                program: program.clone(),
+                total: s.total,
+                failed: s.failed,
+                duration_sum_us: s.sum_us,
+            })
+            .collect(),
+    }
+}
+
+pub fn json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("指标", &snapshot())
+}
+
+// Prometheus 文本格式（0.0.4）；延迟以秒为单位，桶为累计计数
+pub fn prometheus_text() -> String {
+    let m = snapshot();
+    let mut out = String::new();
+    out.push_str("# HELP forgeffi_operations_total 结束的操作数，按结果分类\n");
+    out.push_str("# TYPE forgeffi_operations_total counter\n");
+    for o in &m.operations {
+        let labels = op_labels(o);
+        for (result, n) in [("ok", o.ok), ("failed", o.failed), ("skipped", o.skipped)] {
+            let _ = writeln!(out, "forgeffi_operations_total{{{labels},result=\"{result}\"}} {n}");
+        }
+    }
+    out.push_str("# HELP forgeffi_operation_errors_total 失败的操作数，按错误码分类\n");
+    out.push_str("# TYPE forgeffi_operation_errors_total counter\n");
+    for o in &m.operations {
+        let labels = op_labels(o);
+        for (code, n) in &o.errors {
+            let _ = writeln!(out, "forgeffi_operation_errors_total{{{labels},code=\"{code:?}\"}} {n}");
+        }
+    }
+    out.push_str("# HELP forgeffi_operation_duration_seconds 操作耗时\n");
+    out.push_str("# TYPE forgeffi_operation_duration_seconds histogram\n");
+    for o in &m.operations {
+        let labels = op_labels(o);
+        let mut cumulative = 0;
+        for (i, n) in o.latency_counts.iter().enumerate() {
+            cumulative += n;
+            let le = match m.latency_buckets_ms.get(i) {
+                Some(ms) => format!("{}", *ms as f64 / 1000.0),
+                None => "+Inf".to_string(),
+            };
+            let _ = writeln!(out, "forgeffi_operation_duration_seconds_bucket{{{labels},le=\"{le}\"}} {cumulative}");
+        }
+        let _ = writeln!(
+            out,
+            "forgeffi_operation_duration_seconds_sum{{{labels}}} {}",
+            o.latency_sum_us as f64 / 1_000_000.0
+        );
+        let _ = writeln!(out, "forgeffi_operation_duration_seconds_count{{{labels}}} {}", o.total);
+    }
+    out.push_str("# HELP forgeffi_commands_total 执行的外部命令数\n");
+    out.push_str("# TYPE forgeffi_commands_total counter\n");
+    for c in &m.commands {
+        let program = escape_label(&c.program);
+        let _ = writeln!(out, "forgeffi_commands_total{{program=\"{program}\",result=\"ok\"}} {}", c.total - c.failed);
+        let _ = writeln!(out, "forgeffi_commands_total{{program=\"{program}\",result=\"failed\"}} {}", c.failed);
+    }
+    out.push_str("# HELP forgeffi_command_duration_seconds_total 外部命令累计耗时\n");
+    out.push_str("# TYPE forgeffi_command_duration_seconds_total counter\n");
+    for c in &m.commands {
+        let _ = writeln!(
+            out,
+            "forgeffi_command_duration_seconds_total{{program=\"{}\"}} {}",
+            escape_label(&c.program),
+            c.duration_sum_us as f64 / 1_000_000.0
+        );
+    }
+    out
+}
+
+fn op_labels(o: &OperationMetrics) -> String {
+    format!(
+        "span=\"{}\",op=\"{}\"",
+        escape_label(&o.span),
+        escape_label(o.op.as_deref().unwrap_or_default())
+    )
+}
+
+fn escape_label(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,117 @@
+use forgeffi_base::{
+    ForgeFfiError, LogLevel, NetIfBackend, NetIfBackendInfo, NetIfBackendRole, NetIfBackendsResponse,
+};
+use std::sync::Mutex;
+
+use super::{platform, NETIF_ABI_VERSION};
+use crate::runtime::log;
+
+pub const BACKEND_ENV: &str = "FORGEFFI_NETIF_BACKEND";
+
+static PINNED: Mutex<Option<NetIfBackend>> = Mutex::new(None);
+
+pub fn set_pinned(backend: Option<NetIfBackend>) -> Result<(), ForgeFfiError> {
+    if let Some(b) = backend {
+        check_pinnable(b)?;
+    }
+    match PINNED.lock() {
+        Ok(mut g) => *g = backend,
+        Err(p) => *p.into_inner() = backend,
+    }
+    super::clear_caches();
+    Ok(())
+}
+
+// 运行时配置优先于环境变量；环境变量取值非法或当前平台不可用时忽略并告警
+pub fn pinned() -> Option<NetIfBackend> {
+    let configured = match PINNED.lock() {
+        Ok(g) => *g,
+        Err(p) => *p.into_inner(),
+    };
+    if configured.is_some() {
+        return configured;
+    }
+    let raw = std::env::var(BACKEND_ENV).ok().filter(|s| !s.trim().is_empty())?;
+    match NetIfBackend::parse(&raw).map(|b| check_pinnable(b).map(|_| b)) {
+        Some(Ok(b)) => Some(b),
+        Some(Err(e)) => {
+            log(LogLevel::Warn, format_args!("忽略 {BACKEND_ENV}={raw}: {}", e.message));
+            None
+        }
+        None => {
+            log(LogLevel::Warn, format_args!("忽略 {BACKEND_ENV}={raw}: 未知的后端"));
+            None
+        }
+    }
+}
+
+fn check_pinnable(backend: NetIfBackend) -> Result<(), ForgeFfiError> {
+    if backend == NetIfBackend::Mock {
+        return Ok(());
+    }
+    match platform::BACKENDS.iter().find(|(b, _, _)| *b == backend) {
+        Some((_, _, true)) => Ok(()),
+        Some((_, _, false)) => Err(ForgeFfiError::not_implemented(format!(
+            "netif 后端 {} 尚未实现",
+            backend.as_str()
+        ))),
+        None => Err(ForgeFfiError::unsupported(format!(
+            "当前平台没有 netif 后端 {}",
+            backend.as_str()
+        ))),
+    }
+}
+
+// 同一 role 内：固定了该 role 的后端就只选它，否则选第一个已实现且可用的；
+// mock 启用时所有操作都走内存网卡表，系统后端一律不选
+pub fn backends() -> NetIfBackendsResponse {
+    let pinned = pinned();
+    let mock = super::mock::active();
+    let pinned_role = platform::BACKENDS
+        .iter()
+        .find(|(b, _, _)| Some(*b) == pinned)
+        .map(|(_, role, _)| *role);
+    let mut items: Vec<NetIfBackendInfo> = platform::BACKENDS
+        .iter()
+        .map(|&(backend, role, implemented)| NetIfBackendInfo {
+            backend,
+            role,
+            available: platform::backend_available(backend),
+            implemented,
+            selected: false,
+            notes: platform::backend_notes(backend),
+        })
+        .collect();
+    if mock {
+        items.push(NetIfBackendInfo {
+            backend: NetIfBackend::Mock,
+            role: NetIfBackendRole::Runtime,
+            available: true,
+            implemented: true,
+            selected: true,
+            notes: Some("内存中的假网卡表，不修改系统".to_string()),
+        });
+    } else {
+        for role in [NetIfBackendRole::Runtime, NetIfBackendRole::Persist] {
+            let pick = items.iter().position(|it| {
+                it.role == role
+                    && if pinned_role == Some(role) {
+                        Some(it.backend) == pinned
+                    } else {
+                        it.implemented && it.available
+                    }
+            });
+            if let Some(i) = pick {
+                items[i].selected = true;
+                if !items[i].available {
+                    items[i].notes = Some("已固定使用但当前不可用，相关操作会失败".to_string());
+                }
+            }
+        }
+    }
+    NetIfBackendsResponse {
+        abi: NETIF_ABI_VERSION,
+        pinned,
+        items,
+    }
+}
@@ -0,0 +1,56 @@
+use forgeffi_base::{ForgeFfiError, NetInterface};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// 0 表示关闭缓存（默认），每次 list 都直接枚举系统
+static TTL_MS: AtomicU64 = AtomicU64::new(0);
+// invalidate 时递增；枚举期间发生过失效则结果不入缓存
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static CACHE: Mutex<Option<(Instant, Vec<NetInterface>)>> = Mutex::new(None);
+
+fn lock() -> MutexGuard<'static, Option<(Instant, Vec<NetInterface>)>> {
+    match CACHE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+pub fn set_ttl_ms(ttl_ms: u64) {
+    TTL_MS.store(ttl_ms, Ordering::Release);
+    invalidate();
+}
+
+pub fn ttl_ms() -> u64 {
+    TTL_MS.load(Ordering::Acquire)
+}
+
+pub fn invalidate() {
+    let mut g = lock();
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    *g = None;
+}
+
+// 缓存的是平台层的原始结果；排序、网关回填等仍在每次调用时做
+pub(super) fn get_or_fetch(
+    fetch: impl FnOnce() -> Result<Vec<NetInterface>, ForgeFfiError>,
+) -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let ttl = ttl_ms();
+    if ttl == 0 {
+        return fetch();
+    }
+    let ttl = Duration::from_millis(ttl);
+    if let Some((at, items)) = lock().as_ref()
+        && at.elapsed() < ttl
+    {
+        return Ok(items.clone());
+    }
+    // 枚举期间不持锁，避免慢命令阻塞 invalidate
+    let generation = GENERATION.load(Ordering::Acquire);
+    let items = fetch()?;
+    let mut g = lock();
+    if GENERATION.load(Ordering::Acquire) == generation {
+        *g = Some((Instant::now(), items.clone()));
+    }
+    Ok(items)
+}
@@ -0,0 +1,383 @@
+// commit-confirm：apply 前先落盘待确认记录（含执行前快照），apply 后由后台线程计时，超时未确认则按记录回滚。
+// 记录在确认或回滚成功后才删除；进程崩溃遗留的记录在下次 init 时重新计时，已过期的立即回滚。
+// 记录目录只应由一个进程使用，否则会回滚其它进程的变更。init 会以当前权限按记录回滚，
+// 因此目录与记录都必须归当前有效用户所有、不对其他用户开放，否则整个目录或该记录被忽略
+use super::*;
+
+use crate::runtime::log;
+use crate::util::map_io_error;
+use forgeffi_base::{IpOrigin, LogLevel, NetIfOpSpec, NetIfPendingConfirm, PersistSchema};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::MutexGuard;
+use std::thread::JoinHandle;
+
+const RECORD_SCHEMA: PersistSchema = PersistSchema {
+    kind: "netif_pending_confirm",
+    version: 1,
+    migrations: &[],
+};
+
+static TOKEN_SEQ: AtomicU64 = AtomicU64::new(0);
+static PENDING: Mutex<Vec<Pending>> = Mutex::new(Vec::new());
+
+enum Signal {
+    Confirm,
+    RevertNow,
+}
+
+struct Pending {
+    token: String,
+    target: String,
+    tx: mpsc::Sender<Signal>,
+    handle: Option<JoinHandle<()>>,
+}
+
+fn lock() -> MutexGuard<'static, Vec<Pending>> {
+    match PENDING.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+fn take(token: &str) -> Option<Pending> {
+    let mut g = lock();
+    let i = g.iter().position(|p| p.token == token)?;
+    Some(g.remove(i))
+}
+
+fn record_dir() -> PathBuf {
+    crate::runtime::config()
+        .netif_confirm_dir
+        .map_or_else(default_record_dir, PathBuf::from)
+}
+
+#[cfg(unix)]
+fn default_record_dir() -> PathBuf {
+    PathBuf::from("/var/lib/forgeffi/netif-confirm")
+}
+
+#[cfg(not(unix))]
+fn default_record_dir() -> PathBuf {
+    std::env::var_os("ProgramData")
+        .map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from)
+        .join("forgeffi")
+        .join("netif-confirm")
+}
+
+fn record_path(token: &str) -> PathBuf {
+    record_dir().join(format!("{token}.json"))
+}
+
+// 不存在时以 0700 创建；已存在的目录必须是当前有效用户所有、组和其他用户无任何权限的真实目录
+#[cfg(unix)]
+fn ensure_private_dir(dir: &Path) -> Result<(), ForgeFfiError> {
+    use std::os::unix::fs::DirBuilderExt;
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+        .map_err(map_io_error)?;
+    check_private_dir(dir)
+}
+
+#[cfg(unix)]
+fn check_private_dir(dir: &Path) -> Result<(), ForgeFfiError> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = std::fs::symlink_metadata(dir).map_err(map_io_error)?;
+    if !meta.is_dir() || meta.uid() != nix::unistd::geteuid().as_raw() || meta.mode() & 0o077 != 0 {
+        return Err(ForgeFfiError::permission_denied(format!(
+            "commit-confirm 记录目录 {} 须为当前用户所有且权限为 0700（当前 uid={} mode={:o}）",
+            dir.display(),
+            meta.uid(),
+            meta.mode() & 0o7777
+        )));
+    }
+    Ok(())
+}
+
+// 只接受当前有效用户所有的普通文件，符号链接与他人放入的文件一律跳过
+#[cfg(unix)]
+fn check_record_file(path: &Path) -> Result<(), ForgeFfiError> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = std::fs::symlink_metadata(path).map_err(map_io_error)?;
+    if !meta.is_file() || meta.uid() != nix::unistd::geteuid().as_raw() {
+        return Err(ForgeFfiError::permission_denied(format!("记录不属于当前用户（uid={}）", meta.uid())));
+    }
+    Ok(())
+}
+
+// %ProgramData% 下新建的子目录默认只有创建者与管理员可写，这里不另行检查 ACL
+#[cfg(not(unix))]
+fn ensure_private_dir(dir: &Path) -> Result<(), ForgeFfiError> {
+    std::fs::create_dir_all(dir).map_err(map_io_error)
+}
+
+#[cfg(not(unix))]
+fn check_private_dir(_dir: &Path) -> Result<(), ForgeFfiError> {
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_record_file(path: &Path) -> Result<(), ForgeFfiError> {
+    let meta = std::fs::symlink_metadata(path).map_err(map_io_error)?;
+    if !meta.is_file() {
+        return Err(ForgeFfiError::permission_denied("记录不是普通文件"));
+    }
+    Ok(())
+}
+
+// 先以 create_new（不跟随符号链接）和 0600 写临时文件，再改名覆盖正式记录
+fn write_record(record: &NetIfPendingConfirm) -> Result<(), ForgeFfiError> {
+    let dir = record_dir();
+    ensure_private_dir(&dir)?;
+    let bytes = RECORD_SCHEMA.encode_json(record)?;
+    let tmp = dir.join(format!(
+        ".{}.json.tmp.{}.{}",
+        record.token,
+        std::process::id(),
+        TOKEN_SEQ.fetch_add(1, AtomicOrdering::Relaxed)
+    ));
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut opts, 0o600);
+    let written = opts
+        .open(&tmp)
+        .and_then(|mut f| f.write_all(&bytes))
+        .and_then(|()| std::fs::rename(&tmp, record_path(&record.token)));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(map_io_error(e));
+    }
+    Ok(())
+}
+
+fn remove_record(token: &str) {
+    let _ = std::fs::remove_file(record_path(token));
+}
+
+fn new_token() -> String {
+    let mut h = RandomState::new().build_hasher();
+    h.write_u64(now_unix_ms());
+    h.write_u32(std::process::id());
+    h.write_u64(TOKEN_SEQ.fetch_add(1, AtomicOrdering::Relaxed));
+    format!("{:016x}", h.finish())
+}
+
+// 执行 ops 之前调用：同一网卡已有待确认的变更时拒绝，否则拍快照并落盘
+pub(super) fn begin(
+    target: &ResolvedTarget,
+    ops: &[NetIfOpSpec],
+    timeout_secs: u32,
+) -> Result<NetIfPendingConfirm, ForgeFfiError> {
+    if let Some(p) = lock().iter().find(|p| p.target == target.name) {
+        return Err(ForgeFfiError::busy(format!(
+            "网卡 {} 有尚未确认的变更（token={}），确认或回滚后再提交",
+            target.name, p.token
+        )));
+    }
+    let sel = IfaceSelector {
+        if_index: None,
+        name: Some(target.name.clone()),
+    };
+    let snapshot = snapshot(&NetIfSnapshotRequest::v1(vec![sel]))?;
+    // 快照不含网关：原本是静态地址且有网关时，回滚先用 SetIpv4Static 把地址和网关一起改回去
+    let touches_gateway = ops.iter().any(|s| matches!(s.op, NetIfOp::SetIpv4Static { .. }));
+    let rollback_ops = fresh_state(target)
+        .filter(|_| touches_gateway)
+        .filter(|it| it.ipv4.iter().all(|a| a.origin == Some(IpOrigin::Static)))
+        .and_then(|it| {
+            let first = it.ipv4.first()?;
+            Some(NetIfOp::SetIpv4Static {
+                ip: first.ip.clone(),
+                prefix_len: first.prefix_len,
+                gateway: Some(it.ipv4_gateway?),
+            })
+        })
+        .into_iter()
+        .collect();
+    let record = NetIfPendingConfirm {
+        token: new_token(),
+        target: target.name.clone(),
+        deadline_ms: now_unix_ms() + u64::from(timeout_secs) * 1000,
+        rollback_ops,
+        snapshot,
+    };
+    write_record(&record)?;
+    Ok(record)
+}
+
+// 计时线程没能启动的记录。变更已生效，不能在无人看守的情况下保留，须交给 revert_unarmed 立即回滚
+pub(super) struct Unarmed {
+    record: NetIfPendingConfirm,
+    error: std::io::Error,
+}
+
+// ops 执行完后开始计时，返回截止时间（Unix 毫秒）
+pub(super) fn arm(mut record: NetIfPendingConfirm, timeout_secs: u32) -> Result<u64, Box<Unarmed>> {
+    record.deadline_ms = now_unix_ms() + u64::from(timeout_secs) * 1000;
+    let deadline_ms = record.deadline_ms;
+    if let Err(e) = write_record(&record) {
+        log(LogLevel::Warn, format_args!("更新 commit-confirm 记录失败: {}", e.message));
+    }
+    spawn(&record).map_err(|error| Box::new(Unarmed { record, error }))?;
+    Ok(deadline_ms)
+}
+
+// 回滚会重新获取串行锁与 apply 并发名额，必须在 apply 释放两者之后调用
+pub(super) fn revert_unarmed(unarmed: Unarmed) -> ForgeFfiError {
+    revert(&unarmed.record);
+    ForgeFfiError::system_error(format!(
+        "启动 commit-confirm 计时线程失败，已立即回滚: {}",
+        unarmed.error
+    ))
+}
+
+// 没有任何 op 生效时不需要确认
+pub(super) fn discard(record: &NetIfPendingConfirm) {
+    remove_record(&record.token);
+}
+
+// 启动失败时不回滚，由调用方在合适的时机调用 revert_unarmed
+fn spawn(record: &NetIfPendingConfirm) -> std::io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let wait = Duration::from_millis(record.deadline_ms.saturating_sub(now_unix_ms()));
+    let (token, target) = (record.token.clone(), record.target.clone());
+    // 先登记再启动线程，保证线程里的 take 一定能看到自己
+    let mut g = lock();
+    let thread_record = record.clone();
+    let spawned = std::thread::Builder::new()
+        .name("forgeffi-netif-confirm".to_string())
+        .spawn(move || {
+            match rx.recv_timeout(wait) {
+                Ok(Signal::Confirm) | Err(RecvTimeoutError::Disconnected) => return,
+                Ok(Signal::RevertNow) | Err(RecvTimeoutError::Timeout) => {}
+            }
+            // 超时与 confirm 同时发生时以先摘除记录的一方为准
+            if take(&thread_record.token).is_some() {
+                revert(&thread_record);
+            }
+        });
+    g.push(Pending {
+        token,
+        target,
+        tx,
+        handle: Some(spawned?),
+    });
+    drop(g);
+    crate::runtime::register_shutdown_hook("netif_confirm", revert_all_now);
+    Ok(())
+}
+
+fn revert(record: &NetIfPendingConfirm) {
+    log(
+        LogLevel::Warn,
+        format_args!("commit-confirm 未在期限内确认，回滚网卡 {}（token={}）", record.target, record.token),
+    );
+    let mut ok = true;
+    if !record.rollback_ops.is_empty() {
+        let sel = IfaceSelector {
+            if_index: None,
+            name: Some(record.target.clone()),
+        };
+        match apply_request(NetIfApplyRequest::v1(sel, record.rollback_ops.clone())) {
+            Ok(r) => ok &= r.ok,
+            Err(e) => {
+                ok = false;
+                log(LogLevel::Error, format_args!("回滚 {} 的静态地址/网关失败: {}", record.target, e.message));
+            }
+        }
+    }
+    match restore(NetIfRestoreRequest {
+        abi: NETIF_ABI_VERSION,
+        snapshot: record.snapshot.clone(),
+        dry_run: false,
+    }) {
+        Ok(r) => ok &= r.ok,
+        Err(e) => {
+            ok = false;
+            log(LogLevel::Error, format_args!("按快照回滚 {} 失败: {}", record.target, e.message));
+        }
+    }
+    if ok {
+        remove_record(&record.token);
+    } else {
+        log(
+            LogLevel::Error,
+            format_args!(
+                "回滚 {} 未完全成功，记录保留在 {}，下次 init 时重试",
+                record.target,
+                record_path(&record.token).display()
+            ),
+        );
+    }
+}
+
+pub fn confirm(token: &str) -> Result<(), ForgeFfiError> {
+    let p = take(token).ok_or_else(|| {
+        ForgeFfiError::not_found(format!("没有待确认的变更 token={token}（可能已超时回滚）"))
+    })?;
+    let _ = p.tx.send(Signal::Confirm);
+    remove_record(token);
+    Ok(())
+}
+
+// shutdown 时不再等待期限，未确认的变更立即回滚
+fn revert_all_now() {
+    let handles: Vec<JoinHandle<()>> = lock()
+        .iter_mut()
+        .filter_map(|p| {
+            let _ = p.tx.send(Signal::RevertNow);
+            p.handle.take()
+        })
+        .collect();
+    for h in handles {
+        let _ = h.join();
+    }
+}
+
+// init 时接管上次进程遗留的记录；已在本进程计时的跳过
+pub fn recover() {
+    let dir = record_dir();
+    if !dir.exists() {
+        return;
+    }
+    if let Err(e) = check_private_dir(&dir) {
+        log(LogLevel::Error, format_args!("不接管 commit-confirm 记录: {}", e.message));
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().is_none_or(|x| x != "json") {
+            continue;
+        }
+        let record = check_record_file(&path)
+            .and_then(|()| std::fs::read(&path).map_err(map_io_error))
+            .and_then(|b| RECORD_SCHEMA.decode_json::<NetIfPendingConfirm>(&b));
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                log(LogLevel::Warn, format_args!("忽略无法读取的 commit-confirm 记录 {}: {}", path.display(), e.message));
+                continue;
+            }
+        };
+        if lock().iter().any(|p| p.token == record.token) {
+            continue;
+        }
+        log(
+            LogLevel::Warn,
+            format_args!("发现未确认的 commit-confirm 记录（网卡 {}，token={}）", record.target, record.token),
+        );
+        if let Err(error) = spawn(&record) {
+            let e = revert_unarmed(Unarmed { record, error });
+            log(LogLevel::Error, format_args!("{}", e.message));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::*;
+
+use forgeffi_base::{AdminState, IfaceFlags, IfaceKind, NetIfCapabilities, OperState};
+use nix::ifaddrs::getifaddrs;
+use nix::net::if_::{if_nametoindex, InterfaceFlags};
+use nix::sys::socket::SockaddrStorage;
+use std::collections::BTreeMap;
+
+// 只走 libc，不解析任何命令输出：ip/ifconfig 缺失或输出格式异常（busybox、非英文 locale）时，
+// 至少还能拿到名字、索引、标志、MAC 与地址
+pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let addrs = getifaddrs()
+        .map_err(|e| ForgeFfiError::system_error(format!("getifaddrs 失败: {e}")))?;
+    let mut by_name: BTreeMap<String, NetInterface> = BTreeMap::new();
+    for ifa in addrs {
+        let it = by_name
+            .entry(ifa.interface_name.clone())
+            .or_insert_with(|| blank(&ifa.interface_name, ifa.flags));
+        let Some(addr) = ifa.address else {
+            continue;
+        };
+        let prefix = ifa.netmask.as_ref().and_then(prefix_len_of);
+        if let Some(v4) = addr.as_sockaddr_in() {
+            it.ipv4.push(addr_entry(v4.ip().into(), prefix.unwrap_or(32)));
+        } else if let Some(v6) = addr.as_sockaddr_in6() {
+            it.ipv6.push(addr_entry(v6.ip().into(), prefix.unwrap_or(128)));
+        } else if let Some(link) = addr.as_link_addr() {
+            #[cfg(target_os = "macos")]
+            {
+                let (kind, is_physical) = platform::link_kind(&it.name, link.datalink_type());
+                if kind != IfaceKind::Unknown {
+                    it.kind = kind;
+                }
+                it.is_physical = is_physical;
+            }
+            // 非以太网链路（隧道等）的硬件地址不是 6 字节
+            #[cfg(target_os = "linux")]
+            if link.halen() != 6 {
+                continue;
+            }
+            if let Some(mac) = link.addr().filter(|m| *m != [0; 6]) {
+                it.mac = Some(
+                    mac.iter()
+                        .map(|b| format!("{b:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(":"),
+                );
+            }
+        }
+    }
+    Ok(by_name.into_values().collect())
+}
+
+// 以主后端结果为准，只补齐它缺失的字段，并追加主后端漏掉的网卡
+pub(super) fn merge(mut primary: Vec<NetInterface>, fallback: Vec<NetInterface>) -> Vec<NetInterface> {
+    for fb in fallback {
+        let Some(p) = primary.iter_mut().find(|p| p.name == fb.name) else {
+            primary.push(fb);
+            continue;
+        };
+        if p.if_index == 0 {
+            p.if_index = fb.if_index;
+        }
+        if p.kind == IfaceKind::Unknown {
+            p.kind = fb.kind;
+        }
+        if p.is_physical.is_none() {
+            p.is_physical = fb.is_physical;
+        }
+        if p.mac.is_none() {
+            p.mac = fb.mac;
+        }
+        if p.flags.0 == 0 {
+            p.flags = fb.flags;
+        }
+        if p.admin_state == AdminState::Unknown {
+            p.admin_state = fb.admin_state;
+        }
+        if p.ipv4.is_empty() {
+            p.ipv4 = fb.ipv4;
+        }
+        if p.ipv6.is_empty() {
+            p.ipv6 = fb.ipv6;
+        }
+    }
+    primary
+}
+
+fn blank(name: &str, iff: InterfaceFlags) -> NetInterface {
+    let mut flags = 0u32;
+    for (f, bit) in [
+        (InterfaceFlags::IFF_UP, IfaceFlags::UP),
+        (InterfaceFlags::IFF_RUNNING, IfaceFlags::RUNNING),
+        (InterfaceFlags::IFF_LOOPBACK, IfaceFlags::LOOPBACK),
+        (InterfaceFlags::IFF_BROADCAST, IfaceFlags::BROADCAST),
+        (InterfaceFlags::IFF_MULTICAST, IfaceFlags::MULTICAST),
+        (InterfaceFlags::IFF_POINTOPOINT, IfaceFlags::POINT_TO_POINT),
+    ] {
+        if iff.contains(f) {
+            flags |= bit;
+        }
+    }
+    let up = iff.contains(InterfaceFlags::IFF_UP);
+    let running = iff.contains(InterfaceFlags::IFF_RUNNING);
+    NetInterface {
+        if_index: if_nametoindex(name).unwrap_or(0),
+        name: name.to_string(),
+        display_name: None,
+        kind: if iff.contains(InterfaceFlags::IFF_LOOPBACK) {
+            IfaceKind::Loopback
+        } else if iff.contains(InterfaceFlags::IFF_POINTOPOINT) {
+            IfaceKind::Tunnel
+        } else {
+            IfaceKind::Unknown
+        },
+        is_physical: None,
+        admin_state: if up { AdminState::Up } else { AdminState::Down },
+        oper_state: Some(if up && running { OperState::Up } else { OperState::Down }),
+        flags: IfaceFlags(flags),
+        mac: None,
+        mtu: sysfs_mtu(name),
+        speed_bps: None,
+        vlan_id: None,
+        ipv4: Vec::new(),
+        ipv6: Vec::new(),
+        default_routes: Vec::new(),
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: Vec::new(),
+        tunnel: None,
+        capabilities: NetIfCapabilities {
+            can_set_admin_state: false,
+            can_set_mtu: false,
+            can_add_del_ip: false,
+            can_set_dhcp: false,
+            can_set_dns: false,
+            can_set_ipv6: false,
+            can_rename: false,
+            can_set_description: false,
+            can_set_mac: false,
+            can_manage_routes: false,
+            supported_speeds: Vec::new(),
+            notes: Some("主后端不可用，仅含 getifaddrs 提供的基础信息".to_string()),
+        },
+    }
+}
+
+// sysfs 不依赖任何命令，Linux 上顺带补上 MTU
+#[cfg(target_os = "linux")]
+fn sysfs_mtu(name: &str) -> Option<u32> {
+    std::fs::read_to_string(format!("/sys/class/net/{name}/mtu"))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn sysfs_mtu(_name: &str) -> Option<u32> {
+    None
+}
+
+fn addr_entry(ip: IpAddr, prefix_len: u8) -> IpAddrEntry {
+    IpAddrEntry {
+        ip: ip.to_string(),
+        prefix_len,
+        scope: None,
+        origin: None,
+        flags: None,
+        dad_state: None,
+        valid_lft_secs: None,
+        preferred_lft_secs: None,
+    }
+}
+
+fn prefix_len_of(mask: &SockaddrStorage) -> Option<u8> {
+    if let Some(v4) = mask.as_sockaddr_in() {
+        return u8::try_from(u32::from(v4.ip()).count_ones()).ok();
+    }
+    mask.as_sockaddr_in6()
+        .and_then(|v6| u8::try_from(u128::from(v6.ip()).count_ones()).ok())
+}
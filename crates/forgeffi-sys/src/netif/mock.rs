@@ -0,0 +1,668 @@
+// mock 后端：网卡表保存在内存中，apply 只修改这张表并记录每个 op，
+// 不执行任何系统命令，便于在无权限的 CI 上跑通完整的 JSON/FFI 链路
+use forgeffi_base::{
+    AdminState, DefaultRoute, ForgeFfiError, IfaceFlags, IfaceKind, IpAddrEntry, IpDadState, IpFamily,
+    IpOrigin, IpScope, NetIfBackend, NetIfCapabilities, NetIfMockConfig, NetIfMockLog, NetIfMockRecord,
+    NetIfOp, NetIfQos, NetIfStepResult, NetInterface, OperState, QosEntry, RouteOrigin, RoutingRule, StaticRoute,
+    TunnelInfo, TunnelKind, WireguardInfo, WireguardPeer, WireguardPeerInfo,
+};
+use forgeffi_protocol::{check_abi, decode_request, encode_json, op_uses_target, parse_mac, NETIF_OPS};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::sync::{Mutex, MutexGuard};
+
+use super::{backend, ResolvedTarget, NETIF_ABI_VERSION};
+
+struct MockState {
+    interfaces: Vec<NetInterface>,
+    fail_ops: Vec<String>,
+    applied: Vec<NetIfMockRecord>,
+    batches: u64,
+    // (出口网卡名, 路由)；只用于判断重复添加与删除不存在的条目，不反映到 list
+    routes: Vec<(String, StaticRoute)>,
+    rules: Vec<RoutingRule>,
+    // if_index -> (egress_mbps, ingress_mbps)，qos() 按真实系统的形状合成 qdisc 条目
+    limits: BTreeMap<u32, (Option<u32>, Option<u32>)>,
+}
+
+static STATE: Mutex<Option<MockState>> = Mutex::new(None);
+
+// 显式固定了其他后端时不启用；未固定时由 mock feature 决定是否默认启用
+pub(super) fn active() -> bool {
+    match backend::pinned() {
+        Some(b) => b == NetIfBackend::Mock,
+        None => cfg!(feature = "mock"),
+    }
+}
+
+fn lock() -> MutexGuard<'static, Option<MockState>> {
+    let mut g = match STATE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    g.get_or_insert_with(|| MockState {
+        interfaces: default_interfaces(),
+        fail_ops: Vec::new(),
+        applied: Vec::new(),
+        batches: 0,
+        routes: Vec::new(),
+        rules: Vec::new(),
+        limits: BTreeMap::new(),
+    });
+    g
+}
+
+fn with_state<T>(f: impl FnOnce(&mut MockState) -> T) -> T {
+    let mut g = lock();
+    f(g.as_mut().expect("mock 状态已初始化"))
+}
+
+// 替换网卡表并清空记录；None 恢复内置网卡表
+pub fn configure(cfg: Option<NetIfMockConfig>) -> Result<(), ForgeFfiError> {
+    let (interfaces, fail_ops) = match cfg {
+        Some(cfg) => {
+            check_abi(cfg.abi)?;
+            let interfaces = if cfg.interfaces.is_empty() {
+                default_interfaces()
+            } else {
+                cfg.interfaces
+            };
+            (interfaces, cfg.fail_ops)
+        }
+        None => (default_interfaces(), Vec::new()),
+    };
+    with_state(|s| {
+        s.interfaces = interfaces;
+        s.fail_ops = fail_ops;
+        s.applied.clear();
+        s.routes.clear();
+        s.rules.clear();
+        s.limits.clear();
+    });
+    super::cache::invalidate();
+    Ok(())
+}
+
+pub fn configure_json(req: Option<&str>) -> Result<(), ForgeFfiError> {
+    let cfg = req.map(decode_request::<NetIfMockConfig>).transpose()?;
+    configure(cfg)
+}
+
+pub fn log() -> NetIfMockLog {
+    NetIfMockLog {
+        abi: NETIF_ABI_VERSION,
+        applied: with_state(|s| s.applied.clone()),
+    }
+}
+
+pub fn log_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("mock 记录", &log())
+}
+
+pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    Ok(with_state(|s| s.interfaces.clone()))
+}
+
+// 与 Linux 实现的形状一致：出方向为根 HTB 1:，入方向为 ingress ffff:
+pub(super) fn qos(ifaces: &[NetInterface]) -> Vec<NetIfQos> {
+    with_state(|s| {
+        ifaces
+            .iter()
+            .map(|it| {
+                let (egress_mbps, ingress_mbps) = s.limits.get(&it.if_index).copied().unwrap_or_default();
+                let entry = |kind: &str, handle: &str, parent: Option<&str>, mbps: u32| QosEntry {
+                    kind: kind.to_string(),
+                    handle: Some(handle.to_string()),
+                    parent: parent.map(str::to_string),
+                    match_src: None,
+                    rate_bps: Some(u64::from(mbps) * 1_000_000),
+                };
+                let entries = egress_mbps
+                    .map(|m| entry("htb", "1:", None, m))
+                    .into_iter()
+                    .chain(ingress_mbps.map(|m| entry("ingress", "ffff:", Some("ffff:fff1"), m)))
+                    .collect();
+                NetIfQos {
+                    if_index: it.if_index,
+                    name: it.name.clone(),
+                    egress_mbps,
+                    ingress_mbps,
+                    entries,
+                }
+            })
+            .collect()
+    })
+}
+
+pub(super) fn supported_ops() -> Vec<&'static str> {
+    NETIF_OPS.to_vec()
+}
+
+pub(super) fn apply_standalone(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    with_state(|s| {
+        let r = check_injected(s, op).and_then(|_| apply_standalone_locked(s, op));
+        s.applied.push(NetIfMockRecord {
+            target: None,
+            op: op.clone(),
+            ok: r.is_ok(),
+            batch: None,
+        });
+        r
+    })
+}
+
+pub(super) fn apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    _steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    with_state(|s| {
+        let r = check_injected(s, op).and_then(|_| apply_one_locked(s, target, op));
+        s.applied.push(NetIfMockRecord {
+            target: Some(target.name.clone()),
+            op: op.clone(),
+            ok: r.is_ok(),
+            batch: None,
+        });
+        r
+    })
+}
+
+// 与 PowerShell 后端可合并的 op 范围一致：只改网卡属性、不增删网卡的 op
+pub(super) fn batch_len(ops: &[&NetIfOp]) -> usize {
+    ops.iter()
+        .take_while(|op| {
+            matches!(
+                op,
+                NetIfOp::SetAdminState { .. }
+                    | NetIfOp::SetMtu { .. }
+                    | NetIfOp::AddIp { .. }
+                    | NetIfOp::DelIp { .. }
+                    | NetIfOp::SetIpv4Dhcp { .. }
+                    | NetIfOp::SetIpv6Mode { .. }
+                    | NetIfOp::SetDnsSuffix { .. }
+                    | NetIfOp::SetDnsRegistration { .. }
+            )
+        })
+        .count()
+}
+
+pub(super) fn apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    with_state(|s| {
+        s.batches += 1;
+        let batch = s.batches;
+        ops.iter()
+            .map(|op| {
+                let r = check_injected(s, op).and_then(|_| apply_one_locked(s, target, op));
+                s.applied.push(NetIfMockRecord {
+                    target: Some(target.name.clone()),
+                    op: (*op).clone(),
+                    ok: r.is_ok(),
+                    batch: Some(batch),
+                });
+                r
+            })
+            .collect()
+    })
+}
+
+fn check_injected(s: &MockState, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    let tag = serde_json::to_value(op)
+        .ok()
+        .and_then(|v| v.get("op").and_then(|t| t.as_str()).map(str::to_string))
+        .unwrap_or_default();
+    if s.fail_ops.contains(&tag) {
+        return Err(ForgeFfiError::system_error(format!("mock: 注入失败 op={tag}")));
+    }
+    Ok(())
+}
+
+fn apply_standalone_locked(s: &mut MockState, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    match op {
+        NetIfOp::CreateVlan {
+            parent,
+            vlan_id,
+            name,
+        } => {
+            if !s.interfaces.iter().any(|it| it.name == *parent) {
+                return Err(ForgeFfiError::not_found(format!("未找到网卡 name={parent}")));
+            }
+            let name = name.clone().unwrap_or_else(|| format!("{parent}.{vlan_id}"));
+            let mut it = new_interface(s, &name, IfaceKind::Vlan)?;
+            it.vlan_id = Some(*vlan_id);
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::CreateBridge { name } => {
+            let it = new_interface(s, name, IfaceKind::Bridge)?;
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::CreateDummy { name } => {
+            let it = new_interface(s, name, IfaceKind::Virtual)?;
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::CreateBond { name, members, .. } => {
+            if let Some(m) = members.iter().find(|m| !s.interfaces.iter().any(|it| it.name == **m)) {
+                return Err(ForgeFfiError::not_found(format!("未找到网卡 name={m}")));
+            }
+            let it = new_interface(s, name, IfaceKind::Virtual)?;
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::CreateWireguard {
+            name,
+            listen_port,
+            peers,
+            ..
+        } => {
+            let mut it = new_interface(s, name, IfaceKind::Tunnel)?;
+            it.mac = None;
+            it.mtu = Some(1420);
+            it.tunnel = Some(TunnelInfo {
+                kind: TunnelKind::Wireguard,
+                local: None,
+                remote: None,
+                ttl: None,
+                // mock 不做 Curve25519 运算，public_key 留空
+                wireguard: Some(WireguardInfo {
+                    public_key: None,
+                    listen_port: *listen_port,
+                    fwmark: None,
+                    peers: peers.iter().map(peer_info).collect(),
+                }),
+            });
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::CreateIpTunnel {
+            name,
+            kind,
+            remote,
+            local,
+            ttl,
+        } => {
+            let mut it = new_interface(s, name, IfaceKind::Tunnel)?;
+            it.mac = None;
+            it.tunnel = Some(TunnelInfo {
+                kind: *kind,
+                local: local.clone(),
+                remote: Some(remote.clone()),
+                ttl: *ttl,
+                wireguard: None,
+            });
+            s.interfaces.push(it);
+            Ok(())
+        }
+        NetIfOp::AddBondMember { bond: bridge, member } | NetIfOp::AddBridgeMember { bridge, member } => {
+            for name in [bridge, member] {
+                if !s.interfaces.iter().any(|it| it.name == *name) {
+                    return Err(ForgeFfiError::not_found(format!("未找到网卡 name={name}")));
+                }
+            }
+            Ok(())
+        }
+        NetIfOp::AddRoutingRule { rule } => {
+            if s.rules.contains(rule) {
+                return Err(ForgeFfiError::system_error("规则已存在".to_string()));
+            }
+            s.rules.push(rule.clone());
+            Ok(())
+        }
+        NetIfOp::DelRoutingRule { rule } => {
+            let Some(i) = s.rules.iter().position(|r| r == rule) else {
+                return Err(ForgeFfiError::system_error("规则不存在".to_string()));
+            };
+            s.rules.remove(i);
+            Ok(())
+        }
+        NetIfOp::SwitchNetworkLocation { .. } | NetIfOp::SetServiceOrder { .. } => Ok(()),
+        _ => Err(ForgeFfiError::invalid_argument("该操作需要 target".to_string())),
+    }
+}
+
+fn apply_one_locked(s: &mut MockState, target: &ResolvedTarget, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    let Some(pos) = s.interfaces.iter().position(|it| target.matches(it)) else {
+        return Err(ForgeFfiError::not_found(format!("未找到网卡 name={}", target.name)));
+    };
+    if matches!(
+        op,
+        NetIfOp::DeleteVlan
+            | NetIfOp::DeleteBridge
+            | NetIfOp::DeleteBond
+            | NetIfOp::DeleteInterface
+            | NetIfOp::DeleteTunnel
+    ) {
+        let want = match op {
+            NetIfOp::DeleteVlan => Some(IfaceKind::Vlan),
+            NetIfOp::DeleteBridge => Some(IfaceKind::Bridge),
+            _ => None,
+        };
+        let it = &s.interfaces[pos];
+        // 与真实系统一致：物理网卡与回环不能删除，类型不符时拒绝
+        if matches!(it.kind, IfaceKind::Physical | IfaceKind::Loopback)
+            || want.is_some_and(|k| k != it.kind)
+            || (*op == NetIfOp::DeleteTunnel && it.tunnel.is_none())
+        {
+            return Err(ForgeFfiError::unsupported(format!("不能删除该类型的网卡: {}", it.name)));
+        }
+        s.interfaces.remove(pos);
+        return Ok(());
+    }
+    if let NetIfOp::SetName { new_name } = op
+        && s.interfaces.iter().any(|it| it.name == *new_name)
+    {
+        return Err(ForgeFfiError::system_error(format!("网卡名已存在: {new_name}")));
+    }
+    if !op_uses_target(op) {
+        return apply_standalone_locked(s, op);
+    }
+    match op {
+        NetIfOp::AddRoute { route } => {
+            let dev = &s.interfaces[pos].name;
+            // 与内核一致：同一张表里 dest 与 metric 相同即视为重复
+            if s
+                .routes
+                .iter()
+                .any(|(d, r)| d == dev && r.dest == route.dest && r.table == route.table && r.metric == route.metric)
+            {
+                return Err(ForgeFfiError::system_error(format!("路由已存在: {}", route.dest)));
+            }
+            s.routes.push((dev.clone(), route.clone()));
+            return Ok(());
+        }
+        NetIfOp::DelRoute { route } => {
+            let dev = &s.interfaces[pos].name;
+            let Some(i) = s.routes.iter().position(|(d, r)| d == dev && route_matches(r, route)) else {
+                return Err(ForgeFfiError::system_error(format!("路由不存在: {}", route.dest)));
+            };
+            s.routes.remove(i);
+            return Ok(());
+        }
+        NetIfOp::SetBandwidthLimit {
+            egress_mbps,
+            ingress_mbps,
+        } => {
+            let if_index = s.interfaces[pos].if_index;
+            if egress_mbps.is_none() && ingress_mbps.is_none() {
+                s.limits.remove(&if_index);
+            } else {
+                s.limits.insert(if_index, (*egress_mbps, *ingress_mbps));
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let it = &mut s.interfaces[pos];
+    match op {
+        NetIfOp::SetAdminState { up } => {
+            it.admin_state = if *up { AdminState::Up } else { AdminState::Down };
+            it.oper_state = Some(if *up { OperState::Up } else { OperState::Down });
+            if *up {
+                it.flags.0 |= IfaceFlags::UP | IfaceFlags::RUNNING;
+            } else {
+                it.flags.0 &= !(IfaceFlags::UP | IfaceFlags::RUNNING);
+            }
+        }
+        NetIfOp::SetMtu { mtu } => it.mtu = Some(*mtu),
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            let addr = parse_ip(ip)?;
+            if find_addr(it, addr, *prefix_len).is_some() {
+                return Err(ForgeFfiError::system_error(format!("地址已存在: {ip}/{prefix_len}")));
+            }
+            let mut entry = addr_entry(addr, *prefix_len, IpOrigin::Static);
+            entry.valid_lft_secs = *valid_lft_secs;
+            entry.preferred_lft_secs = preferred_lft_secs.or(*valid_lft_secs);
+            family_mut(it, addr).push(entry);
+        }
+        NetIfOp::DelIp { ip, prefix_len } => {
+            let addr = parse_ip(ip)?;
+            let Some(i) = find_addr(it, addr, *prefix_len) else {
+                return Err(ForgeFfiError::system_error(format!("地址不存在: {ip}/{prefix_len}")));
+            };
+            family_mut(it, addr).remove(i);
+        }
+        NetIfOp::SetIpv4Dhcp { enable } => {
+            let origin = if *enable { IpOrigin::Dhcp } else { IpOrigin::Static };
+            for a in &mut it.ipv4 {
+                a.origin = Some(origin);
+            }
+        }
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => set_static(it, IpFamily::Ipv4, ip, *prefix_len, gateway.as_deref())?,
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => set_static(it, IpFamily::Ipv6, ip, *prefix_len, gateway.as_deref())?,
+        NetIfOp::SetName { new_name } => it.name = new_name.clone(),
+        NetIfOp::SetDescription { text } => {
+            it.display_name = Some(text.clone()).filter(|t| !t.is_empty());
+        }
+        NetIfOp::SetMac { mac } => {
+            let m = parse_mac(mac)?;
+            it.mac = Some(format!(
+                "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+                m[0], m[1], m[2], m[3], m[4], m[5]
+            ));
+        }
+        NetIfOp::SetLinkSpeed { speed_mbps, .. } => {
+            it.speed_bps = speed_mbps.map(|m| u64::from(m) * 1_000_000).or(it.speed_bps);
+        }
+        NetIfOp::SetWireguardPeer { peer } => {
+            let wg = wireguard_mut(it)?;
+            wg.peers.retain(|p| p.public_key != peer.public_key);
+            wg.peers.push(peer_info(peer));
+        }
+        NetIfOp::RemoveWireguardPeer { public_key } => {
+            wireguard_mut(it)?.peers.retain(|p| p.public_key != *public_key);
+        }
+        // 其余 op 只记录，不改变网卡表
+        _ => {}
+    }
+    Ok(())
+}
+
+// 删除时省略的 gateway/metric/src 不参与匹配
+fn route_matches(have: &StaticRoute, want: &StaticRoute) -> bool {
+    have.dest == want.dest
+        && have.table == want.table
+        && (want.gateway.is_none() || have.gateway == want.gateway)
+        && (want.metric.is_none() || have.metric == want.metric)
+        && (want.src.is_none() || have.src == want.src)
+}
+
+fn peer_info(p: &WireguardPeer) -> WireguardPeerInfo {
+    WireguardPeerInfo {
+        public_key: p.public_key.clone(),
+        endpoint: p.endpoint.clone(),
+        allowed_ips: p.allowed_ips.clone(),
+        latest_handshake_unix: None,
+        rx_bytes: 0,
+        tx_bytes: 0,
+        persistent_keepalive_secs: p.persistent_keepalive_secs,
+    }
+}
+
+fn wireguard_mut(it: &mut NetInterface) -> Result<&mut WireguardInfo, ForgeFfiError> {
+    let name = it.name.clone();
+    it.tunnel
+        .as_mut()
+        .and_then(|t| t.wireguard.as_mut())
+        .ok_or_else(|| ForgeFfiError::system_error(format!("不是 WireGuard 网卡: {name}")))
+}
+
+fn new_interface(s: &MockState, name: &str, kind: IfaceKind) -> Result<NetInterface, ForgeFfiError> {
+    if s.interfaces.iter().any(|it| it.name == name) {
+        return Err(ForgeFfiError::system_error(format!("网卡名已存在: {name}")));
+    }
+    let if_index = s.interfaces.iter().map(|it| it.if_index).max().unwrap_or(0) + 1;
+    Ok(NetInterface {
+        if_index,
+        name: name.to_string(),
+        display_name: None,
+        kind,
+        is_physical: Some(false),
+        admin_state: AdminState::Down,
+        oper_state: Some(OperState::Down),
+        flags: IfaceFlags(IfaceFlags::BROADCAST | IfaceFlags::MULTICAST),
+        mac: Some(format!("02:00:00:00:00:{:02x}", if_index & 0xff)),
+        mtu: Some(1500),
+        speed_bps: None,
+        vlan_id: None,
+        ipv4: Vec::new(),
+        ipv6: Vec::new(),
+        default_routes: Vec::new(),
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: Vec::new(),
+        tunnel: None,
+        capabilities: full_capabilities(),
+    })
+}
+
+fn set_static(
+    it: &mut NetInterface,
+    family: IpFamily,
+    ip: &str,
+    prefix_len: u8,
+    gateway: Option<&str>,
+) -> Result<(), ForgeFfiError> {
+    let addr = parse_ip(ip)?;
+    let list = family_mut(it, addr);
+    // IPv6 保留链路本地地址
+    list.retain(|a| a.scope == Some(IpScope::Link));
+    list.push(addr_entry(addr, prefix_len, IpOrigin::Static));
+    it.default_routes.retain(|r| r.family != family);
+    if let Some(gw) = gateway.filter(|g| !g.is_empty()) {
+        it.default_routes.push(DefaultRoute {
+            family,
+            gateway: Some(gw.to_string()),
+            metric: Some(100),
+            origin: Some(RouteOrigin::Static),
+            preferred_src: None,
+        });
+    }
+    Ok(())
+}
+
+fn parse_ip(ip: &str) -> Result<IpAddr, ForgeFfiError> {
+    ip.parse()
+        .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))
+}
+
+fn family_mut(it: &mut NetInterface, addr: IpAddr) -> &mut Vec<IpAddrEntry> {
+    if addr.is_ipv4() { &mut it.ipv4 } else { &mut it.ipv6 }
+}
+
+fn find_addr(it: &NetInterface, addr: IpAddr, prefix_len: u8) -> Option<usize> {
+    let list = if addr.is_ipv4() { &it.ipv4 } else { &it.ipv6 };
+    list.iter()
+        .position(|a| a.prefix_len == prefix_len && a.ip.parse::<IpAddr>().ok() == Some(addr))
+}
+
+fn addr_entry(addr: IpAddr, prefix_len: u8, origin: IpOrigin) -> IpAddrEntry {
+    let scope = match addr {
+        IpAddr::V4(v) if v.is_loopback() => IpScope::Host,
+        IpAddr::V4(v) if v.is_link_local() => IpScope::Link,
+        IpAddr::V6(v) if v.is_loopback() => IpScope::Host,
+        IpAddr::V6(v) if v.is_unicast_link_local() => IpScope::Link,
+        _ => IpScope::Global,
+    };
+    IpAddrEntry {
+        ip: addr.to_string(),
+        prefix_len,
+        scope: Some(scope),
+        origin: Some(origin),
+        flags: None,
+        dad_state: Some(IpDadState::Preferred),
+        valid_lft_secs: None,
+        preferred_lft_secs: None,
+    }
+}
+
+fn full_capabilities() -> NetIfCapabilities {
+    NetIfCapabilities {
+        can_set_admin_state: true,
+        can_set_mtu: true,
+        can_add_del_ip: true,
+        can_set_dhcp: true,
+        can_set_dns: true,
+        can_set_ipv6: true,
+        can_rename: true,
+        can_set_description: true,
+        can_set_mac: true,
+        can_manage_routes: true,
+        supported_speeds: Vec::new(),
+        notes: Some("mock".to_string()),
+    }
+}
+
+// 地址取自文档保留网段（RFC 5737 / RFC 3849），不会与真实网络冲突
+fn default_interfaces() -> Vec<NetInterface> {
+    let lo = NetInterface {
+        if_index: 1,
+        name: "lo".to_string(),
+        display_name: None,
+        kind: IfaceKind::Loopback,
+        is_physical: Some(false),
+        admin_state: AdminState::Up,
+        oper_state: Some(OperState::Up),
+        flags: IfaceFlags(IfaceFlags::UP | IfaceFlags::RUNNING | IfaceFlags::LOOPBACK),
+        mac: None,
+        mtu: Some(65536),
+        speed_bps: None,
+        vlan_id: None,
+        ipv4: vec![addr_entry(IpAddr::from([127, 0, 0, 1]), 8, IpOrigin::Static)],
+        ipv6: vec![addr_entry(std::net::Ipv6Addr::LOCALHOST.into(), 128, IpOrigin::Static)],
+        default_routes: Vec::new(),
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: Vec::new(),
+        tunnel: None,
+        capabilities: full_capabilities(),
+    };
+    let eth0 = NetInterface {
+        if_index: 2,
+        name: "eth0".to_string(),
+        display_name: None,
+        kind: IfaceKind::Physical,
+        is_physical: Some(true),
+        admin_state: AdminState::Up,
+        oper_state: Some(OperState::Up),
+        flags: IfaceFlags(IfaceFlags::UP | IfaceFlags::RUNNING | IfaceFlags::BROADCAST | IfaceFlags::MULTICAST),
+        mac: Some("02:00:00:00:00:02".to_string()),
+        mtu: Some(1500),
+        speed_bps: Some(1_000_000_000),
+        vlan_id: None,
+        ipv4: vec![addr_entry(IpAddr::from([192, 0, 2, 10]), 24, IpOrigin::Dhcp)],
+        ipv6: vec![
+            addr_entry("fe80::2".parse().expect("合法地址"), 64, IpOrigin::Static),
+            addr_entry("2001:db8::10".parse().expect("合法地址"), 64, IpOrigin::Static),
+        ],
+        default_routes: vec![DefaultRoute {
+            family: IpFamily::Ipv4,
+            gateway: Some("192.0.2.1".to_string()),
+            metric: Some(100),
+            origin: Some(RouteOrigin::Dhcp),
+            preferred_src: Some("192.0.2.10".to_string()),
+        }],
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: vec!["192.0.2.53".to_string()],
+        tunnel: None,
+        capabilities: full_capabilities(),
+    };
+    vec![lo, eth0]
+}
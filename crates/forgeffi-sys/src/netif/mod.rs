@@ -1,16 +1,64 @@
 use forgeffi_base::{
-    ForgeFfiError, IfaceSelector, NetIfApplyRequest, NetIfApplyResponse, NetIfListResponse, NetIfOp,
-    NetIfOpResult, NetInterface, ABI_VERSION,
+    cancelled_if_requested, check_cancelled, current_cancel_token, is_cancelled, with_cancel_token,
+    ActiveDefaultRoute, AdminState, DefaultRouteResponse, ErrorCode, ForgeFfiError, IfaceSelector, IpAddrEntry,
+    IpDadState, IpFamily, NetIfApplyRequest, NetIfBackend,
+    NetIfApplyResponse, NetIfListFilter, NetIfListPage, NetIfListRequest, NetIfListResponse,
+    NetIfMultiApplyRequest, NetIfMultiApplyResponse, NetIfOp, NetIfOpResult, NetIfOpSpec, NetIfQosResponse, NetIfScope,
+    NetIfReconcileRequest, NetIfReconcileResponse, NetIfRestoreItem, NetIfRestoreRequest,
+    NetIfRestoreResponse, NetIfSnapshot, NetIfSnapshotEntry, NetIfSnapshotRequest, NetIfSortKey,
+    NetIfStepResult,
+    NetIfTargetResult, NetInterface, NetworkLocation,
+    ABI_VERSION, MIN_SUPPORTED_ABI,
 };
+use forgeffi_protocol::{
+    check_abi, decode_request, desired_state_of, encode_json, netif_op_tag, op_already_applied, op_uses_target, parse_list_cursor,
+    plan_reconcile, resolve_page_size, validate_commit_confirm, validate_list_request,
+    validate_multi_apply_request, validate_netif_op, validate_reconcile_request,
+    validate_restore_request,
+    NETIF_MULTI_DEFAULT_PARALLEL,
+};
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+use forgeffi_protocol::resolve_lifetimes;
+use forgeffi_protocol::LFT_FOREVER;
+use crate::util::{command_deadline, now_unix_ms, with_command_deadline};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+mod backend;
+mod cache;
+mod confirm;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod ifaddrs;
+pub mod mock;
+#[cfg(target_os = "linux")]
+mod netplan;
+pub mod parse;
 #[cfg(target_os = "linux")]
 mod platform_linux;
 #[cfg(target_os = "macos")]
 mod platform_macos;
 #[cfg(target_os = "windows")]
 mod platform_windows;
+#[cfg(target_os = "windows")]
+mod platform_windows_netsh;
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 mod platform_unsupported;
+#[cfg(target_os = "linux")]
+mod qos;
+mod ratelimit;
+#[cfg(target_os = "linux")]
+mod routing;
+#[cfg(target_os = "linux")]
+mod tunnel;
+#[cfg(feature = "watch")]
+mod watch;
+#[cfg(all(feature = "watch", target_os = "linux"))]
+mod watch_linux;
 
 #[cfg(target_os = "linux")]
 use platform_linux as platform;
@@ -21,69 +69,1144 @@ use platform_windows as platform;
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 use platform_unsupported as platform;
 
+pub use backend::{backends, pinned as pinned_backend, set_pinned as set_pinned_backend, BACKEND_ENV};
+pub use cache::{invalidate as cache_invalidate, set_ttl_ms as set_cache_ttl_ms, ttl_ms as cache_ttl_ms};
+pub use confirm::{confirm, recover as recover_pending_confirms};
+pub use ratelimit::{config as rate_limit, set_config as set_rate_limit};
+#[cfg(feature = "watch")]
+pub use watch::{watch, EventSink, NetIfWatcher};
+
 pub const NETIF_ABI_VERSION: u32 = ABI_VERSION;
 
+pub fn clear_caches() {
+    cache::invalidate();
+    ratelimit::reset();
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    platform::clear_caches();
+}
+
 pub fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    list_interfaces_sorted(NetIfSortKey::default())
+}
+
+pub fn list_interfaces_sorted(key: NetIfSortKey) -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let mut items = cache::get_or_fetch(platform_list)?;
+    sort_interfaces(&mut items, key);
+    fill_gateways(&mut items);
+    Ok(items)
+}
+
+fn fill_gateways(items: &mut [NetInterface]) {
+    for it in items.iter_mut() {
+        let gateway = |family| {
+            it.default_routes
+                .iter()
+                .find(|r| r.family == family)
+                .and_then(|r| r.gateway.clone())
+        };
+        it.ipv4_gateway = gateway(IpFamily::Ipv4);
+        it.ipv6_gateway = gateway(IpFamily::Ipv6);
+    }
+}
+
+// mock 后端启用时不读系统，也不走 getifaddrs 兜底
+fn platform_list() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    if mock::active() {
+        return mock::list_interfaces();
+    }
+    system_list()
+}
+
+// 主后端失败（命令缺失、输出无法解析）时退回 getifaddrs；成功时用它补齐缺失字段
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn system_list() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    match (platform::list_interfaces(), ifaddrs::list_interfaces()) {
+        (Ok(items), Ok(fallback)) => Ok(ifaddrs::merge(items, fallback)),
+        (Ok(items), Err(_)) => Ok(items),
+        // 取消导致的失败不走兜底，尽快返回
+        (Err(e), Ok(fallback)) if !fallback.is_empty() && !is_cancelled() => {
+            crate::runtime::log(
+                forgeffi_base::LogLevel::Warn,
+                format_args!("netif 主后端失败，改用 getifaddrs 结果: {}", e.message),
+            );
+            Ok(fallback)
+        }
+        (Err(e), _) => Err(e),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn system_list() -> Result<Vec<NetInterface>, ForgeFfiError> {
     platform::list_interfaces()
 }
 
+pub fn sort_interfaces(items: &mut [NetInterface], key: NetIfSortKey) {
+    for it in items.iter_mut() {
+        it.ipv4.sort_by(cmp_addr);
+        it.ipv6.sort_by(cmp_addr);
+        it.default_routes
+            .sort_by_key(|r| (r.family, r.metric.unwrap_or(u32::MAX)));
+    }
+    match key {
+        NetIfSortKey::IfIndex => {
+            items.sort_by(|a, b| a.if_index.cmp(&b.if_index).then_with(|| a.name.cmp(&b.name)));
+        }
+        NetIfSortKey::Name => {
+            items.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.if_index.cmp(&b.if_index)));
+        }
+    }
+}
+
+fn cmp_addr(a: &IpAddrEntry, b: &IpAddrEntry) -> Ordering {
+    let pa = a.ip.parse::<std::net::IpAddr>().ok();
+    let pb = b.ip.parse::<std::net::IpAddr>().ok();
+    let by_ip = match (pa, pb) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.ip.cmp(&b.ip),
+    };
+    by_ip.then_with(|| a.prefix_len.cmp(&b.prefix_len))
+}
+
 pub fn list_response() -> Result<NetIfListResponse, ForgeFfiError> {
+    list_response_sorted(NetIfSortKey::default())
+}
+
+pub fn list_response_sorted(key: NetIfSortKey) -> Result<NetIfListResponse, ForgeFfiError> {
     Ok(NetIfListResponse {
         abi: NETIF_ABI_VERSION,
-        items: list_interfaces()?,
+        items: list_interfaces_sorted(key)?,
+        network_location: network_location(),
     })
 }
 
+#[cfg(target_os = "macos")]
+fn network_location() -> Option<NetworkLocation> {
+    if mock::active() {
+        return None;
+    }
+    platform::network_location().ok()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn network_location() -> Option<NetworkLocation> {
+    None
+}
+
 pub fn list_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
-    let resp = list_response()?;
+    list_json_bytes_sorted(NetIfSortKey::default())
+}
+
+pub fn list_json_bytes_sorted(key: NetIfSortKey) -> Result<Vec<u8>, ForgeFfiError> {
+    let resp = list_response_sorted(key)?;
+    serde_json::to_vec(&resp)
+        .map_err(|e| ForgeFfiError::system_error(format!("序列化 list 响应失败: {e}")))
+}
+
+pub fn list_page(cursor: Option<&str>, page_size: u32) -> Result<NetIfListPage, ForgeFfiError> {
+    let after = parse_list_cursor(cursor)?;
+    let size = resolve_page_size(page_size)?;
+    let mut items = list_interfaces_sorted(NetIfSortKey::IfIndex)?;
+    items.retain(|it| after.is_none_or(|a| it.if_index > a));
+    let next_cursor = if items.len() > size {
+        items.truncate(size);
+        items.last().map(|it| it.if_index.to_string())
+    } else {
+        None
+    };
+    Ok(NetIfListPage {
+        abi: NETIF_ABI_VERSION,
+        items,
+        next_cursor,
+    })
+}
+
+pub fn list_page_json_bytes(cursor: Option<&str>, page_size: u32) -> Result<Vec<u8>, ForgeFfiError> {
+    let page = list_page(cursor, page_size)?;
+    encode_json("list 分页响应", &page)
+}
+
+pub fn list_filtered(req: &NetIfListRequest) -> Result<serde_json::Value, ForgeFfiError> {
+    validate_list_request(req)?;
+    let pattern = match &req.filter.names_glob {
+        Some(g) => Some(glob::Pattern::new(g).map_err(|e| {
+            ForgeFfiError::invalid_argument(format!("names_glob 非法: {g}: {e}"))
+        })?),
+        None => None,
+    };
+    let mut items = list_interfaces_sorted(req.sort)?;
+    items.retain(|it| filter_matches(&req.filter, pattern.as_ref(), it));
+
+    let mut out = Vec::with_capacity(items.len());
+    for it in &items {
+        let v = serde_json::to_value(it)
+            .map_err(|e| ForgeFfiError::system_error(format!("序列化网卡失败: {e}")))?;
+        out.push(project_fields(v, &req.fields));
+    }
+    let mut resp = serde_json::Map::new();
+    resp.insert("abi".to_string(), NETIF_ABI_VERSION.into());
+    resp.insert("items".to_string(), serde_json::Value::Array(out));
+    // 只在未投影时附带 location：macOS 上要额外跑 scutil
+    if req.fields.is_empty()
+        && let Some(loc) = network_location()
+        && let Ok(v) = serde_json::to_value(loc)
+    {
+        resp.insert("network_location".to_string(), v);
+    }
+    Ok(serde_json::Value::Object(resp))
+}
+
+fn filter_matches(
+    filter: &NetIfListFilter,
+    pattern: Option<&glob::Pattern>,
+    it: &NetInterface,
+) -> bool {
+    if !filter.kinds.is_empty() && !filter.kinds.contains(&it.kind) {
+        return false;
+    }
+    if let Some(p) = pattern
+        && !p.matches(&it.name)
+    {
+        return false;
+    }
+    if filter.only_up && it.admin_state != AdminState::Up {
+        return false;
+    }
+    match filter.family {
+        Some(IpFamily::Ipv4) => !it.ipv4.is_empty(),
+        Some(IpFamily::Ipv6) => !it.ipv6.is_empty(),
+        None => true,
+    }
+}
+
+fn project_fields(v: serde_json::Value, fields: &[String]) -> serde_json::Value {
+    if fields.is_empty() {
+        return v;
+    }
+    let serde_json::Value::Object(mut map) = v else {
+        return v;
+    };
+    map.retain(|k, _| k == "if_index" || k == "name" || fields.iter().any(|f| f == k));
+    serde_json::Value::Object(map)
+}
+
+pub fn list_filtered_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetIfListRequest = decode_request(req_json)?;
+    let resp = list_filtered(&req)?;
     serde_json::to_vec(&resp)
         .map_err(|e| ForgeFfiError::system_error(format!("序列化 list 响应失败: {e}")))
 }
 
+// 用 UDP connect 让内核按路由表选出口（不会发出任何报文），
+// 再用源地址反查网卡；探测失败（无路由、沙箱禁网）时按 metric 最小的默认路由兜底
+pub fn default_routes() -> Result<DefaultRouteResponse, ForgeFfiError> {
+    let ifaces = list_interfaces()?;
+    let mut routes = Vec::new();
+    for family in [IpFamily::Ipv4, IpFamily::Ipv6] {
+        let source = egress_source(family);
+        let owner = source.and_then(|src| {
+            ifaces.iter().find(|it| {
+                it.ipv4
+                    .iter()
+                    .chain(&it.ipv6)
+                    .any(|a| a.ip.split('%').next().and_then(|s| s.parse().ok()) == Some(src))
+            })
+        });
+        let best = |it: &NetInterface| {
+            it.default_routes
+                .iter()
+                .filter(|r| r.family == family)
+                .min_by_key(|r| r.metric.unwrap_or(u32::MAX))
+                .cloned()
+        };
+        let chosen = owner
+            .and_then(|it| best(it).map(|r| (it, r)))
+            .or_else(|| {
+                ifaces
+                    .iter()
+                    .filter_map(|it| best(it).map(|r| (it, r)))
+                    .min_by_key(|(_, r)| r.metric.unwrap_or(u32::MAX))
+            });
+        if let Some((it, r)) = chosen {
+            routes.push(ActiveDefaultRoute {
+                family,
+                if_index: it.if_index,
+                if_name: it.name.clone(),
+                gateway: r.gateway,
+                metric: r.metric,
+                origin: r.origin,
+                source_address: source
+                    .map(|s| s.to_string())
+                    .or(r.preferred_src),
+            });
+        }
+    }
+    Ok(DefaultRouteResponse {
+        abi: NETIF_ABI_VERSION,
+        routes,
+    })
+}
+
+// 当前平台与当前选中后端下可能成功的 op（仍可能因权限或网卡类型失败）
+pub fn supported_ops() -> Vec<&'static str> {
+    if mock::active() {
+        return mock::supported_ops();
+    }
+    platform::supported_ops()
+}
+
+// 每个 op 可显式指定的 scope；能否真正持久化还取决于网卡是否由 NetworkManager 等配置工具管理
+pub fn op_scopes() -> BTreeMap<&'static str, &'static [NetIfScope]> {
+    supported_ops()
+        .into_iter()
+        .map(|op| (op, scopes_of(op)))
+        .filter(|(_, scopes)| !scopes.is_empty())
+        .collect()
+}
+
+// mock 后端不区分运行时与持久化，全部接受
+fn scopes_of(op: &str) -> &'static [NetIfScope] {
+    if mock::active() {
+        return &NetIfScope::ALL;
+    }
+    platform::op_scopes(op)
+}
+
+fn check_scope(spec: &NetIfOpSpec) -> Result<(), ForgeFfiError> {
+    let Some(scope) = spec.scope else {
+        return Ok(());
+    };
+    let tag = netif_op_tag(&spec.op);
+    if scopes_of(tag).contains(&scope) {
+        return Ok(());
+    }
+    Err(ForgeFfiError::unsupported(format!(
+        "当前平台的 {tag} 不支持 scope={}",
+        scope.as_str()
+    )))
+}
+
+thread_local! {
+    static OP_SCOPE: Cell<Option<NetIfScope>> = const { Cell::new(None) };
+}
+
+fn with_op_scope<T>(scope: Option<NetIfScope>, f: impl FnOnce() -> T) -> T {
+    let prev = OP_SCOPE.with(|s| s.replace(scope));
+    let out = f();
+    OP_SCOPE.with(|s| s.set(prev));
+    out
+}
+
+// 平台实现按当前 op 的 scope 决定是否改运行时状态、是否写配置；None 为默认行为
+#[cfg_attr(not(any(target_os = "linux", target_os = "windows")), allow(dead_code))]
+fn op_scope() -> Option<NetIfScope> {
+    OP_SCOPE.with(Cell::get)
+}
+
+pub fn backends_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("netif 后端列表", &backends())
+}
+
+pub fn default_route_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("默认路由", &default_routes()?)
+}
+
+// 每块网卡的限速与原始 qdisc（Windows 为 QoS 策略）；没有任何配置的网卡 entries 为空
+pub fn qos() -> Result<NetIfQosResponse, ForgeFfiError> {
+    let ifaces = list_interfaces()?;
+    let items = if mock::active() {
+        mock::qos(&ifaces)
+    } else {
+        platform::qos(&ifaces)?
+    };
+    Ok(NetIfQosResponse {
+        abi: NETIF_ABI_VERSION,
+        items,
+    })
+}
+
+pub fn qos_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("限速配置", &qos()?)
+}
+
+fn egress_source(family: IpFamily) -> Option<IpAddr> {
+    let (bind, probe): (SocketAddr, SocketAddr) = match family {
+        IpFamily::Ipv4 => (
+            (Ipv4Addr::UNSPECIFIED, 0).into(),
+            (Ipv4Addr::new(8, 8, 8, 8), 53).into(),
+        ),
+        IpFamily::Ipv6 => (
+            (Ipv6Addr::UNSPECIFIED, 0).into(),
+            (Ipv6Addr::new(0x2001, 0x4860, 0x4860, 0, 0, 0, 0, 0x8888), 53).into(),
+        ),
+    };
+    let sock = UdpSocket::bind(bind).ok()?;
+    sock.connect(probe).ok()?;
+    let ip = sock.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then_some(ip)
+}
+
 pub fn apply_request(req: NetIfApplyRequest) -> Result<NetIfApplyResponse, ForgeFfiError> {
-    if req.abi != NETIF_ABI_VERSION {
-        return Err(ForgeFfiError::invalid_argument(format!(
-            "abi 版本不匹配: expected={} got={}"
-            , NETIF_ABI_VERSION, req.abi
-        )));
+    apply_request_with_progress(req, &mut |_, _, _| {})
+}
+
+// (index, total, result)；每个 op 有结果后立即回调一次，顺序与 ops 一致，总共 total 次
+pub type ApplyProgress<'a> = dyn FnMut(usize, usize, &NetIfOpResult) + 'a;
+
+pub fn apply_request_with_progress(
+    req: NetIfApplyRequest,
+    on_progress: &mut ApplyProgress<'_>,
+) -> Result<NetIfApplyResponse, ForgeFfiError> {
+    // 排队等并发名额与串行锁的时间也计入 span，慢 apply 往往卡在这里
+    let span = crate::trace::enter("netif.apply");
+    span.field("ops", req.ops.len());
+    if let Some(name) = &req.target.name {
+        span.field("target", name);
+    }
+    if let Some(if_index) = req.target.if_index {
+        span.field("if_index", if_index);
     }
+    // 串行模式下排队等锁的请求也占用并发名额
+    let res = ratelimit::acquire_apply_slot()
+        .and_then(|_slot| crate::runtime::exclusive(|| apply_exclusive(req, on_progress)))
+        // 计时线程没启动时回滚要重新获取串行锁与并发名额，所以放在两者都释放之后
+        .and_then(|armed| armed.map_err(|u| confirm::revert_unarmed(*u)));
+    span.finish(match &res {
+        Ok(r) => r.results.iter().find_map(|r| r.error.as_ref().map(|e| e.code)),
+        Err(e) => Some(e.code),
+    });
+    res
+}
+
+// 内层 Err 表示 ops 已执行但 commit-confirm 计时没能启动，由调用方在锁外回滚
+fn apply_exclusive(
+    req: NetIfApplyRequest,
+    on_progress: &mut ApplyProgress<'_>,
+) -> Result<Result<NetIfApplyResponse, Box<confirm::Unarmed>>, ForgeFfiError> {
+    check_abi(req.abi)?;
 
+    let deadline = req
+        .deadline_ms
+        .map(|ms| Instant::now() + Duration::from_millis(ms));
     let ifaces = list_interfaces()?;
-    let target = resolve_target(&req.target, &ifaces)?;
+    let mut target = if req.ops.iter().any(|s| op_uses_target(&s.op)) {
+        Some(resolve_target(&req.target, &ifaces)?)
+    } else {
+        None
+    };
+    let pending = match req.confirm_timeout_secs {
+        Some(secs) => {
+            validate_commit_confirm(&req)?;
+            let t = target
+                .as_ref()
+                .ok_or_else(|| ForgeFfiError::invalid_argument("commit-confirm 模式需要 target".to_string()))?;
+            Some((secs, confirm::begin(t, &req.ops, secs)?))
+        }
+        None => None,
+    };
 
     let mut results = Vec::with_capacity(req.ops.len());
     let mut all_ok = true;
+    let mut gone = false;
+    // skip_if_applied 用的当前状态；每执行一个 op 后作废，下一次按需重新读取
+    let mut current: Option<Option<NetInterface>> = None;
+
+    // 随前面的 op 合并执行过的 op 的结果，以及合并时已经通过限流判断的 op
+    let mut batched: BTreeMap<usize, Result<(), ForgeFfiError>> = BTreeMap::new();
 
-    for (i, op) in req.ops.iter().cloned().enumerate() {
-        let r = validate_op(&op).and_then(|_| platform::apply_one(&target, &op));
-        match r {
-            Ok(()) => results.push(NetIfOpResult {
+    let total = req.ops.len();
+    let mut op_span = None;
+    for (i, spec) in req.ops.iter().enumerate() {
+        // 上一个 op 的结果此时已入列
+        finish_op_span(op_span.take(), results.last());
+        if let Some(r) = results.last() {
+            on_progress(i - 1, total, r);
+        }
+        let op = &spec.op;
+        op_span = Some(enter_op_span(i, spec, target.as_ref()));
+        // 已经执行过，不再检查 deadline/取消
+        if let (Some(r), Some(target)) = (batched.remove(&i), target.as_mut()) {
+            if gone {
+                results.push(NetIfOpResult {
+                    i,
+                    ok: false,
+                    skipped: false,
+                    error: Some(target_gone_error(target)),
+                    steps: Vec::new(),
+                });
+                continue;
+            }
+            let r = r.and_then(|_| {
+                with_command_deadline(deadline, || wait_dad_if_requested(target, op))
+                    .map_err(|e| cancelled_if_requested(timeout_if_expired(e, deadline)))
+            });
+            let result = record_applied(target, i, op, r, Vec::new(), &mut gone);
+            all_ok &= result.ok;
+            results.push(result);
+            continue;
+        }
+        if let Some(d) = deadline
+            && Instant::now() >= d
+        {
+            all_ok = false;
+            results.push(NetIfOpResult {
                 i,
-                ok: true,
-                error: None,
-            }),
-            Err(e) => {
+                ok: false,
+                skipped: false,
+                error: Some(ForgeFfiError::catalog(
+                    ErrorCode::Timeout,
+                    "op_deadline_exceeded",
+                    &[("deadline_ms", &req.deadline_ms.unwrap_or_default())],
+                )),
+                steps: Vec::new(),
+            });
+            continue;
+        }
+        if is_cancelled() {
+            all_ok = false;
+            results.push(NetIfOpResult {
+                i,
+                ok: false,
+                skipped: false,
+                error: Some(ForgeFfiError::catalog(ErrorCode::Cancelled, "op_cancelled", &[])),
+                steps: Vec::new(),
+            });
+            continue;
+        }
+        let op_deadline = match spec.timeout_ms {
+            Some(ms) => {
+                let d = Instant::now() + Duration::from_millis(ms);
+                Some(deadline.map_or(d, |r| r.min(d)))
+            }
+            None => deadline,
+        };
+
+        let Some(target) = target.as_mut().filter(|_| op_uses_target(op)) else {
+            let r = validate_netif_op(op).and_then(|_| check_scope(spec)).and_then(|_| {
+                with_command_deadline(op_deadline, || with_op_scope(spec.scope, || platform_apply_standalone(op)))
+                    .map_err(|e| cancelled_if_requested(timeout_if_expired(e, op_deadline)))
+            });
+            all_ok &= r.is_ok();
+            results.push(NetIfOpResult {
+                i,
+                ok: r.is_ok(),
+                skipped: false,
+                error: r.err(),
+                steps: Vec::new(),
+            });
+            continue;
+        };
+
+        if gone {
+            results.push(NetIfOpResult {
+                i,
+                ok: false,
+                skipped: false,
+                error: Some(target_gone_error(target)),
+                steps: Vec::new(),
+            });
+            continue;
+        }
+
+        // 只写配置的 op 与当前运行状态无关，不能据此跳过
+        if req.skip_if_applied && spec.scope != Some(NetIfScope::Persistent) && validate_netif_op(op).is_ok() {
+            let state = current.get_or_insert_with(|| fresh_state(target));
+            if state.as_ref().is_some_and(|it| op_already_applied(op, it)) {
+                results.push(NetIfOpResult {
+                    i,
+                    ok: true,
+                    skipped: true,
+                    error: None,
+                    steps: Vec::new(),
+                });
+                continue;
+            }
+        }
+        match ratelimit::admit(&target.name, op) {
+            ratelimit::Admit::Run => {}
+            ratelimit::Admit::Debounced => {
+                results.push(NetIfOpResult {
+                    i,
+                    ok: true,
+                    skipped: true,
+                    error: None,
+                    steps: Vec::new(),
+                });
+                continue;
+            }
+            ratelimit::Admit::Limited(e) => {
                 all_ok = false;
                 results.push(NetIfOpResult {
                     i,
                     ok: false,
+                    skipped: false,
                     error: Some(e),
+                    steps: Vec::new(),
                 });
+                continue;
             }
         }
+        current = None;
+
+        // 跳过判断需要每个 op 之后的最新状态，这种请求不合并
+        let run = if req.skip_if_applied {
+            1
+        } else {
+            plan_batch(&req.ops[i..], target)
+        };
+        let mut steps = Vec::new();
+        let r = if run > 1 {
+            if let Some(s) = &op_span {
+                s.field("batch", run);
+            }
+            let ops: Vec<&NetIfOp> = req.ops[i..i + run].iter().map(|s| &s.op).collect();
+            let mut rs = with_command_deadline(deadline, || {
+                with_op_scope(spec.scope, || platform_apply_batch(target, &ops))
+            })
+            .into_iter()
+            .map(|r| r.map_err(|e| cancelled_if_requested(timeout_if_expired(e, deadline))));
+            let first = rs.next().unwrap_or(Ok(()));
+            batched.extend((i + 1..).zip(rs));
+            first.and_then(|_| {
+                with_command_deadline(deadline, || wait_dad_if_requested(target, op))
+                    .map_err(|e| cancelled_if_requested(timeout_if_expired(e, deadline)))
+            })
+        } else {
+            validate_netif_op(op).and_then(|_| check_scope(spec)).and_then(|_| {
+                with_command_deadline(op_deadline, || {
+                    with_op_scope(spec.scope, || platform_apply_one(target, op, &mut steps))
+                        .and_then(|_| wait_dad_if_requested(target, op))
+                })
+                .map_err(|e| cancelled_if_requested(timeout_if_expired(e, op_deadline)))
+            })
+        };
+        let result = record_applied(target, i, op, r, steps, &mut gone);
+        all_ok &= result.ok;
+        results.push(result);
+    }
+
+    finish_op_span(op_span.take(), results.last());
+    if let Some(r) = results.last() {
+        on_progress(total - 1, total, r);
     }
 
-    Ok(NetIfApplyResponse {
+    // 无论成败都可能改动过系统状态
+    if !req.ops.is_empty() {
+        cache::invalidate();
+    }
+
+    let last_known = match target {
+        Some(target) if gone => ifaces.into_iter().find(|it| target.matches(it)),
+        _ => None,
+    };
+
+    // 全部被跳过时系统没有变化，不需要确认；失败的 op 可能已部分生效，同样要计时
+    let (confirm_token, confirm_deadline_ms) = match pending {
+        Some((_, record)) if results.iter().all(|r| r.skipped) => {
+            confirm::discard(&record);
+            (None, None)
+        }
+        Some((secs, record)) => {
+            let token = record.token.clone();
+            match confirm::arm(record, secs) {
+                Ok(deadline_ms) => (Some(token), Some(deadline_ms)),
+                Err(unarmed) => return Ok(Err(unarmed)),
+            }
+        }
+        None => (None, None),
+    };
+
+    Ok(Ok(NetIfApplyResponse {
         abi: NETIF_ABI_VERSION,
+        min_abi: MIN_SUPPORTED_ABI,
+        max_abi: NETIF_ABI_VERSION,
         ok: all_ok,
         results,
+        last_known,
+        confirm_token,
+        confirm_deadline_ms,
+    }))
+}
+
+// 执行过的 op 记结果；失败时区分网卡已消失与部分步骤已生效
+fn record_applied(
+    target: &mut ResolvedTarget,
+    i: usize,
+    op: &NetIfOp,
+    r: Result<(), ForgeFfiError>,
+    steps: Vec<NetIfStepResult>,
+    gone: &mut bool,
+) -> NetIfOpResult {
+    match r {
+        Ok(()) => {
+            // 后续 op 按新名字定位（Windows 按 if_index 定位，不受影响）
+            if let NetIfOp::SetName { new_name } = op {
+                target.name = new_name.clone();
+            }
+            ratelimit::record_ok(&target.name, op);
+            NetIfOpResult {
+                i,
+                ok: true,
+                skipped: false,
+                error: None,
+                steps,
+            }
+        }
+        Err(e) => {
+            let e = if target_present(target) {
+                partial_if_steps_applied(e, &steps)
+            } else {
+                *gone = true;
+                target_gone_error(target)
+            };
+            NetIfOpResult {
+                i,
+                ok: false,
+                skipped: false,
+                error: Some(e),
+                steps,
+            }
+        }
+    }
+}
+
+// 从 specs[0]（已通过各项检查、即将执行）起，能与它合并成一次调用的 op 个数；1 表示单独执行。
+// 只合并 scope 相同、没有单独超时、校验通过的相邻 op，具体哪些 op 能合并由后端决定。
+// 合并进来的 op 在这里就做限流判断，判断结果不是 Run 的 op 截断合并并留给它自己的轮次
+fn plan_batch(specs: &[NetIfOpSpec], target: &ResolvedTarget) -> usize {
+    let head = &specs[0];
+    // 限流与去抖按 op 逐个判定，且依赖前一个 op 的执行结果，配置了就不合并
+    if ratelimit::per_op_limited()
+        || head.timeout_ms.is_some()
+        || validate_netif_op(&head.op).is_err()
+        || check_scope(head).is_err()
+    {
+        return 1;
+    }
+    let ops: Vec<&NetIfOp> = specs
+        .iter()
+        .take_while(|s| {
+            s.scope == head.scope
+                && s.timeout_ms.is_none()
+                && op_uses_target(&s.op)
+                && validate_netif_op(&s.op).is_ok()
+                && check_scope(s).is_ok()
+        })
+        .map(|s| &s.op)
+        .collect();
+    if ops.len() < 2 {
+        return 1;
+    }
+    with_op_scope(head.scope, || platform_batch_len(target, &ops)).max(1)
+}
+
+fn enter_op_span(i: usize, spec: &NetIfOpSpec, target: Option<&ResolvedTarget>) -> crate::trace::Span {
+    let span = crate::trace::enter("netif.op");
+    span.field("index", i);
+    span.field("op", netif_op_tag(&spec.op));
+    if let Some(t) = target {
+        span.field("target", &t.name);
+    }
+    if let Some(scope) = spec.scope {
+        span.field("scope", scope.as_str());
+    }
+    if mock::active() {
+        span.backend(NetIfBackend::Mock);
+    }
+    span
+}
+
+fn finish_op_span(span: Option<crate::trace::Span>, r: Option<&NetIfOpResult>) {
+    let (Some(span), Some(r)) = (span, r) else {
+        return;
+    };
+    if r.skipped {
+        span.field("skipped", true);
+    }
+    span.finish(r.error.as_ref().map(|e| e.code));
+}
+
+pub fn multi_apply_request(req: NetIfMultiApplyRequest) -> Result<NetIfMultiApplyResponse, ForgeFfiError> {
+    validate_multi_apply_request(&req)?;
+    let started = Instant::now();
+    let ifaces = list_interfaces()?;
+
+    // 先统一解析：找不到的 target 直接记失败，不同写法指向同一网卡的也拒绝
+    let mut done: Vec<NetIfTargetResult> = Vec::with_capacity(req.targets.len());
+    let mut pending = Vec::with_capacity(req.targets.len());
+    let mut seen: Vec<(String, usize)> = Vec::new();
+    for (i, sel) in req.targets.iter().enumerate() {
+        match resolve_target(sel, &ifaces) {
+            Ok(r) => match seen.iter().find(|(name, _)| *name == r.name) {
+                Some((name, j)) => done.push(target_failed(
+                    i,
+                    sel,
+                    ForgeFfiError::invalid_argument(format!(
+                        "targets[{i}] 与 targets[{j}] 指向同一网卡: {name}"
+                    )),
+                )),
+                None => {
+                    seen.push((r.name, i));
+                    pending.push(i);
+                }
+            },
+            Err(e) => done.push(target_failed(i, sel, e)),
+        }
+    }
+
+    let workers = req
+        .max_parallel
+        .unwrap_or(NETIF_MULTI_DEFAULT_PARALLEL)
+        .min(u32::try_from(pending.len()).unwrap_or(u32::MAX));
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(done);
+    let cancel = current_cancel_token();
+    std::thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| {
+                while let Some(&i) = pending.get(next.fetch_add(1, AtomicOrdering::Relaxed)) {
+                    let r = with_cancel_token(cancel.clone(), || apply_target(&req, i, started));
+                    match results.lock() {
+                        Ok(mut g) => g.push(r),
+                        Err(p) => p.into_inner().push(r),
+                    }
+                }
+            });
+        }
+    });
+
+    let mut targets = match results.into_inner() {
+        Ok(v) => v,
+        Err(p) => p.into_inner(),
+    };
+    targets.sort_by_key(|t| t.i);
+    Ok(NetIfMultiApplyResponse {
+        abi: NETIF_ABI_VERSION,
+        ok: targets.iter().all(|t| t.ok),
+        targets,
+    })
+}
+
+fn apply_target(req: &NetIfMultiApplyRequest, i: usize, started: Instant) -> NetIfTargetResult {
+    let sel = &req.targets[i];
+    // 排队等待的时间也计入批次 deadline
+    let deadline_ms = req.deadline_ms.map(|ms| {
+        ms.saturating_sub(u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX))
+    });
+    let one = NetIfApplyRequest {
+        abi: req.abi,
+        target: sel.clone(),
+        ops: req.ops.clone(),
+        deadline_ms,
+        skip_if_applied: req.skip_if_applied,
+        confirm_timeout_secs: None,
+    };
+    match apply_request(one) {
+        Ok(resp) => NetIfTargetResult {
+            i,
+            target: sel.clone(),
+            ok: resp.ok,
+            results: resp.results,
+            error: None,
+            last_known: resp.last_known,
+        },
+        Err(e) => target_failed(i, sel, e),
+    }
+}
+
+fn target_failed(i: usize, sel: &IfaceSelector, e: ForgeFfiError) -> NetIfTargetResult {
+    NetIfTargetResult {
+        i,
+        target: sel.clone(),
+        ok: false,
+        results: Vec::new(),
+        error: Some(e),
+        last_known: None,
+    }
+}
+
+pub fn multi_apply_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetIfMultiApplyRequest = decode_request(req_json)?;
+    encode_json("multi apply 响应", &multi_apply_request(req)?)
+}
+
+pub fn reconcile(req: NetIfReconcileRequest) -> Result<NetIfReconcileResponse, ForgeFfiError> {
+    validate_reconcile_request(&req)?;
+    let ifaces = list_interfaces()?;
+    reconcile_target(&req, &ifaces)
+}
+
+fn reconcile_target(
+    req: &NetIfReconcileRequest,
+    ifaces: &[NetInterface],
+) -> Result<NetIfReconcileResponse, ForgeFfiError> {
+    let target = resolve_target(&req.target, ifaces)?;
+    // 按实时状态计算 diff，不使用 list 缓存
+    let current = fresh_state(&target)
+        .ok_or_else(|| ForgeFfiError::not_found(format!("未找到网卡 name={}", target.name)))?;
+    let diff = plan_reconcile(&req.desired, &current);
+
+    if req.dry_run || diff.is_empty() {
+        return Ok(NetIfReconcileResponse {
+            abi: NETIF_ABI_VERSION,
+            ok: true,
+            diff,
+            results: Vec::new(),
+            last_known: None,
+        });
+    }
+
+    let resp = apply_request(NetIfApplyRequest {
+        abi: req.abi,
+        target: req.target.clone(),
+        ops: diff.iter().cloned().map(Into::into).collect(),
+        deadline_ms: req.deadline_ms,
+        skip_if_applied: false,
+        confirm_timeout_secs: None,
+    })?;
+    Ok(NetIfReconcileResponse {
+        abi: NETIF_ABI_VERSION,
+        ok: resp.ok,
+        diff,
+        results: resp.results,
+        last_known: resp.last_known,
+    })
+}
+
+pub fn reconcile_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetIfReconcileRequest = decode_request(req_json)?;
+    encode_json("reconcile 响应", &reconcile(req)?)
+}
+
+pub fn snapshot(req: &NetIfSnapshotRequest) -> Result<NetIfSnapshot, ForgeFfiError> {
+    check_abi(req.abi)?;
+    let mut items = platform_list()?;
+    sort_interfaces(&mut items, NetIfSortKey::default());
+    fill_gateways(&mut items);
+    if !req.targets.is_empty() {
+        let wanted = req
+            .targets
+            .iter()
+            .map(|sel| resolve_target(sel, &items))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.retain(|it| wanted.iter().any(|t| t.matches(it)));
+    }
+    Ok(NetIfSnapshot {
+        abi: NETIF_ABI_VERSION,
+        taken_at_ms: now_unix_ms(),
+        interfaces: items
+            .iter()
+            .map(|it| NetIfSnapshotEntry {
+                name: it.name.clone(),
+                if_index: it.if_index,
+                mac: it.mac.clone(),
+                state: desired_state_of(it),
+            })
+            .collect(),
+    })
+}
+
+pub fn snapshot_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req = match req_json {
+        Some(s) => decode_request(s)?,
+        None => NetIfSnapshotRequest::v1(Vec::new()),
+    };
+    encode_json("snapshot", &snapshot(&req)?)
+}
+
+// 逐个网卡 reconcile 到快照状态；单个失败不影响其余条目
+pub fn restore(req: NetIfRestoreRequest) -> Result<NetIfRestoreResponse, ForgeFfiError> {
+    validate_restore_request(&req)?;
+    let ifaces = list_interfaces()?;
+    let mut items = Vec::with_capacity(req.snapshot.interfaces.len());
+    for entry in req.snapshot.interfaces {
+        let one = NetIfReconcileRequest {
+            abi: req.abi,
+            target: IfaceSelector {
+                if_index: None,
+                name: Some(entry.name.clone()),
+            },
+            desired: entry.state,
+            dry_run: req.dry_run,
+            deadline_ms: None,
+        };
+        items.push(match reconcile_target(&one, &ifaces) {
+            Ok(r) => NetIfRestoreItem {
+                name: entry.name,
+                ok: r.ok,
+                diff: r.diff,
+                results: r.results,
+                error: None,
+            },
+            Err(e) => NetIfRestoreItem {
+                name: entry.name,
+                ok: false,
+                diff: Vec::new(),
+                results: Vec::new(),
+                error: Some(e),
+            },
+        });
+    }
+    Ok(NetIfRestoreResponse {
+        abi: NETIF_ABI_VERSION,
+        ok: items.iter().all(|it| it.ok),
+        items,
     })
 }
 
+pub fn restore_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetIfRestoreRequest = decode_request(req_json)?;
+    encode_json("restore 响应", &restore(req)?)
+}
+
+// 到达截止时间后的失败一律视为超时（子进程已被 kill，原始错误只是副作用）
+fn timeout_if_expired(e: ForgeFfiError, deadline: Option<Instant>) -> ForgeFfiError {
+    match deadline {
+        Some(d) if Instant::now() >= d => {
+            let message = format!("op 执行超时: {}", e.message);
+            e.recode(ErrorCode::Timeout, message)
+        }
+        _ => e,
+    }
+}
+
+fn platform_apply_standalone(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    if mock::active() {
+        return mock::apply_standalone(op);
+    }
+    platform::apply_standalone(op)
+}
+
+fn platform_apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    if mock::active() {
+        return mock::apply_one(target, op, steps);
+    }
+    platform::apply_one(target, op, steps)
+}
+
+// ops 开头能合并成一次调用执行的个数（小于 2 时不合并），由各后端按自己的调用方式判断
+fn platform_batch_len(target: &ResolvedTarget, ops: &[&NetIfOp]) -> usize {
+    if mock::active() {
+        return mock::batch_len(ops);
+    }
+    platform::batch_len(target, ops)
+}
+
+// 一次调用执行多个 op，按顺序返回每个 op 的结果；某个 op 失败不影响其余 op 执行
+fn platform_apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    if mock::active() {
+        return mock::apply_batch(target, ops);
+    }
+    platform::apply_batch(target, ops)
+}
+
+// 已有步骤生效后失败，调用方需要知道系统处于中间状态；具体原因保留在失败步骤里
+fn partial_if_steps_applied(e: ForgeFfiError, steps: &[NetIfStepResult]) -> ForgeFfiError {
+    if matches!(e.code, ErrorCode::Timeout | ErrorCode::Cancelled) || !steps.iter().any(|s| s.ok) {
+        return e;
+    }
+    let message = e.message.clone();
+    e.recode(ErrorCode::PartialFailure, message)
+}
+
+const DAD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+fn wait_dad_if_requested(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    let NetIfOp::AddIp {
+        ip,
+        wait_dad_ms: Some(ms),
+        ..
+    } = op
+    else {
+        return Ok(());
+    };
+    let Ok(std::net::IpAddr::V6(want)) = ip.parse() else {
+        return Ok(());
+    };
+
+    let deadline = Instant::now() + Duration::from_millis(u64::from(*ms));
+    let deadline = command_deadline().map_or(deadline, |d| d.min(deadline));
+    loop {
+        check_cancelled()?;
+        let state = platform_list()?
+            .into_iter()
+            .find(|it| target.matches(it))
+            .and_then(|it| {
+                it.ipv6.into_iter().find(|a| {
+                    a.ip.split('%').next().and_then(|v| v.parse().ok()) == Some(want)
+                })
+            })
+            .and_then(|a| a.dad_state);
+        match state {
+            Some(IpDadState::Duplicate) => {
+                return Err(ForgeFfiError::system_error(format!(
+                    "IPv6 地址 DAD 失败（地址冲突）: {ip}"
+                )));
+            }
+            Some(IpDadState::Preferred | IpDadState::Deprecated) => return Ok(()),
+            Some(IpDadState::Tentative) | None => {}
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(ForgeFfiError::system_error(format!(
+                "等待 IPv6 DAD 完成超时（{ms}ms）: {ip}"
+            )));
+        }
+        std::thread::sleep(DAD_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+fn dad_state_from_flags(tentative: bool, duplicate: bool, deprecated: bool) -> IpDadState {
+    if duplicate {
+        IpDadState::Duplicate
+    } else if tentative {
+        IpDadState::Tentative
+    } else if deprecated {
+        IpDadState::Deprecated
+    } else {
+        IpDadState::Preferred
+    }
+}
+
+fn lifetime_from_secs(secs: u64) -> Option<u32> {
+    u32::try_from(secs).ok().filter(|v| *v != LFT_FOREVER)
+}
+
+// 绕过 list 缓存直接读系统，避免按过期状态误判为已生效
+fn fresh_state(target: &ResolvedTarget) -> Option<NetInterface> {
+    let mut items = platform_list().ok()?;
+    sort_interfaces(&mut items, NetIfSortKey::default());
+    fill_gateways(&mut items);
+    items.into_iter().find(|it| target.matches(it))
+}
+
+fn target_present(target: &ResolvedTarget) -> bool {
+    match platform_list() {
+        Ok(items) => items.iter().any(|it| target.matches(it)),
+        Err(_) => true,
+    }
+}
+
+fn target_gone_error(target: &ResolvedTarget) -> ForgeFfiError {
+    ForgeFfiError::catalog(ErrorCode::TargetGone, "iface_gone", &[("name", &target.name)])
+}
+
 pub fn apply_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
-    let req: NetIfApplyRequest = serde_json::from_str(req_json)
-        .map_err(|e| ForgeFfiError::invalid_argument(format!("解析请求 JSON 失败: {e}")))?;
-    let resp = apply_request(req)?;
+    apply_json_bytes_with_progress(req_json, &mut |_, _, _| {})
+}
+
+pub fn apply_json_bytes_with_progress(
+    req_json: &str,
+    on_progress: &mut ApplyProgress<'_>,
+) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: NetIfApplyRequest = decode_request(req_json)?;
+    let resp = apply_request_with_progress(req, on_progress)?;
     serde_json::to_vec(&resp)
         .map_err(|e| ForgeFfiError::system_error(format!("序列化 apply 响应失败: {e}")))
 }
@@ -101,6 +1224,18 @@ struct ResolvedTarget {
     name: String,
 }
 
+impl ResolvedTarget {
+    #[cfg(target_os = "windows")]
+    fn matches(&self, it: &NetInterface) -> bool {
+        it.if_index == self.if_index
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn matches(&self, it: &NetInterface) -> bool {
+        it.name == self.name
+    }
+}
+
 fn resolve_target(sel: &IfaceSelector, ifaces: &[NetInterface]) -> Result<ResolvedTarget, ForgeFfiError> {
     if let Some(idx) = sel.if_index
         && idx != 0
@@ -112,7 +1247,7 @@ fn resolve_target(sel: &IfaceSelector, ifaces: &[NetInterface]) -> Result<Resolv
                 name: i.name.clone(),
             });
         }
-        return Err(ForgeFfiError::not_found(format!("未找到网卡 if_index={idx}")));
+        return Err(ForgeFfiError::catalog(ErrorCode::NotFound, "iface_not_found_index", &[("if_index", &idx)]));
     }
 
     if let Some(ref name) = sel.name {
@@ -123,107 +1258,10 @@ fn resolve_target(sel: &IfaceSelector, ifaces: &[NetInterface]) -> Result<Resolv
                 name: i.name.clone(),
             });
         }
-        return Err(ForgeFfiError::not_found(format!("未找到网卡 name={name}")));
+        return Err(ForgeFfiError::catalog(ErrorCode::NotFound, "iface_not_found_name", &[("name", name)]));
     }
 
     Err(ForgeFfiError::invalid_argument(
         "target 必须至少包含 if_index 或 name".to_string(),
     ))
 }
-
-fn validate_op(op: &NetIfOp) -> Result<(), ForgeFfiError> {
-    match op {
-        NetIfOp::SetAdminState { .. } => Ok(()),
-        NetIfOp::SetMtu { mtu } => {
-            if *mtu == 0 {
-                return Err(ForgeFfiError::invalid_argument("mtu 不能为 0"));
-            }
-            Ok(())
-        }
-        NetIfOp::AddIp { ip, prefix_len } => {
-            if *prefix_len == 0 {
-                return Err(ForgeFfiError::invalid_argument(
-                    "添加 IP 不允许 prefix_len=0".to_string(),
-                ));
-            }
-            let addr: std::net::IpAddr = ip
-                .parse()
-                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
-            match addr {
-                std::net::IpAddr::V4(_) => {
-                    if *prefix_len > 32 {
-                        return Err(ForgeFfiError::invalid_argument(
-                            "IPv4 prefix_len 必须在 0..=32".to_string(),
-                        ));
-                    }
-                }
-                std::net::IpAddr::V6(_) => {
-                    if *prefix_len > 128 {
-                        return Err(ForgeFfiError::invalid_argument(
-                            "IPv6 prefix_len 必须在 0..=128".to_string(),
-                        ));
-                    }
-                }
-            }
-            Ok(())
-        }
-        NetIfOp::DelIp { ip, prefix_len } => {
-            let addr: std::net::IpAddr = ip
-                .parse()
-                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
-            match addr {
-                std::net::IpAddr::V4(_) => {
-                    if *prefix_len > 32 {
-                        return Err(ForgeFfiError::invalid_argument(
-                            "IPv4 prefix_len 必须在 0..=32".to_string(),
-                        ));
-                    }
-                }
-                std::net::IpAddr::V6(_) => {
-                    if *prefix_len > 128 {
-                        return Err(ForgeFfiError::invalid_argument(
-                            "IPv6 prefix_len 必须在 0..=128".to_string(),
-                        ));
-                    }
-                }
-            }
-            Ok(())
-        }
-        NetIfOp::SetIpv4Dhcp { .. } => Ok(()),
-        NetIfOp::SetIpv4Static {
-            ip,
-            prefix_len,
-            gateway,
-        } => {
-            if *prefix_len == 0 {
-                return Err(ForgeFfiError::invalid_argument(
-                    "IPv4 prefix_len 必须在 1..=32".to_string(),
-                ));
-            }
-            if *prefix_len > 32 {
-                return Err(ForgeFfiError::invalid_argument(
-                    "IPv4 prefix_len 必须在 1..=32".to_string(),
-                ));
-            }
-            let addr: std::net::IpAddr = ip
-                .parse()
-                .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
-            if !matches!(addr, std::net::IpAddr::V4(_)) {
-                return Err(ForgeFfiError::invalid_argument(
-                    "SetIpv4Static 仅支持 IPv4".to_string(),
-                ));
-            }
-            if let Some(gw) = gateway {
-                let gw_addr: std::net::IpAddr = gw
-                    .parse()
-                    .map_err(|_| ForgeFfiError::invalid_argument(format!("非法网关: {gw}")))?;
-                if !matches!(gw_addr, std::net::IpAddr::V4(_)) {
-                    return Err(ForgeFfiError::invalid_argument(
-                        "网关必须是 IPv4".to_string(),
-                    ));
-                }
-            }
-            Ok(())
-        }
-    }
-}
@@ -0,0 +1,66 @@
+// netplan 管理的系统（Ubuntu Server 等）上，/etc/systemd/network 下的手写文件会与 netplan 渲染出的配置冲突，
+// 持久化改为维护 /etc/netplan/99-forgeffi.yaml（按文件名排序最后合并，覆盖前面文件中的同名键）再 netplan apply。
+// 文件只包含本库写入的 ethernets 条目，合并时按网卡名替换；被手工改成无法识别的结构时拒绝覆盖
+use super::*;
+
+use super::parse::{parse_netplan_ethernets, render_netplan, NETPLAN_SCHEMA};
+use super::platform_linux::run_checked;
+use crate::util::{command, map_io_error, write_atomic_private, CommandExt};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub(super) use super::parse::{NetplanEthernet as Ethernet, NETPLAN_FILE};
+
+pub(super) const NETPLAN_DIR: &str = "/etc/netplan";
+
+static DETECTED: Mutex<Option<bool>> = Mutex::new(None);
+
+// /etc/netplan 下有配置文件且装有 netplan 命令才算由 netplan 管理
+pub(super) fn detected() -> bool {
+    let mut g = match DETECTED.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    *g.get_or_insert_with(|| {
+        let has_config = std::fs::read_dir(NETPLAN_DIR).is_ok_and(|d| {
+            d.flatten()
+                .any(|e| e.path().extension().is_some_and(|x| x == "yaml" || x == "yml"))
+        });
+        has_config
+            && command("netplan")
+                .arg("--help")
+                .output_with_deadline()
+                .is_ok_and(|o| o.status.success())
+    })
+}
+
+pub(super) fn clear_cache() {
+    if let Ok(mut g) = DETECTED.lock() {
+        *g = None;
+    }
+}
+
+pub(super) fn persist(dev: &str, entry: Ethernet) -> Result<(), ForgeFfiError> {
+    if !detected() {
+        return Err(ForgeFfiError::unsupported(format!(
+            "未检测到 netplan（{NETPLAN_DIR} 下没有配置或缺少 netplan 命令），无法持久化"
+        )));
+    }
+    let path = Path::new(NETPLAN_FILE);
+    let mut ethernets = match std::fs::read_to_string(path) {
+        Ok(existing) => {
+            NETPLAN_SCHEMA.check_text(&existing, "#")?;
+            parse_netplan_ethernets(&existing)?
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(map_io_error(e)),
+    };
+    ethernets.insert(dev.to_string(), entry);
+    // 新版 netplan 对其他用户可读的配置文件会告警；文件可能含有网关等信息，从创建起就是 0600
+    write_atomic_private(path, render_netplan(&ethernets).as_bytes()).map_err(map_io_error)?;
+    // scope=persistent 只写文件，下次 netplan apply 或重启后生效
+    if op_scope() == Some(NetIfScope::Persistent) {
+        return Ok(());
+    }
+    run_checked("netplan", &["apply"])
+}
@@ -0,0 +1,254 @@
+// macOS `ifconfig -a -L -m` 的输出：空行分隔的网卡块，首行 "en0: flags=8863<UP,...> mtu 1500"。
+// if_index、链路类型由平台层从 getifaddrs 补齐，这里一律为 0 / Unknown
+use forgeffi_base::{
+    AdminState, Duplex, IfaceFlags, IfaceKind, IpAddrEntry, LinkSpeedMode, NetIfCapabilities, NetInterface,
+    OperState,
+};
+
+use super::super::{dad_state_from_flags, lifetime_from_secs};
+
+// 每块从顶格的 "name: flags=..." 行开始，后续行以制表符缩进；块之间不一定有空行
+pub fn parse_ifconfig(s: &str) -> Vec<NetInterface> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    for line in s.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indented = line.starts_with(char::is_whitespace);
+        match blocks.last_mut() {
+            Some(b) if indented => b.push(line),
+            // 第一个网卡之前的缩进行没有归属，丢弃
+            None if indented => {}
+            _ => blocks.push(vec![line]),
+        }
+    }
+    blocks
+        .iter()
+        .filter_map(|b| parse_ifconfig_block(&b.join("\n")))
+        .collect()
+}
+
+// "1000baseT mediaopt full-duplex" / "10Gbase-T mediaopt full-duplex,flow-control"；
+// autoselect、none 等没有速率的条目返回 None
+pub(in crate::netif) fn parse_media_line(rest: &str) -> Option<(LinkSpeedMode, &str, Option<&str>)> {
+    let mut it = rest.split_whitespace();
+    let media = it.next()?;
+    let opt = match (it.next(), it.next()) {
+        (Some("mediaopt"), Some(o)) => Some(o),
+        _ => None,
+    };
+    let digits: String = media.chars().take_while(char::is_ascii_digit).collect();
+    let mut speed_mbps: u32 = digits.parse().ok()?;
+    if media[digits.len()..].starts_with('G') {
+        speed_mbps = speed_mbps.checked_mul(1000)?;
+    }
+    let duplex = match opt {
+        Some(o) if o.split(',').any(|x| x == "half-duplex") => Duplex::Half,
+        // 10G 以上只有全双工，驱动通常不带 mediaopt
+        _ => Duplex::Full,
+    };
+    Some((LinkSpeedMode { speed_mbps, duplex }, media, opt))
+}
+
+fn parse_ifconfig_block(block: &str) -> Option<NetInterface> {
+    let mut lines = block.lines();
+    let first = lines.next()?.trim();
+    let name = first.split(':').next()?.trim().to_string();
+
+    let mut flags_val = 0u32;
+    if let Some(start) = first.find('<')
+        && let Some(end) = first[start + 1..].find('>')
+    {
+        let inside = &first[start + 1..start + 1 + end];
+        for f in inside.split(',') {
+            match f.trim() {
+                "UP" => flags_val |= IfaceFlags::UP,
+                "RUNNING" => flags_val |= IfaceFlags::RUNNING,
+                "LOOPBACK" => flags_val |= IfaceFlags::LOOPBACK,
+                "BROADCAST" => flags_val |= IfaceFlags::BROADCAST,
+                "MULTICAST" => flags_val |= IfaceFlags::MULTICAST,
+                "POINTOPOINT" => flags_val |= IfaceFlags::POINT_TO_POINT,
+                _ => {}
+            }
+        }
+    }
+
+    let mtu = parse_mtu(first);
+    let admin_state = if (flags_val & IfaceFlags::UP) != 0 {
+        AdminState::Up
+    } else {
+        AdminState::Down
+    };
+
+    let mut oper_state = None;
+    let mut mac = None;
+    let mut vlan_id = None;
+    let mut ipv4 = Vec::new();
+    let mut ipv6 = Vec::new();
+    let mut supported_speeds = Vec::new();
+
+    for l in std::iter::once("").chain(lines) {
+        let line = l.trim();
+        if line.starts_with("status:") {
+            let v = line.split_whitespace().nth(1).unwrap_or("");
+            oper_state = Some(if v.eq_ignore_ascii_case("active") {
+                OperState::Up
+            } else {
+                OperState::Down
+            });
+        } else if let Some(rest) = line.strip_prefix("vlan:") {
+            vlan_id = rest
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.parse::<u16>().ok())
+                .filter(|v| *v != 0);
+        } else if let Some(rest) = line.strip_prefix("media ") {
+            // ifconfig -m 的 supported media 列表
+            if let Some((mode, _, _)) = parse_media_line(rest) {
+                supported_speeds.push(mode);
+            }
+        } else if line.starts_with("ether ") {
+            mac = line.split_whitespace().nth(1).map(|s| s.to_string());
+        } else if line.starts_with("inet ") {
+            if let Some(ent) = parse_inet(line) {
+                ipv4.push(ent);
+            }
+        } else if line.starts_with("inet6 ")
+            && let Some(ent) = parse_inet6(line)
+        {
+            ipv6.push(ent);
+        }
+    }
+
+    let kind = if name == "lo0" {
+        IfaceKind::Loopback
+    } else if vlan_id.is_some() {
+        IfaceKind::Vlan
+    } else if name.starts_with("bridge") {
+        IfaceKind::Bridge
+    } else {
+        IfaceKind::Unknown
+    };
+
+    // 只有带 ether 地址的接口（en*/bridge/vlan）能改 MAC
+    let can_set_mac = mac.is_some();
+    supported_speeds.sort();
+    supported_speeds.dedup();
+
+    Some(NetInterface {
+        if_index: 0,
+        name,
+        display_name: None,
+        kind,
+        is_physical: None,
+        admin_state,
+        oper_state,
+        flags: IfaceFlags(flags_val),
+        mac,
+        mtu,
+        speed_bps: None,
+        vlan_id,
+        ipv4,
+        ipv6,
+        default_routes: Vec::new(),
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: Vec::new(),
+        tunnel: None,
+        capabilities: NetIfCapabilities {
+            can_set_admin_state: true,
+            can_set_mtu: true,
+            can_add_del_ip: true,
+            can_set_dhcp: true,
+            can_set_dns: false,
+            can_set_ipv6: true,
+            can_rename: false,
+            can_set_description: false,
+            can_set_mac,
+            can_manage_routes: false,
+            supported_speeds,
+            notes: None,
+        },
+    })
+}
+
+fn parse_mtu(first: &str) -> Option<u32> {
+    let idx = first.find("mtu ")?;
+    let rest = &first[idx + 4..];
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+fn parse_inet(line: &str) -> Option<IpAddrEntry> {
+    let mut it = line.split_whitespace();
+    let _ = it.next()?;
+    let ip = it.next()?.to_string();
+    let mut prefix_len = None;
+    while let Some(k) = it.next() {
+        if k == "netmask"
+            && let Some(mask) = it.next()
+        {
+            prefix_len = parse_netmask_to_prefix(mask);
+        }
+    }
+    Some(IpAddrEntry {
+        ip,
+        prefix_len: prefix_len.unwrap_or(32),
+        scope: None,
+        origin: None,
+        flags: None,
+        dad_state: None,
+        valid_lft_secs: None,
+        preferred_lft_secs: None,
+    })
+}
+
+fn parse_inet6(line: &str) -> Option<IpAddrEntry> {
+    let mut it = line.split_whitespace();
+    let _ = it.next()?;
+    let raw_ip = it.next()?;
+    let ip = raw_ip.split('%').next().unwrap_or(raw_ip).to_string();
+    let mut prefix_len = None;
+    let (mut tentative, mut duplicate, mut deprecated) = (false, false, false);
+    let (mut valid_lft_secs, mut preferred_lft_secs) = (None, None);
+    while let Some(k) = it.next() {
+        match k {
+            "prefixlen" => prefix_len = it.next().and_then(|v| v.parse::<u8>().ok()),
+            "vltime" => valid_lft_secs = it.next().and_then(parse_ifconfig_lifetime),
+            "pltime" => preferred_lft_secs = it.next().and_then(parse_ifconfig_lifetime),
+            "tentative" => tentative = true,
+            "duplicated" => duplicate = true,
+            "deprecated" => deprecated = true,
+            _ => {}
+        }
+    }
+    Some(IpAddrEntry {
+        ip,
+        prefix_len: prefix_len.unwrap_or(128),
+        scope: None,
+        origin: None,
+        flags: None,
+        dad_state: Some(dad_state_from_flags(tentative, duplicate, deprecated)),
+        valid_lft_secs,
+        preferred_lft_secs,
+    })
+}
+
+fn parse_ifconfig_lifetime(v: &str) -> Option<u32> {
+    v.parse::<u64>().ok().and_then(lifetime_from_secs)
+}
+
+fn parse_netmask_to_prefix(mask: &str) -> Option<u8> {
+    if let Some(hex) = mask.strip_prefix("0x") {
+        let v = u32::from_str_radix(hex, 16).ok()?;
+        return Some(v.count_ones() as u8);
+    }
+    let parts: Vec<u8> = mask
+        .split('.')
+        .map(|p| p.parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>()?;
+    if parts.len() != 4 {
+        return None;
+    }
+    let v = u32::from_be_bytes([parts[0], parts[1], parts[2], parts[3]]);
+    Some(v.count_ones() as u8)
+}
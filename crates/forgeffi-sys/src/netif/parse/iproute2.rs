@@ -0,0 +1,305 @@
+// iproute2 的 JSON 输出（`ip -j -d address`、`ip -j route show default`）。
+// 字段随 iproute2 版本增减，未知字段忽略，缺失字段按 None/空处理
+use forgeffi_base::{
+    AdminState, DefaultRoute, ForgeFfiError, IfaceFlags, IfaceKind, IpAddrEntry, IpAddrFlags, IpFamily, IpOrigin,
+    IpScope, NetIfCapabilities, NetInterface, OperState, RouteOrigin, TunnelInfo, TunnelKind,
+};
+use serde::Deserialize;
+
+use super::super::{dad_state_from_flags, lifetime_from_secs};
+
+#[derive(Debug, Deserialize)]
+struct IpAddrInfo {
+    family: String,
+    local: String,
+    prefixlen: u8,
+    scope: Option<String>,
+    #[serde(default)]
+    deprecated: bool,
+    #[serde(default)]
+    tentative: bool,
+    #[serde(default)]
+    dadfailed: bool,
+    #[serde(default)]
+    temporary: bool,
+    #[serde(default)]
+    dynamic: bool,
+    valid_life_time: Option<u64>,
+    preferred_life_time: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpIface {
+    ifindex: u32,
+    ifname: String,
+    #[serde(default)]
+    flags: Vec<String>,
+    mtu: Option<u32>,
+    operstate: Option<String>,
+    address: Option<String>,
+    #[serde(default)]
+    addr_info: Vec<IpAddrInfo>,
+    linkinfo: Option<IpLinkInfo>,
+    ifalias: Option<String>,
+    link_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpRoute {
+    dev: Option<String>,
+    gateway: Option<String>,
+    protocol: Option<serde_json::Value>,
+    metric: Option<u32>,
+    prefsrc: Option<String>,
+    #[serde(default)]
+    nexthops: Vec<IpNextHop>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpNextHop {
+    dev: Option<String>,
+    gateway: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpLinkInfo {
+    info_kind: Option<String>,
+    info_data: Option<IpLinkInfoData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpLinkInfoData {
+    id: Option<serde_json::Value>,
+    // gre/ipip/sit 隧道端点，未指定时为 "any"
+    local: Option<String>,
+    remote: Option<String>,
+    ttl: Option<serde_json::Value>,
+}
+
+pub fn parse_ip_address_json(bytes: &[u8]) -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let ifaces: Vec<IpIface> = serde_json::from_slice(bytes)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 ip JSON 失败: {e}")))?;
+    Ok(ifaces.into_iter().map(map_iface).collect())
+}
+
+// 多路径默认路由按 nexthop 拆开，每个出口网卡一条
+pub fn parse_ip_route_json(bytes: &[u8], family: IpFamily) -> Result<Vec<(String, DefaultRoute)>, ForgeFfiError> {
+    let routes: Vec<IpRoute> = serde_json::from_slice(bytes)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 ip route JSON 失败: {e}")))?;
+    let mut out = Vec::new();
+    for r in routes {
+        // iproute2 不打印 proto boot（ip route add 的默认值），缺省即手工配置
+        let origin = match &r.protocol {
+            None => Some(RouteOrigin::Static),
+            Some(p) => p.as_str().map(map_route_protocol),
+        };
+        let hops = if r.nexthops.is_empty() {
+            vec![IpNextHop {
+                dev: r.dev,
+                gateway: r.gateway,
+            }]
+        } else {
+            r.nexthops
+        };
+        for h in hops {
+            let Some(dev) = h.dev else {
+                continue;
+            };
+            out.push((
+                dev,
+                DefaultRoute {
+                    family,
+                    gateway: h.gateway,
+                    metric: r.metric,
+                    origin,
+                    preferred_src: r.prefsrc.clone(),
+                },
+            ));
+        }
+    }
+    Ok(out)
+}
+
+fn map_route_protocol(s: &str) -> RouteOrigin {
+    match s {
+        "static" | "boot" => RouteOrigin::Static,
+        "dhcp" => RouteOrigin::Dhcp,
+        "ra" => RouteOrigin::RouterAdvertisement,
+        "kernel" => RouteOrigin::Kernel,
+        _ => RouteOrigin::Unknown,
+    }
+}
+
+fn map_iface(i: IpIface) -> NetInterface {
+    let mut flags = 0u32;
+    for f in &i.flags {
+        match f.as_str() {
+            "UP" => flags |= IfaceFlags::UP,
+            "LOWER_UP" => flags |= IfaceFlags::RUNNING,
+            "RUNNING" => flags |= IfaceFlags::RUNNING,
+            "LOOPBACK" => flags |= IfaceFlags::LOOPBACK,
+            "BROADCAST" => flags |= IfaceFlags::BROADCAST,
+            "MULTICAST" => flags |= IfaceFlags::MULTICAST,
+            "POINTOPOINT" => flags |= IfaceFlags::POINT_TO_POINT,
+            _ => {}
+        }
+    }
+
+    let admin_state = if (flags & IfaceFlags::UP) != 0 {
+        AdminState::Up
+    } else {
+        AdminState::Down
+    };
+
+    let oper_state = i.operstate.as_deref().map(map_oper_state);
+
+    let (mut ipv4, mut ipv6) = (Vec::new(), Vec::new());
+    for a in i.addr_info {
+        let scope = a.scope.as_deref().map(map_scope);
+        let mut addr_flags = 0u32;
+        if a.temporary {
+            addr_flags |= IpAddrFlags::TEMPORARY;
+        }
+        if a.deprecated {
+            addr_flags |= IpAddrFlags::DEPRECATED;
+        }
+        if a.tentative {
+            addr_flags |= IpAddrFlags::TENTATIVE;
+        }
+
+        let origin = if a.dynamic { Some(IpOrigin::Dhcp) } else { None };
+        let dad_state = (a.family == "inet6")
+            .then(|| dad_state_from_flags(a.tentative, a.dadfailed, a.deprecated));
+
+        let ent = IpAddrEntry {
+            ip: a.local,
+            prefix_len: a.prefixlen,
+            scope,
+            origin,
+            flags: if addr_flags == 0 { None } else { Some(IpAddrFlags(addr_flags)) },
+            dad_state,
+            valid_lft_secs: a.valid_life_time.and_then(lifetime_from_secs),
+            preferred_lft_secs: a.preferred_life_time.and_then(lifetime_from_secs),
+        };
+        if a.family == "inet" {
+            ipv4.push(ent);
+        } else if a.family == "inet6" {
+            ipv6.push(ent);
+        }
+    }
+
+    let info_kind = i.linkinfo.as_ref().and_then(|l| l.info_kind.as_deref());
+    let vlan_id = match info_kind {
+        Some("vlan") => i
+            .linkinfo
+            .as_ref()
+            .and_then(|l| l.info_data.as_ref())
+            .and_then(|d| d.id.as_ref())
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|v| u16::try_from(v).ok()),
+        _ => None,
+    };
+
+    let tunnel_kind = match info_kind {
+        Some("wireguard") => Some(TunnelKind::Wireguard),
+        Some("gre") => Some(TunnelKind::Gre),
+        Some("ipip") => Some(TunnelKind::Ipip),
+        Some("sit") => Some(TunnelKind::Sit),
+        Some("ip6gre" | "ip6tnl" | "vti" | "vti6" | "gretap" | "ip6gretap" | "erspan") => Some(TunnelKind::Other),
+        _ => None,
+    };
+    let tunnel = tunnel_kind.map(|kind| {
+        let data = i.linkinfo.as_ref().and_then(|l| l.info_data.as_ref());
+        let endpoint = |v: Option<&String>| v.filter(|s| s.as_str() != "any").cloned();
+        TunnelInfo {
+            kind,
+            local: endpoint(data.and_then(|d| d.local.as_ref())),
+            remote: endpoint(data.and_then(|d| d.remote.as_ref())),
+            // ttl 0 表示继承内层报文的 TTL
+            ttl: data
+                .and_then(|d| d.ttl.as_ref())
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|v| u8::try_from(v).ok())
+                .filter(|v| *v != 0),
+            wireguard: None,
+        }
+    });
+
+    let kind = if tunnel.is_some() {
+        IfaceKind::Tunnel
+    } else if info_kind == Some("vlan") {
+        IfaceKind::Vlan
+    } else if info_kind == Some("bridge") {
+        IfaceKind::Bridge
+    } else if matches!(info_kind, Some("bond" | "dummy")) {
+        IfaceKind::Virtual
+    } else if i.ifname == "lo" || i.ifname.starts_with("lo") {
+        IfaceKind::Loopback
+    } else if i.ifname.starts_with("tun") {
+        IfaceKind::Tunnel
+    } else if i.ifname.starts_with("tap") {
+        IfaceKind::Virtual
+    } else {
+        IfaceKind::Unknown
+    };
+
+    // 只有以太网类链路层地址可改（loopback/tun/ipip 等没有 MAC）
+    let can_set_mac = i.link_type.as_deref() == Some("ether");
+
+    NetInterface {
+        if_index: i.ifindex,
+        name: i.ifname,
+        display_name: i.ifalias.filter(|s| !s.is_empty()),
+        kind,
+        is_physical: None,
+        admin_state,
+        oper_state,
+        flags: IfaceFlags(flags),
+        mac: i.address,
+        mtu: i.mtu,
+        speed_bps: None,
+        vlan_id,
+        ipv4,
+        ipv6,
+        default_routes: Vec::new(),
+        ipv4_gateway: None,
+        ipv6_gateway: None,
+        dns_servers: Vec::new(),
+        tunnel,
+        capabilities: NetIfCapabilities {
+            can_set_admin_state: true,
+            can_set_mtu: true,
+            can_add_del_ip: true,
+            // 取决于 nmcli 是否可用，由平台层填写
+            can_set_dhcp: false,
+            can_set_dns: false,
+            can_set_ipv6: true,
+            can_rename: true,
+            can_set_description: true,
+            can_set_mac,
+            can_manage_routes: true,
+            supported_speeds: Vec::new(),
+            notes: None,
+        },
+    }
+}
+
+fn map_oper_state(s: &str) -> OperState {
+    match s {
+        "UP" => OperState::Up,
+        "DOWN" => OperState::Down,
+        "DORMANT" => OperState::Dormant,
+        "LOWERLAYERDOWN" => OperState::LowerLayerDown,
+        _ => OperState::Unknown,
+    }
+}
+
+fn map_scope(s: &str) -> IpScope {
+    match s {
+        "host" => IpScope::Host,
+        "link" => IpScope::Link,
+        "global" => IpScope::Global,
+        "site" => IpScope::Site,
+        _ => IpScope::Unknown,
+    }
+}
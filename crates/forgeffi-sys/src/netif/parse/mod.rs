@@ -0,0 +1,24 @@
+//! 平台命令输出的解析器。
+//!
+//! 只做文本/JSON 到 [`NetInterface`](forgeffi_base::NetInterface)（及其附属信息）的转换，不执行命令、不读系统状态，
+//! 所以在任何平台上都能编译：`tests/parsers.rs` 用各系统版本采集的输出做 golden 比对，
+//! 仓库根目录的 `fuzz/` 对同样的入口做模糊测试。平台层负责补齐解析不到的字段（索引、DNS、路由等）。
+
+mod ifconfig;
+mod iproute2;
+mod netplan;
+mod powershell;
+mod tc;
+mod wireguard;
+
+pub use ifconfig::parse_ifconfig;
+pub use iproute2::{parse_ip_address_json, parse_ip_route_json};
+pub use netplan::{parse_netplan_ethernets, render_netplan, NetplanEthernet, NETPLAN_FILE, NETPLAN_SCHEMA};
+pub use powershell::{parse_netqos_policy_json, parse_powershell_list};
+pub use tc::{parse_tc_class_rate, parse_tc_police_rate, parse_tc_qdisc_json, parse_tc_rate};
+pub use wireguard::parse_wg_dump;
+
+#[cfg(target_os = "macos")]
+pub(super) use ifconfig::parse_media_line;
+#[cfg(target_os = "windows")]
+pub(super) use powershell::SPEED_DUPLEX_VALUES;
@@ -0,0 +1,84 @@
+// /etc/netplan/99-forgeffi.yaml 的读写：render_netplan 写出的文件经 parse_netplan_ethernets 读回应得到原样的条目，
+// 其余结构（手工修改过的文件）一律报错，由调用方拒绝覆盖
+use forgeffi_base::{ForgeFfiError, PersistSchema};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const NETPLAN_FILE: &str = "/etc/netplan/99-forgeffi.yaml";
+
+pub const NETPLAN_SCHEMA: PersistSchema = PersistSchema {
+    kind: "netplan_config",
+    version: 1,
+    migrations: &[],
+};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct NetplanEthernet {
+    pub dhcp4: bool,
+    pub addresses: Vec<String>,
+    pub gateway4: Option<String>,
+}
+
+pub fn render_netplan(ethernets: &BTreeMap<String, NetplanEthernet>) -> String {
+    let mut out = NETPLAN_SCHEMA.text_header("#");
+    out.push_str("# 由 forgeffi 生成，按网卡整体覆盖；手工修改可能在下次写入时被拒绝\n");
+    out.push_str("network:\n  version: 2\n  ethernets:\n");
+    for (dev, e) in ethernets {
+        out.push_str(&format!("    \"{dev}\":\n      dhcp4: {}\n", e.dhcp4));
+        if !e.addresses.is_empty() {
+            out.push_str(&format!("      addresses: [{}]\n", e.addresses.join(", ")));
+        }
+        // 旧版 netplan 不认识 to: default，统一写 0.0.0.0/0
+        if let Some(gw) = &e.gateway4 {
+            out.push_str(&format!("      routes:\n        - to: 0.0.0.0/0\n          via: {gw}\n"));
+        }
+    }
+    out
+}
+
+// 只识别 render_netplan 写出的结构（网关另接受 to: default）
+pub fn parse_netplan_ethernets(text: &str) -> Result<BTreeMap<String, NetplanEthernet>, ForgeFfiError> {
+    let unrecognized = |line: &str| {
+        ForgeFfiError::unsupported(format!(
+            "{NETPLAN_FILE} 含有无法识别的内容（可能被手工修改），拒绝覆盖: {}",
+            line.trim()
+        ))
+    };
+    let mut out: BTreeMap<String, NetplanEthernet> = BTreeMap::new();
+    let mut cur: Option<String> = None;
+    for line in text.lines().map(str::trim_end) {
+        let t = line.trim_start();
+        if t.is_empty() || t.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - t.len();
+        let entry = cur.as_ref().and_then(|d| out.get_mut(d));
+        match (indent, t.split_once(':').map(|(k, v)| (k.trim(), v.trim()))) {
+            (0, Some(("network", ""))) | (2, Some(("version", "2"))) | (2, Some(("ethernets", ""))) => {}
+            (4, Some((name, ""))) => {
+                let name = name.trim_matches('"').to_string();
+                out.insert(name.clone(), NetplanEthernet::default());
+                cur = Some(name);
+            }
+            (6, Some(("dhcp4", v))) if entry.is_some() => {
+                if let Some(e) = entry {
+                    e.dhcp4 = v == "true";
+                }
+            }
+            (6, Some(("addresses", v))) if entry.is_some() => {
+                let list = v.strip_prefix('[').and_then(|v| v.strip_suffix(']')).ok_or_else(|| unrecognized(t))?;
+                if let Some(e) = entry {
+                    e.addresses = list.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect();
+                }
+            }
+            (6, Some(("routes", ""))) | (8, Some(("- to", "0.0.0.0/0" | "default"))) if entry.is_some() => {}
+            (10, Some(("via", v))) if entry.is_some() => {
+                if let Some(e) = entry {
+                    e.gateway4 = Some(v.to_string());
+                }
+            }
+            _ => return Err(unrecognized(t)),
+        }
+    }
+    Ok(out)
+}
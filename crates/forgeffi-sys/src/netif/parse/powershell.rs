@@ -0,0 +1,396 @@
+// Windows 列表脚本（Get-NetAdapter 等 cmdlet 结果汇总后 ConvertTo-Json）的输出。
+// ConvertTo-Json 对单元素集合输出对象而不是数组、枚举在 PowerShell 5.1 与 7 下分别是数字和字符串，
+// 这些差异都在这里归一化
+use forgeffi_base::{
+    AdminState, DefaultRoute, Duplex, ForgeFfiError, IfaceFlags, IfaceKind, IpAddrEntry, IpDadState, IpFamily,
+    LinkSpeedMode, NetIfCapabilities, NetInterface, OperState, QosEntry, RouteOrigin,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::super::lifetime_from_secs;
+
+pub fn parse_powershell_list(text: &str) -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let v: Value = serde_json::from_str(text)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}")))?;
+
+    let adapters = normalize_array(v.get("adapters"));
+    let ipif = normalize_array(v.get("ipif"));
+    let ips = normalize_array(v.get("ips"));
+    let routes = normalize_array(v.get("routes"));
+    // 驱动在高级属性里暴露 NetworkAddress 才支持覆盖 MAC
+    let mac_settable: Vec<String> = normalize_array(v.get("macprop"))
+        .into_iter()
+        .filter_map(|it| it.get("Name").and_then(Value::as_str).map(str::to_string))
+        .collect();
+    let mut speeds_by_name: BTreeMap<String, Vec<LinkSpeedMode>> = BTreeMap::new();
+    for it in normalize_array(v.get("speedprop")) {
+        let Some(name) = it.get("Name").and_then(Value::as_str) else {
+            continue;
+        };
+        let mut modes: Vec<LinkSpeedMode> = normalize_array(it.get("ValidRegistryValues"))
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|v| v.parse::<u32>().ok())
+            .filter_map(speed_duplex_mode)
+            .collect();
+        modes.sort();
+        speeds_by_name.insert(name.to_string(), modes);
+    }
+
+    // IPv4 服务器排在 IPv6 前面
+    let mut dns_pairs: BTreeMap<u32, (Vec<String>, Vec<String>)> = BTreeMap::new();
+    for it in normalize_array(v.get("dns")) {
+        let idx = it.get("InterfaceIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if idx == 0 {
+            continue;
+        }
+        let servers = normalize_array(it.get("ServerAddresses"))
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let e = dns_pairs.entry(idx).or_default();
+        match parse_windows_address_family(it.get("AddressFamily")) {
+            WindowsAddressFamily::Ipv4 => e.0.extend(servers),
+            WindowsAddressFamily::Ipv6 => e.1.extend(servers),
+            WindowsAddressFamily::Unknown => {}
+        }
+    }
+    let mut dns_by_idx: BTreeMap<u32, Vec<String>> = dns_pairs
+        .into_iter()
+        .map(|(idx, (mut v4, v6))| {
+            v4.extend(v6);
+            (idx, v4)
+        })
+        .collect();
+
+    let mut mtu_by_idx: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut conn_by_idx: BTreeMap<u32, OperState> = BTreeMap::new();
+    let mut if_metric: BTreeMap<(u32, WindowsAddressFamily), u32> = BTreeMap::new();
+
+    for it in ipif {
+        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if idx == 0 {
+            continue;
+        }
+        if let Some(mtu) = it.get("NlMtu").and_then(Value::as_u64) {
+            mtu_by_idx.insert(idx, mtu as u32);
+        }
+        if let Some(m) = it.get("InterfaceMetric").and_then(Value::as_u64) {
+            let af = parse_windows_address_family(it.get("AddressFamily"));
+            if_metric.insert((idx, af), m as u32);
+        }
+        // 5.1 输出枚举值（1 = Connected），7 输出名字
+        let connected = match it.get("ConnectionState") {
+            Some(Value::String(cs)) => Some(cs.eq_ignore_ascii_case("Connected")),
+            Some(Value::Number(n)) => Some(n.as_u64() == Some(1)),
+            _ => None,
+        };
+        if let Some(c) = connected {
+            conn_by_idx.insert(idx, if c { OperState::Up } else { OperState::Down });
+        }
+    }
+
+    let mut ips_by_idx: BTreeMap<u32, (Vec<IpAddrEntry>, Vec<IpAddrEntry>)> = BTreeMap::new();
+    for it in ips {
+        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if idx == 0 {
+            continue;
+        }
+        let af = parse_windows_address_family(it.get("AddressFamily"));
+        let ip = it.get("IPAddress").and_then(Value::as_str).unwrap_or("");
+        let prefix = it.get("PrefixLength").and_then(Value::as_u64).unwrap_or(0) as u8;
+        if ip.is_empty() {
+            continue;
+        }
+        let dad_state = if af == WindowsAddressFamily::Ipv6 {
+            parse_windows_address_state(it.get("AddressState"))
+        } else {
+            None
+        };
+        let ent = IpAddrEntry {
+            ip: ip.to_string(),
+            prefix_len: prefix,
+            scope: None,
+            origin: None,
+            flags: None,
+            dad_state,
+            valid_lft_secs: it
+                .get("ValidLifetime")
+                .and_then(Value::as_u64)
+                .and_then(lifetime_from_secs),
+            preferred_lft_secs: it
+                .get("PreferredLifetime")
+                .and_then(Value::as_u64)
+                .and_then(lifetime_from_secs),
+        };
+        let e = ips_by_idx.entry(idx).or_insert_with(|| (Vec::new(), Vec::new()));
+        if af == WindowsAddressFamily::Ipv4 {
+            e.0.push(ent);
+        } else if af == WindowsAddressFamily::Ipv6 {
+            e.1.push(ent);
+        }
+    }
+
+    // Windows 按 RouteMetric + InterfaceMetric 选路，这里直接给出有效 metric
+    let mut routes_by_idx: BTreeMap<u32, Vec<DefaultRoute>> = BTreeMap::new();
+    for it in routes {
+        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if idx == 0 {
+            continue;
+        }
+        let af = parse_windows_address_family(it.get("AddressFamily"));
+        let family = match af {
+            WindowsAddressFamily::Ipv4 => IpFamily::Ipv4,
+            WindowsAddressFamily::Ipv6 => IpFamily::Ipv6,
+            WindowsAddressFamily::Unknown => continue,
+        };
+        let metric = it
+            .get("RouteMetric")
+            .and_then(Value::as_u64)
+            .map(|m| (m as u32).saturating_add(if_metric.get(&(idx, af)).copied().unwrap_or(0)));
+        let gateway = it
+            .get("NextHop")
+            .and_then(Value::as_str)
+            .filter(|s| *s != "0.0.0.0" && *s != "::")
+            .map(|s| s.to_string());
+        routes_by_idx.entry(idx).or_default().push(DefaultRoute {
+            family,
+            gateway,
+            metric,
+            origin: it
+                .get("Protocol")
+                .and_then(Value::as_str)
+                .map(map_route_protocol),
+            preferred_src: None,
+        });
+    }
+
+    let mut out = Vec::new();
+    for it in adapters {
+        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if idx == 0 {
+            continue;
+        }
+        let name = it.get("Name").and_then(Value::as_str).unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let can_set_mac = mac_settable.contains(&name);
+        let supported_speeds = speeds_by_name.remove(&name).unwrap_or_default();
+        let display_name = it
+            .get("InterfaceDescription")
+            .and_then(Value::as_str)
+            .map(|s| s.to_string());
+        let status = it.get("Status").and_then(Value::as_str).unwrap_or("");
+        let admin_state = if status.eq_ignore_ascii_case("Up") {
+            AdminState::Up
+        } else if status.eq_ignore_ascii_case("Disabled") {
+            AdminState::Down
+        } else {
+            AdminState::Unknown
+        };
+        let mac = it
+            .get("MacAddress")
+            .and_then(Value::as_str)
+            .map(|s| s.replace('-', ":"));
+
+        let speed_bps = it
+            .get("LinkSpeed")
+            .and_then(Value::as_str)
+            .and_then(parse_link_speed_bps);
+
+        let vlan_id = it
+            .get("VlanID")
+            .and_then(Value::as_u64)
+            .and_then(|v| u16::try_from(v).ok())
+            .filter(|v| *v != 0);
+
+        let mut flags = 0u32;
+        if admin_state == AdminState::Up {
+            flags |= IfaceFlags::UP;
+        }
+
+        let (ipv4, ipv6) = ips_by_idx.remove(&idx).unwrap_or_default();
+
+        out.push(NetInterface {
+            if_index: idx,
+            name,
+            display_name,
+            kind: if vlan_id.is_some() {
+                IfaceKind::Vlan
+            } else {
+                IfaceKind::Unknown
+            },
+            is_physical: None,
+            admin_state,
+            oper_state: conn_by_idx.get(&idx).copied(),
+            flags: IfaceFlags(flags),
+            mac,
+            mtu: mtu_by_idx.get(&idx).copied(),
+            speed_bps,
+            vlan_id,
+            ipv4,
+            ipv6,
+            default_routes: routes_by_idx.remove(&idx).unwrap_or_default(),
+            ipv4_gateway: None,
+            ipv6_gateway: None,
+            dns_servers: dns_by_idx.remove(&idx).unwrap_or_default(),
+            tunnel: None,
+            capabilities: NetIfCapabilities {
+                can_set_admin_state: true,
+                can_set_mtu: true,
+                can_add_del_ip: true,
+                can_set_dhcp: true,
+                can_set_dns: false,
+                can_set_ipv6: true,
+                can_rename: true,
+                can_set_description: false,
+                can_set_mac,
+                can_manage_routes: false,
+                supported_speeds,
+                notes: None,
+            },
+        });
+    }
+
+    Ok(out)
+}
+
+// `Get-NetQosPolicy | Select-Object Name,IPSrcPrefixMatchCondition,ThrottleRateAction | ConvertTo-Json`；
+// 没有策略时 ConvertTo-Json 什么也不输出。ThrottleRateAction 为 0 表示策略不限速（只打 DSCP 等）
+pub fn parse_netqos_policy_json(text: &str) -> Result<Vec<QosEntry>, ForgeFfiError> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let v: Value = serde_json::from_str(text)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 Get-NetQosPolicy JSON 失败: {e}")))?;
+    Ok(normalize_array(Some(&v))
+        .iter()
+        .filter_map(|p| {
+            let name = p.get("Name").and_then(Value::as_str)?;
+            Some(QosEntry {
+                kind: "qos_policy".to_string(),
+                handle: Some(name.to_string()),
+                parent: None,
+                match_src: p
+                    .get("IPSrcPrefixMatchCondition")
+                    .and_then(Value::as_str)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+                rate_bps: p.get("ThrottleRateAction").and_then(Value::as_u64).filter(|r| *r > 0),
+            })
+        })
+        .collect())
+}
+
+fn map_route_protocol(s: &str) -> RouteOrigin {
+    match s.to_ascii_lowercase().as_str() {
+        "netmgmt" => RouteOrigin::Static,
+        "dhcp" => RouteOrigin::Dhcp,
+        "routeradvertisement" | "icmp" => RouteOrigin::RouterAdvertisement,
+        "local" => RouteOrigin::Kernel,
+        _ => RouteOrigin::Unknown,
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum WindowsAddressFamily {
+    Unknown,
+    Ipv4,
+    Ipv6,
+}
+
+fn parse_windows_address_family(v: Option<&Value>) -> WindowsAddressFamily {
+    match v {
+        None => WindowsAddressFamily::Unknown,
+        Some(Value::String(s)) => {
+            if s.eq_ignore_ascii_case("IPv4") {
+                WindowsAddressFamily::Ipv4
+            } else if s.eq_ignore_ascii_case("IPv6") {
+                WindowsAddressFamily::Ipv6
+            } else {
+                WindowsAddressFamily::Unknown
+            }
+        }
+        Some(Value::Number(n)) => match n.as_u64() {
+            Some(2) => WindowsAddressFamily::Ipv4,
+            Some(23) => WindowsAddressFamily::Ipv6,
+            _ => WindowsAddressFamily::Unknown,
+        },
+        _ => WindowsAddressFamily::Unknown,
+    }
+}
+
+fn parse_windows_address_state(v: Option<&Value>) -> Option<IpDadState> {
+    match v? {
+        Value::String(s) => match s.to_ascii_lowercase().as_str() {
+            "tentative" => Some(IpDadState::Tentative),
+            "duplicate" => Some(IpDadState::Duplicate),
+            "deprecated" => Some(IpDadState::Deprecated),
+            "preferred" => Some(IpDadState::Preferred),
+            _ => None,
+        },
+        Value::Number(n) => match n.as_u64() {
+            Some(1) => Some(IpDadState::Tentative),
+            Some(2) => Some(IpDadState::Duplicate),
+            Some(3) => Some(IpDadState::Deprecated),
+            Some(4) => Some(IpDadState::Preferred),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// NDIS 标准化关键字 *SpeedDuplex 的取值，下标即注册表值；0 为自动协商。
+// 厂商自定义取值（如 2.5G）无法识别，不会出现在 supported_speeds 中
+pub(in crate::netif) const SPEED_DUPLEX_VALUES: &[Option<LinkSpeedMode>] = &[
+    None,
+    Some(LinkSpeedMode { speed_mbps: 10, duplex: Duplex::Half }),
+    Some(LinkSpeedMode { speed_mbps: 10, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 100, duplex: Duplex::Half }),
+    Some(LinkSpeedMode { speed_mbps: 100, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 1000, duplex: Duplex::Half }),
+    Some(LinkSpeedMode { speed_mbps: 1000, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 10_000, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 20_000, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 40_000, duplex: Duplex::Full }),
+    Some(LinkSpeedMode { speed_mbps: 100_000, duplex: Duplex::Full }),
+];
+
+fn speed_duplex_mode(value: u32) -> Option<LinkSpeedMode> {
+    SPEED_DUPLEX_VALUES.get(value as usize).copied().flatten()
+}
+
+// 单元素集合会被展开成对象或标量（ServerAddresses 只有一个地址时就是字符串）
+fn normalize_array(v: Option<&Value>) -> Vec<Value> {
+    match v {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(a)) => a.clone(),
+        Some(other) => vec![other.clone()],
+    }
+}
+
+
+fn parse_link_speed_bps(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    let num: f64 = parts[0].parse().ok()?;
+    let unit = parts[1].to_ascii_lowercase();
+    let mul = if unit.contains("gbps") {
+        1_000_000_000f64
+    } else if unit.contains("mbps") {
+        1_000_000f64
+    } else if unit.contains("kbps") {
+        1_000f64
+    } else if unit.contains("bps") {
+        1f64
+    } else {
+        return None;
+    };
+    Some((num * mul) as u64)
+}
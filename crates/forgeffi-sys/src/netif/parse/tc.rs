@@ -0,0 +1,83 @@
+// tc 的输出。qdisc 用 `tc -j qdisc show`；HTB 分类和 police 动作在较老的 iproute2 上没有 JSON 输出
+// （htb class 的 -j 仍打印文本），所以速率从文本里取，只认 SetBandwidthLimit 自己建的那一条
+use forgeffi_base::{ForgeFfiError, QosEntry};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct TcQdisc {
+    kind: String,
+    // `tc qdisc show dev X` 的输出不带 dev
+    #[serde(default)]
+    dev: String,
+    #[serde(default)]
+    handle: Option<String>,
+    #[serde(default)]
+    root: bool,
+    #[serde(default)]
+    parent: Option<String>,
+}
+
+// 返回 (网卡名, qdisc)，rate_bps 留空由调用方按分类/过滤器补齐；只查一块网卡时网卡名为空
+pub fn parse_tc_qdisc_json(bytes: &[u8]) -> Result<Vec<(String, QosEntry)>, ForgeFfiError> {
+    let qdiscs: Vec<TcQdisc> = serde_json::from_slice(bytes)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 tc qdisc JSON 失败: {e}")))?;
+    Ok(qdiscs
+        .into_iter()
+        .map(|q| {
+            let entry = QosEntry {
+                kind: q.kind,
+                handle: q.handle,
+                parent: if q.root { None } else { q.parent },
+                match_src: None,
+                rate_bps: None,
+            };
+            (q.dev, entry)
+        })
+        .collect())
+}
+
+// tc 打印速率按 1000 进位（100Mbit、2500Kbit），-iec 时为 1024 进位（Mibit）
+pub fn parse_tc_rate(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (num, unit) = s.split_at(split);
+    let num: f64 = num.parse().ok()?;
+    let mul: f64 = match unit.to_ascii_lowercase().as_str() {
+        "bit" => 1.0,
+        "kbit" => 1e3,
+        "mbit" => 1e6,
+        "gbit" => 1e9,
+        "tbit" => 1e12,
+        "kibit" => 1024.0,
+        "mibit" => 1024.0 * 1024.0,
+        "gibit" => 1024.0 * 1024.0 * 1024.0,
+        // bps 在 tc 里是字节每秒
+        "bps" => 8.0,
+        _ => return None,
+    };
+    Some((num * mul).round() as u64)
+}
+
+// `tc class show dev X` 中指定 classid 的 rate（不是 ceil）
+pub fn parse_tc_class_rate(text: &str, classid: &str) -> Option<u64> {
+    text.lines().find_map(|line| {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.first() != Some(&"class") || tokens.get(2) != Some(&classid) {
+            return None;
+        }
+        value_after(&tokens, "rate").and_then(parse_tc_rate)
+    })
+}
+
+// `tc filter show dev X parent ffff:` 中第一个 police 动作的 rate
+pub fn parse_tc_police_rate(text: &str) -> Option<u64> {
+    text.lines().find_map(|line| {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let pos = tokens.iter().position(|t| *t == "police")?;
+        value_after(&tokens[pos..], "rate").and_then(parse_tc_rate)
+    })
+}
+
+fn value_after<'a>(tokens: &[&'a str], key: &str) -> Option<&'a str> {
+    let pos = tokens.iter().position(|t| *t == key)?;
+    tokens.get(pos + 1).copied()
+}
@@ -0,0 +1,47 @@
+// `wg show <dev> dump` 的输出：首行为接口（private_key public_key listen_port fwmark），
+// 其后每行一个 peer（public_key preshared_key endpoint allowed_ips latest_handshake rx tx keepalive），
+// 字段以 tab 分隔，缺失值为 "(none)"/"off"/0。私钥与预共享密钥不进入结果
+use forgeffi_base::{WireguardInfo, WireguardPeerInfo};
+
+fn field(s: &str) -> Option<&str> {
+    match s {
+        "" | "(none)" | "off" => None,
+        v => Some(v),
+    }
+}
+
+pub fn parse_wg_dump(text: &str) -> Option<WireguardInfo> {
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let head: Vec<&str> = lines.next()?.split('\t').collect();
+    if head.len() < 4 {
+        return None;
+    }
+    let peers = lines
+        .filter_map(|l| {
+            let f: Vec<&str> = l.split('\t').collect();
+            if f.len() < 8 {
+                return None;
+            }
+            Some(WireguardPeerInfo {
+                public_key: field(f[0])?.to_string(),
+                endpoint: field(f[2]).map(str::to_string),
+                allowed_ips: field(f[3])
+                    .map(|v| v.split(',').map(|s| s.trim().to_string()).collect())
+                    .unwrap_or_default(),
+                latest_handshake_unix: f[4].parse().ok().filter(|t| *t != 0),
+                rx_bytes: f[5].parse().unwrap_or(0),
+                tx_bytes: f[6].parse().unwrap_or(0),
+                persistent_keepalive_secs: field(f[7]).and_then(|v| v.parse().ok()).filter(|v| *v != 0),
+            })
+        })
+        .collect();
+    Some(WireguardInfo {
+        public_key: field(head[1]).map(str::to_string),
+        listen_port: head[2].parse().ok().filter(|p| *p != 0),
+        fwmark: field(head[3]).and_then(|v| {
+            v.strip_prefix("0x")
+                .map_or_else(|| v.parse().ok(), |h| u32::from_str_radix(h, 16).ok())
+        }),
+        peers,
+    })
+}
@@ -1,64 +1,223 @@
 use super::*;
 
+use crate::util::{
+    command, command_failed, map_io_error, map_spawn_error, write_atomic, CommandExt,
+};
 use forgeffi_base::{
-    AdminState, IfaceFlags, IfaceKind, IpAddrEntry, IpAddrFlags, IpOrigin, IpScope,
-    NetIfCapabilities, OperState,
+    BondMode, DefaultRoute, Duplex, IpAddrFlags, IpFamily, IpScope, Ipv6Mode, LinkSpeedMode, NetIfBackend,
+    NetIfBackendRole, NetIfQos, PersistSchema,
 };
-use serde::Deserialize;
-use std::process::Command;
-use std::sync::OnceLock;
-use std::{fs, io, path::Path};
-
-#[derive(Debug, Deserialize)]
-struct IpAddrInfo {
-    family: String,
-    local: String,
-    prefixlen: u8,
-    scope: Option<String>,
-    #[serde(default)]
-    deprecated: bool,
-    #[serde(default)]
-    tentative: bool,
-    #[serde(default)]
-    temporary: bool,
-    #[serde(default)]
-    dynamic: bool,
-}
-
-#[derive(Debug, Deserialize)]
-struct IpIface {
-    ifindex: u32,
-    ifname: String,
-    #[serde(default)]
-    flags: Vec<String>,
-    mtu: Option<u32>,
-    operstate: Option<String>,
-    address: Option<String>,
-    #[serde(default)]
-    addr_info: Vec<IpAddrInfo>,
-}
+use forgeffi_protocol::{parse_mac, validate_linux_ifname, NETIF_OPS};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
 
 pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
-    let out = Command::new("ip")
+    let out = command("ip")
         .arg("-j")
+        .arg("-d")
         .arg("address")
-        .output()
+        .output_with_deadline()
         .map_err(|e| ForgeFfiError::unsupported(format!("无法执行 ip 命令（需要 iproute2）: {e}")))?;
 
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
         return Err(ForgeFfiError::system_error(format!(
-            "ip -j address 失败: {stderr}"
+            "ip -j -d address 失败: {stderr}"
         )));
     }
 
-    let ifaces: Vec<IpIface> = serde_json::from_slice(&out.stdout)
-        .map_err(|e| ForgeFfiError::system_error(format!("解析 ip JSON 失败: {e}")))?;
+    let mut ifaces = parse::parse_ip_address_json(&out.stdout)?;
+    tunnel::fill_wireguard(&mut ifaces);
+
+    let mut routes = default_routes_by_dev();
+    let mut dns = dns_servers_by_dev();
+    let netplan = netplan_selected();
+    let can_set_dhcp = nmcli_available() || netplan;
+    Ok(ifaces
+        .into_iter()
+        .map(|mut it| {
+            it.default_routes = routes.remove(&it.name).unwrap_or_default();
+            it.dns_servers = dns.remove(&it.name).unwrap_or_default();
+            it.capabilities.can_set_dhcp = can_set_dhcp;
+            it.capabilities.supported_speeds = supported_speeds(&it.name);
+            if netplan {
+                it.capabilities.notes = Some(format!(
+                    "由 netplan 管理：未被 NetworkManager 接管的网卡持久化写入 {} 后执行 netplan apply",
+                    netplan::NETPLAN_FILE
+                ));
+            }
+            it
+        })
+        .collect())
+}
+
+// 按链路的 DNS 只有 systemd-resolved 与 NetworkManager 知道；都不可用时返回空
+// （/etc/resolv.conf 是全局配置，无法归属到网卡）
+fn dns_servers_by_dev() -> BTreeMap<String, Vec<String>> {
+    if let Ok(o) = command("resolvectl").arg("dns").output_with_deadline()
+        && o.status.success()
+    {
+        return parse_resolvectl_dns(&String::from_utf8_lossy(&o.stdout));
+    }
+    if nmcli_available()
+        && let Ok(o) = command("nmcli")
+            .args(["-t", "-f", "GENERAL.DEVICE,IP4.DNS,IP6.DNS", "device", "show"])
+            .output_with_deadline()
+        && o.status.success()
+    {
+        return parse_nmcli_dns(&String::from_utf8_lossy(&o.stdout));
+    }
+    BTreeMap::new()
+}
+
+// Link 2 (eth0): 192.168.1.1 1.1.1.1#cloudflare-dns.com
+fn parse_resolvectl_dns(text: &str) -> BTreeMap<String, Vec<String>> {
+    let mut out = BTreeMap::new();
+    for line in text.lines() {
+        let Some(rest) = line.trim().strip_prefix("Link ") else {
+            continue;
+        };
+        let (Some(open), Some(close)) = (rest.find('('), rest.find("):")) else {
+            continue;
+        };
+        let Some(dev) = rest.get(open + 1..close) else {
+            continue;
+        };
+        let servers: Vec<String> = rest[close + 2..]
+            .split_whitespace()
+            .map(|s| s.split('#').next().unwrap_or(s).to_string())
+            .collect();
+        if !servers.is_empty() {
+            out.insert(dev.to_string(), servers);
+        }
+    }
+    out
+}
 
-    Ok(ifaces.into_iter().map(map_iface).collect())
+// GENERAL.DEVICE:eth0
+// IP4.DNS[1]:192.168.1.1
+// IP6.DNS[1]:fe80\:\:1
+fn parse_nmcli_dns(text: &str) -> BTreeMap<String, Vec<String>> {
+    let mut out: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut dev = None;
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if key == "GENERAL.DEVICE" {
+            dev = Some(value.to_string());
+        } else if (key.starts_with("IP4.DNS") || key.starts_with("IP6.DNS"))
+            && let Some(dev) = &dev
+            && !value.is_empty()
+        {
+            out.entry(dev.clone())
+                .or_default()
+                .push(value.replace("\\:", ":"));
+        }
+    }
+    out
 }
 
-pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+// 路由查询失败不影响网卡列表；多路径默认路由按 nexthops 拆到各自的网卡上
+fn default_routes_by_dev() -> BTreeMap<String, Vec<DefaultRoute>> {
+    let mut out: BTreeMap<String, Vec<DefaultRoute>> = BTreeMap::new();
+    for (flag, family) in [("-4", IpFamily::Ipv4), ("-6", IpFamily::Ipv6)] {
+        let Ok(o) = command("ip")
+            .args(["-j", flag, "route", "show", "default"])
+            .output_with_deadline()
+        else {
+            continue;
+        };
+        if !o.status.success() {
+            continue;
+        }
+        let Ok(routes) = parse::parse_ip_route_json(&o.stdout, family) else {
+            continue;
+        };
+        for (dev, route) in routes {
+            out.entry(dev).or_default().push(route);
+        }
+    }
+    out
+}
+
+pub(super) fn apply_standalone(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    match op {
+        NetIfOp::CreateVlan {
+            parent,
+            vlan_id,
+            name,
+        } => {
+            let name = name
+                .clone()
+                .unwrap_or_else(|| format!("{parent}.{vlan_id}"));
+            run_checked(
+                "ip",
+                &[
+                    "link",
+                    "add",
+                    "link",
+                    parent.as_str(),
+                    "name",
+                    name.as_str(),
+                    "type",
+                    "vlan",
+                    "id",
+                    &vlan_id.to_string(),
+                ],
+            )
+        }
+        NetIfOp::CreateBridge { name } => run_checked(
+            "ip",
+            &["link", "add", "name", name.as_str(), "type", "bridge"],
+        ),
+        NetIfOp::AddBridgeMember { bridge, member } => run_checked(
+            "ip",
+            &["link", "set", "dev", member.as_str(), "master", bridge.as_str()],
+        ),
+        NetIfOp::CreateDummy { name } => {
+            validate_linux_ifname(name)?;
+            run_checked("ip", &["link", "add", "name", name.as_str(), "type", "dummy"])
+        }
+        NetIfOp::CreateBond { name, mode, members } => {
+            validate_linux_ifname(name)?;
+            run_checked(
+                "ip",
+                &["link", "add", "name", name.as_str(), "type", "bond", "mode", bond_mode_name(*mode)],
+            )?;
+            members.iter().try_for_each(|m| enslave_to_bond(name, m))
+        }
+        NetIfOp::AddBondMember { bond, member } => enslave_to_bond(bond, member),
+        NetIfOp::CreateWireguard {
+            name,
+            private_key,
+            listen_port,
+            peers,
+        } => tunnel::create_wireguard(name, private_key, *listen_port, peers),
+        NetIfOp::CreateIpTunnel {
+            name,
+            kind,
+            remote,
+            local,
+            ttl,
+        } => tunnel::create_ip_tunnel(name, *kind, remote, local.as_deref(), *ttl),
+        NetIfOp::AddRoutingRule { rule } => routing::rule("add", rule),
+        NetIfOp::DelRoutingRule { rule } => routing::rule("del", rule),
+        NetIfOp::SwitchNetworkLocation { .. } | NetIfOp::SetServiceOrder { .. } => Err(
+            ForgeFfiError::unsupported("仅 macOS 支持网络位置/服务顺序".to_string()),
+        ),
+        _ => Err(ForgeFfiError::invalid_argument(
+            "该操作需要 target".to_string(),
+        )),
+    }
+}
+
+pub(super) fn apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
     match op {
         NetIfOp::SetAdminState { up } => {
             let state = if *up { "up" } else { "down" };
@@ -75,21 +234,34 @@ pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), For
                 &mtu.to_string(),
             ],
         ),
-        NetIfOp::AddIp { ip, prefix_len } => {
-            if let Some(conn) = nmcli_connection_for_dev(&target.name)? {
-                let cidr = format!("{ip}/{prefix_len}");
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.method",
-                    "manual",
-                    "+ipv4.addresses",
-                    cidr.as_str(),
-                ])?;
-                nmcli_checked(&["con", "up", "id", conn.as_str()])
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            // NetworkManager 的连接配置只能表达永久地址，带寿命时直接走 ip
+            if let Some((valid, preferred)) = resolve_lifetimes(*valid_lft_secs, *preferred_lft_secs) {
+                require_runtime_scope("NetworkManager 的连接配置不能表达带寿命的地址")?;
+                run_checked(
+                    "ip",
+                    &[
+                        "addr",
+                        "add",
+                        &format!("{ip}/{prefix_len}"),
+                        "dev",
+                        target.name.as_str(),
+                        "valid_lft",
+                        &ip_lifetime_arg(valid),
+                        "preferred_lft",
+                        &ip_lifetime_arg(preferred),
+                    ],
+                )
+            } else if let Some(conn) = nmcli_connection_for_dev(&target.name)? {
+                nmcli_modify_op(&conn, &target.name, op)
             } else {
+                require_runtime_scope(&format!("{} 未由 NetworkManager 管理", target.name))?;
                 run_checked(
                     "ip",
                     &["addr", "add", &format!("{ip}/{prefix_len}"), "dev", target.name.as_str()],
@@ -99,47 +271,25 @@ pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), For
         NetIfOp::DelIp { ip, prefix_len } => {
             if let Some(conn) = nmcli_connection_for_dev(&target.name)? {
                 let cidr = format!("{ip}/{prefix_len}");
-                match nmcli_try(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "-ipv4.addresses",
-                    cidr.as_str(),
-                ]) {
-                    Ok(()) => nmcli_checked(&["con", "up", "id", conn.as_str()]),
+                let args = nmcli_modify_args(&conn, &target.name, &["-ipv4.addresses", cidr.as_str()]);
+                match nmcli_try(&args) {
+                    Ok(()) => nmcli_activate(&conn),
                     Err(e) => {
                         if e.contains("ipv4.addresses") && e.contains("不允许") {
-                            nmcli_checked(&[
-                                "con",
-                                "mod",
-                                "id",
-                                conn.as_str(),
-                                "ipv4.method",
-                                "auto",
-                                "ipv4.addresses",
-                                "",
-                                "ipv4.gateway",
-                                "",
-                            ])?;
-                            nmcli_checked(&["con", "up", "id", conn.as_str()])
+                            nmcli_modify(
+                                &conn,
+                                &target.name,
+                                &["ipv4.method", "auto", "ipv4.addresses", "", "ipv4.gateway", ""],
+                            )
                         } else {
                             Err(ForgeFfiError::system_error(format!(
-                                "nmcli 命令失败: nmcli {:?}: {}",
-                                [
-                                    "con",
-                                    "mod",
-                                    "id",
-                                    conn.as_str(),
-                                    "-ipv4.addresses",
-                                    cidr.as_str(),
-                                ],
-                                e
+                                "nmcli 命令失败: nmcli {args:?}: {e}"
                             )))
                         }
                     }
                 }
             } else {
+                require_runtime_scope(&format!("{} 未由 NetworkManager 管理", target.name))?;
                 run_checked(
                     "ip",
                     &["addr", "del", &format!("{ip}/{prefix_len}"), "dev", target.name.as_str()],
@@ -148,63 +298,34 @@ pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), For
         }
         NetIfOp::SetIpv4Dhcp { enable } => {
             let Some(conn) = nmcli_connection_for_dev(&target.name)? else {
-                return Err(ForgeFfiError::unsupported(
-                    "未检测到 NetworkManager（nmcli），无法通过本接口切换 DHCP；请使用系统网络管理工具".to_string(),
-                ));
+                if !netplan_selected() {
+                    return Err(ForgeFfiError::unsupported(
+                        "未检测到 NetworkManager（nmcli）或 netplan，无法通过本接口切换 DHCP；请使用系统网络管理工具"
+                            .to_string(),
+                    ));
+                }
+                if op_scope() == Some(NetIfScope::Runtime) {
+                    return Err(ForgeFfiError::unsupported(
+                        "netplan 只能通过配置文件切换 DHCP，不支持 scope=runtime".to_string(),
+                    ));
+                }
+                return netplan_set_dhcp(&target.name, *enable);
             };
 
             if *enable {
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.method",
-                    "auto",
-                ])?;
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.addresses",
-                    "",
-                ])?;
-                nmcli_checked(&["con", "up", "id", conn.as_str()])
+                nmcli_modify_op(&conn, &target.name, op)
             } else {
                 let addr = current_ipv4_cidr_for_dev(&target.name)?.ok_or_else(|| {
                     ForgeFfiError::invalid_argument(
                         "切换为手动前需要先有一个 IPv4 地址（当前未检测到）".to_string(),
                     )
                 })?;
-
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.method",
-                    "manual",
-                ])?;
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.addresses",
-                    addr.as_str(),
-                ])?;
-                if let Some(gw) = current_ipv4_gateway_for_dev(&target.name)? {
-                    nmcli_checked(&[
-                        "con",
-                        "mod",
-                        "id",
-                        conn.as_str(),
-                        "ipv4.gateway",
-                        gw.as_str(),
-                    ])?;
+                let gw = current_ipv4_gateway_for_dev(&target.name)?;
+                let mut props = vec!["ipv4.method", "manual", "ipv4.addresses", addr.as_str()];
+                if let Some(gw) = &gw {
+                    props.extend(["ipv4.gateway", gw.as_str()]);
                 }
-                nmcli_checked(&["con", "up", "id", conn.as_str()])
+                nmcli_modify(&conn, &target.name, &props)
             }
         }
         NetIfOp::SetIpv4Static {
@@ -216,28 +337,258 @@ pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), For
             let gw = gateway.as_deref();
 
             if let Some(conn) = nmcli_connection_for_dev(&target.name)? {
-                nmcli_checked(&[
-                    "con",
-                    "mod",
-                    "id",
-                    conn.as_str(),
-                    "ipv4.method",
-                    "manual",
-                    "ipv4.addresses",
-                    cidr.as_str(),
-                    "ipv4.gateway",
-                    gw.unwrap_or(""),
-                ])?;
-                nmcli_checked(&["con", "up", "id", conn.as_str()])
+                nmcli_modify_op(&conn, &target.name, op)
             } else {
-                apply_runtime_static_ipv4(&target.name, cidr.as_str(), gw)?;
-                persist_systemd_networkd_static_ipv4(&target.name, cidr.as_str(), gw)?;
+                if op_scope() != Some(NetIfScope::Persistent) {
+                    apply_runtime_static_ipv4(&target.name, cidr.as_str(), gw)?;
+                }
+                if op_scope() == Some(NetIfScope::Runtime) {
+                    return Ok(());
+                }
+                persist_static_ipv4(&target.name, cidr.as_str(), gw)
+            }
+        }
+        NetIfOp::SetIpv6Mode { mode } => {
+            let Some(conn) = nmcli_connection_for_dev(&target.name)? else {
+                require_runtime_scope(&format!("{} 未由 NetworkManager 管理", target.name))?;
+                return match mode {
+                    Ipv6Mode::Slaac => set_ipv6_autoconf(&target.name, true),
+                    Ipv6Mode::Static => set_ipv6_autoconf(&target.name, false),
+                    Ipv6Mode::Dhcp6 => Err(ForgeFfiError::unsupported(
+                        "未检测到 NetworkManager（nmcli），无法启用 DHCPv6；请使用系统 DHCPv6 客户端".to_string(),
+                    )),
+                };
+            };
+
+            let (method, addresses) = match mode {
+                Ipv6Mode::Slaac => ("auto", String::new()),
+                Ipv6Mode::Dhcp6 => ("dhcp", String::new()),
+                Ipv6Mode::Static => {
+                    let addr = current_ipv6_cidr_for_dev(&target.name)?.ok_or_else(|| {
+                        ForgeFfiError::invalid_argument(
+                            "切换为手动前需要先有一个全局 IPv6 地址（当前未检测到）".to_string(),
+                        )
+                    })?;
+                    ("manual", addr)
+                }
+            };
+            nmcli_modify(&conn, &target.name, &["ipv6.method", method, "ipv6.addresses", addresses.as_str()])
+        }
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => {
+            let cidr = format!("{ip}/{prefix_len}");
+            let gw = gateway.as_deref().filter(|s| !s.is_empty());
+            if let Some(conn) = nmcli_connection_for_dev(&target.name)? {
+                nmcli_modify_op(&conn, &target.name, op)
+            } else {
+                require_runtime_scope(&format!("{} 未由 NetworkManager 管理", target.name))?;
+                let dev = target.name.as_str();
+                set_ipv6_autoconf(dev, false)?;
+                run_checked("ip", &["-6", "addr", "flush", "dev", dev, "scope", "global"])?;
+                run_checked("ip", &["-6", "addr", "add", cidr.as_str(), "dev", dev])?;
+                if let Some(gw) = gw {
+                    run_checked("ip", &["-6", "route", "replace", "default", "via", gw, "dev", dev])?;
+                }
                 Ok(())
             }
         }
+        NetIfOp::CreateVlan { .. } => apply_standalone(op),
+        NetIfOp::DeleteVlan => run_checked(
+            "ip",
+            &["link", "delete", "dev", target.name.as_str(), "type", "vlan"],
+        ),
+        NetIfOp::CreateBridge { .. }
+        | NetIfOp::AddBridgeMember { .. }
+        | NetIfOp::CreateDummy { .. }
+        | NetIfOp::CreateBond { .. }
+        | NetIfOp::AddBondMember { .. }
+        | NetIfOp::CreateWireguard { .. }
+        | NetIfOp::CreateIpTunnel { .. }
+        | NetIfOp::AddRoutingRule { .. }
+        | NetIfOp::DelRoutingRule { .. }
+        | NetIfOp::SwitchNetworkLocation { .. }
+        | NetIfOp::SetServiceOrder { .. } => apply_standalone(op),
+        NetIfOp::DeleteBridge => run_checked(
+            "ip",
+            &["link", "delete", "dev", target.name.as_str(), "type", "bridge"],
+        ),
+        NetIfOp::RemoveBridgeMember => run_checked(
+            "ip",
+            &["link", "set", "dev", target.name.as_str(), "nomaster"],
+        ),
+        NetIfOp::DeleteInterface => run_checked("ip", &["link", "delete", "dev", target.name.as_str()]),
+        NetIfOp::DeleteBond => run_checked(
+            "ip",
+            &["link", "delete", "dev", target.name.as_str(), "type", "bond"],
+        ),
+        NetIfOp::RemoveBondMember => run_checked(
+            "ip",
+            &["link", "set", "dev", target.name.as_str(), "nomaster"],
+        ),
+        NetIfOp::SetWireguardPeer { peer } => tunnel::set_wireguard_peer(&target.name, peer),
+        NetIfOp::RemoveWireguardPeer { public_key } => tunnel::remove_wireguard_peer(&target.name, public_key),
+        NetIfOp::DeleteTunnel => tunnel::delete_tunnel(target),
+        NetIfOp::AddRoute { route } => routing::route("add", &target.name, route),
+        NetIfOp::DelRoute { route } => routing::route("del", &target.name, route),
+        NetIfOp::SetDnsSuffix { suffix } => {
+            run_checked("resolvectl", &["domain", target.name.as_str(), suffix.as_str()])
+        }
+        NetIfOp::SetDnsRegistration { .. } => Err(ForgeFfiError::unsupported(
+            "Linux 下没有按网卡的动态 DNS 注册开关（由 DHCP 客户端/nsupdate 负责）".to_string(),
+        )),
+        NetIfOp::SetName { new_name } => rename_link(&target.name, new_name, steps),
+        NetIfOp::SetDescription { text } => run_checked(
+            "ip",
+            &["link", "set", "dev", target.name.as_str(), "alias", text.as_str()],
+        ),
+        NetIfOp::SetMac { mac } => set_link_address(&target.name, mac, steps),
+        NetIfOp::SetLinkSpeed { speed_mbps, duplex } => {
+            let dev = target.name.as_str();
+            match speed_mbps {
+                None => run_checked("ethtool", &["-s", dev, "autoneg", "on"]),
+                Some(speed) => {
+                    let duplex = match duplex.unwrap_or(Duplex::Full) {
+                        Duplex::Half => "half",
+                        Duplex::Full => "full",
+                    };
+                    run_checked(
+                        "ethtool",
+                        &["-s", dev, "speed", &speed.to_string(), "duplex", duplex, "autoneg", "off"],
+                    )
+                }
+            }
+        }
+        NetIfOp::SetBandwidthLimit {
+            egress_mbps,
+            ingress_mbps,
+        } => qos::set_bandwidth_limit(&target.name, *egress_mbps, *ingress_mbps),
     }
 }
 
+pub(super) fn qos(ifaces: &[NetInterface]) -> Result<Vec<NetIfQos>, ForgeFfiError> {
+    qos::qos(ifaces)
+}
+
+// 只查询有底层设备的网卡（虚拟网卡没有 PHY）；没有 ethtool 时返回空列表
+fn supported_speeds(dev: &str) -> Vec<LinkSpeedMode> {
+    if !Path::new("/sys/class/net").join(dev).join("device").exists() {
+        return Vec::new();
+    }
+    let Ok(out) = command("ethtool").arg(dev).output_with_deadline() else {
+        return Vec::new();
+    };
+    if !out.status.success() {
+        return Vec::new();
+    }
+    parse_ethtool_link_modes(&String::from_utf8_lossy(&out.stdout))
+}
+
+// Supported link modes:   10baseT/Half 10baseT/Full
+//                         1000baseT/Full
+fn parse_ethtool_link_modes(text: &str) -> Vec<LinkSpeedMode> {
+    let mut out = Vec::new();
+    let mut in_modes = false;
+    for line in text.lines() {
+        let line = line.trim();
+        let tokens = if let Some(rest) = line.strip_prefix("Supported link modes:") {
+            in_modes = true;
+            rest
+        } else if in_modes && !line.contains(':') {
+            line
+        } else {
+            in_modes = false;
+            continue;
+        };
+        for tok in tokens.split_whitespace() {
+            let Some((mode, duplex)) = tok.split_once('/') else {
+                continue;
+            };
+            let digits: String = mode.chars().take_while(char::is_ascii_digit).collect();
+            let Ok(speed_mbps) = digits.parse::<u32>() else {
+                continue;
+            };
+            let duplex = match duplex {
+                "Half" => Duplex::Half,
+                "Full" => Duplex::Full,
+                _ => continue,
+            };
+            out.push(LinkSpeedMode { speed_mbps, duplex });
+        }
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+// 多数驱动允许在 up 状态下直接改；返回 EBUSY 的驱动需要先 down
+fn set_link_address(
+    dev: &str,
+    mac: &str,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    let m = parse_mac(mac)?;
+    let addr = format!(
+        "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        m[0], m[1], m[2], m[3], m[4], m[5]
+    );
+    match run_checked("ip", &["link", "set", "dev", dev, "address", addr.as_str()]) {
+        Err(e) if e.code == ErrorCode::Busy && link_is_up(dev) => {
+            run_step(steps, "link_down", &["link", "set", "dev", dev, "down"])?;
+            let r = run_step(steps, "set_address", &["link", "set", "dev", dev, "address", addr.as_str()]);
+            run_step(steps, "link_up", &["link", "set", "dev", dev, "up"])?;
+            r
+        }
+        r => r,
+    }
+}
+
+// 内核要求接口处于 down 状态才能改名；原来是 up 的改名后（或改名失败后）恢复 up
+fn rename_link(
+    dev: &str,
+    new_name: &str,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    validate_linux_ifname(new_name)?;
+    // 提前拒绝重名，避免无谓地把接口 down/up 一次
+    if new_name != dev && Path::new("/sys/class/net").join(new_name).exists() {
+        return Err(ForgeFfiError::invalid_argument(format!("接口名已存在: {new_name}")));
+    }
+    let was_up = link_is_up(dev);
+    if was_up {
+        run_step(steps, "link_down", &["link", "set", "dev", dev, "down"])?;
+    }
+    let r = run_step(steps, "rename", &["link", "set", "dev", dev, "name", new_name]);
+    if was_up {
+        let current = if r.is_ok() { new_name } else { dev };
+        run_step(steps, "link_up", &["link", "set", "dev", current, "up"])?;
+    }
+    r
+}
+
+fn link_is_up(dev: &str) -> bool {
+    std::fs::read_to_string(format!("/sys/class/net/{dev}/flags"))
+        .ok()
+        .and_then(|s| u32::from_str_radix(s.trim().trim_start_matches("0x"), 16).ok())
+        .is_some_and(|f| f & 0x1 != 0)
+}
+
+fn run_step(
+    steps: &mut Vec<NetIfStepResult>,
+    step: &str,
+    args: &[&str],
+) -> Result<(), ForgeFfiError> {
+    let r = run_checked("ip", args);
+    steps.push(NetIfStepResult {
+        step: step.to_string(),
+        ok: r.is_ok(),
+        error: r.as_ref().err().cloned(),
+    });
+    r
+}
+
 fn apply_runtime_static_ipv4(dev: &str, cidr: &str, gateway: Option<&str>) -> Result<(), ForgeFfiError> {
     run_checked("ip", &["addr", "flush", "dev", dev, "scope", "global"])?;
     run_checked("ip", &["addr", "add", cidr, "dev", dev])?;
@@ -249,6 +600,52 @@ fn apply_runtime_static_ipv4(dev: &str, cidr: &str, gateway: Option<&str>) -> Re
     Ok(())
 }
 
+const NETWORKD_UNIT_SCHEMA: PersistSchema = PersistSchema {
+    kind: "networkd_unit",
+    version: 1,
+    migrations: &[],
+};
+
+// 未被 NetworkManager 管理的网卡：netplan 管理的系统写 netplan，否则写 systemd-networkd 单元
+fn persist_static_ipv4(dev: &str, cidr: &str, gateway: Option<&str>) -> Result<(), ForgeFfiError> {
+    if backend::pinned() == Some(NetIfBackend::Nmcli) {
+        return Err(ForgeFfiError::unsupported(
+            "已固定使用 nmcli 后端，但 NetworkManager 不可用或该网卡未被其管理；已通过 ip 命令临时生效".to_string(),
+        ));
+    }
+    if netplan_selected() {
+        return netplan::persist(
+            dev,
+            netplan::Ethernet {
+                dhcp4: false,
+                addresses: vec![cidr.to_string()],
+                gateway4: gateway.filter(|s| !s.is_empty()).map(str::to_string),
+            },
+        );
+    }
+    persist_systemd_networkd_static_ipv4(dev, cidr, gateway)
+}
+
+// netplan apply 同时完成即时生效；关闭 DHCP 时沿用当前地址与网关，与 nmcli 路径一致
+fn netplan_set_dhcp(dev: &str, enable: bool) -> Result<(), ForgeFfiError> {
+    let entry = if enable {
+        netplan::Ethernet {
+            dhcp4: true,
+            ..Default::default()
+        }
+    } else {
+        let addr = current_ipv4_cidr_for_dev(dev)?.ok_or_else(|| {
+            ForgeFfiError::invalid_argument("切换为手动前需要先有一个 IPv4 地址（当前未检测到）".to_string())
+        })?;
+        netplan::Ethernet {
+            dhcp4: false,
+            addresses: vec![addr],
+            gateway4: current_ipv4_gateway_for_dev(dev)?,
+        }
+    };
+    netplan::persist(dev, entry)
+}
+
 fn persist_systemd_networkd_static_ipv4(
     dev: &str,
     cidr: &str,
@@ -263,67 +660,134 @@ fn persist_systemd_networkd_static_ipv4(
 
     let file_name = format!("99-forgeffi-{dev}.network");
     let path = dir.join(file_name);
+    // 旧版本写入的文件没有 header（version 0），直接按当前格式重写即可；更新版本写入的文件拒绝覆盖
+    match std::fs::read_to_string(&path) {
+        Ok(existing) => {
+            NETWORKD_UNIT_SCHEMA.check_text(&existing, "#")?;
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(map_io_error(e)),
+    }
     let gw_line = gateway
         .filter(|s| !s.is_empty())
         .map(|s| format!("Gateway={s}\n"))
         .unwrap_or_default();
 
     let content = format!(
-        "[Match]\nName={dev}\n\n[Network]\nDHCP=no\nAddress={cidr}\n{gw_line}",
+        "{}[Match]\nName={dev}\n\n[Network]\nDHCP=no\nAddress={cidr}\n{gw_line}",
+        NETWORKD_UNIT_SCHEMA.text_header("#"),
     );
 
     write_atomic(&path, content.as_bytes()).map_err(map_io_error)
 }
 
-fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
-    let parent = path.parent().unwrap_or_else(|| Path::new("/"));
-    let tmp = parent.join(format!(
-        ".{}.tmp.{}",
-        path.file_name().and_then(|s| s.to_str()).unwrap_or("forgeffi"),
-        std::process::id()
-    ));
-    fs::write(&tmp, content)?;
-    fs::rename(&tmp, path)?;
-    Ok(())
+// iproute2 负责即时生效；持久化优先 NetworkManager，其次 netplan，最后 systemd-networkd
+// 恒定返回 Unsupported 的 op 不计入
+pub(super) fn supported_ops() -> Vec<&'static str> {
+    const UNSUPPORTED: &[&str] = &["switch_network_location", "set_service_order", "set_dns_registration"];
+    NETIF_OPS.iter().copied().filter(|op| !UNSUPPORTED.contains(op)).collect()
 }
 
-fn map_io_error(e: io::Error) -> ForgeFfiError {
-    if e.kind() == io::ErrorKind::PermissionDenied {
-        ForgeFfiError::permission_denied(e.to_string())
-    } else {
-        ForgeFfiError::system_error(e.to_string())
+// 地址类 op 经 NetworkManager 可任选范围（runtime 为 nmcli device modify），未被其管理时按具体路径判定；
+// 其余 op 只有 ip 命令，即时生效、重启后丢失
+pub(super) fn op_scopes(op: &str) -> &'static [NetIfScope] {
+    match op {
+        "add_ip" | "del_ip" | "set_ipv4_dhcp" | "set_ipv4_static" | "set_ipv6_mode" | "set_ipv6_static" => {
+            &NetIfScope::ALL
+        }
+        _ => &[NetIfScope::Runtime],
+    }
+}
+
+pub(super) const BACKENDS: &[(NetIfBackend, NetIfBackendRole, bool)] = &[
+    (NetIfBackend::Iproute2, NetIfBackendRole::Runtime, true),
+    (NetIfBackend::Nmcli, NetIfBackendRole::Persist, true),
+    (NetIfBackend::Netplan, NetIfBackendRole::Persist, true),
+    (NetIfBackend::Networkd, NetIfBackendRole::Persist, true),
+];
+
+pub(super) fn backend_available(backend: NetIfBackend) -> bool {
+    match backend {
+        NetIfBackend::Iproute2 => command("ip")
+            .arg("-V")
+            .output_with_deadline()
+            .is_ok_and(|o| o.status.success()),
+        NetIfBackend::Nmcli => nmcli_detected(),
+        NetIfBackend::Netplan => netplan::detected(),
+        NetIfBackend::Networkd => Path::new("/etc/systemd/network").is_dir(),
+        _ => false,
+    }
+}
+
+pub(super) fn backend_notes(backend: NetIfBackend) -> Option<String> {
+    match backend {
+        NetIfBackend::Netplan if netplan::detected() => Some(format!(
+            "写入 {} 后执行 netplan apply",
+            netplan::NETPLAN_FILE
+        )),
+        NetIfBackend::Networkd if netplan::detected() => {
+            Some("系统由 netplan 管理，直接写入的 .network 文件会与 netplan 渲染的配置冲突".to_string())
+        }
+        _ => None,
     }
 }
 
+// 固定为 nmcli/networkd 时不使用 netplan；未固定持久化后端时按检测结果
+fn netplan_selected() -> bool {
+    match backend::pinned() {
+        Some(NetIfBackend::Netplan) => true,
+        Some(NetIfBackend::Nmcli | NetIfBackend::Networkd) => false,
+        _ => netplan::detected(),
+    }
+}
+
+static NMCLI_AVAILABLE: Mutex<Option<bool>> = Mutex::new(None);
+
+// 固定为 networkd/netplan 时视同没有 NetworkManager
 fn nmcli_available() -> bool {
-    static CACHED: OnceLock<bool> = OnceLock::new();
-    *CACHED.get_or_init(|| {
-        Command::new("nmcli")
+    !matches!(backend::pinned(), Some(NetIfBackend::Networkd | NetIfBackend::Netplan)) && nmcli_detected()
+}
+
+fn nmcli_detected() -> bool {
+    let mut g = match NMCLI_AVAILABLE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    *g.get_or_insert_with(|| {
+        command("nmcli")
             .arg("-v")
-            .output()
+            .output_with_deadline()
             .is_ok_and(|o| o.status.success())
     })
 }
 
+pub(super) fn clear_caches() {
+    if let Ok(mut g) = NMCLI_AVAILABLE.lock() {
+        *g = None;
+    }
+    netplan::clear_cache();
+}
+
 fn nmcli_checked(args: &[&str]) -> Result<(), ForgeFfiError> {
-    let out = Command::new("nmcli")
+    let out = command("nmcli")
         .args(args)
-        .output()
-        .map_err(|e| ForgeFfiError::system_error(format!("执行 nmcli 失败: {e}")))?;
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error("nmcli", e))?;
     if out.status.success() {
         Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&out.stderr);
-        Err(ForgeFfiError::system_error(format!(
-            "nmcli 命令失败: nmcli {:?}: {}",
+        Err(command_failed(
+            "nmcli",
             args,
-            stderr.trim()
-        )))
+            &stderr,
+            format!("nmcli 命令失败: nmcli {:?}: {}", args, stderr.trim()),
+        ))
     }
 }
 
 fn nmcli_try(args: &[&str]) -> Result<(), String> {
-    let out = Command::new("nmcli").args(args).output();
+    let out = command("nmcli").args(args).output_with_deadline();
     let Ok(out) = out else {
         return Err("执行 nmcli 失败".to_string());
     };
@@ -334,14 +798,122 @@ fn nmcli_try(args: &[&str]) -> Result<(), String> {
     }
 }
 
+// 按当前 op 的 scope 修改连接属性：runtime 用 device modify 只改设备上已激活的配置，不写连接文件；
+// persistent 只改连接配置、不重新激活；默认与 both 改配置后重新激活
+fn nmcli_modify(conn: &str, dev: &str, props: &[&str]) -> Result<(), ForgeFfiError> {
+    nmcli_checked(&nmcli_modify_args(conn, dev, props))?;
+    nmcli_activate(conn)
+}
+
+// 只需修改连接属性、无需读取当前状态的 op 对应的属性；apply_one 与合并执行共用
+fn nmcli_props(op: &NetIfOp) -> Option<Vec<String>> {
+    let props = match op {
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs: None,
+            preferred_lft_secs: None,
+            ..
+        } => vec!["ipv4.method".into(), "manual".into(), "+ipv4.addresses".into(), format!("{ip}/{prefix_len}")],
+        NetIfOp::SetIpv4Dhcp { enable: true } => {
+            vec!["ipv4.method".into(), "auto".into(), "ipv4.addresses".into(), String::new()]
+        }
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => vec![
+            "ipv4.method".into(),
+            "manual".into(),
+            "ipv4.addresses".into(),
+            format!("{ip}/{prefix_len}"),
+            "ipv4.gateway".into(),
+            gateway.clone().unwrap_or_default(),
+        ],
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => vec![
+            "ipv6.method".into(),
+            "manual".into(),
+            "ipv6.addresses".into(),
+            format!("{ip}/{prefix_len}"),
+            "ipv6.gateway".into(),
+            gateway.clone().unwrap_or_default(),
+        ],
+        _ => return None,
+    };
+    Some(props)
+}
+
+fn nmcli_modify_op(conn: &str, dev: &str, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    let props = nmcli_props(op).unwrap_or_default();
+    let props: Vec<&str> = props.iter().map(String::as_str).collect();
+    nmcli_modify(conn, dev, &props)
+}
+
+// 由 NetworkManager 管理时，相邻的地址类 op 合并成一次 con mod + 一次 con up：重新激活连接往往要数秒，
+// 逐个 op 激活既慢又会让链路反复抖动
+pub(super) fn batch_len(_target: &ResolvedTarget, ops: &[&NetIfOp]) -> usize {
+    if !nmcli_available() {
+        return 0;
+    }
+    ops.iter().take_while(|op| nmcli_props(op).is_some()).count()
+}
+
+// 合并的 con mod 失败时逐个重做以定位是哪个 op 的属性非法；激活失败则所有 op 都未生效
+pub(super) fn apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    let one_by_one = || ops.iter().map(|op| apply_one(target, op, &mut Vec::new())).collect();
+    let conn = match nmcli_connection_for_dev(&target.name) {
+        Ok(Some(conn)) => conn,
+        Ok(None) => return one_by_one(),
+        Err(e) => return vec![Err(e); ops.len()],
+    };
+    let props: Vec<String> = ops.iter().filter_map(|op| nmcli_props(op)).flatten().collect();
+    let props: Vec<&str> = props.iter().map(String::as_str).collect();
+    if nmcli_checked(&nmcli_modify_args(&conn, &target.name, &props)).is_err() {
+        return one_by_one();
+    }
+    let r = nmcli_activate(&conn);
+    vec![r; ops.len()]
+}
+
+fn nmcli_modify_args<'a>(conn: &'a str, dev: &'a str, props: &[&'a str]) -> Vec<&'a str> {
+    let mut args = match op_scope() {
+        Some(NetIfScope::Runtime) => vec!["device", "modify", dev],
+        _ => vec!["con", "mod", "id", conn],
+    };
+    args.extend_from_slice(props);
+    args
+}
+
+fn nmcli_activate(conn: &str) -> Result<(), ForgeFfiError> {
+    match op_scope() {
+        Some(NetIfScope::Runtime | NetIfScope::Persistent) => Ok(()),
+        _ => nmcli_checked(&["con", "up", "id", conn]),
+    }
+}
+
+// 只能用 ip/sysctl 改运行时状态的路径：显式要求持久化时拒绝，不静默降级
+fn require_runtime_scope(reason: &str) -> Result<(), ForgeFfiError> {
+    match op_scope() {
+        Some(scope @ (NetIfScope::Persistent | NetIfScope::Both)) => Err(ForgeFfiError::unsupported(format!(
+            "{reason}，只能修改运行时状态，不支持 scope={}",
+            scope.as_str()
+        ))),
+        _ => Ok(()),
+    }
+}
+
 fn nmcli_connection_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError> {
     if !nmcli_available() {
         return Ok(None);
     }
 
-    let out = Command::new("nmcli")
+    let out = command("nmcli")
         .args(["-t", "-f", "GENERAL.CONNECTION", "dev", "show", dev])
-        .output()
+        .output_with_deadline()
         .map_err(|e| ForgeFfiError::system_error(format!("执行 nmcli 失败: {e}")))?;
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
@@ -352,11 +924,7 @@ fn nmcli_connection_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError>
     }
     let text = String::from_utf8_lossy(&out.stdout);
     let line = text.lines().next().unwrap_or("").trim();
-    let v = line
-        .splitn(2, ':')
-        .nth(1)
-        .unwrap_or("")
-        .trim();
+    let v = line.split_once(':').map(|x| x.1).unwrap_or("").trim();
     if v.is_empty() || v == "--" {
         Ok(None)
     } else {
@@ -364,10 +932,32 @@ fn nmcli_connection_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError>
     }
 }
 
+fn current_ipv6_cidr_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError> {
+    Ok(list_interfaces()?
+        .into_iter()
+        .find(|it| it.name == dev)
+        .and_then(|it| {
+            it.ipv6.into_iter().find(|a| {
+                a.scope == Some(IpScope::Global)
+                    && a.flags.is_none_or(|f| f.0 & IpAddrFlags::TEMPORARY == 0)
+            })
+        })
+        .map(|a| format!("{}/{}", a.ip, a.prefix_len)))
+}
+
+fn set_ipv6_autoconf(dev: &str, enable: bool) -> Result<(), ForgeFfiError> {
+    let v = if enable { "1" } else { "0" };
+    let dir = Path::new("/proc/sys/net/ipv6/conf").join(dev);
+    for key in ["accept_ra", "autoconf"] {
+        std::fs::write(dir.join(key), v).map_err(map_io_error)?;
+    }
+    Ok(())
+}
+
 fn current_ipv4_cidr_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError> {
-    let out = Command::new("ip")
+    let out = command("ip")
         .args(["-j", "address", "show", "dev", dev])
-        .output()
+        .output_with_deadline()
         .map_err(|e| ForgeFfiError::system_error(format!("执行 ip 命令失败: {e}")))?;
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
@@ -411,9 +1001,9 @@ fn current_ipv4_cidr_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError>
 }
 
 fn current_ipv4_gateway_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiError> {
-    let out = Command::new("ip")
+    let out = command("ip")
         .args(["-j", "route", "show", "default", "dev", dev])
-        .output()
+        .output_with_deadline()
         .map_err(|e| ForgeFfiError::system_error(format!("执行 ip 命令失败: {e}")))?;
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
@@ -437,126 +1027,46 @@ fn current_ipv4_gateway_for_dev(dev: &str) -> Result<Option<String>, ForgeFfiErr
     Ok(None)
 }
 
-fn run_checked(program: &str, args: &[&str]) -> Result<(), ForgeFfiError> {
-    let out = Command::new(program)
-        .args(args)
-        .output()
-        .map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}")))?;
-    if out.status.success() {
-        Ok(())
+fn ip_lifetime_arg(secs: u32) -> String {
+    if secs == LFT_FOREVER {
+        "forever".to_string()
     } else {
-        let stderr = String::from_utf8_lossy(&out.stderr);
-        Err(ForgeFfiError::system_error(format!(
-            "命令失败: {program} {:?}: {stderr}",
-            args
-        )))
-    }
-}
-
-fn map_iface(i: IpIface) -> NetInterface {
-    let mut flags = 0u32;
-    for f in &i.flags {
-        match f.as_str() {
-            "UP" => flags |= IfaceFlags::UP,
-            "LOWER_UP" => flags |= IfaceFlags::RUNNING,
-            "RUNNING" => flags |= IfaceFlags::RUNNING,
-            "LOOPBACK" => flags |= IfaceFlags::LOOPBACK,
-            "BROADCAST" => flags |= IfaceFlags::BROADCAST,
-            "MULTICAST" => flags |= IfaceFlags::MULTICAST,
-            "POINTOPOINT" => flags |= IfaceFlags::POINT_TO_POINT,
-            _ => {}
-        }
+        secs.to_string()
     }
+}
 
-    let admin_state = if (flags & IfaceFlags::UP) != 0 {
-        AdminState::Up
-    } else {
-        AdminState::Down
-    };
-
-    let oper_state = i.operstate.as_deref().map(map_oper_state);
-
-    let (mut ipv4, mut ipv6) = (Vec::new(), Vec::new());
-    for a in i.addr_info {
-        let scope = a.scope.as_deref().map(map_scope);
-        let mut addr_flags = 0u32;
-        if a.temporary {
-            addr_flags |= IpAddrFlags::TEMPORARY;
-        }
-        if a.deprecated {
-            addr_flags |= IpAddrFlags::DEPRECATED;
-        }
-        if a.tentative {
-            addr_flags |= IpAddrFlags::TENTATIVE;
-        }
-
-        let origin = if a.dynamic { Some(IpOrigin::Dhcp) } else { None };
-
-        let ent = IpAddrEntry {
-            ip: a.local,
-            prefix_len: a.prefixlen,
-            scope,
-            origin,
-            flags: if addr_flags == 0 { None } else { Some(IpAddrFlags(addr_flags)) },
-        };
-        if a.family == "inet" {
-            ipv4.push(ent);
-        } else if a.family == "inet6" {
-            ipv6.push(ent);
-        }
+fn bond_mode_name(mode: BondMode) -> &'static str {
+    match mode {
+        BondMode::BalanceRr => "balance-rr",
+        BondMode::ActiveBackup => "active-backup",
+        BondMode::BalanceXor => "balance-xor",
+        BondMode::Broadcast => "broadcast",
+        BondMode::Lacp => "802.3ad",
+        BondMode::BalanceTlb => "balance-tlb",
+        BondMode::BalanceAlb => "balance-alb",
     }
+}
 
-    let kind = if i.ifname == "lo" || i.ifname.starts_with("lo") {
-        IfaceKind::Loopback
-    } else if i.ifname.starts_with("tun") {
-        IfaceKind::Tunnel
-    } else if i.ifname.starts_with("tap") {
-        IfaceKind::Virtual
-    } else {
-        IfaceKind::Unknown
-    };
+// bonding 驱动拒绝 up 状态的网卡加入；加入后由 bond 负责拉起成员
+fn enslave_to_bond(bond: &str, member: &str) -> Result<(), ForgeFfiError> {
+    run_checked("ip", &["link", "set", "dev", member, "down"])?;
+    run_checked("ip", &["link", "set", "dev", member, "master", bond])
+}
 
-    NetInterface {
-        if_index: i.ifindex,
-        name: i.ifname,
-        display_name: None,
-        kind,
-        is_physical: None,
-        admin_state,
-        oper_state,
-        flags: IfaceFlags(flags),
-        mac: i.address,
-        mtu: i.mtu,
-        speed_bps: None,
-        ipv4,
-        ipv6,
-        capabilities: NetIfCapabilities {
-            can_set_admin_state: true,
-            can_set_mtu: true,
-            can_add_del_ip: true,
-            can_set_dhcp: nmcli_available(),
-            can_set_dns: false,
-            notes: None,
-        },
-    }
-}
-
-fn map_oper_state(s: &str) -> OperState {
-    match s {
-        "UP" => OperState::Up,
-        "DOWN" => OperState::Down,
-        "DORMANT" => OperState::Dormant,
-        "LOWERLAYERDOWN" => OperState::LowerLayerDown,
-        _ => OperState::Unknown,
-    }
-}
-
-fn map_scope(s: &str) -> IpScope {
-    match s {
-        "host" => IpScope::Host,
-        "link" => IpScope::Link,
-        "global" => IpScope::Global,
-        "site" => IpScope::Site,
-        _ => IpScope::Unknown,
+pub(super) fn run_checked(program: &str, args: &[&str]) -> Result<(), ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(program, e))?;
+    if out.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        Err(command_failed(
+            program,
+            args,
+            &stderr,
+            format!("命令失败: {program} {:?}: {stderr}", args),
+        ))
     }
 }
@@ -1,14 +1,16 @@
 use super::*;
 
 use forgeffi_base::{
-    AdminState, IfaceFlags, IfaceKind, IpAddrEntry, NetIfCapabilities, OperState,
+    DefaultRoute, Duplex, IfaceKind, IpFamily, Ipv6Mode, LinkSpeedMode, NetIfBackend, NetIfBackendRole, NetIfQos,
+    NetworkService,
 };
-use std::process::Command;
+use crate::util::{command, command_failed, map_spawn_error, CommandExt};
+use forgeffi_protocol::{parse_mac, NETIF_OPS};
 
 pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
-    let out = Command::new("ifconfig")
-        .arg("-a")
-        .output()
+    let out = command("ifconfig")
+        .args(["-a", "-L", "-m"])
+        .output_with_deadline()
         .map_err(|e| ForgeFfiError::unsupported(format!("无法执行 ifconfig: {e}")))?;
     if !out.status.success() {
         let stderr = String::from_utf8_lossy(&out.stderr);
@@ -17,10 +19,184 @@ pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
         )));
     }
     let text = String::from_utf8_lossy(&out.stdout);
-    Ok(parse_ifconfig(&text))
+    let mut items = parse::parse_ifconfig(&text);
+    fill_link_info(&mut items);
+    for (family, flag) in [(IpFamily::Ipv4, "inet"), (IpFamily::Ipv6, "inet6")] {
+        let Ok(o) = command("netstat")
+            .args(["-rn", "-f", flag])
+            .output_with_deadline()
+        else {
+            continue;
+        };
+        if !o.status.success() {
+            continue;
+        }
+        for (dev, route) in parse_default_routes(&String::from_utf8_lossy(&o.stdout), family) {
+            if let Some(it) = items.iter_mut().find(|it| it.name == dev) {
+                it.default_routes.push(route);
+            }
+        }
+    }
+    if let Ok(o) = command("scutil").arg("--dns").output_with_deadline()
+        && o.status.success()
+    {
+        for (dev, servers) in parse_scutil_dns(&String::from_utf8_lossy(&o.stdout)) {
+            if let Some(it) = items.iter_mut().find(|it| it.name == dev) {
+                it.dns_servers = servers;
+            }
+        }
+    }
+    Ok(items)
+}
+
+// 只看 "DNS configuration (for scoped queries)" 段：每个 resolver 绑定一个 if_index；
+// 带 domain 的是补充解析器（mDNS、VPN 分流），不算网卡的 DNS
+// resolver #1
+//   nameserver[0] : 192.168.1.1
+//   if_index : 6 (en0)
+fn parse_scutil_dns(text: &str) -> Vec<(String, Vec<String>)> {
+    let mut out: Vec<(String, Vec<String>)> = Vec::new();
+    let mut scoped = false;
+    let mut dev: Option<String> = None;
+    let mut servers = Vec::new();
+    let mut supplemental = false;
+    let mut flush = |dev: &mut Option<String>, servers: &mut Vec<String>, supplemental: bool| {
+        if let Some(d) = dev.take()
+            && !supplemental
+            && !servers.is_empty()
+            && !out.iter().any(|(x, _)| *x == d)
+        {
+            out.push((d, std::mem::take(servers)));
+        }
+        servers.clear();
+    };
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("DNS configuration") {
+            flush(&mut dev, &mut servers, supplemental);
+            scoped = line.contains("scoped");
+            continue;
+        }
+        if !scoped {
+            continue;
+        }
+        if line.starts_with("resolver #") {
+            flush(&mut dev, &mut servers, supplemental);
+            supplemental = false;
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        let key = key.trim();
+        if key.starts_with("nameserver[") {
+            servers.push(value.trim().to_string());
+        } else if key == "if_index" {
+            dev = value
+                .split_once('(')
+                .and_then(|(_, r)| r.strip_suffix(')'))
+                .map(str::to_string);
+        } else if key == "domain" {
+            supplemental = true;
+        }
+    }
+    flush(&mut dev, &mut servers, supplemental);
+    out
+}
+
+// netstat 各版本列数不同，按表头定位 Netif 列；macOS 不提供路由 metric
+fn parse_default_routes(text: &str, family: IpFamily) -> Vec<(String, DefaultRoute)> {
+    let mut out = Vec::new();
+    let mut netif_col = None;
+    for line in text.lines() {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.first() == Some(&"Destination") {
+            netif_col = cols.iter().position(|c| *c == "Netif");
+            continue;
+        }
+        let Some(n) = netif_col else {
+            continue;
+        };
+        if cols.first() != Some(&"default") || cols.len() <= n {
+            continue;
+        }
+        let gateway = cols
+            .get(1)
+            .filter(|g| !g.starts_with("link#"))
+            .map(|g| g.split('%').next().unwrap_or(g).to_string());
+        out.push((
+            cols[n].to_string(),
+            DefaultRoute {
+                family,
+                gateway,
+                metric: None,
+                origin: None,
+                preferred_src: None,
+            },
+        ));
+    }
+    out
 }
 
-pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), ForgeFfiError> {
+pub(super) fn apply_standalone(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    match op {
+        NetIfOp::CreateVlan {
+            parent,
+            vlan_id,
+            name,
+        } => {
+            let name = name.clone().unwrap_or_else(|| format!("vlan{vlan_id}"));
+            run_checked(
+                "ifconfig",
+                &[
+                    name.as_str(),
+                    "create",
+                    "vlan",
+                    &vlan_id.to_string(),
+                    "vlandev",
+                    parent.as_str(),
+                ],
+            )
+        }
+        NetIfOp::CreateBridge { name } => run_checked("ifconfig", &[name.as_str(), "create"]),
+        NetIfOp::AddBridgeMember { bridge, member } => run_checked(
+            "ifconfig",
+            &[bridge.as_str(), "addm", member.as_str()],
+        ),
+        // 克隆接口的名字决定类型，fake ethernet 只能叫 feth<N>
+        NetIfOp::CreateDummy { name } => {
+            let unit = name.strip_prefix("feth").unwrap_or_default();
+            if unit.is_empty() || !unit.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ForgeFfiError::invalid_argument(format!(
+                    "macOS 测试网卡名必须为 feth<N>: {name}"
+                )));
+            }
+            run_checked("ifconfig", &[name.as_str(), "create"])
+        }
+        NetIfOp::CreateBond { .. } | NetIfOp::AddBondMember { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 不支持创建链路聚合（bond）".to_string(),
+        )),
+        NetIfOp::CreateWireguard { .. } | NetIfOp::CreateIpTunnel { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 不支持创建隧道网卡（WireGuard 由用户态 utun 实现）".to_string(),
+        )),
+        NetIfOp::AddRoutingRule { .. } | NetIfOp::DelRoutingRule { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 不支持策略路由规则".to_string(),
+        )),
+        NetIfOp::SwitchNetworkLocation { name } => {
+            run_networksetup(&["-switchtolocation", name.as_str()])
+        }
+        NetIfOp::SetServiceOrder { services } => order_services(services),
+        _ => Err(ForgeFfiError::invalid_argument(
+            "该操作需要 target".to_string(),
+        )),
+    }
+}
+
+pub(super) fn apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    _steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
     match op {
         NetIfOp::SetAdminState { up } => {
             let state = if *up { "up" } else { "down" };
@@ -29,23 +205,368 @@ pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), For
         NetIfOp::SetMtu { mtu } => {
             run_checked("ifconfig", &[target.name.as_str(), "mtu", &mtu.to_string()])
         }
-        NetIfOp::AddIp { ip, prefix_len } => apply_ip(target, ip, *prefix_len, true),
-        NetIfOp::DelIp { ip, prefix_len } => apply_ip(target, ip, *prefix_len, false),
-        NetIfOp::SetIpv4Dhcp { .. } => Err(ForgeFfiError::unsupported(
-            "macOS 下 DHCP 配置不在 V1 范围（可在 V2 通过 networksetup 支持）".to_string(),
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            let lifetimes = resolve_lifetimes(*valid_lft_secs, *preferred_lft_secs);
+            apply_ip(target, ip, *prefix_len, true, lifetimes)
+        }
+        NetIfOp::DelIp { ip, prefix_len } => apply_ip(target, ip, *prefix_len, false, None),
+        NetIfOp::SetIpv4Dhcp { enable } => {
+            let service = network_service_for_device(&target.name)?;
+            if *enable {
+                return run_networksetup(&["-setdhcp", service.as_str()]);
+            }
+            let info = service_ipv4_info(&service)?;
+            let ip = info.ip.ok_or_else(|| {
+                ForgeFfiError::invalid_argument(
+                    "切换为手动前需要先有一个 IPv4 地址（当前未检测到）".to_string(),
+                )
+            })?;
+            let mask = info.subnet_mask.unwrap_or_else(|| "255.255.255.0".to_string());
+            let mut args = vec!["-setmanual", service.as_str(), ip.as_str(), mask.as_str()];
+            if let Some(router) = info.router.as_deref() {
+                args.push(router);
+            }
+            run_networksetup(&args)
+        }
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => {
+            let service = network_service_for_device(&target.name)?;
+            let mask = prefix_to_netmask(*prefix_len);
+            let mut args = vec!["-setmanual", service.as_str(), ip.as_str(), mask.as_str()];
+            if let Some(gw) = gateway.as_deref().filter(|s| !s.is_empty()) {
+                args.push(gw);
+            }
+            run_networksetup(&args)
+        }
+        NetIfOp::SetIpv6Mode { mode } => {
+            let service = network_service_for_device(&target.name)?;
+            match mode {
+                // macOS 的 automatic 同时处理 RA 与 DHCPv6（由 RA 的 M/O 标志决定）
+                Ipv6Mode::Slaac | Ipv6Mode::Dhcp6 => {
+                    run_networksetup(&["-setv6automatic", service.as_str()])
+                }
+                Ipv6Mode::Static => {
+                    let info = service_ipv6_info(&service)?;
+                    let (Some(ip), Some(prefix)) = (info.ip, info.prefix_len) else {
+                        return Err(ForgeFfiError::invalid_argument(
+                            "切换为手动前需要先有一个全局 IPv6 地址（当前未检测到）".to_string(),
+                        ));
+                    };
+                    let mut args = vec!["-setv6manual", service.as_str(), ip.as_str(), prefix.as_str()];
+                    if let Some(router) = info.router.as_deref() {
+                        args.push(router);
+                    }
+                    run_networksetup(&args)
+                }
+            }
+        }
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => {
+            let service = network_service_for_device(&target.name)?;
+            let prefix = prefix_len.to_string();
+            let mut args = vec!["-setv6manual", service.as_str(), ip.as_str(), prefix.as_str()];
+            if let Some(gw) = gateway.as_deref().filter(|s| !s.is_empty()) {
+                args.push(gw);
+            }
+            run_networksetup(&args)
+        }
+        NetIfOp::CreateVlan { .. } => apply_standalone(op),
+        NetIfOp::DeleteVlan => run_checked("ifconfig", &[target.name.as_str(), "destroy"]),
+        NetIfOp::CreateBridge { .. }
+        | NetIfOp::AddBridgeMember { .. }
+        | NetIfOp::CreateDummy { .. }
+        | NetIfOp::CreateBond { .. }
+        | NetIfOp::AddBondMember { .. }
+        | NetIfOp::CreateWireguard { .. }
+        | NetIfOp::CreateIpTunnel { .. }
+        | NetIfOp::AddRoutingRule { .. }
+        | NetIfOp::DelRoutingRule { .. }
+        | NetIfOp::SwitchNetworkLocation { .. }
+        | NetIfOp::SetServiceOrder { .. } => apply_standalone(op),
+        NetIfOp::DeleteBond | NetIfOp::RemoveBondMember => Err(ForgeFfiError::unsupported(
+            "macOS 不支持链路聚合（bond）".to_string(),
+        )),
+        NetIfOp::SetWireguardPeer { .. } | NetIfOp::RemoveWireguardPeer { .. } | NetIfOp::DeleteTunnel => Err(
+            ForgeFfiError::unsupported("macOS 不支持隧道网卡管理".to_string()),
+        ),
+        NetIfOp::AddRoute { .. } | NetIfOp::DelRoute { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 暂不支持路由表管理".to_string(),
         )),
-        NetIfOp::SetIpv4Static { .. } => Err(ForgeFfiError::unsupported(
-            "macOS 下暂未提供 SetIpv4Static（网关/持久化）封装".to_string(),
+        NetIfOp::SetBandwidthLimit { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 不支持按网卡限速（dummynet/pf 需要全局规则集）".to_string(),
+        )),
+        NetIfOp::DeleteBridge | NetIfOp::DeleteInterface => {
+            run_checked("ifconfig", &[target.name.as_str(), "destroy"])
+        }
+        NetIfOp::SetDnsSuffix { suffix } => {
+            let service = network_service_for_device(&target.name)?;
+            let domain = if suffix.is_empty() { "Empty" } else { suffix.as_str() };
+            run_networksetup(&["-setsearchdomains", service.as_str(), domain])
+        }
+        NetIfOp::SetDnsRegistration { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 下不支持按网卡设置动态 DNS 注册".to_string(),
+        )),
+        NetIfOp::RemoveBridgeMember => {
+            let bridge = bridge_of_member(&target.name)?.ok_or_else(|| {
+                ForgeFfiError::not_found(format!("网卡不属于任何 bridge: {}", target.name))
+            })?;
+            run_checked("ifconfig", &[bridge.as_str(), "deletem", target.name.as_str()])
+        }
+        // Wi-Fi 网卡需要处于未关联状态才会接受新地址，否则 ifconfig 报错
+        NetIfOp::SetMac { mac } => {
+            let m = parse_mac(mac)?;
+            let addr = format!(
+                "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+                m[0], m[1], m[2], m[3], m[4], m[5]
+            );
+            run_checked("ifconfig", &[target.name.as_str(), "ether", addr.as_str()])
+        }
+        NetIfOp::SetLinkSpeed { speed_mbps, duplex } => {
+            let dev = target.name.as_str();
+            let Some(speed) = speed_mbps else {
+                return run_checked("ifconfig", &[dev, "media", "autoselect"]);
+            };
+            let want = LinkSpeedMode {
+                speed_mbps: *speed,
+                duplex: duplex.unwrap_or(Duplex::Full),
+            };
+            // ifconfig 需要驱动自己的媒体类型名（1000baseT / 10Gbase-T），从 supported media 里反查
+            let text = ifconfig_capture(&["-m", dev])?;
+            let (media, opt) = text
+                .lines()
+                .filter_map(|l| l.trim().strip_prefix("media "))
+                .filter_map(parse::parse_media_line)
+                .find(|(mode, _, _)| *mode == want)
+                .map(|(_, media, opt)| (media.to_string(), opt.map(str::to_string)))
+                .ok_or_else(|| {
+                    ForgeFfiError::unsupported(format!(
+                        "{dev} 不支持 {speed} Mbps {:?}",
+                        want.duplex
+                    ))
+                })?;
+            let mut args = vec![dev, "media", media.as_str()];
+            if let Some(opt) = &opt {
+                args.extend(["mediaopt", opt.as_str()]);
+            }
+            run_checked("ifconfig", &args)
+        }
+        NetIfOp::SetName { .. } | NetIfOp::SetDescription { .. } => Err(ForgeFfiError::unsupported(
+            "macOS 的 BSD 接口名与描述由系统分配，不支持修改".to_string(),
         )),
     }
 }
 
-fn apply_ip(target: &ResolvedTarget, ip: &str, prefix_len: u8, is_add: bool) -> Result<(), ForgeFfiError> {
+// networksetup 按“网络服务”而不是设备名配置，需要从 -listnetworkserviceorder 反查：
+// (1) Wi-Fi
+// (Hardware Port: Wi-Fi, Device: en0)
+fn network_service_for_device(dev: &str) -> Result<String, ForgeFfiError> {
+    let text = networksetup_capture(&["-listnetworkserviceorder"])?;
+    parse_service_order(&text)
+        .into_iter()
+        .find(|s| s.device.as_deref() == Some(dev))
+        .map(|s| s.name)
+        .ok_or_else(|| ForgeFfiError::not_found(format!("未找到设备 {dev} 对应的网络服务")))
+}
+
+// 被禁用的服务以 "(*) 名称" 出现；VPN 等虚拟服务的 Device 为空
+fn parse_service_order(text: &str) -> Vec<NetworkService> {
+    let mut out = Vec::new();
+    let mut service: Option<(String, bool)> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("(Hardware Port:") {
+            if let Some((name, enabled)) = service.take() {
+                let device = rest
+                    .split("Device:")
+                    .nth(1)
+                    .map(|d| d.trim_end_matches(')').trim().to_string())
+                    .filter(|d| !d.is_empty());
+                out.push(NetworkService {
+                    name,
+                    device,
+                    enabled,
+                });
+            }
+        } else if line.starts_with('(')
+            && let Some((tag, name)) = line.split_once(") ")
+        {
+            service = Some((name.trim().to_string(), tag != "(*"));
+        }
+    }
+    out
+}
+
+pub(super) fn network_location() -> Result<NetworkLocation, ForgeFfiError> {
+    let current = networksetup_capture(&["-getcurrentlocation"])?.trim().to_string();
+    let available = networksetup_capture(&["-listlocations"])?
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let service_order = parse_service_order(&networksetup_capture(&["-listnetworkserviceorder"])?);
+    Ok(NetworkLocation {
+        current,
+        available,
+        service_order,
+    })
+}
+
+// -ordernetworkservices 要求列出全部服务，未指定的按原顺序追加到末尾
+fn order_services(services: &[String]) -> Result<(), ForgeFfiError> {
+    let text = networksetup_capture(&["-listnetworkserviceorder"])?;
+    let current = parse_service_order(&text);
+    for s in services {
+        if !current.iter().any(|c| &c.name == s) {
+            return Err(ForgeFfiError::not_found(format!("未找到网络服务: {s}")));
+        }
+    }
+    let mut args = vec!["-ordernetworkservices"];
+    args.extend(services.iter().map(String::as_str));
+    args.extend(
+        current
+            .iter()
+            .map(|c| c.name.as_str())
+            .filter(|n| !services.iter().any(|s| s == n)),
+    );
+    run_networksetup(&args)
+}
+
+struct ServiceIpv4Info {
+    ip: Option<String>,
+    subnet_mask: Option<String>,
+    router: Option<String>,
+}
+
+fn service_ipv4_info(service: &str) -> Result<ServiceIpv4Info, ForgeFfiError> {
+    let text = networksetup_capture(&["-getinfo", service])?;
+    let field = |key: &str| {
+        text.lines()
+            .find_map(|l| l.strip_prefix(key))
+            .map(|v| v.trim().to_string())
+            .filter(|v| v.parse::<std::net::Ipv4Addr>().is_ok())
+    };
+    Ok(ServiceIpv4Info {
+        ip: field("IP address:"),
+        subnet_mask: field("Subnet mask:"),
+        router: field("Router:"),
+    })
+}
+
+struct ServiceIpv6Info {
+    ip: Option<String>,
+    prefix_len: Option<String>,
+    router: Option<String>,
+}
+
+fn service_ipv6_info(service: &str) -> Result<ServiceIpv6Info, ForgeFfiError> {
+    let text = networksetup_capture(&["-getinfo", service])?;
+    let field = |key: &str| {
+        text.lines()
+            .find_map(|l| l.strip_prefix(key))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty() && v != "none")
+    };
+    Ok(ServiceIpv6Info {
+        ip: field("IPv6 IP address:").filter(|v| v.parse::<std::net::Ipv6Addr>().is_ok()),
+        prefix_len: field("IPv6 Prefix Length:").filter(|v| v.parse::<u8>().is_ok()),
+        router: field("IPv6 Router:"),
+    })
+}
+
+fn prefix_to_netmask(prefix_len: u8) -> String {
+    let bits = u32::MAX.checked_shl(32 - u32::from(prefix_len.min(32))).unwrap_or(0);
+    std::net::Ipv4Addr::from(bits).to_string()
+}
+
+fn run_networksetup(args: &[&str]) -> Result<(), ForgeFfiError> {
+    networksetup_capture(args).map(|_| ())
+}
+
+// networksetup 出错时经常仍返回 0，只在 stdout 打印 "** Error: ..."
+fn networksetup_capture(args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command("networksetup")
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::TimedOut {
+                map_spawn_error("networksetup", e)
+            } else {
+                ForgeFfiError::unsupported(format!("无法执行 networksetup: {e}"))
+            }
+        })?;
+    let stdout = String::from_utf8_lossy(&out.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let msg = format!("{}{}", stdout.trim(), stderr.trim());
+    if out.status.success() && !stdout.contains("** Error") {
+        return Ok(stdout);
+    }
+    let cmdline = format!("networksetup {args:?}");
+    if msg.contains("requires admin") || msg.contains("not permitted") {
+        Err(ForgeFfiError::permission_denied(msg.clone())
+            .with_command(cmdline)
+            .with_stderr(&msg))
+    } else if msg.contains("not a recognized network service") {
+        Err(ForgeFfiError::not_found(msg.clone()).with_command(cmdline).with_stderr(&msg))
+    } else {
+        Err(command_failed(
+            "networksetup",
+            args,
+            &msg,
+            format!("networksetup {:?} 失败: {msg}", args),
+        ))
+    }
+}
+
+fn bridge_of_member(member: &str) -> Result<Option<String>, ForgeFfiError> {
+    let out = command("ifconfig")
+        .arg("-a")
+        .output_with_deadline()
+        .map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: ifconfig: {e}")))?;
+    let text = String::from_utf8_lossy(&out.stdout);
+
+    let mut current: Option<&str> = None;
+    for line in text.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            current = line.split(':').next();
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        if parts.next() == Some("member:") && parts.next() == Some(member) {
+            return Ok(current.map(str::to_string));
+        }
+    }
+    Ok(None)
+}
+
+fn apply_ip(
+    target: &ResolvedTarget,
+    ip: &str,
+    prefix_len: u8,
+    is_add: bool,
+    lifetimes: Option<(u32, u32)>,
+) -> Result<(), ForgeFfiError> {
     let addr: std::net::IpAddr = ip
         .parse()
         .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))?;
     match addr {
         std::net::IpAddr::V4(_) => {
+            if lifetimes.is_some() {
+                return Err(ForgeFfiError::unsupported(
+                    "macOS 下 IPv4 地址不支持设置寿命（仅 IPv6 支持 vltime/pltime）".to_string(),
+                ));
+            }
             let verb = if is_add { "add" } else { "delete" };
             run_checked(
                 "ifconfig",
@@ -54,198 +575,181 @@ fn apply_ip(target: &ResolvedTarget, ip: &str, prefix_len: u8, is_add: bool) ->
         }
         std::net::IpAddr::V6(_) => {
             let verb = if is_add { "add" } else { "delete" };
-            run_checked(
-                "ifconfig",
-                &[
-                    target.name.as_str(),
-                    "inet6",
-                    ip,
-                    "prefixlen",
-                    &prefix_len.to_string(),
-                    verb,
-                ],
-            )
+            let prefix = prefix_len.to_string();
+            let mut args = vec![target.name.as_str(), "inet6", ip, "prefixlen", prefix.as_str()];
+            let lft_args = lifetimes.map(|(valid, preferred)| {
+                [ifconfig_lifetime_arg(valid), ifconfig_lifetime_arg(preferred)]
+            });
+            if let Some([valid, preferred]) = &lft_args {
+                args.extend(["vltime", valid.as_str(), "pltime", preferred.as_str()]);
+            }
+            args.push(verb);
+            run_checked("ifconfig", &args)
         }
     }
 }
 
+fn ifconfig_lifetime_arg(secs: u32) -> String {
+    if secs == LFT_FOREVER {
+        "infty".to_string()
+    } else {
+        secs.to_string()
+    }
+}
+
 fn run_checked(program: &str, args: &[&str]) -> Result<(), ForgeFfiError> {
-    let out = Command::new(program)
+    let out = command(program)
         .args(args)
-        .output()
-        .map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}")))?;
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(program, e))?;
     if out.status.success() {
         Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&out.stderr);
-        Err(ForgeFfiError::system_error(format!(
-            "命令失败: {program} {:?}: {stderr}",
-            args
-        )))
+        Err(command_failed(
+            program,
+            args,
+            &stderr,
+            format!("命令失败: {program} {:?}: {stderr}", args),
+        ))
     }
 }
 
-fn parse_ifconfig(s: &str) -> Vec<NetInterface> {
-    let mut out = Vec::new();
-    for block in s.split("\n\n") {
-        let block = block.trim();
-        if block.is_empty() {
-            continue;
-        }
-        if let Some(i) = parse_ifconfig_block(block) {
-            out.push(i);
-        }
+fn ifconfig_capture(args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command("ifconfig")
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error("ifconfig", e))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(command_failed(
+            "ifconfig",
+            args,
+            &stderr,
+            format!("命令失败: ifconfig {args:?}: {}", stderr.trim()),
+        ));
     }
-    out
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
 }
 
-fn parse_ifconfig_block(block: &str) -> Option<NetInterface> {
-    let mut lines = block.lines();
-    let first = lines.next()?.trim();
-    let name = first.split(':').next()?.trim().to_string();
-
-    let mut flags_val = 0u32;
-    if let Some(start) = first.find('<') {
-        if let Some(end) = first[start + 1..].find('>') {
-            let inside = &first[start + 1..start + 1 + end];
-            for f in inside.split(',') {
-                match f.trim() {
-                    "UP" => flags_val |= IfaceFlags::UP,
-                    "RUNNING" => flags_val |= IfaceFlags::RUNNING,
-                    "LOOPBACK" => flags_val |= IfaceFlags::LOOPBACK,
-                    "BROADCAST" => flags_val |= IfaceFlags::BROADCAST,
-                    "MULTICAST" => flags_val |= IfaceFlags::MULTICAST,
-                    "POINTOPOINT" => flags_val |= IfaceFlags::POINT_TO_POINT,
-                    _ => {}
-                }
-            }
+// ifconfig 不输出索引与链路类型，从 getifaddrs 的 AF_LINK 条目（sockaddr_dl）补齐
+fn fill_link_info(items: &mut [NetInterface]) {
+    let Ok(addrs) = nix::ifaddrs::getifaddrs() else {
+        for it in items.iter_mut() {
+            it.if_index = nix::net::if_::if_nametoindex(it.name.as_str()).unwrap_or(0);
         }
-    }
-
-    let mtu = parse_mtu(first);
-    let admin_state = if (flags_val & IfaceFlags::UP) != 0 {
-        AdminState::Up
-    } else {
-        AdminState::Down
+        return;
     };
-
-    let mut oper_state = None;
-    let mut mac = None;
-    let mut ipv4 = Vec::new();
-    let mut ipv6 = Vec::new();
-
-    for l in std::iter::once("").chain(lines) {
-        let line = l.trim();
-        if line.starts_with("status:") {
-            let v = line.split_whitespace().nth(1).unwrap_or("");
-            oper_state = Some(if v.eq_ignore_ascii_case("active") {
-                OperState::Up
-            } else {
-                OperState::Down
-            });
-        } else if line.starts_with("ether ") {
-            mac = line.split_whitespace().nth(1).map(|s| s.to_string());
-        } else if line.starts_with("inet ") {
-            if let Some(ent) = parse_inet(line) {
-                ipv4.push(ent);
-            }
-        } else if line.starts_with("inet6 ") {
-            if let Some(ent) = parse_inet6(line) {
-                ipv6.push(ent);
-            }
+    for ifa in addrs {
+        let Some(link) = ifa.address.as_ref().and_then(|a| a.as_link_addr()) else {
+            continue;
+        };
+        let Some(it) = items.iter_mut().find(|it| it.name == ifa.interface_name) else {
+            continue;
+        };
+        it.if_index = u32::try_from(link.ifindex()).unwrap_or(0);
+        let (kind, is_physical) = link_kind(&it.name, link.datalink_type());
+        if it.kind == IfaceKind::Unknown {
+            it.kind = kind;
         }
+        it.is_physical = is_physical;
     }
+}
 
-    let kind = if name == "lo0" {
-        IfaceKind::Loopback
-    } else {
-        IfaceKind::Unknown
-    };
+// sdl_type 取值见 <net/if_types.h>；Wi-Fi 也上报为 IFT_ETHER，
+// awdl/llw/ap 等系统虚拟口同为 IFT_ETHER，只能按名字区分
+pub(super) fn link_kind(name: &str, dl_type: u8) -> (IfaceKind, Option<bool>) {
+    const IFT_OTHER: u8 = 0x01;
+    const IFT_ETHER: u8 = 0x06;
+    const IFT_PPP: u8 = 0x17;
+    const IFT_LOOP: u8 = 0x18;
+    const IFT_GIF: u8 = 0x37;
+    const IFT_STF: u8 = 0x39;
+    const IFT_L2VLAN: u8 = 0x87;
+    const IFT_IEEE8023ADLAG: u8 = 0x88;
+    const IFT_BRIDGE: u8 = 0xd1;
+    const IFT_CELLULAR: u8 = 0xff;
+    match dl_type {
+        IFT_LOOP => (IfaceKind::Loopback, Some(false)),
+        IFT_ETHER if name.starts_with("en") => (IfaceKind::Physical, Some(true)),
+        IFT_ETHER => (IfaceKind::Virtual, Some(false)),
+        IFT_CELLULAR => (IfaceKind::Physical, Some(true)),
+        IFT_L2VLAN => (IfaceKind::Vlan, Some(false)),
+        IFT_BRIDGE => (IfaceKind::Bridge, Some(false)),
+        IFT_GIF | IFT_STF | IFT_PPP => (IfaceKind::Tunnel, Some(false)),
+        // utun/ipsec 等内核控制接口
+        IFT_OTHER if name.starts_with("utun") || name.starts_with("ipsec") => {
+            (IfaceKind::Tunnel, Some(false))
+        }
+        IFT_IEEE8023ADLAG => (IfaceKind::Virtual, Some(false)),
+        _ => (IfaceKind::Unknown, None),
+    }
+}
 
-    Some(NetInterface {
-        if_index: 0,
-        name,
-        display_name: None,
-        kind,
-        is_physical: None,
-        admin_state,
-        oper_state,
-        flags: IfaceFlags(flags_val),
-        mac,
-        mtu,
-        speed_bps: None,
-        ipv4,
-        ipv6,
-        capabilities: NetIfCapabilities {
-            can_set_admin_state: true,
-            can_set_mtu: true,
-            can_add_del_ip: true,
-            can_set_dhcp: false,
-            can_set_dns: false,
-            notes: Some("macOS 下 if_index 可能不可用，建议使用 name 定位".to_string()),
-        },
-    })
+pub(super) fn qos(_ifaces: &[NetInterface]) -> Result<Vec<NetIfQos>, ForgeFfiError> {
+    Err(ForgeFfiError::unsupported("macOS 不支持查询限速配置".to_string()))
 }
 
-fn parse_mtu(first: &str) -> Option<u32> {
-    let idx = first.find("mtu ")?;
-    let rest = &first[idx + 4..];
-    rest.split_whitespace().next()?.parse().ok()
+// ifconfig 即时生效；networksetup 修改系统网络服务配置（重启后保留）
+pub(super) fn supported_ops() -> Vec<&'static str> {
+    const UNSUPPORTED: &[&str] = &[
+        "set_dns_registration",
+        "set_name",
+        "set_description",
+        "create_bond",
+        "delete_bond",
+        "add_bond_member",
+        "remove_bond_member",
+        "create_wireguard",
+        "set_wireguard_peer",
+        "remove_wireguard_peer",
+        "create_ip_tunnel",
+        "delete_tunnel",
+        "add_route",
+        "del_route",
+        "add_routing_rule",
+        "del_routing_rule",
+        "set_bandwidth_limit",
+    ];
+    NETIF_OPS.iter().copied().filter(|op| !UNSUPPORTED.contains(op)).collect()
 }
 
-fn parse_inet(line: &str) -> Option<IpAddrEntry> {
-    let mut it = line.split_whitespace();
-    let _ = it.next()?;
-    let ip = it.next()?.to_string();
-    let mut prefix_len = None;
-    while let Some(k) = it.next() {
-        if k == "netmask" {
-            if let Some(mask) = it.next() {
-                prefix_len = parse_netmask_to_prefix(mask);
-            }
-        }
+// networksetup 修改的服务配置同时即时生效；ifconfig 的修改重启后丢失
+pub(super) fn op_scopes(op: &str) -> &'static [NetIfScope] {
+    match op {
+        "set_ipv4_dhcp"
+        | "set_ipv4_static"
+        | "set_ipv6_mode"
+        | "set_ipv6_static"
+        | "set_dns_suffix"
+        | "switch_network_location"
+        | "set_service_order" => &[NetIfScope::Both],
+        _ => &[NetIfScope::Runtime],
     }
-    Some(IpAddrEntry {
-        ip,
-        prefix_len: prefix_len.unwrap_or(32),
-        scope: None,
-        origin: None,
-        flags: None,
-    })
 }
 
-fn parse_inet6(line: &str) -> Option<IpAddrEntry> {
-    let mut it = line.split_whitespace();
-    let _ = it.next()?;
-    let raw_ip = it.next()?;
-    let ip = raw_ip.split('%').next().unwrap_or(raw_ip).to_string();
-    let mut prefix_len = None;
-    while let Some(k) = it.next() {
-        if k == "prefixlen" {
-            prefix_len = it.next().and_then(|v| v.parse::<u8>().ok());
-        }
+pub(super) const BACKENDS: &[(NetIfBackend, NetIfBackendRole, bool)] = &[
+    (NetIfBackend::Ifconfig, NetIfBackendRole::Runtime, true),
+    (NetIfBackend::Networksetup, NetIfBackendRole::Persist, true),
+];
+
+pub(super) fn backend_available(backend: NetIfBackend) -> bool {
+    match backend {
+        NetIfBackend::Ifconfig => std::path::Path::new("/sbin/ifconfig").is_file(),
+        NetIfBackend::Networksetup => std::path::Path::new("/usr/sbin/networksetup").is_file(),
+        _ => false,
     }
-    Some(IpAddrEntry {
-        ip,
-        prefix_len: prefix_len.unwrap_or(128),
-        scope: None,
-        origin: None,
-        flags: None,
-    })
 }
 
-fn parse_netmask_to_prefix(mask: &str) -> Option<u8> {
-    if let Some(hex) = mask.strip_prefix("0x") {
-        let v = u32::from_str_radix(hex, 16).ok()?;
-        return Some(v.count_ones() as u8);
-    }
-    let parts: Vec<u8> = mask
-        .split('.')
-        .map(|p| p.parse::<u8>().ok())
-        .collect::<Option<Vec<u8>>>()?;
-    if parts.len() != 4 {
-        return None;
-    }
-    let v = u32::from_be_bytes([parts[0], parts[1], parts[2], parts[3]]);
-    Some(v.count_ones() as u8)
+// networksetup 每次只能改一项，没有可合并的调用
+pub(super) fn batch_len(_target: &ResolvedTarget, _ops: &[&NetIfOp]) -> usize {
+    0
+}
+
+pub(super) fn apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    ops.iter().map(|op| apply_one(target, op, &mut Vec::new())).collect()
+}
+
+pub(super) fn backend_notes(_backend: NetIfBackend) -> Option<String> {
+    None
 }
@@ -1,10 +1,50 @@
 use super::*;
 
+use forgeffi_base::{NetIfBackend, NetIfBackendRole, NetIfQos};
+
 pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
-    Err(ForgeFfiError::unsupported("当前平台暂不支持 netif".to_string()))
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 netif".to_string()))
+}
+
+pub(super) fn apply_one(
+    _target: &ResolvedTarget,
+    _op: &NetIfOp,
+    _steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 netif".to_string()))
+}
+
+
+pub(super) fn apply_standalone(_op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 netif".to_string()))
+}
+
+pub(super) fn qos(_ifaces: &[NetInterface]) -> Result<Vec<NetIfQos>, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 netif".to_string()))
+}
+
+pub(super) fn supported_ops() -> Vec<&'static str> {
+    Vec::new()
 }
 
-pub(super) fn apply_one(_target: &ResolvedTarget, _op: &NetIfOp) -> Result<(), ForgeFfiError> {
-    Err(ForgeFfiError::unsupported("当前平台暂不支持 netif".to_string()))
+pub(super) fn op_scopes(_op: &str) -> &'static [NetIfScope] {
+    &[]
 }
 
+pub(super) const BACKENDS: &[(NetIfBackend, NetIfBackendRole, bool)] = &[];
+
+pub(super) fn backend_available(_backend: NetIfBackend) -> bool {
+    false
+}
+
+pub(super) fn batch_len(_target: &ResolvedTarget, _ops: &[&NetIfOp]) -> usize {
+    0
+}
+
+pub(super) fn apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    ops.iter().map(|op| apply_one(target, op, &mut Vec::new())).collect()
+}
+
+pub(super) fn backend_notes(_backend: NetIfBackend) -> Option<String> {
+    None
+}
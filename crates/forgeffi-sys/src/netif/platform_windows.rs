@@ -1,217 +1,604 @@
 use super::*;
 
-use forgeffi_base::{
-    AdminState, IfaceFlags, IfaceKind, IpAddrEntry, NetIfCapabilities, OperState,
+use forgeffi_base::{BondMode, Duplex, Ipv6Mode, LinkSpeedMode, NetIfBackend, NetIfBackendRole, NetIfQos, QosEntry};
+use forgeffi_protocol::{parse_mac, validate_windows_ifname, NETIF_OPS};
+use std::sync::Mutex;
+use crate::util::{
+    clear_powershell_engine, map_command_failure, map_spawn_error, powershell_command, powershell_program, ps_quote,
+    CommandExt,
 };
-use serde_json::Value;
-use std::collections::BTreeMap;
-use std::process::Command;
+use super::platform_windows_netsh as netsh;
 
 pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    if use_netsh() {
+        return netsh::list_interfaces();
+    }
     let script = r#"
-$adapters = Get-NetAdapter | Select-Object ifIndex, Name, InterfaceDescription, Status, MacAddress, LinkSpeed
-$ipif = Get-NetIPInterface | Select-Object ifIndex, AddressFamily, Dhcp, NlMtu, ConnectionState
-$ips = Get-NetIPAddress | Select-Object ifIndex, AddressFamily, IPAddress, PrefixLength
-[pscustomobject]@{ adapters=$adapters; ipif=$ipif; ips=$ips } | ConvertTo-Json -Depth 5
+$adapters = Get-NetAdapter | Select-Object ifIndex, Name, InterfaceDescription, Status, MacAddress, LinkSpeed, VlanID
+$ipif = Get-NetIPInterface | Select-Object ifIndex, AddressFamily, Dhcp, NlMtu, ConnectionState, InterfaceMetric
+$ips = Get-NetIPAddress | Select-Object ifIndex, AddressFamily, IPAddress, PrefixLength, AddressState, @{n='ValidLifetime';e={if ($_.ValidLifetime) { [uint64]$_.ValidLifetime.TotalSeconds } else { $null }}}, @{n='PreferredLifetime';e={if ($_.PreferredLifetime) { [uint64]$_.PreferredLifetime.TotalSeconds } else { $null }}}
+$routes = Get-NetRoute -DestinationPrefix '0.0.0.0/0','::/0' -ErrorAction SilentlyContinue | Select-Object ifIndex, AddressFamily, NextHop, RouteMetric, @{n='Protocol';e={"$($_.Protocol)"}}
+$macprop = Get-NetAdapterAdvancedProperty -RegistryKeyword NetworkAddress -ErrorAction SilentlyContinue | Select-Object Name
+$speedprop = Get-NetAdapterAdvancedProperty -RegistryKeyword '*SpeedDuplex' -ErrorAction SilentlyContinue | Select-Object Name, ValidRegistryValues
+$dns = Get-DnsClientServerAddress -ErrorAction SilentlyContinue | Select-Object InterfaceIndex, AddressFamily, ServerAddresses
+[pscustomobject]@{ adapters=$adapters; ipif=$ipif; ips=$ips; routes=$routes; macprop=$macprop; speedprop=$speedprop; dns=$dns } | ConvertTo-Json -Depth 5
 "#;
 
     let text = run_powershell_capture(script)?;
-    let v: Value = serde_json::from_str(&text)
-        .map_err(|e| ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}")))?;
+    parse::parse_powershell_list(&text)
+}
 
-    let adapters = normalize_array(v.get("adapters"));
-    let ipif = normalize_array(v.get("ipif"));
-    let ips = normalize_array(v.get("ips"));
+pub(super) fn apply_standalone(op: &NetIfOp) -> Result<(), ForgeFfiError> {
+    if use_netsh() {
+        return Err(ForgeFfiError::unsupported(
+            "netsh 兜底后端不支持该操作（需要 PowerShell）".to_string(),
+        ));
+    }
+    match op {
+        NetIfOp::CreateVlan {
+            parent,
+            vlan_id,
+            name,
+        } => {
+            let name_arg = name
+                .as_deref()
+                .map(|n| format!(" -Name {}", ps_quote(n)))
+                .unwrap_or_default();
+            run_powershell_checked(&format!(
+                "Add-NetLbfoTeamNic -Team {} -VlanID {vlan_id}{name_arg} -Confirm:$false | Out-Null",
+                ps_quote(parent)
+            ))
+        }
+        NetIfOp::CreateBridge { name } => run_powershell_checked(&format!(
+            "New-VMSwitch -Name {} -SwitchType Internal | Out-Null",
+            ps_quote(name)
+        )),
+        NetIfOp::AddBridgeMember { bridge, member } => run_powershell_checked(&format!(
+            "Set-VMSwitch -Name {} -NetAdapterName {} -AllowManagementOS $true -Confirm:$false | Out-Null",
+            ps_quote(bridge),
+            ps_quote(member)
+        )),
+        NetIfOp::CreateDummy { name } => {
+            validate_windows_ifname(name)?;
+            run_powershell_checked(&create_loopback_script(name))
+        }
+        NetIfOp::CreateBond { name, mode, members } => {
+            run_powershell_checked(&create_team_script(name, *mode, members)?)
+        }
+        NetIfOp::AddBondMember { bond, member } => run_powershell_checked(&format!(
+            "Add-NetLbfoTeamMember -Name {} -Team {} -Confirm:$false | Out-Null",
+            ps_quote(member),
+            ps_quote(bond)
+        )),
+        NetIfOp::SwitchNetworkLocation { .. } | NetIfOp::SetServiceOrder { .. } => Err(
+            ForgeFfiError::unsupported("仅 macOS 支持网络位置/服务顺序".to_string()),
+        ),
+        NetIfOp::CreateWireguard { .. } | NetIfOp::CreateIpTunnel { .. } => Err(ForgeFfiError::unsupported(
+            "Windows 不支持创建隧道网卡（WireGuard 需通过 wireguard.exe 隧道服务管理）".to_string(),
+        )),
+        NetIfOp::AddRoutingRule { .. } | NetIfOp::DelRoutingRule { .. } => Err(ForgeFfiError::unsupported(
+            "Windows 暂不支持策略路由规则".to_string(),
+        )),
+        _ => Err(ForgeFfiError::invalid_argument(
+            "该操作需要 target".to_string(),
+        )),
+    }
+}
 
-    let mut mtu_by_idx: BTreeMap<u32, u32> = BTreeMap::new();
-    let mut conn_by_idx: BTreeMap<u32, OperState> = BTreeMap::new();
+pub(super) fn apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    let idx = target.if_index;
+    if idx == 0 {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "Windows 下必须提供有效 if_index（name={}）",
+            target.name
+        )));
+    }
+    if use_netsh() {
+        return netsh::apply_one(target, op, steps);
+    }
 
-    for it in ipif {
-        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
-        if idx == 0 {
-            continue;
+    if let Some(script) = single_script(idx, op) {
+        return run_powershell_checked(&script?);
+    }
+
+    match op {
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => set_static_ip(idx, "IPv4", ip, *prefix_len, gateway.as_deref(), steps),
+        NetIfOp::SetIpv6Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => set_static_ip(idx, "IPv6", ip, *prefix_len, gateway.as_deref(), steps),
+        NetIfOp::CreateVlan { .. } => apply_standalone(op),
+        NetIfOp::DeleteVlan => run_powershell_checked(&format!(
+            "Get-NetAdapter -InterfaceIndex {idx} | ForEach-Object {{ Get-NetLbfoTeamNic -Name $_.Name }} | Remove-NetLbfoTeamNic -Confirm:$false | Out-Null"
+        )),
+        NetIfOp::CreateBridge { .. }
+        | NetIfOp::AddBridgeMember { .. }
+        | NetIfOp::CreateDummy { .. }
+        | NetIfOp::CreateBond { .. }
+        | NetIfOp::AddBondMember { .. }
+        | NetIfOp::CreateWireguard { .. }
+        | NetIfOp::CreateIpTunnel { .. }
+        | NetIfOp::AddRoutingRule { .. }
+        | NetIfOp::DelRoutingRule { .. }
+        | NetIfOp::SwitchNetworkLocation { .. }
+        | NetIfOp::SetServiceOrder { .. } => apply_standalone(op),
+        // target 是 team 网卡，team 名从 team NIC 反查
+        NetIfOp::DeleteBond => run_powershell_checked(&format!(
+            "Get-NetAdapter -InterfaceIndex {idx} | ForEach-Object {{ Get-NetLbfoTeamNic -Name $_.Name }} | ForEach-Object {{ Remove-NetLbfoTeam -Name $_.Team -Confirm:$false }} | Out-Null"
+        )),
+        NetIfOp::RemoveBondMember => run_powershell_checked(&format!(
+            "Get-NetAdapter -InterfaceIndex {idx} | ForEach-Object {{ Get-NetLbfoTeamMember -Name $_.Name }} | ForEach-Object {{ Remove-NetLbfoTeamMember -Name $_.Name -Team $_.Team -Confirm:$false }} | Out-Null"
+        )),
+        NetIfOp::SetWireguardPeer { .. } | NetIfOp::RemoveWireguardPeer { .. } | NetIfOp::DeleteTunnel => Err(
+            ForgeFfiError::unsupported("Windows 不支持隧道网卡管理".to_string()),
+        ),
+        NetIfOp::AddRoute { .. } | NetIfOp::DelRoute { .. } => Err(ForgeFfiError::unsupported(
+            "Windows 暂不支持路由表管理".to_string(),
+        )),
+        // 只删除 KM-TEST 环回网卡，避免误删物理网卡的设备节点
+        NetIfOp::DeleteInterface => run_powershell_checked(&format!(
+            "$a = Get-NetAdapter -InterfaceIndex {idx}; if ($a.InterfaceDescription -notlike '{LOOPBACK_DESC}*') {{ throw \"只能删除环回测试网卡: $($a.Name)\" }}; pnputil /remove-device $a.PnPDeviceID | Out-Null; if ($LASTEXITCODE -ne 0) {{ throw \"pnputil /remove-device 失败: $LASTEXITCODE\" }}"
+        )),
+        NetIfOp::DeleteBridge => {
+            let switch = vswitch_name(&target.name);
+            run_powershell_checked(&format!("Remove-VMSwitch -Name {} -Force | Out-Null", ps_quote(switch)))
+        }
+        NetIfOp::RemoveBridgeMember => run_powershell_checked(&format!(
+            "$d = (Get-NetAdapter -InterfaceIndex {idx}).InterfaceDescription; Get-VMSwitch | Where-Object {{ $_.NetAdapterInterfaceDescription -eq $d }} | Set-VMSwitch -SwitchType Internal -Confirm:$false | Out-Null"
+        )),
+        NetIfOp::SetAdminState { .. }
+        | NetIfOp::SetMtu { .. }
+        | NetIfOp::AddIp { .. }
+        | NetIfOp::DelIp { .. }
+        | NetIfOp::SetIpv4Dhcp { .. }
+        | NetIfOp::SetIpv6Mode { .. }
+        | NetIfOp::SetDnsSuffix { .. }
+        | NetIfOp::SetDnsRegistration { .. } => unreachable!("由 single_script 处理"),
+        NetIfOp::SetName { new_name } => {
+            validate_windows_ifname(new_name)?;
+            run_powershell_checked(&format!(
+                "Get-NetAdapter -InterfaceIndex {idx} | Rename-NetAdapter -NewName {} -Confirm:$false | Out-Null",
+                ps_quote(new_name)
+            ))
         }
-        if let Some(mtu) = it.get("NlMtu").and_then(Value::as_u64) {
-            mtu_by_idx.insert(idx, mtu as u32);
+        NetIfOp::SetDescription { .. } => Err(ForgeFfiError::unsupported(
+            "Windows 网卡描述（InterfaceDescription）由驱动提供，不支持修改".to_string(),
+        )),
+        NetIfOp::SetMac { mac } => {
+            let m = parse_mac(mac)?;
+            let value: String = m.iter().map(|b| format!("{b:02X}")).collect();
+            set_network_address(idx, &value, steps)
         }
-        if let Some(cs) = it.get("ConnectionState").and_then(Value::as_str) {
-            let st = if cs.eq_ignore_ascii_case("Connected") {
-                OperState::Up
-            } else {
-                OperState::Down
+        NetIfOp::SetLinkSpeed { speed_mbps, duplex } => {
+            let value = match speed_mbps {
+                None => 0,
+                Some(speed) => {
+                    let mode = LinkSpeedMode {
+                        speed_mbps: *speed,
+                        duplex: duplex.unwrap_or(Duplex::Full),
+                    };
+                    parse::SPEED_DUPLEX_VALUES
+                        .iter()
+                        .position(|m| *m == Some(mode))
+                        .ok_or_else(|| {
+                            ForgeFfiError::unsupported(format!(
+                                "*SpeedDuplex 标准取值中没有 {speed} Mbps {:?}",
+                                mode.duplex
+                            ))
+                        })?
+                }
             };
-            conn_by_idx.insert(idx, st);
+            // 修改高级属性会让驱动重新初始化网卡
+            run_powershell_checked(&format!(
+                "$n = (Get-NetAdapter -InterfaceIndex {idx}).Name; Set-NetAdapterAdvancedProperty -Name $n -RegistryKeyword '*SpeedDuplex' -RegistryValue '{value}' | Out-Null"
+            ))
         }
+        NetIfOp::SetBandwidthLimit {
+            egress_mbps,
+            ingress_mbps,
+        } => {
+            if ingress_mbps.is_some() {
+                return Err(ForgeFfiError::unsupported(
+                    "Windows QoS 策略只能限制出方向，不支持 ingress_mbps".to_string(),
+                ));
+            }
+            set_bandwidth_policy(idx, *egress_mbps)
+        }
+    }
+}
+
+// QoS 策略不绑定网卡，按网卡的第一个 IPv4 地址匹配源地址；每块网卡一条，名字带 ifIndex。
+// 策略写入本机策略存储，重启后保留；地址变化后需要重新设置
+fn set_bandwidth_policy(idx: u32, egress_mbps: Option<u32>) -> Result<(), ForgeFfiError> {
+    let remove = format!(
+        "Remove-NetQosPolicy -Name '{QOS_POLICY_PREFIX}{idx}' -Confirm:$false -ErrorAction SilentlyContinue | Out-Null"
+    );
+    let Some(mbps) = egress_mbps else {
+        return run_powershell_checked(&remove);
+    };
+    let bps = u64::from(mbps) * 1_000_000;
+    run_powershell_checked(&format!(
+        "{remove}; $ip = Get-NetIPAddress -InterfaceIndex {idx} -AddressFamily IPv4 -ErrorAction Stop | Select-Object -First 1; New-NetQosPolicy -Name '{QOS_POLICY_PREFIX}{idx}' -IPSrcPrefixMatchCondition \"$($ip.IPAddress)/32\" -ThrottleRateActionBitsPerSecond {bps} | Out-Null"
+    ))
+}
+
+// 每块网卡列出源地址落在其地址上的策略；egress_mbps 只取 SetBandwidthLimit 建立的那一条
+pub(super) fn qos(ifaces: &[NetInterface]) -> Result<Vec<NetIfQos>, ForgeFfiError> {
+    if use_netsh() {
+        return Err(ForgeFfiError::unsupported("netsh 后端不支持查询 QoS 策略".to_string()));
     }
+    let text = run_powershell_capture(
+        "Get-NetQosPolicy | Select-Object Name,IPSrcPrefixMatchCondition,ThrottleRateAction | ConvertTo-Json -Compress",
+    )?;
+    let policies = parse::parse_netqos_policy_json(&text)?;
+    Ok(ifaces
+        .iter()
+        .map(|it| {
+            let ours = format!("{QOS_POLICY_PREFIX}{}", it.if_index);
+            let owns = |p: &QosEntry| {
+                p.handle.as_deref() == Some(ours.as_str())
+                    || p.match_src
+                        .as_deref()
+                        .and_then(|m| m.split('/').next())
+                        .is_some_and(|src| it.ipv4.iter().chain(&it.ipv6).any(|a| a.ip == src))
+            };
+            let entries: Vec<QosEntry> = policies.iter().filter(|&p| owns(p)).cloned().collect();
+            let egress_mbps = entries
+                .iter()
+                .find(|p| p.handle.as_deref() == Some(ours.as_str()))
+                .and_then(|p| p.rate_bps)
+                .map(|bps| u32::try_from(bps / 1_000_000).unwrap_or(u32::MAX));
+            NetIfQos {
+                if_index: it.if_index,
+                name: it.name.clone(),
+                egress_mbps,
+                ingress_mbps: None,
+                entries,
+            }
+        })
+        .collect())
+}
 
-    let mut ips_by_idx: BTreeMap<u32, (Vec<IpAddrEntry>, Vec<IpAddrEntry>)> = BTreeMap::new();
-    for it in ips {
-        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
-        if idx == 0 {
-            continue;
+// 一条脚本即可完成的 op；单独执行与合并执行共用，其余 op 返回 None
+fn single_script(idx: u32, op: &NetIfOp) -> Option<Result<String, ForgeFfiError>> {
+    let store = policy_store_arg();
+    let script = match op {
+        NetIfOp::SetAdminState { up: true } => {
+            format!("Enable-NetAdapter -InterfaceIndex {idx} -Confirm:$false | Out-Null")
+        }
+        NetIfOp::SetAdminState { up: false } => {
+            format!("Disable-NetAdapter -InterfaceIndex {idx} -Confirm:$false | Out-Null")
+        }
+        NetIfOp::SetMtu { mtu } => {
+            format!("Set-NetIPInterface -InterfaceIndex {idx} -NlMtuBytes {mtu}{store} -Confirm:$false | Out-Null")
+        }
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            let family = match ip_family(ip) {
+                Ok(f) => f,
+                Err(e) => return Some(Err(e)),
+            };
+            let lifetimes = match resolve_lifetimes(*valid_lft_secs, *preferred_lft_secs) {
+                Some((valid, preferred)) => format!(
+                    " -ValidLifetime {} -PreferredLifetime {}",
+                    ps_lifetime_arg(valid),
+                    ps_lifetime_arg(preferred)
+                ),
+                None => String::new(),
+            };
+            format!(
+                "New-NetIPAddress -InterfaceIndex {idx} -IPAddress '{ip}' -PrefixLength {prefix_len} -AddressFamily {family}{lifetimes}{store} | Out-Null"
+            )
+        }
+        NetIfOp::DelIp { ip, .. } => {
+            let family = match ip_family(ip) {
+                Ok(f) => f,
+                Err(e) => return Some(Err(e)),
+            };
+            format!(
+                "Remove-NetIPAddress -InterfaceIndex {idx} -IPAddress '{ip}' -AddressFamily {family}{store} -Confirm:$false | Out-Null"
+            )
         }
-        let af = parse_windows_address_family(it.get("AddressFamily"));
-        let ip = it.get("IPAddress").and_then(Value::as_str).unwrap_or("");
-        let prefix = it.get("PrefixLength").and_then(Value::as_u64).unwrap_or(0) as u8;
-        if ip.is_empty() {
-            continue;
+        NetIfOp::SetIpv4Dhcp { enable } => {
+            let mode = if *enable { "Enabled" } else { "Disabled" };
+            format!(
+                "Set-NetIPInterface -InterfaceIndex {idx} -AddressFamily IPv4 -Dhcp {mode}{store} -Confirm:$false | Out-Null"
+            )
         }
-        let ent = IpAddrEntry {
-            ip: ip.to_string(),
-            prefix_len: prefix,
-            scope: None,
-            origin: None,
-            flags: None,
-        };
-        let e = ips_by_idx.entry(idx).or_insert_with(|| (Vec::new(), Vec::new()));
-        if af == WindowsAddressFamily::Ipv4 {
-            e.0.push(ent);
-        } else if af == WindowsAddressFamily::Ipv6 {
-            e.1.push(ent);
+        NetIfOp::SetIpv6Mode { mode } => {
+            let (router_discovery, dhcp) = match mode {
+                Ipv6Mode::Slaac => ("Enabled", "Disabled"),
+                Ipv6Mode::Dhcp6 => ("Enabled", "Enabled"),
+                Ipv6Mode::Static => ("Disabled", "Disabled"),
+            };
+            format!(
+                "Set-NetIPInterface -InterfaceIndex {idx} -AddressFamily IPv6 -RouterDiscovery {router_discovery} -Dhcp {dhcp}{store} -Confirm:$false | Out-Null"
+            )
+        }
+        NetIfOp::SetDnsSuffix { suffix } => format!(
+            "Set-DnsClient -InterfaceIndex {idx} -ConnectionSpecificSuffix '{suffix}' -Confirm:$false | Out-Null"
+        ),
+        NetIfOp::SetDnsRegistration {
+            register,
+            use_suffix,
+        } => {
+            let register = if *register { "$true" } else { "$false" };
+            let use_suffix = match use_suffix {
+                Some(true) => " -UseSuffixWhenRegistering $true",
+                Some(false) => " -UseSuffixWhenRegistering $false",
+                None => "",
+            };
+            format!(
+                "Set-DnsClient -InterfaceIndex {idx} -RegisterThisConnectionsAddress {register}{use_suffix} -Confirm:$false | Out-Null"
+            )
         }
+        _ => return None,
+    };
+    Some(Ok(script))
+}
+
+pub(super) fn batch_len(target: &ResolvedTarget, ops: &[&NetIfOp]) -> usize {
+    if target.if_index == 0 || use_netsh() {
+        return 0;
     }
+    ops.iter()
+        .take_while(|op| matches!(single_script(target.if_index, op), Some(Ok(_))))
+        .count()
+}
+
+const OP_MARKER: &str = "##forgeffi-op ";
 
-    let mut out = Vec::new();
-    for it in adapters {
-        let idx = it.get("ifIndex").and_then(Value::as_u64).unwrap_or(0) as u32;
-        if idx == 0 {
-            continue;
+// 相邻的单脚本 op 合并成一次 PowerShell 调用。每个 op 各自 try/catch，失败不影响后续 op（与逐个执行一致），
+// 并向 stdout 输出带序号的结果标记；进程中途退出或超时时，没有标记的 op 记为该次调用的错误
+pub(super) fn apply_batch(target: &ResolvedTarget, ops: &[&NetIfOp]) -> Vec<Result<(), ForgeFfiError>> {
+    let mut script = String::from(
+        "$OutputEncoding = [System.Text.UTF8Encoding]::new(); [Console]::OutputEncoding = [System.Text.UTF8Encoding]::new(); $ErrorActionPreference = 'Stop'; ",
+    );
+    let mut results: Vec<Option<Result<(), ForgeFfiError>>> = Vec::with_capacity(ops.len());
+    for (k, op) in ops.iter().enumerate() {
+        match single_script(target.if_index, op) {
+            Some(Ok(body)) => {
+                script.push_str(&format!(
+                    "try {{ {body}; '{OP_MARKER}{k} ok' }} catch {{ '{OP_MARKER}{k} fail ' + ($_.ToString() -replace '\\s+', ' ') }}; "
+                ));
+                results.push(None);
+            }
+            Some(Err(e)) => results.push(Some(Err(e))),
+            None => results.push(Some(apply_one(target, op, &mut Vec::new()))),
         }
-        let name = it.get("Name").and_then(Value::as_str).unwrap_or("").to_string();
-        if name.is_empty() {
-            continue;
+    }
+    let out = powershell_command()
+        .arg(&script)
+        .output_with_deadline()
+        .map_err(powershell_spawn_error);
+    if let Ok(o) = &out {
+        for line in String::from_utf8_lossy(&o.stdout).lines() {
+            let Some((k, rest)) = line.trim().strip_prefix(OP_MARKER).and_then(|l| l.split_once(' ')) else {
+                continue;
+            };
+            let Some(slot) = k.parse::<usize>().ok().and_then(|k| results.get_mut(k)) else {
+                continue;
+            };
+            *slot = Some(match rest.strip_prefix("fail") {
+                Some(msg) => Err(map_windows_error(msg)),
+                None => Ok(()),
+            });
         }
-        let display_name = it
-            .get("InterfaceDescription")
-            .and_then(Value::as_str)
-            .map(|s| s.to_string());
-        let status = it.get("Status").and_then(Value::as_str).unwrap_or("");
-        let admin_state = if status.eq_ignore_ascii_case("Up") {
-            AdminState::Up
-        } else if status.eq_ignore_ascii_case("Disabled") {
-            AdminState::Down
-        } else {
-            AdminState::Unknown
-        };
-        let mac = it
-            .get("MacAddress")
-            .and_then(Value::as_str)
-            .map(|s| s.replace('-', ":"));
-
-        let speed_bps = it
-            .get("LinkSpeed")
-            .and_then(Value::as_str)
-            .and_then(parse_link_speed_bps);
-
-        let mut flags = 0u32;
-        if admin_state == AdminState::Up {
-            flags |= IfaceFlags::UP;
+    }
+    let missing = match out {
+        Ok(o) if !o.stderr.iter().all(u8::is_ascii_whitespace) => {
+            map_windows_error(&String::from_utf8_lossy(&o.stderr))
         }
+        Ok(_) => ForgeFfiError::system_error("PowerShell 提前退出，未返回该 op 的结果".to_string()),
+        Err(e) => e,
+    };
+    results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err(missing.clone())))
+        .collect()
+}
+
+// 直接写网卡类注册表键下的 NetworkAddress，驱动只在初始化时读取，需要重启网卡生效
+fn set_network_address(
+    idx: u32,
+    value: &str,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    run_steps(
+        steps,
+        &[
+            (
+                "set_registry",
+                format!(
+                    "$g = (Get-NetAdapter -InterfaceIndex {idx}).InterfaceGuid; $k = Get-ChildItem 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Class\\{{4d36e972-e325-11ce-bfc1-08002be10318}}' -ErrorAction SilentlyContinue | Where-Object {{ (Get-ItemProperty $_.PSPath -Name NetCfgInstanceId -ErrorAction SilentlyContinue).NetCfgInstanceId -eq $g }} | Select-Object -First 1; if (-not $k) {{ throw \"找不到网卡注册表键: $g\" }}; Set-ItemProperty -Path $k.PSPath -Name NetworkAddress -Value '{value}'"
+                ),
+            ),
+            (
+                "restart_adapter",
+                format!("Get-NetAdapter -InterfaceIndex {idx} | Restart-NetAdapter -Confirm:$false"),
+            ),
+        ],
+    )
+}
 
-        let (ipv4, ipv6) = ips_by_idx.remove(&idx).unwrap_or_default();
+// 不带 -PolicyStore 时 cmdlet 同时修改 ActiveStore 与 PersistentStore；指定 scope 时只改其中之一
+fn policy_store_arg() -> &'static str {
+    match op_scope() {
+        Some(NetIfScope::Runtime) => " -PolicyStore ActiveStore",
+        Some(NetIfScope::Persistent) => " -PolicyStore PersistentStore",
+        _ => "",
+    }
+}
 
-        out.push(NetInterface {
-            if_index: idx,
-            name,
-            display_name,
-            kind: IfaceKind::Unknown,
-            is_physical: None,
-            admin_state,
-            oper_state: conn_by_idx.get(&idx).copied(),
-            flags: IfaceFlags(flags),
-            mac,
-            mtu: mtu_by_idx.get(&idx).copied(),
-            speed_bps,
-            ipv4,
-            ipv6,
-            capabilities: NetIfCapabilities {
-                can_set_admin_state: true,
-                can_set_mtu: true,
-                can_add_del_ip: true,
-                can_set_dhcp: true,
-                can_set_dns: false,
-                notes: None,
-            },
+fn set_static_ip(
+    idx: u32,
+    family: &str,
+    ip: &str,
+    prefix_len: u8,
+    gateway: Option<&str>,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    let gateway = gateway.filter(|s| !s.is_empty());
+    let (disable_autoconf, default_prefix) = if family == "IPv6" {
+        ("-RouterDiscovery Disabled -Dhcp Disabled", "::/0")
+    } else {
+        ("-Dhcp Disabled", "0.0.0.0/0")
+    };
+    let mut plan = vec![
+        (
+            "disable_dhcp",
+            format!(
+                "Set-NetIPInterface -InterfaceIndex {idx} -AddressFamily {family} {disable_autoconf} -Confirm:$false | Out-Null"
+            ),
+        ),
+        (
+            "remove_addresses",
+            format!(
+                "Get-NetIPAddress -InterfaceIndex {idx} -AddressFamily {family} -ErrorAction SilentlyContinue | Where-Object {{ $_.PrefixOrigin -ne 'WellKnown' }} | Remove-NetIPAddress -Confirm:$false | Out-Null"
+            ),
+        ),
+    ];
+    if gateway.is_some() {
+        plan.push((
+            "remove_default_route",
+            format!(
+                "Get-NetRoute -InterfaceIndex {idx} -AddressFamily {family} -DestinationPrefix '{default_prefix}' -ErrorAction SilentlyContinue | Remove-NetRoute -Confirm:$false | Out-Null"
+            ),
+        ));
+    }
+    let gw_arg = gateway
+        .map(|gw| format!(" -DefaultGateway '{gw}'"))
+        .unwrap_or_default();
+    plan.push((
+        "add_address",
+        format!(
+            "New-NetIPAddress -InterfaceIndex {idx} -IPAddress '{ip}' -PrefixLength {prefix_len} -AddressFamily {family}{gw_arg} | Out-Null"
+        ),
+    ));
+    run_steps(steps, &plan)
+}
+
+const STEP_MARKER: &str = "##forgeffi-step ";
+
+// 多个步骤合并成一次 PowerShell 调用，省去每步启动进程的开销（pwsh 冷启动常要数百毫秒）。
+// 每步成功后向 stdout 输出标记，第一个失败的步骤把错误写到 stderr 并终止脚本，
+// 据此还原逐步结果：有标记的成功，紧随其后的一步失败，其余未执行
+fn run_steps(steps: &mut Vec<NetIfStepResult>, plan: &[(&str, String)]) -> Result<(), ForgeFfiError> {
+    let mut script = String::from("$ErrorActionPreference = 'Stop'; ");
+    for (step, body) in plan {
+        script.push_str(&format!(
+            "try {{ {body} }} catch {{ [Console]::Error.WriteLine($_.ToString()); exit 1 }}; '{STEP_MARKER}{step}'; "
+        ));
+    }
+    let out = powershell_command()
+        .arg(&script)
+        .output_with_deadline()
+        .map_err(powershell_spawn_error);
+    let done = match &out {
+        Ok(o) => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter(|l| l.trim_start().starts_with(STEP_MARKER))
+            .count(),
+        Err(_) => 0,
+    };
+    let result = match out {
+        Ok(o) if o.status.success() && done == plan.len() => Ok(()),
+        Ok(o) => Err(map_windows_error(&String::from_utf8_lossy(&o.stderr))),
+        Err(e) => Err(e),
+    };
+    for (step, _) in plan.iter().take(done) {
+        steps.push(NetIfStepResult {
+            step: step.to_string(),
+            ok: true,
+            error: None,
+        });
+    }
+    if let (Err(e), Some((step, _))) = (&result, plan.get(done)) {
+        steps.push(NetIfStepResult {
+            step: step.to_string(),
+            ok: false,
+            error: Some(e.clone()),
         });
     }
+    result
+}
 
-    Ok(out)
+fn ps_lifetime_arg(secs: u32) -> String {
+    if secs == LFT_FOREVER {
+        "([TimeSpan]::MaxValue)".to_string()
+    } else {
+        format!("([TimeSpan]::FromSeconds({secs}))")
+    }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum WindowsAddressFamily {
-    Unknown,
-    Ipv4,
-    Ipv6,
+const LOOPBACK_DESC: &str = "Microsoft KM-TEST Loopback Adapter";
+const QOS_POLICY_PREFIX: &str = "forgeffi-bw-";
+
+// 系统没有创建环回网卡的 cmdlet，只能用 WDK 的 devcon 安装 netloop.inf；
+// 新网卡出现在 Get-NetAdapter 之前可能有延迟，按安装前后的 ifIndex 差集找到它再改名
+fn create_loopback_script(name: &str) -> String {
+    format!(
+        "$devcon = Get-Command devcon.exe -ErrorAction SilentlyContinue; \
+         if (-not $devcon) {{ throw '未找到 devcon.exe（Windows Driver Kit 提供），无法创建环回网卡' }}; \
+         $before = @(Get-NetAdapter -IncludeHidden | Where-Object {{ $_.InterfaceDescription -like '{LOOPBACK_DESC}*' }} | ForEach-Object {{ $_.ifIndex }}); \
+         & $devcon.Source install \"$env:windir\\inf\\netloop.inf\" '*msloop' | Out-Null; \
+         if ($LASTEXITCODE -ne 0) {{ throw \"devcon install 失败: $LASTEXITCODE\" }}; \
+         $new = $null; \
+         for ($i = 0; $i -lt 20 -and -not $new; $i++) {{ Start-Sleep -Milliseconds 500; $new = Get-NetAdapter | Where-Object {{ $_.InterfaceDescription -like '{LOOPBACK_DESC}*' -and $before -notcontains $_.ifIndex }} | Select-Object -First 1 }}; \
+         if (-not $new) {{ throw '未找到新建的环回网卡' }}; \
+         Rename-NetAdapter -Name $new.Name -NewName {} -Confirm:$false | Out-Null",
+        ps_quote(name)
+    )
 }
 
-fn parse_windows_address_family(v: Option<&Value>) -> WindowsAddressFamily {
-    match v {
-        None => WindowsAddressFamily::Unknown,
-        Some(Value::String(s)) => {
-            if s.eq_ignore_ascii_case("IPv4") {
-                WindowsAddressFamily::Ipv4
-            } else if s.eq_ignore_ascii_case("IPv6") {
-                WindowsAddressFamily::Ipv6
-            } else {
-                WindowsAddressFamily::Unknown
-            }
-        }
-        Some(Value::Number(n)) => match n.as_u64() {
-            Some(2) => WindowsAddressFamily::Ipv4,
-            Some(23) => WindowsAddressFamily::Ipv6,
-            _ => WindowsAddressFamily::Unknown,
-        },
-        _ => WindowsAddressFamily::Unknown,
-    }
+fn vswitch_name(adapter: &str) -> &str {
+    adapter
+        .strip_prefix("vEthernet (")
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(adapter)
 }
 
-pub(super) fn apply_one(target: &ResolvedTarget, op: &NetIfOp) -> Result<(), ForgeFfiError> {
-    let idx = target.if_index;
-    if idx == 0 {
-        return Err(ForgeFfiError::invalid_argument(format!(
-            "Windows 下必须提供有效 if_index（name={}）",
-            target.name
-        )));
+// NIC Teaming 没有 broadcast；active_backup 用 SwitchIndependent 并把第二个成员设为 Standby
+fn create_team_script(name: &str, mode: BondMode, members: &[String]) -> Result<String, ForgeFfiError> {
+    if members.is_empty() {
+        return Err(ForgeFfiError::invalid_argument("Windows NIC Teaming 创建时 members 不能为空".to_string()));
     }
-
-    match op {
-        NetIfOp::SetAdminState { up } => {
-            if *up {
-                run_powershell_checked(&format!(
-                    "Enable-NetAdapter -InterfaceIndex {idx} -Confirm:$false | Out-Null"
-                ))
-            } else {
-                run_powershell_checked(&format!(
-                    "Disable-NetAdapter -InterfaceIndex {idx} -Confirm:$false | Out-Null"
-                ))
-            }
-        }
-        NetIfOp::SetMtu { mtu } => run_powershell_checked(&format!(
-            "Set-NetIPInterface -InterfaceIndex {idx} -NlMtuBytes {mtu} -Confirm:$false | Out-Null"
-        )),
-        NetIfOp::AddIp { ip, prefix_len } => {
-            let family = ip_family(ip)?;
-            run_powershell_checked(&format!(
-                "New-NetIPAddress -InterfaceIndex {idx} -IPAddress '{ip}' -PrefixLength {prefix_len} -AddressFamily {family} | Out-Null"
-            ))
+    let (teaming, lb) = match mode {
+        BondMode::Lacp => ("Lacp", "Dynamic"),
+        BondMode::BalanceRr | BondMode::BalanceXor => ("Static", "TransportPorts"),
+        BondMode::ActiveBackup | BondMode::BalanceTlb | BondMode::BalanceAlb => ("SwitchIndependent", "Dynamic"),
+        BondMode::Broadcast => {
+            return Err(ForgeFfiError::unsupported("Windows NIC Teaming 不支持 broadcast 模式".to_string()));
         }
-        NetIfOp::DelIp { ip, .. } => {
-            let family = ip_family(ip)?;
-            run_powershell_checked(&format!(
-                "Remove-NetIPAddress -InterfaceIndex {idx} -IPAddress '{ip}' -AddressFamily {family} -Confirm:$false | Out-Null"
-            ))
-        }
-        NetIfOp::SetIpv4Dhcp { enable } => {
-            let mode = if *enable { "Enabled" } else { "Disabled" };
-            run_powershell_checked(&format!(
-                "Set-NetIPInterface -InterfaceIndex {idx} -AddressFamily IPv4 -Dhcp {mode} -Confirm:$false | Out-Null"
-            ))
-        }
-        NetIfOp::SetIpv4Static { .. } => Err(ForgeFfiError::unsupported(
-            "Windows 下暂未提供 SetIpv4Static（网关/持久化）封装，请使用 add_ip/del_ip + 系统网络配置工具".to_string(),
-        )),
+    };
+    let list = members.iter().map(|m| ps_quote(m)).collect::<Vec<_>>().join(",");
+    let mut script = format!(
+        "New-NetLbfoTeam -Name {} -TeamMembers {list} -TeamingMode {teaming} -LoadBalancingAlgorithm {lb} -Confirm:$false | Out-Null",
+        ps_quote(name)
+    );
+    if mode == BondMode::ActiveBackup
+        && let Some(standby) = members.get(1)
+    {
+        script.push_str(&format!(
+            "; Set-NetLbfoTeamMember -Name {} -AdministrativeMode Standby -Confirm:$false | Out-Null",
+            ps_quote(standby)
+        ));
     }
+    Ok(script)
 }
 
 fn ip_family(ip: &str) -> Result<&'static str, ForgeFfiError> {
@@ -224,28 +611,108 @@ fn ip_family(ip: &str) -> Result<&'static str, ForgeFfiError> {
     })
 }
 
-fn normalize_array(v: Option<&Value>) -> Vec<Value> {
-    match v {
-        None => Vec::new(),
-        Some(Value::Array(a)) => a.clone(),
-        Some(Value::Object(_)) => vec![v.unwrap().clone()],
-        _ => Vec::new(),
+// PowerShell 不可用时回退到 netsh；IP Helper API 列出以便宿主探测，固定使用会返回未实现
+pub(super) fn supported_ops() -> Vec<&'static str> {
+    if use_netsh() {
+        return netsh::SUPPORTED_OPS.to_vec();
+    }
+    const UNSUPPORTED: &[&str] = &[
+        "switch_network_location",
+        "set_service_order",
+        "set_description",
+        "create_wireguard",
+        "set_wireguard_peer",
+        "remove_wireguard_peer",
+        "create_ip_tunnel",
+        "delete_tunnel",
+        "add_route",
+        "del_route",
+        "add_routing_rule",
+        "del_routing_rule",
+    ];
+    NETIF_OPS.iter().copied().filter(|op| !UNSUPPORTED.contains(op)).collect()
+}
+
+// PowerShell 下地址/MTU/DHCP 类 cmdlet 支持 -PolicyStore，可任选范围；其余 op 与 netsh 后端都是即时生效并持久保存
+pub(super) fn op_scopes(op: &str) -> &'static [NetIfScope] {
+    if use_netsh() {
+        return &[NetIfScope::Both];
+    }
+    match op {
+        "set_mtu" | "add_ip" | "del_ip" | "set_ipv4_dhcp" | "set_ipv6_mode" => &NetIfScope::ALL,
+        _ => &[NetIfScope::Both],
+    }
+}
+
+pub(super) const BACKENDS: &[(NetIfBackend, NetIfBackendRole, bool)] = &[
+    (NetIfBackend::Powershell, NetIfBackendRole::Runtime, true),
+    (NetIfBackend::Netsh, NetIfBackendRole::Runtime, true),
+    (NetIfBackend::Iphlpapi, NetIfBackendRole::Runtime, false),
+];
+
+pub(super) fn backend_available(backend: NetIfBackend) -> bool {
+    let system32 = || {
+        std::env::var_os("SystemRoot")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| "C:\\Windows".into())
+            .join("System32")
+    };
+    match backend {
+        NetIfBackend::Powershell => powershell_usable(),
+        NetIfBackend::Netsh => system32().join("netsh.exe").is_file(),
+        NetIfBackend::Iphlpapi => system32().join("iphlpapi.dll").is_file(),
+        _ => false,
+    }
+}
+
+pub(super) fn backend_notes(backend: NetIfBackend) -> Option<String> {
+    match backend {
+        NetIfBackend::Powershell if powershell_usable() => Some(format!("使用 {}", powershell_program())),
+        _ => None,
+    }
+}
+
+static POWERSHELL_USABLE: Mutex<Option<bool>> = Mutex::new(None);
+
+// 受限语言模式下 ConvertTo-Json 等管道不可用，与找不到 powershell 同样视为不可用
+fn powershell_usable() -> bool {
+    let mut g = match POWERSHELL_USABLE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    *g.get_or_insert_with(|| {
+        powershell_command()
+            .arg("$ExecutionContext.SessionState.LanguageMode")
+            .output_with_deadline()
+            .is_ok_and(|o| {
+                o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "FullLanguage"
+            })
+    })
+}
+
+fn use_netsh() -> bool {
+    match backend::pinned() {
+        Some(NetIfBackend::Netsh) => true,
+        Some(_) => false,
+        None => !powershell_usable(),
+    }
+}
+
+pub(super) fn clear_caches() {
+    if let Ok(mut g) = POWERSHELL_USABLE.lock() {
+        *g = None;
     }
+    clear_powershell_engine();
 }
 
 fn run_powershell_capture(script: &str) -> Result<String, ForgeFfiError> {
     let script = format!(
         "$OutputEncoding = [System.Text.UTF8Encoding]::new(); [Console]::OutputEncoding = [System.Text.UTF8Encoding]::new(); {script}"
     );
-    let out = Command::new("powershell")
-        .arg("-NoProfile")
-        .arg("-NonInteractive")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-Command")
+    let out = powershell_command()
         .arg(&script)
-        .output()
-        .map_err(|e| ForgeFfiError::unsupported(format!("无法执行 PowerShell: {e}")))?;
+        .output_with_deadline()
+        .map_err(powershell_spawn_error)?;
     if out.status.success() {
         Ok(String::from_utf8_lossy(&out.stdout).to_string())
     } else {
@@ -257,15 +724,10 @@ fn run_powershell_capture(script: &str) -> Result<String, ForgeFfiError> {
 }
 
 fn run_powershell_checked(script: &str) -> Result<(), ForgeFfiError> {
-    let out = Command::new("powershell")
-        .arg("-NoProfile")
-        .arg("-NonInteractive")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-Command")
+    let out = powershell_command()
         .arg(script)
-        .output()
-        .map_err(|e| ForgeFfiError::unsupported(format!("无法执行 PowerShell: {e}")))?;
+        .output_with_deadline()
+        .map_err(powershell_spawn_error)?;
     if out.status.success() {
         Ok(())
     } else {
@@ -274,6 +736,14 @@ fn run_powershell_checked(script: &str) -> Result<(), ForgeFfiError> {
     }
 }
 
+fn powershell_spawn_error(e: std::io::Error) -> ForgeFfiError {
+    if e.kind() == std::io::ErrorKind::TimedOut {
+        map_spawn_error(powershell_program(), e)
+    } else {
+        ForgeFfiError::unsupported(format!("无法执行 PowerShell: {e}"))
+    }
+}
+
 fn map_windows_error(stderr: &str) -> ForgeFfiError {
     let s = stderr.to_lowercase();
     if s.contains("access is denied") || s.contains("权限") {
@@ -281,28 +751,6 @@ fn map_windows_error(stderr: &str) -> ForgeFfiError {
     } else if s.contains("no msft_netadapter objects") || s.contains("cannot find") {
         ForgeFfiError::not_found(stderr.trim().to_string())
     } else {
-        ForgeFfiError::system_error(stderr.trim().to_string())
+        map_command_failure(stderr.trim().to_string())
     }
 }
-
-fn parse_link_speed_bps(s: &str) -> Option<u64> {
-    let s = s.trim();
-    let parts: Vec<&str> = s.split_whitespace().collect();
-    if parts.len() < 2 {
-        return None;
-    }
-    let num: f64 = parts[0].parse().ok()?;
-    let unit = parts[1].to_ascii_lowercase();
-    let mul = if unit.contains("gbps") {
-        1_000_000_000f64
-    } else if unit.contains("mbps") {
-        1_000_000f64
-    } else if unit.contains("kbps") {
-        1_000f64
-    } else if unit.contains("bps") {
-        1f64
-    } else {
-        return None;
-    };
-    Some((num * mul) as u64)
-}
@@ -0,0 +1,524 @@
+use super::*;
+
+use crate::util::{command, command_failed, map_spawn_error, CommandExt};
+use forgeffi_base::{
+    AdminState, DefaultRoute, IfaceFlags, IfaceKind, IpOrigin, NetIfCapabilities, OperState,
+    RouteOrigin,
+};
+use std::collections::BTreeMap;
+
+// PowerShell 不可用（Server Core 受限语言模式等）时的兜底实现，只覆盖基础操作。
+// netsh 输出随系统语言本地化，这里只识别英文输出；其它语言下地址、DHCP 等字段可能缺失
+pub(super) fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let ifaces = parse_ipv4_interfaces(&netsh_capture(&["interface", "ipv4", "show", "interfaces"])?);
+    let admin = parse_show_interface(
+        &netsh_capture(&["interface", "show", "interface"]).unwrap_or_default(),
+    );
+    let mut v4 = parse_ipv4_config(
+        &netsh_capture(&["interface", "ipv4", "show", "config"]).unwrap_or_default(),
+    );
+    let mut v6 = parse_ipv6_addresses(
+        &netsh_capture(&["interface", "ipv6", "show", "addresses"]).unwrap_or_default(),
+    );
+    let macs = command("getmac")
+        .args(["/v", "/fo", "csv", "/nh"])
+        .output_with_deadline()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_getmac_csv(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default();
+
+    let mut out = Vec::with_capacity(ifaces.len());
+    for it in ifaces {
+        let loopback = it.name.starts_with("Loopback Pseudo-Interface");
+        let admin_state = match admin.get(&it.name) {
+            Some(true) => AdminState::Up,
+            Some(false) => AdminState::Down,
+            // 回环等不出现在 "show interface" 里，能看到即视为启用
+            None if loopback => AdminState::Up,
+            None => AdminState::Unknown,
+        };
+        let oper_state = if it.connected { OperState::Up } else { OperState::Down };
+        let mut flags = 0u32;
+        if admin_state == AdminState::Up {
+            flags |= IfaceFlags::UP;
+        }
+        if it.connected {
+            flags |= IfaceFlags::RUNNING;
+        }
+        if loopback {
+            flags |= IfaceFlags::LOOPBACK;
+        }
+
+        let cfg = v4.remove(&it.name).unwrap_or_default();
+        let origin = Some(if cfg.dhcp { IpOrigin::Dhcp } else { IpOrigin::Static });
+        let ipv4 = cfg
+            .addrs
+            .iter()
+            .map(|(ip, prefix_len)| IpAddrEntry {
+                ip: ip.clone(),
+                prefix_len: *prefix_len,
+                scope: None,
+                origin,
+                flags: None,
+                dad_state: None,
+                valid_lft_secs: None,
+                preferred_lft_secs: None,
+            })
+            .collect();
+        let default_routes = cfg
+            .gateway
+            .iter()
+            .map(|gw| DefaultRoute {
+                family: IpFamily::Ipv4,
+                gateway: Some(gw.clone()),
+                metric: cfg.gateway_metric.map(|m| m + it.metric),
+                origin: Some(if cfg.dhcp { RouteOrigin::Dhcp } else { RouteOrigin::Static }),
+                preferred_src: None,
+            })
+            .collect();
+
+        out.push(NetInterface {
+            if_index: it.idx,
+            name: it.name.clone(),
+            display_name: None,
+            kind: if loopback { IfaceKind::Loopback } else { IfaceKind::Unknown },
+            is_physical: None,
+            admin_state,
+            oper_state: Some(oper_state),
+            flags: IfaceFlags(flags),
+            mac: macs.get(&it.name).cloned(),
+            mtu: it.mtu,
+            speed_bps: None,
+            vlan_id: None,
+            ipv4,
+            ipv6: v6.remove(&it.idx).unwrap_or_default(),
+            default_routes,
+            ipv4_gateway: None,
+            ipv6_gateway: None,
+            dns_servers: cfg.dns,
+            tunnel: None,
+            capabilities: NetIfCapabilities {
+                can_set_admin_state: !loopback,
+                can_set_mtu: true,
+                can_add_del_ip: true,
+                can_set_dhcp: !loopback,
+                can_set_dns: false,
+                can_set_ipv6: false,
+                can_rename: false,
+                can_set_description: false,
+                can_set_mac: false,
+                can_manage_routes: false,
+                supported_speeds: Vec::new(),
+                notes: Some(
+                    "netsh 兜底后端：仅支持启停、MTU、地址增删与 DHCP；IPv6 前缀长度按 64 报告"
+                        .to_string(),
+                ),
+            },
+        });
+    }
+    Ok(out)
+}
+
+pub(super) const SUPPORTED_OPS: &[&str] = &[
+    "set_admin_state",
+    "set_mtu",
+    "add_ip",
+    "del_ip",
+    "set_ipv4_dhcp",
+    "set_ipv4_static",
+];
+
+pub(super) fn apply_one(
+    target: &ResolvedTarget,
+    op: &NetIfOp,
+    steps: &mut Vec<NetIfStepResult>,
+) -> Result<(), ForgeFfiError> {
+    // ipv4/ipv6 子上下文接受 ifIndex 作为接口名，避免名字里的空格与引号问题
+    let idx = target.if_index.to_string();
+    match op {
+        NetIfOp::SetAdminState { up } => netsh_checked(&[
+            "interface",
+            "set",
+            "interface",
+            &format!("name={}", target.name),
+            if *up { "admin=enabled" } else { "admin=disabled" },
+        ]),
+        NetIfOp::SetMtu { mtu } => {
+            let mtu_arg = format!("mtu={mtu}");
+            netsh_step(steps, "ipv4_mtu", &["interface", "ipv4", "set", "subinterface", &idx, &mtu_arg, "store=persistent"])?;
+            // IPv6 最小 MTU 为 1280；网卡未绑定 IPv6 时该步骤失败不影响 IPv4 结果
+            if *mtu >= 1280 {
+                let _ = netsh_step(steps, "ipv6_mtu", &["interface", "ipv6", "set", "subinterface", &idx, &mtu_arg, "store=persistent"]);
+            }
+            Ok(())
+        }
+        NetIfOp::AddIp {
+            ip,
+            prefix_len,
+            valid_lft_secs,
+            preferred_lft_secs,
+            ..
+        } => {
+            if valid_lft_secs.is_some() || preferred_lft_secs.is_some() {
+                return Err(ForgeFfiError::unsupported(
+                    "netsh 后端不支持地址生存期".to_string(),
+                ));
+            }
+            match parse_ip(ip)? {
+                IpAddr::V4(_) => netsh_checked(&[
+                    "interface",
+                    "ipv4",
+                    "add",
+                    "address",
+                    &idx,
+                    &format!("address={ip}"),
+                    &format!("mask={}", prefix_to_mask(*prefix_len)),
+                ]),
+                IpAddr::V6(_) => netsh_checked(&[
+                    "interface",
+                    "ipv6",
+                    "add",
+                    "address",
+                    &idx,
+                    &format!("address={ip}/{prefix_len}"),
+                ]),
+            }
+        }
+        NetIfOp::DelIp { ip, .. } => {
+            let family = match parse_ip(ip)? {
+                IpAddr::V4(_) => "ipv4",
+                IpAddr::V6(_) => "ipv6",
+            };
+            netsh_checked(&["interface", family, "delete", "address", &idx, &format!("address={ip}")])
+        }
+        NetIfOp::SetIpv4Dhcp { enable: true } => {
+            netsh_step(steps, "address_dhcp", &["interface", "ipv4", "set", "address", &idx, "source=dhcp"])?;
+            netsh_step(steps, "dns_dhcp", &["interface", "ipv4", "set", "dnsservers", &idx, "source=dhcp"])
+        }
+        // netsh 没有单独关闭 DHCP 的命令：把当前租约地址固化为静态配置
+        NetIfOp::SetIpv4Dhcp { enable: false } => {
+            let cfg = parse_ipv4_config(&netsh_capture(&["interface", "ipv4", "show", "config", &idx])?)
+                .into_values()
+                .next()
+                .unwrap_or_default();
+            let Some((ip, prefix_len)) = cfg.addrs.first() else {
+                return Err(ForgeFfiError::invalid_argument(
+                    "当前没有 IPv4 地址，无法关闭 DHCP；请改用 set_ipv4_static".to_string(),
+                ));
+            };
+            set_static_ipv4(&idx, ip, *prefix_len, cfg.gateway.as_deref())
+        }
+        NetIfOp::SetIpv4Static {
+            ip,
+            prefix_len,
+            gateway,
+        } => set_static_ipv4(&idx, ip, *prefix_len, gateway.as_deref()),
+        _ => Err(ForgeFfiError::unsupported(
+            "netsh 兜底后端不支持该操作（需要 PowerShell）".to_string(),
+        )),
+    }
+}
+
+fn set_static_ipv4(
+    idx: &str,
+    ip: &str,
+    prefix_len: u8,
+    gateway: Option<&str>,
+) -> Result<(), ForgeFfiError> {
+    let gw = gateway
+        .filter(|g| !g.is_empty())
+        .map_or_else(|| "gateway=none".to_string(), |g| format!("gateway={g}"));
+    netsh_checked(&[
+        "interface",
+        "ipv4",
+        "set",
+        "address",
+        idx,
+        "source=static",
+        &format!("address={ip}"),
+        &format!("mask={}", prefix_to_mask(prefix_len)),
+        &gw,
+    ])
+}
+
+fn parse_ip(ip: &str) -> Result<IpAddr, ForgeFfiError> {
+    ip.parse()
+        .map_err(|_| ForgeFfiError::invalid_argument(format!("非法 IP: {ip}")))
+}
+
+fn prefix_to_mask(prefix_len: u8) -> Ipv4Addr {
+    let bits = match prefix_len {
+        0 => 0,
+        p => u32::MAX << (32 - u32::from(p.min(32))),
+    };
+    Ipv4Addr::from(bits)
+}
+
+fn netsh_capture(args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command("netsh")
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error("netsh", e))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).to_string())
+    } else {
+        // netsh 把错误信息写在 stdout
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        Err(command_failed(
+            "netsh",
+            args,
+            &stdout,
+            format!("netsh 命令失败: netsh {args:?}: {}", stdout.trim()),
+        ))
+    }
+}
+
+// netsh 把错误信息写在 stdout 上
+fn netsh_checked(args: &[&str]) -> Result<(), ForgeFfiError> {
+    netsh_capture(args).map(|_| ())
+}
+
+fn netsh_step(
+    steps: &mut Vec<NetIfStepResult>,
+    step: &str,
+    args: &[&str],
+) -> Result<(), ForgeFfiError> {
+    let r = netsh_checked(args);
+    steps.push(NetIfStepResult {
+        step: step.to_string(),
+        ok: r.is_ok(),
+        error: r.as_ref().err().cloned(),
+    });
+    r
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct NetshIface {
+    idx: u32,
+    metric: u32,
+    mtu: Option<u32>,
+    connected: bool,
+    name: String,
+}
+
+// Idx     Met         MTU          State                Name
+// ---  ----------  ----------  ------------  ---------------------------
+//   1          75  4294967295  connected     Loopback Pseudo-Interface 1
+fn parse_ipv4_interfaces(text: &str) -> Vec<NetshIface> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(idx), Some(metric), Some(mtu), Some(state)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let (Ok(idx), Ok(metric), Ok(mtu)) = (idx.parse(), metric.parse(), mtu.parse::<u32>()) else {
+            continue;
+        };
+        let name = parts.collect::<Vec<_>>().join(" ");
+        if name.is_empty() {
+            continue;
+        }
+        out.push(NetshIface {
+            idx,
+            metric,
+            mtu: (mtu != u32::MAX).then_some(mtu),
+            connected: state.eq_ignore_ascii_case("connected"),
+            name,
+        });
+    }
+    out
+}
+
+// Admin State    State          Type             Interface Name
+// Enabled        Connected      Dedicated        Ethernet
+fn parse_show_interface(text: &str) -> BTreeMap<String, bool> {
+    let mut out = BTreeMap::new();
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(admin) = parts.next() else {
+            continue;
+        };
+        let up = if admin.eq_ignore_ascii_case("enabled") {
+            true
+        } else if admin.eq_ignore_ascii_case("disabled") {
+            false
+        } else {
+            continue;
+        };
+        let name = parts.skip(2).collect::<Vec<_>>().join(" ");
+        if !name.is_empty() {
+            out.insert(name, up);
+        }
+    }
+    out
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct NetshIpv4Config {
+    dhcp: bool,
+    addrs: Vec<(String, u8)>,
+    gateway: Option<String>,
+    gateway_metric: Option<u32>,
+    dns: Vec<String>,
+}
+
+// Configuration for interface "Ethernet"
+//     DHCP enabled:                         Yes
+//     IP Address:                           192.168.1.10
+//     Subnet Prefix:                        192.168.1.0/24 (mask 255.255.255.0)
+//     Default Gateway:                      192.168.1.1
+//     Gateway Metric:                       0
+//     DNS servers configured through DHCP:  192.168.1.1
+//                                           8.8.8.8
+fn parse_ipv4_config(text: &str) -> BTreeMap<String, NetshIpv4Config> {
+    let mut out: BTreeMap<String, NetshIpv4Config> = BTreeMap::new();
+    let mut cur: Option<String> = None;
+    let mut pending_ip: Option<String> = None;
+    let mut in_dns = false;
+    for line in text.lines() {
+        let t = line.trim();
+        if let Some(rest) = t.strip_prefix("Configuration for interface ") {
+            cur = Some(rest.trim_matches('"').to_string());
+            pending_ip = None;
+            in_dns = false;
+            continue;
+        }
+        let Some(name) = &cur else {
+            continue;
+        };
+        let cfg = out.entry(name.clone()).or_default();
+        let Some((key, value)) = t.split_once(':') else {
+            // DNS 列表的续行只有一个地址
+            if in_dns && t.parse::<Ipv4Addr>().is_ok() {
+                cfg.dns.push(t.to_string());
+            } else {
+                in_dns = false;
+            }
+            continue;
+        };
+        let value = value.trim();
+        in_dns = false;
+        match key.trim() {
+            "DHCP enabled" => cfg.dhcp = value.eq_ignore_ascii_case("yes"),
+            "IP Address" => pending_ip = Some(value.to_string()),
+            "Subnet Prefix" => {
+                let prefix = value
+                    .split_once('/')
+                    .and_then(|(_, r)| r.split_whitespace().next())
+                    .and_then(|p| p.parse::<u8>().ok());
+                if let (Some(ip), Some(prefix)) = (pending_ip.take(), prefix) {
+                    cfg.addrs.push((ip, prefix));
+                }
+            }
+            "Default Gateway" if value.parse::<Ipv4Addr>().is_ok() => {
+                cfg.gateway.get_or_insert_with(|| value.to_string());
+            }
+            "Gateway Metric" => cfg.gateway_metric = value.parse().ok(),
+            k if k.contains("DNS Servers") || k.contains("DNS servers") => {
+                in_dns = true;
+                if value.parse::<Ipv4Addr>().is_ok() {
+                    cfg.dns.push(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+// Interface 12: Ethernet
+//
+// Addr Type  DAD State   Valid Life Pref. Life Address
+// ---------  ----------- ---------- ---------- ------------------------
+// Dhcp       Preferred     6d23h58m   23h58m fd00::5
+fn parse_ipv6_addresses(text: &str) -> BTreeMap<u32, Vec<IpAddrEntry>> {
+    let mut out: BTreeMap<u32, Vec<IpAddrEntry>> = BTreeMap::new();
+    let mut cur: Option<u32> = None;
+    for line in text.lines() {
+        let t = line.trim();
+        if let Some(rest) = t.strip_prefix("Interface ") {
+            cur = rest.split(':').next().and_then(|v| v.trim().parse().ok());
+            continue;
+        }
+        let Some(idx) = cur else {
+            continue;
+        };
+        let parts: Vec<&str> = t.split_whitespace().collect();
+        let [kind, dad, valid, preferred, addr] = parts[..] else {
+            continue;
+        };
+        let Ok(ip) = addr.split('%').next().unwrap_or(addr).parse::<Ipv6Addr>() else {
+            continue;
+        };
+        let origin = match kind.to_ascii_lowercase().as_str() {
+            "dhcp" => Some(IpOrigin::Dhcp),
+            "manual" => Some(IpOrigin::Static),
+            _ => None,
+        };
+        let dad_state = match dad.to_ascii_lowercase().as_str() {
+            "tentative" => Some(IpDadState::Tentative),
+            "duplicate" => Some(IpDadState::Duplicate),
+            "deprecated" => Some(IpDadState::Deprecated),
+            "preferred" => Some(IpDadState::Preferred),
+            _ => None,
+        };
+        out.entry(idx).or_default().push(IpAddrEntry {
+            ip: addr.to_string(),
+            prefix_len: if ip.is_loopback() { 128 } else { 64 },
+            scope: None,
+            origin,
+            flags: None,
+            dad_state,
+            valid_lft_secs: parse_netsh_lifetime(valid).and_then(lifetime_from_secs),
+            preferred_lft_secs: parse_netsh_lifetime(preferred).and_then(lifetime_from_secs),
+        });
+    }
+    out
+}
+
+// "infinite" 或 "6d23h58m12s" 这类组合
+fn parse_netsh_lifetime(s: &str) -> Option<u64> {
+    if s.eq_ignore_ascii_case("infinite") {
+        return None;
+    }
+    let mut total = 0u64;
+    let mut num = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            continue;
+        }
+        let n: u64 = num.parse().ok()?;
+        num.clear();
+        total += n * match c {
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+    }
+    num.is_empty().then_some(total)
+}
+
+// "Ethernet","Intel(R) Ethernet Connection","00-11-22-33-44-55","\Device\Tcpip_{...}"
+fn parse_getmac_csv(text: &str) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    for line in text.lines() {
+        let fields: Vec<&str> = line
+            .trim()
+            .trim_matches('"')
+            .split("\",\"")
+            .collect();
+        let [name, _, mac, ..] = fields[..] else {
+            continue;
+        };
+        if mac.len() == 17 && mac.bytes().filter(|b| *b == b'-').count() == 5 {
+            out.insert(name.to_string(), mac.replace('-', ":"));
+        }
+    }
+    out
+}
@@ -0,0 +1,131 @@
+// 限速的 Linux 实现：出方向为根 HTB（handle 1:）加一个默认分类 1:10，入方向为 ingress qdisc 上的 police 过滤器
+// （超出速率直接丢包，不排队）。只改运行时状态，重启后不保留。
+// 取消限速时只删除与上面形状一致的 qdisc，其它工具配置的 fq_codel/mq 等保持不动
+use super::*;
+
+use super::platform_linux::run_checked;
+use crate::util::{command, command_failed, map_spawn_error, CommandExt};
+use forgeffi_base::{NetIfQos, QosEntry};
+
+const HTB_HANDLE: &str = "1:";
+const HTB_CLASS: &str = "1:10";
+const INGRESS_HANDLE: &str = "ffff:";
+// police 的桶深按 10ms 的流量算，太小时 TCP 吞吐会远低于设定速率
+const POLICE_BURST_MIN: u64 = 32 * 1024;
+
+fn tc_output(args: &[&str]) -> Result<Vec<u8>, ForgeFfiError> {
+    let out = command("tc")
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error("tc", e))?;
+    if out.status.success() {
+        Ok(out.stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        Err(command_failed("tc", args, &stderr, format!("命令失败: tc {args:?}: {stderr}")))
+    }
+}
+
+fn qdiscs_of(dev: &str) -> Result<Vec<QosEntry>, ForgeFfiError> {
+    let out = tc_output(&["-j", "qdisc", "show", "dev", dev])?;
+    Ok(parse::parse_tc_qdisc_json(&out)?
+        .into_iter()
+        .map(|(_, q)| q)
+        .collect())
+}
+
+fn is_our_htb(q: &QosEntry) -> bool {
+    q.kind == "htb" && q.parent.is_none() && q.handle.as_deref() == Some(HTB_HANDLE)
+}
+
+fn is_ingress(q: &QosEntry) -> bool {
+    q.kind == "ingress"
+}
+
+pub(super) fn set_bandwidth_limit(
+    dev: &str,
+    egress_mbps: Option<u32>,
+    ingress_mbps: Option<u32>,
+) -> Result<(), ForgeFfiError> {
+    let current = qdiscs_of(dev)?;
+    match egress_mbps {
+        Some(mbps) => {
+            let rate = format!("{mbps}mbit");
+            // htb 不支持 change，已是我们的根 HTB 时只改分类速率
+            if !current.iter().any(is_our_htb) {
+                run_checked(
+                    "tc",
+                    &["qdisc", "replace", "dev", dev, "root", "handle", HTB_HANDLE, "htb", "default", "10"],
+                )?;
+            }
+            run_checked(
+                "tc",
+                &[
+                    "class", "replace", "dev", dev, "parent", HTB_HANDLE, "classid", HTB_CLASS, "htb", "rate", &rate,
+                    "ceil", &rate,
+                ],
+            )?;
+        }
+        None if current.iter().any(is_our_htb) => run_checked("tc", &["qdisc", "del", "dev", dev, "root"])?,
+        None => {}
+    }
+    match ingress_mbps {
+        Some(mbps) => {
+            let rate = format!("{mbps}mbit");
+            let burst = (u64::from(mbps) * 1_000_000 / 8 / 100).max(POLICE_BURST_MIN).to_string();
+            if current.iter().any(is_ingress) {
+                // 换速率时先清掉旧的 police，ingress 上只保留这一条过滤器
+                run_checked("tc", &["filter", "del", "dev", dev, "parent", INGRESS_HANDLE])?;
+            } else {
+                run_checked("tc", &["qdisc", "add", "dev", dev, "handle", INGRESS_HANDLE, "ingress"])?;
+            }
+            run_checked(
+                "tc",
+                &[
+                    "filter", "add", "dev", dev, "parent", INGRESS_HANDLE, "protocol", "all", "u32", "match", "u32",
+                    "0", "0", "police", "rate", &rate, "burst", &burst, "drop", "flowid", ":1",
+                ],
+            )?;
+        }
+        None if current.iter().any(is_ingress) => run_checked("tc", &["qdisc", "del", "dev", dev, "ingress"])?,
+        None => {}
+    }
+    Ok(())
+}
+
+// 一次 tc -j qdisc show 取全部网卡，只对有 HTB/ingress 的网卡再查分类与过滤器
+pub(super) fn qos(ifaces: &[NetInterface]) -> Result<Vec<NetIfQos>, ForgeFfiError> {
+    let mut by_dev: BTreeMap<String, Vec<QosEntry>> = BTreeMap::new();
+    for (dev, q) in parse::parse_tc_qdisc_json(&tc_output(&["-j", "qdisc", "show"])?)? {
+        by_dev.entry(dev).or_default().push(q);
+    }
+    let mut items = Vec::new();
+    for it in ifaces {
+        let mut entries = by_dev.remove(&it.name).unwrap_or_default();
+        let mut egress_mbps = None;
+        let mut ingress_mbps = None;
+        for q in &mut entries {
+            if is_our_htb(q) {
+                let text = tc_output(&["class", "show", "dev", &it.name])?;
+                q.rate_bps = parse::parse_tc_class_rate(&String::from_utf8_lossy(&text), HTB_CLASS);
+                egress_mbps = q.rate_bps.map(to_mbps);
+            } else if is_ingress(q) {
+                let text = tc_output(&["filter", "show", "dev", &it.name, "parent", INGRESS_HANDLE])?;
+                q.rate_bps = parse::parse_tc_police_rate(&String::from_utf8_lossy(&text));
+                ingress_mbps = q.rate_bps.map(to_mbps);
+            }
+        }
+        items.push(NetIfQos {
+            if_index: it.if_index,
+            name: it.name.clone(),
+            egress_mbps,
+            ingress_mbps,
+            entries,
+        });
+    }
+    Ok(items)
+}
+
+fn to_mbps(bps: u64) -> u32 {
+    u32::try_from(bps / 1_000_000).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,128 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError, NetIfOp, NetIfRateLimit};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// 进行中的 apply 请求数，只在 max_concurrent_applies 非 0 时维护
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static STATE: Mutex<State> = Mutex::new(State {
+    config: NetIfRateLimit {
+        max_ops_per_sec_per_iface: 0,
+        max_concurrent_applies: 0,
+        debounce_ms: 0,
+    },
+    buckets: None,
+    last_ok: None,
+});
+
+struct State {
+    config: NetIfRateLimit,
+    // 网卡名 -> (剩余令牌, 上次补充时间)；改名后按新名字重新计
+    buckets: Option<HashMap<String, (f64, Instant)>>,
+    // 网卡名 -> (最近一次成功执行的 op, 完成时间)
+    last_ok: Option<HashMap<String, (NetIfOp, Instant)>>,
+}
+
+fn lock() -> MutexGuard<'static, State> {
+    match STATE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+pub fn set_config(config: NetIfRateLimit) {
+    let mut g = lock();
+    g.config = config;
+    g.buckets = None;
+    g.last_ok = None;
+}
+
+pub fn config() -> NetIfRateLimit {
+    lock().config
+}
+
+// 清空各网卡的配额与去抖记录，配置保持不变
+pub fn reset() {
+    let mut g = lock();
+    g.buckets = None;
+    g.last_ok = None;
+}
+
+pub(super) fn per_op_limited() -> bool {
+    let c = config();
+    c.max_ops_per_sec_per_iface != 0 || c.debounce_ms != 0
+}
+
+pub(super) struct ApplySlot(bool);
+
+impl Drop for ApplySlot {
+    fn drop(&mut self) {
+        if self.0 {
+            ACTIVE.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+pub(super) fn acquire_apply_slot() -> Result<ApplySlot, ForgeFfiError> {
+    let max = config().max_concurrent_applies as usize;
+    if max == 0 {
+        return Ok(ApplySlot(false));
+    }
+    ACTIVE
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then_some(n + 1))
+        .map(|_| ApplySlot(true))
+        .map_err(|n| ForgeFfiError::catalog(ErrorCode::Busy, "apply_concurrency_limited", &[("max", &max), ("active", &n)]))
+}
+
+pub(super) enum Admit {
+    Run,
+    Debounced,
+    Limited(ForgeFfiError),
+}
+
+// 去抖先于限流判断：被去抖跳过的 op 不消耗令牌
+pub(super) fn admit(iface: &str, op: &NetIfOp) -> Admit {
+    let mut g = lock();
+    let now = Instant::now();
+    let NetIfRateLimit {
+        max_ops_per_sec_per_iface: rate,
+        debounce_ms,
+        ..
+    } = g.config;
+
+    if debounce_ms > 0
+        && let Some((last, at)) = g.last_ok.as_ref().and_then(|m| m.get(iface))
+        && last == op
+        && now.duration_since(*at) < Duration::from_millis(debounce_ms)
+    {
+        return Admit::Debounced;
+    }
+
+    if rate == 0 {
+        return Admit::Run;
+    }
+    let cap = f64::from(rate);
+    let (tokens, at) = g.buckets.get_or_insert_with(HashMap::new).entry(iface.to_string()).or_insert((cap, now));
+    *tokens = (*tokens + now.duration_since(*at).as_secs_f64() * cap).min(cap);
+    *at = now;
+    if *tokens < 1.0 {
+        return Admit::Limited(ForgeFfiError::catalog(
+            ErrorCode::Busy,
+            "iface_rate_limited",
+            &[("name", &iface), ("rate", &rate)],
+        ));
+    }
+    *tokens -= 1.0;
+    Admit::Run
+}
+
+pub(super) fn record_ok(iface: &str, op: &NetIfOp) {
+    let mut g = lock();
+    if g.config.debounce_ms == 0 {
+        return;
+    }
+    g.last_ok
+        .get_or_insert_with(HashMap::new)
+        .insert(iface.to_string(), (op.clone(), Instant::now()));
+}
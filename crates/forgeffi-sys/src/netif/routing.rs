@@ -0,0 +1,72 @@
+// 静态路由与策略路由规则的 Linux 实现：ip route / ip rule，只改运行时路由表，重启后不保留。
+// 地址族显式传 -4/-6：ip rule 不带 from/to 时默认只作用于 IPv4
+use super::*;
+
+use super::platform_linux::run_checked;
+use forgeffi_base::{RoutingRule, StaticRoute};
+use forgeffi_protocol::{parse_cidr, validate_linux_ifname};
+
+fn is_ipv6(cidr: &str) -> bool {
+    parse_cidr(cidr).is_some_and(|(ip, _)| ip.is_ipv6())
+}
+
+fn family_flag(ipv6: bool) -> String {
+    if ipv6 { "-6" } else { "-4" }.to_string()
+}
+
+fn run_ip(args: &[String]) -> Result<(), ForgeFfiError> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_checked("ip", &args)
+}
+
+// action 为 add 或 del；del 时给出的字段都参与匹配
+pub(super) fn route(action: &str, dev: &str, route: &StaticRoute) -> Result<(), ForgeFfiError> {
+    let mut args = vec![family_flag(is_ipv6(&route.dest)), "route".into(), action.into(), route.dest.clone()];
+    if let Some(gw) = &route.gateway {
+        args.extend(["via".into(), gw.clone()]);
+    }
+    args.extend(["dev".into(), dev.to_string()]);
+    if let Some(table) = route.table {
+        args.extend(["table".into(), table.to_string()]);
+    }
+    if let Some(metric) = route.metric {
+        args.extend(["metric".into(), metric.to_string()]);
+    }
+    if let Some(src) = &route.src {
+        args.extend(["src".into(), src.clone()]);
+    }
+    run_ip(&args)
+}
+
+pub(super) fn rule(action: &str, rule: &RoutingRule) -> Result<(), ForgeFfiError> {
+    for name in [&rule.iif, &rule.oif].into_iter().flatten() {
+        validate_linux_ifname(name)?;
+    }
+    let ipv6 = rule
+        .from
+        .iter()
+        .chain(&rule.to)
+        .next()
+        .map_or(rule.ipv6, |cidr| is_ipv6(cidr));
+    let mut args = vec![family_flag(ipv6), "rule".into(), action.into()];
+    if let Some(p) = rule.priority {
+        args.extend(["priority".into(), p.to_string()]);
+    }
+    if let Some(from) = &rule.from {
+        args.extend(["from".into(), from.clone()]);
+    }
+    if let Some(to) = &rule.to {
+        args.extend(["to".into(), to.clone()]);
+    }
+    if let Some(mark) = rule.fwmark {
+        args.extend(["fwmark".into(), format!("{mark:#x}")]);
+    }
+    if let Some(iif) = &rule.iif {
+        args.extend(["iif".into(), iif.clone()]);
+    }
+    if let Some(oif) = &rule.oif {
+        args.extend(["oif".into(), oif.clone()]);
+    }
+    args.extend(["table".into(), rule.table.to_string()]);
+    run_ip(&args)
+}
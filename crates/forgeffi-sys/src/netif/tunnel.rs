@@ -0,0 +1,113 @@
+// WireGuard 与 IP 隧道（gre/ipip/sit）的 Linux 实现：建链走 ip link，WireGuard 的密钥与 peer 走 wg set。
+// 私钥/预共享密钥只通过 0600 临时文件交给 wg，不出现在命令行参数里（/proc/<pid>/cmdline 对所有用户可见）
+use super::*;
+
+use super::platform_linux::run_checked;
+use crate::util::{command, CommandExt, SecretFile};
+use forgeffi_base::{TunnelKind, WireguardPeer};
+use forgeffi_protocol::validate_linux_ifname;
+
+// 创建失败时删掉已建出的链路，避免留下没有密钥的半成品网卡
+pub(super) fn create_wireguard(
+    name: &str,
+    private_key: &str,
+    listen_port: Option<u16>,
+    peers: &[WireguardPeer],
+) -> Result<(), ForgeFfiError> {
+    validate_linux_ifname(name)?;
+    run_checked("ip", &["link", "add", "dev", name, "type", "wireguard"])?;
+    let configure = || {
+        let key = SecretFile::new("wg", private_key.as_bytes())?;
+        let port = listen_port.map(|p| p.to_string());
+        let mut args = vec!["set", name, "private-key", key.path()];
+        if let Some(port) = &port {
+            args.extend(["listen-port", port.as_str()]);
+        }
+        run_checked("wg", &args)?;
+        peers.iter().try_for_each(|p| set_wireguard_peer(name, p))
+    };
+    configure().inspect_err(|_| {
+        let _ = run_checked("ip", &["link", "delete", "dev", name]);
+    })
+}
+
+pub(super) fn set_wireguard_peer(dev: &str, peer: &WireguardPeer) -> Result<(), ForgeFfiError> {
+    let psk = peer.preshared_key.as_deref().map(|k| SecretFile::new("wg", k.as_bytes())).transpose()?;
+    let allowed = peer.allowed_ips.join(",");
+    let keepalive = peer.persistent_keepalive_secs.map(|v| v.to_string());
+    let mut args = vec!["set", dev, "peer", peer.public_key.as_str()];
+    if let Some(psk) = &psk {
+        args.extend(["preshared-key", psk.path()]);
+    }
+    if let Some(ep) = &peer.endpoint {
+        args.extend(["endpoint", ep.as_str()]);
+    }
+    // wg set 会整体替换 allowed-ips，空串表示清空
+    args.extend(["allowed-ips", allowed.as_str()]);
+    if let Some(k) = &keepalive {
+        args.extend(["persistent-keepalive", k.as_str()]);
+    }
+    run_checked("wg", &args)
+}
+
+pub(super) fn remove_wireguard_peer(dev: &str, public_key: &str) -> Result<(), ForgeFfiError> {
+    run_checked("wg", &["set", dev, "peer", public_key, "remove"])
+}
+
+pub(super) fn create_ip_tunnel(
+    name: &str,
+    kind: TunnelKind,
+    remote: &str,
+    local: Option<&str>,
+    ttl: Option<u8>,
+) -> Result<(), ForgeFfiError> {
+    validate_linux_ifname(name)?;
+    let kind = match kind {
+        TunnelKind::Gre => "gre",
+        TunnelKind::Ipip => "ipip",
+        TunnelKind::Sit => "sit",
+        other => {
+            return Err(ForgeFfiError::invalid_argument(format!(
+                "CreateIpTunnel 不支持 kind={other:?}"
+            )))
+        }
+    };
+    let ttl = ttl.map(|v| v.to_string());
+    let mut args = vec!["link", "add", "name", name, "type", kind, "remote", remote];
+    if let Some(local) = local {
+        args.extend(["local", local]);
+    }
+    if let Some(ttl) = &ttl {
+        args.extend(["ttl", ttl.as_str()]);
+    }
+    run_checked("ip", &args)
+}
+
+// 只删隧道网卡，target 不是隧道时拒绝，避免误删物理网卡或其它虚拟网卡
+pub(super) fn delete_tunnel(target: &ResolvedTarget) -> Result<(), ForgeFfiError> {
+    let is_tunnel = platform::list_interfaces()?
+        .iter()
+        .any(|it| it.name == target.name && it.tunnel.is_some());
+    if !is_tunnel {
+        return Err(ForgeFfiError::invalid_argument(format!(
+            "{} 不是隧道网卡",
+            target.name
+        )));
+    }
+    run_checked("ip", &["link", "delete", "dev", target.name.as_str()])
+}
+
+// 读取 wg 状态需要 wg 工具与 CAP_NET_ADMIN；失败时保持 wireguard 为空，不影响 list
+pub(super) fn fill_wireguard(items: &mut [NetInterface]) {
+    for it in items {
+        let Some(tunnel) = it.tunnel.as_mut().filter(|t| t.kind == TunnelKind::Wireguard) else {
+            continue;
+        };
+        let Ok(out) = command("wg").args(["show", it.name.as_str(), "dump"]).output_with_deadline() else {
+            return;
+        };
+        if out.status.success() {
+            tunnel.wireguard = parse::parse_wg_dump(&String::from_utf8_lossy(&out.stdout));
+        }
+    }
+}
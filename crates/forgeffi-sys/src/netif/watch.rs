@@ -0,0 +1,57 @@
+use forgeffi_base::{ForgeFfiError, NetIfEvent};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+pub type EventSink = Box<dyn FnMut(&NetIfEvent) + Send + 'static>;
+
+pub struct NetIfWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl NetIfWatcher {
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        // 在自己的回调里 stop 时不能 join 自身，线程看到 stop 标志后自行退出
+        if let Some(h) = self.handle.take()
+            && h.thread().id() != std::thread::current().id()
+        {
+            let _ = h.join();
+        }
+    }
+}
+
+impl Drop for NetIfWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+pub fn watch(mut sink: EventSink) -> Result<NetIfWatcher, ForgeFfiError> {
+    // 收到任何变更事件都让 list 缓存失效
+    let sink: EventSink = Box::new(move |ev| {
+        super::cache::invalidate();
+        sink(ev);
+    });
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = spawn_platform(sink, Arc::clone(&stop))?;
+    Ok(NetIfWatcher {
+        stop,
+        handle: Some(handle),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn spawn_platform(sink: EventSink, stop: Arc<AtomicBool>) -> Result<JoinHandle<()>, ForgeFfiError> {
+    super::watch_linux::spawn(sink, stop)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn spawn_platform(_sink: EventSink, _stop: Arc<AtomicBool>) -> Result<JoinHandle<()>, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 netif watch".to_string()))
+}
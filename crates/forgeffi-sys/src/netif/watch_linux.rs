@@ -0,0 +1,278 @@
+use super::dad_state_from_flags;
+use super::watch::EventSink;
+use crate::util::now_unix_ms;
+use forgeffi_base::{ForgeFfiError, NetIfEvent, NetIfEventKind};
+use nix::errno::Errno;
+use nix::sys::socket::{
+    bind, recv, setsockopt, socket, sockopt, AddressFamily, MsgFlags, NetlinkAddr, SockFlag,
+    SockProtocol, SockType,
+};
+use nix::sys::time::{TimeVal, TimeValLike};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::fd::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+const NLMSG_HDR_LEN: usize = 16;
+const NLMSG_NOOP: u16 = 1;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLMSG_OVERRUN: u16 = 4;
+
+const RTM_NEWLINK: u16 = 16;
+const RTM_DELLINK: u16 = 17;
+const RTM_NEWADDR: u16 = 20;
+const RTM_DELADDR: u16 = 21;
+const RTM_NEWROUTE: u16 = 24;
+const RTM_DELROUTE: u16 = 25;
+
+const RTMGRP_LINK: u32 = 0x1;
+const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+const RTMGRP_IPV4_ROUTE: u32 = 0x40;
+const RTMGRP_IPV6_IFADDR: u32 = 0x100;
+const RTMGRP_IPV6_ROUTE: u32 = 0x400;
+
+const IFLA_IFNAME: u16 = 3;
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+const IFA_LABEL: u16 = 3;
+const IFA_FLAGS: u16 = 8;
+const IFA_F_DADFAILED: u32 = 0x08;
+const IFA_F_DEPRECATED: u32 = 0x20;
+const IFA_F_TENTATIVE: u32 = 0x40;
+const RTA_DST: u16 = 1;
+const RTA_OIF: u16 = 4;
+const RTA_GATEWAY: u16 = 5;
+const RTA_PRIORITY: u16 = 6;
+
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+
+pub(super) fn spawn(mut sink: EventSink, stop: Arc<AtomicBool>) -> Result<JoinHandle<()>, ForgeFfiError> {
+    let fd = socket(
+        AddressFamily::Netlink,
+        SockType::Raw,
+        SockFlag::SOCK_CLOEXEC,
+        SockProtocol::NetlinkRoute,
+    )
+    .map_err(map_errno)?;
+    let groups = RTMGRP_LINK
+        | RTMGRP_IPV4_IFADDR
+        | RTMGRP_IPV4_ROUTE
+        | RTMGRP_IPV6_IFADDR
+        | RTMGRP_IPV6_ROUTE;
+    bind(fd.as_raw_fd(), &NetlinkAddr::new(0, groups)).map_err(map_errno)?;
+    setsockopt(&fd, sockopt::ReceiveTimeout, &TimeVal::milliseconds(200)).map_err(map_errno)?;
+
+    std::thread::Builder::new()
+        .name("forgeffi-netif-watch".to_string())
+        .spawn(move || {
+            let mut seq = 0u64;
+            let mut buf = vec![0u8; 64 * 1024];
+            while !stop.load(Ordering::Acquire) {
+                match recv(fd.as_raw_fd(), &mut buf, MsgFlags::empty()) {
+                    Ok(n) => {
+                        for mut ev in parse_messages(&buf[..n]) {
+                            seq += 1;
+                            ev.seq = seq;
+                            sink(&ev);
+                        }
+                    }
+                    Err(Errno::EAGAIN | Errno::EINTR) => continue,
+                    Err(Errno::ENOBUFS) => {
+                        seq += 1;
+                        sink(&NetIfEvent::new(seq, NetIfEventKind::Overrun, now_unix_ms()));
+                    }
+                    Err(_) => break,
+                }
+            }
+        })
+        .map_err(|e| ForgeFfiError::system_error(format!("启动 watch 线程失败: {e}")))
+}
+
+fn map_errno(e: Errno) -> ForgeFfiError {
+    match e {
+        Errno::EPERM | Errno::EACCES => ForgeFfiError::permission_denied(format!("netlink: {e}")),
+        _ => ForgeFfiError::system_error(format!("netlink: {e}")),
+    }
+    .with_errno(e as i32)
+}
+
+fn parse_messages(mut data: &[u8]) -> Vec<NetIfEvent> {
+    let mut out = Vec::new();
+    let ts = now_unix_ms();
+    while data.len() >= NLMSG_HDR_LEN {
+        let len = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let ty = u16::from_ne_bytes([data[4], data[5]]);
+        if len < NLMSG_HDR_LEN || len > data.len() {
+            break;
+        }
+        let payload = &data[NLMSG_HDR_LEN..len];
+
+        match ty {
+            NLMSG_NOOP | NLMSG_ERROR | NLMSG_DONE => {}
+            NLMSG_OVERRUN => out.push(NetIfEvent::new(0, NetIfEventKind::Overrun, ts)),
+            RTM_NEWLINK | RTM_DELLINK => {
+                if let Some(ev) = parse_link(ty, payload, ts) {
+                    out.push(ev);
+                }
+            }
+            RTM_NEWADDR | RTM_DELADDR => {
+                if let Some(ev) = parse_addr(ty, payload, ts) {
+                    out.push(ev);
+                }
+            }
+            RTM_NEWROUTE | RTM_DELROUTE => {
+                if let Some(ev) = parse_route(ty, payload, ts) {
+                    out.push(ev);
+                }
+            }
+            _ => {
+                let mut ev = NetIfEvent::new(0, NetIfEventKind::Unknown, ts);
+                ev.kernel_type = Some(ty);
+                out.push(ev);
+            }
+        }
+
+        let aligned = align4(len);
+        if aligned >= data.len() {
+            break;
+        }
+        data = &data[aligned..];
+    }
+    out
+}
+
+fn parse_link(ty: u16, payload: &[u8], ts: u64) -> Option<NetIfEvent> {
+    if payload.len() < 16 {
+        return None;
+    }
+    let kind = if ty == RTM_NEWLINK {
+        NetIfEventKind::NewLink
+    } else {
+        NetIfEventKind::DelLink
+    };
+    let mut ev = NetIfEvent::new(0, kind, ts);
+    ev.kernel_type = Some(ty);
+    ev.if_index = Some(read_u32(&payload[4..8]));
+    for (at, val) in attrs(&payload[16..]) {
+        if at == IFLA_IFNAME {
+            ev.name = Some(c_string(val));
+        }
+    }
+    Some(ev)
+}
+
+fn parse_addr(ty: u16, payload: &[u8], ts: u64) -> Option<NetIfEvent> {
+    if payload.len() < 8 {
+        return None;
+    }
+    let family = payload[0];
+    let kind = if ty == RTM_NEWADDR {
+        NetIfEventKind::NewAddr
+    } else {
+        NetIfEventKind::DelAddr
+    };
+    let mut ev = NetIfEvent::new(0, kind, ts);
+    ev.kernel_type = Some(ty);
+    ev.prefix_len = Some(payload[1]);
+    ev.if_index = Some(read_u32(&payload[4..8]));
+
+    let mut address = None;
+    let mut local = None;
+    let mut flags = u32::from(payload[2]);
+    for (at, val) in attrs(&payload[8..]) {
+        match at {
+            IFA_ADDRESS => address = format_ip(family, val),
+            IFA_LOCAL => local = format_ip(family, val),
+            IFA_LABEL => ev.name = Some(c_string(val)),
+            IFA_FLAGS if val.len() >= 4 => flags = read_u32(val),
+            _ => {}
+        }
+    }
+    ev.ip = local.or(address);
+    if family == AF_INET6 && ty == RTM_NEWADDR {
+        ev.dad_state = Some(dad_state_from_flags(
+            flags & IFA_F_TENTATIVE != 0,
+            flags & IFA_F_DADFAILED != 0,
+            flags & IFA_F_DEPRECATED != 0,
+        ));
+    }
+    Some(ev)
+}
+
+fn parse_route(ty: u16, payload: &[u8], ts: u64) -> Option<NetIfEvent> {
+    if payload.len() < 12 {
+        return None;
+    }
+    let family = payload[0];
+    let kind = if ty == RTM_NEWROUTE {
+        NetIfEventKind::NewRoute
+    } else {
+        NetIfEventKind::DelRoute
+    };
+    let mut ev = NetIfEvent::new(0, kind, ts);
+    ev.kernel_type = Some(ty);
+    ev.prefix_len = Some(payload[1]);
+    for (at, val) in attrs(&payload[12..]) {
+        match at {
+            RTA_DST => ev.ip = format_ip(family, val),
+            RTA_OIF if val.len() >= 4 => ev.if_index = Some(read_u32(val)),
+            RTA_GATEWAY => ev.gateway = format_ip(family, val),
+            RTA_PRIORITY if val.len() >= 4 => ev.metric = Some(read_u32(val)),
+            _ => {}
+        }
+    }
+    if ev.ip.is_none() && payload[1] == 0 {
+        ev.ip = match family {
+            AF_INET => Some(Ipv4Addr::UNSPECIFIED.to_string()),
+            AF_INET6 => Some(Ipv6Addr::UNSPECIFIED.to_string()),
+            _ => None,
+        };
+    }
+    Some(ev)
+}
+
+fn attrs(mut data: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut out = Vec::new();
+    while data.len() >= 4 {
+        let len = u16::from_ne_bytes([data[0], data[1]]) as usize;
+        let ty = u16::from_ne_bytes([data[2], data[3]]) & 0x3fff;
+        if len < 4 || len > data.len() {
+            break;
+        }
+        out.push((ty, &data[4..len]));
+        let aligned = align4(len);
+        if aligned >= data.len() {
+            break;
+        }
+        data = &data[aligned..];
+    }
+    out
+}
+
+fn align4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn read_u32(b: &[u8]) -> u32 {
+    u32::from_ne_bytes([b[0], b[1], b[2], b[3]])
+}
+
+fn c_string(b: &[u8]) -> String {
+    let end = b.iter().position(|c| *c == 0).unwrap_or(b.len());
+    String::from_utf8_lossy(&b[..end]).to_string()
+}
+
+fn format_ip(family: u8, b: &[u8]) -> Option<String> {
+    match family {
+        AF_INET if b.len() >= 4 => Some(Ipv4Addr::new(b[0], b[1], b[2], b[3]).to_string()),
+        AF_INET6 if b.len() >= 16 => {
+            let mut a = [0u8; 16];
+            a.copy_from_slice(&b[..16]);
+            Some(Ipv6Addr::from(a).to_string())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,146 @@
+// 阻止系统睡眠：每个句柄对应一个持有者子进程，子进程存活期间系统不会因空闲进入睡眠，
+// 释放句柄即结束子进程。持有者会盯住本进程的 pid，宿主崩溃时随之退出，不会遗留抑制。
+// 只阻止空闲睡眠；用户主动合盖/选择睡眠仍由系统策略决定
+use forgeffi_base::ForgeFfiError;
+use std::collections::BTreeMap;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::util::{command, command_failed, map_spawn_error};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+// 持有者启动后在这段时间内退出视为失败（工具缺失、被 polkit 拒绝等）
+const STARTUP_GRACE: Duration = Duration::from_millis(200);
+const STARTUP_POLL: Duration = Duration::from_millis(20);
+
+struct Holder {
+    child: Child,
+    reason: String,
+}
+
+static HOLDERS: Mutex<BTreeMap<u64, Holder>> = Mutex::new(BTreeMap::new());
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+fn holders() -> MutexGuard<'static, BTreeMap<u64, Holder>> {
+    match HOLDERS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+fn validate_reason(reason: &str) -> Result<(), ForgeFfiError> {
+    if reason.trim().is_empty() {
+        return Err(ForgeFfiError::invalid_argument("reason 不能为空"));
+    }
+    if reason.len() > 255 {
+        return Err(ForgeFfiError::invalid_argument("reason 不能超过 255 字节"));
+    }
+    if reason.chars().any(char::is_control) {
+        return Err(ForgeFfiError::invalid_argument("reason 不能包含控制字符"));
+    }
+    Ok(())
+}
+
+// 返回的句柄非 0，须用 release 释放；tool_shutdown 会释放全部句柄
+pub fn inhibit_sleep(reason: &str) -> Result<u64, ForgeFfiError> {
+    validate_reason(reason)?;
+    let (program, args) = platform::holder(reason, std::process::id())?;
+    let mut child = command(program)
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| map_spawn_error(program, e))?;
+    wait_started(&mut child, program, &args)?;
+
+    crate::runtime::register_shutdown_hook("power", release_all);
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    holders().insert(
+        handle,
+        Holder {
+            child,
+            reason: reason.to_string(),
+        },
+    );
+    Ok(handle)
+}
+
+fn wait_started(child: &mut Child, program: &str, args: &[String]) -> Result<(), ForgeFfiError> {
+    let deadline = Instant::now() + STARTUP_GRACE;
+    while Instant::now() < deadline {
+        match child.try_wait() {
+            Ok(None) => std::thread::sleep(STARTUP_POLL),
+            Ok(Some(status)) => {
+                let mut stderr = String::new();
+                if let Some(mut err) = child.stderr.take() {
+                    let _ = std::io::Read::read_to_string(&mut err, &mut stderr);
+                }
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                return Err(command_failed(
+                    program,
+                    &args,
+                    &stderr,
+                    format!("阻止睡眠失败: {program} 提前退出（{status}）: {}", stderr.trim()),
+                ));
+            }
+            Err(e) => return Err(ForgeFfiError::system_error(format!("检查 {program} 状态失败: {e}"))),
+        }
+    }
+    // 之后不再读 stderr，关掉管道避免持有者写满后阻塞
+    child.stderr = None;
+    Ok(())
+}
+
+pub fn release(handle: u64) -> Result<(), ForgeFfiError> {
+    let holder = holders()
+        .remove(&handle)
+        .ok_or_else(|| ForgeFfiError::not_found(format!("未找到睡眠抑制句柄: {handle}")))?;
+    stop(holder);
+    Ok(())
+}
+
+fn stop(mut holder: Holder) {
+    crate::runtime::log(
+        forgeffi_base::LogLevel::Debug,
+        format_args!("释放睡眠抑制: {}", holder.reason),
+    );
+    let _ = holder.child.kill();
+    let _ = holder.child.wait();
+}
+
+pub fn release_all() {
+    let all = std::mem::take(&mut *holders());
+    all.into_values().for_each(stop);
+}
+
+// 当前持有的句柄与对应 reason，按句柄升序
+pub fn active() -> Vec<(u64, String)> {
+    holders().iter().map(|(h, v)| (*h, v.reason.clone())).collect()
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn holder_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| (*s).to_string()).collect()
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+// 经 logind 取得 block 模式的 sleep/idle 抑制锁，锁随 systemd-inhibit 子进程存在；
+// tail --pid 在宿主进程退出后结束，锁随之释放。非 root 是否放行由 polkit 决定
+pub(super) fn holder(reason: &str, pid: u32) -> Result<(&'static str, Vec<String>), ForgeFfiError> {
+    let why = format!("--why={reason}");
+    let pid = format!("--pid={pid}");
+    Ok((
+        "systemd-inhibit",
+        holder_args(&[
+            "--what=sleep:idle",
+            "--who=forgeffi",
+            why.as_str(),
+            "--mode=block",
+            "tail",
+            pid.as_str(),
+            "-f",
+            "/dev/null",
+        ]),
+    ))
+}
@@ -0,0 +1,8 @@
+use super::*;
+
+// caffeinate 持有 PreventUserIdleSystemSleep / PreventSystemSleep 的 IOKit assertion，
+// -w 让它在宿主进程退出时一并退出。assertion 不带 reason，可通过 pmset -g assertions 看到 caffeinate
+pub(super) fn holder(_reason: &str, pid: u32) -> Result<(&'static str, Vec<String>), ForgeFfiError> {
+    let pid = pid.to_string();
+    Ok(("caffeinate", holder_args(&["-i", "-s", "-w", pid.as_str()])))
+}
@@ -0,0 +1,5 @@
+use super::*;
+
+pub(super) fn holder(_reason: &str, _pid: u32) -> Result<(&'static str, Vec<String>), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持阻止睡眠".to_string()))
+}
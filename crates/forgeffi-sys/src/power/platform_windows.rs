@@ -0,0 +1,15 @@
+use super::*;
+
+// SetThreadExecutionState 只对调用线程生效且本 crate 禁止 unsafe，
+// 因此由一个 PowerShell 子进程设置 ES_CONTINUOUS | ES_SYSTEM_REQUIRED 后等待宿主进程退出
+pub(super) fn holder(_reason: &str, pid: u32) -> Result<(&'static str, Vec<String>), ForgeFfiError> {
+    let script = format!(
+        "$k = Add-Type -Name ForgeFfiPower -Namespace ForgeFfi -PassThru -MemberDefinition '[DllImport(\"kernel32.dll\")] public static extern uint SetThreadExecutionState(uint f);'; \
+         if ($k::SetThreadExecutionState(0x80000001) -eq 0) {{ throw 'SetThreadExecutionState 失败' }}; \
+         Wait-Process -Id {pid} -ErrorAction SilentlyContinue"
+    );
+    Ok((
+        crate::util::powershell_program(),
+        holder_args(&["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script.as_str()]),
+    ))
+}
@@ -0,0 +1,79 @@
+use forgeffi_base::{ForgeFfiError, OpPrivilege, PrivilegeReport, PrivilegeVerdict, ABI_VERSION};
+use forgeffi_protocol::encode_json;
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use crate::util::{command, command_failed, map_spawn_error, CommandExt};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub use forgeffi_protocol::NETIF_OPS;
+
+// 只根据进程身份推断，不实际尝试任何修改
+pub fn report() -> Result<PrivilegeReport, ForgeFfiError> {
+    let mut r = platform::report()?;
+    r.abi = ABI_VERSION;
+    Ok(r)
+}
+
+pub fn report_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("权限报告", &report()?)
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn op(name: &str, verdict: PrivilegeVerdict, requires: Option<&str>, reason: Option<&str>) -> OpPrivilege {
+    OpPrivilege {
+        op: name.to_string(),
+        verdict,
+        requires: requires.map(str::to_string),
+        reason: reason.map(str::to_string),
+    }
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn allowed_if(ok: bool) -> PrivilegeVerdict {
+    if ok {
+        PrivilegeVerdict::Allowed
+    } else {
+        PrivilegeVerdict::Denied
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn run_capture(program: &str, args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(program, e))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(command_failed(
+            program,
+            args,
+            &stderr,
+            format!("命令失败: {program}: {}", stderr.trim()),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
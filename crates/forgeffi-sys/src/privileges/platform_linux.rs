@@ -0,0 +1,158 @@
+use super::*;
+
+// capability 位号 -> 名称（include/uapi/linux/capability.h）
+const CAP_NAMES: &[&str] = &[
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+const CAP_NET_ADMIN: u32 = 12;
+
+// 通过 ip(8)/ethtool/tc 直接操作内核的 op
+const IP_OPS: &[&str] = &[
+    "set_admin_state",
+    "set_mtu",
+    "add_ip",
+    "del_ip",
+    "create_vlan",
+    "delete_vlan",
+    "create_bridge",
+    "delete_bridge",
+    "add_bridge_member",
+    "remove_bridge_member",
+    "create_dummy",
+    "delete_interface",
+    "create_bond",
+    "delete_bond",
+    "add_bond_member",
+    "remove_bond_member",
+    "create_wireguard",
+    "set_wireguard_peer",
+    "remove_wireguard_peer",
+    "create_ip_tunnel",
+    "delete_tunnel",
+    "add_route",
+    "del_route",
+    "add_routing_rule",
+    "del_routing_rule",
+    "set_name",
+    "set_description",
+    "set_mac",
+    "set_link_speed",
+    "set_bandwidth_limit",
+];
+
+// 经 NetworkManager / systemd-resolved 的 D-Bus 接口，非 root 时由 polkit 决定
+const POLKIT_OPS: &[&str] = &[
+    "set_ipv4_dhcp",
+    "set_ipv4_static",
+    "set_ipv6_mode",
+    "set_ipv6_static",
+    "set_dns_suffix",
+];
+
+pub(super) fn report() -> Result<PrivilegeReport, ForgeFfiError> {
+    let text = std::fs::read_to_string("/proc/self/status")
+        .map_err(|e| ForgeFfiError::system_error(format!("读取 /proc/self/status 失败: {e}")))?;
+    let mut euid = None;
+    let mut cap_eff = 0u64;
+    let mut cap_amb = 0u64;
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            // Uid: real effective saved fs
+            "Uid" => euid = value.split_whitespace().nth(1).and_then(|s| s.parse::<u32>().ok()),
+            "CapEff" => cap_eff = u64::from_str_radix(value, 16).unwrap_or(0),
+            "CapAmb" => cap_amb = u64::from_str_radix(value, 16).unwrap_or(0),
+            _ => {}
+        }
+    }
+    let root = euid == Some(0);
+    // op 实际由子进程（ip/nmcli）执行：root 在 execve 后保留能力集，
+    // 非 root 进程的文件能力不会继承，只有 ambient 集会传给子进程
+    let child_caps = if root { cap_eff } else { cap_amb };
+    let net_admin = child_caps & (1 << CAP_NET_ADMIN) != 0;
+
+    let capabilities = (0..64u32)
+        .filter(|bit| cap_eff & (1u64 << bit) != 0)
+        .map(|bit| {
+            CAP_NAMES
+                .get(bit as usize)
+                .map_or_else(|| format!("CAP_{bit}"), |s| (*s).to_string())
+        })
+        .collect();
+
+    let ops = NETIF_OPS
+        .iter()
+        .map(|&name| {
+            if IP_OPS.contains(&name) {
+                let reason = (!net_admin && cap_eff & (1 << CAP_NET_ADMIN) != 0)
+                    .then_some("进程有 CAP_NET_ADMIN 但不在 ambient 集中，子进程 ip 不会继承");
+                op(name, allowed_if(net_admin), Some("CAP_NET_ADMIN"), reason)
+            } else if POLKIT_OPS.contains(&name) {
+                if root {
+                    op(name, PrivilegeVerdict::Allowed, Some("polkit"), None)
+                } else {
+                    op(
+                        name,
+                        PrivilegeVerdict::Unknown,
+                        Some("polkit"),
+                        Some("非 root 时由 polkit 策略决定（NetworkManager / systemd-resolved）"),
+                    )
+                }
+            } else {
+                op(name, PrivilegeVerdict::Unsupported, None, None)
+            }
+        })
+        .collect();
+
+    Ok(PrivilegeReport {
+        abi: ABI_VERSION,
+        elevated: root,
+        uid: euid,
+        capabilities,
+        ops,
+    })
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+// 通过 ifconfig 直接操作内核的 op，只有 root 能执行
+const IFCONFIG_OPS: &[&str] = &[
+    "set_admin_state",
+    "set_mtu",
+    "add_ip",
+    "del_ip",
+    "create_vlan",
+    "delete_vlan",
+    "create_bridge",
+    "delete_bridge",
+    "add_bridge_member",
+    "remove_bridge_member",
+    "create_dummy",
+    "delete_interface",
+    "set_mac",
+    "set_link_speed",
+];
+
+pub(super) fn report() -> Result<PrivilegeReport, ForgeFfiError> {
+    let uid = run_capture("id", &["-u"])?
+        .trim()
+        .parse::<u32>()
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 id -u 输出失败: {e}")))?;
+    let root = uid == 0;
+
+    let ops = NETIF_OPS
+        .iter()
+        .map(|&name| {
+            if IFCONFIG_OPS.contains(&name) {
+                op(name, allowed_if(root), Some("root"), None)
+            } else if matches!(
+                name,
+                "set_dns_registration"
+                    | "set_name"
+                    | "set_description"
+                    | "create_bond"
+                    | "delete_bond"
+                    | "add_bond_member"
+                    | "remove_bond_member"
+                    | "create_wireguard"
+                    | "set_wireguard_peer"
+                    | "remove_wireguard_peer"
+                    | "create_ip_tunnel"
+                    | "delete_tunnel"
+                    | "add_route"
+                    | "del_route"
+                    | "add_routing_rule"
+                    | "del_routing_rule"
+                    | "set_bandwidth_limit"
+            ) {
+                op(name, PrivilegeVerdict::Unsupported, None, None)
+            } else if root {
+                op(name, PrivilegeVerdict::Allowed, Some("admin"), None)
+            } else {
+                // networksetup 对 admin 组用户是否放行取决于系统授权设置
+                op(
+                    name,
+                    PrivilegeVerdict::Unknown,
+                    Some("admin"),
+                    Some("networksetup 修改配置需要管理员授权"),
+                )
+            }
+        })
+        .collect();
+
+    Ok(PrivilegeReport {
+        abi: ABI_VERSION,
+        elevated: root,
+        uid: Some(uid),
+        capabilities: Vec::new(),
+        ops,
+    })
+}
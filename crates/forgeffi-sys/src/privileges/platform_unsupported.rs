@@ -0,0 +1,5 @@
+use super::*;
+
+pub(super) fn report() -> Result<PrivilegeReport, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持权限检测".to_string()))
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+pub(super) fn report() -> Result<PrivilegeReport, ForgeFfiError> {
+    // UAC 开启时管理员账户未提权运行也会得到 False
+    let script = "([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)";
+    let text = run_capture(
+        crate::util::powershell_program(),
+        &["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script],
+    )?;
+    let admin = text.trim().eq_ignore_ascii_case("true");
+
+    let ops = NETIF_OPS
+        .iter()
+        .map(|&name| match name {
+            "switch_network_location"
+            | "set_service_order"
+            | "set_description"
+            | "create_wireguard"
+            | "set_wireguard_peer"
+            | "remove_wireguard_peer"
+            | "create_ip_tunnel"
+            | "delete_tunnel"
+            | "add_route"
+            | "del_route"
+            | "add_routing_rule"
+            | "del_routing_rule" => {
+                op(name, PrivilegeVerdict::Unsupported, None, None)
+            }
+            _ => op(name, allowed_if(admin), Some("Administrator"), None),
+        })
+        .collect();
+
+    Ok(PrivilegeReport {
+        abi: ABI_VERSION,
+        elevated: admin,
+        uid: None,
+        capabilities: Vec::new(),
+        ops,
+    })
+}
@@ -0,0 +1,127 @@
+use forgeffi_base::{ErrorCode, ForgeFfiError, LogLevel, RuntimeConfig};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static SERIALIZED: AtomicBool = AtomicBool::new(false);
+static MUTATION_LOCK: Mutex<()> = Mutex::new(());
+// 宿主没有调用 init 时不向 stderr 输出任何内容；init 后按配置（缺省 warn）
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Off as u8);
+pub type ShutdownHook = fn();
+
+static SHUTDOWN_HOOKS: Mutex<Vec<(&'static str, ShutdownHook)>> = Mutex::new(Vec::new());
+
+pub fn init(config: &RuntimeConfig) -> Result<(), ForgeFfiError> {
+    forgeffi_protocol::check_abi(config.abi)?;
+    // 后端固定可能失败，放在其它副作用之前
+    #[cfg(feature = "netif")]
+    {
+        crate::netif::set_pinned_backend(config.netif_backend)?;
+        crate::netif::set_cache_ttl_ms(config.netif_cache_ttl_ms);
+        crate::netif::set_rate_limit(config.netif_rate_limit);
+    }
+    LOG_LEVEL.store(config.log_level as u8, Ordering::Release);
+    crate::trace::set_capacity(config.trace_capacity);
+    SERIALIZED.store(config.serialize_mutations, Ordering::Release);
+    forgeffi_base::set_runtime_config(config.clone());
+    // 记录目录来自配置，必须在配置生效之后
+    #[cfg(feature = "netif")]
+    crate::netif::recover_pending_confirms();
+    if !INITIALIZED.swap(true, Ordering::AcqRel) {
+        log(LogLevel::Info, "runtime 已初始化");
+    }
+    Ok(())
+}
+
+// 返回实际生效的配置（缺省字段已填默认值）
+pub fn init_json(config_json: Option<&str>) -> Result<RuntimeConfig, ForgeFfiError> {
+    let config = match config_json {
+        Some(s) => serde_json::from_str(s)
+            .map_err(|e| ForgeFfiError::catalog(ErrorCode::InvalidArgument, "init_config_invalid", &[("detail", &e)]))?,
+        None => RuntimeConfig::v1(),
+    };
+    init(&config)?;
+    Ok(config)
+}
+
+// 最近一次 init 生效的配置；未 init 时为默认值
+pub fn config() -> RuntimeConfig {
+    forgeffi_base::runtime_config()
+}
+
+pub fn shutdown() {
+    let hooks = match SHUTDOWN_HOOKS.lock() {
+        Ok(g) => g.clone(),
+        Err(p) => p.into_inner().clone(),
+    };
+    for (name, hook) in hooks.iter().rev() {
+        log(LogLevel::Debug, format_args!("执行 shutdown hook: {name}"));
+        hook();
+    }
+
+    #[cfg(feature = "netif")]
+    crate::netif::clear_caches();
+
+    if INITIALIZED.swap(false, Ordering::AcqRel) {
+        log(LogLevel::Info, "runtime 已关闭");
+    }
+}
+
+pub fn set_serialized(on: bool) {
+    SERIALIZED.store(on, Ordering::Release);
+}
+
+pub fn is_serialized() -> bool {
+    SERIALIZED.load(Ordering::Acquire)
+}
+
+// 串行模式下修改类调用互斥执行；非串行模式直接调用。f 内不得再次进入 exclusive（锁不可重入）
+pub fn exclusive<T>(f: impl FnOnce() -> T) -> T {
+    if !is_serialized() {
+        return f();
+    }
+    let _guard = match MUTATION_LOCK.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    f()
+}
+
+pub fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
+
+pub fn register_shutdown_hook(name: &'static str, hook: ShutdownHook) {
+    let mut g = match SHUTDOWN_HOOKS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    if !g.iter().any(|(n, _)| *n == name) {
+        g.push((name, hook));
+    }
+}
+
+pub fn log_level() -> LogLevel {
+    match LOG_LEVEL.load(Ordering::Acquire) {
+        0 => LogLevel::Off,
+        1 => LogLevel::Error,
+        2 => LogLevel::Warn,
+        3 => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+pub fn log(level: LogLevel, msg: impl Display) {
+    if level > log_level() {
+        return;
+    }
+    let tag = match level {
+        LogLevel::Off => return,
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warn",
+        LogLevel::Info => "info",
+        LogLevel::Debug => "debug",
+    };
+    eprintln!("[forgeffi][{tag}] {msg}");
+}
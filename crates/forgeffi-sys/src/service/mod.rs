@@ -0,0 +1,178 @@
+use forgeffi_base::{
+    ErrorCode, ForgeFfiError, ServiceApplyRequest, ServiceApplyResponse, ServiceInfo, ServiceListResponse,
+    ServiceManager, ServiceOp, ServiceOpResult, ServiceStartMode, ServiceState, ServiceStatusRequest, ABI_VERSION,
+};
+use forgeffi_protocol::{
+    decode_request, encode_json, validate_service_apply_request, validate_service_name,
+    validate_service_status_request, SERVICE_OPS,
+};
+
+use crate::util::{command, command_failed, map_spawn_error, CommandExt};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+// 当前系统的服务管理器；Linux 上没有以 systemd 启动时为空
+pub fn manager() -> Option<ServiceManager> {
+    platform::manager()
+}
+
+// 服务管理器不可用时为空
+pub fn supported_ops() -> Vec<&'static str> {
+    if manager().is_some() {
+        SERVICE_OPS.to_vec()
+    } else {
+        Vec::new()
+    }
+}
+
+pub fn list() -> Result<ServiceListResponse, ForgeFfiError> {
+    let manager = manager().ok_or_else(no_manager)?;
+    let mut items = platform::list()?;
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(ServiceListResponse {
+        abi: ABI_VERSION,
+        manager: Some(manager),
+        supported_ops: supported_ops().into_iter().map(str::to_string).collect(),
+        items,
+    })
+}
+
+pub fn status(name: &str) -> Result<ServiceInfo, ForgeFfiError> {
+    validate_service_name(name)?;
+    manager().ok_or_else(no_manager)?;
+    platform::status(name)
+}
+
+pub fn apply_request(req: &ServiceApplyRequest) -> Result<ServiceApplyResponse, ForgeFfiError> {
+    validate_service_apply_request(req)?;
+    manager().ok_or_else(no_manager)?;
+    let mut results = Vec::with_capacity(req.ops.len());
+    let mut all_ok = true;
+    let mut touched: Vec<&str> = Vec::new();
+    for (i, op) in req.ops.iter().enumerate() {
+        let r = crate::runtime::exclusive(|| platform::run_op(op));
+        all_ok &= r.is_ok();
+        results.push(ServiceOpResult {
+            i,
+            ok: r.is_ok(),
+            error: r.err(),
+        });
+        if !touched.contains(&op.name()) {
+            touched.push(op.name());
+        }
+    }
+    Ok(ServiceApplyResponse {
+        abi: ABI_VERSION,
+        ok: all_ok,
+        results,
+        last_known: touched.into_iter().filter_map(|n| platform::status(n).ok()).collect(),
+    })
+}
+
+pub fn list_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("服务列表", &list()?)
+}
+
+pub fn status_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: ServiceStatusRequest = decode_request(req_json)?;
+    validate_service_status_request(&req)?;
+    encode_json("服务状态", &status(&req.name)?)
+}
+
+pub fn apply_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: ServiceApplyRequest = decode_request(req_json)?;
+    encode_json("服务 apply 响应", &apply_request(&req)?)
+}
+
+fn no_manager() -> ForgeFfiError {
+    ForgeFfiError::unsupported("当前系统没有可用的服务管理器（Linux 需要以 systemd 启动）")
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn run_capture(program: &str, args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(program, e))?;
+    if out.status.success() {
+        return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    // launchctl 把部分错误写到 stdout
+    let msg = if stderr.trim().is_empty() { stdout } else { stderr };
+    let err = command_failed(
+        program,
+        args,
+        &msg,
+        format!("命令失败: {program} {}: {}", args.join(" "), msg.trim()),
+    );
+    Err(classify_failure(err))
+}
+
+// 服务管理器的错误只能从输出文本区分权限不足与服务不存在
+fn classify_failure(err: ForgeFfiError) -> ForgeFfiError {
+    let s = err.message.to_lowercase();
+    let code = if s.contains("access denied")
+        || s.contains("access is denied")
+        || s.contains("permission denied")
+        || s.contains("interactive authentication required")
+        || s.contains("operation not permitted")
+        || s.contains("not privileged")
+    {
+        ErrorCode::PermissionDenied
+    } else if s.contains("not found")
+        || s.contains("not loaded")
+        || s.contains("does not exist")
+        || s.contains("could not find")
+        || s.contains("cannot find any service")
+        || s.contains("no such process")
+    {
+        ErrorCode::NotFound
+    } else {
+        return err;
+    };
+    let message = err.message.clone();
+    err.recode(code, message)
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn not_found(name: &str) -> ForgeFfiError {
+    ForgeFfiError::not_found(format!("未找到服务: {name}"))
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn info(name: &str, state: ServiceState) -> ServiceInfo {
+    ServiceInfo {
+        name: name.to_string(),
+        display_name: None,
+        state,
+        start_mode: None,
+        pid: None,
+    }
+}
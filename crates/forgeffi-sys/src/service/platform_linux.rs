@@ -0,0 +1,106 @@
+use super::*;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub(super) fn manager() -> Option<ServiceManager> {
+    Path::new("/run/systemd/system").exists().then_some(ServiceManager::Systemd)
+}
+
+// list-units 只含已加载的 unit；未加载但已安装的 unit 从 list-unit-files 补齐，状态为 stopped
+pub(super) fn list() -> Result<Vec<ServiceInfo>, ForgeFfiError> {
+    let units = run_capture(
+        "systemctl",
+        &["list-units", "--type=service", "--all", "--no-legend", "--no-pager", "--plain"],
+    )?;
+    let files = run_capture(
+        "systemctl",
+        &["list-unit-files", "--type=service", "--no-legend", "--no-pager"],
+    )?;
+    let mut modes: BTreeMap<&str, Option<ServiceStartMode>> = files
+        .lines()
+        .filter_map(|l| {
+            let mut f = l.split_whitespace();
+            Some((f.next()?, start_mode(f.next()?)))
+        })
+        // 模板本身（foo@.service）不能直接启停
+        .filter(|(name, _)| !name.ends_with("@.service"))
+        .collect();
+
+    let mut items: Vec<ServiceInfo> = units
+        .lines()
+        .filter_map(|l| {
+            // 未找到/失败的 unit 行首带状态符号
+            let mut f = l.trim_start_matches(['●', '*', ' ']).split_whitespace();
+            let (name, load, active, sub) = (f.next()?, f.next()?, f.next()?, f.next()?);
+            if load == "not-found" {
+                return None;
+            }
+            let desc = f.collect::<Vec<_>>().join(" ");
+            Some(ServiceInfo {
+                display_name: Some(desc).filter(|d| !d.is_empty()),
+                start_mode: modes.remove(name).flatten(),
+                ..info(name, state(active, sub))
+            })
+        })
+        .collect();
+    items.extend(modes.into_iter().map(|(name, mode)| ServiceInfo {
+        start_mode: mode,
+        ..info(name, ServiceState::Stopped)
+    }));
+    Ok(items)
+}
+
+pub(super) fn status(name: &str) -> Result<ServiceInfo, ForgeFfiError> {
+    let text = run_capture(
+        "systemctl",
+        &[
+            "show",
+            name,
+            "--no-pager",
+            "--property=Id,Description,LoadState,ActiveState,SubState,UnitFileState,MainPID",
+        ],
+    )?;
+    let kv: BTreeMap<&str, &str> = text.lines().filter_map(|l| l.split_once('=')).collect();
+    let get = |k: &str| kv.get(k).copied().unwrap_or_default();
+    if get("LoadState") == "not-found" {
+        return Err(not_found(name));
+    }
+    Ok(ServiceInfo {
+        display_name: Some(get("Description").to_string()).filter(|d| !d.is_empty()),
+        start_mode: start_mode(get("UnitFileState")),
+        pid: get("MainPID").parse().ok().filter(|p| *p != 0),
+        ..info(
+            if get("Id").is_empty() { name } else { get("Id") },
+            state(get("ActiveState"), get("SubState")),
+        )
+    })
+}
+
+pub(super) fn run_op(op: &ServiceOp) -> Result<(), ForgeFfiError> {
+    let verb = forgeffi_protocol::service_op_tag(op);
+    run_capture("systemctl", &[verb, "--no-ask-password", op.name()]).map(|_| ())
+}
+
+fn state(active: &str, sub: &str) -> ServiceState {
+    match active {
+        // oneshot 服务执行完后为 active (exited)，视为已停止
+        "active" if sub == "exited" => ServiceState::Stopped,
+        "active" | "reloading" => ServiceState::Running,
+        "inactive" => ServiceState::Stopped,
+        "activating" => ServiceState::Starting,
+        "deactivating" => ServiceState::Stopping,
+        "failed" => ServiceState::Failed,
+        _ => ServiceState::Unknown,
+    }
+}
+
+// static/indirect 等不能 enable 的 unit 只能被依赖拉起，按 manual 处理
+fn start_mode(s: &str) -> Option<ServiceStartMode> {
+    match s {
+        "enabled" | "enabled-runtime" | "alias" => Some(ServiceStartMode::Auto),
+        "disabled" | "static" | "indirect" | "generated" | "transient" => Some(ServiceStartMode::Manual),
+        "masked" | "masked-runtime" => Some(ServiceStartMode::Disabled),
+        _ => None,
+    }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+pub(super) fn manager() -> Option<ServiceManager> {
+    Some(ServiceManager::Launchd)
+}
+
+// root 管理 system 域（LaunchDaemons），普通用户管理自己的 gui/<uid> 域（LaunchAgents）
+fn domain() -> Result<String, ForgeFfiError> {
+    let uid = run_capture("id", &["-u"])?;
+    let uid = uid.trim();
+    Ok(if uid == "0" {
+        "system".to_string()
+    } else {
+        format!("gui/{uid}")
+    })
+}
+
+// `launchctl list`：PID、上次退出状态、label，以 tab 分隔，首行为表头
+pub(super) fn list() -> Result<Vec<ServiceInfo>, ForgeFfiError> {
+    let disabled = disabled_map(&domain()?);
+    let text = run_capture("launchctl", &["list"])?;
+    Ok(text
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let mut f = l.split('\t');
+            let (pid, status, label) = (f.next()?, f.next()?, f.next()?.trim());
+            let pid = pid.trim().parse::<u32>().ok();
+            let state = match (pid, status.trim()) {
+                (Some(_), _) => ServiceState::Running,
+                (None, "0") => ServiceState::Stopped,
+                (None, _) => ServiceState::Failed,
+            };
+            Some(ServiceInfo {
+                pid,
+                start_mode: Some(start_mode(&disabled, label)),
+                ..info(label, state)
+            })
+        })
+        .collect())
+}
+
+// `launchctl list <label>` 输出形如 "PID" = 123; 的属性列表
+pub(super) fn status(name: &str) -> Result<ServiceInfo, ForgeFfiError> {
+    let text = run_capture("launchctl", &["list", name])?;
+    let field = |key: &str| {
+        text.lines().find_map(|l| {
+            let (k, v) = l.trim().split_once(" = ")?;
+            (k.trim_matches('"') == key).then(|| v.trim_end_matches(';').trim_matches('"').to_string())
+        })
+    };
+    let pid = field("PID").and_then(|p| p.parse::<u32>().ok());
+    let state = match (pid, field("LastExitStatus").as_deref()) {
+        (Some(_), _) => ServiceState::Running,
+        (None, Some("0") | None) => ServiceState::Stopped,
+        (None, Some(_)) => ServiceState::Failed,
+    };
+    let disabled = disabled_map(&domain()?);
+    Ok(ServiceInfo {
+        pid,
+        start_mode: Some(start_mode(&disabled, name)),
+        ..info(name, state)
+    })
+}
+
+pub(super) fn run_op(op: &ServiceOp) -> Result<(), ForgeFfiError> {
+    let target = format!("{}/{}", domain()?, op.name());
+    let target = target.as_str();
+    let args = match op {
+        ServiceOp::Start { .. } => vec!["kickstart", target],
+        ServiceOp::Stop { .. } => vec!["kill", "SIGTERM", target],
+        ServiceOp::Restart { .. } => vec!["kickstart", "-k", target],
+        ServiceOp::Enable { .. } => vec!["enable", target],
+        ServiceOp::Disable { .. } => vec!["disable", target],
+    };
+    run_capture("launchctl", &args).map(|_| ())
+}
+
+// `launchctl print-disabled <domain>`：每行 "label" => disabled|enabled（旧版本为 true|false）
+fn disabled_map(domain: &str) -> BTreeMap<String, bool> {
+    let Ok(text) = run_capture("launchctl", &["print-disabled", domain]) else {
+        return BTreeMap::new();
+    };
+    text.lines()
+        .filter_map(|l| {
+            let (k, v) = l.trim().split_once("=>")?;
+            let disabled = matches!(v.trim(), "disabled" | "true");
+            Some((k.trim().trim_matches('"').to_string(), disabled))
+        })
+        .collect()
+}
+
+// launchd 没有“手动”概念：已加载且未被禁用的 job 按其 plist 的 RunAtLoad/KeepAlive 启动
+fn start_mode(disabled: &BTreeMap<String, bool>, label: &str) -> ServiceStartMode {
+    if disabled.get(label).copied().unwrap_or(false) {
+        ServiceStartMode::Disabled
+    } else {
+        ServiceStartMode::Auto
+    }
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+pub(super) fn manager() -> Option<ServiceManager> {
+    None
+}
+
+pub(super) fn list() -> Result<Vec<ServiceInfo>, ForgeFfiError> {
+    Err(no_manager())
+}
+
+pub(super) fn status(_name: &str) -> Result<ServiceInfo, ForgeFfiError> {
+    Err(no_manager())
+}
+
+pub(super) fn run_op(_op: &ServiceOp) -> Result<(), ForgeFfiError> {
+    Err(no_manager())
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+use serde_json::Value;
+
+pub(super) fn manager() -> Option<ServiceManager> {
+    Some(ServiceManager::Scm)
+}
+
+fn powershell(script: &str) -> Result<String, ForgeFfiError> {
+    run_capture(
+        crate::util::powershell_program(),
+        &["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script],
+    )
+}
+
+// 服务名已通过 validate_service_name，不含引号；这里仍按 PowerShell 单引号规则转义
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+const SELECT: &str = "Select-Object Name,DisplayName,State,StartMode,ProcessId | ConvertTo-Json -Compress";
+
+pub(super) fn list() -> Result<Vec<ServiceInfo>, ForgeFfiError> {
+    let text = powershell(&format!("@(Get-CimInstance Win32_Service | {SELECT})"))?;
+    Ok(parse(&text)?.iter().filter_map(service_info).collect())
+}
+
+pub(super) fn status(name: &str) -> Result<ServiceInfo, ForgeFfiError> {
+    let text = powershell(&format!(
+        "@(Get-CimInstance Win32_Service -Filter \"Name={}\" | {SELECT})",
+        quote(name)
+    ))?;
+    parse(&text)?.iter().find_map(service_info).ok_or_else(|| not_found(name))
+}
+
+pub(super) fn run_op(op: &ServiceOp) -> Result<(), ForgeFfiError> {
+    let name = quote(op.name());
+    let script = match op {
+        ServiceOp::Start { .. } => format!("Start-Service -Name {name}"),
+        ServiceOp::Stop { .. } => format!("Stop-Service -Name {name} -Force"),
+        ServiceOp::Restart { .. } => format!("Restart-Service -Name {name} -Force"),
+        ServiceOp::Enable { .. } => format!("Set-Service -Name {name} -StartupType Automatic"),
+        ServiceOp::Disable { .. } => format!("Set-Service -Name {name} -StartupType Disabled"),
+    };
+    powershell(&format!("$ErrorActionPreference = 'Stop'; {script} | Out-Null")).map(|_| ())
+}
+
+// ConvertTo-Json 对单个元素不输出数组，空结果输出空串
+fn parse(text: &str) -> Result<Vec<Value>, ForgeFfiError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    match serde_json::from_str(text) {
+        Ok(Value::Array(v)) => Ok(v),
+        Ok(v) => Ok(vec![v]),
+        Err(e) => Err(ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}"))),
+    }
+}
+
+fn service_info(v: &Value) -> Option<ServiceInfo> {
+    let name = v.get("Name")?.as_str()?;
+    let state = match v.get("State").and_then(Value::as_str).unwrap_or_default() {
+        "Running" => ServiceState::Running,
+        "Stopped" => ServiceState::Stopped,
+        "Start Pending" | "Continue Pending" => ServiceState::Starting,
+        "Stop Pending" | "Pause Pending" => ServiceState::Stopping,
+        _ => ServiceState::Unknown,
+    };
+    let start_mode = match v.get("StartMode").and_then(Value::as_str).unwrap_or_default() {
+        // Boot/System 为驱动的加载时机
+        "Auto" | "Boot" | "System" => Some(ServiceStartMode::Auto),
+        "Manual" => Some(ServiceStartMode::Manual),
+        "Disabled" => Some(ServiceStartMode::Disabled),
+        _ => None,
+    };
+    Some(ServiceInfo {
+        display_name: v.get("DisplayName").and_then(Value::as_str).map(str::to_string),
+        start_mode,
+        pid: v
+            .get("ProcessId")
+            .and_then(Value::as_u64)
+            .and_then(|p| u32::try_from(p).ok())
+            .filter(|p| *p != 0),
+        ..info(name, state)
+    })
+}
@@ -0,0 +1,119 @@
+use forgeffi_base::{
+    ForgeFfiError, IpFamily, SocketEntry, SocketListRequest, SocketListResponse, SocketProtocol,
+    SocketState, ABI_VERSION,
+};
+use forgeffi_protocol::{check_abi, decode_request, encode_json};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub fn list() -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    let mut items = platform::list()?;
+    items.sort_by(|a, b| {
+        (a.protocol, a.family, a.local_port, &a.local_address, a.remote_port, &a.remote_address).cmp(&(
+            b.protocol,
+            b.family,
+            b.local_port,
+            &b.local_address,
+            b.remote_port,
+            &b.remote_address,
+        ))
+    });
+    items.dedup();
+    Ok(items)
+}
+
+pub fn list_filtered(req: &SocketListRequest) -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    check_abi(req.abi)?;
+    let mut items = list()?;
+    items.retain(|e| {
+        req.protocol.is_none_or(|p| p == e.protocol) && (!req.listening_only || is_listening(e))
+    });
+    Ok(items)
+}
+
+pub fn list_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: SocketListRequest = match req_json {
+        Some(s) => decode_request(s)?,
+        None => SocketListRequest::v1(),
+    };
+    let items = list_filtered(&req)?;
+    encode_json("socket 列表", &SocketListResponse {
+        abi: ABI_VERSION,
+        items,
+    })
+}
+
+fn is_listening(e: &SocketEntry) -> bool {
+    match e.protocol {
+        SocketProtocol::Tcp => e.state == Some(SocketState::Listen),
+        SocketProtocol::Udp => e.remote_port.is_none_or(|p| p == 0),
+    }
+}
+
+// Linux 内核 TCP 状态编号（include/net/tcp_states.h）
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn tcp_state_from_linux(st: u8) -> SocketState {
+    match st {
+        1 => SocketState::Established,
+        2 => SocketState::SynSent,
+        3 | 12 => SocketState::SynReceived,
+        4 => SocketState::FinWait1,
+        5 => SocketState::FinWait2,
+        6 => SocketState::TimeWait,
+        7 => SocketState::Closed,
+        8 => SocketState::CloseWait,
+        9 => SocketState::LastAck,
+        10 => SocketState::Listen,
+        11 => SocketState::Closing,
+        _ => SocketState::Unknown,
+    }
+}
+
+// 兼容 netstat/lsof（SYN_SENT）与 PowerShell（SynSent）两种写法
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn tcp_state_from_name(s: &str) -> SocketState {
+    let norm: String = s
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match norm.as_str() {
+        "listen" => SocketState::Listen,
+        "synsent" => SocketState::SynSent,
+        "synreceived" | "synrecv" | "synrcvd" => SocketState::SynReceived,
+        "established" => SocketState::Established,
+        "finwait1" => SocketState::FinWait1,
+        "finwait2" => SocketState::FinWait2,
+        "closewait" => SocketState::CloseWait,
+        "closing" => SocketState::Closing,
+        "lastack" => SocketState::LastAck,
+        "timewait" => SocketState::TimeWait,
+        "closed" => SocketState::Closed,
+        _ => SocketState::Unknown,
+    }
+}
+
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn family_of(addr: &str) -> IpFamily {
+    if addr.contains(':') {
+        IpFamily::Ipv6
+    } else {
+        IpFamily::Ipv4
+    }
+}
@@ -0,0 +1,265 @@
+use super::*;
+
+use nix::errno::Errno;
+use nix::sys::socket::{
+    recv, sendto, setsockopt, socket, sockopt, AddressFamily, MsgFlags, NetlinkAddr, SockFlag,
+    SockProtocol, SockType,
+};
+use nix::sys::time::{TimeVal, TimeValLike};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::fd::AsRawFd;
+
+const NLMSG_HDR_LEN: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_MSG_LEN: usize = 72;
+
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+
+struct RawSocket {
+    entry: SocketEntry,
+    inode: u64,
+}
+
+pub(super) fn list() -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    // sock_diag 一次 dump 即可拿到全部 socket；被 seccomp/LSM 拦截时退回 /proc/net
+    let raw = match sock_diag_dump() {
+        Ok(v) => v,
+        Err(_) => proc_net_dump()?,
+    };
+    let owners = socket_owners();
+    Ok(raw
+        .into_iter()
+        .map(|mut r| {
+            if let Some((pid, name)) = owners.get(&r.inode) {
+                r.entry.pid = Some(*pid);
+                r.entry.process_name = name.clone();
+            }
+            r.entry
+        })
+        .collect())
+}
+
+fn sock_diag_dump() -> Result<Vec<RawSocket>, ForgeFfiError> {
+    let fd = socket(
+        AddressFamily::Netlink,
+        SockType::Datagram,
+        SockFlag::SOCK_CLOEXEC,
+        SockProtocol::NetlinkSockDiag,
+    )
+    .map_err(map_errno)?;
+    setsockopt(&fd, sockopt::ReceiveTimeout, &TimeVal::seconds(2)).map_err(map_errno)?;
+
+    let mut out = Vec::new();
+    let mut seq = 0u32;
+    let mut buf = vec![0u8; 64 * 1024];
+    for family in [AF_INET, AF_INET6] {
+        for proto in [IPPROTO_TCP, IPPROTO_UDP] {
+            seq += 1;
+            let req = build_diag_request(seq, family, proto);
+            sendto(fd.as_raw_fd(), &req, &NetlinkAddr::new(0, 0), MsgFlags::empty())
+                .map_err(map_errno)?;
+            'recv: loop {
+                let n = recv(fd.as_raw_fd(), &mut buf, MsgFlags::empty()).map_err(map_errno)?;
+                let mut data = &buf[..n];
+                while data.len() >= NLMSG_HDR_LEN {
+                    let len = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]) as usize;
+                    let ty = u16::from_ne_bytes([data[4], data[5]]);
+                    if len < NLMSG_HDR_LEN || len > data.len() {
+                        break;
+                    }
+                    match ty {
+                        NLMSG_DONE => break 'recv,
+                        NLMSG_ERROR => {
+                            return Err(ForgeFfiError::system_error(
+                                "sock_diag 返回错误".to_string(),
+                            ));
+                        }
+                        SOCK_DIAG_BY_FAMILY => {
+                            if let Some(r) = parse_diag_msg(&data[NLMSG_HDR_LEN..len], proto) {
+                                out.push(r);
+                            }
+                        }
+                        _ => {}
+                    }
+                    data = &data[(len + 3) & !3..];
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn build_diag_request(seq: u32, family: u8, proto: u8) -> Vec<u8> {
+    // nlmsghdr(16) + inet_diag_req_v2(56)
+    let len = NLMSG_HDR_LEN + 56;
+    let mut m = Vec::with_capacity(len);
+    m.extend_from_slice(&(len as u32).to_ne_bytes());
+    m.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    m.extend_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+    m.extend_from_slice(&seq.to_ne_bytes());
+    m.extend_from_slice(&0u32.to_ne_bytes());
+    m.extend_from_slice(&[family, proto, 0, 0]);
+    // idiag_states：全部状态
+    m.extend_from_slice(&u32::MAX.to_ne_bytes());
+    m.resize(len, 0);
+    m
+}
+
+fn parse_diag_msg(p: &[u8], proto: u8) -> Option<RawSocket> {
+    if p.len() < INET_DIAG_MSG_LEN {
+        return None;
+    }
+    let family = p[0];
+    let sport = u16::from_be_bytes([p[4], p[5]]);
+    let dport = u16::from_be_bytes([p[6], p[7]]);
+    let (src, dst) = match family {
+        AF_INET => (
+            Ipv4Addr::new(p[8], p[9], p[10], p[11]).to_string(),
+            Ipv4Addr::new(p[24], p[25], p[26], p[27]).to_string(),
+        ),
+        AF_INET6 => (
+            Ipv6Addr::from(<[u8; 16]>::try_from(&p[8..24]).ok()?).to_string(),
+            Ipv6Addr::from(<[u8; 16]>::try_from(&p[24..40]).ok()?).to_string(),
+        ),
+        _ => return None,
+    };
+    let inode = u32::from_ne_bytes([p[68], p[69], p[70], p[71]]);
+    Some(raw_socket(family == AF_INET6, proto, src, sport, dst, dport, p[1], u64::from(inode)))
+}
+
+fn proc_net_dump() -> Result<Vec<RawSocket>, ForgeFfiError> {
+    let mut out = Vec::new();
+    for (file, v6, proto) in [
+        ("/proc/net/tcp", false, IPPROTO_TCP),
+        ("/proc/net/tcp6", true, IPPROTO_TCP),
+        ("/proc/net/udp", false, IPPROTO_UDP),
+        ("/proc/net/udp6", true, IPPROTO_UDP),
+    ] {
+        // 内核关闭 IPv6 时 tcp6/udp6 不存在
+        let Ok(text) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        for line in text.lines().skip(1) {
+            let f: Vec<&str> = line.split_whitespace().collect();
+            if f.len() < 10 {
+                continue;
+            }
+            let (Some((src, sport)), Some((dst, dport))) = (parse_proc_addr(f[1]), parse_proc_addr(f[2]))
+            else {
+                continue;
+            };
+            let st = u8::from_str_radix(f[3], 16).unwrap_or(0);
+            let inode = f[9].parse::<u64>().unwrap_or(0);
+            out.push(raw_socket(v6, proto, src, sport, dst, dport, st, inode));
+        }
+    }
+    if out.is_empty() && !std::path::Path::new("/proc/net/tcp").exists() {
+        return Err(ForgeFfiError::unsupported("无法读取 /proc/net".to_string()));
+    }
+    Ok(out)
+}
+
+// /proc/net 中地址为按 32 位字输出的主机字节序十六进制，端口为普通十六进制
+fn parse_proc_addr(s: &str) -> Option<(String, u16)> {
+    let (addr, port) = s.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for i in (0..addr.len()).step_by(8) {
+        let word = u32::from_str_radix(addr.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string(),
+        16 => Ipv6Addr::from(<[u8; 16]>::try_from(bytes.as_slice()).ok()?).to_string(),
+        _ => return None,
+    };
+    Some((ip, port))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn raw_socket(
+    v6: bool,
+    proto: u8,
+    src: String,
+    sport: u16,
+    dst: String,
+    dport: u16,
+    st: u8,
+    inode: u64,
+) -> RawSocket {
+    let (protocol, state) = if proto == IPPROTO_TCP {
+        (SocketProtocol::Tcp, Some(tcp_state_from_linux(st)))
+    } else {
+        (SocketProtocol::Udp, None)
+    };
+    let connected = dport != 0;
+    RawSocket {
+        entry: SocketEntry {
+            protocol,
+            family: if v6 { IpFamily::Ipv6 } else { IpFamily::Ipv4 },
+            local_address: src,
+            local_port: sport,
+            remote_address: connected.then_some(dst),
+            remote_port: connected.then_some(dport),
+            state,
+            pid: None,
+            process_name: None,
+        },
+        inode,
+    }
+}
+
+// 遍历 /proc/<pid>/fd 建立 inode -> 进程 的映射；无权限读取的进程直接跳过
+fn socket_owners() -> HashMap<u64, (u32, Option<String>)> {
+    let mut out = HashMap::new();
+    let Ok(procs) = std::fs::read_dir("/proc") else {
+        return out;
+    };
+    for p in procs.flatten() {
+        let Some(pid) = p.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(p.path().join("fd")) else {
+            continue;
+        };
+        let mut name: Option<Option<String>> = None;
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let Some(inode) = target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let name = name
+                .get_or_insert_with(|| {
+                    std::fs::read_to_string(p.path().join("comm"))
+                        .ok()
+                        .map(|s| s.trim().to_string())
+                })
+                .clone();
+            out.entry(inode).or_insert((pid, name));
+        }
+    }
+    out
+}
+
+fn map_errno(e: Errno) -> ForgeFfiError {
+    match e {
+        Errno::EPERM | Errno::EACCES => ForgeFfiError::permission_denied(format!("sock_diag: {e}")),
+        _ => ForgeFfiError::system_error(format!("sock_diag: {e}")),
+    }
+    .with_errno(e as i32)
+}
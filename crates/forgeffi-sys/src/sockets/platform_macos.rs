@@ -0,0 +1,123 @@
+use super::*;
+
+use crate::util::{command, map_spawn_error};
+
+// 非 root 时 lsof 只能看到当前用户的进程
+pub(super) fn list() -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    let out = command("lsof")
+        .args(["-nP", "-iTCP", "-iUDP", "-FpctPnT"])
+        .output()
+        .map_err(|e| map_spawn_error("lsof", e))?;
+    // 没有匹配的 socket 时 lsof 以 1 退出且不输出内容
+    if !out.status.success() && !out.stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ForgeFfiError::system_error(format!("命令失败: lsof: {}", stderr.trim())));
+    }
+    Ok(parse_lsof(&String::from_utf8_lossy(&out.stdout)))
+}
+
+#[derive(Default)]
+struct Pending {
+    v6: Option<bool>,
+    proto: Option<SocketProtocol>,
+    name: Option<String>,
+    state: Option<String>,
+}
+
+fn parse_lsof(text: &str) -> Vec<SocketEntry> {
+    let mut out = Vec::new();
+    let mut pid: Option<u32> = None;
+    let mut cmd: Option<String> = None;
+    let mut cur: Option<Pending> = None;
+
+    for line in text.lines() {
+        let Some(tag) = line.chars().next() else {
+            continue;
+        };
+        let val = &line[tag.len_utf8()..];
+        match tag {
+            'p' => {
+                flush(&mut out, cur.take(), pid, &cmd);
+                pid = val.parse().ok();
+                cmd = None;
+            }
+            'c' => cmd = Some(val.to_string()),
+            'f' => {
+                flush(&mut out, cur.take(), pid, &cmd);
+                cur = Some(Pending::default());
+            }
+            't' => {
+                if let Some(c) = cur.as_mut() {
+                    c.v6 = Some(val == "IPv6");
+                }
+            }
+            'P' => {
+                if let Some(c) = cur.as_mut() {
+                    c.proto = match val {
+                        "TCP" => Some(SocketProtocol::Tcp),
+                        "UDP" => Some(SocketProtocol::Udp),
+                        _ => None,
+                    };
+                }
+            }
+            'n' => {
+                if let Some(c) = cur.as_mut() {
+                    c.name = Some(val.to_string());
+                }
+            }
+            'T' => {
+                if let (Some(c), Some(st)) = (cur.as_mut(), val.strip_prefix("ST=")) {
+                    c.state = Some(st.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(&mut out, cur, pid, &cmd);
+    out
+}
+
+fn flush(out: &mut Vec<SocketEntry>, p: Option<Pending>, pid: Option<u32>, cmd: &Option<String>) {
+    let Some(p) = p else {
+        return;
+    };
+    let (Some(protocol), Some(name)) = (p.proto, p.name) else {
+        return;
+    };
+    let v6 = p.v6.unwrap_or(false);
+    let (local, remote) = match name.split_once("->") {
+        Some((l, r)) => (l, Some(r)),
+        None => (name.as_str(), None),
+    };
+    let Some((local_address, local_port)) = split_endpoint(local, v6) else {
+        return;
+    };
+    let remote = remote.and_then(|r| split_endpoint(r, v6));
+    out.push(SocketEntry {
+        protocol,
+        family: if v6 { IpFamily::Ipv6 } else { family_of(&local_address) },
+        local_address,
+        local_port,
+        remote_address: remote.as_ref().map(|r| r.0.clone()),
+        remote_port: remote.map(|r| r.1),
+        state: match protocol {
+            SocketProtocol::Tcp => p.state.as_deref().map(tcp_state_from_name),
+            SocketProtocol::Udp => None,
+        },
+        pid,
+        process_name: cmd.clone(),
+    });
+}
+
+// "*:80"、"127.0.0.1:80"、"[::1]:80"、"[fe80::1%lo0]:80"
+fn split_endpoint(s: &str, v6: bool) -> Option<(String, u16)> {
+    let (addr, port) = s.rsplit_once(':')?;
+    let port = if port == "*" { 0 } else { port.parse().ok()? };
+    let addr = addr.trim_start_matches('[').trim_end_matches(']');
+    let addr = match addr {
+        "*" if v6 => "::".to_string(),
+        "*" => "0.0.0.0".to_string(),
+        a => a.to_string(),
+    };
+    Some((addr, port))
+}
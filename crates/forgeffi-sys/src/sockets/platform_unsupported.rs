@@ -0,0 +1,5 @@
+use super::*;
+
+pub(super) fn list() -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 socket 枚举".to_string()))
+}
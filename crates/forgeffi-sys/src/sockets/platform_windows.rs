@@ -0,0 +1,75 @@
+use super::*;
+
+use crate::util::{map_spawn_error, powershell_command, powershell_program, CommandExt};
+use serde_json::Value;
+
+pub(super) fn list() -> Result<Vec<SocketEntry>, ForgeFfiError> {
+    // Get-NetTCPConnection/Get-NetUDPEndpoint 底层即 GetExtendedTcpTable/GetExtendedUdpTable
+    let script = r#"
+$procs = @{}; Get-Process | ForEach-Object { $procs[[uint32]$_.Id] = $_.ProcessName }
+$tcp = Get-NetTCPConnection -ErrorAction SilentlyContinue | Select-Object LocalAddress, LocalPort, RemoteAddress, RemotePort, @{n='State';e={"$($_.State)"}}, OwningProcess, @{n='ProcessName';e={$procs[[uint32]$_.OwningProcess]}}
+$udp = Get-NetUDPEndpoint -ErrorAction SilentlyContinue | Select-Object LocalAddress, LocalPort, OwningProcess, @{n='ProcessName';e={$procs[[uint32]$_.OwningProcess]}}
+[pscustomobject]@{ tcp=$tcp; udp=$udp } | ConvertTo-Json -Depth 4 -Compress
+"#;
+    let script = format!(
+        "[Console]::OutputEncoding = [System.Text.UTF8Encoding]::new(); {script}"
+    );
+    let out = powershell_command()
+        .arg(&script)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(powershell_program(), e))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(ForgeFfiError::system_error(format!("PowerShell 失败: {}", stderr.trim())));
+    }
+    let v: Value = serde_json::from_slice(&out.stdout)
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}")))?;
+
+    let mut items = Vec::new();
+    for (key, protocol) in [("tcp", SocketProtocol::Tcp), ("udp", SocketProtocol::Udp)] {
+        for it in as_array(v.get(key)) {
+            let Some(local_address) = it.get("LocalAddress").and_then(Value::as_str) else {
+                continue;
+            };
+            let remote_port = it
+                .get("RemotePort")
+                .and_then(Value::as_u64)
+                .map(|p| p as u16)
+                .filter(|p| *p != 0);
+            let pid = it
+                .get("OwningProcess")
+                .and_then(Value::as_u64)
+                .map(|p| p as u32);
+            items.push(SocketEntry {
+                protocol,
+                family: family_of(local_address),
+                local_address: local_address.to_string(),
+                local_port: it.get("LocalPort").and_then(Value::as_u64).unwrap_or(0) as u16,
+                remote_address: remote_port.and(
+                    it.get("RemoteAddress")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                ),
+                remote_port,
+                state: it
+                    .get("State")
+                    .and_then(Value::as_str)
+                    .map(tcp_state_from_name),
+                pid,
+                process_name: it
+                    .get("ProcessName")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            });
+        }
+    }
+    Ok(items)
+}
+
+fn as_array(v: Option<&Value>) -> Vec<&Value> {
+    match v {
+        Some(Value::Array(a)) => a.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(other) => vec![other],
+    }
+}
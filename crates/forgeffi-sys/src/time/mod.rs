@@ -0,0 +1,143 @@
+use forgeffi_base::{
+    ForgeFfiError, TimeApplyRequest, TimeApplyResponse, TimeOp, TimeOpResult, TimeStatus,
+    ABI_VERSION,
+};
+use forgeffi_protocol::{decode_request, encode_json, validate_time_apply_request};
+
+use crate::util::{command, map_command_failure, map_spawn_error, now_unix_ms, CommandExt};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub fn status() -> Result<TimeStatus, ForgeFfiError> {
+    let mut st = platform::status()?;
+    st.abi = ABI_VERSION;
+    st.unix_ms = now_unix_ms();
+    Ok(st)
+}
+
+pub fn apply_request(req: &TimeApplyRequest) -> Result<TimeApplyResponse, ForgeFfiError> {
+    validate_time_apply_request(req)?;
+    let mut results = Vec::with_capacity(req.ops.len());
+    let mut all_ok = true;
+    for (i, op) in req.ops.iter().enumerate() {
+        let r = crate::runtime::exclusive(|| match op {
+            TimeOp::SetTimezone { timezone } => platform::set_timezone(timezone),
+            TimeOp::SetNtpServers { servers, enabled } => platform::set_ntp_servers(servers, *enabled),
+        });
+        all_ok &= r.is_ok();
+        results.push(TimeOpResult {
+            i,
+            ok: r.is_ok(),
+            error: r.err(),
+        });
+    }
+    Ok(TimeApplyResponse {
+        abi: ABI_VERSION,
+        ok: all_ok,
+        results,
+        last_known: status().ok(),
+    })
+}
+
+pub fn status_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("时间状态", &status()?)
+}
+
+pub fn apply_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: TimeApplyRequest = decode_request(req_json)?;
+    encode_json("时间 apply 响应", &apply_request(&req)?)
+}
+
+fn empty_status() -> TimeStatus {
+    TimeStatus {
+        abi: ABI_VERSION,
+        unix_ms: 0,
+        timezone: None,
+        utc_offset_secs: None,
+        ntp_enabled: None,
+        ntp_synchronized: None,
+        ntp_servers: Vec::new(),
+    }
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn run_capture(program: &str, args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output_with_deadline()
+        .map_err(|e| map_spawn_error(program, e))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        // systemsetup/w32tm 把错误写到 stdout
+        let msg = if stderr.trim().is_empty() { stdout } else { stderr };
+        Err(map_time_failure(format!(
+            "命令失败: {program} {}: {}",
+            args.first().copied().unwrap_or_default(),
+            msg.trim()
+        )))
+    }
+}
+
+fn map_time_failure(message: String) -> ForgeFfiError {
+    let s = message.to_lowercase();
+    if s.contains("access denied")
+        || s.contains("access is denied")
+        || s.contains("permission denied")
+        || s.contains("interactive authentication required")
+        || s.contains("administrator access")
+        || s.contains("not authorized")
+    {
+        ForgeFfiError::permission_denied(message)
+    } else if s.contains("invalid time zone") || s.contains("invalid timezone") {
+        ForgeFfiError::not_found(message)
+    } else {
+        map_command_failure(message)
+    }
+}
+
+// "+0800" / "-0530" -> 秒
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn parse_utc_offset(s: &str) -> Option<i32> {
+    let s = s.trim();
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    if rest.len() != 4 {
+        return None;
+    }
+    let h: i32 = rest[..2].parse().ok()?;
+    let m: i32 = rest[2..].parse().ok()?;
+    Some(sign * (h * 3600 + m * 60))
+}
+
+// /etc/localtime -> .../zoneinfo/Asia/Shanghai
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn timezone_from_localtime() -> Option<String> {
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    let s = target.to_str()?;
+    s.split_once("zoneinfo/").map(|(_, tz)| tz.to_string())
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+use crate::util::{map_io_error, write_atomic};
+use std::path::Path;
+
+const TIMESYNCD_DROPIN_DIR: &str = "/etc/systemd/timesyncd.conf.d";
+const TIMESYNCD_DROPIN: &str = "/etc/systemd/timesyncd.conf.d/forgeffi.conf";
+
+pub(super) fn status() -> Result<TimeStatus, ForgeFfiError> {
+    let mut st = empty_status();
+    // 没有 systemd 的环境（容器、Alpine）退回读取 /etc/localtime
+    if let Ok(text) = run_capture("timedatectl", &["show"]) {
+        for (k, v) in key_values(&text) {
+            match k {
+                "Timezone" if !v.is_empty() => st.timezone = Some(v.to_string()),
+                "NTP" => st.ntp_enabled = Some(v == "yes"),
+                "NTPSynchronized" => st.ntp_synchronized = Some(v == "yes"),
+                _ => {}
+            }
+        }
+    }
+    if st.timezone.is_none() {
+        st.timezone = timezone_from_localtime().or_else(|| {
+            std::fs::read_to_string("/etc/timezone")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        });
+    }
+    st.utc_offset_secs = run_capture("date", &["+%z"]).ok().and_then(|s| parse_utc_offset(&s));
+    st.ntp_servers = ntp_servers();
+    Ok(st)
+}
+
+pub(super) fn set_timezone(timezone: &str) -> Result<(), ForgeFfiError> {
+    let zone = Path::new("/usr/share/zoneinfo").join(timezone);
+    if !zone.is_file() {
+        return Err(ForgeFfiError::not_found(format!("未知时区: {timezone}")));
+    }
+    if systemd_running() {
+        return run_capture("timedatectl", &["set-timezone", timezone]).map(|_| ());
+    }
+    // 没有 systemd 时直接替换 /etc/localtime 软链接并同步 /etc/timezone
+    let tmp = Path::new("/etc/.localtime.forgeffi");
+    let _ = std::fs::remove_file(tmp);
+    std::os::unix::fs::symlink(&zone, tmp).map_err(map_io_error)?;
+    std::fs::rename(tmp, "/etc/localtime").map_err(map_io_error)?;
+    if Path::new("/etc/timezone").exists() {
+        write_atomic(Path::new("/etc/timezone"), format!("{timezone}\n").as_bytes())
+            .map_err(map_io_error)?;
+    }
+    Ok(())
+}
+
+pub(super) fn set_ntp_servers(servers: &[String], enabled: Option<bool>) -> Result<(), ForgeFfiError> {
+    if !systemd_running() {
+        return Err(ForgeFfiError::unsupported(
+            "设置 NTP 服务器需要 systemd-timesyncd".to_string(),
+        ));
+    }
+    // 用 drop-in 覆盖 NTP=，不改动发行版自带的 timesyncd.conf
+    if servers.is_empty() {
+        match std::fs::remove_file(TIMESYNCD_DROPIN) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(map_io_error(e)),
+        }
+    } else {
+        std::fs::create_dir_all(TIMESYNCD_DROPIN_DIR).map_err(map_io_error)?;
+        let content = format!("# 由 forgeffi 生成\n[Time]\nNTP={}\n", servers.join(" "));
+        write_atomic(Path::new(TIMESYNCD_DROPIN), content.as_bytes()).map_err(map_io_error)?;
+    }
+    if let Some(on) = enabled {
+        run_capture("timedatectl", &["set-ntp", if on { "true" } else { "false" }])?;
+    }
+    run_capture("systemctl", &["try-restart", "systemd-timesyncd"]).map(|_| ())
+}
+
+fn ntp_servers() -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    if let Ok(text) = run_capture(
+        "timedatectl",
+        &["show-timesync", "-p", "SystemNTPServers", "-p", "LinkNTPServers"],
+    ) {
+        for (_, v) in key_values(&text) {
+            out.extend(v.split_whitespace().map(str::to_string));
+        }
+    }
+    if out.is_empty() {
+        out = configured_servers();
+    }
+    out.dedup();
+    out
+}
+
+// timesyncd 未运行时按 timesyncd → chrony → ntpd 的顺序读取配置文件
+fn configured_servers() -> Vec<String> {
+    let mut files: Vec<std::path::PathBuf> = vec!["/etc/systemd/timesyncd.conf".into()];
+    if let Ok(rd) = std::fs::read_dir(TIMESYNCD_DROPIN_DIR) {
+        let mut extra: Vec<_> = rd.flatten().map(|e| e.path()).collect();
+        extra.sort();
+        files.extend(extra);
+    }
+    let mut out = Vec::new();
+    for f in &files {
+        if let Ok(text) = std::fs::read_to_string(f) {
+            for line in text.lines() {
+                if let Some(v) = line.trim().strip_prefix("NTP=") {
+                    // 后出现的 NTP= 覆盖前面的
+                    out = v.split_whitespace().map(str::to_string).collect();
+                }
+            }
+        }
+    }
+    if !out.is_empty() {
+        return out;
+    }
+    for f in ["/etc/chrony/chrony.conf", "/etc/chrony.conf", "/etc/ntp.conf"] {
+        if let Ok(text) = std::fs::read_to_string(f) {
+            for line in text.lines() {
+                let mut it = line.split_whitespace();
+                if matches!(it.next(), Some("server" | "pool"))
+                    && let Some(host) = it.next()
+                {
+                    out.push(host.to_string());
+                }
+            }
+        }
+        if !out.is_empty() {
+            break;
+        }
+    }
+    out
+}
+
+fn systemd_running() -> bool {
+    Path::new("/run/systemd/system").exists()
+}
+
+fn key_values(text: &str) -> impl Iterator<Item = (&str, &str)> {
+    text.lines().filter_map(|l| l.split_once('='))
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+use crate::util::{map_io_error, write_atomic};
+use std::path::Path;
+
+pub(super) fn status() -> Result<TimeStatus, ForgeFfiError> {
+    let mut st = empty_status();
+    st.timezone = timezone_from_localtime();
+    st.utc_offset_secs = run_capture("date", &["+%z"]).ok().and_then(|s| parse_utc_offset(&s));
+    // systemsetup 的查询同样需要管理员权限，失败时只能读 /etc/ntp.conf
+    st.ntp_enabled = systemsetup(&["-getusingnetworktime"])
+        .ok()
+        .and_then(|s| s.split_once(':').map(|(_, v)| v.trim().eq_ignore_ascii_case("on")));
+    st.ntp_servers = ntp_conf_servers();
+    if st.ntp_servers.is_empty()
+        && let Ok(s) = systemsetup(&["-getnetworktimeserver"])
+        && let Some((_, v)) = s.split_once(':')
+        && !v.trim().is_empty()
+    {
+        st.ntp_servers.push(v.trim().to_string());
+    }
+    Ok(st)
+}
+
+pub(super) fn set_timezone(timezone: &str) -> Result<(), ForgeFfiError> {
+    systemsetup(&["-settimezone", timezone]).map(|_| ())
+}
+
+pub(super) fn set_ntp_servers(servers: &[String], enabled: Option<bool>) -> Result<(), ForgeFfiError> {
+    // systemsetup 只接受一个服务器，其余的直接追加写入 /etc/ntp.conf
+    if let Some(first) = servers.first() {
+        systemsetup(&["-setnetworktimeserver", first])?;
+        if servers.len() > 1 {
+            let content: String = servers.iter().map(|s| format!("server {s}\n")).collect();
+            write_atomic(Path::new("/etc/ntp.conf"), content.as_bytes()).map_err(map_io_error)?;
+        }
+    }
+    if let Some(on) = enabled {
+        systemsetup(&["-setusingnetworktime", if on { "on" } else { "off" }])?;
+    }
+    Ok(())
+}
+
+// systemsetup 出错时也可能以 0 退出，只能检查输出
+fn systemsetup(args: &[&str]) -> Result<String, ForgeFfiError> {
+    let text = run_capture("systemsetup", args)?;
+    let lower = text.to_lowercase();
+    if lower.contains("exiting!") || lower.contains("error") || lower.contains("invalid") {
+        return Err(map_time_failure(format!(
+            "命令失败: systemsetup {}: {}",
+            args.first().copied().unwrap_or_default(),
+            text.trim()
+        )));
+    }
+    Ok(text)
+}
+
+fn ntp_conf_servers() -> Vec<String> {
+    std::fs::read_to_string("/etc/ntp.conf")
+        .map(|text| {
+            text.lines()
+                .filter_map(|l| {
+                    let mut it = l.split_whitespace();
+                    matches!(it.next(), Some("server" | "pool"))
+                        .then(|| it.next().map(str::to_string))
+                        .flatten()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,13 @@
+use super::*;
+
+pub(super) fn status() -> Result<TimeStatus, ForgeFfiError> {
+    Ok(empty_status())
+}
+
+pub(super) fn set_timezone(_timezone: &str) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持设置时区".to_string()))
+}
+
+pub(super) fn set_ntp_servers(_servers: &[String], _enabled: Option<bool>) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持设置 NTP".to_string()))
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+use serde_json::Value;
+
+pub(super) fn status() -> Result<TimeStatus, ForgeFfiError> {
+    let script = r#"
+$tz = Get-TimeZone
+$p = Get-ItemProperty 'HKLM:\SYSTEM\CurrentControlSet\Services\W32Time\Parameters' -ErrorAction SilentlyContinue
+$svc = Get-Service w32time -ErrorAction SilentlyContinue
+[pscustomobject]@{ tz=$tz.Id; offset=[int]$tz.GetUtcOffset([DateTime]::Now).TotalSeconds; ntp=$p.NtpServer; type=$p.Type; running=($svc.Status -eq 'Running') } | ConvertTo-Json -Compress
+"#;
+    let text = run_capture(
+        crate::util::powershell_program(),
+        &["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script],
+    )?;
+    let v: Value = serde_json::from_str(text.trim())
+        .map_err(|e| ForgeFfiError::system_error(format!("解析 PowerShell JSON 失败: {e}")))?;
+
+    let mut st = empty_status();
+    st.timezone = v.get("tz").and_then(Value::as_str).map(str::to_string);
+    st.utc_offset_secs = v.get("offset").and_then(Value::as_i64).map(|s| s as i32);
+    // Type=NoSync 表示关闭；服务未运行时同样不会同步
+    let running = v.get("running").and_then(Value::as_bool).unwrap_or(false);
+    st.ntp_enabled = v
+        .get("type")
+        .and_then(Value::as_str)
+        .map(|t| running && !t.eq_ignore_ascii_case("NoSync"));
+    st.ntp_servers = v
+        .get("ntp")
+        .and_then(Value::as_str)
+        .map(parse_peer_list)
+        .unwrap_or_default();
+    Ok(st)
+}
+
+pub(super) fn set_timezone(timezone: &str) -> Result<(), ForgeFfiError> {
+    run_capture("tzutil", &["/s", timezone]).map(|_| ())
+}
+
+pub(super) fn set_ntp_servers(servers: &[String], enabled: Option<bool>) -> Result<(), ForgeFfiError> {
+    if enabled == Some(false) {
+        run_capture("w32tm", &["/config", "/syncfromflags:NO", "/update"])?;
+        if servers.is_empty() {
+            return Ok(());
+        }
+    }
+    // 0x8 = 以客户端模式请求
+    let peers: Vec<String> = servers.iter().map(|s| format!("{s},0x8")).collect();
+    let peerlist = format!("/manualpeerlist:{}", peers.join(" "));
+    let mut args = vec!["/config", peerlist.as_str(), "/update"];
+    if enabled != Some(false) {
+        args.insert(2, "/syncfromflags:MANUAL");
+    }
+    run_capture("w32tm", &args)?;
+    if enabled == Some(true) {
+        // 服务未启动时 resync 会失败，这里不作为错误
+        let _ = run_capture("w32tm", &["/resync", "/nowait"]);
+    }
+    Ok(())
+}
+
+// "time.windows.com,0x9 pool.ntp.org,0x8" -> ["time.windows.com", "pool.ntp.org"]
+fn parse_peer_list(s: &str) -> Vec<String> {
+    s.split_whitespace()
+        .map(|p| p.split(',').next().unwrap_or(p).to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
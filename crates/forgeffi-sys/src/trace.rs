@@ -0,0 +1,339 @@
+// 进程内的轻量 span 记录：apply 与其中每个 op 各一个 span，带耗时、后端与期间执行的外部命令。
+// 结束的 span 进入环形缓冲（tool_trace_dump_json），同时推给订阅者；不依赖 tracing，与 runtime::log 一样自带。
+// span 只在创建它的线程上打开/关闭，外部命令按线程归属到最内层的 span
+use forgeffi_base::{
+    ErrorCode, ForgeFfiError, LogLevel, NetIfBackend, TraceCommand, TraceDumpResponse, TraceSpan, ABI_VERSION,
+    TRACE_DEFAULT_CAPACITY,
+};
+use forgeffi_protocol::encode_json;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Display;
+use std::io;
+use std::marker::PhantomData;
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use crate::runtime::log;
+
+// 每个订阅者最多积压的 span；回调跟不上时丢弃新的 span，不阻塞 apply
+const SUBSCRIBER_QUEUE: usize = 256;
+// 单个命令参数的最大长度（PowerShell 脚本可能很长）
+const MAX_ARG_LEN: usize = 1024;
+// 参数名（或 key=value 的 key）包含这些词时，其值替换为 ***
+const SECRET_MARKERS: [&str; 6] = ["psk", "password", "passwd", "secret", "private-key", "preshared"];
+
+struct Open {
+    id: u64,
+    parent: Option<u64>,
+    name: &'static str,
+    start: Instant,
+    start_unix_ms: u64,
+    backend: Option<NetIfBackend>,
+    fields: BTreeMap<String, String>,
+    commands: Vec<TraceCommand>,
+}
+
+struct Ring {
+    spans: VecDeque<TraceSpan>,
+    dropped: u64,
+}
+
+thread_local! {
+    static STACK: RefCell<Vec<Open>> = const { RefCell::new(Vec::new()) };
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static CAPACITY: AtomicU32 = AtomicU32::new(TRACE_DEFAULT_CAPACITY);
+static RING: Mutex<Ring> = Mutex::new(Ring {
+    spans: VecDeque::new(),
+    dropped: 0,
+});
+static SUBSCRIBERS: Mutex<Vec<(u64, SyncSender<TraceSpan>)>> = Mutex::new(Vec::new());
+static NEXT_SUBSCRIBER: AtomicU64 = AtomicU64::new(1);
+
+fn ring() -> MutexGuard<'static, Ring> {
+    match RING.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+fn subscribers() -> MutexGuard<'static, Vec<(u64, SyncSender<TraceSpan>)>> {
+    match SUBSCRIBERS.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    }
+}
+
+// 由 runtime::init 调用；缩小容量时立即丢弃最旧的 span
+pub(crate) fn set_capacity(capacity: Option<u32>) {
+    let capacity = capacity.unwrap_or(TRACE_DEFAULT_CAPACITY);
+    CAPACITY.store(capacity, Ordering::Release);
+    let mut g = ring();
+    while g.spans.len() > capacity as usize {
+        g.spans.pop_front();
+        g.dropped += 1;
+    }
+}
+
+// 打开的 span；finish 记录结果，未 finish 就被丢弃（提前返回或 panic）时记为失败
+pub(crate) struct Span {
+    id: u64,
+    // 只能在创建线程上关闭
+    _not_send: PhantomData<*const ()>,
+}
+
+// 不论是否保留到环形缓冲都会打开：结束的 span 同时用于指标统计
+pub(crate) fn enter(name: &'static str) -> Span {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    STACK.with(|s| {
+        let mut s = s.borrow_mut();
+        let parent = s.last().map(|o| o.id);
+        s.push(Open {
+            id,
+            parent,
+            name,
+            start: Instant::now(),
+            start_unix_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            backend: None,
+            fields: BTreeMap::new(),
+            commands: Vec::new(),
+        });
+    });
+    Span {
+        id,
+        _not_send: PhantomData,
+    }
+}
+
+impl Span {
+    pub(crate) fn field(&self, key: &'static str, value: impl Display) {
+        self.with_open(|o| {
+            o.fields.insert(key.to_string(), value.to_string());
+        });
+    }
+
+    pub(crate) fn backend(&self, backend: NetIfBackend) {
+        self.with_open(|o| o.backend = Some(backend));
+    }
+
+    pub(crate) fn finish(mut self, error: Option<ErrorCode>) {
+        close(self.id, true, error);
+        self.id = 0;
+    }
+
+    fn with_open(&self, f: impl FnOnce(&mut Open)) {
+        if self.id == 0 {
+            return;
+        }
+        STACK.with(|s| {
+            if let Some(o) = s.borrow_mut().iter_mut().rev().find(|o| o.id == self.id) {
+                f(o);
+            }
+        });
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        close(self.id, false, None);
+    }
+}
+
+fn close(id: u64, finished: bool, error: Option<ErrorCode>) {
+    if id == 0 {
+        return;
+    }
+    // 正常情况下就是栈顶；内层 span 漏关时一并丢弃，避免命令挂到错误的 span 上
+    let Some(open) = STACK.with(|s| {
+        let mut s = s.borrow_mut();
+        let pos = s.iter().rposition(|o| o.id == id)?;
+        s.truncate(pos + 1);
+        s.pop()
+    }) else {
+        return;
+    };
+    let backend = open
+        .backend
+        .or_else(|| open.commands.iter().find_map(|c| backend_of_program(&c.program)));
+    let span = TraceSpan {
+        id: open.id,
+        parent: open.parent,
+        name: open.name.to_string(),
+        start_unix_ms: open.start_unix_ms,
+        duration_us: open.start.elapsed().as_micros() as u64,
+        ok: finished && error.is_none(),
+        error,
+        backend,
+        fields: open.fields,
+        commands: open.commands,
+    };
+    publish(span);
+}
+
+fn publish(span: TraceSpan) {
+    crate::metrics::observe(&span);
+    {
+        let subs = subscribers();
+        for (_, tx) in subs.iter() {
+            if let Err(TrySendError::Full(_)) = tx.try_send(span.clone()) {
+                log(LogLevel::Debug, format_args!("trace 订阅者积压已满，丢弃 span #{}", span.id));
+            }
+        }
+    }
+    let capacity = CAPACITY.load(Ordering::Acquire) as usize;
+    if capacity == 0 {
+        return;
+    }
+    let mut g = ring();
+    while g.spans.len() >= capacity {
+        g.spans.pop_front();
+        g.dropped += 1;
+    }
+    g.spans.push_back(span);
+}
+
+fn backend_of_program(program: &str) -> Option<NetIfBackend> {
+    let base = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let base = base.strip_suffix(".exe").unwrap_or(base).to_ascii_lowercase();
+    match base.as_str() {
+        "ip" | "sysctl" | "tc" => Some(NetIfBackend::Iproute2),
+        "nmcli" => Some(NetIfBackend::Nmcli),
+        "netplan" => Some(NetIfBackend::Netplan),
+        "networkctl" => Some(NetIfBackend::Networkd),
+        "powershell" | "pwsh" => Some(NetIfBackend::Powershell),
+        "netsh" => Some(NetIfBackend::Netsh),
+        "ifconfig" => Some(NetIfBackend::Ifconfig),
+        "networksetup" => Some(NetIfBackend::Networksetup),
+        _ => None,
+    }
+}
+
+// 由 CommandExt::output_with_deadline 调用；当前线程没有打开的 span 时什么也不做
+pub(crate) fn record_command(cmd: &Command, start: Instant, result: &io::Result<Output>) {
+    let in_span = STACK.with(|s| !s.borrow().is_empty());
+    if !in_span {
+        return;
+    }
+    let (exit_code, error) = match result {
+        Ok(out) => (out.status.code(), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let command = TraceCommand {
+        program: cmd.get_program().to_string_lossy().into_owned(),
+        args: redact_args(cmd.get_args().map(|a| a.to_string_lossy().into_owned())),
+        duration_us: start.elapsed().as_micros() as u64,
+        exit_code,
+        error,
+    };
+    STACK.with(|s| {
+        if let Some(o) = s.borrow_mut().last_mut() {
+            o.commands.push(command);
+        }
+    });
+}
+
+fn redact_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    let is_secret = |s: &str| {
+        let s = s.to_ascii_lowercase();
+        SECRET_MARKERS.iter().any(|m| s.contains(m))
+    };
+    let mut out = Vec::new();
+    let mut hide_next = false;
+    for arg in args {
+        let arg = if hide_next {
+            "***".to_string()
+        } else if let Some((k, _)) = arg.split_once('=').filter(|(k, _)| is_secret(k)) {
+            format!("{k}=***")
+        } else if arg.len() > MAX_ARG_LEN {
+            let cut = (0..=MAX_ARG_LEN).rev().find(|i| arg.is_char_boundary(*i)).unwrap_or(0);
+            format!("{}…", &arg[..cut])
+        } else {
+            arg
+        };
+        hide_next = is_secret(&arg) && !arg.contains('=');
+        out.push(arg);
+    }
+    out
+}
+
+pub fn dump() -> TraceDumpResponse {
+    let g = ring();
+    TraceDumpResponse {
+        abi: ABI_VERSION,
+        capacity: CAPACITY.load(Ordering::Acquire),
+        dropped: g.dropped,
+        spans: g.spans.iter().cloned().collect(),
+    }
+}
+
+pub fn dump_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    encode_json("trace 记录", &dump())
+}
+
+pub type SpanSink = Box<dyn FnMut(&TraceSpan) + Send>;
+
+// 每个订阅一个投递线程，按结束顺序串行回调；stop 返回后不再回调
+pub struct TraceSubscription {
+    id: u64,
+    stopped: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+pub fn subscribe(sink: SpanSink) -> Result<TraceSubscription, ForgeFfiError> {
+    let (tx, rx) = sync_channel(SUBSCRIBER_QUEUE);
+    let stopped = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stopped);
+    let handle = std::thread::Builder::new()
+        .name("forgeffi-trace".to_string())
+        .spawn(move || deliver(rx, sink, &flag))
+        .map_err(|e| ForgeFfiError::system_error(format!("创建 trace 投递线程失败: {e}")))?;
+    let id = NEXT_SUBSCRIBER.fetch_add(1, Ordering::Relaxed);
+    subscribers().push((id, tx));
+    Ok(TraceSubscription {
+        id,
+        stopped,
+        handle: Some(handle),
+    })
+}
+
+fn deliver(rx: Receiver<TraceSpan>, mut sink: SpanSink, stopped: &AtomicBool) {
+    while let Ok(span) = rx.recv() {
+        if stopped.load(Ordering::Acquire) {
+            return;
+        }
+        sink(&span);
+    }
+}
+
+impl TraceSubscription {
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+        // 丢掉发送端后投递线程的 recv 返回错误并退出
+        subscribers().retain(|(id, _)| *id != self.id);
+        // 在自己的回调里 stop 时不能 join 自身，线程看到 stop 标志后自行退出
+        if let Some(h) = self.handle.take()
+            && h.thread().id() != std::thread::current().id()
+        {
+            let _ = h.join();
+        }
+    }
+}
+
+impl Drop for TraceSubscription {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,400 @@
+use forgeffi_base::{current_cancel_token, CancelToken, ForgeFfiError};
+#[cfg(windows)]
+use forgeffi_base::PowerShellEngine;
+use std::cell::Cell;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use std::{fs, io, path::Path};
+
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("/"));
+    let tmp = parent.join(format!(
+        ".{}.tmp.{}",
+        path.file_name().and_then(|s| s.to_str()).unwrap_or("forgeffi"),
+        std::process::id()
+    ));
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// 同 write_atomic，但临时文件以 create_new、0600 创建：改名前后内容都不会对其他用户可读
+#[cfg(all(feature = "netif", target_os = "linux"))]
+pub(crate) fn write_atomic_private(path: &Path, content: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    let parent = path.parent().unwrap_or_else(|| Path::new("/"));
+    let tmp = parent.join(format!(
+        ".{}.tmp.{}",
+        path.file_name().and_then(|s| s.to_str()).unwrap_or("forgeffi"),
+        std::process::id()
+    ));
+    // 上次崩溃留下的同名临时文件
+    let _ = fs::remove_file(&tmp);
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&tmp)
+        .and_then(|mut f| f.write_all(content))
+        .and_then(|()| fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
+
+// 把密钥交给外部命令的临时文件：文件名带随机成分且以 create_new 创建（不跟随符号链接、不覆盖已有文件），
+// unix 下权限 0600；Windows 的 %TEMP% 是按用户隔离 ACL 的目录。离开作用域即删除，提前返回或 panic 也不例外
+#[cfg_attr(
+    not(any(all(feature = "netif", target_os = "linux"), all(feature = "wifi", any(target_os = "linux", windows)))),
+    allow(dead_code)
+)]
+pub(crate) struct SecretFile(std::path::PathBuf);
+
+#[cfg_attr(
+    not(any(all(feature = "netif", target_os = "linux"), all(feature = "wifi", any(target_os = "linux", windows)))),
+    allow(dead_code)
+)]
+impl SecretFile {
+    pub(crate) fn new(tag: &str, content: &[u8]) -> Result<Self, ForgeFfiError> {
+        use std::hash::{BuildHasher, Hasher};
+        use std::io::Write;
+        static SEQ: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let mut h = std::collections::hash_map::RandomState::new().build_hasher();
+        h.write_u64(SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+        let path = std::env::temp_dir().join(format!(".forgeffi-{tag}-{}-{:016x}", std::process::id(), h.finish()));
+        let mut opts = fs::OpenOptions::new();
+        opts.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut opts, 0o600);
+        let mut f = opts.open(&path).map_err(map_io_error)?;
+        let file = Self(path);
+        f.write_all(content).map_err(map_io_error)?;
+        Ok(file)
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        self.0.to_str().unwrap_or_default()
+    }
+}
+
+impl Drop for SecretFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+pub(crate) fn map_io_error(e: io::Error) -> ForgeFfiError {
+    let errno = e.raw_os_error();
+    let err = match e.kind() {
+        io::ErrorKind::PermissionDenied => ForgeFfiError::permission_denied(e.to_string()),
+        io::ErrorKind::NotFound => ForgeFfiError::not_found(e.to_string()),
+        io::ErrorKind::ResourceBusy => ForgeFfiError::busy(e.to_string()),
+        io::ErrorKind::TimedOut => ForgeFfiError::timeout(e.to_string()),
+        _ if is_sharing_violation(&e) => ForgeFfiError::busy(e.to_string()),
+        _ => ForgeFfiError::system_error(e.to_string()),
+    };
+    match errno {
+        Some(n) => err.with_errno(n),
+        None => err,
+    }
+}
+
+// ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION 不会被标准库归到 ResourceBusy
+#[cfg(windows)]
+fn is_sharing_violation(e: &io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(32 | 33))
+}
+
+#[cfg(not(windows))]
+fn is_sharing_violation(_e: &io::Error) -> bool {
+    false
+}
+
+pub(crate) fn map_spawn_error(program: &str, e: io::Error) -> ForgeFfiError {
+    let err = if e.kind() == io::ErrorKind::TimedOut {
+        ForgeFfiError::timeout(format!("{program}: {e}"))
+    } else if e.kind() == io::ErrorKind::Interrupted {
+        ForgeFfiError::cancelled(format!("{program}: {e}"))
+    } else if e.kind() == io::ErrorKind::NotFound {
+        ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}"))
+            .with_hint(format!("确认系统已安装 {program} 且位于 PATH 中"))
+    } else {
+        ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}"))
+    };
+    let err = err.with_command(program);
+    match e.raw_os_error() {
+        Some(n) => err.with_errno(n),
+        None => err,
+    }
+}
+
+// 外部命令只能从输出文本判断 EBUSY / 共享冲突
+pub(crate) fn map_command_failure(message: String) -> ForgeFfiError {
+    let s = message.to_lowercase();
+    if s.contains("device or resource busy")
+        || s.contains("resource busy")
+        || s.contains("being used by another process")
+        || s.contains("sharing violation")
+    {
+        ForgeFfiError::busy(message)
+    } else {
+        ForgeFfiError::system_error(message)
+    }
+}
+
+// 命令以非零状态退出：message 保持原有格式，命令行与 stderr 另外放进结构化字段
+pub(crate) fn command_failed(program: &str, args: &[&str], stderr: &str, message: String) -> ForgeFfiError {
+    map_command_failure(message)
+        .with_command(format!("{program} {args:?}"))
+        .with_stderr(stderr)
+}
+
+pub(crate) fn now_unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(unix)]
+const KEEP_ENV: &[&str] = &["PATH", "HOME", "TMPDIR", "DBUS_SYSTEM_BUS_ADDRESS"];
+
+#[cfg(windows)]
+const KEEP_ENV: &[&str] = &[
+    "PATH",
+    "PATHEXT",
+    "SystemRoot",
+    "SystemDrive",
+    "windir",
+    "ComSpec",
+    "TEMP",
+    "TMP",
+    "PSModulePath",
+    "ProgramFiles",
+    "ProgramFiles(x86)",
+    "ProgramW6432",
+    "ProgramData",
+    "CommonProgramFiles",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "COMPUTERNAME",
+    "USERNAME",
+    "USERDOMAIN",
+    "NUMBER_OF_PROCESSORS",
+    "PROCESSOR_ARCHITECTURE",
+];
+
+#[cfg(not(any(unix, windows)))]
+const KEEP_ENV: &[&str] = &["PATH"];
+
+// 所有外部命令都从这里创建，嵌入多线程宿主时保持可预期：
+// - 环境变量只保留白名单，不把宿主的凭据/代理等变量带给子进程；unix 下固定 LC_ALL=C 保证输出可解析
+// - stdin 置空；unix 下放进独立进程组，宿主终端的 Ctrl-C 等信号不会波及子进程
+// - 不使用 pre_exec（本 crate 禁止 unsafe），std 因此在 Linux/macOS 上走 posix_spawn，
+//   fork 后不会在子进程里执行分配/加锁；std 自己创建的描述符均带 CLOEXEC，
+//   宿主未设置 CLOEXEC 的描述符仍会被继承，这一点需要宿主自行保证
+pub(crate) fn command(program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.env_clear();
+    for key in KEEP_ENV {
+        if let Some(v) = std::env::var_os(key) {
+            cmd.env(key, v);
+        }
+    }
+    cmd.stdin(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+        if std::env::var_os("PATH").is_none() {
+            cmd.env("PATH", "/usr/sbin:/usr/bin:/sbin:/bin");
+        }
+        cmd.env("LC_ALL", "C").env("LANG", "C");
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt as _;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+// 选定的 PowerShell 及选定时的配置值；配置变化或 clear_powershell_engine 后重新选择
+#[cfg(windows)]
+static POWERSHELL_ENGINE: std::sync::Mutex<Option<(Option<PowerShellEngine>, PowerShellEngine)>> =
+    std::sync::Mutex::new(None);
+
+// Windows PowerShell 可能被策略禁用、只留下 PowerShell 7：init 配置的 powershell_engine 优先，
+// 否则按 pwsh → powershell 探测第一个能启动的；都不可用时仍返回 powershell，由调用处报错
+#[cfg(windows)]
+pub(crate) fn powershell_engine() -> PowerShellEngine {
+    let configured = forgeffi_base::runtime_config().powershell_engine;
+    let mut g = match POWERSHELL_ENGINE.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    if let Some((c, engine)) = *g
+        && c == configured
+    {
+        return engine;
+    }
+    let engine = configured.unwrap_or_else(|| {
+        PowerShellEngine::candidates(None)
+            .iter()
+            .copied()
+            .find(|e| {
+                command(e.program())
+                    .args(["-NoProfile", "-NonInteractive", "-Command", "$PSVersionTable.PSVersion.Major"])
+                    .output_with_deadline()
+                    .is_ok_and(|o| o.status.success())
+            })
+            .unwrap_or(PowerShellEngine::Powershell)
+    });
+    *g = Some((configured, engine));
+    engine
+}
+
+#[cfg(windows)]
+pub(crate) fn clear_powershell_engine() {
+    match POWERSHELL_ENGINE.lock() {
+        Ok(mut g) => *g = None,
+        Err(p) => *p.into_inner() = None,
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn powershell_program() -> &'static str {
+    powershell_engine().program()
+}
+
+// 把任意文本放进 PowerShell 单引号字符串。PowerShell 除 ASCII ' 外还把 U+2018..=U+201B（‘ ’ ‚ ‛）
+// 当作单引号定界符，两个相邻的引号字符表示一个字面引号，所以这几种字符都要双写。
+// 拼进脚本的名字一律经过这里，不依赖各处校验函数的字符黑名单
+#[cfg(windows)]
+pub(crate) fn ps_quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for c in s.chars() {
+        if matches!(c, '\'' | '\u{2018}'..='\u{201B}') {
+            out.push(c);
+        }
+        out.push(c);
+    }
+    out.push('\'');
+    out
+}
+
+// 带统一启动参数的 PowerShell 命令，调用方再追加脚本
+#[cfg(windows)]
+pub(crate) fn powershell_command() -> Command {
+    let mut cmd = command(powershell_program());
+    cmd.args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command"]);
+    cmd
+}
+
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+thread_local! {
+    static COMMAND_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+pub(crate) fn with_command_deadline<T>(deadline: Option<Instant>, f: impl FnOnce() -> T) -> T {
+    let prev = COMMAND_DEADLINE.with(|d| d.replace(deadline));
+    let out = f();
+    COMMAND_DEADLINE.with(|d| d.set(prev));
+    out
+}
+
+pub(crate) fn command_deadline() -> Option<Instant> {
+    COMMAND_DEADLINE.with(Cell::get)
+}
+
+fn default_command_deadline() -> Option<Instant> {
+    let ms = forgeffi_base::runtime_config().command_timeout_ms;
+    (ms > 0).then(|| Instant::now() + Duration::from_millis(ms))
+}
+
+pub(crate) trait CommandExt {
+    fn output_with_deadline(&mut self) -> io::Result<Output>;
+}
+
+impl CommandExt for Command {
+    // 命令行、耗时与退出码记入当前线程打开的 trace span
+    fn output_with_deadline(&mut self) -> io::Result<Output> {
+        let start = Instant::now();
+        let out = output_polled(self);
+        crate::trace::record_command(self, start, &out);
+        out
+    }
+}
+
+// 当前线程设置了截止时间或取消令牌时轮询子进程：到点 kill 并返回 TimedOut，
+// 被取消时 kill 并返回 Interrupted；两者都没有时等价于 output()。
+// 未设置截止时间时使用 init 配置的 command_timeout_ms
+fn output_polled(cmd: &mut Command) -> io::Result<Output> {
+    let deadline = command_deadline().or_else(default_command_deadline);
+    let cancel = current_cancel_token();
+    if deadline.is_none() && cancel.is_none() {
+        return cmd.output();
+    }
+    if cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, "操作已取消，未启动子进程"));
+    }
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Output {
+                status,
+                stdout: join_drain(stdout),
+                stderr: join_drain(stderr),
+            });
+        }
+        if cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "操作已取消，已终止子进程",
+            ));
+        }
+        let now = Instant::now();
+        if let Some(deadline) = deadline
+            && now >= deadline
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "命令执行超时，已终止子进程",
+            ));
+        }
+        let wait = deadline.map_or(COMMAND_POLL_INTERVAL, |d| COMMAND_POLL_INTERVAL.min(d - now));
+        std::thread::sleep(wait);
+    }
+}
+
+fn drain<R: Read + Send + 'static>(r: Option<R>) -> Option<JoinHandle<Vec<u8>>> {
+    r.map(|mut r| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = r.read_to_end(&mut buf);
+            buf
+        })
+    })
+}
+
+fn join_drain(h: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    h.and_then(|h| h.join().ok()).unwrap_or_default()
+}
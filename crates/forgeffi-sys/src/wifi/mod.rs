@@ -0,0 +1,151 @@
+use forgeffi_base::{
+    ForgeFfiError, WifiConnectRequest, WifiDisconnectRequest, WifiNetwork, WifiOpResponse,
+    WifiScanRequest, WifiScanResponse, WifiSecurity, ABI_VERSION,
+};
+use forgeffi_protocol::{
+    check_abi, decode_request, validate_psk, validate_ssid, validate_wifi_field,
+};
+
+#[cfg(target_os = "linux")]
+mod platform_linux;
+#[cfg(target_os = "macos")]
+mod platform_macos;
+#[cfg(target_os = "windows")]
+mod platform_windows;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform_unsupported;
+
+#[cfg(target_os = "linux")]
+use platform_linux as platform;
+#[cfg(target_os = "macos")]
+use platform_macos as platform;
+#[cfg(target_os = "windows")]
+use platform_windows as platform;
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+use platform_unsupported as platform;
+
+pub const WIFI_ABI_VERSION: u32 = ABI_VERSION;
+
+pub fn scan(interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    if let Some(ifname) = interface {
+        validate_wifi_field("interface", ifname)?;
+    }
+    platform::scan(interface)
+}
+
+pub fn connect(ssid: &str, psk: Option<&str>, interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    validate_ssid(ssid)?;
+    if let Some(psk) = psk {
+        validate_psk(psk)?;
+    }
+    if let Some(ifname) = interface {
+        validate_wifi_field("interface", ifname)?;
+    }
+    crate::runtime::exclusive(|| platform::connect(ssid, psk, interface))
+}
+
+pub fn disconnect(interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    if let Some(ifname) = interface {
+        validate_wifi_field("interface", ifname)?;
+    }
+    crate::runtime::exclusive(|| platform::disconnect(interface))
+}
+
+pub fn scan_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: WifiScanRequest = match req_json {
+        Some(s) => decode_request(s)?,
+        None => WifiScanRequest {
+            abi: WIFI_ABI_VERSION,
+            interface: None,
+        },
+    };
+    check_abi(req.abi)?;
+    let items = scan(req.interface.as_deref())?;
+    serde_json::to_vec(&WifiScanResponse {
+        abi: WIFI_ABI_VERSION,
+        items,
+    })
+    .map_err(|e| ForgeFfiError::system_error(format!("序列化 wifi 扫描结果失败: {e}")))
+}
+
+pub fn connect_json_bytes(req_json: &str) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: WifiConnectRequest = decode_request(req_json)?;
+    check_abi(req.abi)?;
+    connect(&req.ssid, req.psk.as_deref(), req.interface.as_deref())?;
+    ok_json_bytes()
+}
+
+pub fn disconnect_json_bytes(req_json: Option<&str>) -> Result<Vec<u8>, ForgeFfiError> {
+    let req: WifiDisconnectRequest = match req_json {
+        Some(s) => decode_request(s)?,
+        None => WifiDisconnectRequest {
+            abi: WIFI_ABI_VERSION,
+            interface: None,
+        },
+    };
+    check_abi(req.abi)?;
+    disconnect(req.interface.as_deref())?;
+    ok_json_bytes()
+}
+
+fn ok_json_bytes() -> Result<Vec<u8>, ForgeFfiError> {
+    serde_json::to_vec(&WifiOpResponse {
+        abi: WIFI_ABI_VERSION,
+        ok: true,
+    })
+    .map_err(|e| ForgeFfiError::system_error(format!("序列化 wifi 响应失败: {e}")))
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+    allow(dead_code)
+)]
+fn parse_security(s: &str) -> Vec<WifiSecurity> {
+    let mut out = Vec::new();
+    for tok in s.split(|c: char| c.is_whitespace() || c == ',') {
+        let t = tok.to_ascii_uppercase();
+        if t.is_empty() {
+            continue;
+        }
+        let sec = if t == "NONE" || t == "--" || t == "OPEN" {
+            Some(WifiSecurity::Open)
+        } else if t.starts_with("WPA3") || t.contains("SAE") {
+            Some(WifiSecurity::Wpa3)
+        } else if t.starts_with("WPA2") || t.starts_with("RSN") {
+            Some(WifiSecurity::Wpa2)
+        } else if t.starts_with("WPA") {
+            Some(WifiSecurity::Wpa)
+        } else if t.starts_with("WEP") {
+            Some(WifiSecurity::Wep)
+        } else {
+            None
+        };
+        if let Some(sec) = sec {
+            out.push(sec);
+        }
+        if t.contains("802.1X") || t.contains("ENTERPRISE") || t.contains("EAP") {
+            out.push(WifiSecurity::Enterprise);
+        }
+    }
+    if out.is_empty() {
+        out.push(if s.trim().is_empty() {
+            WifiSecurity::Open
+        } else {
+            WifiSecurity::Unknown
+        });
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn channel_from_frequency(mhz: u32) -> Option<u32> {
+    match mhz {
+        2412..=2472 => Some((mhz - 2407) / 5),
+        2484 => Some(14),
+        5000..=5895 => Some((mhz - 5000) / 5),
+        5955..=7115 => Some((mhz - 5950) / 5),
+        _ => None,
+    }
+}
@@ -0,0 +1,272 @@
+use super::*;
+
+use crate::util::{command, SecretFile};
+
+pub(super) fn scan(interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    if program_available("nmcli") {
+        return scan_nmcli(interface);
+    }
+    if program_available("iw") {
+        let ifname = match interface {
+            Some(v) => v.to_string(),
+            None => first_iw_interface()?
+                .ok_or_else(|| ForgeFfiError::not_found("未找到无线网卡".to_string()))?,
+        };
+        return scan_iw(&ifname);
+    }
+    Err(ForgeFfiError::unsupported(
+        "Wi-Fi 扫描需要 NetworkManager（nmcli）或 iw".to_string(),
+    ))
+}
+
+pub(super) fn connect(ssid: &str, psk: Option<&str>, interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    require_nmcli()?;
+    let Some(psk) = psk else {
+        let mut args = vec!["device", "wifi", "connect", ssid];
+        if let Some(ifname) = interface {
+            args.extend(["ifname", ifname]);
+        }
+        return run_nmcli(&args).map(|_| ());
+    };
+    // device wifi connect 只能在命令行上带 password（/proc/<pid>/cmdline 对所有用户可见），
+    // 因此先建（或复用）同名 Wi-Fi 连接，再由 connection up 通过 passwd-file 从 0600 临时文件读取 PSK；
+    // 本次新建的连接在激活失败时删除
+    let created = if wifi_connection_exists(ssid)? {
+        false
+    } else {
+        let mut add = vec![
+            "connection", "add", "type", "wifi", "con-name", ssid, "ssid", ssid, "wifi-sec.key-mgmt", "wpa-psk",
+        ];
+        if let Some(ifname) = interface {
+            add.extend(["ifname", ifname]);
+        }
+        run_nmcli(&add)?;
+        true
+    };
+    let secret = SecretFile::new("wifi", format!("802-11-wireless-security.psk:{psk}\n").as_bytes())?;
+    let mut up = vec!["connection", "up", "id", ssid, "passwd-file", secret.path()];
+    if let Some(ifname) = interface {
+        up.extend(["ifname", ifname]);
+    }
+    run_nmcli(&up).map(|_| ()).inspect_err(|_| {
+        if created {
+            let _ = run_nmcli(&["connection", "delete", "id", ssid]);
+        }
+    })
+}
+
+fn wifi_connection_exists(name: &str) -> Result<bool, ForgeFfiError> {
+    let text = run_nmcli(&["-t", "-f", "NAME,TYPE", "connection", "show"])?;
+    Ok(text.lines().any(|l| {
+        let f = split_terse(l);
+        f.len() >= 2 && f[0] == name && f[1] == "802-11-wireless"
+    }))
+}
+
+pub(super) fn disconnect(interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    require_nmcli()?;
+    let ifname = match interface {
+        Some(v) => v.to_string(),
+        None => connected_wifi_device()?.ok_or_else(|| {
+            ForgeFfiError::not_found("没有已连接的 Wi-Fi 网卡".to_string())
+        })?,
+    };
+    run_nmcli(&["device", "disconnect", ifname.as_str()]).map(|_| ())
+}
+
+fn scan_nmcli(interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    let mut args = vec![
+        "-t",
+        "-f",
+        "IN-USE,BSSID,SSID,CHAN,FREQ,SIGNAL,SECURITY",
+        "device",
+        "wifi",
+        "list",
+        "--rescan",
+        "auto",
+    ];
+    if let Some(ifname) = interface {
+        args.extend(["ifname", ifname]);
+    }
+    let text = run_nmcli(&args)?;
+
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let f = split_terse(line);
+        if f.len() < 7 {
+            continue;
+        }
+        let frequency_mhz = f[4]
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<u32>().ok());
+        out.push(WifiNetwork {
+            ssid: f[2].clone(),
+            bssid: Some(f[1].clone()).filter(|v| !v.is_empty()),
+            signal_dbm: None,
+            signal_percent: f[5].parse::<u8>().ok(),
+            frequency_mhz,
+            channel: f[3].parse::<u32>().ok(),
+            security: parse_security(&f[6]),
+            in_use: Some(f[0].trim() == "*"),
+        });
+    }
+    Ok(out)
+}
+
+fn split_terse(line: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(n) = chars.next() {
+                    cur.push(n);
+                }
+            }
+            ':' => out.push(std::mem::take(&mut cur)),
+            _ => cur.push(c),
+        }
+    }
+    out.push(cur);
+    out
+}
+
+fn first_iw_interface() -> Result<Option<String>, ForgeFfiError> {
+    let text = run_capture("iw", &["dev"])?;
+    Ok(text.lines().find_map(|l| {
+        l.trim()
+            .strip_prefix("Interface ")
+            .map(|v| v.trim().to_string())
+    }))
+}
+
+fn scan_iw(ifname: &str) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    let text = run_capture("iw", &["dev", ifname, "scan"])?;
+    Ok(parse_iw_scan(&text))
+}
+
+fn parse_iw_scan(text: &str) -> Vec<WifiNetwork> {
+    let mut out: Vec<WifiNetwork> = Vec::new();
+    let mut sec = String::new();
+    let mut privacy = false;
+
+    let finish = |out: &mut Vec<WifiNetwork>, sec: &mut String, privacy: &mut bool| {
+        if let Some(last) = out.last_mut() {
+            let s = if sec.is_empty() && *privacy {
+                "WEP"
+            } else {
+                sec.as_str()
+            };
+            last.security = parse_security(s);
+        }
+        sec.clear();
+        *privacy = false;
+    };
+
+    for raw in text.lines() {
+        let line = raw.trim();
+        if let Some(rest) = raw.strip_prefix("BSS ") {
+            finish(&mut out, &mut sec, &mut privacy);
+            let bssid: String = rest.chars().take_while(|c| *c != '(' && !c.is_whitespace()).collect();
+            out.push(WifiNetwork {
+                ssid: String::new(),
+                bssid: Some(bssid),
+                signal_dbm: None,
+                signal_percent: None,
+                frequency_mhz: None,
+                channel: None,
+                security: Vec::new(),
+                in_use: Some(rest.contains("associated")),
+            });
+            continue;
+        }
+        let Some(cur) = out.last_mut() else {
+            continue;
+        };
+        if let Some(v) = line.strip_prefix("SSID: ") {
+            cur.ssid = v.to_string();
+        } else if let Some(v) = line.strip_prefix("freq: ") {
+            cur.frequency_mhz = v.split('.').next().and_then(|s| s.trim().parse().ok());
+            cur.channel = cur.channel.or_else(|| cur.frequency_mhz.and_then(channel_from_frequency));
+        } else if let Some(v) = line.strip_prefix("signal: ") {
+            cur.signal_dbm = v
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse::<f64>().ok())
+                .map(|d| d.round() as i32);
+        } else if let Some(v) = line.strip_prefix("DS Parameter set: channel ") {
+            cur.channel = v.trim().parse().ok();
+        } else if line.starts_with("capability:") && line.contains("Privacy") {
+            privacy = true;
+        } else if line.starts_with("RSN:") {
+            sec.push_str(" WPA2");
+        } else if line.starts_with("WPA:") {
+            sec.push_str(" WPA");
+        } else if let Some(v) = line.strip_prefix("* Authentication suites:") {
+            if v.contains("SAE") {
+                sec.push_str(" WPA3");
+            }
+            if v.contains("802.1X") {
+                sec.push_str(" 802.1X");
+            }
+        }
+    }
+    finish(&mut out, &mut sec, &mut privacy);
+    out
+}
+
+fn connected_wifi_device() -> Result<Option<String>, ForgeFfiError> {
+    let text = run_nmcli(&["-t", "-f", "DEVICE,TYPE,STATE", "device"])?;
+    Ok(text.lines().find_map(|l| {
+        let f = split_terse(l);
+        (f.len() >= 3 && f[1] == "wifi" && f[2].starts_with("connected")).then(|| f[0].clone())
+    }))
+}
+
+fn require_nmcli() -> Result<(), ForgeFfiError> {
+    if program_available("nmcli") {
+        Ok(())
+    } else {
+        Err(ForgeFfiError::unsupported(
+            "Wi-Fi 连接/断开需要 NetworkManager（nmcli）".to_string(),
+        ))
+    }
+}
+
+fn program_available(program: &str) -> bool {
+    command(program)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+fn run_nmcli(args: &[&str]) -> Result<String, ForgeFfiError> {
+    run_capture("nmcli", args).map_err(|e| {
+        if e.message.contains("No network with SSID") {
+            ForgeFfiError::not_found(e.message)
+        } else if e.message.contains("Not authorized") || e.message.contains("not authorized") {
+            ForgeFfiError::permission_denied(e.message)
+        } else {
+            e
+        }
+    })
+}
+
+fn run_capture(program: &str, args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output()
+        .map_err(|e| ForgeFfiError::system_error(format!("执行命令失败: {program}: {e}")))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        Err(ForgeFfiError::system_error(format!(
+            "命令失败: {program} {}: {stderr}",
+            args.first().copied().unwrap_or_default()
+        )))
+    }
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+use crate::util::command;
+
+const AIRPORT: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+pub(super) fn scan(interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    let mut args = Vec::new();
+    let dev;
+    if let Some(ifname) = interface {
+        dev = format!("--interface={ifname}");
+        args.push(dev.as_str());
+    }
+    args.push("-s");
+    let text = run_capture(AIRPORT, &args)?;
+    Ok(parse_airport_scan(&text))
+}
+
+pub(super) fn connect(ssid: &str, psk: Option<&str>, interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    let dev = match interface {
+        Some(v) => v.to_string(),
+        None => wifi_device()?,
+    };
+    let mut args = vec!["-setairportnetwork", dev.as_str(), ssid];
+    if let Some(psk) = psk {
+        args.push(psk);
+    }
+    let text = run_capture("networksetup", &args)?;
+    // networksetup 连接失败时仍返回 0，只在 stdout 给出提示
+    let t = text.trim();
+    if t.is_empty() {
+        return Ok(());
+    }
+    if t.contains("Could not find network") {
+        return Err(ForgeFfiError::not_found(t.to_string()));
+    }
+    Err(ForgeFfiError::system_error(t.to_string()))
+}
+
+pub(super) fn disconnect(interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    let mut args = Vec::new();
+    let dev;
+    if let Some(ifname) = interface {
+        dev = format!("--interface={ifname}");
+        args.push(dev.as_str());
+    }
+    args.push("-z");
+    run_capture(AIRPORT, &args).map(|_| ())
+}
+
+fn wifi_device() -> Result<String, ForgeFfiError> {
+    let text = run_capture("networksetup", &["-listallhardwareports"])?;
+    let mut is_wifi = false;
+    for line in text.lines() {
+        if let Some(port) = line.strip_prefix("Hardware Port: ") {
+            is_wifi = port.trim() == "Wi-Fi" || port.trim() == "AirPort";
+        } else if let Some(dev) = line.strip_prefix("Device: ")
+            && is_wifi
+        {
+            return Ok(dev.trim().to_string());
+        }
+    }
+    Err(ForgeFfiError::not_found("未找到无线网卡".to_string()))
+}
+
+fn parse_airport_scan(text: &str) -> Vec<WifiNetwork> {
+    let mut out = Vec::new();
+    for line in text.lines().skip(1) {
+        let Some((bssid_at, bssid)) = find_bssid(line) else {
+            continue;
+        };
+        let ssid = line[..bssid_at].trim().to_string();
+        let mut rest = line[bssid_at + bssid.len()..].split_whitespace();
+        let signal_dbm = rest.next().and_then(|v| v.parse::<i32>().ok());
+        let channel = rest.next().and_then(|v| {
+            v.split(',')
+                .next()
+                .and_then(|c| c.parse::<u32>().ok())
+        });
+        let _ht = rest.next();
+        let _cc = rest.next();
+        let security: Vec<&str> = rest.collect();
+        out.push(WifiNetwork {
+            ssid,
+            bssid: Some(bssid.to_string()),
+            signal_dbm,
+            signal_percent: None,
+            frequency_mhz: None,
+            channel,
+            security: parse_security(&security.join(" ")),
+            in_use: None,
+        });
+    }
+    out
+}
+
+fn find_bssid(line: &str) -> Option<(usize, &str)> {
+    let mut offset = 0;
+    for tok in line.split(' ') {
+        if is_mac(tok) {
+            return Some((offset, tok));
+        }
+        offset += tok.len() + 1;
+    }
+    None
+}
+
+fn is_mac(s: &str) -> bool {
+    let parts: Vec<&str> = s.split(':').collect();
+    parts.len() == 6
+        && parts
+            .iter()
+            .all(|p| (1..=2).contains(&p.len()) && p.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn run_capture(program: &str, args: &[&str]) -> Result<String, ForgeFfiError> {
+    let out = command(program)
+        .args(args)
+        .output()
+        .map_err(|e| ForgeFfiError::unsupported(format!("执行命令失败: {program}: {e}")))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        if stderr.contains("not permitted") || stderr.contains("root") {
+            return Err(ForgeFfiError::permission_denied(stderr.trim().to_string()));
+        }
+        // 参数里可能有 PSK（-setairportnetwork <dev> <ssid> <psk>），只带子命令
+        Err(ForgeFfiError::system_error(format!(
+            "命令失败: {program} {}: {stderr}",
+            args.first().copied().unwrap_or_default()
+        )))
+    }
+}
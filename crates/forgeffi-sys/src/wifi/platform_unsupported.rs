@@ -0,0 +1,13 @@
+use super::*;
+
+pub(super) fn scan(_interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 wifi".to_string()))
+}
+
+pub(super) fn connect(_ssid: &str, _psk: Option<&str>, _interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 wifi".to_string()))
+}
+
+pub(super) fn disconnect(_interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    Err(ForgeFfiError::not_implemented("当前平台暂不支持 wifi".to_string()))
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+use crate::util::{command, SecretFile};
+
+pub(super) fn scan(interface: Option<&str>) -> Result<Vec<WifiNetwork>, ForgeFfiError> {
+    let mut args = vec![
+        "wlan".to_string(),
+        "show".to_string(),
+        "networks".to_string(),
+        "mode=bssid".to_string(),
+    ];
+    if let Some(ifname) = interface {
+        args.push(format!("interface={ifname}"));
+    }
+    let text = run_netsh(&args)?;
+    Ok(parse_netsh_networks(&text))
+}
+
+pub(super) fn connect(ssid: &str, psk: Option<&str>, interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    // 配置里有明文 keyMaterial：写入唯一的 create_new 临时文件，netsh 读完立即删除，出错或 panic 时由 Drop 删除
+    let profile = SecretFile::new("wifi", profile_xml(ssid, psk).as_bytes())?;
+    let mut add = vec![
+        "wlan".to_string(),
+        "add".to_string(),
+        "profile".to_string(),
+        format!("filename={}", profile.path()),
+    ];
+    if let Some(ifname) = interface {
+        add.push(format!("interface={ifname}"));
+    }
+    let added = run_netsh(&add);
+    drop(profile);
+    added?;
+
+    let mut args = vec![
+        "wlan".to_string(),
+        "connect".to_string(),
+        format!("name={ssid}"),
+        format!("ssid={ssid}"),
+    ];
+    if let Some(ifname) = interface {
+        args.push(format!("interface={ifname}"));
+    }
+    run_netsh(&args).map(|_| ())
+}
+
+pub(super) fn disconnect(interface: Option<&str>) -> Result<(), ForgeFfiError> {
+    let mut args = vec!["wlan".to_string(), "disconnect".to_string()];
+    if let Some(ifname) = interface {
+        args.push(format!("interface={ifname}"));
+    }
+    run_netsh(&args).map(|_| ())
+}
+
+fn profile_xml(ssid: &str, psk: Option<&str>) -> String {
+    let name = xml_escape(ssid);
+    let security = match psk {
+        None => "<authEncryption><authentication>open</authentication><encryption>none</encryption><useOneX>false</useOneX></authEncryption>".to_string(),
+        Some(psk) => {
+            let key_type = if psk.len() == 64 { "networkKey" } else { "passPhrase" };
+            format!(
+                "<authEncryption><authentication>WPA2PSK</authentication><encryption>AES</encryption><useOneX>false</useOneX></authEncryption>\
+<sharedKey><keyType>{key_type}</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+                xml_escape(psk)
+            )
+        }
+    };
+    format!(
+        "<?xml version=\"1.0\"?>\
+<WLANProfile xmlns=\"http://www.microsoft.com/networking/WLAN/profile/v1\">\
+<name>{name}</name>\
+<SSIDConfig><SSID><name>{name}</name></SSID></SSIDConfig>\
+<connectionType>ESS</connectionType><connectionMode>manual</connectionMode>\
+<MSM><security>{security}</security></MSM>\
+</WLANProfile>"
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn parse_netsh_networks(text: &str) -> Vec<WifiNetwork> {
+    let mut out: Vec<WifiNetwork> = Vec::new();
+    let mut ssid = String::new();
+    let mut auth = String::new();
+    let mut first_of_ssid = out.len();
+
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        let head = key.split_whitespace().next().unwrap_or_default();
+
+        if head == "SSID" {
+            ssid = value.to_string();
+            auth.clear();
+            first_of_ssid = out.len();
+        } else if key == "Authentication" || key == "身份验证" {
+            auth = value.to_string();
+            for n in &mut out[first_of_ssid..] {
+                n.security = parse_security(&auth);
+            }
+        } else if head == "BSSID" {
+            out.push(WifiNetwork {
+                ssid: ssid.clone(),
+                bssid: Some(value.to_string()),
+                signal_dbm: None,
+                signal_percent: None,
+                frequency_mhz: None,
+                channel: None,
+                security: parse_security(&auth),
+                in_use: None,
+            });
+        } else if out.len() > first_of_ssid {
+            let Some(cur) = out.last_mut() else {
+                continue;
+            };
+            if key == "Signal" || key == "信号" {
+                cur.signal_percent = value.trim_end_matches('%').trim().parse().ok();
+            } else if key == "Channel" || key == "信道" || key == "频道" {
+                cur.channel = value.parse().ok();
+            }
+        }
+    }
+    out
+}
+
+fn run_netsh(args: &[String]) -> Result<String, ForgeFfiError> {
+    let out = command("netsh")
+        .args(args)
+        .output()
+        .map_err(|e| ForgeFfiError::unsupported(format!("无法执行 netsh: {e}")))?;
+    let stdout = String::from_utf8_lossy(&out.stdout).into_owned();
+    if out.status.success() {
+        return Ok(stdout);
+    }
+    // netsh 把错误信息写到 stdout
+    let msg = format!("{}{}", stdout.trim(), String::from_utf8_lossy(&out.stderr).trim());
+    let lower = msg.to_lowercase();
+    if lower.contains("access is denied") || msg.contains("拒绝访问") {
+        Err(ForgeFfiError::permission_denied(msg))
+    } else if lower.contains("no wireless interface") || lower.contains("not found") || msg.contains("没有无线接口") {
+        Err(ForgeFfiError::not_found(msg))
+    } else {
+        Err(ForgeFfiError::system_error(format!("netsh 失败: {msg}")))
+    }
+}
@@ -0,0 +1,285 @@
+// 抓包的纯用户态部分：filter 语法与匹配、pcap 读取与 pcapng 写出。
+// 最后一个用例在 Linux 上抓 lo，没有 CAP_NET_RAW 时跳过
+#![cfg(feature = "capture")]
+
+use forgeffi_base::{CaptureStartRequest, CaptureStopReason, ErrorCode};
+use forgeffi_sys::capture::filter::{CaptureFilter, LINKTYPE_ETHERNET, LINKTYPE_NULL, LINKTYPE_RAW};
+use forgeffi_sys::capture::pcap::PcapReader;
+use forgeffi_sys::capture::pcapng::{packet_block_len, PcapngWriter};
+
+fn ipv4_tcp(src: [u8; 4], sport: u16, dst: [u8; 4], dport: u16) -> Vec<u8> {
+    let mut ip = vec![0x45, 0, 0, 40, 0, 0, 0x40, 0, 64, 6, 0, 0];
+    ip.extend_from_slice(&src);
+    ip.extend_from_slice(&dst);
+    ip.extend_from_slice(&sport.to_be_bytes());
+    ip.extend_from_slice(&dport.to_be_bytes());
+    ip.extend_from_slice(&[0u8; 16]);
+    ip
+}
+
+fn ipv6_udp(src_last: u8, sport: u16, dport: u16) -> Vec<u8> {
+    let mut ip = vec![0x60, 0, 0, 0, 0, 8, 17, 64];
+    let mut src = [0u8; 16];
+    src[0] = 0xfd;
+    src[15] = src_last;
+    let mut dst = [0u8; 16];
+    dst[0] = 0xfd;
+    dst[15] = 1;
+    ip.extend_from_slice(&src);
+    ip.extend_from_slice(&dst);
+    ip.extend_from_slice(&sport.to_be_bytes());
+    ip.extend_from_slice(&dport.to_be_bytes());
+    ip.extend_from_slice(&[0, 8, 0, 0]);
+    ip
+}
+
+fn ether(ethertype: u16, vlan: Option<u16>, payload: &[u8]) -> Vec<u8> {
+    let mut f = vec![0xff; 6];
+    f.extend_from_slice(&[0x02, 0, 0, 0, 0, 1]);
+    if let Some(id) = vlan {
+        f.extend_from_slice(&0x8100u16.to_be_bytes());
+        f.extend_from_slice(&id.to_be_bytes());
+    }
+    f.extend_from_slice(&ethertype.to_be_bytes());
+    f.extend_from_slice(payload);
+    f
+}
+
+fn arp_request(sender: [u8; 4], target: [u8; 4]) -> Vec<u8> {
+    let mut a = vec![0, 1, 8, 0, 6, 4, 0, 1, 0x02, 0, 0, 0, 0, 1];
+    a.extend_from_slice(&sender);
+    a.extend_from_slice(&[0u8; 6]);
+    a.extend_from_slice(&target);
+    ether(0x0806, None, &a)
+}
+
+fn matches(filter: &str, linktype: u16, frame: &[u8]) -> bool {
+    CaptureFilter::parse(filter)
+        .unwrap_or_else(|e| panic!("解析 {filter:?} 失败: {e:?}"))
+        .matches(linktype, frame)
+}
+
+#[test]
+fn filter_rejects_bad_syntax() {
+    for bad in [
+        "tcp host 10.0.0.1",
+        "port",
+        "port http",
+        "src ip",
+        "(tcp",
+        "tcp)",
+        "host example.com",
+        "net 10.0.0.1",
+        "tcp and",
+        "vlan 10",
+        "ether host 02:00:00:00:00:01",
+        "tcp port 80 udp",
+    ] {
+        let err = CaptureFilter::parse(bad).expect_err(bad);
+        assert_eq!(err.code, ErrorCode::InvalidArgument, "{bad}");
+    }
+    assert!(CaptureFilter::parse("  ").unwrap().matches_all());
+}
+
+#[test]
+fn filter_matches_ipv4_tcp() {
+    let frame = ether(0x0800, None, &ipv4_tcp([10, 0, 0, 1], 40000, [192, 168, 1, 2], 443));
+    for yes in [
+        "ip",
+        "tcp",
+        "tcp port 443",
+        "tcp dst port 443",
+        "src port 40000",
+        "host 192.168.1.2",
+        "src host 10.0.0.1",
+        "net 10.0.0.0/8 and dst net 192.168.0.0/16",
+        "udp or tcp",
+        "!udp && (port 443 || port 80)",
+        "not ip6",
+        "ether proto 0x0800",
+    ] {
+        assert!(matches(yes, LINKTYPE_ETHERNET, &frame), "{yes}");
+    }
+    for no in [
+        "ip6",
+        "udp",
+        "udp port 443",
+        "src port 443",
+        "dst host 10.0.0.1",
+        "net 172.16.0.0/12",
+        "tcp and not port 443",
+        "arp",
+        "icmp",
+    ] {
+        assert!(!matches(no, LINKTYPE_ETHERNET, &frame), "{no}");
+    }
+    // 同一个包以裸 IP 链路出现
+    let raw = ipv4_tcp([10, 0, 0, 1], 40000, [192, 168, 1, 2], 443);
+    assert!(matches("tcp port 443", LINKTYPE_RAW, &raw));
+}
+
+#[test]
+fn filter_matches_vlan_ipv6_and_arp() {
+    let frame = ether(0x86dd, Some(100), &ipv6_udp(9, 5353, 53));
+    assert!(matches("ip6 and udp dst port 53", LINKTYPE_ETHERNET, &frame));
+    assert!(matches("host fd00::9", LINKTYPE_ETHERNET, &frame));
+    assert!(matches("src net fd00::/64", LINKTYPE_ETHERNET, &frame));
+    assert!(!matches("tcp port 53", LINKTYPE_ETHERNET, &frame));
+    assert!(!matches("ip", LINKTYPE_ETHERNET, &frame));
+
+    let arp = arp_request([10, 0, 0, 1], [10, 0, 0, 254]);
+    assert!(matches("arp", LINKTYPE_ETHERNET, &arp));
+    assert!(matches("dst host 10.0.0.254", LINKTYPE_ETHERNET, &arp));
+    assert!(!matches("ip", LINKTYPE_ETHERNET, &arp));
+    assert!(!matches("port 53", LINKTYPE_ETHERNET, &arp));
+
+    // BSD loopback：4 字节主机序地址族
+    let mut null = 2u32.to_le_bytes().to_vec();
+    null.extend(ipv4_tcp([127, 0, 0, 1], 1, [127, 0, 0, 1], 22));
+    assert!(matches("tcp port 22", LINKTYPE_NULL, &null));
+
+    // 截断的包不会越界，只是匹配不上
+    assert!(!matches("tcp port 443", LINKTYPE_ETHERNET, &frame[..20]));
+    assert!(!matches("ip", 9999, &frame));
+    assert!(matches("", 9999, &frame));
+}
+
+fn pcap_stream(big_endian: bool, nanos: bool, packets: &[(u32, u32, &[u8])]) -> Vec<u8> {
+    let u32b = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+    let u16b = |v: u16| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+    let mut s = Vec::new();
+    s.extend(u32b(if nanos { 0xA1B2_3C4D } else { 0xA1B2_C3D4 }));
+    s.extend(u16b(2));
+    s.extend(u16b(4));
+    s.extend(u32b(0));
+    s.extend(u32b(0));
+    s.extend(u32b(65535));
+    s.extend(u32b(1));
+    for (sec, frac, data) in packets {
+        s.extend(u32b(*sec));
+        s.extend(u32b(*frac));
+        s.extend(u32b(data.len() as u32));
+        s.extend(u32b(data.len() as u32 + 10));
+        s.extend_from_slice(data);
+    }
+    s
+}
+
+fn le32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(b[at..at + 4].try_into().unwrap())
+}
+
+#[test]
+fn pcap_reader_handles_byte_order_and_nanos() {
+    let a = [1u8, 2, 3];
+    let b = [4u8; 61];
+    for (be, nanos) in [(false, false), (true, false), (false, true), (true, true)] {
+        let frac = if nanos { 250_000_000 } else { 250_000 };
+        let stream = pcap_stream(be, nanos, &[(10, frac, &a), (11, 0, &b)]);
+        let mut r = PcapReader::new(stream.as_slice()).unwrap();
+        assert_eq!(r.linktype(), LINKTYPE_ETHERNET);
+        assert_eq!(r.snaplen(), 65535);
+        let p = r.next_packet().unwrap().unwrap();
+        assert_eq!((p.ts_us, p.orig_len, p.data.as_slice()), (10_250_000, 13, &a[..]));
+        let p = r.next_packet().unwrap().unwrap();
+        assert_eq!((p.ts_us, p.data.len()), (11_000_000, 61));
+        assert!(r.next_packet().unwrap().is_none());
+    }
+
+    let stream = pcap_stream(false, false, &[(1, 0, &b)]);
+    let mut r = PcapReader::new(&stream[..stream.len() - 5]).unwrap();
+    assert!(r.next_packet().is_err());
+    assert!(PcapReader::new(&b[..]).is_err());
+    assert!(PcapReader::new(&[][..]).is_err());
+}
+
+#[test]
+fn pcapng_writer_layout() {
+    let mut w = PcapngWriter::new(Vec::new(), LINKTYPE_ETHERNET, 262_144, "eth0").unwrap();
+    let header_len = w.bytes_written();
+    let data = [0xabu8; 61];
+    w.write_packet(0x1_0000_0002, 100, &data).unwrap();
+    assert_eq!(w.bytes_written(), header_len + packet_block_len(data.len()));
+    let out = w.finish().unwrap();
+    assert_eq!(out.len() as u64, header_len + packet_block_len(data.len()));
+
+    // SHB：类型、字节序标记、首尾长度一致
+    assert_eq!(le32(&out, 0), 0x0A0D_0D0A);
+    let shb_len = le32(&out, 4) as usize;
+    assert_eq!(le32(&out, 8), 0x1A2B_3C4D);
+    assert_eq!(le32(&out, shb_len - 4) as usize, shb_len);
+
+    // IDB：链路类型与 snaplen，if_name 选项
+    let idb = &out[shb_len..];
+    assert_eq!(le32(idb, 0), 1);
+    let idb_len = le32(idb, 4) as usize;
+    assert_eq!(u16::from_le_bytes([idb[8], idb[9]]), LINKTYPE_ETHERNET);
+    assert_eq!(le32(idb, 12), 262_144);
+    assert_eq!(&idb[16..24], &[2, 0, 4, 0, b'e', b't', b'h', b'0']);
+
+    // EPB：时间戳拆成高低 32 位，数据补齐到 4 字节
+    let epb = &idb[idb_len..];
+    assert_eq!(le32(epb, 0), 6);
+    let epb_len = le32(epb, 4) as usize;
+    assert_eq!(epb_len, epb.len());
+    assert_eq!(epb_len % 4, 0);
+    assert_eq!((le32(epb, 12), le32(epb, 16)), (1, 2));
+    assert_eq!((le32(epb, 20), le32(epb, 24)), (61, 100));
+    assert_eq!(&epb[28..28 + 61], &data[..]);
+    assert_eq!(le32(epb, epb_len - 4) as usize, epb_len);
+}
+
+#[test]
+fn start_rejects_invalid_requests() {
+    let dir = std::env::temp_dir();
+    let path = dir.join("forgeffi-capture-invalid.pcapng").to_string_lossy().into_owned();
+    let mut req = CaptureStartRequest::v1("lo", &path);
+    req.max_secs = Some(0);
+    assert_eq!(forgeffi_sys::capture::start(&req).unwrap_err().code, ErrorCode::InvalidArgument);
+
+    let mut req = CaptureStartRequest::v1("lo", &path);
+    req.filter = Some("tcp host".to_string());
+    assert_eq!(forgeffi_sys::capture::start(&req).unwrap_err().code, ErrorCode::InvalidArgument);
+
+    let req = CaptureStartRequest::v1("-i", &path);
+    assert_eq!(forgeffi_sys::capture::start(&req).unwrap_err().code, ErrorCode::InvalidArgument);
+
+    assert_eq!(forgeffi_sys::capture::stop(u64::MAX).unwrap_err().code, ErrorCode::NotFound);
+    assert!(!std::path::Path::new(&path).exists());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn capture_loopback_udp() {
+    use std::net::UdpSocket;
+
+    let path = std::env::temp_dir()
+        .join(format!("forgeffi-capture-{}.pcapng", std::process::id()))
+        .to_string_lossy()
+        .into_owned();
+    let sock = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let port = sock.local_addr().unwrap().port();
+    let mut req = CaptureStartRequest::v1("lo", &path);
+    req.filter = Some(format!("udp dst port {port}"));
+    req.max_secs = Some(30);
+    let handle = match forgeffi_sys::capture::start(&req) {
+        Ok(h) => h,
+        Err(e) if e.code == ErrorCode::PermissionDenied => return,
+        Err(e) => panic!("启动抓包失败: {e:?}"),
+    };
+    assert!(forgeffi_sys::capture::active().contains(&handle));
+    for _ in 0..3 {
+        sock.send_to(b"forgeffi", ("127.0.0.1", port)).unwrap();
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let summary = forgeffi_sys::capture::stop(handle).unwrap();
+    let file = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(summary.reason, CaptureStopReason::Stopped);
+    assert_eq!(summary.linktype, LINKTYPE_ETHERNET);
+    assert!(summary.packets >= 3, "{summary:?}");
+    assert_eq!(summary.file_bytes, file.len() as u64);
+    assert_eq!(le32(&file, 0), 0x0A0D_0D0A);
+    assert!(!forgeffi_sys::capture::active().contains(&handle));
+}
@@ -0,0 +1,234 @@
+// 平台输出解析器的 golden 测试：tests/fixtures/<解析器>/ 下的每个样本对应一个 .golden.json。
+// 新增样本或有意改变解析结果后，用 FORGEFFI_UPDATE_GOLDEN=1 重新生成，提交前检查 diff
+#![cfg(feature = "netif")]
+
+use forgeffi_base::IpFamily;
+use forgeffi_sys::netif::parse;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const UPDATE_ENV: &str = "FORGEFFI_UPDATE_GOLDEN";
+
+fn samples(dir: &str, prefix: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(dir);
+    let mut out: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("读取 {} 失败: {e}", dir.display()))
+        .map(|e| e.expect("目录项").path())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            name.starts_with(prefix) && !name.ends_with(".golden.json")
+        })
+        .collect();
+    out.sort();
+    assert!(!out.is_empty(), "{} 下没有 {prefix}* 样本", dir.display());
+    out
+}
+
+fn check_golden(sample: &Path, actual: &impl Serialize) {
+    let golden = sample.with_extension("golden.json");
+    let actual = serde_json::to_string_pretty(actual).expect("序列化解析结果") + "\n";
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::write(&golden, &actual).expect("写入 golden 文件");
+        return;
+    }
+    let expected = std::fs::read_to_string(&golden)
+        .unwrap_or_else(|e| panic!("读取 {} 失败（{UPDATE_ENV}=1 可生成）: {e}", golden.display()));
+    assert!(
+        expected == actual,
+        "{} 的解析结果与 golden 不一致（有意修改时用 {UPDATE_ENV}=1 重新生成）\n--- 实际 ---\n{actual}",
+        sample.display()
+    );
+}
+
+fn read(path: &Path) -> Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|e| panic!("读取 {} 失败: {e}", path.display()))
+}
+
+#[test]
+fn ifconfig_samples() {
+    for p in samples("ifconfig", "") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        check_golden(&p, &parse::parse_ifconfig(&text));
+    }
+}
+
+#[test]
+fn ip_address_samples() {
+    for p in samples("iproute2", "addr-") {
+        let items = parse::parse_ip_address_json(&read(&p))
+            .unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &items);
+    }
+}
+
+#[test]
+fn ip_route_samples() {
+    for p in samples("iproute2", "route") {
+        let name = p.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let family = if name.starts_with("route6") { IpFamily::Ipv6 } else { IpFamily::Ipv4 };
+        let routes = parse::parse_ip_route_json(&read(&p), family)
+            .unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &routes);
+    }
+}
+
+#[test]
+fn powershell_samples() {
+    for p in samples("powershell", "windows-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let items = parse::parse_powershell_list(&text)
+            .unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &items);
+    }
+}
+
+#[test]
+fn netqos_policy_samples() {
+    for p in samples("powershell", "netqos-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let items = parse::parse_netqos_policy_json(&text)
+            .unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &items);
+    }
+}
+
+#[test]
+fn tc_samples() {
+    for p in samples("tc", "qdisc-") {
+        let items = parse::parse_tc_qdisc_json(&read(&p)).unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &items);
+    }
+    // 只取 SetBandwidthLimit 使用的 1:10 分类
+    for p in samples("tc", "class-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        check_golden(&p, &parse::parse_tc_class_rate(&text, "1:10"));
+    }
+    for p in samples("tc", "filter-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        check_golden(&p, &parse::parse_tc_police_rate(&text));
+    }
+    assert_eq!(parse::parse_tc_rate("100Mbit"), Some(100_000_000));
+    assert_eq!(parse::parse_tc_rate("1.5Gbit"), Some(1_500_000_000));
+    assert_eq!(parse::parse_tc_rate("8Mibit"), Some(8 * 1024 * 1024));
+    assert_eq!(parse::parse_tc_rate("12"), None);
+    assert_eq!(parse::parse_tc_rate("fastbit"), None);
+}
+
+#[test]
+fn wg_dump_samples() {
+    for p in samples("wireguard", "dump-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let info = parse::parse_wg_dump(&text).unwrap_or_else(|| panic!("{}: 没有接口行", p.display()));
+        check_golden(&p, &info);
+    }
+}
+
+// rendered-* 是 render_netplan 的原样输出，读回再渲染必须逐字节一致；accepted-* 读回再渲染后条目不变；
+// rejected-* 是手工改过的结构，必须拒绝
+#[test]
+fn netplan_samples() {
+    for p in samples("netplan", "rendered-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let parsed = parse::parse_netplan_ethernets(&text).unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        assert_eq!(parse::render_netplan(&parsed), text, "{} 渲染结果与样本不一致", p.display());
+        check_golden(&p, &parsed);
+    }
+    for p in samples("netplan", "accepted-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let parsed = parse::parse_netplan_ethernets(&text).unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        let reparsed = parse::parse_netplan_ethernets(&parse::render_netplan(&parsed)).expect("读回渲染结果");
+        assert_eq!(reparsed, parsed, "{} 渲染后读回的条目不一致", p.display());
+        check_golden(&p, &parsed);
+    }
+    for p in samples("netplan", "rejected-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        let err = parse::parse_netplan_ethernets(&text).expect_err(&p.display().to_string());
+        assert_eq!(err.code, forgeffi_base::ErrorCode::Unsupported, "{}", p.display());
+    }
+}
+
+// 截断、类型错位的输出只能返回错误或跳过条目，不能 panic
+#[test]
+fn malformed_output_does_not_panic() {
+    for p in samples("iproute2", "") {
+        let bytes = read(&p);
+        for cut in (0..bytes.len()).step_by(97) {
+            let _ = parse::parse_ip_address_json(&bytes[..cut]);
+            let _ = parse::parse_ip_route_json(&bytes[..cut], IpFamily::Ipv4);
+        }
+    }
+    for p in samples("tc", "") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        for cut in (0..text.len()).filter(|i| text.is_char_boundary(*i)).step_by(53) {
+            let _ = parse::parse_tc_qdisc_json(&text.as_bytes()[..cut]);
+            let _ = parse::parse_tc_class_rate(&text[..cut], "1:10");
+            let _ = parse::parse_tc_police_rate(&text[..cut]);
+        }
+    }
+    for p in samples("powershell", "")
+        .into_iter()
+        .chain(samples("ifconfig", ""))
+        .chain(samples("wireguard", ""))
+    {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        for cut in (0..text.len()).filter(|i| text.is_char_boundary(*i)).step_by(61) {
+            let _ = parse::parse_powershell_list(&text[..cut]);
+            let _ = parse::parse_ifconfig(&text[..cut]);
+            let _ = parse::parse_wg_dump(&text[..cut]);
+            let _ = parse::parse_netqos_policy_json(&text[..cut]);
+        }
+    }
+    assert!(parse::parse_ip_address_json(br#"{"ifindex":1}"#).is_err());
+    assert!(parse::parse_ip_address_json(br#"[{"ifindex":"1","ifname":"lo"}]"#).is_err());
+    assert!(parse::parse_powershell_list("").is_err());
+    assert_eq!(parse::parse_powershell_list(r#"{"adapters":"oops","ips":7}"#).map(|v| v.len()).ok(), Some(0));
+    assert!(parse::parse_ifconfig("\t\n\n  stray indented line\n").is_empty());
+    assert!(parse::parse_wg_dump("only-one-field\n").is_none());
+    assert!(parse::parse_tc_qdisc_json(br#"[{"handle":"1:","dev":"eth0"}]"#).is_err());
+    assert_eq!(parse::parse_netqos_policy_json("").map(|v| v.len()).ok(), Some(0));
+}
+
+#[cfg(feature = "lldp")]
+fn hex_frame(path: &Path) -> Vec<u8> {
+    let text: String = String::from_utf8(read(path))
+        .expect("样本是 UTF-8")
+        .split_whitespace()
+        .collect();
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).expect("十六进制"))
+        .collect()
+}
+
+#[cfg(feature = "lldp")]
+#[test]
+fn lldp_samples() {
+    use forgeffi_sys::lldp::parse as lldp;
+
+    for p in samples("lldp", "lldpctl-") {
+        let items = lldp::parse_lldpctl_json(&read(&p)).unwrap_or_else(|e| panic!("{}: {}", p.display(), e.message));
+        check_golden(&p, &items);
+    }
+    for p in samples("lldp", "lldptool-") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        check_golden(&p, &lldp::parse_lldptool_text(&text));
+    }
+    // TLV 被截断的帧和非 LLDP/CDP 帧解析为 null
+    for p in samples("lldp", "frame-") {
+        let frame = hex_frame(&p);
+        let parsed = lldp::parse_discovery_frame(&frame);
+        for cut in 0..frame.len() {
+            let _ = lldp::parse_discovery_frame(&frame[..cut]);
+        }
+        check_golden(&p, &parsed);
+    }
+    for p in samples("lldp", "lldp") {
+        let text = String::from_utf8(read(&p)).expect("样本是 UTF-8");
+        for cut in (0..text.len()).filter(|i| text.is_char_boundary(*i)).step_by(37) {
+            let _ = lldp::parse_lldpctl_json(&text.as_bytes()[..cut]);
+            let _ = lldp::parse_lldptool_text(&text[..cut]);
+        }
+    }
+    assert!(lldp::parse_lldpctl_json(b"[]").is_err());
+    assert!(lldp::parse_lldpctl_json(br#"{"lldp":{"interface":"eth0"}}"#).is_ok_and(|v| v.is_empty()));
+}
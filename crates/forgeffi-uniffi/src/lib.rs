@@ -0,0 +1,58 @@
+// UniFFI 绑定：在 forgeffi-sys 的 Rust API 之上导出带类型的接口，
+// Swift/Kotlin/Python 绑定由 uniffi-bindgen 从编译好的库生成；与 C ABI（JSON）并存，互不影响
+use std::fmt;
+
+use forgeffi_base as base;
+
+mod types;
+
+pub use types::*;
+
+uniffi::setup_scaffolding!("forgeffi");
+
+#[derive(Debug, uniffi::Error)]
+pub enum ForgeFfiError {
+    Failed { code: ErrorCode, message: String },
+}
+
+impl fmt::Display for ForgeFfiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForgeFfiError::Failed { code, message } => write!(f, "{code:?}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ForgeFfiError {}
+
+impl From<base::ForgeFfiError> for ForgeFfiError {
+    fn from(e: base::ForgeFfiError) -> Self {
+        ForgeFfiError::Failed {
+            code: e.code.into(),
+            message: e.message,
+        }
+    }
+}
+
+#[uniffi::export]
+pub fn abi_version() -> u32 {
+    base::ABI_VERSION
+}
+
+#[uniffi::export]
+pub fn list_interfaces() -> Result<Vec<NetInterface>, ForgeFfiError> {
+    let items = forgeffi_sys::netif::list_interfaces()?;
+    Ok(items.into_iter().map(Into::into).collect())
+}
+
+#[uniffi::export]
+pub fn apply(req: NetIfApplyRequest) -> Result<NetIfApplyResponse, ForgeFfiError> {
+    let resp = forgeffi_sys::netif::apply_request(req.into())?;
+    Ok(resp.into())
+}
+
+#[uniffi::export]
+pub fn confirm(token: String) -> Result<(), ForgeFfiError> {
+    forgeffi_sys::netif::confirm(&token)?;
+    Ok(())
+}
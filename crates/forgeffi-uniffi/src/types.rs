@@ -0,0 +1,624 @@
+// forgeffi-base 的 netif 类型在 UniFFI 侧的镜像。base 保持无绑定依赖，
+// 这里逐字段对应，字段含义见 forgeffi-base/src/netif.rs
+use forgeffi_base as base;
+
+// 无字段枚举与 base 一一对应，两个方向都要转换（请求里的 Ipv6Mode/Duplex 需要反向）
+macro_rules! mirror_enum {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, uniffi::Enum)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl From<base::$name> for $name {
+            fn from(v: base::$name) -> Self {
+                match v {
+                    $(base::$name::$variant => $name::$variant),+
+                }
+            }
+        }
+
+        impl From<$name> for base::$name {
+            fn from(v: $name) -> Self {
+                match v {
+                    $($name::$variant => base::$name::$variant),+
+                }
+            }
+        }
+    };
+}
+
+mirror_enum!(ErrorCode {
+    Ok,
+    InvalidArgument,
+    NotFound,
+    Unsupported,
+    PermissionDenied,
+    SystemError,
+    TargetGone,
+    Timeout,
+    Busy,
+    PartialFailure,
+    NotImplemented,
+    Cancelled,
+    BufferTooSmall,
+    Unknown,
+});
+mirror_enum!(IfaceKind { Unknown, Physical, Virtual, Loopback, Tunnel, Vlan, Bridge });
+mirror_enum!(AdminState { Unknown, Up, Down });
+mirror_enum!(OperState { Unknown, Up, Down, Dormant, LowerLayerDown });
+mirror_enum!(IpScope { Unknown, Host, Link, Site, Global });
+mirror_enum!(IpOrigin { Unknown, Static, Dhcp });
+mirror_enum!(IpFamily { Ipv4, Ipv6 });
+mirror_enum!(RouteOrigin { Unknown, Static, Dhcp, RouterAdvertisement, Kernel });
+mirror_enum!(Ipv6Mode { Slaac, Dhcp6, Static });
+mirror_enum!(NetIfScope { Runtime, Persistent, Both });
+mirror_enum!(IpDadState { Tentative, Duplicate, Deprecated, Preferred });
+mirror_enum!(Duplex { Half, Full });
+mirror_enum!(BondMode { BalanceRr, ActiveBackup, BalanceXor, Broadcast, Lacp, BalanceTlb, BalanceAlb });
+mirror_enum!(TunnelKind { Wireguard, Gre, Ipip, Sit, Other });
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct IpAddress {
+    pub ip: String,
+    pub prefix_len: u8,
+    pub scope: Option<IpScope>,
+    pub origin: Option<IpOrigin>,
+    // IpAddrFlags 位掩码
+    pub flags: Option<u32>,
+    pub dad_state: Option<IpDadState>,
+    pub valid_lft_secs: Option<u32>,
+    pub preferred_lft_secs: Option<u32>,
+}
+
+impl From<base::IpAddrEntry> for IpAddress {
+    fn from(v: base::IpAddrEntry) -> Self {
+        Self {
+            ip: v.ip,
+            prefix_len: v.prefix_len,
+            scope: v.scope.map(Into::into),
+            origin: v.origin.map(Into::into),
+            flags: v.flags.map(|f| f.0),
+            dad_state: v.dad_state.map(Into::into),
+            valid_lft_secs: v.valid_lft_secs,
+            preferred_lft_secs: v.preferred_lft_secs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct DefaultRoute {
+    pub family: IpFamily,
+    pub gateway: Option<String>,
+    pub metric: Option<u32>,
+    pub origin: Option<RouteOrigin>,
+    pub preferred_src: Option<String>,
+}
+
+impl From<base::DefaultRoute> for DefaultRoute {
+    fn from(v: base::DefaultRoute) -> Self {
+        Self {
+            family: v.family.into(),
+            gateway: v.gateway,
+            metric: v.metric,
+            origin: v.origin.map(Into::into),
+            preferred_src: v.preferred_src,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct LinkSpeedMode {
+    pub speed_mbps: u32,
+    pub duplex: Duplex,
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfCapabilities {
+    pub can_set_admin_state: bool,
+    pub can_set_mtu: bool,
+    pub can_add_del_ip: bool,
+    pub can_set_dhcp: bool,
+    pub can_set_dns: bool,
+    pub can_set_ipv6: bool,
+    pub can_rename: bool,
+    pub can_set_description: bool,
+    pub can_set_mac: bool,
+    pub can_manage_routes: bool,
+    pub supported_speeds: Vec<LinkSpeedMode>,
+    pub notes: Option<String>,
+}
+
+impl From<base::NetIfCapabilities> for NetIfCapabilities {
+    fn from(v: base::NetIfCapabilities) -> Self {
+        Self {
+            can_set_admin_state: v.can_set_admin_state,
+            can_set_mtu: v.can_set_mtu,
+            can_add_del_ip: v.can_add_del_ip,
+            can_set_dhcp: v.can_set_dhcp,
+            can_set_dns: v.can_set_dns,
+            can_set_ipv6: v.can_set_ipv6,
+            can_rename: v.can_rename,
+            can_set_description: v.can_set_description,
+            can_set_mac: v.can_set_mac,
+            can_manage_routes: v.can_manage_routes,
+            supported_speeds: v
+                .supported_speeds
+                .into_iter()
+                .map(|s| LinkSpeedMode {
+                    speed_mbps: s.speed_mbps,
+                    duplex: s.duplex.into(),
+                })
+                .collect(),
+            notes: v.notes,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct WireguardPeerInfo {
+    pub public_key: String,
+    pub endpoint: Option<String>,
+    pub allowed_ips: Vec<String>,
+    pub latest_handshake_unix: Option<u64>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub persistent_keepalive_secs: Option<u16>,
+}
+
+impl From<base::WireguardPeerInfo> for WireguardPeerInfo {
+    fn from(v: base::WireguardPeerInfo) -> Self {
+        Self {
+            public_key: v.public_key,
+            endpoint: v.endpoint,
+            allowed_ips: v.allowed_ips,
+            latest_handshake_unix: v.latest_handshake_unix,
+            rx_bytes: v.rx_bytes,
+            tx_bytes: v.tx_bytes,
+            persistent_keepalive_secs: v.persistent_keepalive_secs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct WireguardInfo {
+    pub public_key: Option<String>,
+    pub listen_port: Option<u16>,
+    pub fwmark: Option<u32>,
+    pub peers: Vec<WireguardPeerInfo>,
+}
+
+impl From<base::WireguardInfo> for WireguardInfo {
+    fn from(v: base::WireguardInfo) -> Self {
+        Self {
+            public_key: v.public_key,
+            listen_port: v.listen_port,
+            fwmark: v.fwmark,
+            peers: v.peers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct TunnelInfo {
+    pub kind: TunnelKind,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    pub ttl: Option<u8>,
+    pub wireguard: Option<WireguardInfo>,
+}
+
+impl From<base::TunnelInfo> for TunnelInfo {
+    fn from(v: base::TunnelInfo) -> Self {
+        Self {
+            kind: v.kind.into(),
+            local: v.local,
+            remote: v.remote,
+            ttl: v.ttl,
+            wireguard: v.wireguard.map(Into::into),
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetInterface {
+    pub if_index: u32,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub kind: IfaceKind,
+    pub is_physical: Option<bool>,
+    pub admin_state: AdminState,
+    pub oper_state: Option<OperState>,
+    // IfaceFlags 位掩码
+    pub flags: u32,
+    pub mac: Option<String>,
+    pub mtu: Option<u32>,
+    pub speed_bps: Option<u64>,
+    pub vlan_id: Option<u16>,
+    pub ipv4: Vec<IpAddress>,
+    pub ipv6: Vec<IpAddress>,
+    pub default_routes: Vec<DefaultRoute>,
+    pub ipv4_gateway: Option<String>,
+    pub ipv6_gateway: Option<String>,
+    pub dns_servers: Vec<String>,
+    pub tunnel: Option<TunnelInfo>,
+    pub capabilities: NetIfCapabilities,
+}
+
+impl From<base::NetInterface> for NetInterface {
+    fn from(v: base::NetInterface) -> Self {
+        Self {
+            if_index: v.if_index,
+            name: v.name,
+            display_name: v.display_name,
+            kind: v.kind.into(),
+            is_physical: v.is_physical,
+            admin_state: v.admin_state.into(),
+            oper_state: v.oper_state.map(Into::into),
+            flags: v.flags.0,
+            mac: v.mac,
+            mtu: v.mtu,
+            speed_bps: v.speed_bps,
+            vlan_id: v.vlan_id,
+            ipv4: v.ipv4.into_iter().map(Into::into).collect(),
+            ipv6: v.ipv6.into_iter().map(Into::into).collect(),
+            default_routes: v.default_routes.into_iter().map(Into::into).collect(),
+            ipv4_gateway: v.ipv4_gateway,
+            ipv6_gateway: v.ipv6_gateway,
+            dns_servers: v.dns_servers,
+            tunnel: v.tunnel.map(Into::into),
+            capabilities: v.capabilities.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct WireguardPeer {
+    pub public_key: String,
+    pub preshared_key: Option<String>,
+    pub endpoint: Option<String>,
+    pub allowed_ips: Vec<String>,
+    pub persistent_keepalive_secs: Option<u16>,
+}
+
+impl From<WireguardPeer> for base::WireguardPeer {
+    fn from(v: WireguardPeer) -> Self {
+        Self {
+            public_key: v.public_key,
+            preshared_key: v.preshared_key,
+            endpoint: v.endpoint,
+            allowed_ips: v.allowed_ips,
+            persistent_keepalive_secs: v.persistent_keepalive_secs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct StaticRoute {
+    pub dest: String,
+    pub gateway: Option<String>,
+    pub table: Option<u32>,
+    pub metric: Option<u32>,
+    pub src: Option<String>,
+}
+
+impl From<StaticRoute> for base::StaticRoute {
+    fn from(v: StaticRoute) -> Self {
+        Self {
+            dest: v.dest,
+            gateway: v.gateway,
+            table: v.table,
+            metric: v.metric,
+            src: v.src,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct RoutingRule {
+    pub table: u32,
+    pub priority: Option<u32>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub fwmark: Option<u32>,
+    pub iif: Option<String>,
+    pub oif: Option<String>,
+    pub ipv6: bool,
+}
+
+impl From<RoutingRule> for base::RoutingRule {
+    fn from(v: RoutingRule) -> Self {
+        Self {
+            table: v.table,
+            priority: v.priority,
+            from: v.from,
+            to: v.to,
+            fwmark: v.fwmark,
+            iif: v.iif,
+            oif: v.oif,
+            ipv6: v.ipv6,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct IfaceSelector {
+    pub if_index: Option<u32>,
+    pub name: Option<String>,
+}
+
+impl From<IfaceSelector> for base::IfaceSelector {
+    fn from(v: IfaceSelector) -> Self {
+        Self {
+            if_index: v.if_index,
+            name: v.name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Enum)]
+pub enum NetIfOp {
+    SetAdminState { up: bool },
+    SetMtu { mtu: u32 },
+    AddIp {
+        ip: String,
+        prefix_len: u8,
+        wait_dad_ms: Option<u32>,
+        valid_lft_secs: Option<u32>,
+        preferred_lft_secs: Option<u32>,
+    },
+    DelIp { ip: String, prefix_len: u8 },
+    SetIpv4Dhcp { enable: bool },
+    SetIpv4Static { ip: String, prefix_len: u8, gateway: Option<String> },
+    SetIpv6Mode { mode: Ipv6Mode },
+    SetIpv6Static { ip: String, prefix_len: u8, gateway: Option<String> },
+    CreateVlan { parent: String, vlan_id: u16, name: Option<String> },
+    DeleteVlan,
+    CreateBridge { name: String },
+    DeleteBridge,
+    AddBridgeMember { bridge: String, member: String },
+    RemoveBridgeMember,
+    CreateDummy { name: String },
+    DeleteInterface,
+    CreateBond { name: String, mode: BondMode, members: Vec<String> },
+    DeleteBond,
+    AddBondMember { bond: String, member: String },
+    RemoveBondMember,
+    CreateWireguard {
+        name: String,
+        private_key: String,
+        listen_port: Option<u16>,
+        peers: Vec<WireguardPeer>,
+    },
+    SetWireguardPeer { peer: WireguardPeer },
+    RemoveWireguardPeer { public_key: String },
+    CreateIpTunnel {
+        name: String,
+        kind: TunnelKind,
+        remote: String,
+        local: Option<String>,
+        ttl: Option<u8>,
+    },
+    DeleteTunnel,
+    AddRoute { route: StaticRoute },
+    DelRoute { route: StaticRoute },
+    AddRoutingRule { rule: RoutingRule },
+    DelRoutingRule { rule: RoutingRule },
+    SetDnsSuffix { suffix: String },
+    SwitchNetworkLocation { name: String },
+    SetServiceOrder { services: Vec<String> },
+    SetDnsRegistration { register: bool, use_suffix: Option<bool> },
+    SetName { new_name: String },
+    SetDescription { text: String },
+    SetMac { mac: String },
+    SetLinkSpeed { speed_mbps: Option<u32>, duplex: Option<Duplex> },
+    SetBandwidthLimit { egress_mbps: Option<u32>, ingress_mbps: Option<u32> },
+}
+
+impl From<NetIfOp> for base::NetIfOp {
+    fn from(v: NetIfOp) -> Self {
+        use base::NetIfOp as B;
+        match v {
+            NetIfOp::SetAdminState { up } => B::SetAdminState { up },
+            NetIfOp::SetMtu { mtu } => B::SetMtu { mtu },
+            NetIfOp::AddIp {
+                ip,
+                prefix_len,
+                wait_dad_ms,
+                valid_lft_secs,
+                preferred_lft_secs,
+            } => B::AddIp {
+                ip,
+                prefix_len,
+                wait_dad_ms,
+                valid_lft_secs,
+                preferred_lft_secs,
+            },
+            NetIfOp::DelIp { ip, prefix_len } => B::DelIp { ip, prefix_len },
+            NetIfOp::SetIpv4Dhcp { enable } => B::SetIpv4Dhcp { enable },
+            NetIfOp::SetIpv4Static { ip, prefix_len, gateway } => B::SetIpv4Static { ip, prefix_len, gateway },
+            NetIfOp::SetIpv6Mode { mode } => B::SetIpv6Mode { mode: mode.into() },
+            NetIfOp::SetIpv6Static { ip, prefix_len, gateway } => B::SetIpv6Static { ip, prefix_len, gateway },
+            NetIfOp::CreateVlan { parent, vlan_id, name } => B::CreateVlan { parent, vlan_id, name },
+            NetIfOp::DeleteVlan => B::DeleteVlan,
+            NetIfOp::CreateBridge { name } => B::CreateBridge { name },
+            NetIfOp::DeleteBridge => B::DeleteBridge,
+            NetIfOp::AddBridgeMember { bridge, member } => B::AddBridgeMember { bridge, member },
+            NetIfOp::RemoveBridgeMember => B::RemoveBridgeMember,
+            NetIfOp::CreateDummy { name } => B::CreateDummy { name },
+            NetIfOp::DeleteInterface => B::DeleteInterface,
+            NetIfOp::CreateBond { name, mode, members } => B::CreateBond {
+                name,
+                mode: mode.into(),
+                members,
+            },
+            NetIfOp::DeleteBond => B::DeleteBond,
+            NetIfOp::AddBondMember { bond, member } => B::AddBondMember { bond, member },
+            NetIfOp::RemoveBondMember => B::RemoveBondMember,
+            NetIfOp::CreateWireguard {
+                name,
+                private_key,
+                listen_port,
+                peers,
+            } => B::CreateWireguard {
+                name,
+                private_key,
+                listen_port,
+                peers: peers.into_iter().map(Into::into).collect(),
+            },
+            NetIfOp::SetWireguardPeer { peer } => B::SetWireguardPeer { peer: peer.into() },
+            NetIfOp::RemoveWireguardPeer { public_key } => B::RemoveWireguardPeer { public_key },
+            NetIfOp::CreateIpTunnel {
+                name,
+                kind,
+                remote,
+                local,
+                ttl,
+            } => B::CreateIpTunnel {
+                name,
+                kind: kind.into(),
+                remote,
+                local,
+                ttl,
+            },
+            NetIfOp::DeleteTunnel => B::DeleteTunnel,
+            NetIfOp::AddRoute { route } => B::AddRoute { route: route.into() },
+            NetIfOp::DelRoute { route } => B::DelRoute { route: route.into() },
+            NetIfOp::AddRoutingRule { rule } => B::AddRoutingRule { rule: rule.into() },
+            NetIfOp::DelRoutingRule { rule } => B::DelRoutingRule { rule: rule.into() },
+            NetIfOp::SetDnsSuffix { suffix } => B::SetDnsSuffix { suffix },
+            NetIfOp::SwitchNetworkLocation { name } => B::SwitchNetworkLocation { name },
+            NetIfOp::SetServiceOrder { services } => B::SetServiceOrder { services },
+            NetIfOp::SetDnsRegistration { register, use_suffix } => B::SetDnsRegistration { register, use_suffix },
+            NetIfOp::SetName { new_name } => B::SetName { new_name },
+            NetIfOp::SetDescription { text } => B::SetDescription { text },
+            NetIfOp::SetMac { mac } => B::SetMac { mac },
+            NetIfOp::SetLinkSpeed { speed_mbps, duplex } => B::SetLinkSpeed {
+                speed_mbps,
+                duplex: duplex.map(Into::into),
+            },
+            NetIfOp::SetBandwidthLimit {
+                egress_mbps,
+                ingress_mbps,
+            } => B::SetBandwidthLimit {
+                egress_mbps,
+                ingress_mbps,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfOpSpec {
+    pub op: NetIfOp,
+    #[uniffi(default = None)]
+    pub timeout_ms: Option<u64>,
+    #[uniffi(default = None)]
+    pub scope: Option<NetIfScope>,
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfApplyRequest {
+    pub target: IfaceSelector,
+    pub ops: Vec<NetIfOpSpec>,
+    #[uniffi(default = None)]
+    pub deadline_ms: Option<u64>,
+    #[uniffi(default = false)]
+    pub skip_if_applied: bool,
+    #[uniffi(default = None)]
+    pub confirm_timeout_secs: Option<u32>,
+}
+
+impl From<NetIfApplyRequest> for base::NetIfApplyRequest {
+    fn from(v: NetIfApplyRequest) -> Self {
+        Self {
+            abi: base::ABI_VERSION,
+            target: v.target.into(),
+            ops: v
+                .ops
+                .into_iter()
+                .map(|s| base::NetIfOpSpec {
+                    op: s.op.into(),
+                    timeout_ms: s.timeout_ms,
+                    scope: s.scope.map(Into::into),
+                })
+                .collect(),
+            deadline_ms: v.deadline_ms,
+            skip_if_applied: v.skip_if_applied,
+            confirm_timeout_secs: v.confirm_timeout_secs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct ErrorInfo {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl From<base::ForgeFfiError> for ErrorInfo {
+    fn from(v: base::ForgeFfiError) -> Self {
+        Self {
+            code: v.code.into(),
+            message: v.message,
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfStepResult {
+    pub step: String,
+    pub ok: bool,
+    pub error: Option<ErrorInfo>,
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfOpResult {
+    // 对应请求 ops 中的下标
+    pub index: u64,
+    pub ok: bool,
+    pub skipped: bool,
+    pub error: Option<ErrorInfo>,
+    pub steps: Vec<NetIfStepResult>,
+}
+
+impl From<base::NetIfOpResult> for NetIfOpResult {
+    fn from(v: base::NetIfOpResult) -> Self {
+        Self {
+            index: v.i as u64,
+            ok: v.ok,
+            skipped: v.skipped,
+            error: v.error.map(Into::into),
+            steps: v
+                .steps
+                .into_iter()
+                .map(|s| NetIfStepResult {
+                    step: s.step,
+                    ok: s.ok,
+                    error: s.error.map(Into::into),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct NetIfApplyResponse {
+    pub ok: bool,
+    pub results: Vec<NetIfOpResult>,
+    pub last_known: Option<NetInterface>,
+    pub confirm_token: Option<String>,
+    pub confirm_deadline_ms: Option<u64>,
+}
+
+impl From<base::NetIfApplyResponse> for NetIfApplyResponse {
+    fn from(v: base::NetIfApplyResponse) -> Self {
+        Self {
+            ok: v.ok,
+            results: v.results.into_iter().map(Into::into).collect(),
+            last_known: v.last_known.map(Into::into),
+            confirm_token: v.confirm_token,
+            confirm_deadline_ms: v.confirm_deadline_ms,
+        }
+    }
+}
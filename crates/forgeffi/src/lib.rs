@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 
 pub use forgeffi_base as base;
+pub use forgeffi_protocol as protocol;
 
 #[cfg(feature = "net")]
 pub use forgeffi_net as net;
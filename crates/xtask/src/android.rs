@@ -0,0 +1,179 @@
+use std::fs;
+use std::io::{Cursor, Write as _};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context as _};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::{
+    android_ndk_root, is_ffi_pkg, jni, profile_dir_name, resolve_packages, workspace_root,
+    ArtifactKind, BuildArgs,
+};
+
+const KOTLIN_WRAPPER_TEMPLATE: &str = include_str!("../templates/android/ForgeFfi.kt");
+
+pub(crate) fn default_android_targets() -> Vec<String> {
+    vec![
+        "aarch64-linux-android".to_string(),
+        "x86_64-linux-android".to_string(),
+    ]
+}
+
+pub(crate) fn android_abi(target: &str) -> Option<&'static str> {
+    match target {
+        "aarch64-linux-android" => Some("arm64-v8a"),
+        "x86_64-linux-android" => Some("x86_64"),
+        "armv7-linux-androideabi" => Some("armeabi-v7a"),
+        "i686-linux-android" => Some("x86"),
+        _ => None,
+    }
+}
+
+fn clang_triple(target: &str) -> &str {
+    match target {
+        "armv7-linux-androideabi" => "armv7a-linux-androideabi",
+        other => other,
+    }
+}
+
+fn ndk_host_tag() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows-x86_64"
+    } else if cfg!(target_os = "macos") {
+        "darwin-x86_64"
+    } else {
+        "linux-x86_64"
+    }
+}
+
+pub(crate) fn configure_linker(cmd: &mut Command, target: &str, api: u32) -> anyhow::Result<()> {
+    let env_key = format!(
+        "CARGO_TARGET_{}_LINKER",
+        target.to_ascii_uppercase().replace('-', "_")
+    );
+    if std::env::var_os(&env_key).is_some() {
+        return Ok(());
+    }
+
+    let ndk = android_ndk_root()
+        .ok_or_else(|| anyhow!("缺少 Android NDK（请设置 ANDROID_NDK_HOME/ANDROID_NDK_ROOT 等）"))?;
+    let suffix = if cfg!(target_os = "windows") { ".cmd" } else { "" };
+    let clang = ndk
+        .join("toolchains")
+        .join("llvm")
+        .join("prebuilt")
+        .join(ndk_host_tag())
+        .join("bin")
+        .join(format!("{}{api}-clang{suffix}", clang_triple(target)));
+    if !clang.is_file() {
+        bail!("未找到 NDK clang: {}", clang.display());
+    }
+    cmd.env(env_key, clang);
+    Ok(())
+}
+
+pub(crate) fn build_aar(args: BuildArgs) -> anyhow::Result<()> {
+    if args.android_targets.is_empty() {
+        bail!("--android-targets 不能为空");
+    }
+    for t in &args.android_targets {
+        if android_abi(t).is_none() {
+            bail!("不支持的 Android target: {t}");
+        }
+    }
+
+    let workspace_root = workspace_root()?;
+    let dist_dir = args
+        .dist_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist"));
+
+    for t in &args.android_targets {
+        crate::build(BuildArgs {
+            target: Some(t.clone()),
+            artifact: ArtifactKind::Cdylib,
+            zigbuild: false,
+            dist_dir: Some(dist_dir.clone()),
+            ..args.clone()
+        })
+        .with_context(|| format!("构建 Android target 失败: {t}"))?;
+    }
+
+    for pkg in resolve_packages(&args)? {
+        if !is_ffi_pkg(pkg) && pkg != jni::JNI_PKG {
+            continue;
+        }
+        assemble_aar(&workspace_root, &dist_dir, pkg, &args)?;
+    }
+    Ok(())
+}
+
+fn assemble_aar(workspace_root: &Path, dist_dir: &Path, pkg: &str, args: &BuildArgs) -> anyhow::Result<()> {
+    let lib_basename = pkg.replace('-', "_");
+    let so_name = format!("lib{lib_basename}.so");
+    let profile = profile_dir_name(args.profile);
+
+    let out_dir = dist_dir.join("android").join(profile).join(pkg);
+    fs::create_dir_all(&out_dir).context("创建 aar 输出目录失败")?;
+    let aar_path = out_dir.join(format!("{pkg}.aar"));
+
+    let file = fs::File::create(&aar_path)
+        .with_context(|| format!("创建 aar 失败: {}", aar_path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let opts = SimpleFileOptions::default();
+
+    let package_id = lib_basename.replace('_', ".");
+    zip.start_file("AndroidManifest.xml", opts)?;
+    zip.write_all(
+        format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\" package=\"com.{package_id}\">\n    <uses-sdk android:minSdkVersion=\"{}\" />\n</manifest>\n",
+            args.android_api
+        )
+        .as_bytes(),
+    )?;
+
+    zip.start_file("classes.jar", opts)?;
+    // JNI 包带上 com.forgeffi 的 Java 类，其余包只有 C 导出
+    let classes = if pkg == jni::JNI_PKG {
+        jni::build_jar(workspace_root)?
+    } else {
+        empty_jar()?
+    };
+    zip.write_all(&classes)?;
+
+    zip.start_file("R.txt", opts)?;
+
+    for t in &args.android_targets {
+        let abi = android_abi(t).ok_or_else(|| anyhow!("不支持的 Android target: {t}"))?;
+        let so = dist_dir
+            .join(t)
+            .join(profile)
+            .join(pkg)
+            .join("cdylib")
+            .join(&so_name);
+        let bytes = fs::read(&so).with_context(|| format!("读取动态库失败: {}", so.display()))?;
+        zip.start_file(format!("jni/{abi}/{so_name}"), opts)?;
+        zip.write_all(&bytes)?;
+    }
+    zip.finish().context("写入 aar 失败")?;
+    println!("dist: {}", aar_path.display());
+
+    if args.aar_wrapper {
+        let kt_dir = out_dir.join("kotlin").join("com").join("forgeffi");
+        fs::create_dir_all(&kt_dir).context("创建 Kotlin 包装目录失败")?;
+        let kt_path = kt_dir.join("ForgeFfi.kt");
+        fs::write(&kt_path, KOTLIN_WRAPPER_TEMPLATE.replace("{{LIB_NAME}}", &lib_basename))
+            .with_context(|| format!("写入 Kotlin 包装失败: {}", kt_path.display()))?;
+        println!("dist: {}", kt_path.display());
+    }
+    Ok(())
+}
+
+fn empty_jar() -> anyhow::Result<Vec<u8>> {
+    let mut jar = ZipWriter::new(Cursor::new(Vec::new()));
+    jar.start_file("META-INF/MANIFEST.MF", SimpleFileOptions::default())?;
+    jar.write_all(b"Manifest-Version: 1.0\r\n\r\n")?;
+    Ok(jar.finish()?.into_inner())
+}
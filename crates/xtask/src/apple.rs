@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _};
+
+use crate::{
+    host_target_triple, is_ffi_pkg, profile_dir_name, resolve_packages, run_checked,
+    staticlib_filename, workspace_root, ArtifactKind, BuildArgs,
+};
+
+pub(crate) fn default_apple_targets() -> Vec<String> {
+    vec![
+        "aarch64-apple-ios".to_string(),
+        "aarch64-apple-ios-sim".to_string(),
+        "x86_64-apple-ios".to_string(),
+        "aarch64-apple-darwin".to_string(),
+        "x86_64-apple-darwin".to_string(),
+    ]
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum ApplePlatform {
+    Ios,
+    IosSimulator,
+    Macos,
+}
+
+impl ApplePlatform {
+    fn of(target: &str) -> Option<Self> {
+        if target.ends_with("-apple-ios-sim") || target == "x86_64-apple-ios" {
+            Some(Self::IosSimulator)
+        } else if target.ends_with("-apple-ios") {
+            Some(Self::Ios)
+        } else if target.ends_with("-apple-darwin") {
+            Some(Self::Macos)
+        } else {
+            None
+        }
+    }
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::Ios => "ios",
+            Self::IosSimulator => "ios-simulator",
+            Self::Macos => "macos",
+        }
+    }
+}
+
+pub(crate) fn build_xcframework(args: BuildArgs) -> anyhow::Result<()> {
+    let host = host_target_triple()?;
+    if !host.contains("apple-darwin") {
+        bail!("xcframework 需要在 macOS 主机上构建（需要 lipo/xcodebuild）");
+    }
+
+    let mut groups: BTreeMap<ApplePlatform, Vec<String>> = BTreeMap::new();
+    for t in &args.apple_targets {
+        let Some(p) = ApplePlatform::of(t) else {
+            bail!("不支持的 Apple target: {t}");
+        };
+        groups.entry(p).or_default().push(t.clone());
+    }
+    if groups.is_empty() {
+        bail!("--apple-targets 不能为空");
+    }
+
+    let workspace_root = workspace_root()?;
+    let dist_dir = args
+        .dist_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist"));
+
+    for t in &args.apple_targets {
+        crate::build(BuildArgs {
+            target: Some(t.clone()),
+            artifact: ArtifactKind::Staticlib,
+            zigbuild: false,
+            headers: true,
+            dist_dir: Some(dist_dir.clone()),
+            ..args.clone()
+        })
+        .with_context(|| format!("构建 Apple target 失败: {t}"))?;
+    }
+
+    for pkg in resolve_packages(&args)? {
+        if !is_ffi_pkg(pkg) {
+            continue;
+        }
+        assemble_xcframework(&dist_dir, pkg, &args, &groups)?;
+    }
+    Ok(())
+}
+
+fn assemble_xcframework(
+    dist_dir: &Path,
+    pkg: &str,
+    args: &BuildArgs,
+    groups: &BTreeMap<ApplePlatform, Vec<String>>,
+) -> anyhow::Result<()> {
+    let profile = profile_dir_name(args.profile);
+    let out_dir = dist_dir.join("apple").join(profile).join(pkg);
+    let work_dir = out_dir.join("work");
+    fs::create_dir_all(&work_dir).context("创建 xcframework 工作目录失败")?;
+
+    let headers_dir = work_dir.join("Headers");
+    write_headers_with_modulemap(dist_dir, pkg, profile, groups, &headers_dir)?;
+
+    let mut libraries = Vec::new();
+    for (platform, targets) in groups {
+        let libs: Vec<PathBuf> = targets
+            .iter()
+            .map(|t| {
+                dist_dir
+                    .join(t)
+                    .join(profile)
+                    .join(pkg)
+                    .join("staticlib")
+                    .join(staticlib_filename(pkg, t))
+            })
+            .collect();
+
+        let lib = if libs.len() == 1 {
+            libs[0].clone()
+        } else {
+            let dir = work_dir.join(platform.dir_name());
+            fs::create_dir_all(&dir).context("创建 lipo 输出目录失败")?;
+            let fat = dir.join(staticlib_filename(pkg, &targets[0]));
+            let mut cmd = Command::new("lipo");
+            cmd.arg("-create");
+            for l in &libs {
+                cmd.arg(l);
+            }
+            cmd.arg("-output").arg(&fat);
+            run_checked("lipo", &mut cmd)?;
+            fat
+        };
+        libraries.push(lib);
+    }
+
+    let framework_name = module_name(pkg);
+    let xcframework = out_dir.join(format!("{framework_name}.xcframework"));
+    if xcframework.exists() {
+        fs::remove_dir_all(&xcframework)
+            .with_context(|| format!("删除旧 xcframework 失败: {}", xcframework.display()))?;
+    }
+
+    let mut cmd = Command::new("xcodebuild");
+    cmd.arg("-create-xcframework");
+    for lib in &libraries {
+        cmd.arg("-library").arg(lib);
+        cmd.arg("-headers").arg(&headers_dir);
+    }
+    cmd.arg("-output").arg(&xcframework);
+    run_checked("xcodebuild -create-xcframework", &mut cmd)?;
+    println!("dist: {}", xcframework.display());
+    Ok(())
+}
+
+fn write_headers_with_modulemap(
+    dist_dir: &Path,
+    pkg: &str,
+    profile: &str,
+    groups: &BTreeMap<ApplePlatform, Vec<String>>,
+    headers_dir: &Path,
+) -> anyhow::Result<()> {
+    let header_name = format!("{pkg}.h");
+    let src = groups
+        .values()
+        .flatten()
+        .map(|t| {
+            dist_dir
+                .join(t)
+                .join(profile)
+                .join(pkg)
+                .join("include")
+                .join(&header_name)
+        })
+        .find(|p| p.is_file())
+        .with_context(|| format!("未找到 cbindgen 头文件: {header_name}"))?;
+
+    if headers_dir.exists() {
+        fs::remove_dir_all(headers_dir).context("清理 Headers 目录失败")?;
+    }
+    fs::create_dir_all(headers_dir).context("创建 Headers 目录失败")?;
+    fs::copy(&src, headers_dir.join(&header_name))
+        .with_context(|| format!("复制头文件失败: {}", src.display()))?;
+
+    let modulemap = format!(
+        "module {} {{\n    header \"{header_name}\"\n    export *\n}}\n",
+        module_name(pkg)
+    );
+    fs::write(headers_dir.join("module.modulemap"), modulemap).context("写入 module.modulemap 失败")?;
+    Ok(())
+}
+
+fn module_name(pkg: &str) -> String {
+    pkg.split('-')
+        .map(|part| {
+            let mut c = part.chars();
+            match c.next() {
+                Some(f) => f.to_ascii_uppercase().to_string() + c.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,206 @@
+// forgeffi.build.toml：把一组构建（target × 产物类型）写成文件，CI 与本地用同一份配置复现
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _};
+use serde::{Deserialize, Serialize};
+
+use crate::parallel::{run_jobs, BuildJob};
+use crate::{
+    android, apple, common_targets, host_target_triple, skip_target_reason, unique_targets,
+    ArtifactKind, BuildArgs, BuildMode, BuildProfile, Module,
+};
+
+pub(crate) const DEFAULT_CONFIG_FILE: &str = "forgeffi.build.toml";
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BuildConfig {
+    // 相对路径以配置文件所在目录为基准
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) dist_dir: Option<PathBuf>,
+
+    #[serde(default, rename = "build")]
+    pub(crate) builds: Vec<BuildEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BuildEntry {
+    // 为空表示本机 target；"all" 展开为本机加常用 target
+    #[serde(default)]
+    pub(crate) targets: Vec<String>,
+
+    #[serde(default = "default_profile")]
+    pub(crate) profile: BuildProfile,
+
+    #[serde(default = "default_mode")]
+    pub(crate) mode: BuildMode,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) modules: Vec<Module>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) features: Vec<String>,
+
+    #[serde(default = "default_artifacts")]
+    pub(crate) artifacts: Vec<ArtifactKind>,
+
+    #[serde(default = "default_true")]
+    pub(crate) zigbuild: bool,
+
+    #[serde(default = "default_zig_version")]
+    pub(crate) zig_version: String,
+
+    #[serde(default = "default_true")]
+    pub(crate) headers: bool,
+
+    // 覆盖文件级 dist_dir
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) dist_dir: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) android_targets: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) android_api: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) apple_targets: Option<Vec<String>>,
+}
+
+fn default_profile() -> BuildProfile {
+    BuildProfile::Release
+}
+
+fn default_mode() -> BuildMode {
+    BuildMode::ModuleFfi
+}
+
+fn default_artifacts() -> Vec<ArtifactKind> {
+    vec![ArtifactKind::Cdylib]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_zig_version() -> String {
+    "0.12.0".to_string()
+}
+
+pub(crate) fn load(path: &Path) -> anyhow::Result<BuildConfig> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("读取构建配置失败: {}", path.display()))?;
+    let cfg: BuildConfig =
+        toml::from_str(&text).with_context(|| format!("解析构建配置失败: {}", path.display()))?;
+    if cfg.builds.is_empty() {
+        bail!("构建配置中没有 [[build]] 条目: {}", path.display());
+    }
+    Ok(cfg)
+}
+
+pub(crate) fn save(path: &Path, cfg: &BuildConfig) -> anyhow::Result<()> {
+    let text = toml::to_string_pretty(cfg).context("序列化构建配置失败")?;
+    fs::write(path, text).with_context(|| format!("写入构建配置失败: {}", path.display()))?;
+    println!("config: {}", path.display());
+    Ok(())
+}
+
+// 按配置逐条构建；单个 target 失败不影响其余条目，最后统一报告
+pub(crate) fn run(path: &Path, jobs: usize) -> anyhow::Result<()> {
+    let cfg = load(path)?;
+    let base_dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let host = host_target_triple()?;
+
+    let mut build_jobs = Vec::new();
+    for (idx, entry) in cfg.builds.iter().enumerate() {
+        let dist_dir = entry
+            .dist_dir
+            .as_ref()
+            .or(cfg.dist_dir.as_ref())
+            .map(|d| base_dir.join(d));
+        for &artifact in &entry.artifacts {
+            let base_args = to_build_args(entry, artifact, dist_dir.clone());
+            if artifact.is_multi_target_bundle() {
+                build_jobs.push(BuildJob {
+                    label: format!("build[{idx}] {}", artifact.as_str()),
+                    args: BuildArgs {
+                        zigbuild: false,
+                        aar_wrapper: artifact == ArtifactKind::Aar,
+                        ..base_args
+                    },
+                });
+                continue;
+            }
+
+            let (targets, all_selected) = expand_targets(&entry.targets, &host);
+            for target in targets {
+                if let Some(reason) = skip_target_reason(&host, &target, all_selected) {
+                    println!("提示: 跳过 target={target}（{reason}）");
+                    continue;
+                }
+                build_jobs.push(BuildJob {
+                    label: format!("build[{idx}] {target} {}", artifact.as_str()),
+                    args: BuildArgs {
+                        target: Some(target),
+                        ..base_args.clone()
+                    },
+                });
+            }
+        }
+    }
+
+    let failures = run_jobs(build_jobs, jobs);
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        let mut msg = String::from("部分构建失败:\n");
+        for (label, e) in failures {
+            msg.push_str(&format!("- {label}: {e:#}\n"));
+        }
+        bail!(msg)
+    }
+}
+
+fn expand_targets(targets: &[String], host: &str) -> (Vec<String>, bool) {
+    if targets.is_empty() {
+        return (vec![host.to_string()], false);
+    }
+    if targets.iter().any(|t| t == "all") {
+        let mut all = vec![host.to_string()];
+        all.extend(common_targets());
+        return (unique_targets(all), true);
+    }
+    (targets.to_vec(), false)
+}
+
+fn to_build_args(entry: &BuildEntry, artifact: ArtifactKind, dist_dir: Option<PathBuf>) -> BuildArgs {
+    BuildArgs {
+        target: None,
+        profile: entry.profile,
+        mode: entry.mode,
+        modules: entry.modules.clone(),
+        features: entry.features.clone(),
+        artifact,
+        zig_version: entry.zig_version.clone(),
+        zigbuild: entry.zigbuild,
+        headers: entry.headers,
+        dist_dir,
+        android_targets: entry
+            .android_targets
+            .clone()
+            .unwrap_or_else(android::default_android_targets),
+        android_api: entry.android_api.unwrap_or(21),
+        aar_wrapper: false,
+        apple_targets: entry
+            .apple_targets
+            .clone()
+            .unwrap_or_else(apple::default_apple_targets),
+        config: None,
+        jobs: 1,
+    }
+}
@@ -0,0 +1,146 @@
+// 增量 dist：记录每个 FFI 包上次写入 dist 时的输入指纹，
+// 指纹不变且产物仍在时跳过复制、头文件与示例步骤
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    cargo_target_dir, find_artifact_path, find_windows_import_libs, profile_dir_name, sha256_file,
+    ArtifactKind, BuildArgs,
+};
+
+pub(crate) const STATE_FILE_NAME: &str = ".dist-state.json";
+const STATE_SCHEMA: u32 = 1;
+
+#[derive(Serialize, Deserialize, Default)]
+struct StateFile {
+    schema: u32,
+    packages: BTreeMap<String, String>,
+}
+
+pub(crate) struct DistState {
+    path: PathBuf,
+    root: PathBuf,
+    state: StateFile,
+}
+
+impl DistState {
+    pub(crate) fn load(dist_dir: &Path, target: &str, args: &BuildArgs) -> Self {
+        let root = dist_dir.join(target).join(profile_dir_name(args.profile));
+        let path = root.join(STATE_FILE_NAME);
+        // 读不到或格式不符都当作没有状态，完整执行一次即可恢复
+        let state = fs::read(&path)
+            .ok()
+            .and_then(|b| serde_json::from_slice::<StateFile>(&b).ok())
+            .filter(|s| s.schema == STATE_SCHEMA)
+            .unwrap_or_default();
+        Self { path, root, state }
+    }
+
+    pub(crate) fn is_up_to_date(&self, pkg: &str, fingerprint: &str) -> bool {
+        self.state.packages.get(pkg).is_some_and(|f| f == fingerprint)
+            && self.root.join(pkg).is_dir()
+            && self.root.join(crate::manifest::MANIFEST_FILE_NAME).is_file()
+    }
+
+    pub(crate) fn record(&mut self, pkg: &str, fingerprint: String) {
+        self.state.packages.insert(pkg.to_string(), fingerprint);
+    }
+
+    pub(crate) fn save(&mut self) -> anyhow::Result<()> {
+        self.state.schema = STATE_SCHEMA;
+        fs::create_dir_all(&self.root).context("创建 dist 目录失败")?;
+        let text = serde_json::to_string_pretty(&self.state).context("序列化 dist 状态失败")?;
+        fs::write(&self.path, text)
+            .with_context(|| format!("写入 dist 状态失败: {}", self.path.display()))
+    }
+}
+
+// 指纹覆盖写入 dist 的全部输入：cargo 产物本身、头文件来源（FFI crate 源码与 cbindgen 配置）、
+// 示例源码，以及影响这些步骤的构建参数
+pub(crate) fn fingerprint(
+    workspace_root: &Path,
+    pkg: &str,
+    target: &str,
+    args: &BuildArgs,
+) -> anyhow::Result<Option<String>> {
+    let out_dir = cargo_target_dir(workspace_root)
+        .join(target)
+        .join(profile_dir_name(args.profile));
+    let lib_name = pkg.replace('-', "_");
+    let artifact = find_artifact_path(&out_dir, &lib_name, target, args.artifact).or_else(|_| {
+        find_artifact_path(&out_dir, &lib_name, target, ArtifactKind::Staticlib)
+    });
+    let Ok(artifact) = artifact else {
+        return Ok(None);
+    };
+
+    let mut h = Sha256::new();
+    h.update(format!(
+        "schema={STATE_SCHEMA}\npkg={pkg}\ntarget={target}\nartifact={}\nheaders={}\nfeatures={}\nzig={}\nzigbuild={}\n",
+        args.artifact.as_str(),
+        args.headers,
+        args.features.join(","),
+        args.zig_version,
+        args.zigbuild
+    ));
+    h.update(sha256_file(&artifact)?);
+    if target.contains("windows") {
+        for lib in find_windows_import_libs(&out_dir, &lib_name)? {
+            h.update(sha256_file(&lib)?);
+        }
+    }
+
+    let crates = workspace_root.join("crates");
+    if args.headers {
+        for dir in fs::read_dir(&crates).context("读取 crates 目录失败")? {
+            let dir = dir.context("读取 crates 目录项失败")?.path();
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if name.starts_with("forgeffi-") && name.contains("ffi") {
+                hash_tree(&dir.join("src"), &mut h)?;
+                hash_file_if_exists(&dir.join("cbindgen.toml"), &mut h)?;
+            }
+        }
+        hash_file_if_exists(&crates.join("forgeffi-base").join("src").join("lib.rs"), &mut h)?;
+    }
+    hash_tree(&workspace_root.join("examples"), &mut h)?;
+
+    Ok(Some(format!("{:x}", h.finalize())))
+}
+
+fn hash_tree(dir: &Path, h: &mut Sha256) -> anyhow::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("读取目录失败: {}", dir.display()))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("读取目录项失败: {}", dir.display()))?;
+    entries.sort_by_key(|e| e.file_name());
+    for ent in entries {
+        let p = ent.path();
+        let ty = ent.file_type().context("读取文件类型失败")?;
+        if ty.is_dir() {
+            hash_tree(&p, h)?;
+        } else if ty.is_file() {
+            h.update(p.to_string_lossy().as_bytes());
+            h.update(sha256_file(&p)?);
+        }
+    }
+    Ok(())
+}
+
+fn hash_file_if_exists(p: &Path, h: &mut Sha256) -> anyhow::Result<()> {
+    if p.is_file() {
+        h.update(p.to_string_lossy().as_bytes());
+        h.update(sha256_file(p)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use anyhow::{anyhow, bail, Context as _};
+
+use crate::testrun::prepend_env_path;
+use crate::{cdylib_filename, copy_dir_all, host_target_triple, profile_dir_name, wasm, BuildProfile};
+
+// 无人值守校验时各 C 示例的参数；不在表里的（交互式 netif_list）只编译不运行
+const C_SMOKE_ARGS: [(&str, &[&str]); 4] = [
+    ("netif_agent", &["0"]),
+    ("netif_apply", &[]),
+    ("netif_watch", &["1"]),
+    ("fs_demo", &[]),
+];
+const FS_DEMO_FILE: &str = "forgeffi_fs_demo.txt";
+
+pub(crate) fn stage_host_examples(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    pkg: &str,
+    target: &str,
+    profile: BuildProfile,
+    c_examples: &[String],
+) -> anyhow::Result<()> {
+    if target.contains("-linux-android") || target.contains("-apple-ios") || wasm::is_wasm_target(target) {
+        return Ok(());
+    }
+
+    let bin_dir = dist_dir
+        .join(target)
+        .join(profile_dir_name(profile))
+        .join("examples");
+    let native = host_target_triple()? == target;
+    validate_c_examples(&bin_dir, target, native, c_examples)?;
+
+    if pkg != "forgeffi-net-ffi" && pkg != "forgeffi-ffi" {
+        return Ok(());
+    }
+    let src_dir = workspace_root.join("examples");
+
+    for sub in ["python", "csharp"] {
+        let src = src_dir.join(sub);
+        if src.is_dir() {
+            copy_dir_all(&src, &bin_dir.join(sub))?;
+        }
+    }
+
+    let lib = Some(bin_dir.join(cdylib_filename(pkg, target))).filter(|p| p.is_file());
+    let runnable_lib = lib.as_deref().filter(|_| native);
+
+    validate_python(&bin_dir, runnable_lib)?;
+    build_csharp(&bin_dir, runnable_lib)?;
+
+    Ok(())
+}
+
+fn validate_c_examples(
+    bin_dir: &Path,
+    target: &str,
+    native: bool,
+    c_examples: &[String],
+) -> anyhow::Result<()> {
+    if !native {
+        return Ok(());
+    }
+    for (name, args) in C_SMOKE_ARGS {
+        if !c_examples.iter().any(|n| n == name) {
+            continue;
+        }
+        let exe = bin_dir.join(if target.contains("windows") {
+            format!("{name}.exe")
+        } else {
+            name.to_string()
+        });
+        if !exe.is_file() {
+            continue;
+        }
+
+        let mut cmd = Command::new(&exe);
+        cmd.args(args);
+        cmd.current_dir(bin_dir);
+        with_lib_dir(&mut cmd, bin_dir);
+        let out = run_capture(name, &mut cmd)?;
+        if name == "fs_demo" {
+            let _ = std::fs::remove_file(bin_dir.join(FS_DEMO_FILE));
+        }
+        if name == "netif_agent" {
+            check_json_output(name, out)?;
+        } else {
+            check_json_lines(name, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_python(bin_dir: &Path, lib: Option<&Path>) -> anyhow::Result<()> {
+    let script = bin_dir.join("python").join("netif_list.py");
+    if !script.is_file() {
+        return Ok(());
+    }
+    let Some(python) = find_tool(&["python3", "python"]) else {
+        println!("提示: 未找到 python，跳过 Python 示例校验");
+        return Ok(());
+    };
+
+    let mut cmd = Command::new(&python);
+    cmd.arg("-c")
+        .arg("import ast,sys; ast.parse(open(sys.argv[1], encoding='utf-8').read())")
+        .arg(&script);
+    let out = run_capture("python (语法检查)", &mut cmd)?;
+    if !out.status.success() {
+        bail!(
+            "Python 示例语法检查失败: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    if let Some(lib) = lib {
+        let mut cmd = Command::new(&python);
+        cmd.arg(&script).arg("--lib").arg(lib).arg("--json");
+        check_json_output("python netif_list.py", run_capture("python", &mut cmd)?)?;
+    }
+    println!("dist: {}", script.display());
+    Ok(())
+}
+
+fn build_csharp(bin_dir: &Path, lib: Option<&Path>) -> anyhow::Result<()> {
+    let proj_dir = bin_dir.join("csharp");
+    let csproj = proj_dir.join("ForgeFfiExample.csproj");
+    if !csproj.is_file() {
+        return Ok(());
+    }
+    let Some(dotnet) = find_tool(&["dotnet"]) else {
+        println!("提示: 未找到 dotnet，C# 示例仅复制源码: {}", proj_dir.display());
+        return Ok(());
+    };
+
+    let out_dir = proj_dir.join("bin");
+    let mut cmd = Command::new(&dotnet);
+    cmd.arg("build")
+        .arg(&csproj)
+        .arg("-c")
+        .arg("Release")
+        .arg("-o")
+        .arg(&out_dir);
+    let out = run_capture("dotnet build", &mut cmd)?;
+    if !out.status.success() {
+        bail!(
+            "dotnet build 失败:\n{}",
+            String::from_utf8_lossy(&out.stdout)
+        );
+    }
+
+    if let Some(lib) = lib {
+        let mut cmd = Command::new(&dotnet);
+        cmd.arg(out_dir.join("ForgeFfiExample.dll")).arg("--json");
+        cmd.env("FORGEFFI_NETIF_LIB", lib);
+        check_json_output("ForgeFfiExample", run_capture("dotnet", &mut cmd)?)?;
+    }
+    println!("dist: {}", out_dir.display());
+    Ok(())
+}
+
+fn find_tool(candidates: &[&str]) -> Option<PathBuf> {
+    candidates.iter().map(PathBuf::from).find(|bin| {
+        Command::new(bin)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    })
+}
+
+fn with_lib_dir(cmd: &mut Command, dir: &Path) {
+    cmd.env("LD_LIBRARY_PATH", prepend_env_path("LD_LIBRARY_PATH", dir));
+    cmd.env("DYLD_LIBRARY_PATH", prepend_env_path("DYLD_LIBRARY_PATH", dir));
+}
+
+fn run_capture(name: &str, cmd: &mut Command) -> anyhow::Result<Output> {
+    cmd.stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("运行失败: {name}"))
+}
+
+// stdout 每个非空行都必须是 JSON（监听类示例可能没有输出）
+fn check_json_lines(name: &str, out: Output) -> anyhow::Result<()> {
+    if !out.status.success() {
+        bail!(
+            "示例 {name} 退出码异常: {}\n{}",
+            out.status,
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+    for line in String::from_utf8_lossy(&out.stdout).lines().filter(|l| !l.trim().is_empty()) {
+        serde_json::from_str::<serde_json::Value>(line)
+            .with_context(|| format!("示例 {name} 输出不是 JSON: {line}"))?;
+    }
+    println!("校验通过: {name}");
+    Ok(())
+}
+
+fn check_json_output(name: &str, out: Output) -> anyhow::Result<()> {
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    if !out.status.success() {
+        bail!(
+            "示例 {name} 退出码异常: {}\n{}",
+            out.status,
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+    let line = stdout
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .ok_or_else(|| anyhow!("示例 {name} 没有输出"))?;
+    let v: serde_json::Value =
+        serde_json::from_str(line).with_context(|| format!("示例 {name} 输出不是 JSON"))?;
+    if !v.get("items").is_some_and(serde_json::Value::is_array) {
+        bail!("示例 {name} 输出缺少 items 数组");
+    }
+    println!("校验通过: {name}");
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _};
+use clap::{ArgAction, Parser};
+
+use crate::{ensure_binary, manifest, normalize_modules, run_checked, workspace_root, Module};
+
+// 聚合 FFI 的合并头文件名；各模块段落用 FORGEFFI_HAS_<MODULE> 控制
+pub(crate) const AMALGAMATED_HEADER: &str = "forgeffi.h";
+
+const ALL_MODULES: [Module; 3] = [Module::Net, Module::Fs, Module::Sys];
+
+// 由 xtask 统一注入、各 crate 的 cbindgen.toml 不得自行设置的顶层键
+const MANAGED_KEYS: [&str; 2] = ["include_guard", "after_includes"];
+
+#[derive(Parser, Clone)]
+pub(crate) struct HeadersArgs {
+    #[arg(long, value_delimiter = ',', num_args = 0..)]
+    modules: Vec<Module>,
+
+    #[arg(long, default_value_t = false, action = ArgAction::Set)]
+    aggregate: bool,
+
+    #[arg(long, value_delimiter = ',', num_args = 0.., default_values_t = ["full".to_string()])]
+    features: Vec<String>,
+
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: HeadersArgs) -> anyhow::Result<()> {
+    let workspace_root = workspace_root()?;
+    let out_dir = args
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist").join("include"));
+    fs::create_dir_all(&out_dir).context("创建头文件输出目录失败")?;
+
+    // 未指定任何包时生成全部 FFI 头文件
+    let mut pkgs: Vec<&str> = Vec::new();
+    if !args.modules.is_empty() || !args.aggregate {
+        let modules = if args.modules.is_empty() {
+            ALL_MODULES.to_vec()
+        } else {
+            normalize_modules(&args.modules)
+        };
+        pkgs.extend(modules.into_iter().map(Module::ffi_pkg));
+    }
+    if args.aggregate || args.modules.is_empty() {
+        pkgs.push("forgeffi-ffi");
+    }
+
+    for pkg in &pkgs {
+        let header_path = out_dir.join(format!("{pkg}.h"));
+        generate_c_header(&workspace_root, pkg, &header_path)?;
+        println!("header: {}", header_path.display());
+    }
+    if pkgs.contains(&"forgeffi-ffi") {
+        let header_path = out_dir.join(AMALGAMATED_HEADER);
+        let modules = manifest::aggregate_modules(&args.features);
+        generate_amalgamated_header(&workspace_root, &modules, &header_path)?;
+        println!("header: {}", header_path.display());
+    }
+    Ok(())
+}
+
+pub(crate) fn generate_c_header(
+    workspace_root: &Path,
+    pkg: &str,
+    header_path: &Path,
+) -> anyhow::Result<()> {
+    ensure_binary("cbindgen", "cbindgen")?;
+
+    let crate_dir = workspace_root.join("crates").join(pkg);
+    let config = effective_config(workspace_root, pkg, &crate_dir)?;
+    run_cbindgen(workspace_root, pkg, &config, header_path)
+}
+
+// 合并头：公共部分（ffi-core 与聚合入口自身）加上各模块自己的条目，
+// 模块之间互不重复，所以不需要在文本层面去重
+pub(crate) fn generate_amalgamated_header(
+    workspace_root: &Path,
+    modules: &[String],
+    header_path: &Path,
+) -> anyhow::Result<()> {
+    ensure_binary("cbindgen", "cbindgen")?;
+
+    let abi = manifest::read_abi_version(workspace_root)?;
+    let mut out = String::new();
+    out.push_str("/* 由 cargo xtask 合并生成，请勿手工修改 */\n\n");
+    out.push_str("#ifndef FORGEFFI_H\n#define FORGEFFI_H\n\n");
+    out.push_str("#include <stdarg.h>\n#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n#include <stdlib.h>\n\n");
+    let _ = writeln!(out, "#define FORGEFFI_ABI_VERSION {abi}");
+    for m in ALL_MODULES {
+        if modules.iter().any(|x| x == m.name()) {
+            let _ = writeln!(out, "#define {} 1", has_macro(m));
+        }
+    }
+    out.push_str("\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+    let core = cbindgen_fragment(workspace_root, "forgeffi-ffi", &["forgeffi-ffi-core"])?;
+    out.push_str(core.trim());
+    out.push('\n');
+    for m in ALL_MODULES {
+        let body = cbindgen_fragment(workspace_root, m.ffi_pkg(), &[])?;
+        let body = body.trim();
+        if body.is_empty() {
+            continue;
+        }
+        let guard = has_macro(m);
+        let _ = write!(out, "\n#ifdef {guard}\n\n{body}\n\n#endif /* {guard} */\n");
+    }
+
+    out.push_str("\n#ifdef __cplusplus\n} /* extern \"C\" */\n#endif\n\n#endif /* FORGEFFI_H */\n");
+    fs::write(header_path, out)
+        .with_context(|| format!("写入头文件失败: {}", header_path.display()))
+}
+
+// 片段只含条目本身；guard、include 与 extern "C" 由合并头统一生成
+fn cbindgen_fragment(workspace_root: &Path, pkg: &str, include: &[&str]) -> anyhow::Result<String> {
+    // 公共片段负责给出 tool_event_cb，模块片段只引用它
+    let (parse, export_include) = if include.is_empty() {
+        ("parse_deps = false\n".to_string(), "")
+    } else {
+        let list = include
+            .iter()
+            .map(|c| format!("\"{c}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        (
+            format!("parse_deps = true\ninclude = [\"forgeffi-base\", {list}]\n"),
+            "include = [\"ToolEventCb\"]\n",
+        )
+    };
+    let config = format!(
+        "language = \"C\"\nno_includes = true\ncpp_compat = false\ndocumentation = true\nusize_is_size_t = true\n\n\
+         [export]\nprefix = \"forgeffi_\"\nrenaming_overrides_prefixing = true\n{export_include}\n\
+         [export.rename]\n\"ToolEventCb\" = \"tool_event_cb\"\n\n[enum]\nprefix_with_name = true\n\n[parse]\n{parse}"
+    );
+
+    let dir = tempfile::tempdir().context("创建临时目录失败")?;
+    let path = dir.path().join(format!("{pkg}.h"));
+    run_cbindgen(workspace_root, pkg, &config, &path)?;
+    fs::read_to_string(&path).with_context(|| format!("读取 cbindgen 输出失败: {}", path.display()))
+}
+
+fn run_cbindgen(
+    workspace_root: &Path,
+    pkg: &str,
+    config: &str,
+    header_path: &Path,
+) -> anyhow::Result<()> {
+    let crate_dir = workspace_root.join("crates").join(pkg);
+    if !crate_dir.is_dir() {
+        bail!("未找到 crate 目录: {}", crate_dir.display());
+    }
+
+    let mut config_file = tempfile::Builder::new()
+        .prefix("cbindgen-")
+        .suffix(".toml")
+        .tempfile()
+        .context("创建临时 cbindgen 配置失败")?;
+    config_file
+        .write_all(config.as_bytes())
+        .context("写入临时 cbindgen 配置失败")?;
+
+    let mut cmd = Command::new("cbindgen");
+    cmd.current_dir(workspace_root);
+    cmd.arg("--config").arg(config_file.path());
+    cmd.arg("--crate").arg(pkg);
+    cmd.arg("--output").arg(header_path);
+    cmd.arg(crate_dir);
+
+    run_checked("cbindgen", &mut cmd)
+}
+
+// crate 自带的 cbindgen.toml 加上统一的 include guard 与版本宏；
+// 顶层键只能写在第一个表之前，所以放在最前面
+fn effective_config(workspace_root: &Path, pkg: &str, crate_dir: &Path) -> anyhow::Result<String> {
+    let path = crate_dir.join("cbindgen.toml");
+    let base = fs::read_to_string(&path)
+        .with_context(|| format!("读取 cbindgen 配置失败: {}", path.display()))?;
+    for line in base.lines() {
+        let key = line.split('=').next().unwrap_or_default().trim();
+        if MANAGED_KEYS.contains(&key) {
+            bail!("{} 不应设置 {key}（由 xtask 统一生成）", path.display());
+        }
+    }
+
+    let abi = manifest::read_abi_version(workspace_root)?;
+    Ok(format!(
+        "include_guard = \"{}\"\nafter_includes = \"#define FORGEFFI_ABI_VERSION {abi}\"\n\n{base}",
+        include_guard(pkg)
+    ))
+}
+
+fn has_macro(m: Module) -> String {
+    format!("FORGEFFI_HAS_{}", m.name().to_ascii_uppercase())
+}
+
+fn include_guard(pkg: &str) -> String {
+    format!("{}_H", pkg.to_ascii_uppercase().replace('-', "_"))
+}
@@ -0,0 +1,113 @@
+// JNI 绑定（forgeffi-jni）：编译 crates/forgeffi-jni/java 下的 com.forgeffi 类并打成 jar，
+// 与动态库一起放到 dist/<target>/<profile>/forgeffi-jni/；--artifact aar 时 jar 作为 AAR 的 classes.jar
+use std::fs;
+use std::io::{Cursor, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context as _};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::{cargo_target_dir, find_artifact_path, profile_dir_name, run_checked, ArtifactKind, BuildProfile};
+
+pub(crate) const JNI_PKG: &str = "forgeffi-jni";
+const JAR_FILE_NAME: &str = "forgeffi-jni.jar";
+
+pub(crate) fn stage(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    target: &str,
+    profile: BuildProfile,
+) -> anyhow::Result<()> {
+    let out_dir = cargo_target_dir(workspace_root)
+        .join(target)
+        .join(profile_dir_name(profile));
+    let src = find_artifact_path(&out_dir, &JNI_PKG.replace('-', "_"), target, ArtifactKind::Cdylib)
+        .with_context(|| format!("未找到产物: pkg={JNI_PKG}"))?;
+
+    // 动态库与 FFI 包一样放在 cdylib/ 下，AAR 打包沿用同一路径
+    let dst_dir = dist_dir.join(target).join(profile_dir_name(profile)).join(JNI_PKG);
+    let lib_dir = dst_dir.join(ArtifactKind::Cdylib.as_str());
+    fs::create_dir_all(&lib_dir).context("创建 dist 子目录失败")?;
+    let dst = lib_dir.join(src.file_name().ok_or_else(|| anyhow!("产物路径缺少文件名"))?);
+    fs::copy(&src, &dst)
+        .with_context(|| format!("复制产物失败: {} -> {}", src.display(), dst.display()))?;
+    println!("dist: {}", dst.display());
+
+    let jar = dst_dir.join(JAR_FILE_NAME);
+    fs::write(&jar, build_jar(workspace_root)?)
+        .with_context(|| format!("写入文件失败: {}", jar.display()))?;
+    println!("dist: {}", jar.display());
+    Ok(())
+}
+
+// 目标 Java 8 字节码，Android D8 与桌面 JDK 都能直接使用
+pub(crate) fn build_jar(workspace_root: &Path) -> anyhow::Result<Vec<u8>> {
+    let found = Command::new("javac")
+        .arg("-version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !found {
+        bail!("未找到 javac，请安装 JDK（8 及以上）");
+    }
+
+    let src_root = workspace_root.join("crates").join(JNI_PKG).join("java");
+    let mut sources = Vec::new();
+    collect_files(&src_root, "java", &mut sources)?;
+    sources.sort();
+
+    let classes_dir = cargo_target_dir(workspace_root).join("forgeffi-jni-classes");
+    if classes_dir.is_dir() {
+        fs::remove_dir_all(&classes_dir).context("清理 class 输出目录失败")?;
+    }
+    fs::create_dir_all(&classes_dir).context("创建 class 输出目录失败")?;
+
+    let mut cmd = Command::new("javac");
+    cmd.arg("--release")
+        .arg("8")
+        .arg("-encoding")
+        .arg("UTF-8")
+        .arg("-d")
+        .arg(&classes_dir)
+        .args(&sources);
+    run_checked("javac", &mut cmd)?;
+
+    let mut classes = Vec::new();
+    collect_files(&classes_dir, "class", &mut classes)?;
+    classes.sort();
+
+    let mut jar = ZipWriter::new(Cursor::new(Vec::new()));
+    let opts = SimpleFileOptions::default();
+    jar.start_file("META-INF/MANIFEST.MF", opts)?;
+    jar.write_all(b"Manifest-Version: 1.0\r\n\r\n")?;
+    for p in classes {
+        let rel = p
+            .strip_prefix(&classes_dir)
+            .context("路径不在 class 输出目录内")?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        jar.start_file(rel, opts)?;
+        jar.write_all(&fs::read(&p).with_context(|| format!("读取文件失败: {}", p.display()))?)?;
+    }
+    Ok(jar.finish()?.into_inner())
+}
+
+fn collect_files(dir: &Path, ext: &str, out: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for ent in fs::read_dir(dir).with_context(|| format!("读取目录失败: {}", dir.display()))? {
+        let ent = ent.with_context(|| format!("读取目录项失败: {}", dir.display()))?;
+        let p = ent.path();
+        if ent.file_type().context("读取文件类型失败")?.is_dir() {
+            collect_files(&p, ext, out)?;
+        } else if p.extension().is_some_and(|e| e == ext) {
+            out.push(p);
+        }
+    }
+    Ok(())
+}
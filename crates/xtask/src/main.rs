@@ -10,9 +10,27 @@ use anyhow::{anyhow, bail, Context as _};
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use directories::BaseDirs;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest as _, Sha256};
 
+mod android;
+mod apple;
+mod buildconfig;
+mod diststate;
+mod examples;
+mod headers;
+mod jni;
+mod manifest;
+mod node;
+mod package;
+mod parallel;
+mod pkgconfig;
+mod python;
+mod testffi;
+mod testrun;
+mod uniffi;
+mod wasm;
+
 #[derive(Parser)]
 #[command(version, about = "ForgeFFI 构建工具")]
 struct Cli {
@@ -22,9 +40,21 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    Menu,
+    Menu(MenuArgs),
     Build(BuildArgs),
     Zig(ZigArgs),
+    Headers(headers::HeadersArgs),
+    Package(package::PackageArgs),
+    Test(testrun::TestArgs),
+    // ASan/LSan 下的 C 侧出参用例与 Miri 下的 mem 用例
+    TestFfi(testffi::TestFfiArgs),
+}
+
+#[derive(Parser, Clone)]
+struct MenuArgs {
+    // 多 target 时同时运行的构建数
+    #[arg(long, default_value_t = 1)]
+    jobs: usize,
 }
 
 #[derive(Parser, Clone)]
@@ -64,9 +94,30 @@ struct BuildArgs {
 
     #[arg(long)]
     dist_dir: Option<PathBuf>,
+
+    #[arg(long, value_delimiter = ',', num_args = 0.., default_values_t = android::default_android_targets())]
+    android_targets: Vec<String>,
+
+    #[arg(long, default_value_t = 21)]
+    android_api: u32,
+
+    #[arg(long, default_value_t = false, action = ArgAction::Set)]
+    aar_wrapper: bool,
+
+    #[arg(long, value_delimiter = ',', num_args = 0.., default_values_t = apple::default_apple_targets())]
+    apple_targets: Vec<String>,
+
+    // 指定后按配置文件构建，忽略其余构建参数
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    // 仅对 --config 的多 target 构建生效
+    #[arg(long, default_value_t = 1)]
+    jobs: usize,
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 enum BuildProfile {
     Debug,
     Release,
@@ -81,12 +132,21 @@ impl BuildProfile {
     }
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 enum BuildMode {
     ModuleRust,
     ModuleFfi,
     AggregateRust,
     AggregateFfi,
+    // Node.js 原生扩展（forgeffi-node，napi-rs）
+    Node,
+    // Python wheel（forgeffi-py，PyO3 + maturin）
+    PythonWheel,
+    // JNI 绑定（forgeffi-jni）：动态库 + jar，Android 可配合 --artifact aar
+    Jni,
+    // UniFFI 绑定（forgeffi-uniffi）：动态库 + 生成的 Kotlin/Swift/Python 绑定
+    Uniffi,
 }
 
 impl fmt::Display for BuildMode {
@@ -96,12 +156,17 @@ impl fmt::Display for BuildMode {
             BuildMode::ModuleFfi => "module-ffi",
             BuildMode::AggregateRust => "aggregate-rust",
             BuildMode::AggregateFfi => "aggregate-ffi",
+            BuildMode::Node => "node",
+            BuildMode::PythonWheel => "python-wheel",
+            BuildMode::Jni => "jni",
+            BuildMode::Uniffi => "uniffi",
         };
         f.write_str(s)
     }
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 enum Module {
     Net,
     Fs,
@@ -109,6 +174,14 @@ enum Module {
 }
 
 impl Module {
+    fn name(self) -> &'static str {
+        match self {
+            Module::Net => "net",
+            Module::Fs => "fs",
+            Module::Sys => "sys",
+        }
+    }
+
     fn rust_pkg(self) -> &'static str {
         match self {
             Module::Net => "forgeffi-net",
@@ -127,10 +200,13 @@ impl Module {
 
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 enum ArtifactKind {
     Cdylib,
     Staticlib,
+    Aar,
+    Xcframework,
 }
 
 impl ArtifactKind {
@@ -138,24 +214,34 @@ impl ArtifactKind {
         match self {
             ArtifactKind::Cdylib => "cdylib",
             ArtifactKind::Staticlib => "staticlib",
+            ArtifactKind::Aar => "aar",
+            ArtifactKind::Xcframework => "xcframework",
         }
     }
+
+    fn is_multi_target_bundle(self) -> bool {
+        matches!(self, ArtifactKind::Aar | ArtifactKind::Xcframework)
+    }
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match cli.command {
-        Commands::Menu => menu(),
+        Commands::Menu(args) => menu(args.jobs),
         Commands::Build(args) => build(args),
         Commands::Zig(args) => {
             let zig = ensure_zig(&args.version)?;
             println!("{}", zig.display());
             Ok(())
         }
+        Commands::Headers(args) => headers::run(args),
+        Commands::Package(args) => package::run(args),
+        Commands::Test(args) => testrun::run(args),
+        Commands::TestFfi(args) => testffi::run(args),
     }
 }
 
-fn menu() -> anyhow::Result<()> {
+fn menu(jobs: usize) -> anyhow::Result<()> {
     let theme = ColorfulTheme::default();
 
     let profiles = [BuildProfile::Debug, BuildProfile::Release];
@@ -171,8 +257,21 @@ fn menu() -> anyhow::Result<()> {
         BuildMode::ModuleFfi,
         BuildMode::AggregateRust,
         BuildMode::AggregateFfi,
+        BuildMode::Node,
+        BuildMode::PythonWheel,
+        BuildMode::Jni,
+        BuildMode::Uniffi,
+    ];
+    let mode_labels = [
+        "模块 Rust",
+        "模块 FFI",
+        "聚合 Rust",
+        "聚合 FFI",
+        "Node.js 扩展",
+        "Python wheel",
+        "JNI（Java/Kotlin）",
+        "UniFFI（Kotlin/Swift/Python）",
     ];
-    let mode_labels = ["模块 Rust", "模块 FFI", "聚合 Rust", "聚合 FFI"];
     let mode_idx = Select::with_theme(&theme)
         .with_prompt("选择构建模式")
         .items(&mode_labels)
@@ -182,15 +281,38 @@ fn menu() -> anyhow::Result<()> {
 
     let artifact = match mode {
         BuildMode::ModuleFfi | BuildMode::AggregateFfi => {
-            let artifacts = [ArtifactKind::Cdylib, ArtifactKind::Staticlib];
+            let artifacts = [
+                ArtifactKind::Cdylib,
+                ArtifactKind::Staticlib,
+                ArtifactKind::Aar,
+                ArtifactKind::Xcframework,
+            ];
             let artifact_idx = Select::with_theme(&theme)
                 .with_prompt("选择产物类型")
-                .items(&["动态库(cdylib)", "静态库(staticlib)"])
+                .items(&[
+                    "动态库(cdylib)",
+                    "静态库(staticlib)",
+                    "Android AAR(aar)",
+                    "Apple XCFramework(xcframework)",
+                ])
                 .default(0)
                 .interact()?;
             artifacts[artifact_idx]
         }
-        BuildMode::ModuleRust | BuildMode::AggregateRust => ArtifactKind::Cdylib,
+        BuildMode::Jni => {
+            let artifacts = [ArtifactKind::Cdylib, ArtifactKind::Aar];
+            let artifact_idx = Select::with_theme(&theme)
+                .with_prompt("选择产物类型")
+                .items(&["动态库 + jar", "Android AAR(aar)"])
+                .default(0)
+                .interact()?;
+            artifacts[artifact_idx]
+        }
+        BuildMode::ModuleRust
+        | BuildMode::AggregateRust
+        | BuildMode::Node
+        | BuildMode::PythonWheel
+        | BuildMode::Uniffi => ArtifactKind::Cdylib,
     };
 
     let (modules, features) = match mode {
@@ -223,6 +345,9 @@ fn menu() -> anyhow::Result<()> {
             let feature = items[selected].to_string();
             (Vec::new(), vec![feature])
         }
+        BuildMode::Node | BuildMode::PythonWheel | BuildMode::Jni | BuildMode::Uniffi => {
+            (Vec::new(), Vec::new())
+        }
     };
 
     let zigbuild = Confirm::with_theme(&theme)
@@ -284,8 +409,42 @@ fn menu() -> anyhow::Result<()> {
 
     let dist_dir = Some(PathBuf::from("dist"));
 
-    let workspace_root = workspace_root()?;
-    let mut failures = Vec::new();
+    let base_args = BuildArgs {
+        target: None,
+        profile,
+        mode,
+        modules,
+        features,
+        artifact,
+        zig_version,
+        zigbuild,
+        headers,
+        dist_dir,
+        android_targets: android::default_android_targets(),
+        android_api: 21,
+        aar_wrapper: false,
+        apple_targets: apple::default_apple_targets(),
+        config: None,
+        jobs: 1,
+    };
+
+    let save = Confirm::with_theme(&theme)
+        .with_prompt(format!("将本次选择保存到 {}", buildconfig::DEFAULT_CONFIG_FILE))
+        .default(false)
+        .interact()?;
+    if save {
+        save_menu_config(&base_args, &selected_targets, all_selected)?;
+    }
+
+    if artifact.is_multi_target_bundle() {
+        return build(BuildArgs {
+            zigbuild: false,
+            aar_wrapper: artifact == ArtifactKind::Aar,
+            ..base_args
+        });
+    }
+
+    let mut build_jobs = Vec::new();
 
     for original_target in selected_targets {
         if let Some(reason) = skip_target_reason(&host, &original_target, all_selected) {
@@ -320,23 +479,14 @@ fn menu() -> anyhow::Result<()> {
                 println!(
                     "提示: 为使用 zigbuild，target 已从 {original_target} 切换为 {mapped}"
                 );
-                run_one_build(
-                    &workspace_root,
-                    BuildArgs {
+                build_jobs.push(parallel::BuildJob {
+                    label: original_target.clone(),
+                    args: BuildArgs {
                         target: Some(mapped.to_string()),
-                        profile,
-                        mode,
-                        modules: modules.clone(),
-                        features: features.clone(),
-                        artifact,
-                        zig_version: zig_version.clone(),
                         zigbuild: true,
-                        headers,
-                        dist_dir: dist_dir.clone(),
+                        ..base_args.clone()
                     },
-                )
-                .map_err(|e| failures.push((original_target.clone(), e)))
-                .ok();
+                });
                 continue;
             }
 
@@ -350,25 +500,18 @@ fn menu() -> anyhow::Result<()> {
             zigbuild
         };
 
-        run_one_build(
-            &workspace_root,
-            BuildArgs {
+        build_jobs.push(parallel::BuildJob {
+            label: original_target.clone(),
+            args: BuildArgs {
                 target: Some(original_target.clone()),
-                profile,
-                mode,
-                modules: modules.clone(),
-                features: features.clone(),
-                artifact,
-                zig_version: zig_version.clone(),
                 zigbuild: effective_zigbuild,
-                headers,
-                dist_dir: dist_dir.clone(),
+                ..base_args.clone()
             },
-        )
-        .map_err(|e| failures.push((original_target.clone(), e)))
-        .ok();
+        });
     }
 
+    let failures = parallel::run_jobs(build_jobs, jobs);
+
     if failures.is_empty() {
         Ok(())
     } else {
@@ -380,8 +523,44 @@ fn menu() -> anyhow::Result<()> {
     }
 }
 
-fn run_one_build(_workspace_root: &Path, args: BuildArgs) -> anyhow::Result<()> {
-    build(args)
+fn save_menu_config(
+    args: &BuildArgs,
+    selected_targets: &[String],
+    all_selected: bool,
+) -> anyhow::Result<()> {
+    let path = workspace_root()?.join(buildconfig::DEFAULT_CONFIG_FILE);
+    if path.exists()
+        && !Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{} 已存在，是否覆盖", path.display()))
+            .default(false)
+            .interact()?
+    {
+        return Ok(());
+    }
+    let targets = if all_selected {
+        vec!["all".to_string()]
+    } else {
+        selected_targets.to_vec()
+    };
+    let cfg = buildconfig::BuildConfig {
+        dist_dir: args.dist_dir.clone(),
+        builds: vec![buildconfig::BuildEntry {
+            targets,
+            profile: args.profile,
+            mode: args.mode,
+            modules: args.modules.clone(),
+            features: args.features.clone(),
+            artifacts: vec![args.artifact],
+            zigbuild: args.zigbuild,
+            zig_version: args.zig_version.clone(),
+            headers: args.headers,
+            dist_dir: None,
+            android_targets: None,
+            android_api: None,
+            apple_targets: None,
+        }],
+    };
+    buildconfig::save(&path, &cfg)
 }
 
 fn skip_target_reason(host: &str, target: &str, all_selected: bool) -> Option<String> {
@@ -403,11 +582,15 @@ fn skip_target_reason(host: &str, target: &str, all_selected: bool) -> Option<St
 }
 
 fn has_android_ndk() -> bool {
+    android_ndk_root().is_some()
+}
+
+fn android_ndk_root() -> Option<PathBuf> {
     const KEYS: [&str; 4] = ["ANDROID_NDK_HOME", "ANDROID_NDK_ROOT", "NDK_HOME", "NDK_ROOT"];
-    KEYS.iter().any(|k| {
+    KEYS.iter().find_map(|k| {
         std::env::var_os(k)
             .map(PathBuf::from)
-            .is_some_and(|p| p.is_dir())
+            .filter(|p| p.is_dir())
     })
 }
 
@@ -418,6 +601,21 @@ fn unique_targets(mut targets: Vec<String>) -> Vec<String> {
 }
 
 fn build(mut args: BuildArgs) -> anyhow::Result<()> {
+    if let Some(path) = args.config.take() {
+        return buildconfig::run(&path, args.jobs);
+    }
+
+    // wheel 由 maturin 完成编译与打包，不走下面的 cargo build 流程
+    if args.mode == BuildMode::PythonWheel {
+        return python::build_wheel(args);
+    }
+
+    match args.artifact {
+        ArtifactKind::Aar => return android::build_aar(args),
+        ArtifactKind::Xcframework => return apple::build_xcframework(args),
+        ArtifactKind::Cdylib | ArtifactKind::Staticlib => {}
+    }
+
     let workspace_root = workspace_root()?;
 
     if args.target.is_none() {
@@ -440,6 +638,13 @@ fn build(mut args: BuildArgs) -> anyhow::Result<()> {
             bail!("cargo-zigbuild 不支持该 Windows MSVC target: {target}");
         }
     }
+    if matches!(args.mode, BuildMode::Node | BuildMode::Jni | BuildMode::Uniffi) && wasm::is_wasm_target(&target) {
+        bail!("{} 模式不支持 wasm target: {target}", args.mode);
+    }
+    if args.zigbuild && wasm::is_wasm_target(&target) {
+        println!("提示: wasm target 不需要 zigbuild，使用普通 cargo build：{target}");
+        args.zigbuild = false;
+    }
 
     let target = args
         .target
@@ -462,6 +667,8 @@ fn build(mut args: BuildArgs) -> anyhow::Result<()> {
     ensure_rust_target(&target)?;
 
     let pkgs = resolve_packages(&args)?;
+    let mut dist_state = diststate::DistState::load(&dist_dir, &target, &args);
+    let mut dist_changed = !pkgs.iter().all(|p| is_ffi_pkg(p));
     for pkg in pkgs {
         let (cmd_name, mut cmd) = if args.zigbuild {
             let mut c = Command::new("cargo");
@@ -485,10 +692,32 @@ fn build(mut args: BuildArgs) -> anyhow::Result<()> {
         if !args.features.is_empty() {
             cmd.arg("--features").arg(args.features.join(","));
         }
+        if !args.zigbuild && target.contains("-linux-android") {
+            android::configure_linker(&mut cmd, &target, args.android_api)?;
+        }
 
         run_checked(cmd_name, &mut cmd)?;
 
+        if pkg == node::NODE_PKG {
+            node::stage_addon(&workspace_root, &dist_dir, &target, args.profile)?;
+        }
+        if pkg == jni::JNI_PKG {
+            jni::stage(&workspace_root, &dist_dir, &target, args.profile)?;
+        }
+        if pkg == uniffi::UNIFFI_PKG {
+            uniffi::stage(&workspace_root, &dist_dir, &target, args.profile)?;
+        }
+
         if is_ffi_pkg(pkg) {
+            let fingerprint = diststate::fingerprint(&workspace_root, pkg, &target, &args)?;
+            if let Some(fp) = &fingerprint
+                && dist_state.is_up_to_date(pkg, fp)
+            {
+                println!("up to date: {pkg} ({target})");
+                continue;
+            }
+            dist_changed = true;
+
             copy_artifact_to_dist(
                 &workspace_root,
                 &dist_dir,
@@ -498,11 +727,19 @@ fn build(mut args: BuildArgs) -> anyhow::Result<()> {
                 args.artifact,
             )?;
 
-            if args.headers {
-                generate_c_header_to_dist(&workspace_root, &dist_dir, pkg, &target, args.profile)?;
+            // wasm 不提供 C ABI 头文件，导出面见 forgeffi-net-ffi/wit
+            if args.headers && !wasm::is_wasm_target(&target) {
+                generate_c_header_to_dist(
+                    &workspace_root,
+                    &dist_dir,
+                    pkg,
+                    &target,
+                    args.profile,
+                    &args.features,
+                )?;
             }
 
-            build_c_example_netif_list_if_applicable(
+            let c_examples = build_c_examples_if_applicable(
                 &workspace_root,
                 &dist_dir,
                 pkg,
@@ -510,14 +747,78 @@ fn build(mut args: BuildArgs) -> anyhow::Result<()> {
                 args.profile,
                 args.artifact,
                 &args.zig_version,
+                &args.features,
+            )?;
+
+            examples::stage_host_examples(
+                &workspace_root,
+                &dist_dir,
+                pkg,
+                &target,
+                args.profile,
+                &c_examples,
             )?;
+
+            if wasm::is_wasm_target(&target) {
+                wasm::stage_bindings(&workspace_root, &dist_dir, pkg, &target, args.profile)?;
+            }
+
+            if let Some(fp) = fingerprint {
+                dist_state.record(pkg, fp);
+            }
         }
     }
 
+    if !dist_changed {
+        println!("up to date: {target}");
+        return Ok(());
+    }
+
+    if !wasm::is_wasm_target(&target) {
+        pkgconfig::write_consumer_configs(&workspace_root, &dist_dir, &target, args.profile)?;
+    }
+    manifest::write_dist_manifest(&workspace_root, &dist_dir, &target, &args)?;
+    dist_state.save()?;
+
     Ok(())
 }
 
-fn build_c_example_netif_list_if_applicable(
+// examples/c 下的每个 .c 都是独立示例；fs_ 前缀依赖 fs 模块，其余依赖 net 模块，
+// 只有当前包导出了对应模块时才编译（静态链接时缺符号会直接链接失败）
+fn discover_c_examples(workspace_root: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let dir = workspace_root.join("examples").join("c");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for ent in fs::read_dir(&dir).with_context(|| format!("读取目录失败: {}", dir.display()))? {
+        let p = ent.with_context(|| format!("读取目录项失败: {}", dir.display()))?.path();
+        if p.extension().is_some_and(|e| e == "c")
+            && let Some(stem) = p.file_stem().and_then(|s| s.to_str())
+        {
+            out.push((stem.to_string(), p));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn c_example_module(name: &str) -> &'static str {
+    if name.starts_with("fs_") { "fs" } else { "net" }
+}
+
+fn ffi_pkg_modules(pkg: &str, features: &[String]) -> Vec<String> {
+    match pkg {
+        "forgeffi-net-ffi" => vec!["net".to_string()],
+        "forgeffi-fs-ffi" => vec!["fs".to_string()],
+        "forgeffi-ffi" => manifest::aggregate_modules(features),
+        _ => Vec::new(),
+    }
+}
+
+// 返回已编译的示例名（不含 .exe）
+#[allow(clippy::too_many_arguments)]
+fn build_c_examples_if_applicable(
     workspace_root: &Path,
     dist_dir: &Path,
     pkg: &str,
@@ -525,17 +826,19 @@ fn build_c_example_netif_list_if_applicable(
     profile: BuildProfile,
     artifact: ArtifactKind,
     zig_version: &str,
-) -> anyhow::Result<()> {
-    if pkg != "forgeffi-net-ffi" && pkg != "forgeffi-ffi" {
-        return Ok(());
+    features: &[String],
+) -> anyhow::Result<Vec<String>> {
+    if target.contains("-linux-android") || target.contains("-apple-ios") || wasm::is_wasm_target(target) {
+        return Ok(Vec::new());
     }
 
-    let src = workspace_root
-        .join("examples")
-        .join("c")
-        .join("netif_list.c");
-    if !src.is_file() {
-        return Ok(());
+    let modules = ffi_pkg_modules(pkg, features);
+    let srcs: Vec<(String, PathBuf)> = discover_c_examples(workspace_root)?
+        .into_iter()
+        .filter(|(name, _)| modules.iter().any(|m| m == c_example_module(name)))
+        .collect();
+    if srcs.is_empty() {
+        return Ok(Vec::new());
     }
 
     let zig = ensure_zig(zig_version)?;
@@ -546,33 +849,6 @@ fn build_c_example_netif_list_if_applicable(
         .join("examples");
     fs::create_dir_all(&bin_dir).context("创建 examples 目录失败")?;
 
-    let exe_name = if target.contains("windows") {
-        "netif_list.exe"
-    } else {
-        "netif_list"
-    };
-    let exe_path = bin_dir.join(exe_name);
-
-    let mut cmd = Command::new(&zig);
-    cmd.arg("cc");
-    cmd.arg("-std=c11");
-
-    if let Some(zig_target) = zig_target_from_rust_target(target) {
-        cmd.arg("-target").arg(zig_target);
-    }
-
-    match profile {
-        BuildProfile::Debug => {
-            cmd.arg("-O0");
-            cmd.arg("-g");
-        }
-        BuildProfile::Release => {
-            cmd.arg("-O2");
-        }
-    }
-    cmd.arg(&src);
-    cmd.arg("-o").arg(&exe_path);
-
     let effective_artifact = match artifact {
         ArtifactKind::Cdylib => {
             if has_cdylib(dist_dir, target, profile, pkg) {
@@ -581,57 +857,96 @@ fn build_c_example_netif_list_if_applicable(
                 ArtifactKind::Staticlib
             }
         }
-        ArtifactKind::Staticlib => ArtifactKind::Staticlib,
+        ArtifactKind::Staticlib | ArtifactKind::Aar | ArtifactKind::Xcframework => {
+            ArtifactKind::Staticlib
+        }
     };
 
-    match effective_artifact {
-        ArtifactKind::Cdylib => {
-            if !target.contains("windows") {
-                cmd.arg("-ldl");
+    let mut built = Vec::with_capacity(srcs.len());
+    for (name, src) in srcs {
+        let exe_name = if target.contains("windows") {
+            format!("{name}.exe")
+        } else {
+            name.clone()
+        };
+        let exe_path = bin_dir.join(exe_name);
+
+        let mut cmd = Command::new(&zig);
+        cmd.arg("cc");
+        cmd.arg("-std=c11");
+
+        if let Some(zig_target) = zig_target_from_rust_target(target) {
+            cmd.arg("-target").arg(zig_target);
+        }
+
+        match profile {
+            BuildProfile::Debug => {
+                cmd.arg("-O0");
+                cmd.arg("-g");
+            }
+            BuildProfile::Release => {
+                cmd.arg("-O2");
             }
         }
-        ArtifactKind::Staticlib => {
-            cmd.arg("-DFORGEFFI_STATIC=1");
-            let include_dir = dist_dir
-                .join(target)
-                .join(profile_dir_name(profile))
-                .join(pkg)
-                .join("include");
-            cmd.arg("-I").arg(&include_dir);
-
-            let staticlib_dir = dist_dir
-                .join(target)
-                .join(profile_dir_name(profile))
-                .join(pkg)
-                .join("staticlib");
-            let staticlib_file = staticlib_filename(pkg, target);
-            let staticlib_path = staticlib_dir.join(staticlib_file);
-            cmd.arg(&staticlib_path);
-
-            if !target.contains("windows") {
-                cmd.arg("-lunwind");
+        cmd.arg(&src);
+        cmd.arg("-o").arg(&exe_path);
+
+        match effective_artifact {
+            ArtifactKind::Cdylib => {
+                if !target.contains("windows") {
+                    cmd.arg("-ldl");
+                }
+            }
+            ArtifactKind::Staticlib | ArtifactKind::Aar | ArtifactKind::Xcframework => {
+                cmd.arg("-DFORGEFFI_STATIC=1");
+                let include_dir = dist_dir
+                    .join(target)
+                    .join(profile_dir_name(profile))
+                    .join(pkg)
+                    .join("include");
+                cmd.arg("-I").arg(&include_dir);
+
+                let staticlib_dir = dist_dir
+                    .join(target)
+                    .join(profile_dir_name(profile))
+                    .join(pkg)
+                    .join("staticlib");
+                let staticlib_file = staticlib_filename(pkg, target);
+                let staticlib_path = staticlib_dir.join(staticlib_file);
+                cmd.arg(&staticlib_path);
+
+                if !target.contains("windows") {
+                    cmd.arg("-lunwind");
+                }
             }
         }
-    }
 
-    run_checked("zig cc (examples/c/netif_list.c)", &mut cmd)?;
-    println!("dist: {}", exe_path.display());
+        run_checked(&format!("zig cc (examples/c/{name}.c)"), &mut cmd)?;
+        println!("dist: {}", exe_path.display());
+        built.push(name);
+    }
 
     if effective_artifact == ArtifactKind::Cdylib {
         copy_runtime_dylib_if_present(dist_dir, &bin_dir, pkg, target, profile)?;
     }
-    Ok(())
+    Ok(built)
 }
 
-fn has_cdylib(dist_dir: &Path, target: &str, profile: BuildProfile, pkg: &str) -> bool {
+fn cdylib_filename(pkg: &str, target: &str) -> String {
     let lib_basename = pkg.replace('-', "_");
-    let lib_file = if target.contains("windows") {
+    if target.contains("windows") {
         format!("{lib_basename}.dll")
     } else if target.contains("apple-darwin") {
         format!("lib{lib_basename}.dylib")
+    } else if wasm::is_wasm_target(target) {
+        format!("{lib_basename}.wasm")
     } else {
         format!("lib{lib_basename}.so")
-    };
+    }
+}
+
+fn has_cdylib(dist_dir: &Path, target: &str, profile: BuildProfile, pkg: &str) -> bool {
+    let lib_file = cdylib_filename(pkg, target);
 
     dist_dir
         .join(target)
@@ -686,14 +1001,7 @@ fn copy_runtime_dylib_if_present(
     target: &str,
     profile: BuildProfile,
 ) -> anyhow::Result<()> {
-    let lib_basename = pkg.replace('-', "_");
-    let lib_file = if target.contains("windows") {
-        format!("{lib_basename}.dll")
-    } else if target.contains("apple-darwin") {
-        format!("lib{lib_basename}.dylib")
-    } else {
-        format!("lib{lib_basename}.so")
-    };
+    let lib_file = cdylib_filename(pkg, target);
 
     let candidate = dist_dir
         .join(target)
@@ -729,6 +1037,10 @@ fn resolve_packages(args: &BuildArgs) -> anyhow::Result<Vec<&'static str>> {
         }
         BuildMode::AggregateRust => Ok(vec!["forgeffi"]),
         BuildMode::AggregateFfi => Ok(vec!["forgeffi-ffi"]),
+        BuildMode::Node => Ok(vec![node::NODE_PKG]),
+        BuildMode::PythonWheel => Ok(vec![python::PY_PKG]),
+        BuildMode::Jni => Ok(vec![jni::JNI_PKG]),
+        BuildMode::Uniffi => Ok(vec![uniffi::UNIFFI_PKG]),
     }
 }
 
@@ -757,8 +1069,8 @@ fn copy_artifact_to_dist(
     kind: ArtifactKind,
 ) -> anyhow::Result<()> {
     let out_dir = match profile {
-        BuildProfile::Debug => workspace_root.join("target").join(target).join("debug"),
-        BuildProfile::Release => workspace_root.join("target").join(target).join("release"),
+        BuildProfile::Debug => cargo_target_dir(workspace_root).join(target).join("debug"),
+        BuildProfile::Release => cargo_target_dir(workspace_root).join(target).join("release"),
     };
 
     let lib_name = pkg.replace('-', "_");
@@ -854,14 +1166,8 @@ fn generate_c_header_to_dist(
     pkg: &str,
     target: &str,
     profile: BuildProfile,
+    features: &[String],
 ) -> anyhow::Result<()> {
-    ensure_binary("cbindgen", "cbindgen")?;
-
-    let crate_dir = workspace_root.join("crates").join(pkg);
-    if !crate_dir.is_dir() {
-        bail!("未找到 crate 目录: {}", crate_dir.display());
-    }
-
     let include_dir = dist_dir
         .join(target)
         .join(match profile {
@@ -873,16 +1179,15 @@ fn generate_c_header_to_dist(
     fs::create_dir_all(&include_dir).context("创建 include 目录失败")?;
 
     let header_path = include_dir.join(format!("{pkg}.h"));
-
-    let mut cmd = Command::new("cbindgen");
-    cmd.current_dir(workspace_root);
-    cmd.arg("--lang").arg("c");
-    cmd.arg("--crate").arg(pkg);
-    cmd.arg("--output").arg(&header_path);
-    cmd.arg(crate_dir);
-
-    run_checked("cbindgen", &mut cmd)?;
+    headers::generate_c_header(workspace_root, pkg, &header_path)?;
     println!("dist: {}", header_path.display());
+
+    if pkg == "forgeffi-ffi" {
+        let header_path = include_dir.join(headers::AMALGAMATED_HEADER);
+        let modules = manifest::aggregate_modules(features);
+        headers::generate_amalgamated_header(workspace_root, &modules, &header_path)?;
+        println!("dist: {}", header_path.display());
+    }
     Ok(())
 }
 
@@ -901,6 +1206,8 @@ fn find_artifact_path(
                 format!("{lib_basename}.dll")
             } else if is_macos {
                 format!("lib{lib_basename}.dylib")
+            } else if wasm::is_wasm_target(target) {
+                format!("{lib_basename}.wasm")
             } else {
                 format!("lib{lib_basename}.so")
             };
@@ -914,6 +1221,9 @@ fn find_artifact_path(
             };
             out_dir.join(file)
         }
+        ArtifactKind::Aar | ArtifactKind::Xcframework => {
+            bail!("{} 不是 cargo 直接产物，需通过打包步骤生成", kind.as_str())
+        }
     };
 
     if path.exists() {
@@ -996,6 +1306,14 @@ fn ensure_binary(bin: &str, install_crate: &str) -> anyhow::Result<()> {
     }
 }
 
+// 与 cargo 一致：优先 CARGO_TARGET_DIR（相对路径按 workspace 根目录解析）
+fn cargo_target_dir(workspace_root: &Path) -> PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(d) if !d.is_empty() => workspace_root.join(d),
+        _ => workspace_root.join("target"),
+    }
+}
+
 fn workspace_root() -> anyhow::Result<PathBuf> {
     let out = Command::new("cargo")
         .arg("metadata")
@@ -1121,6 +1439,8 @@ fn common_targets() -> Vec<String> {
         "x86_64-linux-android".to_string(),
         "aarch64-apple-ios".to_string(),
         "aarch64-apple-ios-sim".to_string(),
+        "wasm32-unknown-unknown".to_string(),
+        "wasm32-wasip1".to_string(),
     ]
 }
 
@@ -1323,18 +1643,25 @@ fn download_to_file(url: &str, out: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn verify_sha256(path: &Path, expected_hex: &str) -> anyhow::Result<()> {
-    let mut file = fs::File::open(path).context("打开下载文件失败")?;
+fn sha256_file(path: &Path) -> anyhow::Result<String> {
+    let mut file =
+        fs::File::open(path).with_context(|| format!("打开文件失败: {}", path.display()))?;
     let mut hasher = Sha256::new();
     let mut buf = [0u8; 1024 * 64];
     loop {
-        let n = file.read(&mut buf).context("读取下载文件失败")?;
+        let n = file
+            .read(&mut buf)
+            .with_context(|| format!("读取文件失败: {}", path.display()))?;
         if n == 0 {
             break;
         }
         hasher.update(&buf[..n]);
     }
-    let actual = format!("{:x}", hasher.finalize());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn verify_sha256(path: &Path, expected_hex: &str) -> anyhow::Result<()> {
+    let actual = sha256_file(path)?;
     if actual.eq_ignore_ascii_case(expected_hex) {
         Ok(())
     } else {
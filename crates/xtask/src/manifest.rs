@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Context as _};
+use serde::Serialize;
+
+use crate::{diststate, normalize_modules, profile_dir_name, sha256_file, BuildArgs, BuildMode};
+
+pub(crate) const MANIFEST_FILE_NAME: &str = "manifest.json";
+const MANIFEST_SCHEMA: u32 = 1;
+
+#[derive(Serialize)]
+struct DistManifest {
+    schema: u32,
+    target: String,
+    profile: String,
+    mode: String,
+    artifact: String,
+    abi_version: u32,
+    modules: Vec<String>,
+    features: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_dirty: Option<bool>,
+    build_timestamp: String,
+    build_timestamp_unix: u64,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Serialize)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+pub(crate) fn write_dist_manifest(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    target: &str,
+    args: &BuildArgs,
+) -> anyhow::Result<()> {
+    let root = dist_dir.join(target).join(profile_dir_name(args.profile));
+    if !root.is_dir() {
+        return Ok(());
+    }
+
+    let mut paths = Vec::new();
+    collect_files(&root, &mut paths)?;
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for p in paths {
+        let rel = p
+            .strip_prefix(&root)
+            .map_err(|_| anyhow!("路径不在 dist 目录内: {}", p.display()))?;
+        let rel = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        if rel == MANIFEST_FILE_NAME || rel == diststate::STATE_FILE_NAME {
+            continue;
+        }
+        let size = fs::metadata(&p)
+            .with_context(|| format!("读取文件元信息失败: {}", p.display()))?
+            .len();
+        files.push(ManifestFile {
+            path: rel,
+            size,
+            sha256: sha256_file(&p)?,
+        });
+    }
+
+    let modules = match args.mode {
+        BuildMode::ModuleRust | BuildMode::ModuleFfi => normalize_modules(&args.modules)
+            .into_iter()
+            .map(|m| m.name().to_string())
+            .collect(),
+        BuildMode::AggregateRust | BuildMode::AggregateFfi => aggregate_modules(&args.features),
+        BuildMode::Node | BuildMode::PythonWheel | BuildMode::Jni | BuildMode::Uniffi => {
+            vec!["net".to_string()]
+        }
+    };
+
+    let ts = build_timestamp_unix();
+    let (git_commit, git_dirty) = git_info(workspace_root);
+    let manifest = DistManifest {
+        schema: MANIFEST_SCHEMA,
+        target: target.to_string(),
+        profile: profile_dir_name(args.profile).to_string(),
+        mode: args.mode.to_string(),
+        artifact: args.artifact.as_str().to_string(),
+        abi_version: read_abi_version(workspace_root)?,
+        modules,
+        features: args.features.clone(),
+        git_commit,
+        git_dirty,
+        build_timestamp: format_rfc3339_utc(ts),
+        build_timestamp_unix: ts,
+        files,
+    };
+
+    let path = root.join(MANIFEST_FILE_NAME);
+    let text = serde_json::to_string_pretty(&manifest).context("序列化 manifest 失败")?;
+    fs::write(&path, text).with_context(|| format!("写入 manifest 失败: {}", path.display()))?;
+    println!("dist: {}", path.display());
+    Ok(())
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for ent in fs::read_dir(dir).with_context(|| format!("读取目录失败: {}", dir.display()))? {
+        let ent = ent.with_context(|| format!("读取目录项失败: {}", dir.display()))?;
+        let ty = ent.file_type().context("读取文件类型失败")?;
+        if ty.is_dir() {
+            collect_files(&ent.path(), out)?;
+        } else if ty.is_file() {
+            out.push(ent.path());
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn aggregate_modules(features: &[String]) -> Vec<String> {
+    let mut out = Vec::new();
+    for m in ["net", "fs", "sys"] {
+        if features.iter().any(|f| f == m || f == "full") {
+            out.push(m.to_string());
+        }
+    }
+    out
+}
+
+pub(crate) fn read_abi_version(workspace_root: &Path) -> anyhow::Result<u32> {
+    let path = workspace_root
+        .join("crates")
+        .join("forgeffi-base")
+        .join("src")
+        .join("lib.rs");
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("读取文件失败: {}", path.display()))?;
+    for line in text.lines() {
+        if let Some(rest) = line.trim().strip_prefix("pub const ABI_VERSION: u32 =") {
+            let v = rest.trim().trim_end_matches(';').trim();
+            return v
+                .parse()
+                .with_context(|| format!("解析 ABI_VERSION 失败: {v}"));
+        }
+    }
+    Err(anyhow!("未在 {} 中找到 ABI_VERSION", path.display()))
+}
+
+pub(crate) fn read_package_version(workspace_root: &Path, pkg: &str) -> anyhow::Result<String> {
+    let path = workspace_root.join("crates").join(pkg).join("Cargo.toml");
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("读取文件失败: {}", path.display()))?;
+    for line in text.lines() {
+        if let Some(rest) = line.trim().strip_prefix("version =") {
+            return Ok(rest.trim().trim_matches('"').to_string());
+        }
+    }
+    Err(anyhow!("未在 {} 中找到 version", path.display()))
+}
+
+pub(crate) fn git_info(workspace_root: &Path) -> (Option<String>, Option<bool>) {
+    let commit = Command::new("git")
+        .current_dir(workspace_root)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|s| !s.is_empty());
+    let dirty = commit.as_ref().and_then(|_| {
+        Command::new("git")
+            .current_dir(workspace_root)
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| !o.stdout.is_empty())
+    });
+    (commit, dirty)
+}
+
+pub(crate) fn build_timestamp_unix() -> u64 {
+    if let Some(v) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    {
+        return v;
+    }
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub(crate) fn format_rfc3339_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (h, m, s) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let mo = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(mo <= 2);
+
+    format!("{y:04}-{mo:02}-{d:02}T{h:02}:{m:02}:{s:02}Z")
+}
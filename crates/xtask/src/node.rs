@@ -0,0 +1,88 @@
+// Node.js 扩展（forgeffi-node）：把 cargo 产出的动态库改名为 forgeffi.node，
+// 连同 JS 入口、类型声明与 package.json 放到 dist/<target>/<profile>/forgeffi-node/
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context as _};
+
+use crate::{cargo_target_dir, find_artifact_path, manifest, profile_dir_name, ArtifactKind, BuildProfile};
+
+pub(crate) const NODE_PKG: &str = "forgeffi-node";
+const ADDON_FILE_NAME: &str = "forgeffi.node";
+const JS_FILES: [&str; 2] = ["index.js", "index.d.ts"];
+
+pub(crate) fn stage_addon(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    target: &str,
+    profile: BuildProfile,
+) -> anyhow::Result<()> {
+    let out_dir = cargo_target_dir(workspace_root)
+        .join(target)
+        .join(profile_dir_name(profile));
+    let src = find_artifact_path(&out_dir, &NODE_PKG.replace('-', "_"), target, ArtifactKind::Cdylib)
+        .with_context(|| format!("未找到产物: pkg={NODE_PKG}"))?;
+
+    let dst_dir = dist_dir.join(target).join(profile_dir_name(profile)).join(NODE_PKG);
+    fs::create_dir_all(&dst_dir).context("创建 dist 子目录失败")?;
+    let dst = dst_dir.join(ADDON_FILE_NAME);
+    fs::copy(&src, &dst)
+        .with_context(|| format!("复制产物失败: {} -> {}", src.display(), dst.display()))?;
+    println!("dist: {}", dst.display());
+
+    let js_dir = workspace_root.join("crates").join(NODE_PKG).join("js");
+    for name in JS_FILES {
+        let src = js_dir.join(name);
+        let dst = dst_dir.join(name);
+        fs::copy(&src, &dst)
+            .with_context(|| format!("复制文件失败: {} -> {}", src.display(), dst.display()))?;
+        println!("dist: {}", dst.display());
+    }
+
+    let version = manifest::read_package_version(workspace_root, NODE_PKG)?;
+    let path = dst_dir.join("package.json");
+    let text = serde_json::to_string_pretty(&package_json(&version, target)?)
+        .context("序列化 package.json 失败")?;
+    fs::write(&path, text).with_context(|| format!("写入文件失败: {}", path.display()))?;
+    println!("dist: {}", path.display());
+    Ok(())
+}
+
+// os/cpu 让 npm 只在匹配的平台上安装该包
+fn package_json(version: &str, target: &str) -> anyhow::Result<serde_json::Value> {
+    let (os, cpu) = node_platform(target)
+        .ok_or_else(|| anyhow!("Node.js 扩展不支持该 target: {target}"))?;
+    Ok(serde_json::json!({
+        "name": format!("forgeffi-{os}-{cpu}"),
+        "version": version,
+        "main": "index.js",
+        "types": "index.d.ts",
+        "files": ["index.js", "index.d.ts", ADDON_FILE_NAME],
+        "os": [os],
+        "cpu": [cpu],
+        "license": "MIT OR Apache-2.0",
+    }))
+}
+
+// 对应 Node.js 的 process.platform / process.arch
+fn node_platform(target: &str) -> Option<(&'static str, &'static str)> {
+    let os = if target.contains("windows") {
+        "win32"
+    } else if target.contains("apple-darwin") {
+        "darwin"
+    } else if target.contains("-linux-android") {
+        "android"
+    } else if target.contains("-linux-") {
+        "linux"
+    } else {
+        return None;
+    };
+    let cpu = match target.split('-').next()? {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        "i686" => "ia32",
+        "armv7" => "arm",
+        _ => return None,
+    };
+    Some((os, cpu))
+}
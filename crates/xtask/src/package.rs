@@ -0,0 +1,230 @@
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _};
+use clap::{Parser, ValueEnum};
+
+use crate::{
+    diststate, host_target_triple, manifest, profile_dir_name, run_checked, sha256_file, workspace_root,
+    BuildProfile,
+};
+
+// 与 dist 产物一起打进发布包的仓库根目录文件
+const ROOT_FILES: [&str; 4] = ["LICENSE-APACHE", "LICENSE-MIT", "README.md", "README.en.md"];
+
+#[derive(Copy, Clone, Debug, ValueEnum, Eq, PartialEq)]
+enum PackageFormat {
+    Auto,
+    TarGz,
+    Zip,
+}
+
+#[derive(Parser, Clone)]
+pub(crate) struct PackageArgs {
+    #[arg(long, value_delimiter = ',', num_args = 0..)]
+    targets: Vec<String>,
+
+    #[arg(long, default_value = "release")]
+    profile: BuildProfile,
+
+    #[arg(long, default_value = "auto")]
+    format: PackageFormat,
+
+    #[arg(long)]
+    dist_dir: Option<PathBuf>,
+
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+
+    #[arg(long)]
+    minisign_key: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: PackageArgs) -> anyhow::Result<()> {
+    let workspace_root = workspace_root()?;
+    let dist_dir = args
+        .dist_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist"));
+    let out_dir = args
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| dist_dir.join("packages"));
+    let targets = if args.targets.is_empty() {
+        vec![host_target_triple()?]
+    } else {
+        args.targets.clone()
+    };
+    if let Some(key) = &args.minisign_key
+        && !key.is_file()
+    {
+        bail!("未找到 minisign 私钥: {}", key.display());
+    }
+
+    let version = manifest::read_package_version(&workspace_root, "forgeffi-ffi")?;
+    fs::create_dir_all(&out_dir).context("创建打包输出目录失败")?;
+
+    for target in &targets {
+        let root = dist_dir.join(target).join(profile_dir_name(args.profile));
+        // 以 manifest 作为构建完成的标志，避免把半成品打包发布
+        if !root.join(manifest::MANIFEST_FILE_NAME).is_file() {
+            bail!(
+                "未找到构建产物: {}（请先运行 cargo xtask build --target {target} --profile {}）",
+                root.display(),
+                profile_dir_name(args.profile)
+            );
+        }
+
+        let format = match args.format {
+            PackageFormat::Auto if target.contains("windows") => PackageFormat::Zip,
+            PackageFormat::Auto => PackageFormat::TarGz,
+            f => f,
+        };
+        let base_name = format!("forgeffi-{version}-{target}");
+        let entries = collect_entries(&workspace_root, &root, &base_name)?;
+        let archive = match format {
+            PackageFormat::Zip => {
+                let path = out_dir.join(format!("{base_name}.zip"));
+                write_zip(&path, &entries)?;
+                path
+            }
+            PackageFormat::TarGz | PackageFormat::Auto => {
+                let path = out_dir.join(format!("{base_name}.tar.gz"));
+                write_tar_gz(&path, &entries)?;
+                path
+            }
+        };
+        write_checksum(&archive)?;
+        if let Some(key) = &args.minisign_key {
+            sign(&archive, key)?;
+        }
+        println!("package: {}", archive.display());
+    }
+    Ok(())
+}
+
+// (源文件, 包内路径)；包内统一放在 <base_name>/ 目录下
+fn collect_entries(
+    workspace_root: &Path,
+    root: &Path,
+    base_name: &str,
+) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    files.retain(|p| !p.ends_with(diststate::STATE_FILE_NAME));
+    files.sort();
+
+    let mut out = Vec::with_capacity(files.len() + ROOT_FILES.len());
+    for name in ROOT_FILES {
+        let p = workspace_root.join(name);
+        if p.is_file() {
+            out.push((p, format!("{base_name}/{name}")));
+        }
+    }
+    for p in files {
+        let rel = p
+            .strip_prefix(root)
+            .context("路径不在 dist 目录内")?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        out.push((p, format!("{base_name}/{rel}")));
+    }
+    Ok(out)
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for ent in fs::read_dir(dir).with_context(|| format!("读取目录失败: {}", dir.display()))? {
+        let ent = ent.with_context(|| format!("读取目录项失败: {}", dir.display()))?;
+        let ty = ent.file_type().context("读取文件类型失败")?;
+        if ty.is_dir() {
+            collect_files(&ent.path(), out)?;
+        } else if ty.is_file() {
+            out.push(ent.path());
+        }
+    }
+    Ok(())
+}
+
+fn write_tar_gz(path: &Path, entries: &[(PathBuf, String)]) -> anyhow::Result<()> {
+    let file = fs::File::create(path)
+        .with_context(|| format!("创建文件失败: {}", path.display()))?;
+    let enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut ar = tar::Builder::new(enc);
+    // 不写入属主与修改时间，同样的产物得到同样的包
+    ar.mode(tar::HeaderMode::Deterministic);
+    for (src, name) in entries {
+        ar.append_path_with_name(src, name)
+            .with_context(|| format!("写入 tar 条目失败: {}", src.display()))?;
+    }
+    let enc = ar.into_inner().context("写入 tar 失败")?;
+    enc.finish().context("写入 gzip 失败")?;
+    Ok(())
+}
+
+fn write_zip(path: &Path, entries: &[(PathBuf, String)]) -> anyhow::Result<()> {
+    let file = fs::File::create(path)
+        .with_context(|| format!("创建文件失败: {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    for (src, name) in entries {
+        let opts = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(file_mode(src)?);
+        zip.start_file(name.as_str(), opts)
+            .with_context(|| format!("写入 zip 条目失败: {name}"))?;
+        let mut f = fs::File::open(src)
+            .with_context(|| format!("打开文件失败: {}", src.display()))?;
+        std::io::copy(&mut f, &mut zip)
+            .with_context(|| format!("写入 zip 条目失败: {name}"))?;
+    }
+    zip.finish().context("写入 zip 失败")?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> anyhow::Result<u32> {
+    use std::os::unix::fs::PermissionsExt as _;
+    let meta = fs::metadata(path).with_context(|| format!("读取文件元信息失败: {}", path.display()))?;
+    Ok(if meta.permissions().mode() & 0o111 != 0 {
+        0o755
+    } else {
+        0o644
+    })
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> anyhow::Result<u32> {
+    Ok(0o644)
+}
+
+// <archive>.sha256，格式与 sha256sum 输出一致，可直接 sha256sum -c
+fn write_checksum(archive: &Path) -> anyhow::Result<()> {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = archive.with_file_name(format!("{name}.sha256"));
+    let mut f = fs::File::create(&path)
+        .with_context(|| format!("创建文件失败: {}", path.display()))?;
+    writeln!(f, "{}  {name}", sha256_file(archive)?)
+        .with_context(|| format!("写入文件失败: {}", path.display()))?;
+    Ok(())
+}
+
+// 生成 <archive>.minisig；私钥有口令时 minisign 会在终端提示输入
+fn sign(archive: &Path, key: &Path) -> anyhow::Result<()> {
+    let found = Command::new("minisign")
+        .arg("-v")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !found {
+        bail!("未找到 minisign，请先安装: https://jedisct1.github.io/minisign/");
+    }
+    let mut cmd = Command::new("minisign");
+    cmd.arg("-S").arg("-s").arg(key).arg("-m").arg(archive);
+    run_checked("minisign", &mut cmd)
+}
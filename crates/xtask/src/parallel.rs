@@ -0,0 +1,200 @@
+// 多 target 并行构建：每个 target 以子进程方式运行 `xtask build`，
+// 各自使用独立的 CARGO_TARGET_DIR（同一 target 目录下 cargo 会互相等待构建锁），
+// 输出按行加上 [label] 前缀后交错打印，失败在最后统一汇总
+use std::collections::{BTreeSet, VecDeque};
+use std::ffi::OsString;
+use std::io::{BufRead as _, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Context as _};
+use clap::ValueEnum;
+
+use crate::{
+    build, cargo_target_dir, ensure_cargo_subcommand, ensure_rust_target, ensure_zig,
+    host_target_triple, map_windows_msvc_target_for_zigbuild, workspace_root, BuildArgs,
+};
+
+pub(crate) struct BuildJob {
+    pub(crate) label: String,
+    pub(crate) args: BuildArgs,
+}
+
+pub(crate) fn run_jobs(jobs: Vec<BuildJob>, parallel: usize) -> Vec<(String, anyhow::Error)> {
+    if parallel <= 1 || jobs.len() <= 1 {
+        let mut failures = Vec::new();
+        for job in jobs {
+            if let Err(e) = build(job.args) {
+                failures.push((job.label, e));
+            }
+        }
+        return failures;
+    }
+
+    if let Err(e) = prepare_tools(&jobs) {
+        return vec![("prepare".to_string(), e)];
+    }
+    let host = match host_target_triple() {
+        Ok(h) => h,
+        Err(e) => return vec![("prepare".to_string(), e)],
+    };
+    let mut ready = VecDeque::with_capacity(jobs.len());
+    let mut early_failures = Vec::new();
+    for job in jobs {
+        match prepare_target(&job.args, &host) {
+            Ok(()) => ready.push_back(job),
+            Err(e) => early_failures.push((job.label, e)),
+        }
+    }
+
+    let queue = Mutex::new(ready);
+    let failures = Mutex::new(early_failures);
+    std::thread::scope(|s| {
+        for _ in 0..parallel {
+            s.spawn(|| loop {
+                let job = match queue.lock() {
+                    Ok(mut q) => q.pop_front(),
+                    Err(p) => p.into_inner().pop_front(),
+                };
+                let Some(job) = job else { break };
+                println!("[{}] 开始构建", job.label);
+                let res = run_child(&job);
+                match &res {
+                    Ok(()) => println!("[{}] 完成", job.label),
+                    Err(e) => println!("[{}] 失败: {e:#}", job.label),
+                }
+                if let Err(e) = res {
+                    match failures.lock() {
+                        Ok(mut f) => f.push((job.label, e)),
+                        Err(p) => p.into_inner().push((job.label, e)),
+                    }
+                }
+            });
+        }
+    });
+    failures.into_inner().unwrap_or_else(|p| p.into_inner())
+}
+
+// 下载 Zig、安装 cargo-zigbuild 与 rust target 都不适合并发进行，先在父进程里做完
+fn prepare_tools(jobs: &[BuildJob]) -> anyhow::Result<()> {
+    let zig_versions: BTreeSet<&str> = jobs
+        .iter()
+        .filter(|j| j.args.zigbuild)
+        .map(|j| j.args.zig_version.as_str())
+        .collect();
+    if !zig_versions.is_empty() {
+        ensure_cargo_subcommand("zigbuild")?;
+    }
+    for v in zig_versions {
+        ensure_zig(v)?;
+    }
+    Ok(())
+}
+
+fn prepare_target(args: &BuildArgs, host: &str) -> anyhow::Result<()> {
+    let Some(target) = args.target.as_deref() else {
+        return Ok(());
+    };
+    let target = if args.zigbuild && target.contains("windows-msvc") && target != host {
+        map_windows_msvc_target_for_zigbuild(target).unwrap_or(target)
+    } else {
+        target
+    };
+    ensure_rust_target(target)
+}
+
+fn run_child(job: &BuildJob) -> anyhow::Result<()> {
+    let exe = std::env::current_exe().context("定位 xtask 可执行文件失败")?;
+    let workspace_root = workspace_root()?;
+    let key = job.args.target.clone().unwrap_or_else(|| job.label.replace(' ', "-"));
+    let target_dir = cargo_target_dir(&workspace_root).join("xtask-jobs").join(key);
+
+    let mut cmd = Command::new(exe);
+    cmd.current_dir(&workspace_root);
+    cmd.arg("build");
+    cmd.args(to_cli_args(&job.args));
+    cmd.env("CARGO_TARGET_DIR", &target_dir);
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("启动子进程失败: {}", job.label))?;
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("获取子进程 stdout 失败"))?;
+    let stderr = child.stderr.take().ok_or_else(|| anyhow!("获取子进程 stderr 失败"))?;
+    std::thread::scope(|s| {
+        s.spawn(|| forward_lines(stdout, &job.label, false));
+        s.spawn(|| forward_lines(stderr, &job.label, true));
+    });
+
+    let status = child.wait().context("等待子进程失败")?;
+    if status.success() {
+        Ok(())
+    } else {
+        bail!("xtask build 失败: {status}")
+    }
+}
+
+fn forward_lines(r: impl Read, label: &str, to_stderr: bool) {
+    for line in BufReader::new(r).lines() {
+        let Ok(line) = line else { break };
+        if to_stderr {
+            eprintln!("[{label}] {line}");
+        } else {
+            println!("[{label}] {line}");
+        }
+    }
+}
+
+fn to_cli_args(args: &BuildArgs) -> Vec<OsString> {
+    let mut out: Vec<OsString> = Vec::new();
+    let mut push = |k: &str, v: String| {
+        out.push(format!("--{k}={v}").into());
+    };
+    if let Some(t) = &args.target {
+        push("target", t.clone());
+    }
+    push("profile", value_name(args.profile));
+    push("mode", value_name(args.mode));
+    if !args.modules.is_empty() {
+        let m = args.modules.iter().map(|m| value_name(*m)).collect::<Vec<_>>();
+        push("modules", m.join(","));
+    }
+    if !args.features.is_empty() {
+        push("features", args.features.join(","));
+    }
+    push("artifact", value_name(args.artifact));
+    push("zig-version", args.zig_version.clone());
+    push("zigbuild", args.zigbuild.to_string());
+    push("headers", args.headers.to_string());
+    if !args.android_targets.is_empty() {
+        push("android-targets", args.android_targets.join(","));
+    }
+    push("android-api", args.android_api.to_string());
+    push("aar-wrapper", args.aar_wrapper.to_string());
+    if !args.apple_targets.is_empty() {
+        push("apple-targets", args.apple_targets.join(","));
+    }
+    if let Some(d) = &args.dist_dir {
+        out.push("--dist-dir".into());
+        out.push(absolute(d).into_os_string());
+    }
+    out
+}
+
+fn value_name<T: ValueEnum>(v: T) -> String {
+    v.to_possible_value()
+        .map(|p| p.get_name().to_string())
+        .unwrap_or_default()
+}
+
+// 子进程的工作目录是 workspace 根目录，相对 dist_dir 先按当前目录展开
+fn absolute(p: &Path) -> std::path::PathBuf {
+    if p.is_absolute() {
+        p.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(p)
+    }
+}
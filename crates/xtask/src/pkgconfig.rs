@@ -0,0 +1,211 @@
+// 为 C/C++ 使用方生成 forgeffi.pc 与 ForgeFFIConfig.cmake，
+// 内容按 dist/<target>/<profile>/ 下实际存在的产物生成，路径全部相对于配置文件所在目录
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context as _;
+
+use crate::{
+    cdylib_filename, manifest, profile_dir_name, staticlib_filename, ArtifactKind, BuildProfile,
+};
+
+const PC_FILE_NAME: &str = "forgeffi.pc";
+const CMAKE_CONFIG_FILE_NAME: &str = "ForgeFFIConfig.cmake";
+const CMAKE_VERSION_FILE_NAME: &str = "ForgeFFIConfigVersion.cmake";
+
+const FFI_PKGS: [&str; 4] = [
+    "forgeffi-ffi",
+    "forgeffi-net-ffi",
+    "forgeffi-fs-ffi",
+    "forgeffi-sys-ffi",
+];
+
+struct DistLib {
+    pkg: &'static str,
+    has_include: bool,
+    // 动态库文件名与 Windows 导入库文件名
+    cdylib: Option<(String, Option<String>)>,
+    staticlib: Option<String>,
+}
+
+pub(crate) fn write_consumer_configs(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    target: &str,
+    profile: BuildProfile,
+) -> anyhow::Result<()> {
+    let root = dist_dir.join(target).join(profile_dir_name(profile));
+    let libs = scan_dist(&root, target)?;
+    if libs.is_empty() {
+        return Ok(());
+    }
+
+    let version = manifest::read_package_version(workspace_root, "forgeffi-ffi")?;
+    let abi = manifest::read_abi_version(workspace_root)?;
+
+    let files = [
+        (PC_FILE_NAME, pkg_config(&libs, target, &version)),
+        (CMAKE_CONFIG_FILE_NAME, cmake_config(&libs, target, profile, abi)),
+        (CMAKE_VERSION_FILE_NAME, cmake_version(&version)),
+    ];
+    for (name, text) in files {
+        let path = root.join(name);
+        fs::write(&path, text).with_context(|| format!("写入文件失败: {}", path.display()))?;
+        println!("dist: {}", path.display());
+    }
+    Ok(())
+}
+
+fn scan_dist(root: &Path, target: &str) -> anyhow::Result<Vec<DistLib>> {
+    let mut out = Vec::new();
+    for pkg in FFI_PKGS {
+        let dir = root.join(pkg);
+        if !dir.is_dir() {
+            continue;
+        }
+        let lib_name = pkg.replace('-', "_");
+        let cdylib_dir = dir.join(ArtifactKind::Cdylib.as_str());
+        let cdylib_file = cdylib_filename(pkg, target);
+        let cdylib = if cdylib_dir.join(&cdylib_file).is_file() {
+            let implib = [format!("{lib_name}.dll.lib"), format!("lib{lib_name}.dll.a"), format!("{lib_name}.dll.a")]
+                .into_iter()
+                .find(|f| cdylib_dir.join(f).is_file());
+            Some((cdylib_file, implib))
+        } else {
+            None
+        };
+        let static_file = staticlib_filename(pkg, target);
+        let staticlib = dir
+            .join(ArtifactKind::Staticlib.as_str())
+            .join(&static_file)
+            .is_file()
+            .then_some(static_file);
+        if cdylib.is_none() && staticlib.is_none() {
+            continue;
+        }
+        out.push(DistLib {
+            pkg,
+            has_include: dir.join("include").is_dir(),
+            cdylib,
+            staticlib,
+        });
+    }
+    // 聚合库已包含全部模块，与模块库同时链接会出现重复符号
+    if out.iter().any(|l| l.pkg == "forgeffi-ffi") {
+        out.retain(|l| l.pkg == "forgeffi-ffi");
+    }
+    Ok(out)
+}
+
+// 静态库需要额外链接的系统库，对应 rustc --print native-static-libs 的常见输出
+fn native_static_libs(target: &str) -> &'static [&'static str] {
+    if target.contains("windows") {
+        &["kernel32", "advapi32", "ntdll", "userenv", "ws2_32", "bcrypt"]
+    } else if target.contains("apple") {
+        &["System", "c", "m", "iconv"]
+    } else if target.contains("android") {
+        &["dl", "m", "c"]
+    } else {
+        &["pthread", "dl", "m", "c"]
+    }
+}
+
+fn pkg_config(libs: &[DistLib], target: &str, version: &str) -> String {
+    let mut cflags = Vec::new();
+    let mut lib_flags = Vec::new();
+    for l in libs {
+        if l.has_include {
+            cflags.push(format!("-I${{prefix}}/{}/include", l.pkg));
+        }
+        let kind = if l.cdylib.is_some() {
+            ArtifactKind::Cdylib
+        } else {
+            ArtifactKind::Staticlib
+        };
+        lib_flags.push(format!("-L${{prefix}}/{}/{}", l.pkg, kind.as_str()));
+        lib_flags.push(format!("-l{}", l.pkg.replace('-', "_")));
+    }
+    let private = native_static_libs(target)
+        .iter()
+        .map(|l| format!("-l{l}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# 由 cargo xtask 生成，请勿手工修改（target={target}）");
+    out.push_str("prefix=${pcfiledir}\n\n");
+    out.push_str("Name: ForgeFFI\n");
+    out.push_str("Description: ForgeFFI C ABI\n");
+    let _ = writeln!(out, "Version: {version}");
+    let _ = writeln!(out, "Cflags: {}", cflags.join(" "));
+    let _ = writeln!(out, "Libs: {}", lib_flags.join(" "));
+    let _ = writeln!(out, "Libs.private: {private}");
+    out
+}
+
+fn cmake_config(libs: &[DistLib], target: &str, profile: BuildProfile, abi: u32) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# 由 cargo xtask 生成，请勿手工修改（target={target} profile={}）",
+        profile_dir_name(profile)
+    );
+    out.push_str("get_filename_component(_FORGEFFI_ROOT \"${CMAKE_CURRENT_LIST_DIR}\" ABSOLUTE)\n\n");
+    let _ = writeln!(out, "set(ForgeFFI_ABI_VERSION {abi})");
+    let _ = writeln!(out, "set(ForgeFFI_TARGET \"{target}\")\n");
+
+    let system_libs = native_static_libs(target).join(";");
+    let mut default_targets = Vec::new();
+    for l in libs {
+        let name = l.pkg.replace('-', "_");
+        let include = if l.has_include {
+            format!("\n    INTERFACE_INCLUDE_DIRECTORIES \"${{_FORGEFFI_ROOT}}/{}/include\"", l.pkg)
+        } else {
+            String::new()
+        };
+
+        if let Some((file, implib)) = &l.cdylib {
+            let tgt = format!("ForgeFFI::{name}");
+            let implib = implib
+                .as_ref()
+                .map(|f| format!("\n    IMPORTED_IMPLIB \"${{_FORGEFFI_ROOT}}/{}/cdylib/{f}\"", l.pkg))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "if(NOT TARGET {tgt})\n  add_library({tgt} SHARED IMPORTED)\n  set_target_properties({tgt} PROPERTIES\n    IMPORTED_LOCATION \"${{_FORGEFFI_ROOT}}/{}/cdylib/{file}\"{implib}{include})\nendif()\n",
+                l.pkg
+            );
+            default_targets.push(tgt);
+        }
+        if let Some(file) = &l.staticlib {
+            let tgt = format!("ForgeFFI::{name}_static");
+            let _ = writeln!(
+                out,
+                "if(NOT TARGET {tgt})\n  add_library({tgt} STATIC IMPORTED)\n  set_target_properties({tgt} PROPERTIES\n    IMPORTED_LOCATION \"${{_FORGEFFI_ROOT}}/{}/staticlib/{file}\"\n    INTERFACE_LINK_LIBRARIES \"{system_libs}\"{include})\nendif()\n",
+                l.pkg
+            );
+            if l.cdylib.is_none() {
+                default_targets.push(tgt);
+            }
+        }
+    }
+
+    // ForgeFFI::ForgeFFI 优先指向动态库，只有静态库时指向静态库
+    let _ = writeln!(
+        out,
+        "if(NOT TARGET ForgeFFI::ForgeFFI)\n  add_library(ForgeFFI::ForgeFFI INTERFACE IMPORTED)\n  set_target_properties(ForgeFFI::ForgeFFI PROPERTIES\n    INTERFACE_LINK_LIBRARIES \"{}\")\nendif()\n",
+        default_targets.join(";")
+    );
+    out.push_str("unset(_FORGEFFI_ROOT)\nset(ForgeFFI_FOUND TRUE)\n");
+    out
+}
+
+fn cmake_version(version: &str) -> String {
+    format!(
+        "set(PACKAGE_VERSION \"{version}\")\n\
+         if(PACKAGE_FIND_VERSION VERSION_GREATER PACKAGE_VERSION)\n  set(PACKAGE_VERSION_COMPATIBLE FALSE)\n\
+         else()\n  set(PACKAGE_VERSION_COMPATIBLE TRUE)\n  if(PACKAGE_FIND_VERSION VERSION_EQUAL PACKAGE_VERSION)\n    set(PACKAGE_VERSION_EXACT TRUE)\n  endif()\n\
+         endif()\n"
+    )
+}
@@ -0,0 +1,78 @@
+// Python wheel（forgeffi-py，PyO3 + maturin）：wheel 输出到 dist/<target>/<profile>/forgeffi-py/
+use std::fs;
+use std::process::Command;
+
+use anyhow::{bail, Context as _};
+
+use crate::testrun::prepend_env_path;
+use crate::{
+    android, ensure_binary, ensure_rust_target, ensure_zig, host_target_triple, manifest,
+    profile_dir_name, run_checked, wasm, workspace_root, BuildArgs, BuildProfile,
+};
+
+pub(crate) const PY_PKG: &str = "forgeffi-py";
+
+pub(crate) fn build_wheel(mut args: BuildArgs) -> anyhow::Result<()> {
+    let workspace_root = workspace_root()?;
+    let target = match args.target.clone() {
+        Some(t) => t,
+        None => host_target_triple()?,
+    };
+    if wasm::is_wasm_target(&target) || target.contains("-apple-ios") {
+        bail!("python-wheel 模式不支持该 target: {target}");
+    }
+    // maturin --zig 不支持 MSVC，交给本机 MSVC 工具链
+    if args.zigbuild && target.contains("windows-msvc") {
+        println!("提示: MSVC target 使用普通 maturin build（关闭 zig）：{target}");
+        args.zigbuild = false;
+    }
+
+    let dist_dir = args
+        .dist_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist"));
+    let out_dir = dist_dir
+        .join(&target)
+        .join(profile_dir_name(args.profile))
+        .join(PY_PKG);
+    // 版本号变化后旧 wheel 会与新 wheel 并存，先清空
+    if out_dir.is_dir() {
+        fs::remove_dir_all(&out_dir).context("清理 wheel 输出目录失败")?;
+    }
+    fs::create_dir_all(&out_dir).context("创建 wheel 输出目录失败")?;
+
+    ensure_binary("maturin", "maturin")?;
+    ensure_rust_target(&target)?;
+
+    let mut cmd = Command::new("maturin");
+    cmd.current_dir(&workspace_root);
+    cmd.arg("build")
+        .arg("--manifest-path")
+        .arg(workspace_root.join("crates").join(PY_PKG).join("Cargo.toml"))
+        .arg("--target")
+        .arg(&target)
+        .arg("--out")
+        .arg(&out_dir);
+    if args.profile == BuildProfile::Release {
+        cmd.arg("--release");
+    }
+    if args.zigbuild {
+        let zig = ensure_zig(&args.zig_version)?;
+        if let Some(dir) = zig.parent() {
+            cmd.env("PATH", prepend_env_path("PATH", dir));
+        }
+        cmd.arg("--zig");
+    } else if target.contains("-linux-android") {
+        android::configure_linker(&mut cmd, &target, args.android_api)?;
+    }
+    run_checked("maturin build", &mut cmd)?;
+
+    for ent in fs::read_dir(&out_dir).context("读取 wheel 输出目录失败")? {
+        let path = ent.context("读取 wheel 输出目录失败")?.path();
+        if path.extension().is_some_and(|e| e == "whl") {
+            println!("dist: {}", path.display());
+        }
+    }
+
+    manifest::write_dist_manifest(&workspace_root, &dist_dir, &target, &args)
+}
@@ -0,0 +1,134 @@
+// cargo xtask test-ffi：检查 write_out/tool_free 出参约定的内存安全。
+// C 侧用 ASan/LSan 编译 templates/ffi_harness.c 并链接宿主的 forgeffi-ffi cdylib（Rust 侧走系统分配器，
+// 分配与释放同样被拦截）；另跑两个故意误用的模式确认检测器生效。Rust 侧在 Miri 下跑 forgeffi-ffi-core 的 mem 用例
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use anyhow::{anyhow, bail, Context as _};
+use clap::{ArgAction, Parser};
+
+use crate::{cargo_target_dir, cdylib_filename, host_target_triple, run_checked, workspace_root};
+
+const FFI_PKG: &str = "forgeffi-ffi";
+const HARNESS_SRC: &str = "crates/xtask/templates/ffi_harness.c";
+
+#[derive(Parser, Clone)]
+pub(crate) struct TestFfiArgs {
+    // C 编译器，需支持 -fsanitize=address；默认取 CC 环境变量，否则 cc
+    #[arg(long)]
+    cc: Option<String>,
+
+    // Miri 需要 nightly 工具链与 miri 组件
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    miri: bool,
+
+    #[arg(long, default_value = "nightly")]
+    miri_toolchain: String,
+}
+
+pub(crate) fn run(args: TestFfiArgs) -> anyhow::Result<()> {
+    let workspace_root = workspace_root()?;
+    let host = host_target_triple()?;
+    if host.contains("-windows-") {
+        bail!("test-ffi 依赖 ASan/LSan，仅支持 Linux/macOS 主机");
+    }
+    // macOS 的 ASan 不带 LeakSanitizer
+    let detect_leaks = host.contains("-linux-");
+
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(&workspace_root)
+        .args(["build", "-p", FFI_PKG, "--features", "full"]);
+    run_checked(&format!("cargo build -p {FFI_PKG}"), &mut cmd)?;
+    let lib_dir = cargo_target_dir(&workspace_root).join("debug");
+    let lib = lib_dir.join(cdylib_filename(FFI_PKG, &host));
+    if !lib.is_file() {
+        bail!("未找到 cdylib: {}", lib.display());
+    }
+
+    let work_dir = tempfile::tempdir().context("创建临时目录失败")?;
+    let exe = work_dir.path().join("ffi_harness");
+    let cc = args
+        .cc
+        .clone()
+        .or_else(|| std::env::var("CC").ok().filter(|s| !s.is_empty()))
+        .unwrap_or_else(|| "cc".to_string());
+    let mut cmd = Command::new(&cc);
+    cmd.args(["-std=c11", "-O0", "-g", "-fsanitize=address,undefined", "-fno-omit-frame-pointer"])
+        .arg(workspace_root.join(HARNESS_SRC))
+        .arg("-o")
+        .arg(&exe)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lforgeffi_ffi")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()));
+    run_checked(&format!("{cc} (ffi_harness.c)"), &mut cmd)?;
+
+    let out = run_harness(&exe, "ok", detect_leaks)?;
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    if !out.status.success() || stderr.contains("Sanitizer") {
+        bail!(
+            "ffi_harness 失败: {}\n--- stdout ---\n{}\n--- stderr ---\n{stderr}",
+            out.status,
+            String::from_utf8_lossy(&out.stdout)
+        );
+    }
+    println!("test-ffi: ASan{} 通过", if detect_leaks { "/LSan" } else { "" });
+
+    expect_detected(&exe, "double-free", "attempting double-free", detect_leaks)?;
+    if detect_leaks {
+        expect_detected(&exe, "leak", "detected memory leaks", detect_leaks)?;
+    }
+
+    if args.miri {
+        run_miri(&workspace_root, &args.miri_toolchain)?;
+    } else {
+        println!("test-ffi: 跳过 Miri");
+    }
+    println!("test-ffi: 通过");
+    Ok(())
+}
+
+fn run_harness(exe: &Path, mode: &str, detect_leaks: bool) -> anyhow::Result<Output> {
+    let asan_options = format!("detect_leaks={}:halt_on_error=1", u8::from(detect_leaks));
+    Command::new(exe)
+        .arg(mode)
+        .env("ASAN_OPTIONS", asan_options)
+        .env("UBSAN_OPTIONS", "print_stacktrace=1:halt_on_error=1")
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("启动失败: {}", exe.display()))
+}
+
+// 故意误用的模式必须被检测出来，否则说明 sanitizer 没有生效（例如编译器不支持或被 LD_PRELOAD 干扰）
+fn expect_detected(exe: &Path, mode: &str, needle: &str, detect_leaks: bool) -> anyhow::Result<()> {
+    let out = run_harness(exe, mode, detect_leaks)?;
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    if out.status.success() || !stderr.contains(needle) {
+        bail!("ffi_harness {mode} 未被检测到（期望输出包含 {needle:?}）\n--- stderr ---\n{stderr}");
+    }
+    println!("test-ffi: {mode} 已被检测到");
+    Ok(())
+}
+
+fn run_miri(workspace_root: &Path, toolchain: &str) -> anyhow::Result<()> {
+    let available = Command::new("cargo")
+        .arg(format!("+{toolchain}"))
+        .args(["miri", "--version"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if !available {
+        return Err(anyhow!(
+            "未找到 {toolchain} 工具链的 miri（rustup component add --toolchain {toolchain} miri），或用 --miri false 跳过"
+        ));
+    }
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(workspace_root)
+        .arg(format!("+{toolchain}"))
+        .args(["miri", "test", "-p", "forgeffi-ffi-core", "--test", "mem"]);
+    run_checked("cargo miri test (forgeffi-ffi-core mem)", &mut cmd)?;
+    println!("test-ffi: Miri 通过");
+    Ok(())
+}
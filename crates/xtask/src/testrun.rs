@@ -0,0 +1,280 @@
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context as _};
+use clap::{ArgAction, Parser};
+
+use crate::{
+    android, apple, build, host_target_triple, manifest, map_windows_msvc_target_for_zigbuild,
+    profile_dir_name, workspace_root, ArtifactKind, BuildArgs, BuildMode, BuildProfile, Module,
+};
+
+const JSON_OUT_NAME: &str = "netif_list.test.json";
+
+#[derive(Parser, Clone)]
+pub(crate) struct TestArgs {
+    #[arg(long)]
+    target: Option<String>,
+
+    #[arg(long, default_value = "debug")]
+    profile: BuildProfile,
+
+    #[arg(long, default_value = "cdylib")]
+    artifact: ArtifactKind,
+
+    #[arg(long, default_value = "0.12.0")]
+    zig_version: String,
+
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    zigbuild: bool,
+
+    #[arg(long)]
+    runner: Option<String>,
+
+    #[arg(long)]
+    dist_dir: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: TestArgs) -> anyhow::Result<()> {
+    if !matches!(
+        args.artifact,
+        ArtifactKind::Cdylib | ArtifactKind::Staticlib
+    ) {
+        bail!("xtask test 仅支持 cdylib/staticlib");
+    }
+
+    let workspace_root = workspace_root()?;
+    let host = host_target_triple()?;
+    let mut target = args.target.clone().unwrap_or_else(|| host.clone());
+    if args.zigbuild && target.contains("windows-msvc") && target != host {
+        let mapped = map_windows_msvc_target_for_zigbuild(&target)
+            .ok_or_else(|| anyhow!("cargo-zigbuild 不支持该 Windows MSVC target: {target}"))?;
+        println!("提示: 为使用 zigbuild，target 已从 {target} 切换为 {mapped}");
+        target = mapped.to_string();
+    }
+
+    let runner = match &args.runner {
+        Some(s) => s.split_whitespace().map(str::to_string).collect(),
+        None => default_runner(&host, &target)?,
+    };
+    if let Some(bin) = runner.first() {
+        ensure_runner_available(bin)?;
+    }
+
+    let dist_dir = args
+        .dist_dir
+        .clone()
+        .unwrap_or_else(|| workspace_root.join("dist"));
+
+    build(BuildArgs {
+        target: Some(target.clone()),
+        profile: args.profile,
+        mode: BuildMode::ModuleFfi,
+        modules: vec![Module::Net],
+        features: Vec::new(),
+        artifact: args.artifact,
+        zig_version: args.zig_version.clone(),
+        zigbuild: args.zigbuild,
+        headers: true,
+        dist_dir: Some(dist_dir.clone()),
+        android_targets: android::default_android_targets(),
+        android_api: 21,
+        aar_wrapper: false,
+        apple_targets: apple::default_apple_targets(),
+        config: None,
+        jobs: 1,
+    })?;
+
+    let bin_dir = dist_dir
+        .join(&target)
+        .join(profile_dir_name(args.profile))
+        .join("examples");
+    let exe_name = if target.contains("windows") {
+        "netif_list.exe"
+    } else {
+        "netif_list"
+    };
+    let exe_path = bin_dir.join(exe_name);
+    if !exe_path.is_file() {
+        bail!("未找到 C 示例可执行文件: {}", exe_path.display());
+    }
+
+    let abi = manifest::read_abi_version(&workspace_root)?;
+    run_netif_list(&exe_path, &bin_dir, &runner, abi)?;
+
+    println!("test: {target} 通过");
+    Ok(())
+}
+
+fn default_runner(host: &str, target: &str) -> anyhow::Result<Vec<String>> {
+    if host == target {
+        return Ok(Vec::new());
+    }
+
+    let host_arch = host.split('-').next().unwrap_or_default();
+    let target_arch = target.split('-').next().unwrap_or_default();
+
+    if target.contains("-linux-") && !target.contains("-android") {
+        if !host.contains("-linux-") {
+            bail!("在非 Linux 主机上运行 Linux target 需要通过 --runner 指定运行器: {target}");
+        }
+        if host_arch == target_arch {
+            return Ok(Vec::new());
+        }
+        let qemu_arch = qemu_arch(target_arch)
+            .ok_or_else(|| anyhow!("未知的 qemu-user 架构: {target_arch}（可用 --runner 指定）"))?;
+        return Ok(vec![format!("qemu-{qemu_arch}")]);
+    }
+
+    if target.contains("-windows-") {
+        if host.contains("-windows-") {
+            return Ok(Vec::new());
+        }
+        if target.contains("-windows-gnu") {
+            return Ok(vec!["wine".to_string()]);
+        }
+        bail!("非 Windows 主机仅支持通过 wine 运行 windows-gnu target: {target}");
+    }
+
+    if target.contains("-apple-darwin") && host.contains("-apple-darwin") {
+        return Ok(Vec::new());
+    }
+
+    bail!("没有适用于 {target} 的运行器（可用 --runner 指定）")
+}
+
+fn qemu_arch(rust_arch: &str) -> Option<&'static str> {
+    match rust_arch {
+        "x86_64" => Some("x86_64"),
+        "i586" | "i686" => Some("i386"),
+        "aarch64" => Some("aarch64"),
+        "arm" | "armv7" => Some("arm"),
+        "riscv64gc" => Some("riscv64"),
+        "powerpc64le" => Some("ppc64le"),
+        "s390x" => Some("s390x"),
+        "loongarch64" => Some("loongarch64"),
+        _ => None,
+    }
+}
+
+fn ensure_runner_available(bin: &str) -> anyhow::Result<()> {
+    let ok = Command::new(bin)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok();
+    if !ok {
+        bail!("未找到运行器: {bin}");
+    }
+    Ok(())
+}
+
+fn run_netif_list(
+    exe_path: &Path,
+    bin_dir: &Path,
+    runner: &[String],
+    abi: u32,
+) -> anyhow::Result<()> {
+    let work_dir = tempfile::tempdir().context("创建临时目录失败")?;
+
+    let mut cmd = match runner.split_first() {
+        Some((bin, rest)) => {
+            let mut c = Command::new(bin);
+            c.args(rest);
+            c.arg(exe_path);
+            c
+        }
+        None => Command::new(exe_path),
+    };
+    cmd.current_dir(work_dir.path());
+    cmd.env(
+        "LD_LIBRARY_PATH",
+        prepend_env_path("LD_LIBRARY_PATH", bin_dir),
+    );
+    cmd.env(
+        "DYLD_LIBRARY_PATH",
+        prepend_env_path("DYLD_LIBRARY_PATH", bin_dir),
+    );
+    cmd.env("WINEDEBUG", "-all");
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    println!("运行: {}", exe_path.display());
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("启动失败: {}", exe_path.display()))?;
+    {
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("无法写入 stdin"))?;
+        write!(stdin, "1\n2\n{JSON_OUT_NAME}\n0\n").context("写入 stdin 失败")?;
+    }
+    let output = child.wait_with_output().context("等待进程结束失败")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        bail!(
+            "C 示例退出码异常: {}\n--- stdout ---\n{stdout}\n--- stderr ---\n{stderr}",
+            output.status
+        );
+    }
+    if !stderr.contains("rc=0,") {
+        bail!("tool_netif_list_json 未返回 0\n--- stderr ---\n{stderr}");
+    }
+
+    let json_path = work_dir.path().join(JSON_OUT_NAME);
+    let bytes = fs::read(&json_path).with_context(|| {
+        format!(
+            "未生成 JSON 输出: {}\n--- stderr ---\n{stderr}",
+            json_path.display()
+        )
+    })?;
+    let v: serde_json::Value = serde_json::from_slice(&bytes).context("JSON 输出无法解析")?;
+
+    let got_abi = v.get("abi").and_then(serde_json::Value::as_u64);
+    if got_abi != Some(u64::from(abi)) {
+        bail!("JSON abi 不匹配: 期望 {abi}, 实际 {got_abi:?}");
+    }
+    let items = v
+        .get("items")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| anyhow!("JSON 缺少 items 数组"))?;
+    for (i, item) in items.iter().enumerate() {
+        if item
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .is_none()
+        {
+            bail!("items[{i}] 缺少 name");
+        }
+    }
+
+    let rows = stdout
+        .lines()
+        .filter(|l| l.starts_with('[') && l.contains("if_index="))
+        .count();
+    if rows != items.len().min(64) {
+        bail!(
+            "C 示例输出行数与 JSON 不一致: rows={rows}, items={}",
+            items.len()
+        );
+    }
+
+    println!("netif_list: {} 个网卡, abi={abi}", items.len());
+    Ok(())
+}
+
+pub(crate) fn prepend_env_path(key: &str, dir: &Path) -> std::ffi::OsString {
+    let mut paths = vec![dir.to_path_buf()];
+    if let Some(old) = std::env::var_os(key) {
+        paths.extend(std::env::split_paths(&old));
+    }
+    std::env::join_paths(paths).unwrap_or_else(|_| dir.as_os_str().to_os_string())
+}
@@ -0,0 +1,66 @@
+// UniFFI 绑定（forgeffi-uniffi）：动态库放到 dist/<target>/<profile>/forgeffi-uniffi/cdylib/，
+// 再用本机编译的 uniffi-bindgen 从库内元数据生成 Kotlin/Swift/Python 绑定到 bindings/<lang>/
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Context as _};
+
+use crate::{cargo_target_dir, find_artifact_path, profile_dir_name, run_checked, ArtifactKind, BuildProfile};
+
+pub(crate) const UNIFFI_PKG: &str = "forgeffi-uniffi";
+const LANGUAGES: [&str; 3] = ["kotlin", "swift", "python"];
+
+pub(crate) fn stage(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    target: &str,
+    profile: BuildProfile,
+) -> anyhow::Result<()> {
+    let out_dir = cargo_target_dir(workspace_root)
+        .join(target)
+        .join(profile_dir_name(profile));
+    let src = find_artifact_path(&out_dir, &UNIFFI_PKG.replace('-', "_"), target, ArtifactKind::Cdylib)
+        .with_context(|| format!("未找到产物: pkg={UNIFFI_PKG}"))?;
+
+    let dst_dir = dist_dir.join(target).join(profile_dir_name(profile)).join(UNIFFI_PKG);
+    let lib_dir = dst_dir.join(ArtifactKind::Cdylib.as_str());
+    fs::create_dir_all(&lib_dir).context("创建 dist 子目录失败")?;
+    let dst = lib_dir.join(src.file_name().ok_or_else(|| anyhow!("产物路径缺少文件名"))?);
+    fs::copy(&src, &dst)
+        .with_context(|| format!("复制产物失败: {} -> {}", src.display(), dst.display()))?;
+    println!("dist: {}", dst.display());
+
+    // 绑定只依赖库中的元数据，与 target 无关；bindgen 本身始终按宿主编译
+    for lang in LANGUAGES {
+        let bindings_dir = dst_dir.join("bindings").join(lang);
+        if bindings_dir.is_dir() {
+            fs::remove_dir_all(&bindings_dir).context("清理绑定输出目录失败")?;
+        }
+        fs::create_dir_all(&bindings_dir).context("创建绑定输出目录失败")?;
+
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(workspace_root)
+            .arg("run")
+            .arg("-q")
+            .arg("-p")
+            .arg(UNIFFI_PKG)
+            .arg("--features")
+            .arg("cli")
+            .arg("--bin")
+            .arg("uniffi-bindgen")
+            .arg("--")
+            .arg("generate")
+            .arg("--library")
+            .arg(&dst)
+            .arg("--language")
+            .arg(lang)
+            // 格式化依赖 ktlint/swift-format/yapf，缺失时只会刷警告
+            .arg("--no-format")
+            .arg("--out-dir")
+            .arg(&bindings_dir);
+        run_checked("uniffi-bindgen", &mut cmd)?;
+        println!("dist: {}", bindings_dir.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+// wasm32 目标：cargo 产出的 .wasm 再按宿主加工，
+// 浏览器（wasm32-unknown-unknown）用 wasm-bindgen 生成 JS 胶水，WASI 宿主用 wasm-tools 封装为组件
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context as _};
+use serde::Deserialize;
+
+use crate::{ensure_binary, profile_dir_name, run_checked, ArtifactKind, BuildProfile};
+
+// wasip1 核心模块转组件需要 preview1 适配器（wasi_snapshot_preview1.reactor.wasm，随 wasmtime 发布）
+const WASI_ADAPTER_ENV: &str = "FORGEFFI_WASI_ADAPTER";
+
+pub(crate) fn is_wasm_target(target: &str) -> bool {
+    target.starts_with("wasm32-")
+}
+
+// 产物写到 <pkg>/wasm/；只有带 wasm 导出面的包（net）需要加工
+pub(crate) fn stage_bindings(
+    workspace_root: &Path,
+    dist_dir: &Path,
+    pkg: &str,
+    target: &str,
+    profile: BuildProfile,
+) -> anyhow::Result<()> {
+    if pkg != "forgeffi-net-ffi" && pkg != "forgeffi-ffi" {
+        return Ok(());
+    }
+    let pkg_dir = dist_dir.join(target).join(profile_dir_name(profile)).join(pkg);
+    let lib_name = pkg.replace('-', "_");
+    let module = pkg_dir
+        .join(ArtifactKind::Cdylib.as_str())
+        .join(format!("{lib_name}.wasm"));
+    if !module.is_file() {
+        return Ok(());
+    }
+
+    let out_dir = pkg_dir.join("wasm");
+    if out_dir.is_dir() {
+        fs::remove_dir_all(&out_dir).context("清理 wasm 输出目录失败")?;
+    }
+    fs::create_dir_all(&out_dir).context("创建 wasm 输出目录失败")?;
+
+    if target.contains("wasi") {
+        componentize(workspace_root, &module, &out_dir, &lib_name)
+    } else {
+        run_wasm_bindgen(workspace_root, &module, &out_dir, &lib_name)
+    }
+}
+
+fn run_wasm_bindgen(
+    workspace_root: &Path,
+    module: &Path,
+    out_dir: &Path,
+    lib_name: &str,
+) -> anyhow::Result<()> {
+    ensure_wasm_bindgen(workspace_root)?;
+    let mut cmd = Command::new("wasm-bindgen");
+    cmd.arg("--target")
+        .arg("web")
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg("--out-name")
+        .arg(lib_name)
+        .arg(module);
+    run_checked("wasm-bindgen", &mut cmd)?;
+    println!("dist: {}", out_dir.join(format!("{lib_name}.js")).display());
+    Ok(())
+}
+
+fn componentize(
+    workspace_root: &Path,
+    module: &Path,
+    out_dir: &Path,
+    lib_name: &str,
+) -> anyhow::Result<()> {
+    let wit_src = workspace_root
+        .join("crates")
+        .join("forgeffi-net-ffi")
+        .join("wit")
+        .join("forgeffi.wit");
+    let wit_dst = out_dir.join("forgeffi.wit");
+    fs::copy(&wit_src, &wit_dst)
+        .with_context(|| format!("复制 WIT 失败: {} -> {}", wit_src.display(), wit_dst.display()))?;
+    println!("dist: {}", wit_dst.display());
+
+    let Some(adapter) = std::env::var_os(WASI_ADAPTER_ENV).map(PathBuf::from) else {
+        // 核心模块已内嵌 WIT 类型信息，之后仍可手工执行 wasm-tools component new
+        println!("提示: 未设置 {WASI_ADAPTER_ENV}，跳过组件封装，仅输出核心模块");
+        return Ok(());
+    };
+    if !adapter.is_file() {
+        bail!("未找到 WASI 适配器: {}", adapter.display());
+    }
+
+    ensure_binary("wasm-tools", "wasm-tools")?;
+    let component = out_dir.join(format!("{lib_name}.component.wasm"));
+    let mut cmd = Command::new("wasm-tools");
+    cmd.arg("component")
+        .arg("new")
+        .arg(module)
+        .arg("--adapt")
+        .arg(format!("wasi_snapshot_preview1={}", adapter.display()))
+        .arg("-o")
+        .arg(&component);
+    run_checked("wasm-tools component new", &mut cmd)?;
+    println!("dist: {}", component.display());
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+}
+
+// wasm-bindgen CLI 必须与库的版本完全一致，否则生成的胶水代码无法加载
+fn ensure_wasm_bindgen(workspace_root: &Path) -> anyhow::Result<()> {
+    let lock_path = workspace_root.join("Cargo.lock");
+    let text = fs::read_to_string(&lock_path)
+        .with_context(|| format!("读取文件失败: {}", lock_path.display()))?;
+    let lock: CargoLock = toml::from_str(&text).context("解析 Cargo.lock 失败")?;
+    let version = lock
+        .package
+        .into_iter()
+        .find(|p| p.name == "wasm-bindgen")
+        .map(|p| p.version)
+        .ok_or_else(|| anyhow!("Cargo.lock 中未找到 wasm-bindgen"))?;
+
+    let installed = Command::new("wasm-bindgen")
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    if installed.split_whitespace().any(|w| w == version) {
+        return Ok(());
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.arg("install")
+        .arg("wasm-bindgen-cli")
+        .arg("--version")
+        .arg(format!("={version}"))
+        .arg("--locked");
+    run_checked("cargo install wasm-bindgen-cli", &mut cmd)
+}
@@ -0,0 +1,48 @@
+package com.forgeffi
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.NativeLong
+import com.sun.jna.Pointer
+import com.sun.jna.ptr.NativeLongByReference
+import com.sun.jna.ptr.PointerByReference
+
+interface ForgeFfiNative : Library {
+    fun tool_netif_list_json(outPtr: PointerByReference, outLen: NativeLongByReference): Int
+    fun tool_netif_apply_json(
+        reqPtr: ByteArray,
+        reqLen: NativeLong,
+        outPtr: PointerByReference,
+        outLen: NativeLongByReference,
+    ): Int
+    fun tool_free(ptr: Pointer, len: NativeLong)
+}
+
+class ForgeFfiException(val code: Int, val json: String) : RuntimeException("forgeffi error code=$code: $json")
+
+object ForgeFfi {
+    private val lib: ForgeFfiNative = Native.load("{{LIB_NAME}}", ForgeFfiNative::class.java)
+
+    fun listJson(): String = call { p, l -> lib.tool_netif_list_json(p, l) }
+
+    fun applyJson(request: String): String {
+        val bytes = request.toByteArray(Charsets.UTF_8)
+        return call { p, l -> lib.tool_netif_apply_json(bytes, NativeLong(bytes.size.toLong()), p, l) }
+    }
+
+    private inline fun call(f: (PointerByReference, NativeLongByReference) -> Int): String {
+        val outPtr = PointerByReference()
+        val outLen = NativeLongByReference()
+        val rc = f(outPtr, outLen)
+        val ptr = outPtr.value
+        val len = outLen.value
+        val text = if (ptr == null) "" else String(ptr.getByteArray(0, len.toInt()), Charsets.UTF_8)
+        if (ptr != null) {
+            lib.tool_free(ptr, len)
+        }
+        if (rc != 0) {
+            throw ForgeFfiException(rc, text)
+        }
+        return text
+    }
+}
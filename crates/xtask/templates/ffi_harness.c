@@ -0,0 +1,170 @@
+/*
+ * xtask test-ffi 使用的 C 侧用例：在 ASan/LSan 下反复调用出参为 JSON 缓冲区的导出并用 tool_free 释放，
+ * 进程退出时由 LSan 报告泄漏。直接链接聚合库（forgeffi-ffi，features=full），使用 mock 后端，不需要权限。
+ *
+ * 用法: ffi_harness [ok|double-free|leak]
+ *   ok           正常用例，期望退出码 0 且无泄漏报告
+ *   double-free  故意对同一缓冲区调用两次 tool_free，期望 ASan 报 double-free
+ *   leak         故意不释放一个出参缓冲区，期望 LSan 报泄漏
+ * 后两种用于确认检测器确实生效，由 xtask 检查其失败输出
+ */
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int32_t tool_init(const uint8_t *cfg_ptr, size_t cfg_len);
+int32_t tool_init_json(const uint8_t *cfg_ptr, size_t cfg_len, uint8_t **out_ptr, size_t *out_len);
+int32_t tool_config_json(uint8_t **out_ptr, size_t *out_len);
+int32_t tool_capabilities_json(uint8_t **out_ptr, size_t *out_len);
+int32_t tool_build_info_json(uint8_t **out_ptr, size_t *out_len);
+int32_t tool_metrics_json(uint8_t **out_ptr, size_t *out_len);
+const char *tool_version(void);
+int32_t tool_shutdown(void);
+void tool_free(uint8_t *ptr, size_t len);
+
+int32_t tool_netif_mock_configure_json(const uint8_t *cfg_ptr, size_t cfg_len);
+int32_t tool_netif_list_json(uint8_t **out_ptr, size_t *out_len);
+int32_t tool_netif_list_json_into(uint8_t *buf, size_t buf_len, size_t *written_len);
+int32_t tool_netif_apply_json(const uint8_t *req_ptr, size_t req_len, uint8_t **out_ptr, size_t *out_len);
+int32_t tool_netif_backends_json(uint8_t **out_ptr, size_t *out_len);
+/* ToolNetIface 的布局与这里无关，按不透明指针传回释放函数 */
+int32_t tool_netif_list_structs(void **out_array, size_t *out_count);
+void tool_netif_list_structs_free(void *array, size_t count);
+
+typedef int32_t (*json_out_fn)(uint8_t **out_ptr, size_t *out_len);
+
+static int failures = 0;
+
+#define CHECK(cond, ...)                                   \
+    do {                                                   \
+        if (!(cond)) {                                     \
+            fprintf(stderr, "FAIL %s:%d: ", __FILE__, __LINE__); \
+            fprintf(stderr, __VA_ARGS__);                  \
+            fprintf(stderr, "\n");                         \
+            failures++;                                    \
+        }                                                  \
+    } while (0)
+
+static int looks_like_json(const uint8_t *p, size_t len) {
+    return p != NULL && len >= 2 && p[0] == '{' && p[len - 1] == '}';
+}
+
+static void call_out(const char *name, json_out_fn f, int32_t expect_rc) {
+    uint8_t *p = NULL;
+    size_t len = 0;
+    int32_t rc = f(&p, &len);
+    CHECK(rc == expect_rc, "%s rc=%d, 期望 %d", name, rc, expect_rc);
+    CHECK(looks_like_json(p, len), "%s 出参不是 JSON 对象 (len=%zu)", name, len);
+    tool_free(p, len);
+}
+
+static void call_with_req(
+    const char *name,
+    int32_t (*f)(const uint8_t *, size_t, uint8_t **, size_t *),
+    const char *req,
+    int expect_ok) {
+    uint8_t *p = NULL;
+    size_t len = 0;
+    int32_t rc = f((const uint8_t *)req, req == NULL ? 0 : strlen(req), &p, &len);
+    CHECK((rc == 0) == expect_ok, "%s rc=%d", name, rc);
+    CHECK(looks_like_json(p, len), "%s 出参不是 JSON 对象 (len=%zu)", name, len);
+    tool_free(p, len);
+}
+
+static void exercise_into(void) {
+    size_t need = 0;
+    int32_t rc = tool_netif_list_json_into(NULL, 0, &need);
+    CHECK(rc != 0 && need > 0, "list_json_into 探测长度 rc=%d need=%zu", rc, need);
+    uint8_t *buf = (uint8_t *)malloc(need + 256);
+    size_t written = 0;
+    rc = tool_netif_list_json_into(buf, need + 256, &written);
+    CHECK(rc == 0 && looks_like_json(buf, written), "list_json_into rc=%d written=%zu", rc, written);
+    free(buf);
+}
+
+static void exercise_structs(void) {
+    void *arr = NULL;
+    size_t count = 0;
+    int32_t rc = tool_netif_list_structs(&arr, &count);
+    CHECK(rc == 0 && arr != NULL && count == 2, "list_structs rc=%d count=%zu", rc, count);
+    tool_netif_list_structs_free(arr, count);
+    tool_netif_list_structs_free(NULL, 0);
+}
+
+static int run_ok(void) {
+    const char *init = "{\"abi\":1,\"netif_backend\":\"mock\"}";
+    CHECK(tool_init((const uint8_t *)init, strlen(init)) == 0, "tool_init 失败");
+    CHECK(tool_netif_mock_configure_json(NULL, 0) == 0, "mock configure 失败");
+    CHECK(tool_version() != NULL && strlen(tool_version()) > 0, "tool_version 为空");
+    tool_free(NULL, 0);
+
+    for (int i = 0; i < 200; i++) {
+        call_out("tool_config_json", tool_config_json, 0);
+        call_out("tool_capabilities_json", tool_capabilities_json, 0);
+        call_out("tool_build_info_json", tool_build_info_json, 0);
+        call_out("tool_netif_list_json", tool_netif_list_json, 0);
+        call_out("tool_netif_backends_json", tool_netif_backends_json, 0);
+        call_out("tool_metrics_json", tool_metrics_json, 0);
+        call_with_req("tool_init_json", tool_init_json, init, 1);
+        /* 错误路径同样写出 ErrorResponse，需要释放 */
+        call_with_req("tool_init_json(bad)", tool_init_json, "{\"abi\":1,\"locale\":7}", 0);
+        call_with_req("tool_init_json(not json)", tool_init_json, "not json", 0);
+        call_with_req(
+            "tool_netif_apply_json",
+            tool_netif_apply_json,
+            "{\"abi\":1,\"target\":{\"name\":\"eth0\"},\"ops\":[{\"op\":\"set_mtu\",\"mtu\":1400}]}",
+            1);
+        call_with_req(
+            "tool_netif_apply_json(missing iface)",
+            tool_netif_apply_json,
+            "{\"abi\":1,\"target\":{\"name\":\"nope0\"},\"ops\":[{\"op\":\"set_mtu\",\"mtu\":1400}]}",
+            0);
+        call_with_req("tool_netif_apply_json(empty)", tool_netif_apply_json, NULL, 0);
+        exercise_into();
+        exercise_structs();
+        if (failures > 0) {
+            break;
+        }
+    }
+
+    tool_shutdown();
+    if (failures > 0) {
+        fprintf(stderr, "ffi_harness: %d 项失败\n", failures);
+        return 1;
+    }
+    printf("ffi_harness: ok\n");
+    return 0;
+}
+
+static int run_double_free(void) {
+    uint8_t *p = NULL;
+    size_t len = 0;
+    tool_config_json(&p, &len);
+    tool_free(p, len);
+    tool_free(p, len);
+    return 0;
+}
+
+static int run_leak(void) {
+    uint8_t *p = NULL;
+    size_t len = 0;
+    tool_config_json(&p, &len);
+    p = NULL;
+    return 0;
+}
+
+int main(int argc, char **argv) {
+    const char *mode = argc > 1 ? argv[1] : "ok";
+    if (strcmp(mode, "ok") == 0) {
+        return run_ok();
+    }
+    if (strcmp(mode, "double-free") == 0) {
+        return run_double_free();
+    }
+    if (strcmp(mode, "leak") == 0) {
+        return run_leak();
+    }
+    fprintf(stderr, "未知模式: %s\n", mode);
+    return 2;
+}
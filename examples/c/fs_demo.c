@@ -0,0 +1,278 @@
+#if defined(_WIN32)
+#define _CRT_SECURE_NO_WARNINGS 1
+#endif
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#if defined(_WIN32)
+#define WIN32_LEAN_AND_MEAN
+#include <windows.h>
+#else
+#include <dlfcn.h>
+#endif
+
+// 与 forgeffi-base FsOpenMode 数值一致
+#define FS_MODE_READ 0u
+#define FS_MODE_WRITE 1u
+
+typedef void (*tool_fs_progress_cb)(const uint8_t *json_ptr, size_t json_len, void *user_data);
+typedef int32_t (*tool_fs_open_fn)(const uint8_t *path_ptr, size_t path_len, uint32_t mode, uint64_t *out_handle);
+typedef int32_t (*tool_fs_read_fn)(uint64_t handle, uint8_t *buf, size_t len, size_t *out_read);
+typedef int32_t (*tool_fs_write_fn)(uint64_t handle, const uint8_t *buf, size_t len, size_t *out_written);
+typedef int32_t (*tool_fs_close_fn)(uint64_t handle);
+typedef int32_t (*tool_fs_hash_json_fn)(const uint8_t *req_ptr, size_t req_len, tool_fs_progress_cb cb, void *user_data, uint8_t **out_ptr, size_t *out_len);
+typedef int32_t (*tool_fs_mounts_json_fn)(const uint8_t *req_ptr, size_t req_len, uint8_t **out_ptr, size_t *out_len);
+typedef void (*tool_free_fn)(uint8_t *ptr, size_t len);
+
+#if defined(FORGEFFI_STATIC)
+int32_t tool_fs_open(const uint8_t *path_ptr, size_t path_len, uint32_t mode, uint64_t *out_handle);
+int32_t tool_fs_read(uint64_t handle, uint8_t *buf, size_t len, size_t *out_read);
+int32_t tool_fs_write(uint64_t handle, const uint8_t *buf, size_t len, size_t *out_written);
+int32_t tool_fs_close(uint64_t handle);
+int32_t tool_fs_hash_json(const uint8_t *req_ptr, size_t req_len, tool_fs_progress_cb cb, void *user_data, uint8_t **out_ptr, size_t *out_len);
+int32_t tool_fs_mounts_json(const uint8_t *req_ptr, size_t req_len, uint8_t **out_ptr, size_t *out_len);
+void tool_free(uint8_t *ptr, size_t len);
+#endif
+
+typedef struct {
+    tool_fs_open_fn open;
+    tool_fs_read_fn read;
+    tool_fs_write_fn write;
+    tool_fs_close_fn close;
+    tool_fs_hash_json_fn hash_json;
+    tool_fs_mounts_json_fn mounts_json;
+    tool_free_fn free;
+} fs_api;
+
+static void *load_library(void) {
+#if defined(FORGEFFI_STATIC)
+    return NULL;
+#else
+#if defined(_WIN32)
+    const char *candidates[] = {"forgeffi_fs_ffi.dll", "forgeffi_ffi.dll"};
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        HMODULE h = LoadLibraryA(candidates[i]);
+        if (h != NULL) {
+            return (void *)h;
+        }
+    }
+    return NULL;
+#else
+#if defined(__APPLE__)
+    const char *candidates[] = {"libforgeffi_fs_ffi.dylib", "libforgeffi_ffi.dylib"};
+#else
+    const char *candidates[] = {"libforgeffi_fs_ffi.so", "libforgeffi_ffi.so"};
+#endif
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        void *h = dlopen(candidates[i], RTLD_NOW | RTLD_LOCAL);
+        if (h != NULL) {
+            return h;
+        }
+    }
+    return NULL;
+#endif
+#endif
+}
+
+static void *load_symbol(void *lib, const char *name) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+    (void)name;
+    return NULL;
+#else
+#if defined(_WIN32)
+    return (void *)GetProcAddress((HMODULE)lib, name);
+#else
+    return dlsym(lib, name);
+#endif
+#endif
+}
+
+static void close_library(void *lib) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+#else
+#if defined(_WIN32)
+    if (lib != NULL) {
+        FreeLibrary((HMODULE)lib);
+    }
+#else
+    if (lib != NULL) {
+        dlclose(lib);
+    }
+#endif
+#endif
+}
+
+// 路径写进请求 JSON 前转义（Windows 路径里的反斜杠等）
+static int json_escape(const char *s, char *out, size_t cap) {
+    size_t n = 0;
+    for (; *s != '\0'; s++) {
+        const char *rep = NULL;
+        char tmp[8];
+        switch (*s) {
+            case '"': rep = "\\\""; break;
+            case '\\': rep = "\\\\"; break;
+            case '\n': rep = "\\n"; break;
+            case '\r': rep = "\\r"; break;
+            case '\t': rep = "\\t"; break;
+            default:
+                if ((unsigned char)*s < 0x20) {
+                    snprintf(tmp, sizeof(tmp), "\\u%04x", (unsigned)(unsigned char)*s);
+                    rep = tmp;
+                }
+                break;
+        }
+        size_t add = rep != NULL ? strlen(rep) : 1;
+        if (n + add + 1 > cap) {
+            return 0;
+        }
+        if (rep != NULL) {
+            memcpy(out + n, rep, add);
+        } else {
+            out[n] = *s;
+        }
+        n += add;
+    }
+    out[n] = '\0';
+    return 1;
+}
+
+static void on_progress(const uint8_t *json_ptr, size_t json_len, void *user_data) {
+    (void)user_data;
+    fputs("progress: ", stderr);
+    fwrite(json_ptr, 1, json_len, stderr);
+    fputc('\n', stderr);
+}
+
+static int write_then_read(const fs_api *api, const char *path) {
+    static const char content[] = "hello from forgeffi fs_demo\n";
+    uint64_t h = 0;
+    int32_t rc = api->open((const uint8_t *)path, strlen(path), FS_MODE_WRITE, &h);
+    if (rc != 0) {
+        fprintf(stderr, "tool_fs_open(write) rc=%d\n", (int)rc);
+        return 0;
+    }
+    size_t written = 0;
+    rc = api->write(h, (const uint8_t *)content, sizeof(content) - 1, &written);
+    api->close(h);
+    if (rc != 0 || written != sizeof(content) - 1) {
+        fprintf(stderr, "tool_fs_write rc=%d written=%zu\n", (int)rc, written);
+        return 0;
+    }
+
+    rc = api->open((const uint8_t *)path, strlen(path), FS_MODE_READ, &h);
+    if (rc != 0) {
+        fprintf(stderr, "tool_fs_open(read) rc=%d\n", (int)rc);
+        return 0;
+    }
+    char buf[128];
+    size_t total = 0;
+    for (;;) {
+        size_t got = 0;
+        rc = api->read(h, (uint8_t *)buf + total, sizeof(buf) - 1 - total, &got);
+        // 读到 0 字节表示 EOF
+        if (rc != 0 || got == 0) {
+            break;
+        }
+        total += got;
+    }
+    api->close(h);
+    buf[total] = '\0';
+    if (rc != 0 || strcmp(buf, content) != 0) {
+        fprintf(stderr, "读回内容不一致 rc=%d\n", (int)rc);
+        return 0;
+    }
+    fprintf(stderr, "写入并读回 %zu 字节: %s\n", total, path);
+    return 1;
+}
+
+// 用法: fs_demo [文件路径，默认 forgeffi_fs_demo.txt]
+// 依次演示 open/write/read/close、带进度回调的 hash，以及挂载点列表；stdout 每行一个 JSON
+int main(int argc, char **argv) {
+    fs_api api;
+    memset(&api, 0, sizeof(api));
+    const char *path = argc > 1 ? argv[1] : "forgeffi_fs_demo.txt";
+
+    void *lib = load_library();
+
+#if defined(FORGEFFI_STATIC)
+    api.open = tool_fs_open;
+    api.read = tool_fs_read;
+    api.write = tool_fs_write;
+    api.close = tool_fs_close;
+    api.hash_json = tool_fs_hash_json;
+    api.mounts_json = tool_fs_mounts_json;
+    api.free = tool_free;
+#else
+    if (lib == NULL) {
+        fprintf(stderr, "未能加载 ForgeFFI 动态库，请把 .dll/.so/.dylib 放到当前目录或 PATH/LD_LIBRARY_PATH 可找到的位置\n");
+        return 2;
+    }
+
+    api.open = (tool_fs_open_fn)load_symbol(lib, "tool_fs_open");
+    api.read = (tool_fs_read_fn)load_symbol(lib, "tool_fs_read");
+    api.write = (tool_fs_write_fn)load_symbol(lib, "tool_fs_write");
+    api.close = (tool_fs_close_fn)load_symbol(lib, "tool_fs_close");
+    api.hash_json = (tool_fs_hash_json_fn)load_symbol(lib, "tool_fs_hash_json");
+    api.mounts_json = (tool_fs_mounts_json_fn)load_symbol(lib, "tool_fs_mounts_json");
+    api.free = (tool_free_fn)load_symbol(lib, "tool_free");
+#endif
+
+    if (api.open == NULL || api.read == NULL || api.write == NULL || api.close == NULL || api.hash_json == NULL ||
+        api.mounts_json == NULL || api.free == NULL) {
+        fprintf(stderr, "missing symbols: tool_fs_*/tool_free（动态库未包含 fs 模块？）\n");
+        close_library(lib);
+        return 3;
+    }
+
+    if (!write_then_read(&api, path)) {
+        close_library(lib);
+        return 1;
+    }
+
+    char escaped[1024];
+    char req[1200];
+    if (!json_escape(path, escaped, sizeof(escaped))) {
+        fprintf(stderr, "路径过长\n");
+        close_library(lib);
+        return 1;
+    }
+    snprintf(req, sizeof(req), "{\"abi\":1,\"path\":\"%s\",\"algorithm\":\"sha256\"}", escaped);
+
+    uint8_t *out = NULL;
+    size_t out_len = 0;
+    int32_t rc = api.hash_json((const uint8_t *)req, strlen(req), on_progress, NULL, &out, &out_len);
+    if (out != NULL && out_len != 0) {
+        fwrite(out, 1, out_len, stdout);
+        fputc('\n', stdout);
+        api.free(out, out_len);
+    }
+    if (rc != 0) {
+        fprintf(stderr, "tool_fs_hash_json rc=%d\n", (int)rc);
+        close_library(lib);
+        return 1;
+    }
+
+    // 请求可为空，此时返回全部挂载点
+    out = NULL;
+    out_len = 0;
+    rc = api.mounts_json(NULL, 0, &out, &out_len);
+    if (out != NULL && out_len != 0) {
+        fwrite(out, 1, out_len, stdout);
+        fputc('\n', stdout);
+        api.free(out, out_len);
+    }
+    fflush(stdout);
+    if (rc != 0) {
+        fprintf(stderr, "tool_fs_mounts_json rc=%d\n", (int)rc);
+        close_library(lib);
+        return 1;
+    }
+
+    close_library(lib);
+    return 0;
+}
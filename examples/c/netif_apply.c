@@ -0,0 +1,143 @@
+#if defined(_WIN32)
+#define _CRT_SECURE_NO_WARNINGS 1
+#endif
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#if defined(_WIN32)
+#define WIN32_LEAN_AND_MEAN
+#include <windows.h>
+#else
+#include <dlfcn.h>
+#endif
+
+typedef uint32_t (*tool_netif_abi_version_fn)(void);
+typedef int32_t (*tool_netif_apply_json_fn)(const uint8_t *req_ptr, size_t req_len, uint8_t **out_ptr, size_t *out_len);
+typedef void (*tool_free_fn)(uint8_t *ptr, size_t len);
+
+#if defined(FORGEFFI_STATIC)
+uint32_t tool_netif_abi_version(void);
+int32_t tool_netif_apply_json(const uint8_t *req_ptr, size_t req_len, uint8_t **out_ptr, size_t *out_len);
+void tool_free(uint8_t *ptr, size_t len);
+#endif
+
+static void *load_library(void) {
+#if defined(FORGEFFI_STATIC)
+    return NULL;
+#else
+#if defined(_WIN32)
+    const char *candidates[] = {"forgeffi_net_ffi.dll", "forgeffi_ffi.dll"};
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        HMODULE h = LoadLibraryA(candidates[i]);
+        if (h != NULL) {
+            return (void *)h;
+        }
+    }
+    return NULL;
+#else
+#if defined(__APPLE__)
+    const char *candidates[] = {"libforgeffi_net_ffi.dylib", "libforgeffi_ffi.dylib"};
+#else
+    const char *candidates[] = {"libforgeffi_net_ffi.so", "libforgeffi_ffi.so"};
+#endif
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        void *h = dlopen(candidates[i], RTLD_NOW | RTLD_LOCAL);
+        if (h != NULL) {
+            return h;
+        }
+    }
+    return NULL;
+#endif
+#endif
+}
+
+static void *load_symbol(void *lib, const char *name) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+    (void)name;
+    return NULL;
+#else
+#if defined(_WIN32)
+    return (void *)GetProcAddress((HMODULE)lib, name);
+#else
+    return dlsym(lib, name);
+#endif
+#endif
+}
+
+static void close_library(void *lib) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+#else
+#if defined(_WIN32)
+    if (lib != NULL) {
+        FreeLibrary((HMODULE)lib);
+    }
+#else
+    if (lib != NULL) {
+        dlclose(lib);
+    }
+#endif
+#endif
+}
+
+// 用法: netif_apply [请求 JSON]
+// 不带参数时对 if_index=1（通常是回环网卡）提交空 ops，只验证调用链路，不修改系统配置
+int main(int argc, char **argv) {
+    tool_netif_abi_version_fn abi_version = NULL;
+    tool_netif_apply_json_fn apply_json = NULL;
+    tool_free_fn free_fn = NULL;
+
+    void *lib = load_library();
+
+#if defined(FORGEFFI_STATIC)
+    abi_version = tool_netif_abi_version;
+    apply_json = tool_netif_apply_json;
+    free_fn = tool_free;
+#else
+    if (lib == NULL) {
+        fprintf(stderr, "未能加载 ForgeFFI 动态库，请把 .dll/.so/.dylib 放到当前目录或 PATH/LD_LIBRARY_PATH 可找到的位置\n");
+        return 2;
+    }
+
+    abi_version = (tool_netif_abi_version_fn)load_symbol(lib, "tool_netif_abi_version");
+    apply_json = (tool_netif_apply_json_fn)load_symbol(lib, "tool_netif_apply_json");
+    free_fn = (tool_free_fn)load_symbol(lib, "tool_free");
+#endif
+
+    if (abi_version == NULL || apply_json == NULL || free_fn == NULL) {
+        fprintf(stderr, "missing symbols: tool_netif_abi_version/tool_netif_apply_json/tool_free\n");
+        close_library(lib);
+        return 3;
+    }
+
+    char default_req[128];
+    const char *req = NULL;
+    if (argc > 1) {
+        req = argv[1];
+    } else {
+        snprintf(default_req, sizeof(default_req), "{\"abi\":%u,\"target\":{\"if_index\":1},\"ops\":[]}", (unsigned)abi_version());
+        req = default_req;
+    }
+
+    uint8_t *buf = NULL;
+    size_t len = 0;
+    int32_t rc = apply_json((const uint8_t *)req, strlen(req), &buf, &len);
+    // 失败时输出的是错误 JSON（code/message），同样需要 tool_free
+    if (buf != NULL && len != 0) {
+        fwrite(buf, 1, len, stdout);
+        fputc('\n', stdout);
+        fflush(stdout);
+        free_fn(buf, len);
+    }
+
+    close_library(lib);
+    if (rc != 0) {
+        fprintf(stderr, "tool_netif_apply_json rc=%d\n", (int)rc);
+        return 1;
+    }
+    return 0;
+}
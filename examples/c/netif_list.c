@@ -224,6 +224,21 @@ static const char *error_code_to_zh(const char *code) {
     if (strcmp(code, "SystemError") == 0) {
         return "系统错误";
     }
+    if (strcmp(code, "TargetGone") == 0) {
+        return "网卡已消失";
+    }
+    if (strcmp(code, "Timeout") == 0) {
+        return "执行超时";
+    }
+    if (strcmp(code, "Busy") == 0) {
+        return "设备忙";
+    }
+    if (strcmp(code, "PartialFailure") == 0) {
+        return "部分步骤已生效";
+    }
+    if (strcmp(code, "NotImplemented") == 0) {
+        return "尚未实现";
+    }
     return "未知错误";
 }
 
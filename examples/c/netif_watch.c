@@ -0,0 +1,159 @@
+#if defined(_WIN32)
+#define _CRT_SECURE_NO_WARNINGS 1
+#else
+#define _POSIX_C_SOURCE 200809L
+#endif
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#if defined(_WIN32)
+#define WIN32_LEAN_AND_MEAN
+#include <windows.h>
+#else
+#include <dlfcn.h>
+#include <time.h>
+#endif
+
+/* 与头文件中的 tool_event_cb 一致，watch/agent 等事件订阅共用；线程约定见头文件注释 */
+typedef void (*tool_event_cb)(const uint8_t *json_ptr, size_t json_len, void *user_data);
+typedef int32_t (*tool_netif_watch_start_fn)(tool_event_cb cb, void *user_data, uint64_t *out_handle);
+typedef int32_t (*tool_netif_watch_stop_fn)(uint64_t handle);
+
+#if defined(FORGEFFI_STATIC)
+int32_t tool_netif_watch_start(tool_event_cb cb, void *user_data, uint64_t *out_handle);
+int32_t tool_netif_watch_stop(uint64_t handle);
+#endif
+
+static void *load_library(void) {
+#if defined(FORGEFFI_STATIC)
+    return NULL;
+#else
+#if defined(_WIN32)
+    const char *candidates[] = {"forgeffi_net_ffi.dll", "forgeffi_ffi.dll"};
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        HMODULE h = LoadLibraryA(candidates[i]);
+        if (h != NULL) {
+            return (void *)h;
+        }
+    }
+    return NULL;
+#else
+#if defined(__APPLE__)
+    const char *candidates[] = {"libforgeffi_net_ffi.dylib", "libforgeffi_ffi.dylib"};
+#else
+    const char *candidates[] = {"libforgeffi_net_ffi.so", "libforgeffi_ffi.so"};
+#endif
+    for (size_t i = 0; i < (sizeof(candidates) / sizeof(candidates[0])); i++) {
+        void *h = dlopen(candidates[i], RTLD_NOW | RTLD_LOCAL);
+        if (h != NULL) {
+            return h;
+        }
+    }
+    return NULL;
+#endif
+#endif
+}
+
+static void *load_symbol(void *lib, const char *name) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+    (void)name;
+    return NULL;
+#else
+#if defined(_WIN32)
+    return (void *)GetProcAddress((HMODULE)lib, name);
+#else
+    return dlsym(lib, name);
+#endif
+#endif
+}
+
+static void close_library(void *lib) {
+#if defined(FORGEFFI_STATIC)
+    (void)lib;
+#else
+#if defined(_WIN32)
+    if (lib != NULL) {
+        FreeLibrary((HMODULE)lib);
+    }
+#else
+    if (lib != NULL) {
+        dlclose(lib);
+    }
+#endif
+#endif
+}
+
+static void sleep_ms(unsigned ms) {
+#if defined(_WIN32)
+    Sleep(ms);
+#else
+    struct timespec ts;
+    ts.tv_sec = (time_t)(ms / 1000);
+    ts.tv_nsec = (long)(ms % 1000) * 1000000L;
+    nanosleep(&ts, NULL);
+#endif
+}
+
+// 回调在库内部的监听线程上触发，json_ptr 只在回调期间有效，需要保留时自行复制
+static void on_event(const uint8_t *json_ptr, size_t json_len, void *user_data) {
+    unsigned long *count = (unsigned long *)user_data;
+    *count += 1;
+    fwrite(json_ptr, 1, json_len, stdout);
+    fputc('\n', stdout);
+    fflush(stdout);
+}
+
+// 用法: netif_watch [秒数，默认 10]
+int main(int argc, char **argv) {
+    tool_netif_watch_start_fn watch_start = NULL;
+    tool_netif_watch_stop_fn watch_stop = NULL;
+
+    unsigned seconds = 10;
+    if (argc > 1) {
+        seconds = (unsigned)strtoul(argv[1], NULL, 10);
+    }
+
+    void *lib = load_library();
+
+#if defined(FORGEFFI_STATIC)
+    watch_start = tool_netif_watch_start;
+    watch_stop = tool_netif_watch_stop;
+#else
+    if (lib == NULL) {
+        fprintf(stderr, "未能加载 ForgeFFI 动态库，请把 .dll/.so/.dylib 放到当前目录或 PATH/LD_LIBRARY_PATH 可找到的位置\n");
+        return 2;
+    }
+
+    watch_start = (tool_netif_watch_start_fn)load_symbol(lib, "tool_netif_watch_start");
+    watch_stop = (tool_netif_watch_stop_fn)load_symbol(lib, "tool_netif_watch_stop");
+#endif
+
+    if (watch_start == NULL || watch_stop == NULL) {
+        fprintf(stderr, "missing symbols: tool_netif_watch_start/tool_netif_watch_stop\n");
+        close_library(lib);
+        return 3;
+    }
+
+    unsigned long count = 0;
+    uint64_t handle = 0;
+    int32_t rc = watch_start(on_event, &count, &handle);
+    if (rc != 0) {
+        // 不支持监听的平台返回 Unsupported，不视为示例失败
+        fprintf(stderr, "tool_netif_watch_start rc=%d（当前平台可能不支持）\n", (int)rc);
+        close_library(lib);
+        return 0;
+    }
+
+    fprintf(stderr, "监听网卡变更 %u 秒...\n", seconds);
+    sleep_ms(seconds * 1000u);
+    // stop 返回后不会再有回调，此时才能释放 user_data 和卸载库
+    watch_stop(handle);
+    fprintf(stderr, "收到事件: %lu\n", count);
+
+    close_library(lib);
+    return 0;
+}
@@ -0,0 +1,132 @@
+#nullable enable
+
+using System;
+using System.Runtime.CompilerServices;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading;
+
+namespace ForgeFFI;
+
+/// <summary>
+/// tool_event_cb 回调的封送辅助：把托管委托包装成可传给 tool_netif_watch_start / tool_agent_start 的
+/// 函数指针与 user_data，Dispose 时调用对应的 stop 并释放 GCHandle。
+///
+/// 线程约定（与生成头文件中 tool_event_cb 的注释一致）：
+/// - handler 在库内部的订阅线程上调用，同一订阅串行、按事件顺序；需要更新 UI 时自行切回 UI 线程；
+/// - Dispose（stop）返回后不会再有回调；允许在 handler 内部 Dispose；
+/// - handler 抛出的异常不会穿过 C 边界，转交 onError（未设置时丢弃）。
+///
+/// 注意：订阅全部 Dispose 之前不能卸载动态库（不要先 Dispose ForgeFfiNetIf）。
+/// </summary>
+public sealed class ForgeFfiEventSubscription : IDisposable
+{
+    /// <summary>
+    /// 调用具体的 *_start 导出：把 cb 与 userData 原样传入，成功时写出句柄。
+    /// </summary>
+    public delegate int StartFn(IntPtr cb, IntPtr userData, out ulong handle);
+
+    private readonly Action<string> _handler;
+    private readonly Action<Exception>? _onError;
+    private readonly Func<ulong, int> _stop;
+    private GCHandle _self;
+    private ulong _handle;
+    private int _disposed;
+
+    private ForgeFfiEventSubscription(Action<string> handler, Action<Exception>? onError, Func<ulong, int> stop)
+    {
+        _handler = handler;
+        _onError = onError;
+        _stop = stop;
+    }
+
+    /// <summary>
+    /// 库侧的订阅句柄（不透明，stop 之后失效）。
+    /// </summary>
+    public ulong Handle => _handle;
+
+    /// <summary>
+    /// 静态的非托管回调入口，签名即 tool_event_cb；不依赖委托实例，无需担心被 GC 回收。
+    /// </summary>
+    public static unsafe IntPtr CallbackPointer =>
+        (IntPtr)(delegate* unmanaged[Cdecl]<byte*, nuint, IntPtr, void>)&OnEvent;
+
+    /// <summary>
+    /// 启动订阅。返回 start 的错误码；非 0 时 subscription 为 null，GCHandle 已释放。
+    /// </summary>
+    public static int Start(
+        StartFn start,
+        Func<ulong, int> stop,
+        Action<string> handler,
+        Action<Exception>? onError,
+        out ForgeFfiEventSubscription? subscription)
+    {
+        if (start is null)
+        {
+            throw new ArgumentNullException(nameof(start));
+        }
+        if (stop is null)
+        {
+            throw new ArgumentNullException(nameof(stop));
+        }
+        if (handler is null)
+        {
+            throw new ArgumentNullException(nameof(handler));
+        }
+
+        var sub = new ForgeFfiEventSubscription(handler, onError, stop);
+        // start 返回前回调就可能触发，GCHandle 必须先就绪
+        sub._self = GCHandle.Alloc(sub);
+        var rc = start(CallbackPointer, GCHandle.ToIntPtr(sub._self), out var handle);
+        if (rc != 0)
+        {
+            sub._self.Free();
+            subscription = null;
+            return rc;
+        }
+        sub._handle = handle;
+        subscription = sub;
+        return 0;
+    }
+
+    public void Dispose()
+    {
+        if (Interlocked.Exchange(ref _disposed, 1) != 0)
+        {
+            return;
+        }
+        if (_handle != 0)
+        {
+            _stop(_handle);
+        }
+        // stop 返回后不再回调；在 handler 内部 Dispose 时 OnEvent 已取得实例引用，不会再读 user_data
+        _self.Free();
+    }
+
+    [UnmanagedCallersOnly(CallConvs = new[] { typeof(CallConvCdecl) })]
+    private static unsafe void OnEvent(byte* jsonPtr, nuint jsonLen, IntPtr userData)
+    {
+        ForgeFfiEventSubscription? sub = null;
+        try
+        {
+            sub = GCHandle.FromIntPtr(userData).Target as ForgeFfiEventSubscription;
+            if (sub is null || Volatile.Read(ref sub._disposed) != 0)
+            {
+                return;
+            }
+            var json = Encoding.UTF8.GetString(jsonPtr, checked((int)jsonLen));
+            sub._handler(json);
+        }
+        catch (Exception ex)
+        {
+            try
+            {
+                sub?._onError?.Invoke(ex);
+            }
+            catch
+            {
+                // onError 自身的异常同样不能穿过 C 边界
+            }
+        }
+    }
+}
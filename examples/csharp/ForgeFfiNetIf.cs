@@ -31,20 +31,40 @@ public sealed class ForgeFfiNetIf : IDisposable
     public const string EnvLibPath = "FORGEFFI_NETIF_LIB";
 
     private const int ErrorCodeInvalidArgument = 1;
+    private const int ErrorCodeUnsupported = 3;
 
     private readonly IntPtr _lib;
     private readonly tool_netif_list_json_fn _list;
     private readonly tool_netif_apply_json_fn _apply;
     private readonly tool_free_fn _free;
+    // 旧版本库没有该导出，为 null 时 Confirm 返回 Unsupported
+    private readonly tool_netif_confirm_fn? _confirm;
+    // 旧版本库没有 tool_netif_qos_json，为 null 时 QosJson 返回 Unsupported
+    private readonly tool_netif_list_json_fn? _qos;
+    // 不支持监听的构建没有这两个导出，为 null 时 Watch 返回 Unsupported
+    private readonly tool_netif_watch_start_fn? _watchStart;
+    private readonly tool_netif_watch_stop_fn? _watchStop;
 
     private bool _disposed;
 
-    private ForgeFfiNetIf(IntPtr lib, tool_netif_list_json_fn list, tool_netif_apply_json_fn apply, tool_free_fn free)
+    private ForgeFfiNetIf(
+        IntPtr lib,
+        tool_netif_list_json_fn list,
+        tool_netif_apply_json_fn apply,
+        tool_free_fn free,
+        tool_netif_confirm_fn? confirm,
+        tool_netif_list_json_fn? qos,
+        tool_netif_watch_start_fn? watchStart,
+        tool_netif_watch_stop_fn? watchStop)
     {
         _lib = lib;
         _list = list;
         _apply = apply;
         _free = free;
+        _confirm = confirm;
+        _qos = qos;
+        _watchStart = watchStart;
+        _watchStop = watchStop;
     }
 
     /// <summary>
@@ -138,8 +158,23 @@ public sealed class ForgeFfiNetIf : IDisposable
             var list = Marshal.GetDelegateForFunctionPointer<tool_netif_list_json_fn>(listPtr);
             var apply = Marshal.GetDelegateForFunctionPointer<tool_netif_apply_json_fn>(applyPtr);
             var free = Marshal.GetDelegateForFunctionPointer<tool_free_fn>(freePtr);
+            var confirm = NativeLibrary.TryGetExport(lib, "tool_netif_confirm", out var confirmPtr)
+                ? Marshal.GetDelegateForFunctionPointer<tool_netif_confirm_fn>(confirmPtr)
+                : null;
+            var qos = NativeLibrary.TryGetExport(lib, "tool_netif_qos_json", out var qosPtr)
+                ? Marshal.GetDelegateForFunctionPointer<tool_netif_list_json_fn>(qosPtr)
+                : null;
+
+            tool_netif_watch_start_fn? watchStart = null;
+            tool_netif_watch_stop_fn? watchStop = null;
+            if (NativeLibrary.TryGetExport(lib, "tool_netif_watch_start", out var watchStartPtr)
+                && NativeLibrary.TryGetExport(lib, "tool_netif_watch_stop", out var watchStopPtr))
+            {
+                watchStart = Marshal.GetDelegateForFunctionPointer<tool_netif_watch_start_fn>(watchStartPtr);
+                watchStop = Marshal.GetDelegateForFunctionPointer<tool_netif_watch_stop_fn>(watchStopPtr);
+            }
 
-            return new ForgeFfiNetIf(lib, list, apply, free);
+            return new ForgeFfiNetIf(lib, list, apply, free, confirm, qos, watchStart, watchStop);
         }
         catch
         {
@@ -161,6 +196,24 @@ public sealed class ForgeFfiNetIf : IDisposable
         return new NetifCallResult(rc, json);
     }
 
+    /// <summary>
+    /// 每块网卡的限速（egress_mbps/ingress_mbps）与当前 qdisc / QoS 策略（返回 JSON 字符串）。
+    /// </summary>
+    public NetifCallResult QosJson()
+    {
+        EnsureNotDisposed();
+        if (_qos is null)
+        {
+            var err = $"{{\"abi\":1,\"ok\":false,\"error\":{{\"code\":\"Unsupported\",\"message\":{JsonString("动态库没有 tool_netif_qos_json 导出")} }} }}";
+            return new NetifCallResult(ErrorCodeUnsupported, err);
+        }
+        IntPtr outPtr = IntPtr.Zero;
+        nuint outLen = 0;
+        var rc = _qos(out outPtr, out outLen);
+        var json = ReadAndFreeUtf8(outPtr, outLen);
+        return new NetifCallResult(rc, json);
+    }
+
     /// <summary>
     /// 发送原始请求 JSON（UTF-8）并返回响应 JSON。
     /// </summary>
@@ -188,10 +241,16 @@ public sealed class ForgeFfiNetIf : IDisposable
 
     /// <summary>
     /// 添加 IP（if_index 定位）。
+    /// 
+    /// waitDadMs：可选；IPv6 地址添加后等待 DAD 完成的最长毫秒数（DAD 失败或超时会返回错误）。
+    /// validLftSecs/preferredLftSecs：可选；地址的有效/首选寿命（秒），不传表示永久地址。
     /// </summary>
-    public NetifCallResult AddIp(uint ifIndex, string ip, byte prefixLen)
+    public NetifCallResult AddIp(uint ifIndex, string ip, byte prefixLen, uint? waitDadMs = null, uint? validLftSecs = null, uint? preferredLftSecs = null)
     {
-        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"add_ip\",\"ip\":{JsonString(ip)},\"prefix_len\":{prefixLen}}}]}}";
+        var dadPart = waitDadMs is uint ms ? $",\"wait_dad_ms\":{ms}" : "";
+        var validPart = validLftSecs is uint v ? $",\"valid_lft_secs\":{v}" : "";
+        var preferredPart = preferredLftSecs is uint p ? $",\"preferred_lft_secs\":{p}" : "";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"add_ip\",\"ip\":{JsonString(ip)},\"prefix_len\":{prefixLen}{dadPart}{validPart}{preferredPart}}}]}}";
         return ApplyJson(req);
     }
 
@@ -227,6 +286,364 @@ public sealed class ForgeFfiNetIf : IDisposable
         return ApplyJson(req);
     }
 
+    /// <summary>
+    /// 以 commit-confirm 方式设置静态 IPv4：confirmTimeoutSecs 秒内未用响应中的 confirm_token 调用 Confirm，
+    /// 库会自动回滚到修改前的地址与网关。远程修改管理网卡时使用，改错失联后无需人工恢复。
+    /// </summary>
+    public NetifCallResult SetIpv4StaticWithConfirm(uint ifIndex, string ip, byte prefixLen, string? gateway, uint confirmTimeoutSecs)
+    {
+        var gwPart = string.IsNullOrWhiteSpace(gateway) ? "" : $",\"gateway\":{JsonString(gateway!)}";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"confirm_timeout_secs\":{confirmTimeoutSecs},\"ops\":[{{\"op\":\"set_ipv4_static\",\"ip\":{JsonString(ip)},\"prefix_len\":{prefixLen}{gwPart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 确认 commit-confirm 变更（token 取自 apply 响应的 confirm_token）。
+    /// 返回 0 表示已确认；token 不存在（已超时回滚）时返回 NotFound 错误码。
+    /// </summary>
+    public int Confirm(string token)
+    {
+        if (token is null)
+        {
+            throw new ArgumentNullException(nameof(token));
+        }
+        EnsureNotDisposed();
+        if (_confirm is null)
+        {
+            return ErrorCodeUnsupported;
+        }
+
+        var bytes = Encoding.UTF8.GetBytes(token);
+        unsafe
+        {
+            fixed (byte* p = bytes)
+            {
+                return _confirm(p, (nuint)bytes.Length);
+            }
+        }
+    }
+
+    /// <summary>
+    /// 监听网卡变更：onEvent 收到每条事件的 JSON，在库内部的监听线程上调用（同一订阅串行）。
+    /// 返回 0 时 subscription 非空，Dispose 即停止监听；须在 Dispose 本对象之前 Dispose 订阅。
+    /// 当前平台或构建不支持监听时返回 Unsupported 错误码。
+    /// </summary>
+    public int Watch(Action<string> onEvent, Action<Exception>? onError, out ForgeFfiEventSubscription? subscription)
+    {
+        EnsureNotDisposed();
+        subscription = null;
+        if (_watchStart is null || _watchStop is null)
+        {
+            return ErrorCodeUnsupported;
+        }
+
+        var start = _watchStart;
+        var stop = _watchStop;
+        return ForgeFfiEventSubscription.Start(
+            (IntPtr cb, IntPtr userData, out ulong handle) => start(cb, userData, out handle),
+            handle => stop(handle),
+            onEvent,
+            onError,
+            out subscription);
+    }
+
+    /// <summary>
+    /// 设置 IPv6 配置方式：mode 取值 "slaac" / "dhcp6" / "static"（static 会把当前全局地址固定为手动配置）。
+    /// </summary>
+    public NetifCallResult SetIpv6Mode(uint ifIndex, string mode)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_ipv6_mode\",\"mode\":{JsonString(mode)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 设置静态 IPv6（关闭 SLAAC/DHCPv6，替换现有全局地址）。gateway：可选。
+    /// </summary>
+    public NetifCallResult SetIpv6Static(uint ifIndex, string ip, byte prefixLen, string? gateway = null)
+    {
+        var gwPart = string.IsNullOrWhiteSpace(gateway) ? "" : $",\"gateway\":{JsonString(gateway!)}";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_ipv6_static\",\"ip\":{JsonString(ip)},\"prefix_len\":{prefixLen}{gwPart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 在父网卡上创建 VLAN 子接口（不需要 target）。
+    /// 
+    /// 参数：
+    /// - parent：父网卡名（Windows 下为 LBFO Team 名）
+    /// - vlanId：1..=4094
+    /// - name：可选；为空则由平台决定（Linux: parent.vlanId，macOS: vlan{vlanId}）
+    /// </summary>
+    public NetifCallResult CreateVlan(string parent, ushort vlanId, string? name = null)
+    {
+        var namePart = string.IsNullOrWhiteSpace(name) ? "" : $",\"name\":{JsonString(name!)}";
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_vlan\",\"parent\":{JsonString(parent)},\"vlan_id\":{vlanId}{namePart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 删除 VLAN 子接口（if_index 定位）。
+    /// </summary>
+    public NetifCallResult DeleteVlan(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"delete_vlan\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 创建 bridge（不需要 target；Windows 下为 Hyper-V Internal vSwitch）。
+    /// </summary>
+    public NetifCallResult CreateBridge(string name)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_bridge\",\"name\":{JsonString(name)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 把网卡加入 bridge（不需要 target）。
+    /// </summary>
+    public NetifCallResult AddBridgeMember(string bridge, string member)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"add_bridge_member\",\"bridge\":{JsonString(bridge)},\"member\":{JsonString(member)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 切换 macOS 网络位置（不需要 target；其他平台返回 Unsupported）。
+    /// </summary>
+    public NetifCallResult SwitchNetworkLocation(string name)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"switch_network_location\",\"name\":{JsonString(name)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 调整 macOS 网络服务顺序（不需要 target；未列出的服务保持原顺序排在后面）。
+    /// </summary>
+    public NetifCallResult SetServiceOrder(params string[] services)
+    {
+        var list = string.Join(",", Array.ConvertAll(services, JsonString));
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"set_service_order\",\"services\":[{list}]}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 删除 bridge（if_index 定位）。
+    /// </summary>
+    public NetifCallResult DeleteBridge(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"delete_bridge\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 把网卡移出其所属 bridge（if_index 定位成员网卡）。
+    /// </summary>
+    public NetifCallResult RemoveBridgeMember(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"remove_bridge_member\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 创建测试用虚拟网卡（不需要 target；Linux dummy，macOS 名字须为 feth&lt;N&gt;，Windows 需要 devcon）。
+    /// </summary>
+    public NetifCallResult CreateDummy(string name)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_dummy\",\"name\":{JsonString(name)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 删除虚拟网卡（if_index 定位；物理网卡会被系统拒绝）。
+    /// </summary>
+    public NetifCallResult DeleteInterface(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"delete_interface\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 创建链路聚合（不需要 target；mode 为 balance_rr/active_backup/balance_xor/broadcast/lacp/balance_tlb/balance_alb，
+    /// Windows 为 NIC Teaming，仅 Windows Server 可用且 members 不能为空；macOS 返回 Unsupported）。
+    /// </summary>
+    public NetifCallResult CreateBond(string name, string mode, params string[] members)
+    {
+        var list = string.Join(",", Array.ConvertAll(members, JsonString));
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_bond\",\"name\":{JsonString(name)},\"mode\":{JsonString(mode)},\"members\":[{list}]}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 把网卡加入 bond（不需要 target；成员会先被 down）。
+    /// </summary>
+    public NetifCallResult AddBondMember(string bond, string member)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"add_bond_member\",\"bond\":{JsonString(bond)},\"member\":{JsonString(member)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 删除 bond（if_index 定位 bond 网卡）。
+    /// </summary>
+    public NetifCallResult DeleteBond(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"delete_bond\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 把网卡移出其所属 bond（if_index 定位成员网卡）。
+    /// </summary>
+    public NetifCallResult RemoveBondMember(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"remove_bond_member\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 创建 WireGuard 网卡（不需要 target；仅 Linux，需要 wireguard 内核模块与 wg 工具）。
+    /// privateKey 为 base64 私钥，只经临时文件传给 wg；peer 用 SetWireguardPeer 逐个添加。
+    /// </summary>
+    public NetifCallResult CreateWireguard(string name, string privateKey, ushort? listenPort = null)
+    {
+        var portPart = listenPort is null ? "" : $",\"listen_port\":{listenPort}";
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_wireguard\",\"name\":{JsonString(name)},\"private_key\":{JsonString(privateKey)}{portPart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 新增或覆盖 WireGuard peer（if_index 定位 wg 网卡；allowedIps 整体替换，endpoint 可选，形如 host:port）。
+    /// </summary>
+    public NetifCallResult SetWireguardPeer(uint ifIndex, string publicKey, string? endpoint, params string[] allowedIps)
+    {
+        var epPart = string.IsNullOrWhiteSpace(endpoint) ? "" : $",\"endpoint\":{JsonString(endpoint!)}";
+        var list = string.Join(",", Array.ConvertAll(allowedIps, JsonString));
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_wireguard_peer\",\"peer\":{{\"public_key\":{JsonString(publicKey)}{epPart},\"allowed_ips\":[{list}]}}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 按公钥移除 WireGuard peer（if_index 定位 wg 网卡）。
+    /// </summary>
+    public NetifCallResult RemoveWireguardPeer(uint ifIndex, string publicKey)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"remove_wireguard_peer\",\"public_key\":{JsonString(publicKey)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 创建 IP 隧道（不需要 target；仅 Linux）。kind 为 gre/ipip/sit，remote/local 为 IPv4，local 可选。
+    /// </summary>
+    public NetifCallResult CreateIpTunnel(string name, string kind, string remote, string? local = null)
+    {
+        var localPart = string.IsNullOrWhiteSpace(local) ? "" : $",\"local\":{JsonString(local!)}";
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"create_ip_tunnel\",\"name\":{JsonString(name)},\"kind\":{JsonString(kind)},\"remote\":{JsonString(remote)}{localPart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 删除隧道网卡（if_index 定位；不是隧道的网卡会被拒绝）。
+    /// </summary>
+    public NetifCallResult DeleteTunnel(uint ifIndex)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"delete_tunnel\"}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 添加或删除经由该网卡的路由（仅 Linux，先检查 capabilities.can_manage_routes）。
+    /// dest 为 ip/prefix_len（主机路由用 /32 或 /128）；table 省略时为 main 表，删除时省略的 gateway/metric 不参与匹配。
+    /// </summary>
+    public NetifCallResult SetRoute(uint ifIndex, bool add, string dest, string? gateway = null, uint? table = null, uint? metric = null)
+    {
+        var gwPart = string.IsNullOrWhiteSpace(gateway) ? "" : $",\"gateway\":{JsonString(gateway!)}";
+        var tablePart = table is null ? "" : $",\"table\":{table}";
+        var metricPart = metric is null ? "" : $",\"metric\":{metric}";
+        var op = add ? "add_route" : "del_route";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"{op}\",\"route\":{{\"dest\":{JsonString(dest)}{gwPart}{tablePart}{metricPart}}}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 添加或删除按源地址选表的策略路由规则（不需要 target；仅 Linux）。
+    /// from 为 ip/prefix_len；priority 省略时由内核分配，删除时需与添加时一致。
+    /// </summary>
+    public NetifCallResult SetSourceRoutingRule(bool add, string from, uint table, uint? priority = null)
+    {
+        var priorityPart = priority is null ? "" : $",\"priority\":{priority}";
+        var op = add ? "add_routing_rule" : "del_routing_rule";
+        var req = $"{{\"abi\":1,\"target\":{{}},\"ops\":[{{\"op\":\"{op}\",\"rule\":{{\"table\":{table},\"from\":{JsonString(from)}{priorityPart}}}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 设置连接专用 DNS 后缀（空字符串表示清除；Linux 走 resolvectl domain，macOS 走搜索域）。
+    /// </summary>
+    public NetifCallResult SetDnsSuffix(uint ifIndex, string suffix)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_dns_suffix\",\"suffix\":{JsonString(suffix)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 设置是否在 DNS 中注册本连接地址（仅 Windows）；useSuffix 控制注册时是否使用连接专用后缀。
+    /// </summary>
+    public NetifCallResult SetDnsRegistration(uint ifIndex, bool register, bool? useSuffix = null)
+    {
+        var suffixPart = useSuffix is bool u ? $",\"use_suffix\":{(u ? "true" : "false")}" : "";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_dns_registration\",\"register\":{(register ? "true" : "false")}{suffixPart}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 重命名网卡（Linux 会临时 down 再恢复；macOS 不支持）。
+    /// </summary>
+    public NetifCallResult SetName(uint ifIndex, string newName)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_name\",\"new_name\":{JsonString(newName)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 设置网卡描述（仅 Linux，写入 ifalias；空字符串表示清除）。
+    /// </summary>
+    public NetifCallResult SetDescription(uint ifIndex, string text)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_description\",\"text\":{JsonString(text)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 覆盖网卡 MAC 地址（必须是单播地址；Windows 会重启网卡，先检查 capabilities.can_set_mac）。
+    /// </summary>
+    public NetifCallResult SetMac(uint ifIndex, string mac)
+    {
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_mac\",\"mac\":{JsonString(mac)}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 固定链路速率与双工（取值参考 capabilities.supported_speeds）；speedMbps 为 null 时恢复自动协商。
+    /// </summary>
+    public NetifCallResult SetLinkSpeed(uint ifIndex, uint? speedMbps, bool fullDuplex = true)
+    {
+        var body = speedMbps is uint s ? $",\"speed_mbps\":{s},\"duplex\":\"{(fullDuplex ? "full" : "half")}\"" : "";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_link_speed\"{body}}}]}}";
+        return ApplyJson(req);
+    }
+
+    /// <summary>
+    /// 限制收发速率（Mbps）；为 null 的方向取消限速。Windows 只支持出方向（egressMbps）。
+    /// </summary>
+    public NetifCallResult SetBandwidthLimit(uint ifIndex, uint? egressMbps, uint? ingressMbps = null)
+    {
+        var egressPart = egressMbps is uint e ? $",\"egress_mbps\":{e}" : "";
+        var ingressPart = ingressMbps is uint i ? $",\"ingress_mbps\":{i}" : "";
+        var req = $"{{\"abi\":1,\"target\":{{\"if_index\":{ifIndex}}},\"ops\":[{{\"op\":\"set_bandwidth_limit\"{egressPart}{ingressPart}}}]}}";
+        return ApplyJson(req);
+    }
+
     /// <summary>
     /// 通过网卡名解析 if_index（内部调用 ListJson 并解析 items）。
     /// 
@@ -322,9 +739,9 @@ public sealed class ForgeFfiNetIf : IDisposable
     /// <summary>
     /// 添加 IP（按网卡名定位）。
     /// </summary>
-    public NetifCallResult AddIpByName(string ifName, string ip, byte prefixLen)
+    public NetifCallResult AddIpByName(string ifName, string ip, byte prefixLen, uint? waitDadMs = null, uint? validLftSecs = null, uint? preferredLftSecs = null)
     {
-        return WithIfIndex(ifName, idx => AddIp(idx, ip, prefixLen));
+        return WithIfIndex(ifName, idx => AddIp(idx, ip, prefixLen, waitDadMs, validLftSecs, preferredLftSecs));
     }
 
     /// <summary>
@@ -351,6 +768,14 @@ public sealed class ForgeFfiNetIf : IDisposable
         return WithIfIndex(ifName, idx => SetIpv4Static(idx, ip, prefixLen, gateway));
     }
 
+    /// <summary>
+    /// 删除 VLAN 子接口（按网卡名定位）。
+    /// </summary>
+    public NetifCallResult DeleteVlanByName(string ifName)
+    {
+        return WithIfIndex(ifName, idx => DeleteVlan(idx));
+    }
+
     public void Dispose()
     {
         if (_disposed)
@@ -506,8 +931,18 @@ public sealed class ForgeFfiNetIf : IDisposable
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     private unsafe delegate int tool_netif_apply_json_fn(byte* reqPtr, nuint reqLen, out IntPtr outPtr, out nuint outLen);
 
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    private unsafe delegate int tool_netif_confirm_fn(byte* tokenPtr, nuint tokenLen);
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     private delegate void tool_free_fn(IntPtr ptr, nuint len);
+
+    // cb 为 tool_event_cb 函数指针，见 ForgeFfiEventSubscription.CallbackPointer
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    private delegate int tool_netif_watch_start_fn(IntPtr cb, IntPtr userData, out ulong handle);
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    private delegate int tool_netif_watch_stop_fn(ulong handle);
 }
 
 /// <summary>
@@ -0,0 +1,93 @@
+#nullable enable
+
+using System;
+using System.Text.Json;
+
+namespace ForgeFFI;
+
+/// <summary>
+/// ForgeFFI NetIF C# 控制台示例：列出网卡并打印 IPv4/IPv6。
+///
+/// 用法：dotnet ForgeFfiExample.dll [--json | --watch [秒数]]
+/// </summary>
+public static class Program
+{
+    public static int Main(string[] args)
+    {
+        ForgeFfiNetIf netif;
+        try
+        {
+            netif = ForgeFfiNetIf.LoadDefault();
+        }
+        catch (Exception e)
+        {
+            Console.Error.WriteLine(e.Message);
+            return 2;
+        }
+
+        using (netif)
+        {
+            if (args.Length > 0 && args[0] == "--watch")
+            {
+                var seconds = args.Length > 1 ? int.Parse(args[1]) : 10;
+                return Watch(netif, seconds);
+            }
+
+            var r = netif.ListJson();
+            if (!r.IsOk)
+            {
+                Console.Error.WriteLine($"tool_netif_list_json rc={r.Code}: {r.Json}");
+                return 1;
+            }
+
+            if (args.Length > 0 && args[0] == "--json")
+            {
+                Console.WriteLine(r.Json);
+                return 0;
+            }
+
+            using var doc = JsonDocument.Parse(r.Json);
+            foreach (var it in doc.RootElement.GetProperty("items").EnumerateArray())
+            {
+                Console.WriteLine($"[{it.GetProperty("if_index").GetUInt32()}] {it.GetProperty("name").GetString()} ({it.GetProperty("kind").GetString()})");
+                Console.WriteLine($"     IPv4={JoinAddrs(it, "ipv4")}");
+                Console.WriteLine($"     IPv6={JoinAddrs(it, "ipv6")}");
+            }
+        }
+        return 0;
+    }
+
+    private static int Watch(ForgeFfiNetIf netif, int seconds)
+    {
+        // 回调在库内部线程上触发，这里只打印，不碰其它状态
+        var rc = netif.Watch(
+            json => Console.WriteLine(json),
+            ex => Console.Error.WriteLine($"事件处理失败: {ex.Message}"),
+            out var sub);
+        if (rc != 0)
+        {
+            Console.Error.WriteLine($"tool_netif_watch_start rc={rc}（当前平台可能不支持）");
+            return 0;
+        }
+        using (sub)
+        {
+            Console.Error.WriteLine($"监听网卡变更 {seconds} 秒...");
+            System.Threading.Thread.Sleep(seconds * 1000);
+        }
+        return 0;
+    }
+
+    private static string JoinAddrs(JsonElement iface, string key)
+    {
+        if (!iface.TryGetProperty(key, out var arr) || arr.GetArrayLength() == 0)
+        {
+            return "-";
+        }
+        var parts = new System.Collections.Generic.List<string>();
+        foreach (var a in arr.EnumerateArray())
+        {
+            parts.Add($"{a.GetProperty("ip").GetString()}/{a.GetProperty("prefix_len").GetInt32()}");
+        }
+        return string.Join(", ", parts);
+    }
+}
@@ -0,0 +1,108 @@
+#!/usr/bin/env python3
+"""ForgeFFI NetIF（网卡管理）ctypes 示例。
+
+用法：
+    python3 netif_list.py [--lib <动态库路径>] [--json]
+
+动态库查找顺序：--lib 参数、环境变量 FORGEFFI_NETIF_LIB、脚本所在目录及其上级目录、系统默认搜索路径。
+"""
+
+import argparse
+import ctypes
+import json
+import os
+import sys
+
+ABI_VERSION = 1
+ENV_LIB_PATH = "FORGEFFI_NETIF_LIB"
+
+
+def candidate_names():
+    if sys.platform == "win32":
+        return ["forgeffi_net_ffi.dll", "forgeffi_ffi.dll"]
+    if sys.platform == "darwin":
+        return ["libforgeffi_net_ffi.dylib", "libforgeffi_ffi.dylib"]
+    return ["libforgeffi_net_ffi.so", "libforgeffi_ffi.so"]
+
+
+def load_library(explicit):
+    paths = []
+    if explicit:
+        paths.append(explicit)
+    env = os.environ.get(ENV_LIB_PATH)
+    if env:
+        paths.append(env)
+    here = os.path.dirname(os.path.abspath(__file__))
+    for d in (here, os.path.dirname(here)):
+        paths.extend(os.path.join(d, n) for n in candidate_names())
+    paths.extend(candidate_names())
+
+    errors = []
+    for p in paths:
+        if os.sep in p and not os.path.isfile(p):
+            continue
+        try:
+            return ctypes.CDLL(p)
+        except OSError as e:
+            errors.append(f"{p}: {e}")
+    raise OSError("未能加载 ForgeFFI 动态库:\n  " + "\n  ".join(errors or paths))
+
+
+def bind(lib):
+    u8p = ctypes.POINTER(ctypes.c_uint8)
+    lib.tool_netif_list_json.argtypes = [ctypes.POINTER(u8p), ctypes.POINTER(ctypes.c_size_t)]
+    lib.tool_netif_list_json.restype = ctypes.c_int32
+    lib.tool_free.argtypes = [u8p, ctypes.c_size_t]
+    lib.tool_free.restype = None
+    return lib
+
+
+def list_json(lib):
+    ptr = ctypes.POINTER(ctypes.c_uint8)()
+    length = ctypes.c_size_t(0)
+    rc = lib.tool_netif_list_json(ctypes.byref(ptr), ctypes.byref(length))
+    try:
+        data = ctypes.string_at(ptr, length.value) if ptr and length.value else b""
+    finally:
+        if ptr:
+            lib.tool_free(ptr, length.value)
+    return rc, data.decode("utf-8")
+
+
+def main():
+    ap = argparse.ArgumentParser(description="ForgeFFI NetIF ctypes 示例")
+    ap.add_argument("--lib", help="动态库路径")
+    ap.add_argument("--json", action="store_true", help="输出原始 JSON")
+    args = ap.parse_args()
+
+    try:
+        lib = bind(load_library(args.lib))
+    except OSError as e:
+        print(e, file=sys.stderr)
+        return 2
+
+    rc, text = list_json(lib)
+    if rc != 0:
+        print(f"tool_netif_list_json rc={rc}: {text}", file=sys.stderr)
+        return 1
+
+    resp = json.loads(text)
+    if resp.get("abi") != ABI_VERSION:
+        print(f"abi 不匹配: {resp.get('abi')}", file=sys.stderr)
+        return 1
+
+    if args.json:
+        print(text)
+        return 0
+
+    for it in resp.get("items", []):
+        v4 = ", ".join(f"{a['ip']}/{a['prefix_len']}" for a in it.get("ipv4", [])) or "-"
+        v6 = ", ".join(f"{a['ip']}/{a['prefix_len']}" for a in it.get("ipv6", [])) or "-"
+        print(f"[{it['if_index']}] {it['name']} ({it['kind']})")
+        print(f"     IPv4={v4}")
+        print(f"     IPv6={v6}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
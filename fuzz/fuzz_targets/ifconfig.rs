@@ -0,0 +1,10 @@
+#![no_main]
+
+use forgeffi_sys::netif::parse::parse_ifconfig;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = parse_ifconfig(s);
+    }
+});
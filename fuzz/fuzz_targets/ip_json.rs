@@ -0,0 +1,10 @@
+#![no_main]
+
+use forgeffi_base::IpFamily;
+use forgeffi_sys::netif::parse::{parse_ip_address_json, parse_ip_route_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_ip_address_json(data);
+    let _ = parse_ip_route_json(data, IpFamily::Ipv6);
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use forgeffi_sys::lldp::parse::{parse_discovery_frame, parse_lldpctl_json, parse_lldptool_text};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_discovery_frame(data);
+    let _ = parse_lldpctl_json(data);
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = parse_lldptool_text(s);
+    }
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+// 宿主传入的请求 JSON：解码（含旧 abi 升级）与校验都只能返回错误，不能 panic
+use forgeffi_base::{NetIfApplyRequest, NetIfListRequest, NetIfMultiApplyRequest};
+use forgeffi_protocol::{decode_request, validate_list_request, validate_multi_apply_request, validate_netif_op};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(req) = decode_request::<NetIfApplyRequest>(s) {
+        for spec in &req.ops {
+            let _ = validate_netif_op(&spec.op);
+        }
+    }
+    if let Ok(req) = decode_request::<NetIfListRequest>(s) {
+        let _ = validate_list_request(&req);
+    }
+    if let Ok(req) = decode_request::<NetIfMultiApplyRequest>(s) {
+        let _ = validate_multi_apply_request(&req);
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use forgeffi_sys::capture::filter::{CaptureFilter, LINKTYPE_ETHERNET, LINKTYPE_NULL, LINKTYPE_RAW};
+use forgeffi_sys::capture::pcap::PcapReader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(mut r) = PcapReader::new(data) {
+        while let Ok(Some(_)) = r.next_packet() {}
+    }
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = CaptureFilter::parse(s);
+    }
+    let f = CaptureFilter::parse("tcp port 80 or (ip6 and not udp) or net 10.0.0.0/8").unwrap();
+    for lt in [LINKTYPE_ETHERNET, LINKTYPE_RAW, LINKTYPE_NULL] {
+        let _ = f.matches(lt, data);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use forgeffi_sys::netif::parse::{parse_netqos_policy_json, parse_powershell_list};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = parse_powershell_list(s);
+        let _ = parse_netqos_policy_json(s);
+    }
+});
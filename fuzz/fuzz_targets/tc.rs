@@ -0,0 +1,12 @@
+#![no_main]
+
+use forgeffi_sys::netif::parse::{parse_tc_class_rate, parse_tc_police_rate, parse_tc_qdisc_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_tc_qdisc_json(data);
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = parse_tc_class_rate(s, "1:10");
+        let _ = parse_tc_police_rate(s);
+    }
+});